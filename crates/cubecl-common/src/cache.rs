@@ -43,6 +43,7 @@ pub struct CacheOption {
     separator: Option<Vec<u8>>,
     version: Option<String>,
     root: Option<String>,
+    directory: Option<PathBuf>,
     lock_max_duration: Option<Duration>,
 }
 
@@ -83,23 +84,33 @@ impl CacheOption {
 
     /// The root directory for the cache.
     ///
-    /// It will appear in the directory "$HOME/.cache/{root}/"
+    /// It will appear in the directory "{directory}/{root}/"
     pub fn root<R: Into<String>>(mut self, root: R) -> Self {
         self.root = Some(root.into());
         self
     }
 
-    fn resolve(self) -> (Vec<u8>, String, String, Duration) {
+    /// The directory containing the cache roots.
+    ///
+    /// Defaults to the `CUBECL_CACHE_DIR` environment variable when it is set, and to
+    /// "$HOME/.cache" otherwise.
+    pub fn directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    fn resolve(self) -> (Vec<u8>, PathBuf, String, String, Duration) {
         let separator = self.separator.unwrap_or_else(|| b"\n".to_vec());
         let version = self
             .version
             .unwrap_or_else(|| std::env!("CARGO_PKG_VERSION").to_string());
         let root = self.root.unwrap_or_else(|| "cubecl".to_string());
+        let directory = self.directory.unwrap_or_else(default_directory);
         let duration = self
             .lock_max_duration
             .unwrap_or_else(|| Duration::from_secs(30));
 
-        (separator, root, version, duration)
+        (separator, directory, root, version, duration)
     }
 }
 
@@ -114,8 +125,8 @@ impl<T: Serialize + DeserializeOwned + PartialEq + Eq + Clone> CacheValue for T
 impl<K: CacheKey, V: CacheValue> Cache<K, V> {
     /// Create a new cache and load the data from the provided path if it exists.
     pub fn new<P: AsRef<Path>>(path: P, option: CacheOption) -> Self {
-        let (separator, root, version, lock_max_duration) = option.resolve();
-        let path = get_persistent_cache_file_path(path, root, version);
+        let (separator, directory, root, version, lock_max_duration) = option.resolve();
+        let path = get_persistent_cache_file_path(path, directory, root, version);
 
         let mut this = Self {
             in_memory_cache: HashMap::new(),
@@ -269,24 +280,32 @@ impl<K: CacheKey, V: CacheValue> Display for Cache<K, V> {
     }
 }
 
+fn default_directory() -> PathBuf {
+    match std::env::var_os("CUBECL_CACHE_DIR") {
+        Some(directory) => PathBuf::from(directory),
+        None => dirs::home_dir()
+            .expect("An home directory should exist")
+            .join(".cache"),
+    }
+}
+
 fn get_persistent_cache_file_path<P: AsRef<Path>>(
     path_partial: P,
+    directory: PathBuf,
     root: String,
     version: String,
 ) -> PathBuf {
     let path_partial: &Path = path_partial.as_ref();
-    let home_dir = dirs::home_dir().expect("An home directory should exist");
     let add_extension = !path_partial.ends_with("json.log");
 
-    let mut path = home_dir
-        .join(".cache")
+    let mut path = directory
         .join(sanitize_path_segment(&root))
         .join(sanitize_path_segment(&version));
 
     for segment in path_partial.iter() {
         // Skip the root directory since it resets the previous path segments.
         //
-        // "/path/file" == "path/file" => "$HOME/.cache/tracel-ai/path/file"
+        // "/path/file" == "path/file" => "{directory}/tracel-ai/path/file"
         if segment == "/" {
             continue;
         }
@@ -353,4 +372,67 @@ mod tests {
         let value2_actual = cache.get(&key2()).unwrap();
         assert_eq!(value2_actual, &value2());
     }
+
+    /// A directory removed when dropped, even when the test fails.
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_cache_directory() {
+        let temp_dir =
+            TempDir(std::env::temp_dir().join(format!("cubecl-test-cache-{}", std::process::id())));
+        let directory = &temp_dir.0;
+        let option = || {
+            CacheOption::default()
+                .directory(directory.clone())
+                .version("test")
+        };
+
+        let mut cache = Cache::<String, String>::new("test", option());
+        cache
+            .insert("key".to_string(), "value".to_string())
+            .unwrap();
+        assert!(directory.join("cubecl/test/test.json.log").exists());
+
+        // A new cache is loaded from the same file.
+        let cache = Cache::<String, String>::new("test", option());
+        assert_eq!(cache.get(&"key".to_string()), Some(&"value".to_string()));
+    }
+
+    #[test]
+    fn test_cache_write_replaces_file() {
+        let temp_dir = TempDir(
+            std::env::temp_dir().join(format!("cubecl-test-cache-write-{}", std::process::id())),
+        );
+        let directory = &temp_dir.0;
+        let option = || {
+            CacheOption::default()
+                .directory(directory.clone())
+                .version("test")
+        };
+
+        let mut cache = Cache::<String, String>::new("test", option());
+        cache
+            .insert("key1".to_string(), "value1".to_string())
+            .unwrap();
+        cache
+            .insert("key2".to_string(), "value2".to_string())
+            .unwrap();
+
+        // Only the cache file is left once the temporary files are renamed over it.
+        let files = std::fs::read_dir(directory.join("cubecl/test"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(files, ["test.json.log"]);
+
+        let cache = Cache::<String, String>::new("test", option());
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"key2".to_string()), Some(&"value2".to_string()));
+    }
 }
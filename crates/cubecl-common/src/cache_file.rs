@@ -63,7 +63,11 @@ impl CacheFile {
         self.lock.unlock();
     }
 
-    /// Write the content to the file.
+    /// Append the content to the file.
+    ///
+    /// The whole file is written to a temporary file that replaces the cache file with a rename,
+    /// so a process killed while writing never leaves a partial entry behind, and readers always
+    /// see either the old or the new content.
     ///
     /// Panics if the file isn't locked or there is an internal error.
     pub fn write(&mut self, content: &[u8]) {
@@ -71,12 +75,18 @@ impl CacheFile {
             panic!("The cache file should be locked before writing content to it.")
         }
 
-        let mut file = fs::OpenOptions::new()
-            .append(true)
-            .open(&self.path)
-            .unwrap();
+        let mut bytes = fs::read(&self.path).unwrap();
+        bytes.extend_from_slice(content);
+
+        let mut path_tmp = self.path.clone().into_os_string();
+        path_tmp.push(format!(".{}.tmp", std::process::id()));
+
+        let mut file = File::create(&path_tmp).unwrap();
+        file.write_all(&bytes).unwrap();
+        file.sync_all().unwrap();
+        fs::rename(&path_tmp, &self.path).unwrap();
 
-        self.cursor += file.write(content).unwrap() as u64;
+        self.cursor = bytes.len() as u64;
     }
 }
 
@@ -0,0 +1,423 @@
+use bytemuck::{Pod, Zeroable};
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use num_traits::{NumCast, ToPrimitive};
+
+/// Layout of an 8-bit floating point format, with the IEEE 754 meaning of the exponent bias and
+/// of subnormals.
+struct Fp8Format {
+    mantissa_bits: u32,
+    bias: i32,
+    /// Bits of the largest finite value, without the sign
+    max_bits: u8,
+    /// Bits of the canonical NaN, without the sign
+    nan_bits: u8,
+    /// Whether an exponent with all bits set encodes the infinities and NaNs, like in IEEE 754.
+    /// Otherwise only the bits of [nan_bits](Fp8Format::nan_bits) encode NaN
+    ieee_special: bool,
+}
+
+const E4M3: Fp8Format = Fp8Format {
+    mantissa_bits: 3,
+    bias: 7,
+    max_bits: 0x7E,
+    nan_bits: 0x7F,
+    ieee_special: false,
+};
+
+const E5M2: Fp8Format = Fp8Format {
+    mantissa_bits: 2,
+    bias: 15,
+    max_bits: 0x7B,
+    nan_bits: 0x7F,
+    ieee_special: true,
+};
+
+impl Fp8Format {
+    /// Rounds `value` to the nearest representable value, ties to even. Values out of range,
+    /// infinities included, saturate to the largest finite value like the `__NV_SATFINITE`
+    /// conversions of CUDA.
+    fn from_f32(&self, value: f32) -> u8 {
+        let bits = value.to_bits();
+        let sign = ((bits >> 24) & 0x80) as u8;
+
+        if value.is_nan() {
+            return sign | self.nan_bits;
+        }
+        if value.is_infinite() {
+            return sign | self.max_bits;
+        }
+
+        let exponent = ((bits >> 23) & 0xFF) as i32;
+        // Subnormal `f32`s are far below the smallest `fp8` and round to zero.
+        if exponent == 0 {
+            return sign;
+        }
+        let mantissa = ((bits & 0x7F_FFFF) | 0x80_0000) as u64;
+        let exponent = exponent - 127;
+
+        let min_exponent = 1 - self.bias;
+        let mut shift = (23 - self.mantissa_bits) as i32;
+        if exponent < min_exponent {
+            shift += min_exponent - exponent;
+        }
+        let rounded = round_shift(mantissa, shift.min(40) as u32);
+
+        let encoded = if exponent < min_exponent {
+            // Rounding up to the smallest normal value gives its encoding as well.
+            rounded
+        } else {
+            let biased = (exponent + self.bias) as u64;
+            // Rounding up past the mantissa carries into the exponent.
+            (biased << self.mantissa_bits) + rounded - (1 << self.mantissa_bits)
+        };
+
+        sign | encoded.min(self.max_bits as u64) as u8
+    }
+
+    fn to_f32(&self, bits: u8) -> f32 {
+        let sign = ((bits & 0x80) as u32) << 24;
+        let magnitude = bits & 0x7F;
+        let exponent = (magnitude >> self.mantissa_bits) as i32;
+        let mantissa = (magnitude & ((1 << self.mantissa_bits) - 1)) as u32;
+
+        if magnitude == self.nan_bits {
+            return f32::NAN;
+        }
+        if self.ieee_special && exponent == (0x7F >> self.mantissa_bits) {
+            return match mantissa {
+                0 => f32::from_bits(sign | f32::INFINITY.to_bits()),
+                _ => f32::NAN,
+            };
+        }
+
+        if exponent == 0 {
+            // Subnormals are the mantissa times the quantum of the smallest exponent.
+            let quantum = 1 - self.bias - self.mantissa_bits as i32;
+            let quantum = f32::from_bits(((127 + quantum) as u32) << 23);
+            let value = mantissa as f32 * quantum;
+            return f32::from_bits(sign | value.to_bits());
+        }
+
+        let exponent = (exponent - self.bias + 127) as u32;
+        f32::from_bits(sign | (exponent << 23) | (mantissa << (23 - self.mantissa_bits)))
+    }
+}
+
+/// Shifts `value` right by `shift` bits, rounding to the nearest integer with ties to even.
+fn round_shift(value: u64, shift: u32) -> u64 {
+    if shift == 0 {
+        return value;
+    }
+
+    let result = value >> shift;
+    let remainder = value & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+
+    if remainder > half || (remainder == half && result & 1 == 1) {
+        result + 1
+    } else {
+        result
+    }
+}
+
+macro_rules! fp8 {
+    ($(#[$meta:meta])* $name:ident, $format:expr) => {
+        $(#[$meta])*
+        #[allow(non_camel_case_types)]
+        #[repr(transparent)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[derive(Clone, Copy, Default, Zeroable, Pod)]
+        pub struct $name(u8);
+
+        impl $name {
+            /// Constructs a value from the raw bits.
+            #[inline]
+            #[must_use]
+            pub const fn from_bits(bits: u8) -> Self {
+                Self(bits)
+            }
+
+            /// Converts the value into the underlying bit representation.
+            #[inline]
+            #[must_use]
+            pub const fn to_bits(self) -> u8 {
+                self.0
+            }
+
+            /// Constructs a value from a 32-bit floating point value.
+            ///
+            /// This operation is lossy. Values are rounded to the nearest representable value,
+            /// ties to even, and values out of range saturate to the largest finite value, the
+            /// infinities included. NaN values are preserved.
+            #[inline]
+            #[must_use]
+            pub fn from_f32(value: f32) -> Self {
+                Self($format.from_f32(value))
+            }
+
+            /// Constructs a value from a 64-bit floating point value, going through [`f32`].
+            #[inline]
+            #[must_use]
+            pub fn from_f64(value: f64) -> Self {
+                Self::from_f32(value as f32)
+            }
+
+            /// Converts the value into an [`f32`] value.
+            ///
+            /// This conversion is lossless as all values can be represented exactly in [`f32`].
+            #[inline]
+            #[must_use]
+            pub fn to_f32(self) -> f32 {
+                $format.to_f32(self.0)
+            }
+
+            /// Converts the value into an [`f64`] value.
+            ///
+            /// This conversion is lossless as all values can be represented exactly in [`f64`].
+            #[inline]
+            #[must_use]
+            pub fn to_f64(self) -> f64 {
+                self.to_f32() as f64
+            }
+
+            /// Whether the value represents `NaN`
+            pub fn is_nan(&self) -> bool {
+                Self::to_f32(*self).is_nan()
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                Self::to_f32(*self) == Self::to_f32(*other)
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Self::to_f32(*self).partial_cmp(&Self::to_f32(*other))
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                Debug::fmt(&Self::to_f32(*self), f)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                Display::fmt(&Self::to_f32(*self), f)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self(self.0 ^ 0x80)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32() * rhs.to_f32())
+            }
+        }
+
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl Div for $name {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32() / rhs.to_f32())
+            }
+        }
+
+        impl DivAssign for $name {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32() + rhs.to_f32())
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32() - rhs.to_f32())
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl ToPrimitive for $name {
+            fn to_i64(&self) -> Option<i64> {
+                Some($name::to_f32(*self) as i64)
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                Some($name::to_f32(*self) as u64)
+            }
+
+            fn to_f32(&self) -> Option<f32> {
+                Some($name::to_f32(*self))
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                Some($name::to_f64(*self))
+            }
+        }
+
+        impl NumCast for $name {
+            fn from<T: num_traits::ToPrimitive>(n: T) -> Option<Self> {
+                Some(Self::from_f32(n.to_f32()?))
+            }
+        }
+    };
+}
+
+fp8!(
+    /// An 8-bit floating point type with 4 exponent bits and 3 mantissa bits.
+    ///
+    /// The format has no infinities and a single NaN per sign, its largest finite value being
+    /// `448`. It has more precision than [`e5m2`] and is usually used for weights and
+    /// activations.
+    e4m3,
+    E4M3
+);
+
+fp8!(
+    /// An 8-bit floating point type with 5 exponent bits and 2 mantissa bits.
+    ///
+    /// The format follows IEEE 754 with infinities and NaNs, its largest finite value being
+    /// `57344`. It has more range than [`e4m3`] and is usually used for gradients.
+    e5m2,
+    E5M2
+);
+
+impl e4m3 {
+    /// Largest finite value, `448`
+    pub const MAX: Self = Self(0x7E);
+    /// Smallest finite value, `-448`
+    pub const MIN: Self = Self(0xFE);
+    /// Smallest positive normal value, `2^-6`
+    pub const MIN_POSITIVE: Self = Self(0x08);
+    /// Difference between `1` and the next larger representable value, `2^-3`
+    pub const EPSILON: Self = Self(0x20);
+    /// NaN
+    pub const NAN: Self = Self(0x7F);
+}
+
+impl e5m2 {
+    /// Largest finite value, `57344`
+    pub const MAX: Self = Self(0x7B);
+    /// Smallest finite value, `-57344`
+    pub const MIN: Self = Self(0xFB);
+    /// Smallest positive normal value, `2^-14`
+    pub const MIN_POSITIVE: Self = Self(0x04);
+    /// Difference between `1` and the next larger representable value, `2^-2`
+    pub const EPSILON: Self = Self(0x34);
+    /// Positive infinity
+    pub const INFINITY: Self = Self(0x7C);
+    /// Negative infinity
+    pub const NEG_INFINITY: Self = Self(0xFC);
+    /// NaN
+    pub const NAN: Self = Self(0x7F);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn e4m3_round_trips_every_value() {
+        for bits in 0..=u8::MAX {
+            let value = e4m3::from_bits(bits);
+            if value.is_nan() {
+                assert!(e4m3::from_f32(value.to_f32()).is_nan());
+            } else {
+                assert_eq!(e4m3::from_f32(value.to_f32()).to_bits(), bits, "{bits:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn e5m2_round_trips_every_value() {
+        for bits in 0..=u8::MAX {
+            let value = e5m2::from_bits(bits);
+            if value.is_nan() {
+                assert!(e5m2::from_f32(value.to_f32()).is_nan());
+            } else if value.to_f32().is_infinite() {
+                // Infinities saturate, like the CUDA conversions.
+                assert_eq!(
+                    e5m2::from_f32(value.to_f32()).to_f32(),
+                    value.to_f32().signum() * 57344.0
+                );
+            } else {
+                assert_eq!(e5m2::from_f32(value.to_f32()).to_bits(), bits, "{bits:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn fp8_constants() {
+        assert_eq!(e4m3::MAX.to_f32(), 448.0);
+        assert_eq!(e4m3::MIN.to_f32(), -448.0);
+        assert_eq!(e4m3::MIN_POSITIVE.to_f32(), 2f32.powi(-6));
+        assert_eq!(e4m3::EPSILON.to_f32(), 0.125);
+        assert_eq!(e4m3::from_bits(0x01).to_f32(), 2f32.powi(-9));
+        assert_eq!(e5m2::MAX.to_f32(), 57344.0);
+        assert_eq!(e5m2::MIN.to_f32(), -57344.0);
+        assert_eq!(e5m2::MIN_POSITIVE.to_f32(), 2f32.powi(-14));
+        assert_eq!(e5m2::EPSILON.to_f32(), 0.25);
+        assert_eq!(e5m2::INFINITY.to_f32(), f32::INFINITY);
+        assert_eq!(e5m2::from_bits(0x01).to_f32(), 2f32.powi(-16));
+    }
+
+    #[test]
+    fn fp8_rounds_to_nearest_even_and_saturates() {
+        // Halfway between 1 and 1.125 rounds to the even mantissa.
+        assert_eq!(e4m3::from_f32(1.0625).to_f32(), 1.0);
+        assert_eq!(e4m3::from_f32(1.1875).to_f32(), 1.25);
+        assert_eq!(e4m3::from_f32(1.07).to_f32(), 1.125);
+        // Rounding the largest mantissa carries into the exponent.
+        assert_eq!(e4m3::from_f32(1.95).to_f32(), 2.0);
+        assert_eq!(e5m2::from_f32(-1.9).to_f32(), -2.0);
+        // Subnormals and the smallest normal value.
+        assert_eq!(e4m3::from_f32(2f32.powi(-10)).to_f32(), 0.0);
+        assert_eq!(e4m3::from_f32(2f32.powi(-10) * 1.5).to_f32(), 2f32.powi(-9));
+        assert_eq!(e4m3::from_f32(2f32.powi(-6) * 0.99).to_f32(), 2f32.powi(-6));
+        // Out of range values saturate.
+        assert_eq!(e4m3::from_f32(1000.0).to_f32(), 448.0);
+        assert_eq!(e4m3::from_f32(f32::NEG_INFINITY).to_f32(), -448.0);
+        assert_eq!(e5m2::from_f32(1e6).to_f32(), 57344.0);
+        assert!(e4m3::from_f32(f32::NAN).is_nan());
+        assert_eq!(-e4m3::from_f32(2.0), e4m3::from_f32(-2.0));
+    }
+}
@@ -34,12 +34,76 @@ impl CubeDim {
         Self { x, y, z }
     }
 
+    /// Create a new cube dim with the given x, y and z, making sure the total number of units
+    /// fits in a `u32` and doesn't exceed `max_units`, e.g. the `max_units_per_cube` of the device.
+    pub const fn try_new(x: u32, y: u32, z: u32, max_units: u32) -> Result<Self, CubeDimError> {
+        let cube_dim = Self { x, y, z };
+
+        match cube_dim.checked_num_elems() {
+            Some(num_elems) if num_elems <= max_units => Ok(cube_dim),
+            Some(num_elems) => Err(CubeDimError::TooManyUnits {
+                num_elems,
+                max_units,
+            }),
+            None => Err(CubeDimError::Overflow { x, y, z }),
+        }
+    }
+
     /// Total numbers of units per cube
+    ///
+    /// # Panics
+    ///
+    /// If the number of units overflows a `u32`.
     pub const fn num_elems(&self) -> u32 {
-        self.x * self.y * self.z
+        match self.checked_num_elems() {
+            Some(num_elems) => num_elems,
+            None => panic!("The number of units per cube overflows a u32"),
+        }
+    }
+
+    /// Total numbers of units per cube, or `None` if it overflows a `u32`.
+    pub const fn checked_num_elems(&self) -> Option<u32> {
+        match self.x.checked_mul(self.y) {
+            Some(xy) => xy.checked_mul(self.z),
+            None => None,
+        }
     }
 }
 
+/// Error when creating an invalid [CubeDim].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CubeDimError {
+    /// The total number of units doesn't fit in a `u32`.
+    #[allow(missing_docs)]
+    Overflow { x: u32, y: u32, z: u32 },
+    /// The cube has more units than the maximum allowed.
+    #[allow(missing_docs)]
+    TooManyUnits { num_elems: u32, max_units: u32 },
+}
+
+impl core::fmt::Display for CubeDimError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CubeDimError::Overflow { x, y, z } => {
+                write!(
+                    f,
+                    "The number of units of cube dim ({x}, {y}, {z}) overflows a u32"
+                )
+            }
+            CubeDimError::TooManyUnits {
+                num_elems,
+                max_units,
+            } => write!(
+                f,
+                "The cube has {num_elems} units but at most {max_units} are supported"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CubeDimError {}
+
 impl Default for CubeDim {
     fn default() -> Self {
         Self {
@@ -59,3 +123,39 @@ pub enum ExecutionMode {
     /// Unchecked kernels are unsafe.
     Unchecked,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_new_accepts_cube_within_limit() {
+        assert_eq!(
+            CubeDim::try_new(16, 16, 4, 1024),
+            Ok(CubeDim::new(16, 16, 4))
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_too_many_units() {
+        assert_eq!(
+            CubeDim::try_new(32, 32, 2, 1024),
+            Err(CubeDimError::TooManyUnits {
+                num_elems: 2048,
+                max_units: 1024
+            })
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_overflow() {
+        assert_eq!(
+            CubeDim::try_new(u32::MAX, 2, 1, u32::MAX),
+            Err(CubeDimError::Overflow {
+                x: u32::MAX,
+                y: 2,
+                z: 1
+            })
+        );
+    }
+}
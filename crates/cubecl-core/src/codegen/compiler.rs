@@ -26,4 +26,7 @@ pub trait Compiler: Sync + Send + 'static + Clone + core::fmt::Debug {
 #[derive(Clone, Debug, Default)]
 pub struct WgpuCompilationOptions {
     pub supports_fp_fast_math: bool,
+    /// Emulate plane operations with shared memory, see
+    /// [Feature::PlaneEmulated](crate::Feature::PlaneEmulated). Only the WGSL compiler supports it.
+    pub emulate_planes: bool,
 }
@@ -1,4 +1,8 @@
-use std::{fmt::Display, marker::PhantomData};
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
 
 use crate::{Compiler, Kernel, KernelId, KernelOptions};
 use alloc::sync::Arc;
@@ -205,7 +209,28 @@ pub struct KernelDefinition {
     pub options: KernelOptions,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+impl KernelDefinition {
+    /// Hash of the content of the kernel definition, usable as a key to cache compiled kernels
+    /// between runs.
+    ///
+    /// The hash covers the bindings, the cube dim, the options and the instructions and variables
+    /// of the body, but not the variable allocator or debug names. It uses an unseeded hasher, so
+    /// it is the same across runs for an identical definition.
+    pub fn stable_hash(&self) -> u64 {
+        let mut hasher = fnv::FnvHasher::default();
+
+        self.inputs.hash(&mut hasher);
+        self.outputs.hash(&mut hasher);
+        self.named.hash(&mut hasher);
+        self.cube_dim.hash(&mut hasher);
+        self.body.hash(&mut hasher);
+        self.options.hash(&mut hasher);
+
+        hasher.finish()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct Binding {
     pub location: Location,
@@ -215,14 +240,14 @@ pub struct Binding {
     pub has_extended_meta: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub enum Location {
     Storage,
     Cube,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub enum Visibility {
     Read,
@@ -320,3 +345,97 @@ impl<C: Compiler> CubeTask<C> for Box<dyn CubeTask<C>> {
         self.as_ref().name()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KernelSettings, compute::KernelBuilder};
+    use cubecl_ir::{Elem, FloatKind, Instruction, Operation, UIntKind};
+
+    fn definition(cube_dim: CubeDim) -> KernelDefinition {
+        KernelDefinition {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            named: Vec::new(),
+            cube_dim,
+            body: Scope::root(false),
+            options: KernelOptions::default(),
+        }
+    }
+
+    #[test]
+    fn stable_hash_is_deterministic() {
+        let lhs = definition(CubeDim::new(8, 8, 1));
+        let rhs = definition(CubeDim::new(8, 8, 1));
+
+        assert_eq!(lhs.stable_hash(), rhs.stable_hash());
+    }
+
+    #[test]
+    fn stable_hash_changes_with_content() {
+        let lhs = definition(CubeDim::new(8, 8, 1));
+        let rhs = definition(CubeDim::new(16, 8, 1));
+
+        assert_ne!(lhs.stable_hash(), rhs.stable_hash());
+    }
+
+    /// A kernel copying its input array to its output array when `copy` is set.
+    fn built(elem: Elem, scalars: usize, copy: bool) -> KernelDefinition {
+        let mut builder = KernelBuilder::default();
+        let input = builder.input_array(Item::new(elem));
+        let output = builder.output_array(Item::new(elem));
+
+        for _ in 0..scalars {
+            builder.scalar(Elem::UInt(UIntKind::U32));
+        }
+        if copy {
+            builder
+                .context
+                .register(Instruction::new(Operation::Copy(*input), *output));
+        }
+
+        builder.build(KernelSettings::default().cube_dim(CubeDim::new(8, 8, 1)))
+    }
+
+    #[test]
+    fn stable_hash_is_equal_for_identical_rebuild() {
+        let f32 = Elem::Float(FloatKind::F32);
+
+        assert_eq!(
+            built(f32, 1, true).stable_hash(),
+            built(f32, 1, true).stable_hash()
+        );
+    }
+
+    #[test]
+    fn stable_hash_changes_with_bindings() {
+        let lhs = built(Elem::Float(FloatKind::F32), 0, true);
+        let rhs = built(Elem::Float(FloatKind::F16), 0, true);
+
+        assert_ne!(lhs.stable_hash(), rhs.stable_hash());
+    }
+
+    #[test]
+    fn stable_hash_changes_with_scalars() {
+        let f32 = Elem::Float(FloatKind::F32);
+
+        assert_ne!(
+            built(f32, 0, true).stable_hash(),
+            built(f32, 1, true).stable_hash()
+        );
+        assert_ne!(
+            built(f32, 1, true).stable_hash(),
+            built(f32, 2, true).stable_hash()
+        );
+    }
+
+    #[test]
+    fn stable_hash_changes_with_body() {
+        let f32 = Elem::Float(FloatKind::F32);
+
+        assert_ne!(
+            built(f32, 0, false).stable_hash(),
+            built(f32, 0, true).stable_hash()
+        );
+    }
+}
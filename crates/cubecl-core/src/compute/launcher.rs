@@ -9,12 +9,15 @@ use crate::{
 };
 use crate::{compute::KernelTask, ir::UIntKind};
 use bytemuck::NoUninit;
+use cubecl_common::{e4m3, e5m2};
 use cubecl_runtime::client::ComputeClient;
 use cubecl_runtime::server::{Binding, CubeCount};
 
 /// Prepare a kernel for [launch](KernelLauncher::launch).
 pub struct KernelLauncher<R: Runtime> {
     tensors: TensorState<R>,
+    scalar_e4m3: ScalarState<e4m3>,
+    scalar_e5m2: ScalarState<e5m2>,
     scalar_bf16: ScalarState<half::bf16>,
     scalar_f16: ScalarState<half::f16>,
     scalar_f32: ScalarState<f32>,
@@ -91,6 +94,18 @@ impl<R: Runtime> KernelLauncher<R> {
         self.scalar_i64.push(scalar);
     }
 
+    /// Register a e4m3 scalar to be launched.
+    pub fn register_e4m3(&mut self, scalar: e4m3) {
+        self.register_scalar(Elem::Float(FloatKind::E4M3));
+        self.scalar_e4m3.push(scalar);
+    }
+
+    /// Register a e5m2 scalar to be launched.
+    pub fn register_e5m2(&mut self, scalar: e5m2) {
+        self.register_scalar(Elem::Float(FloatKind::E5M2));
+        self.scalar_e5m2.push(scalar);
+    }
+
     /// Register a bf16 scalar to be launched.
     pub fn register_bf16(&mut self, scalar: half::bf16) {
         self.register_scalar(Elem::Float(FloatKind::BF16));
@@ -166,6 +181,8 @@ impl<R: Runtime> KernelLauncher<R> {
         for elem in self.scalar_order.drain(..) {
             match elem {
                 Elem::Float(kind) | Elem::AtomicFloat(kind) => match kind {
+                    FloatKind::E4M3 => self.scalar_e4m3.register::<R>(client, &mut bindings),
+                    FloatKind::E5M2 => self.scalar_e5m2.register::<R>(client, &mut bindings),
                     FloatKind::F16 => self.scalar_f16.register::<R>(client, &mut bindings),
                     FloatKind::BF16 => self.scalar_bf16.register::<R>(client, &mut bindings),
                     FloatKind::TF32 => self.scalar_f32.register::<R>(client, &mut bindings),
@@ -230,12 +247,12 @@ pub enum ScalarState<T> {
 impl<R: Runtime> TensorState<R> {
     /// Push a new tensor to the state.
     pub fn push_tensor(&mut self, tensor: &TensorArg<'_, R>) {
-        let (tensor, vectorization) = match tensor {
+        let (tensor, vectorization, offset) = match tensor {
             TensorArg::Handle {
                 handle,
                 vectorization_factor,
-                ..
-            } => (handle, vectorization_factor),
+                offset,
+            } => (handle, vectorization_factor, *offset),
             TensorArg::Alias { .. } => return,
         };
 
@@ -254,16 +271,32 @@ impl<R: Runtime> TensorState<R> {
             panic!("Should be init")
         };
 
+        let handle = match offset {
+            0 => tensor.handle.clone(),
+            offset => tensor
+                .handle
+                .clone()
+                .offset_start((offset * tensor.elem_size) as u64),
+        };
+
         let elem_size = tensor.elem_size * *vectorization as usize;
-        let buffer_len = tensor.handle.size() / elem_size as u64;
+        let buffer_len = handle.size() / elem_size as u64;
         let len = tensor.shape.iter().product::<usize>() / *vectorization as usize;
-        bindings.push(tensor.handle.clone().binding());
+        bindings.push(handle.binding());
         metadata.with_tensor(
             tensor.strides.len() as u32,
-            buffer_len as u32,
-            len as u32,
-            tensor.shape.iter().map(|it| *it as u32).collect(),
-            tensor.strides.iter().map(|it| *it as u32).collect(),
+            saturating_u32(buffer_len as usize),
+            saturating_u32(len),
+            tensor
+                .shape
+                .iter()
+                .map(|it| dim_u32("shape", *it))
+                .collect(),
+            tensor
+                .strides
+                .iter()
+                .map(|it| dim_u32("stride", *it))
+                .collect(),
         );
     }
 
@@ -296,7 +329,10 @@ impl<R: Runtime> TensorState<R> {
         let elem_size = array.elem_size * *vectorization as usize;
         let buffer_len = array.handle.size() / elem_size as u64;
         bindings.push(array.handle.clone().binding());
-        metadata.with_array(buffer_len as u32, array.length[0] as u32);
+        metadata.with_array(
+            saturating_u32(buffer_len as usize),
+            saturating_u32(array.length[0]),
+        );
     }
 
     fn register(
@@ -346,6 +382,8 @@ impl<R: Runtime> Default for KernelLauncher<R> {
     fn default() -> Self {
         Self {
             tensors: TensorState::Empty,
+            scalar_e4m3: ScalarState::Empty,
+            scalar_e5m2: ScalarState::Empty,
             scalar_bf16: ScalarState::Empty,
             scalar_f16: ScalarState::Empty,
             scalar_f32: ScalarState::Empty,
@@ -364,3 +402,19 @@ impl<R: Runtime> Default for KernelLauncher<R> {
         }
     }
 }
+
+/// Lengths are stored on 32 bits. Longer ones are stored as `u32::MAX` rather than wrapped around,
+/// so that checked indexing stays within the buffer, the elements past `u32::MAX` being only
+/// reachable with 64-bit unchecked indexing.
+fn saturating_u32(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
+
+/// Shapes and strides are stored on 32 bits as well, and can't be saturated without breaking the
+/// offsets computed from them. A tensor can have more than `u32::MAX` elements, but each of its
+/// dimensions and strides must fit.
+fn dim_u32(name: &str, value: usize) -> u32 {
+    u32::try_from(value).unwrap_or_else(|_| {
+        panic!("Tensor {name} {value} doesn't fit in the 32 bits of the tensor metadata")
+    })
+}
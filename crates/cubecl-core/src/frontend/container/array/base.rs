@@ -286,9 +286,63 @@ mod indexation {
         {
             unexpanded!()
         }
+
+        /// Perform an unchecked index into the array with a 64-bit index, for arrays with more
+        /// than `u32::MAX` elements.
+        ///
+        /// The length of an array is stored on 32 bits, so only unchecked indexing can reach the
+        /// elements past `u32::MAX`.
+        ///
+        /// # Safety
+        /// Out of bounds indexing causes undefined behaviour and may segfault. Ensure index is
+        /// always in bounds
+        pub unsafe fn index_unchecked_u64(&self, _i: u64) -> &E {
+            unexpanded!()
+        }
+
+        /// Perform an unchecked index assignment into the array with a 64-bit index, like
+        /// [index_unchecked_u64](Array::index_unchecked_u64).
+        ///
+        /// # Safety
+        /// Out of bounds indexing causes undefined behaviour and may segfault. Ensure index is
+        /// always in bounds
+        pub unsafe fn index_assign_unchecked_u64(&mut self, _i: u64, _value: E) {
+            unexpanded!()
+        }
     }
 
     impl<E: CubePrimitive> ExpandElementTyped<Array<E>> {
+        pub fn __expand_index_unchecked_u64_method(
+            self,
+            scope: &mut Scope,
+            i: ExpandElementTyped<u64>,
+        ) -> ExpandElementTyped<E> {
+            let out = scope.create_local(self.expand.item);
+            scope.register(Instruction::new(
+                Operator::UncheckedIndex(BinaryOperator {
+                    lhs: *self.expand,
+                    rhs: i.expand.consume(),
+                }),
+                *out,
+            ));
+            out.into()
+        }
+
+        pub fn __expand_index_assign_unchecked_u64_method(
+            self,
+            scope: &mut Scope,
+            i: ExpandElementTyped<u64>,
+            value: ExpandElementTyped<E>,
+        ) {
+            scope.register(Instruction::new(
+                Operator::UncheckedIndexAssign(BinaryOperator {
+                    lhs: i.expand.consume(),
+                    rhs: value.expand.consume(),
+                }),
+                *self.expand,
+            ));
+        }
+
         pub fn __expand_index_unchecked_method(
             self,
             scope: &mut Scope,
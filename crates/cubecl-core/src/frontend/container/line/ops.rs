@@ -4,8 +4,8 @@ use num_traits::{NumCast, ToPrimitive};
 use crate::{
     frontend::{
         Abs, Ceil, Clamp, Cos, CubeIndex, CubeIndexMut, CubePrimitive, Erf, Exp,
-        ExpandElementTyped, Floor, Log, Log1p, Max, Min, Powf, Recip, Remainder, Round, Sin, Sqrt,
-        Tanh,
+        ExpandElementTyped, Floor, Log, Log1p, Max, Min, Powf, Recip, Remainder, Round,
+        SaturatingAdd, SaturatingSub, Sin, Sqrt, Tanh, WrappingAdd, WrappingMul,
     },
     prelude::{BitwiseNot, CountOnes, FindFirstSet, LeadingZeros, ReverseBits},
     unexpanded,
@@ -246,6 +246,10 @@ impl<P: CubePrimitive + Sin> Sin for Line<P> {}
 impl<P: CubePrimitive + Tanh> Tanh for Line<P> {}
 impl<P: CubePrimitive + Recip> Recip for Line<P> {}
 impl<P: CubePrimitive + Remainder> Remainder for Line<P> {}
+impl<P: CubePrimitive + SaturatingAdd> SaturatingAdd for Line<P> {}
+impl<P: CubePrimitive + SaturatingSub> SaturatingSub for Line<P> {}
+impl<P: CubePrimitive + WrappingAdd> WrappingAdd for Line<P> {}
+impl<P: CubePrimitive + WrappingMul> WrappingMul for Line<P> {}
 impl<P: CubePrimitive + Round> Round for Line<P> {}
 impl<P: CubePrimitive + Floor> Floor for Line<P> {}
 impl<P: CubePrimitive + Ceil> Ceil for Line<P> {}
@@ -22,6 +22,8 @@ pub enum TensorArg<'a, R: Runtime> {
         handle: TensorHandleRef<'a, R>,
         /// The vectorization factor.
         vectorization_factor: u8,
+        /// Offset of the first element of the tensor in the handle, in elements.
+        offset: usize,
     },
     /// The tensor is aliasing another input tensor.
     Alias {
@@ -30,6 +32,25 @@ pub enum TensorArg<'a, R: Runtime> {
     },
 }
 
+impl<R: Runtime> Clone for TensorArg<'_, R> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Handle {
+                handle,
+                vectorization_factor,
+                offset,
+            } => Self::Handle {
+                handle: handle.clone(),
+                vectorization_factor: *vectorization_factor,
+                offset: *offset,
+            },
+            Self::Alias { input_pos } => Self::Alias {
+                input_pos: *input_pos,
+            },
+        }
+    }
+}
+
 /// Tensor representation with a reference to the [server handle](cubecl_runtime::server::Handle),
 /// the strides and the shape.
 pub struct TensorHandleRef<'a, R: Runtime> {
@@ -40,6 +61,18 @@ pub struct TensorHandleRef<'a, R: Runtime> {
     pub runtime: PhantomData<R>,
 }
 
+impl<R: Runtime> Clone for TensorHandleRef<'_, R> {
+    fn clone(&self) -> Self {
+        Self {
+            handle: self.handle,
+            strides: self.strides,
+            shape: self.shape,
+            elem_size: self.elem_size,
+            runtime: PhantomData,
+        }
+    }
+}
+
 impl<R: Runtime> TensorHandleRef<'_, R> {
     pub fn size(&self) -> usize {
         self.shape.iter().product()
@@ -137,6 +170,38 @@ impl<'a, R: Runtime> TensorArg<'a, R> {
                     E::size().expect("Element should have a size"),
                 ),
                 vectorization_factor: factor,
+                offset: 0,
+            }
+        }
+    }
+
+    /// Create a new tensor argument starting `offset` elements into the handle.
+    ///
+    /// The offset is applied when binding the handle, so the same compiled kernel is reused for
+    /// every offset, and it maps to the dynamic buffer offset of the backend. The offset in bytes
+    /// must respect the buffer offset alignment of the runtime.
+    ///
+    /// # Safety
+    ///
+    /// If you provide wrong strides, shapes or offset, it might create undefined behavior caused by
+    /// out-of-bound reads and writes.
+    pub unsafe fn from_raw_parts_with_offset<E: CubePrimitive>(
+        handle: &'a cubecl_runtime::server::Handle,
+        strides: &'a [usize],
+        shape: &'a [usize],
+        factor: u8,
+        offset: usize,
+    ) -> Self {
+        unsafe {
+            Self::Handle {
+                handle: TensorHandleRef::from_raw_parts(
+                    handle,
+                    strides,
+                    shape,
+                    E::size().expect("Element should have a size"),
+                ),
+                vectorization_factor: factor,
+                offset,
             }
         }
     }
@@ -159,6 +224,7 @@ impl<'a, R: Runtime> TensorArg<'a, R> {
             Self::Handle {
                 handle: TensorHandleRef::from_raw_parts(handle, strides, shape, elem_size),
                 vectorization_factor: factor,
+                offset: 0,
             }
         }
     }
@@ -4,7 +4,7 @@ use crate::{
     ir::{ConstantScalarValue, Operation, Scope, Variable, VariableKind},
     prelude::{KernelBuilder, KernelLauncher, init_expand},
 };
-use cubecl_common::{flex32, tf32};
+use cubecl_common::{e4m3, e5m2, flex32, tf32};
 use cubecl_ir::ExpandElement;
 use half::{bf16, f16};
 use std::{
@@ -188,6 +188,8 @@ from_const!(f16);
 from_const!(bf16);
 from_const!(flex32);
 from_const!(tf32);
+from_const!(e4m3);
+from_const!(e5m2);
 from_const!(f32);
 from_const!(bool);
 
@@ -8,6 +8,7 @@ use crate::{
 
 use super::Numeric;
 
+mod fp8;
 mod relaxed;
 mod tensor_float;
 mod typemap;
@@ -63,6 +64,22 @@ pub trait Float:
     fn __expand_new(scope: &mut Scope, val: f32) -> <Self as CubeType>::ExpandType {
         __expand_new(scope, val)
     }
+
+    /// Positive infinity, usable as the identity of a `min` reduction or a mask value.
+    fn infinity() -> Self {
+        Self::INFINITY
+    }
+    fn __expand_infinity(scope: &mut Scope) -> <Self as CubeType>::ExpandType {
+        __expand_new(scope, f32::INFINITY)
+    }
+
+    /// Negative infinity, usable as the identity of a `max` reduction or a mask value.
+    fn neg_infinity() -> Self {
+        Self::NEG_INFINITY
+    }
+    fn __expand_neg_infinity(scope: &mut Scope) -> <Self as CubeType>::ExpandType {
+        __expand_new(scope, f32::NEG_INFINITY)
+    }
 }
 
 macro_rules! impl_float {
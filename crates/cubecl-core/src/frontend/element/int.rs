@@ -5,6 +5,7 @@ use crate::frontend::{CubeType, Numeric};
 use crate::ir::{Elem, IntKind, Scope};
 use crate::prelude::BitwiseNot;
 use crate::prelude::{FindFirstSet, LeadingZeros};
+use crate::prelude::{SaturatingAdd, SaturatingSub, WrappingAdd, WrappingMul};
 use crate::{
     compute::{KernelBuilder, KernelLauncher},
     prelude::{CountOnes, ReverseBits},
@@ -26,6 +27,10 @@ pub trait Int:
     + BitwiseNot
     + LeadingZeros
     + FindFirstSet
+    + SaturatingAdd
+    + SaturatingSub
+    + WrappingAdd
+    + WrappingMul
     + std::ops::Rem<Output = Self>
     + core::ops::Add<Output = Self>
     + core::ops::Sub<Output = Self>
@@ -197,6 +197,10 @@ impl<const POS: u8> ReverseBits for IntExpand<POS> {}
 impl<const POS: u8> CountOnes for IntExpand<POS> {}
 impl<const POS: u8> FindFirstSet for IntExpand<POS> {}
 impl<const POS: u8> LeadingZeros for IntExpand<POS> {}
+impl<const POS: u8> SaturatingAdd for IntExpand<POS> {}
+impl<const POS: u8> SaturatingSub for IntExpand<POS> {}
+impl<const POS: u8> WrappingAdd for IntExpand<POS> {}
+impl<const POS: u8> WrappingMul for IntExpand<POS> {}
 
 impl<T: Index, const POS: u8> CubeIndex<T> for IntExpand<POS> {
     type Output = Self;
@@ -53,7 +53,7 @@ pub mod index_assign {
     use ir::{Instruction, UIntKind, VariableKind};
 
     use crate::{
-        flex32,
+        e4m3, e5m2, flex32,
         frontend::CubeType,
         prelude::{ExpandElementTyped, SliceMut},
         tf32,
@@ -104,7 +104,7 @@ pub mod index_assign {
     impl_index!(Tensor);
     impl_index!(SharedMemory);
     impl_index_vec!(
-        i64, i32, i16, i8, f16, bf16, flex32, tf32, f32, f64, u64, u32, u16, u8
+        i64, i32, i16, i8, f16, bf16, flex32, tf32, e4m3, e5m2, f32, f64, u64, u32, u16, u8
     );
 
     impl<E: CubeType, I: Index> CubeIndexMut<I> for SliceMut<E> {}
@@ -114,7 +114,7 @@ pub mod index {
     use ir::{UIntKind, VariableKind};
 
     use crate::{
-        flex32,
+        e4m3, e5m2, flex32,
         frontend::{
             CubeType,
             operation::base::{binary_expand, binary_expand_no_vec},
@@ -176,7 +176,7 @@ pub mod index {
     impl_index!(Tensor);
     impl_index!(SharedMemory);
     impl_index_vec!(
-        i64, i32, i16, i8, f16, flex32, tf32, bf16, f32, f64, u64, u32, u16, u8
+        i64, i32, i16, i8, f16, flex32, tf32, e4m3, e5m2, bf16, f32, f64, u64, u32, u16, u8
     );
 
     impl<E: CubeType, I: Index> CubeIndex<I> for Slice<E> {
@@ -1,9 +1,9 @@
 use crate::ir::{Arithmetic, Bitwise, ExpandElement, Operator, Scope};
+use crate::{e4m3, e5m2, frontend::CubeType, tf32};
 use crate::{
     flex32,
     frontend::{CubePrimitive, ExpandElementTyped},
 };
-use crate::{frontend::CubeType, tf32};
 use crate::{
     frontend::operation::base::{binary_expand, binary_expand_fixed_output},
     unexpanded,
@@ -221,6 +221,8 @@ impl_binary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -234,6 +236,8 @@ impl_binary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64,
     i8,
@@ -255,6 +259,8 @@ impl_binary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64,
     i8,
@@ -276,6 +282,8 @@ impl_binary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64,
     i8,
@@ -287,6 +295,66 @@ impl_binary_func!(
     u32,
     u64
 );
+impl_binary_func!(
+    SaturatingAdd,
+    saturating_add,
+    __expand_saturating_add,
+    __expand_saturating_add_method,
+    Arithmetic::SaturatingAdd,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64
+);
+impl_binary_func!(
+    SaturatingSub,
+    saturating_sub,
+    __expand_saturating_sub,
+    __expand_saturating_sub_method,
+    Arithmetic::SaturatingSub,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64
+);
+impl_binary_func!(
+    WrappingAdd,
+    wrapping_add,
+    __expand_wrapping_add,
+    __expand_wrapping_add_method,
+    Arithmetic::WrappingAdd,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64
+);
+impl_binary_func!(
+    WrappingMul,
+    wrapping_mul,
+    __expand_wrapping_mul,
+    __expand_wrapping_mul_method,
+    Arithmetic::WrappingMul,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64
+);
 impl_binary_func_fixed_output_vectorization!(
     Dot,
     dot,
@@ -298,6 +366,8 @@ impl_binary_func_fixed_output_vectorization!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64,
     i8,
@@ -1,7 +1,7 @@
 use half::{bf16, f16};
 
 use crate::{
-    flex32,
+    e4m3, e5m2, flex32,
     ir::{Arithmetic, ClampOperator, ExpandElement, Scope},
     prelude::CubePrimitive,
     tf32, unexpanded,
@@ -40,6 +40,8 @@ impl Clamp for f16 {}
 impl Clamp for bf16 {}
 impl Clamp for flex32 {}
 impl Clamp for tf32 {}
+impl Clamp for e4m3 {}
+impl Clamp for e5m2 {}
 impl Clamp for f32 {}
 impl Clamp for f64 {}
 impl Clamp for i8 {}
@@ -2,7 +2,7 @@ use cubecl_ir::{Bitwise, Operator};
 use half::{bf16, f16};
 
 use crate::{
-    flex32,
+    e4m3, e5m2, flex32,
     ir::{Arithmetic, ExpandElement, Scope},
     prelude::{CubePrimitive, ExpandElementTyped},
     tf32, unexpanded,
@@ -95,6 +95,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64,
     i8,
@@ -115,6 +117,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -127,6 +131,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -139,6 +145,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -151,6 +159,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -163,6 +173,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -175,6 +187,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -187,6 +201,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -199,6 +215,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -211,6 +229,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -223,6 +243,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -235,6 +257,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -247,6 +271,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -260,6 +286,8 @@ impl_unary_func_fixed_out_vectorization!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
@@ -272,6 +300,8 @@ impl_unary_func!(
     bf16,
     flex32,
     tf32,
+    e4m3,
+    e5m2,
     f32,
     f64
 );
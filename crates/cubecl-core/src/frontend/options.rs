@@ -26,5 +26,7 @@ bitflags::bitflags! {
         const AllowTransform = 1 << 6;
         /// Allow using slightly lower precision intrinsics (CUDA `--use_fast_math`)
         const ReducedPrecision = 1 << 7;
+        /// Flush denormal values to zero (CUDA `--ftz=true`).
+        const FlushDenormals = 1 << 8;
     }
 }
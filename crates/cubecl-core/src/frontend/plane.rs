@@ -1,3 +1,13 @@
+//! Plane operations, also known as warp (CUDA), wavefront (HIP) or subgroup (Vulkan/WGSL)
+//! operations. The reductions and the broadcast are also available under their `subcube_*` names.
+//!
+//! They lower directly to the backend intrinsics with [Feature::Plane](crate::Feature::Plane). On
+//! devices without warps/subgroups, runtimes registering
+//! [Feature::PlaneEmulated](crate::Feature::PlaneEmulated) lower them to shared memory instead,
+//! with the same results, see [expand_plane_emulated](crate::prelude::expand_plane_emulated).
+//! The emulation synchronizes the units of the cube, so algorithms with a shared memory strategy,
+//! like those of `cubecl-reduce`, should still prefer it when only the emulation is available.
+
 use cubecl_ir::ExpandElement;
 
 use super::{CubePrimitive, Line};
@@ -393,3 +403,15 @@ pub mod plane_ballot {
         output.into()
     }
 }
+
+/// Alias of [plane_sum()] using the subcube naming.
+pub use plane_sum as subcube_sum;
+
+/// Alias of [plane_max()] using the subcube naming.
+pub use plane_max as subcube_max;
+
+/// Alias of [plane_min()] using the subcube naming.
+pub use plane_min as subcube_min;
+
+/// Alias of [plane_broadcast()] using the subcube naming.
+pub use plane_broadcast as subcube_broadcast;
@@ -1,4 +1,4 @@
-use cubecl_ir::{Elem, ExpandElement, Variable};
+use cubecl_ir::{BinaryOperator, Elem, ExpandElement, Plane, UnaryOperator, Variable};
 
 use crate::prelude::*;
 use crate::{self as cubecl, unexpanded};
@@ -84,3 +84,276 @@ pub fn expand_erf(scope: &mut Scope, input: Variable, out: Variable) {
     let res = erf::expand::<FloatExpand<0>>(scope, ExpandElement::Plain(input).into());
     assign::expand(scope, res, ExpandElement::Plain(out).into());
 }
+
+/// The combination of the values of a plane emulated by [expand_plane_emulated].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum PlaneFold {
+    Sum,
+    Prod,
+    Min,
+    Max,
+}
+
+/// The units of the plane whose values are combined by [expand_plane_emulated].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum PlaneRange {
+    /// Every unit of the plane.
+    All,
+    /// The units up to and including this one.
+    Inclusive,
+    /// The units before this one.
+    Exclusive,
+}
+
+/// Shared memory with the value of every unit of the cube at its `UNIT_POS`.
+#[cube]
+fn plane_emulated_share<N: Numeric>(
+    value: Line<N>,
+    #[comptime] units: u32,
+) -> SharedMemory<Line<N>> {
+    let mut shared = SharedMemory::<N>::new_lined(units, value.size());
+    shared[UNIT_POS] = value;
+    sync_units();
+
+    shared
+}
+
+#[cube]
+fn plane_combine<N: Numeric>(lhs: Line<N>, rhs: Line<N>, #[comptime] fold: PlaneFold) -> Line<N> {
+    let mut out = lhs;
+
+    if comptime!(fold == PlaneFold::Sum) {
+        out = lhs + rhs;
+    }
+    if comptime!(fold == PlaneFold::Prod) {
+        out = lhs * rhs;
+    }
+    if comptime!(fold == PlaneFold::Min) {
+        out = Min::min(lhs, rhs);
+    }
+    if comptime!(fold == PlaneFold::Max) {
+        out = Max::max(lhs, rhs);
+    }
+
+    out
+}
+
+#[cube]
+fn plane_emulated_fold<N: Numeric>(
+    value: Line<N>,
+    #[comptime] fold: PlaneFold,
+    #[comptime] range: PlaneRange,
+    #[comptime] plane_dim: u32,
+    #[comptime] units: u32,
+) -> Line<N> {
+    let shared = plane_emulated_share::<N>(value, units);
+    let start = UNIT_POS - UNIT_POS_X;
+
+    let mut acc = shared[start];
+    let mut from = start + 1;
+    let mut end = start + plane_dim;
+
+    if comptime!(range == PlaneRange::Inclusive) {
+        end = UNIT_POS + 1;
+    }
+    if comptime!(range == PlaneRange::Exclusive) {
+        let mut identity = N::from_int(0);
+        if comptime!(fold == PlaneFold::Prod) {
+            identity = N::from_int(1);
+        }
+        acc = Line::empty(value.size()).fill(identity);
+        from = start;
+        end = UNIT_POS;
+    }
+
+    for i in from..end {
+        acc = plane_combine::<N>(acc, shared[i], fold);
+    }
+
+    // The shared memory may be written again by the next iteration of a loop.
+    sync_units();
+
+    acc
+}
+
+#[cube]
+fn plane_emulated_broadcast<N: Numeric>(
+    value: Line<N>,
+    index: u32,
+    #[comptime] units: u32,
+) -> Line<N> {
+    let shared = plane_emulated_share::<N>(value, units);
+    let out = shared[UNIT_POS - UNIT_POS_X + index];
+    sync_units();
+
+    out
+}
+
+/// `plane_all`, or `plane_any` when `any` is set.
+#[cube]
+fn plane_emulated_all(
+    value: Line<bool>,
+    #[comptime] any: bool,
+    #[comptime] plane_dim: u32,
+    #[comptime] units: u32,
+) -> Line<bool> {
+    let mut shared = SharedMemory::<u32>::new_lined(units, value.size());
+    shared[UNIT_POS] = Line::cast_from(value);
+    sync_units();
+
+    let start = UNIT_POS - UNIT_POS_X;
+    let mut acc = shared[start];
+
+    for i in start + 1..start + plane_dim {
+        if comptime!(any) {
+            acc = Max::max(acc, shared[i]);
+        } else {
+            acc = Min::min(acc, shared[i]);
+        }
+    }
+    sync_units();
+
+    Line::cast_from(acc)
+}
+
+#[cube]
+fn plane_emulated_elect() -> bool {
+    UNIT_POS_X == 0
+}
+
+#[cube]
+fn plane_emulated_ballot(
+    value: bool,
+    #[comptime] plane_dim: u32,
+    #[comptime] units: u32,
+) -> Line<u32> {
+    let mut shared = SharedMemory::<u32>::new(units);
+    shared[UNIT_POS] = u32::cast_from(value);
+    sync_units();
+
+    let start = UNIT_POS - UNIT_POS_X;
+    let mut ballot = Line::empty(4u32).fill(0u32);
+
+    #[unroll]
+    for word in 0..4u32 {
+        let mut bits = 0u32;
+
+        for bit in 0..32u32 {
+            let lane = word * 32 + bit;
+            if lane < plane_dim {
+                bits |= shared[start + lane] << bit;
+            }
+        }
+
+        ballot[word] = bits;
+    }
+    sync_units();
+
+    ballot
+}
+
+fn emulate_fold<N: Numeric>(
+    scope: &mut Scope,
+    input: Variable,
+    out: ExpandElement,
+    fold: PlaneFold,
+    range: PlaneRange,
+    cube_dim: CubeDim,
+) {
+    scope.register_elem::<N>(input.item.elem);
+    let res = plane_emulated_fold::expand::<N>(
+        scope,
+        ExpandElement::Plain(input).into(),
+        fold,
+        range,
+        cube_dim.x,
+        cube_dim.num_elems(),
+    );
+    assign::expand(scope, res, out.into());
+}
+
+fn emulate_broadcast<N: Numeric>(
+    scope: &mut Scope,
+    op: BinaryOperator,
+    out: ExpandElement,
+    cube_dim: CubeDim,
+) {
+    scope.register_elem::<N>(op.lhs.item.elem);
+    let res = plane_emulated_broadcast::expand::<N>(
+        scope,
+        ExpandElement::Plain(op.lhs).into(),
+        ExpandElement::Plain(op.rhs).into(),
+        cube_dim.num_elems(),
+    );
+    assign::expand(scope, res, out.into());
+}
+
+/// Expand a plane operation emulated with shared memory, for devices without warps/subgroups.
+///
+/// The emulated planes are the rows of units along x of the cube, so their dimension is
+/// `cube_dim.x` and the position of a unit in its plane is `UNIT_POS_X`. The values are staged in
+/// shared memory of their own element type, so integers keep their full precision.
+///
+/// # Uniform control flow
+///
+/// The units exchange their values through shared memory between two [sync_units()], which every
+/// unit of the cube must reach. The operation must therefore be called in uniform control flow:
+/// if only some units of the cube reach it, for example in a branch on `UNIT_POS`, the kernel
+/// deadlocks instead of combining the values of the active units. All units are also considered
+/// active for [plane_elect()].
+pub fn expand_plane_emulated(scope: &mut Scope, plane: Plane, out: Variable, cube_dim: CubeDim) {
+    let plane_dim = cube_dim.x;
+    let units = cube_dim.num_elems();
+    let out = ExpandElement::Plain(out);
+
+    let mut emulate = |op: UnaryOperator, fold: PlaneFold, range: PlaneRange| {
+        let out = out.clone();
+        if op.input.item.elem.is_int() {
+            emulate_fold::<IntExpand<0>>(scope, op.input, out, fold, range, cube_dim);
+        } else {
+            emulate_fold::<FloatExpand<0>>(scope, op.input, out, fold, range, cube_dim);
+        }
+    };
+
+    match plane {
+        Plane::Sum(op) => emulate(op, PlaneFold::Sum, PlaneRange::All),
+        Plane::InclusiveSum(op) => emulate(op, PlaneFold::Sum, PlaneRange::Inclusive),
+        Plane::ExclusiveSum(op) => emulate(op, PlaneFold::Sum, PlaneRange::Exclusive),
+        Plane::Prod(op) => emulate(op, PlaneFold::Prod, PlaneRange::All),
+        Plane::InclusiveProd(op) => emulate(op, PlaneFold::Prod, PlaneRange::Inclusive),
+        Plane::ExclusiveProd(op) => emulate(op, PlaneFold::Prod, PlaneRange::Exclusive),
+        Plane::Min(op) => emulate(op, PlaneFold::Min, PlaneRange::All),
+        Plane::Max(op) => emulate(op, PlaneFold::Max, PlaneRange::All),
+        Plane::Broadcast(op) if op.lhs.item.elem.is_int() => {
+            emulate_broadcast::<IntExpand<0>>(scope, op, out, cube_dim)
+        }
+        Plane::Broadcast(op) => emulate_broadcast::<FloatExpand<0>>(scope, op, out, cube_dim),
+        Plane::All(op) => {
+            let input = ExpandElement::Plain(op.input).into();
+            let res = plane_emulated_all::expand(scope, input, false, plane_dim, units);
+            assign::expand(scope, res, out.into());
+        }
+        Plane::Any(op) => {
+            let input = ExpandElement::Plain(op.input).into();
+            let res = plane_emulated_all::expand(scope, input, true, plane_dim, units);
+            assign::expand(scope, res, out.into());
+        }
+        Plane::Ballot(op) => {
+            assert!(
+                plane_dim <= 128,
+                "A ballot holds 128 units, but the emulated plane has {plane_dim}"
+            );
+            let res = plane_emulated_ballot::expand(
+                scope,
+                ExpandElement::Plain(op.input).into(),
+                plane_dim,
+                units,
+            );
+            assign::expand(scope, res, out.into());
+        }
+        Plane::Elect => {
+            let res = plane_emulated_elect::expand(scope);
+            assign::expand(scope, res, out.into());
+        }
+    }
+}
@@ -147,10 +147,15 @@ pub struct DeviceId {
 }
 
 /// ID used to identify a Just-in-Time environment.
+///
+/// The ID also holds a hash of the hardware properties, the device name and the driver version, so
+/// that autotune results cached on disk for a device index aren't reused after the device is
+/// replaced by another one or its driver is updated.
 #[derive(Hash, PartialEq, Eq, Debug, Clone)]
 pub struct CubeTuneId {
     device: DeviceId,
     name: &'static str,
+    hardware: u64,
 }
 
 impl CubeTuneId {
@@ -159,9 +164,19 @@ impl CubeTuneId {
         client: &ComputeClient<R::Server, R::Channel>,
         device: &R::Device,
     ) -> Self {
+        // The hash is part of the cache path, so it must not change between runs. Two devices
+        // with the same limits can still tune differently, and so can the same device after a
+        // driver update, so both are part of the hash.
+        let properties = client.properties();
+        let mut hasher = fnv::FnvHasher::default();
+        properties.hardware_properties().hash(&mut hasher);
+        properties.device_name().hash(&mut hasher);
+        properties.driver_version().hash(&mut hasher);
+
         Self {
             device: R::device_id(device),
             name: R::name(client),
+            hardware: hasher.finish(),
         }
     }
 }
@@ -169,8 +184,8 @@ impl CubeTuneId {
 impl core::fmt::Display for CubeTuneId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
-            "device-{}-{}-{}",
-            self.device.type_id, self.device.index_id, self.name
+            "device-{}-{}-{}-{:016x}",
+            self.device.type_id, self.device.index_id, self.name, self.hardware
         ))
     }
 }
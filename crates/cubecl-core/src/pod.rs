@@ -1,4 +1,4 @@
-use cubecl_common::flex32;
+use cubecl_common::{e4m3, e5m2, flex32};
 
 use crate::ir::{Elem, FloatKind, IntKind, UIntKind};
 
@@ -298,3 +298,45 @@ impl CubeElement for flex32 {
         <flex32 as num_traits::Float>::min_value()
     }
 }
+
+impl CubeElement for e4m3 {
+    fn type_name() -> &'static str {
+        "e4m3"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_elem() -> Elem {
+        Elem::Float(FloatKind::E4M3)
+    }
+    fn maximum_value() -> Self {
+        e4m3::MAX
+    }
+    fn minimum_value() -> Self {
+        e4m3::MIN
+    }
+}
+
+impl CubeElement for e5m2 {
+    fn type_name() -> &'static str {
+        "e5m2"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_elem() -> Elem {
+        Elem::Float(FloatKind::E5M2)
+    }
+    fn maximum_value() -> Self {
+        e5m2::MAX
+    }
+    fn minimum_value() -> Self {
+        e5m2::MIN
+    }
+}
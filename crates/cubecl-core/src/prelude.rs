@@ -23,5 +23,5 @@ pub use cubecl_runtime::server::CubeCount;
 
 pub use crate::frontend::*;
 pub use crate::{comment, comptime, derive_cube_comptime, terminate};
-pub use cubecl_common::{CubeDim, ExecutionMode, flex32, tf32};
+pub use cubecl_common::{CubeDim, CubeDimError, ExecutionMode, e4m3, e5m2, flex32, tf32};
 pub use cubecl_ir::Scope;
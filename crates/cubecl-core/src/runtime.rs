@@ -24,7 +24,10 @@ pub trait Runtime: Send + Sync + 'static + core::fmt::Debug {
     /// Retrieve the compute client from the runtime device.
     fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel>;
 
-    /// The runtime name on the given device.
+    /// The runtime name on the given device, e.g. `cuda` or `wgpu<spirv>`.
+    ///
+    /// Combined with the [device name](cubecl_runtime::DeviceProperties::device_name) of the
+    /// client, it identifies where kernels run, e.g. for persistent caches.
     fn name(client: &ComputeClient<Self::Server, Self::Channel>) -> &'static str;
 
     /// Return true if global input array lengths should be added to kernel info.
@@ -52,6 +55,10 @@ pub trait Runtime: Send + Sync + 'static + core::fmt::Debug {
 pub enum Feature {
     /// The plane feature enables all basic warp/subgroup operations.
     Plane,
+    /// Plane operations are available, but emulated with shared memory on a device without
+    /// warps/subgroups. Emulated planes are the rows of units along x of the cube, and every unit
+    /// of the cube must reach a plane operation, like [sync_units](crate::prelude::sync_units).
+    PlaneEmulated,
     /// The cmma feature enables cooperative matrix-multiply and accumulate operations.
     Cmma {
         a: Elem,
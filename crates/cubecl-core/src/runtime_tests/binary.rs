@@ -128,6 +128,174 @@ test_binary_impl!(
     ]
 );
 
+macro_rules! test_binary_impl_int {
+    (
+        $test_name:ident,
+        $int_type:ident,
+        $binary_func:expr,
+        [$({
+            input_vectorization: $input_vectorization:expr,
+            out_vectorization: $out_vectorization:expr,
+            lhs: $lhs:expr,
+            rhs: $rhs:expr,
+            expected: $expected:expr
+        }),*]) => {
+        pub fn $test_name<R: Runtime, $int_type: Int + CubeElement>(client: ComputeClient<R::Server, R::Channel>) {
+            #[cube(launch_unchecked)]
+            fn test_function<$int_type: Int>(lhs: &Array<$int_type>, rhs: &Array<$int_type>, output: &mut Array<$int_type>) {
+                if ABSOLUTE_POS < rhs.len() {
+                    output[ABSOLUTE_POS] = $binary_func(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+                }
+            }
+
+            $(
+            {
+                let lhs = $lhs;
+                let rhs = $rhs;
+                let output_handle = client.empty($expected.len() * core::mem::size_of::<$int_type>());
+                let lhs_handle = client.create($int_type::as_bytes(lhs));
+                let rhs_handle = client.create($int_type::as_bytes(rhs));
+
+                unsafe {
+                    test_function::launch_unchecked::<$int_type, R>(
+                        &client,
+                        CubeCount::Static(1, 1, 1),
+                        CubeDim::new((lhs.len() / $input_vectorization as usize) as u32, 1, 1),
+                        ArrayArg::from_raw_parts::<$int_type>(&lhs_handle, lhs.len(), $input_vectorization),
+                        ArrayArg::from_raw_parts::<$int_type>(&rhs_handle, rhs.len(), $input_vectorization),
+                        ArrayArg::from_raw_parts::<$int_type>(&output_handle, $expected.len(), $out_vectorization),
+                    )
+                };
+
+                let actual = client.read_one(output_handle.binding());
+                let actual = $int_type::from_bytes(&actual);
+
+                assert_eq!(actual, $expected);
+            }
+            )*
+        }
+    };
+}
+
+test_binary_impl_int!(test_saturating_add, I, I::saturating_add, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        lhs: &[I::max_value(), I::min_value(), I::new(2), I::new(-3)],
+        rhs: as_type![I: 1, -1, 3, 1],
+        expected: &[I::max_value(), I::min_value(), I::new(5), I::new(-2)]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        lhs: &[I::max_value(), I::min_value(), I::new(2), I::new(-3)],
+        rhs: as_type![I: 1, -1, 3, 1],
+        expected: &[I::max_value(), I::min_value(), I::new(5), I::new(-2)]
+    }
+]);
+
+test_binary_impl_int!(test_saturating_sub, I, I::saturating_sub, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        lhs: &[I::min_value(), I::max_value(), I::new(5), I::new(-3)],
+        rhs: as_type![I: 1, -1, 3, 1],
+        expected: &[I::min_value(), I::max_value(), I::new(2), I::new(-4)]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        lhs: &[I::min_value(), I::max_value(), I::new(5), I::new(-3)],
+        rhs: as_type![I: 1, -1, 3, 1],
+        expected: &[I::min_value(), I::max_value(), I::new(2), I::new(-4)]
+    }
+]);
+
+test_binary_impl_int!(test_wrapping_add, I, I::wrapping_add, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        lhs: &[I::max_value(), I::min_value(), I::new(2), I::new(-3)],
+        rhs: as_type![I: 1, -1, 3, 1],
+        expected: &[I::min_value(), I::max_value(), I::new(5), I::new(-2)]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        lhs: &[I::max_value(), I::min_value(), I::new(2), I::new(-3)],
+        rhs: as_type![I: 1, -1, 3, 1],
+        expected: &[I::min_value(), I::max_value(), I::new(5), I::new(-2)]
+    }
+]);
+
+test_binary_impl_int!(test_wrapping_mul, I, I::wrapping_mul, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        lhs: &[I::max_value(), I::min_value(), I::new(3), I::new(-4)],
+        rhs: as_type![I: 2, 2, -2, 5],
+        expected: as_type![I: -2, 0, -6, -20]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        lhs: &[I::max_value(), I::min_value(), I::new(3), I::new(-4)],
+        rhs: as_type![I: 2, 2, -2, 5],
+        expected: as_type![I: -2, 0, -6, -20]
+    }
+]);
+
+test_binary_impl_int!(test_saturating_add_unsigned, I, I::saturating_add, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        lhs: &[I::max_value(), I::max_value(), I::new(2), I::new(0)],
+        rhs: as_type![I: 1, 0, 3, 0],
+        expected: &[I::max_value(), I::max_value(), I::new(5), I::new(0)]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        lhs: &[I::max_value(), I::max_value(), I::new(2), I::new(0)],
+        rhs: as_type![I: 1, 0, 3, 0],
+        expected: &[I::max_value(), I::max_value(), I::new(5), I::new(0)]
+    }
+]);
+
+test_binary_impl_int!(test_wrapping_add_unsigned, I, I::wrapping_add, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        lhs: &[I::max_value(), I::max_value(), I::new(2), I::new(0)],
+        rhs: as_type![I: 1, 3, 3, 0],
+        expected: as_type![I: 0, 2, 5, 0]
+    },
+    {
+        input_vectorization: 2,
+        out_vectorization: 2,
+        lhs: &[I::max_value(), I::max_value(), I::new(2), I::new(0)],
+        rhs: as_type![I: 1, 3, 3, 0],
+        expected: as_type![I: 0, 2, 5, 0]
+    }
+]);
+
+test_binary_impl_int!(test_saturating_sub_unsigned, I, I::saturating_sub, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        lhs: as_type![I: 0, 5, 3, 1],
+        rhs: as_type![I: 1, 3, 5, 0],
+        expected: as_type![I: 0, 2, 0, 1]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        lhs: as_type![I: 0, 5, 3, 1],
+        rhs: as_type![I: 1, 3, 5, 0],
+        expected: as_type![I: 0, 2, 0, 1]
+    }
+]);
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_binary {
@@ -151,3 +319,56 @@ macro_rules! testgen_binary {
         }
     };
 }
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_binary_int {
+    () => {
+        mod binary_int {
+            use super::*;
+
+            macro_rules! add_test {
+                ($test_name:ident) => {
+                    #[test]
+                    fn $test_name() {
+                        let client = TestRuntime::client(&Default::default());
+                        cubecl_core::runtime_tests::binary::$test_name::<TestRuntime, IntType>(
+                            client,
+                        );
+                    }
+                };
+            }
+
+            add_test!(test_saturating_add);
+            add_test!(test_saturating_sub);
+            add_test!(test_wrapping_add);
+            add_test!(test_wrapping_mul);
+        }
+    };
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_binary_uint {
+    () => {
+        mod binary_uint {
+            use super::*;
+
+            macro_rules! add_test {
+                ($test_name:ident) => {
+                    #[test]
+                    fn $test_name() {
+                        let client = TestRuntime::client(&Default::default());
+                        cubecl_core::runtime_tests::binary::$test_name::<TestRuntime, UintType>(
+                            client,
+                        );
+                    }
+                };
+            }
+
+            add_test!(test_saturating_add_unsigned);
+            add_test!(test_saturating_sub_unsigned);
+            add_test!(test_wrapping_add_unsigned);
+        }
+    };
+}
@@ -0,0 +1,360 @@
+use crate::{
+    self as cubecl, Feature,
+    ir::{Elem, FloatKind, IntKind, UIntKind},
+};
+
+use cubecl::prelude::*;
+use half::f16;
+
+/// Averages two lines of `u8` in `i32`, whose sum may not fit in 8 bits, and narrows the average
+/// back to `u8`.
+#[cube(launch_unchecked)]
+pub fn kernel_widen_add_narrow(
+    lhs: &Array<Line<u8>>,
+    rhs: &Array<Line<u8>>,
+    output: &mut Array<Line<u8>>,
+) {
+    if ABSOLUTE_POS < output.len() {
+        let sum =
+            Line::<i32>::cast_from(lhs[ABSOLUTE_POS]) + Line::<i32>::cast_from(rhs[ABSOLUTE_POS]);
+        let two = Line::empty(output.line_size()).fill(2i32);
+
+        output[ABSOLUTE_POS] = Line::cast_from(sum / two);
+    }
+}
+
+pub fn test_widen_add_narrow<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+    line_size: u8,
+) {
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::UInt(UIntKind::U8)))
+    {
+        println!("u8 not supported - skipped");
+        return;
+    }
+
+    let length = 64;
+    let lhs = (0..length)
+        .map(|i| (i * 37 % 256) as u8)
+        .collect::<Vec<_>>();
+    let rhs = (0..length)
+        .map(|i| 255 - (i * 11 % 256) as u8)
+        .collect::<Vec<_>>();
+    let lhs_handle = client.create(u8::as_bytes(&lhs));
+    let rhs_handle = client.create(u8::as_bytes(&rhs));
+    let output_handle = client.empty(length);
+    let num_lines = length / line_size as usize;
+
+    unsafe {
+        kernel_widen_add_narrow::launch_unchecked::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(num_lines as u32, 1, 1),
+            ArrayArg::from_raw_parts::<u8>(&lhs_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<u8>(&rhs_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<u8>(&output_handle, num_lines, line_size),
+        )
+    };
+
+    let actual = client.read_one(output_handle.binding());
+    let actual = u8::from_bytes(&actual);
+    let expected = lhs
+        .iter()
+        .zip(&rhs)
+        .map(|(&lhs, &rhs)| ((lhs as i32 + rhs as i32) / 2) as u8)
+        .collect::<Vec<_>>();
+
+    assert_eq!(&actual[..length], expected);
+}
+
+/// Subtracts two lines of `i8` in `i32` and narrows the difference back to `i8`, wrapping it when it
+/// overflows.
+#[cube(launch_unchecked)]
+pub fn kernel_widen_sub_narrow(
+    lhs: &Array<Line<i8>>,
+    rhs: &Array<Line<i8>>,
+    output: &mut Array<Line<i8>>,
+) {
+    if ABSOLUTE_POS < output.len() {
+        let diff =
+            Line::<i32>::cast_from(lhs[ABSOLUTE_POS]) - Line::<i32>::cast_from(rhs[ABSOLUTE_POS]);
+
+        output[ABSOLUTE_POS] = Line::cast_from(diff);
+    }
+}
+
+pub fn test_widen_sub_narrow<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+    line_size: u8,
+) {
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::Int(IntKind::I8)))
+    {
+        println!("i8 not supported - skipped");
+        return;
+    }
+
+    let length = 64;
+    let lhs = (0..length)
+        .map(|i| (i * 37 % 256) as u8 as i8)
+        .collect::<Vec<_>>();
+    let rhs = (0..length)
+        .map(|i| (127 - (i * 11 % 256) as i32) as i8)
+        .collect::<Vec<_>>();
+    let lhs_handle = client.create(i8::as_bytes(&lhs));
+    let rhs_handle = client.create(i8::as_bytes(&rhs));
+    let output_handle = client.empty(length);
+    let num_lines = length / line_size as usize;
+
+    unsafe {
+        kernel_widen_sub_narrow::launch_unchecked::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(num_lines as u32, 1, 1),
+            ArrayArg::from_raw_parts::<i8>(&lhs_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<i8>(&rhs_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<i8>(&output_handle, num_lines, line_size),
+        )
+    };
+
+    let actual = client.read_one(output_handle.binding());
+    let actual = i8::from_bytes(&actual);
+    let expected = lhs
+        .iter()
+        .zip(&rhs)
+        .map(|(&lhs, &rhs)| (lhs as i32 - rhs as i32) as i8)
+        .collect::<Vec<_>>();
+
+    assert_eq!(&actual[..length], expected);
+}
+
+/// Narrows lines of `f32` to the 8-bit float `F`, then widens them back to `f32` and to `f16`.
+#[cube(launch_unchecked)]
+pub fn kernel_fp8_round_trip<F: Float>(
+    input: &Array<Line<f32>>,
+    narrowed: &mut Array<Line<F>>,
+    widened: &mut Array<Line<f32>>,
+    widened_half: &mut Array<Line<f16>>,
+) {
+    if ABSOLUTE_POS < input.len() {
+        let value = Line::<F>::cast_from(input[ABSOLUTE_POS]);
+
+        narrowed[ABSOLUTE_POS] = value;
+        widened[ABSOLUTE_POS] = Line::cast_from(value);
+        widened_half[ABSOLUTE_POS] = Line::cast_from(value);
+    }
+}
+
+pub fn test_fp8_round_trip<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+    line_size: u8,
+) {
+    let properties = client.properties();
+    if !properties.feature_enabled(Feature::Type(F::as_elem_native_unchecked()))
+        || !properties.feature_enabled(Feature::Type(Elem::Float(FloatKind::F16)))
+    {
+        println!("{} not supported - skipped", F::as_elem_native_unchecked());
+        return;
+    }
+
+    // Exact values, values rounded to the nearest even one, subnormals and values saturated to
+    // the largest finite one.
+    let mut input = vec![
+        0.0, -0.0, 1.0, -2.5, 1.0625, 1.1875, 0.3, 448.0, 57344.0, 1e6, -1e6, 0.001, 3e-5,
+        -0.0078125, 17.0, 100.0,
+    ];
+    input.extend((0..48).map(|i| (i as f32 - 24.0) * 0.37));
+    let length = input.len();
+
+    let input_handle = client.create(f32::as_bytes(&input));
+    let narrowed_handle = client.empty(length * size_of::<F>());
+    let widened_handle = client.empty(length * size_of::<f32>());
+    let widened_half_handle = client.empty(length * size_of::<f16>());
+    let num_lines = length / line_size as usize;
+
+    unsafe {
+        kernel_fp8_round_trip::launch_unchecked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(num_lines as u32, 1, 1),
+            ArrayArg::from_raw_parts::<f32>(&input_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<F>(&narrowed_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<f32>(&widened_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<f16>(&widened_half_handle, num_lines, line_size),
+        )
+    };
+
+    // The conversions round to the nearest even value and saturate, like the host ones.
+    let expected = input.iter().map(|&value| F::new(value)).collect::<Vec<_>>();
+    let expected_f32 = expected
+        .iter()
+        .map(|value| value.to_f32().unwrap())
+        .collect::<Vec<_>>();
+    let expected_f16 = expected_f32
+        .iter()
+        .map(|&value| f16::from_f32(value))
+        .collect::<Vec<_>>();
+
+    let narrowed = client.read_one(narrowed_handle.binding());
+    assert_eq!(F::from_bytes(&narrowed), expected);
+    let widened = client.read_one(widened_handle.binding());
+    assert_eq!(f32::from_bytes(&widened), expected_f32);
+    let widened_half = client.read_one(widened_half_handle.binding());
+    assert_eq!(f16::from_bytes(&widened_half), expected_f16);
+}
+
+/// Multiplies and adds lines of the 8-bit float `F`, and selects the smallest of two lines, which
+/// is computed in `f32` and rounded back to `F`.
+#[cube(launch_unchecked)]
+pub fn kernel_fp8_arithmetic<F: Float>(
+    lhs: &Array<Line<F>>,
+    rhs: &Array<Line<F>>,
+    mul_add: &mut Array<Line<F>>,
+    min: &mut Array<Line<F>>,
+) {
+    if ABSOLUTE_POS < lhs.len() {
+        let a = lhs[ABSOLUTE_POS];
+        let b = rhs[ABSOLUTE_POS];
+
+        mul_add[ABSOLUTE_POS] = a * b + a;
+        min[ABSOLUTE_POS] = select_many(a.less_than(b), a, b);
+    }
+}
+
+pub fn test_fp8_arithmetic<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+    line_size: u8,
+) {
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(F::as_elem_native_unchecked()))
+    {
+        println!("{} not supported - skipped", F::as_elem_native_unchecked());
+        return;
+    }
+
+    let length = 64;
+    let lhs = (0..length)
+        .map(|i| F::new((i % 13) as f32 * 0.75 - 4.5))
+        .collect::<Vec<_>>();
+    let rhs = (0..length)
+        .map(|i| F::new((i % 7) as f32 * 1.5 - 5.0))
+        .collect::<Vec<_>>();
+
+    let lhs_handle = client.create(F::as_bytes(&lhs));
+    let rhs_handle = client.create(F::as_bytes(&rhs));
+    let mul_add_handle = client.empty(length * size_of::<F>());
+    let min_handle = client.empty(length * size_of::<F>());
+    let num_lines = length / line_size as usize;
+
+    unsafe {
+        kernel_fp8_arithmetic::launch_unchecked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(num_lines as u32, 1, 1),
+            ArrayArg::from_raw_parts::<F>(&lhs_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<F>(&rhs_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<F>(&mul_add_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<F>(&min_handle, num_lines, line_size),
+        )
+    };
+
+    // Every operation rounds its f32 result to the nearest 8-bit float.
+    let to_f32 = |value: &F| value.to_f32().unwrap();
+    let expected_mul_add = lhs
+        .iter()
+        .zip(&rhs)
+        .map(|(a, b)| F::new(to_f32(&F::new(to_f32(a) * to_f32(b))) + to_f32(a)))
+        .collect::<Vec<_>>();
+    let expected_min = lhs
+        .iter()
+        .zip(&rhs)
+        .map(|(a, b)| if to_f32(a) < to_f32(b) { *a } else { *b })
+        .collect::<Vec<_>>();
+
+    let mul_add = client.read_one(mul_add_handle.binding());
+    assert_eq!(F::from_bytes(&mul_add), expected_mul_add);
+    let min = client.read_one(min_handle.binding());
+    assert_eq!(F::from_bytes(&min), expected_min);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_cast {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_widen_add_narrow_scalar() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_widen_add_narrow::<TestRuntime>(client, 1);
+        }
+
+        #[test]
+        fn test_widen_add_narrow_vec2() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_widen_add_narrow::<TestRuntime>(client, 2);
+        }
+
+        #[test]
+        fn test_widen_add_narrow_vec4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_widen_add_narrow::<TestRuntime>(client, 4);
+        }
+
+        #[test]
+        fn test_widen_sub_narrow_scalar() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_widen_sub_narrow::<TestRuntime>(client, 1);
+        }
+
+        #[test]
+        fn test_widen_sub_narrow_vec4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_widen_sub_narrow::<TestRuntime>(client, 4);
+        }
+
+        #[test]
+        fn test_fp8_e4m3_round_trip_scalar() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_fp8_round_trip::<TestRuntime, cubecl_core::e4m3>(
+                client, 1,
+            );
+        }
+
+        #[test]
+        fn test_fp8_e4m3_round_trip_vec4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_fp8_round_trip::<TestRuntime, cubecl_core::e4m3>(
+                client, 4,
+            );
+        }
+
+        #[test]
+        fn test_fp8_e5m2_round_trip_vec4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_fp8_round_trip::<TestRuntime, cubecl_core::e5m2>(
+                client, 4,
+            );
+        }
+
+        #[test]
+        fn test_fp8_e4m3_arithmetic_scalar() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_fp8_arithmetic::<TestRuntime, cubecl_core::e4m3>(
+                client, 1,
+            );
+        }
+
+        #[test]
+        fn test_fp8_e5m2_arithmetic_vec4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_fp8_arithmetic::<TestRuntime, cubecl_core::e5m2>(
+                client, 4,
+            );
+        }
+    };
+}
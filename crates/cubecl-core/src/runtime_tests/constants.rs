@@ -29,6 +29,35 @@ pub fn test_constant_array<R: Runtime>(client: ComputeClient<R::Server, R::Chann
     assert_eq!(actual[0], 5.0);
 }
 
+#[cube(launch)]
+fn infinity_kernel<F: Float>(out: &mut Array<F>) {
+    if UNIT_POS == 0 {
+        out[0] = F::infinity();
+        out[1] = F::neg_infinity();
+        out[2] = F::min_value();
+        out[3] = F::max_value();
+    }
+}
+
+pub fn test_infinity<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[0.0; 4]));
+
+    infinity_kernel::launch::<f32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<f32>(&handle, 4, 1) },
+    );
+
+    let actual = client.read_one(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(
+        actual,
+        &[f32::INFINITY, f32::NEG_INFINITY, f32::MIN, f32::MAX]
+    );
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_constants {
@@ -40,5 +69,11 @@ macro_rules! testgen_constants {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::constants::test_constant_array::<TestRuntime>(client);
         }
+
+        #[test]
+        fn test_infinity() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::constants::test_infinity::<TestRuntime>(client);
+        }
     };
 }
@@ -0,0 +1,89 @@
+use crate::{
+    self as cubecl, Feature,
+    ir::{Elem, UIntKind},
+};
+
+use cubecl::prelude::*;
+
+/// Counts the `u16` values of the input in bins of 4096 values, reading lines of `line_size`.
+#[cube(launch_unchecked)]
+pub fn kernel_histogram_u16(
+    input: &Array<Line<u16>>,
+    bins: &mut Array<Atomic<u32>>,
+    #[comptime] line_size: u32,
+) {
+    if ABSOLUTE_POS < input.len() {
+        let line = input[ABSOLUTE_POS];
+
+        #[unroll]
+        for i in 0..line_size {
+            let bin = u32::cast_from(line[i]) / 4096;
+            Atomic::add(&bins[bin], 1u32);
+        }
+    }
+}
+
+pub fn test_histogram_u16<R: Runtime>(client: ComputeClient<R::Server, R::Channel>, line_size: u8) {
+    let properties = client.properties();
+    if !properties.feature_enabled(Feature::Type(Elem::UInt(UIntKind::U16)))
+        || !properties.feature_enabled(Feature::Type(Elem::AtomicUInt(UIntKind::U32)))
+    {
+        println!("u16 histogram not supported - skipped");
+        return;
+    }
+
+    let length = 256;
+    // Spread over the whole range, so that the top 4 bits of each value pick its bin.
+    let input = (0..length)
+        .map(|i| (i * 7919 % 65536) as u16)
+        .collect::<Vec<_>>();
+    let input_handle = client.create(u16::as_bytes(&input));
+    let bins_handle = client.create(u32::as_bytes(&[0; 16]));
+    let num_lines = length / line_size as usize;
+
+    unsafe {
+        kernel_histogram_u16::launch_unchecked::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(num_lines as u32, 1, 1),
+            ArrayArg::from_raw_parts::<u16>(&input_handle, num_lines, line_size),
+            ArrayArg::from_raw_parts::<u32>(&bins_handle, 16, 1),
+            line_size as u32,
+        )
+    };
+
+    let actual = client.read_one(bins_handle.binding());
+    let actual = u32::from_bytes(&actual);
+    let mut expected = [0u32; 16];
+    for value in input {
+        expected[value as usize / 4096] += 1;
+    }
+
+    assert_eq!(actual, expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_histogram {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_histogram_u16_scalar() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::histogram::test_histogram_u16::<TestRuntime>(client, 1);
+        }
+
+        #[test]
+        fn test_histogram_u16_vec2() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::histogram::test_histogram_u16::<TestRuntime>(client, 2);
+        }
+
+        #[test]
+        fn test_histogram_u16_vec4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::histogram::test_histogram_u16::<TestRuntime>(client, 4);
+        }
+    };
+}
@@ -1,4 +1,7 @@
-use crate::{self as cubecl, as_type};
+use crate::{
+    self as cubecl, Feature, as_type,
+    ir::{Elem, UIntKind},
+};
 
 use cubecl::prelude::*;
 
@@ -41,6 +44,48 @@ pub fn test_kernel_index_scalar<R: Runtime, F: Float + CubeElement>(
     assert_eq!(actual[2], F::new(123.0));
 }
 
+#[cube(launch_unchecked)]
+pub fn kernel_assign_u64_index(output: &mut Array<u8>, index: u64) {
+    if UNIT_POS == 0 {
+        unsafe { output.index_assign_unchecked_u64(index, u8::new(7)) };
+    }
+}
+
+/// Writes the last element of an array longer than `u32::MAX` elements, skipped when the device
+/// doesn't have the memory for it, or when its size is unknown.
+pub fn test_kernel_index_u64<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let tail = 256;
+    let length = (u32::MAX as usize + 1) + tail;
+    let properties = client.properties();
+    let memory = properties.memory_properties();
+    // Leave half of the memory to the other tests running on the device. The buffer must also be
+    // allocated in a single page.
+    let enough_memory = memory
+        .total_memory
+        .is_some_and(|total| total / 2 >= length as u64)
+        && memory.max_page_size >= length as u64;
+    if !properties.feature_enabled(Feature::Type(Elem::UInt(UIntKind::U64))) || !enough_memory {
+        println!("{length} elements buffer not supported - skipped");
+        return;
+    }
+
+    let handle = client.empty(length);
+
+    unsafe {
+        kernel_assign_u64_index::launch_unchecked::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_single(),
+            ArrayArg::from_raw_parts::<u8>(&handle, length, 1),
+            ScalarArg::new(length as u64 - 1),
+        )
+    };
+
+    let actual = client.read_one(handle.offset_start((length - tail) as u64).binding());
+
+    assert_eq!(actual[tail - 1], 7);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_index {
@@ -56,3 +101,17 @@ macro_rules! testgen_index {
         }
     };
 }
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_index_u64 {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_assign_index_u64() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::index::test_kernel_index_u64::<TestRuntime>(client);
+        }
+    };
+}
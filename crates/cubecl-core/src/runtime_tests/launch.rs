@@ -19,7 +19,7 @@ pub fn kernel_with_comptime_tag(output: &mut ComptimeTag) {
     }
 }
 
-#[cube(launch)]
+#[cube(launch, create_dummy_kernel)]
 pub fn kernel_with_generics<F: Float>(output: &mut Array<F>) {
     if UNIT_POS == 0 {
         output[0] = F::new(5.0);
@@ -83,6 +83,49 @@ pub fn test_kernel_with_generics<R: Runtime, F: Float + CubeElement>(
     assert_eq!(actual[0], F::new(5.0));
 }
 
+/// The f16 and f32 instantiations of the same kernel are distinct kernels, so that neither the
+/// compilation cache nor the autotune results of one are reused for the other.
+pub fn test_kernel_id_per_elem<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.empty(2 * core::mem::size_of::<f32>());
+    let create = || {
+        (
+            kernel_with_generics::create_dummy_kernel::<half::f16, R>(
+                CubeCount::Static(1, 1, 1),
+                CubeDim::default(),
+                unsafe { ArrayArg::from_raw_parts::<half::f16>(&handle, 2, 1) },
+            ),
+            kernel_with_generics::create_dummy_kernel::<f32, R>(
+                CubeCount::Static(1, 1, 1),
+                CubeDim::default(),
+                unsafe { ArrayArg::from_raw_parts::<f32>(&handle, 2, 1) },
+            ),
+        )
+    };
+    let (kernel_f16, kernel_f32) = create();
+
+    assert_ne!(kernel_f16.id(), kernel_f32.id());
+    assert_ne!(
+        kernel_f16.id().stable_format(),
+        kernel_f32.id().stable_format()
+    );
+
+    let (definition_f16, definition_f32) = (kernel_f16.define(), kernel_f32.define());
+    assert_eq!(
+        definition_f16.options.kernel_name,
+        "kernel_with_generics_f16"
+    );
+    assert_eq!(
+        definition_f32.options.kernel_name,
+        "kernel_with_generics_f32"
+    );
+    assert_eq!(definition_f16.outputs[0].item.elem.to_string(), "f16");
+    assert_eq!(definition_f32.outputs[0].item.elem.to_string(), "f32");
+
+    // Creating the same instantiation again gives the same kernel.
+    let (kernel_f16_again, _) = create();
+    assert_eq!(kernel_f16.id(), kernel_f16_again.id());
+}
+
 pub fn test_kernel_without_generics<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
     let handle = client.create(f32::as_bytes(&[0.0, 1.0]));
 
@@ -113,6 +156,12 @@ macro_rules! testgen_launch {
             );
         }
 
+        #[test]
+        fn test_launch_kernel_id_per_elem() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_id_per_elem::<TestRuntime>(client);
+        }
+
         #[test]
         fn test_launch_without_generics() {
             let client = TestRuntime::client(&Default::default());
@@ -3,12 +3,14 @@ pub mod atomic;
 pub mod barrier;
 pub mod binary;
 pub mod branch;
+pub mod cast;
 pub mod cmma;
 pub mod const_match;
 pub mod constants;
 pub mod debug;
 pub mod different_rank;
 pub mod enums;
+pub mod histogram;
 pub mod index;
 pub mod launch;
 pub mod line;
@@ -99,6 +101,7 @@ macro_rules! testgen_float {
 macro_rules! testgen_int {
     () => {
         cubecl_core::testgen_unary_int!();
+        cubecl_core::testgen_binary_int!();
         cubecl_core::testgen_atomic_int!();
     };
 }
@@ -107,6 +110,7 @@ macro_rules! testgen_int {
 #[macro_export]
 macro_rules! testgen_uint {
     () => {
+        cubecl_core::testgen_binary_uint!();
         cubecl_core::testgen_const_match!();
     };
 }
@@ -115,6 +119,7 @@ macro_rules! testgen_uint {
 #[macro_export]
 macro_rules! testgen_untyped {
     () => {
+        cubecl_core::testgen_cast!();
         cubecl_core::testgen_cmma!();
         cubecl_core::testgen_metadata!();
         cubecl_core::testgen_topology!();
@@ -122,6 +127,8 @@ macro_rules! testgen_untyped {
         cubecl_core::testgen_constants!();
         cubecl_core::testgen_tensor_indexing!();
         cubecl_core::testgen_debug!();
+        cubecl_core::testgen_histogram!();
+        cubecl_core::testgen_index_u64!();
 
         cubecl_core::testgen_enums!();
     };
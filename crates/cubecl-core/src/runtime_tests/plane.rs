@@ -108,6 +108,16 @@ pub fn kernel_broadcast<F: Float>(output: &mut Tensor<F>) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_sum_u32(output: &mut Tensor<u32>) {
+    let val = output[UNIT_POS];
+    let val2 = subcube_sum(val);
+
+    if UNIT_POS == 0 {
+        output[0] = val2;
+    }
+}
+
 #[cube(launch)]
 pub fn kernel_ballot(output: &mut Tensor<Line<u32>>) {
     let val2 = plane_ballot(UNIT_POS < 8);
@@ -535,7 +545,7 @@ pub fn test_plane_any<
 pub fn test_plane_ballot<TestRuntime: Runtime>(
     client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
 ) {
-    if !client.properties().feature_enabled(Feature::Plane) {
+    if !supports_planes(&client) {
         // Can't execute the test.
         return;
     }
@@ -558,6 +568,34 @@ pub fn test_plane_ballot<TestRuntime: Runtime>(
     assert_eq!(u32::from_bytes(&actual), &expected);
 }
 
+pub fn test_plane_sum_u32<TestRuntime: Runtime>(
+    client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
+) {
+    if !supports_planes(&client) {
+        // Can't execute the test.
+        return;
+    }
+
+    // Neither the values nor their sum are representable as f32.
+    let plane_size = 32;
+    let input = vec![(1u32 << 24) + 1; plane_size as usize];
+    let handle = client.create(u32::as_bytes(&input));
+    let (shape, strides) = ([input.len()], [1]);
+
+    unsafe {
+        kernel_sum_u32::launch::<TestRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(plane_size, 1, 1),
+            TensorArg::from_raw_parts::<u32>(&handle, &strides, &shape, 1),
+        );
+    }
+
+    let actual = client.read_one(handle.binding());
+
+    assert_eq!(u32::from_bytes(&actual)[0], plane_size * ((1 << 24) + 1));
+}
+
 pub fn test_plane_elect<
     TestRuntime: Runtime,
     F: Float + num_traits::Float + CubeElement + Display,
@@ -625,6 +663,12 @@ pub fn test_plane_broadcast<
     );
 }
 
+/// Whether plane operations are available, natively or emulated with shared memory.
+fn supports_planes<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> bool {
+    let properties = client.properties();
+    properties.feature_enabled(Feature::Plane) || properties.feature_enabled(Feature::PlaneEmulated)
+}
+
 fn test_plane_operation<
     TestRuntime: Runtime,
     F: Float + num_traits::Float + CubeElement + Display,
@@ -638,7 +682,7 @@ fn test_plane_operation<
 ) where
     Launch: Fn(CubeCount, TensorArg<'_, TestRuntime>),
 {
-    if !client.properties().feature_enabled(Feature::Plane) {
+    if !supports_planes(&client) {
         // Can't execute the test.
         return;
     }
@@ -905,6 +949,12 @@ macro_rules! testgen_plane {
             impl_test_plane_broadcast(4);
         }
 
+        #[test]
+        fn test_plane_sum_u32() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::plane::test_plane_sum_u32::<TestRuntime>(client.clone());
+        }
+
         #[test]
         fn test_plane_ballot() {
             let client = TestRuntime::client(&Default::default());
@@ -46,6 +46,38 @@ pub fn test_tensor_coordinate<R: Runtime>(client: ComputeClient<R::Server, R::Ch
     }
 }
 
+#[cube(launch)]
+pub fn tensor_copy(input: &Tensor<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS];
+    }
+}
+
+pub fn test_tensor_offset<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    // Large enough for the offset in bytes to be aligned on every backend.
+    let offset = 64;
+    let len = 8;
+
+    let data = (0..offset + len).map(|i| i as f32).collect::<Vec<_>>();
+    let input = client.create(f32::as_bytes(&data));
+    let output = client.empty(core::mem::size_of::<f32>() * len);
+
+    unsafe {
+        tensor_copy::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(len as u32, 1, 1),
+            TensorArg::from_raw_parts_with_offset::<f32>(&input, &[1], &[len], 1, offset),
+            ArrayArg::from_raw_parts::<f32>(&output, len, 1),
+        )
+    };
+
+    let actual = client.read_one(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &data[offset..]);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_tensor_indexing {
@@ -57,5 +89,11 @@ macro_rules! testgen_tensor_indexing {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::tensor::test_tensor_coordinate::<TestRuntime>(client);
         }
+
+        #[test]
+        fn test_tensor_offset() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::tensor::test_tensor_offset::<TestRuntime>(client);
+        }
     };
 }
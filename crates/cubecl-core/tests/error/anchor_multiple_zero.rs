@@ -0,0 +1,10 @@
+use cubecl::AutotuneKey;
+use cubecl_core as cubecl;
+
+#[derive(AutotuneKey)]
+struct Key {
+    #[autotune(anchor(multiple = 0))]
+    size: usize,
+}
+
+fn main() {}
@@ -1,3 +1,5 @@
+use cubecl_core::ir::FloatKind;
+
 use crate::shared::{Dialect, WmmaCompiler};
 
 use super::{arch::CudaArchitecture, mma::CudaWmmaCompiler};
@@ -62,6 +64,9 @@ impl Dialect for CudaDialect {
     fn include_bf16(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("#include <cuda_bf16.h>\n")
     }
+    fn include_fp8(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <cuda_fp8.h>\n")
+    }
     fn include_runtime(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("#include <cuda_runtime.h>\n")
     }
@@ -72,6 +77,13 @@ impl Dialect for CudaDialect {
     fn bfloat162_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("__nv_bfloat162")
     }
+    fn fp8_type_name(kind: FloatKind) -> Option<&'static str> {
+        match kind {
+            FloatKind::E4M3 => Some("__nv_fp8_e4m3"),
+            FloatKind::E5M2 => Some("__nv_fp8_e5m2"),
+            _ => None,
+        }
+    }
     fn warp_shuffle(var: &str, source: &str) -> String {
         format!("__shfl_sync(-1, {var}, {source})")
     }
@@ -93,4 +105,7 @@ impl Dialect for CudaDialect {
     fn warp_ballot(out: &str) -> String {
         format!("__ballot_sync(-1, {out})")
     }
+    fn trap(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("__trap();\n")
+    }
 }
@@ -86,6 +86,9 @@ impl WmmaCompiler<CudaDialect> for CudaWmmaCompiler {
                 })
                 .collect();
             result.extend(combinations);
+        }
+        // Tensor cores multiply tf32 from Ampere onwards.
+        if arch.version >= 80 {
             result.push((
                 gpu::Elem::Float(gpu::FloatKind::TF32),
                 gpu::Elem::Float(gpu::FloatKind::TF32),
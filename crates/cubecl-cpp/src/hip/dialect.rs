@@ -1,5 +1,7 @@
 use std::marker::PhantomData;
 
+use cubecl_core::ir::FloatKind;
+
 use crate::shared::{Dialect, WmmaCompiler};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
@@ -65,6 +67,9 @@ impl<M: WmmaCompiler<Self>> Dialect for HipDialect<M> {
         // "hip_bf16.h" triggers redefinition errors during compilation
         f.write_str("#include <hip/hip_bfloat16.h>\n")
     }
+    fn include_fp8(_f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        unreachable!("8-bit floats are rejected when compiling the kernel")
+    }
     fn include_runtime(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("#include <hip/hip_runtime.h>\n")
     }
@@ -75,6 +80,10 @@ impl<M: WmmaCompiler<Self>> Dialect for HipDialect<M> {
         // "hip_bfloat16.h" has no "hip_bfloat162" type
         f.write_str("hip_bfloat16")
     }
+    fn fp8_type_name(_kind: FloatKind) -> Option<&'static str> {
+        // Some architectures only have the FNUZ variants of "hip_fp8.h", encoded differently
+        None
+    }
     fn warp_shuffle(var: &str, source: &str) -> String {
         format!("__shfl({var}, {source})")
     }
@@ -96,4 +105,7 @@ impl<M: WmmaCompiler<Self>> Dialect for HipDialect<M> {
     fn warp_ballot(out: &str) -> String {
         format!("__ballot({out})")
     }
+    fn trap(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("abort();\n")
+    }
 }
@@ -5,7 +5,7 @@ use cubecl_common::ExecutionMode;
 use cubecl_core::ir::VariableKind;
 use cubecl_core::{
     Compiler, Feature,
-    ir::{self as gpu},
+    ir::{self as gpu, OperationReflect},
 };
 use cubecl_core::{
     ir::{Operation, SourceLoc},
@@ -30,10 +30,13 @@ pub trait Dialect:
     // includes
     fn include_f16(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn include_bf16(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    fn include_fp8(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn include_runtime(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     // types
     fn bfloat16_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn bfloat162_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    /// Name of the `E4M3` or `E5M2` 8-bit float type, `None` when the dialect doesn't support it.
+    fn fp8_type_name(kind: gpu::FloatKind) -> Option<&'static str>;
     // warp instructions (all threads participating)
     fn warp_shuffle(var: &str, source: &str) -> String;
     fn warp_shuffle_xor(var: &str, offset: &str) -> String;
@@ -42,16 +45,29 @@ pub trait Dialect:
     fn warp_all(var: &str) -> String;
     fn warp_any(var: &str) -> String;
     fn warp_ballot(var: &str) -> String;
+    // debugging
+    fn trap(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
 }
 
 #[derive(Clone, Debug)]
 pub struct CompilationOptions {
     pub warp_size: u32,
+    /// Check every global and shared memory access against the length of the accessed memory,
+    /// printing the faulty index and aborting the kernel on out of bounds accesses.
+    ///
+    /// This is meant for debugging indexing bugs and is slow, so it is disabled by default.
+    ///
+    /// Only the CUDA and HIP runtimes support it. WGSL and SPIR-V have no way to abort a kernel,
+    /// so the wgpu runtime doesn't expose the flag.
+    pub bounds_checks: bool,
 }
 
 impl Default for CompilationOptions {
     fn default() -> Self {
-        Self { warp_size: 32 }
+        Self {
+            warp_size: 32,
+            bounds_checks: false,
+        }
     }
 }
 
@@ -70,10 +86,12 @@ pub struct CppCompiler<D: Dialect> {
     barrier: bool,
     bf16: bool,
     f16: bool,
+    fp8: bool,
     printf: bool,
     num_inputs: usize,
     num_outputs: usize,
     ext_meta_positions: Vec<u32>,
+    binding_sizes: Vec<Option<usize>>,
     items: HashSet<Item<D>>,
     strategy: ExecutionMode,
     settings: VariableSettings,
@@ -143,6 +161,10 @@ impl<D: Dialect> CppCompiler<D> {
             .options
             .fp_math_mode
             .contains(FastMath::ReducedPrecision);
+        let flush_denormals = value
+            .options
+            .fp_math_mode
+            .contains(FastMath::FlushDenormals);
 
         ComputeKernel {
             inputs,
@@ -155,7 +177,9 @@ impl<D: Dialect> CppCompiler<D> {
             barrier: self.barrier,
             bf16: self.bf16,
             f16: self.f16,
+            fp8: self.fp8,
             fast_math,
+            flush_denormals,
             items: self.items,
             kernel_name: value.options.kernel_name,
         }
@@ -168,6 +192,7 @@ impl<D: Dialect> CppCompiler<D> {
         let mut num_ext = 0;
 
         for binding in value.inputs.iter().chain(value.outputs.iter()) {
+            self.binding_sizes.push(binding.size);
             self.ext_meta_positions.push(num_ext);
             if binding.has_extended_meta {
                 num_ext += 1;
@@ -240,7 +265,27 @@ impl<D: Dialect> CppCompiler<D> {
                     out: self.compile_variable(out.unwrap()),
                 }));
             }
+            gpu::Operation::Arithmetic(op) if has_fp8(op.args(), out) => {
+                let out = out.unwrap();
+                let args = self.widen_fp8_args(op.args().unwrap(), instructions, scope);
+                let op = gpu::Arithmetic::from_code_and_args(op.op_code(), &args).unwrap();
+                let wide = self.widen_fp8_out(out, instructions, scope);
+
+                self.compile_arithmetic(op, Some(wide), instructions);
+                if wide != out {
+                    instructions.push(Instruction::Assign(UnaryInstruction {
+                        input: self.compile_variable(wide),
+                        out: self.compile_variable(out),
+                    }));
+                }
+            }
             gpu::Operation::Arithmetic(op) => self.compile_arithmetic(op, out, instructions),
+            gpu::Operation::Comparison(op) if has_fp8(op.args(), None) => {
+                let args = self.widen_fp8_args(op.args().unwrap(), instructions, scope);
+                let op = gpu::Comparison::from_code_and_args(op.op_code(), &args).unwrap();
+
+                self.compile_comparison(op, out, instructions);
+            }
             gpu::Operation::Comparison(op) => self.compile_comparison(op, out, instructions),
             gpu::Operation::Bitwise(op) => self.compile_bitwise(op, out, instructions),
             gpu::Operation::Operator(op) => self.compile_operator(op, out, instructions, scope),
@@ -504,6 +549,62 @@ impl<D: Dialect> CppCompiler<D> {
         }
     }
 
+    /// Traps when `index` is out of the bounds of `var`, if bounds checks are enabled and `var` is
+    /// global or shared memory. The length comes from the binding size when it is known at compile
+    /// time, and from the metadata otherwise.
+    fn compile_bounds_check(
+        &mut self,
+        var: gpu::Variable,
+        index: gpu::Variable,
+        scope: &mut gpu::Scope,
+        instructions: &mut Vec<Instruction<D>>,
+    ) {
+        if !self.compilation_options.bounds_checks {
+            return;
+        }
+
+        let binding_size = match var.kind {
+            gpu::VariableKind::GlobalInputArray(id) => self.binding_sizes[id as usize],
+            gpu::VariableKind::GlobalOutputArray(id) => {
+                self.binding_sizes[self.num_inputs + id as usize]
+            }
+            gpu::VariableKind::SharedMemory { length, .. } => Some(length as usize),
+            _ => None,
+        };
+
+        // Lengths in the metadata are saturated at `u32::MAX`, so they can't bound 64-bit indices.
+        let is_u64 = index.item.elem == gpu::Elem::UInt(gpu::UIntKind::U64);
+
+        let len = match binding_size {
+            Some(size) => {
+                let kind = match is_u64 {
+                    true => gpu::UIntKind::U64,
+                    false => gpu::UIntKind::U32,
+                };
+                gpu::Variable::constant(gpu::ConstantScalarValue::UInt(size as u64, kind))
+            }
+            None if is_u64 => return,
+            None if var.has_length() => {
+                let len = *scope.create_local(gpu::Item::new(gpu::Elem::UInt(gpu::UIntKind::U32)));
+                instructions.extend(self.compile_scope(scope));
+
+                let length = match var.has_buffer_length() {
+                    true => gpu::Metadata::BufferLength { var },
+                    false => gpu::Metadata::Length { var },
+                };
+                instructions.push(self.compile_metadata(length, Some(len)));
+                len
+            }
+            None => return,
+        };
+
+        self.printf = true;
+        instructions.push(Instruction::BoundsCheck {
+            index: self.compile_variable(index),
+            len: self.compile_variable(len),
+        });
+    }
+
     fn compile_metadata(
         &mut self,
         metadata: gpu::Metadata,
@@ -784,6 +885,18 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Arithmetic::Dot(op) => {
                 instructions.push(Instruction::Dot(self.compile_binary(op, out)))
             }
+            gpu::Arithmetic::SaturatingAdd(op) => {
+                instructions.push(Instruction::SaturatingAdd(self.compile_binary(op, out)))
+            }
+            gpu::Arithmetic::SaturatingSub(op) => {
+                instructions.push(Instruction::SaturatingSub(self.compile_binary(op, out)))
+            }
+            gpu::Arithmetic::WrappingAdd(op) => {
+                instructions.push(Instruction::WrappingAdd(self.compile_binary(op, out)))
+            }
+            gpu::Arithmetic::WrappingMul(op) => {
+                instructions.push(Instruction::WrappingMul(self.compile_binary(op, out)))
+            }
         };
     }
 
@@ -896,6 +1009,7 @@ impl<D: Dialect> CppCompiler<D> {
                 }
             }
             gpu::Operator::Index(op) => {
+                self.compile_bounds_check(op.lhs, op.rhs, scope, instructions);
                 if matches!(self.strategy, ExecutionMode::Checked) && op.lhs.has_length() {
                     let lhs = op.lhs;
                     let rhs = op.rhs;
@@ -921,9 +1035,11 @@ impl<D: Dialect> CppCompiler<D> {
                 }
             }
             gpu::Operator::UncheckedIndex(op) => {
+                self.compile_bounds_check(op.lhs, op.rhs, scope, instructions);
                 instructions.push(Instruction::Index(self.compile_binary(op, out)))
             }
             gpu::Operator::IndexAssign(op) => {
+                self.compile_bounds_check(out, op.lhs, scope, instructions);
                 if let ExecutionMode::Checked = self.strategy {
                     if out.has_length() {
                         expand_checked_index_assign(scope, op.lhs, op.rhs, out);
@@ -934,6 +1050,7 @@ impl<D: Dialect> CppCompiler<D> {
                 instructions.push(Instruction::IndexAssign(self.compile_binary(op, out)));
             }
             gpu::Operator::UncheckedIndexAssign(op) => {
+                self.compile_bounds_check(out, op.lhs, scope, instructions);
                 instructions.push(Instruction::IndexAssign(self.compile_binary(op, out)))
             }
             gpu::Operator::And(op) => {
@@ -988,6 +1105,49 @@ impl<D: Dialect> CppCompiler<D> {
         };
     }
 
+    /// Converts the 8-bit float arguments of an operation to `f32`, since the 8-bit float types
+    /// of CUDA only have conversions.
+    fn widen_fp8_args(
+        &mut self,
+        args: Vec<gpu::Variable>,
+        instructions: &mut Vec<Instruction<D>>,
+        scope: &mut gpu::Scope,
+    ) -> Vec<gpu::Variable> {
+        args.into_iter()
+            .map(|arg| {
+                if !is_fp8(arg.item.elem) {
+                    return arg;
+                }
+
+                let wide = self.widen_fp8_out(arg, instructions, scope);
+                instructions.push(Instruction::Assign(UnaryInstruction {
+                    input: self.compile_variable(arg),
+                    out: self.compile_variable(wide),
+                }));
+                wide
+            })
+            .collect()
+    }
+
+    /// Declares the `f32` variable an operation writes to instead of an 8-bit float output.
+    fn widen_fp8_out(
+        &mut self,
+        out: gpu::Variable,
+        instructions: &mut Vec<Instruction<D>>,
+        scope: &mut gpu::Scope,
+    ) -> gpu::Variable {
+        if !is_fp8(out.item.elem) {
+            return out;
+        }
+
+        let elem = gpu::Elem::Float(gpu::FloatKind::F32);
+        let wide = *scope.create_local_restricted(gpu::Item { elem, ..out.item });
+        instructions.push(Instruction::DeclareVariable {
+            var: self.compile_variable(wide),
+        });
+        wide
+    }
+
     fn compile_binary(
         &mut self,
         value: gpu::BinaryOperator,
@@ -1223,6 +1383,16 @@ impl<D: Dialect> CppCompiler<D> {
                     self.bf16 = true;
                     Elem::BF16
                 }
+                gpu::FloatKind::E4M3 | gpu::FloatKind::E5M2 => {
+                    if D::fp8_type_name(kind).is_none() {
+                        panic!("{kind:?} isn't supported by this backend");
+                    }
+                    self.fp8 = true;
+                    match kind {
+                        gpu::FloatKind::E4M3 => Elem::E4M3,
+                        _ => Elem::E5M2,
+                    }
+                }
                 gpu::FloatKind::TF32 => Elem::TF32,
                 gpu::FloatKind::Flex32 => Elem::F32,
                 gpu::FloatKind::F32 => Elem::F32,
@@ -1262,6 +1432,21 @@ impl<D: Dialect> CppCompiler<D> {
     }
 }
 
+fn is_fp8(elem: gpu::Elem) -> bool {
+    matches!(
+        elem,
+        gpu::Elem::Float(gpu::FloatKind::E4M3 | gpu::FloatKind::E5M2)
+    )
+}
+
+/// Whether an operation reads or writes 8-bit floats, which are computed in `f32`.
+fn has_fp8(args: Option<Vec<gpu::Variable>>, out: Option<gpu::Variable>) -> bool {
+    args.into_iter()
+        .flatten()
+        .chain(out)
+        .any(|var| is_fp8(var.item.elem))
+}
+
 pub fn register_supported_types(props: &mut DeviceProperties<Feature>) {
     let supported_types = [
         gpu::Elem::UInt(gpu::UIntKind::U8),
@@ -1290,3 +1475,55 @@ pub fn register_supported_types(props: &mut DeviceProperties<Feature>) {
         props.register_feature(Feature::Type(ty));
     }
 }
+
+#[cfg(all(test, feature = "cuda"))]
+mod tests {
+    use cubecl_common::ExecutionMode;
+    use cubecl_core as cubecl;
+    use cubecl_core::{Compiler, ir as gpu, prelude::*};
+
+    use super::CompilationOptions;
+    use crate::CudaCompiler;
+
+    #[cube]
+    fn shifted_copy(input: &Array<f32>, output: &mut Array<f32>) {
+        output[UNIT_POS] = input[UNIT_POS + 1];
+    }
+
+    fn compile_shifted_copy(bounds_checks: bool) -> String {
+        let mut builder = KernelBuilder::default();
+        let item = gpu::Item::new(f32::as_elem_native_unchecked());
+        let input = builder.input_array(item);
+        let output = builder.output_array(item);
+        shifted_copy::expand(&mut builder.context, input.into(), output.into());
+
+        let options = CompilationOptions {
+            bounds_checks,
+            ..Default::default()
+        };
+        CudaCompiler::default()
+            .compile(
+                builder.build(KernelSettings::default()),
+                &options,
+                ExecutionMode::Unchecked,
+            )
+            .to_string()
+    }
+
+    #[test]
+    fn bounds_checks_trap_on_out_of_bounds_accesses() {
+        let source = compile_shifted_copy(true);
+
+        // One check for the read of the input and one for the write of the output.
+        assert_eq!(source.matches("Out of bounds access").count(), 2);
+        assert_eq!(source.matches("__trap();").count(), 2);
+    }
+
+    #[test]
+    fn bounds_checks_disabled_by_default() {
+        let source = compile_shifted_copy(false);
+
+        assert!(!source.contains("Out of bounds access"));
+        assert!(!source.contains("__trap();"));
+    }
+}
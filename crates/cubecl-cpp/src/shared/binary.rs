@@ -202,6 +202,135 @@ impl<D: Dialect> Binary<D> for Min {
     }
 }
 
+/// Integer addition clamped to the bounds of the type.
+///
+/// There is no saturating instruction, so the sum is computed with wrapping arithmetic and
+/// overflow is detected from it: an unsigned sum that wrapped is smaller than the operands, and a
+/// signed sum that overflowed has a sign that differs from both operands.
+pub struct SaturatingAdd;
+
+impl<D: Dialect> Binary<D> for SaturatingAdd {
+    fn format_scalar<Lhs: Display, Rhs: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: Lhs,
+        rhs: Rhs,
+        item: Item<D>,
+    ) -> std::fmt::Result {
+        let elem = item.elem;
+        let (min, max) = int_bounds(elem);
+        let wide = wrapping_type(elem);
+        let sum = format!("{elem}({wide}({lhs}) + {wide}({rhs}))");
+
+        if is_signed(elem) {
+            write!(
+                f,
+                "((({lhs} ^ {sum}) & ({rhs} ^ {sum})) < 0 ? ({lhs} < 0 ? {elem}({min}) : {elem}({max})) : {sum})"
+            )
+        } else {
+            write!(f, "({sum} < {lhs} ? {elem}({max}) : {sum})")
+        }
+    }
+}
+
+/// Integer subtraction clamped to the bounds of the type.
+///
+/// An unsigned difference saturates to zero when `rhs > lhs`. A signed difference overflowed when
+/// the operands have different signs and the sign of the result differs from `lhs`.
+pub struct SaturatingSub;
+
+impl<D: Dialect> Binary<D> for SaturatingSub {
+    fn format_scalar<Lhs: Display, Rhs: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: Lhs,
+        rhs: Rhs,
+        item: Item<D>,
+    ) -> std::fmt::Result {
+        let elem = item.elem;
+        let (min, max) = int_bounds(elem);
+        let wide = wrapping_type(elem);
+        let diff = format!("{elem}({wide}({lhs}) - {wide}({rhs}))");
+
+        if is_signed(elem) {
+            write!(
+                f,
+                "((({lhs} ^ {rhs}) & ({lhs} ^ {diff})) < 0 ? ({lhs} < 0 ? {elem}({min}) : {elem}({max})) : {diff})"
+            )
+        } else {
+            write!(f, "({lhs} < {rhs} ? {elem}(0) : {diff})")
+        }
+    }
+}
+
+/// Integer addition modulo `2^bits`.
+///
+/// Computed on an unsigned type, since signed overflow is undefined behavior in C++.
+pub struct WrappingAdd;
+
+impl<D: Dialect> Binary<D> for WrappingAdd {
+    fn format_scalar<Lhs: Display, Rhs: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: Lhs,
+        rhs: Rhs,
+        item: Item<D>,
+    ) -> std::fmt::Result {
+        let elem = item.elem;
+        let wide = wrapping_type(elem);
+        write!(f, "{elem}({wide}({lhs}) + {wide}({rhs}))")
+    }
+}
+
+/// Integer multiplication modulo `2^bits`.
+///
+/// Computed on an unsigned type, since signed overflow is undefined behavior in C++.
+pub struct WrappingMul;
+
+impl<D: Dialect> Binary<D> for WrappingMul {
+    fn format_scalar<Lhs: Display, Rhs: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: Lhs,
+        rhs: Rhs,
+        item: Item<D>,
+    ) -> std::fmt::Result {
+        let elem = item.elem;
+        let wide = wrapping_type(elem);
+        write!(f, "{elem}({wide}({lhs}) * {wide}({rhs}))")
+    }
+}
+
+/// Unsigned type of at least 32 bits that the integer operations on `elem` are computed with.
+///
+/// Smaller types would be promoted to `int`, where overflow is undefined. Converting the result
+/// back to `elem` truncates it to the width of the type.
+fn wrapping_type<D: Dialect>(elem: Elem<D>) -> &'static str {
+    match elem {
+        Elem::I8 | Elem::I16 | Elem::I32 | Elem::U8 | Elem::U16 | Elem::U32 => "uint",
+        Elem::I64 | Elem::U64 => "uint64",
+        _ => {
+            panic!("Saturating and wrapping operations are only supported on integers, got {elem}")
+        }
+    }
+}
+
+fn is_signed<D: Dialect>(elem: Elem<D>) -> bool {
+    matches!(elem, Elem::I8 | Elem::I16 | Elem::I32 | Elem::I64)
+}
+
+/// Smallest and largest values of an integer type, as C++ literals.
+fn int_bounds<D: Dialect>(elem: Elem<D>) -> (&'static str, &'static str) {
+    match elem {
+        Elem::I8 => ("-128", "127"),
+        Elem::I16 => ("-32768", "32767"),
+        // The negated literals don't fit in the type, the minimum is built from the maximum.
+        Elem::I32 => ("-2147483647 - 1", "2147483647"),
+        Elem::I64 => ("-9223372036854775807ll - 1", "9223372036854775807ll"),
+        Elem::U8 => ("0", "255"),
+        Elem::U16 => ("0", "65535"),
+        Elem::U32 => ("0", "4294967295u"),
+        Elem::U64 => ("0", "18446744073709551615ull"),
+        _ => panic!("Saturating operations are only supported on integers, got {elem}"),
+    }
+}
+
 pub struct IndexAssign;
 pub struct Index;
 
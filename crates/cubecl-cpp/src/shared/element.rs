@@ -1,4 +1,5 @@
 use cubecl_core::{
+    e4m3, e5m2,
     ir::{self as gpu, BarrierLevel, ConstantScalarValue, Id},
     tf32,
 };
@@ -9,6 +10,8 @@ use super::{COUNTER_TMP_VAR, Dialect, Fragment, FragmentIdent};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub enum Elem<D: Dialect> {
+    E4M3,
+    E5M2,
     TF32,
     F32,
     F64,
@@ -74,6 +77,8 @@ impl<D: Dialect> Display for Elem<D> {
             Elem::F64 => f.write_str("double"),
             Elem::BF16 => D::bfloat16_type_name(f),
             Elem::BF162 => D::bfloat162_type_name(f),
+            Elem::E4M3 => f.write_str(D::fp8_type_name(gpu::FloatKind::E4M3).unwrap()),
+            Elem::E5M2 => f.write_str(D::fp8_type_name(gpu::FloatKind::E5M2).unwrap()),
             Elem::TF32 => f.write_str("float"),
             Elem::I8 => f.write_str("char"),
             Elem::I16 => f.write_str("short"),
@@ -264,6 +269,12 @@ impl<D: Dialect> Display for Variable<D> {
                     gpu::IntKind::I32 => write!(f, "{elem}({})", *val as i32),
                     gpu::IntKind::I64 => write!(f, "{elem}({})", *val),
                 },
+                // `Debug` renders non-finite values as `inf`/`NaN`, which aren't valid C++.
+                ConstantScalarValue::Float(val, _) if val.is_nan() => write!(f, "{elem}(NAN)"),
+                ConstantScalarValue::Float(val, _) if val.is_infinite() => {
+                    let sign = if val.is_sign_negative() { "-" } else { "" };
+                    write!(f, "{elem}({sign}INFINITY)")
+                }
                 ConstantScalarValue::Float(val, kind) => match kind {
                     gpu::FloatKind::F16 => {
                         write!(f, "{elem}({:?})", half::f16::from_f64(*val))
@@ -271,6 +282,9 @@ impl<D: Dialect> Display for Variable<D> {
                     gpu::FloatKind::BF16 => {
                         write!(f, "{elem}({:?})", half::bf16::from_f64(*val))
                     }
+                    gpu::FloatKind::E4M3 | gpu::FloatKind::E5M2 => {
+                        write!(f, "{elem}({:?})", *val as f32)
+                    }
                     gpu::FloatKind::Flex32 => write!(f, "{elem}({:?})", *val as f32),
                     gpu::FloatKind::TF32 => write!(f, "{elem}({:?})", *val as f32),
                     gpu::FloatKind::F32 => write!(f, "{elem}({:?})", *val as f32),
@@ -613,6 +627,8 @@ impl<D: Dialect> Elem<D> {
             Elem::F162 => 2 * core::mem::size_of::<f16>(),
             Elem::BF162 => 2 * core::mem::size_of::<bf16>(),
             Elem::BF16 => core::mem::size_of::<bf16>(),
+            Elem::E4M3 => core::mem::size_of::<e4m3>(),
+            Elem::E5M2 => core::mem::size_of::<e5m2>(),
             Elem::TF32 => core::mem::size_of::<tf32>(),
             Elem::F32 => core::mem::size_of::<f32>(),
             Elem::F64 => core::mem::size_of::<f64>(),
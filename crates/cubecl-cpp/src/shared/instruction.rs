@@ -44,6 +44,10 @@ pub enum Instruction<D: Dialect> {
     Modulo(BinaryInstruction<D>),
     Remainder(BinaryInstruction<D>),
     Add(BinaryInstruction<D>),
+    SaturatingAdd(BinaryInstruction<D>),
+    SaturatingSub(BinaryInstruction<D>),
+    WrappingAdd(BinaryInstruction<D>),
+    WrappingMul(BinaryInstruction<D>),
     Fma {
         a: Variable<D>,
         b: Variable<D>,
@@ -61,6 +65,10 @@ pub enum Instruction<D: Dialect> {
         rhs: Variable<D>,
         out: Variable<D>,
     },
+    BoundsCheck {
+        index: Variable<D>,
+        len: Variable<D>,
+    },
     ConditionalRead {
         cond: Variable<D>,
         slice: Variable<D>,
@@ -225,6 +233,10 @@ impl<D: Dialect> Display for Instruction<D> {
                 }
             },
             Instruction::Add(it) => Add::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::SaturatingAdd(it) => SaturatingAdd::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::SaturatingSub(it) => SaturatingSub::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::WrappingAdd(it) => WrappingAdd::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::WrappingMul(it) => WrappingMul::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Slice {
                 input,
                 start,
@@ -276,6 +288,15 @@ impl<D: Dialect> Display for Instruction<D> {
                     }
                 }
             }
+            Instruction::BoundsCheck { index, len } => {
+                writeln!(f, "if ({index} >= {len}) {{")?;
+                writeln!(
+                    f,
+                    "printf(\"Out of bounds access: index %llu, length %llu\\n\", (unsigned long long) {index}, (unsigned long long) {len});"
+                )?;
+                D::trap(f)?;
+                f.write_str("}\n")
+            }
             Instruction::ConditionalRead {
                 cond,
                 slice,
@@ -55,7 +55,9 @@ pub struct ComputeKernel<D: Dialect> {
     pub barrier: bool,
     pub bf16: bool,
     pub f16: bool,
+    pub fp8: bool,
     pub fast_math: bool,
+    pub flush_denormals: bool,
     pub items: HashSet<super::Item<D>>,
     pub kernel_name: String,
 }
@@ -84,6 +86,10 @@ impl<D: Dialect> Display for ComputeKernel<D> {
             D::include_f16(f)?;
         }
 
+        if self.fp8 {
+            D::include_fp8(f)?;
+        }
+
         if self.wmma_activated {
             D::wmma_includes(f)?;
         }
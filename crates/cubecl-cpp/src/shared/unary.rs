@@ -303,9 +303,17 @@ impl<D: Dialect> Unary<D> for Assign {
     {
         // Cast only when necessary.
         if elem != input.elem() {
-            match elem {
-                Elem::TF32 => write!(f, "nvcuda::wmma::__float_to_tf32({input})"),
-                elem => write!(f, "{elem}({input})"),
+            match (input.elem(), elem) {
+                (_, Elem::TF32) => write!(f, "nvcuda::wmma::__float_to_tf32({input})"),
+                // 8-bit floats only convert from and to `__half` and `float` with the conversion
+                // intrinsics, other types go through `float`.
+                (Elem::E4M3 | Elem::E5M2, Elem::F16 | Elem::F32) => write!(f, "{elem}({input})"),
+                (Elem::E4M3 | Elem::E5M2, elem) => write!(f, "{elem}(float({input}))"),
+                (Elem::F16 | Elem::F32, elem @ (Elem::E4M3 | Elem::E5M2)) => {
+                    write!(f, "{elem}({input})")
+                }
+                (_, elem @ (Elem::E4M3 | Elem::E5M2)) => write!(f, "{elem}(float({input}))"),
+                (_, elem) => write!(f, "{elem}({input})"),
             }
         } else {
             write!(f, "{input}")
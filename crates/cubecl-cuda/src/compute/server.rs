@@ -393,6 +393,7 @@ impl CudaContext {
         let shared_mem_bytes = compute_kernel.shared_memory_size();
         let cube_dim = kernel_compiled.cube_dim;
         let fast_math = compute_kernel.fast_math;
+        let flush_denormals = compute_kernel.flush_denormals;
         let arch = format!("--gpu-architecture=sm_{}", self.arch);
 
         let include_path = include_path();
@@ -401,6 +402,9 @@ impl CudaContext {
         if fast_math {
             options.push("--use_fast_math");
         }
+        if flush_denormals {
+            options.push("--ftz=true");
+        }
 
         let kernel_compiled = logger.debug(kernel_compiled);
 
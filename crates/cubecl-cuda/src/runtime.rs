@@ -20,7 +20,7 @@ use cubecl_cpp::{
     CudaCompiler, WmmaCompiler,
     cuda::{arch::CudaArchitecture, mma::CudaWmmaCompiler},
     register_supported_types,
-    shared::register_wmma_features,
+    shared::{CompilationOptions, register_wmma_features},
 };
 
 /// Options configuring the CUDA runtime.
@@ -28,6 +28,9 @@ use cubecl_cpp::{
 pub struct RuntimeOptions {
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// Checks every memory access of the compiled kernels, aborting on out of bounds accesses.
+    /// Meant for debugging, see [`CompilationOptions::bounds_checks`].
+    pub bounds_checks: bool,
 }
 
 #[derive(Debug)]
@@ -76,6 +79,7 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
     let mem_properties = MemoryDeviceProperties {
         max_page_size: max_memory / 4,
         alignment: CudaStorage::ALIGNMENT,
+        total_memory: Some(max_memory),
     };
 
     let hardware_props = unsafe {
@@ -111,9 +115,25 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
     let memory_management =
         MemoryManagement::from_configuration(storage, &mem_properties, options.memory_config);
 
-    let mut device_props = DeviceProperties::new(&[Feature::Plane], mem_properties, hardware_props);
+    let device_name = cudarc::driver::result::device::get_name(device_ptr).unwrap();
+    let mut device_props = DeviceProperties::new(
+        &[Feature::Plane],
+        mem_properties,
+        hardware_props,
+        device_name,
+    );
+    let driver_version = unsafe {
+        let mut version = MaybeUninit::uninit();
+        cudarc::driver::sys::lib().cuDriverGetVersion(version.as_mut_ptr());
+        version.assume_init()
+    };
+    // CUDA encodes the version as `1000 * major + 10 * minor`.
+    device_props.register_driver_version(format!(
+        "{}.{}",
+        driver_version / 1000,
+        (driver_version % 1000) / 10
+    ));
     register_supported_types(&mut device_props);
-    device_props.register_feature(Feature::Type(Elem::Float(FloatKind::TF32)));
     if arch.version >= 60 {
         device_props.register_feature(Feature::Type(Elem::AtomicFloat(FloatKind::F64)));
     }
@@ -122,6 +142,15 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
         device_props.register_feature(Feature::Pipeline);
         device_props.register_feature(Feature::Barrier);
     }
+    // tf32 only exists as an operand of the tensor cores, which multiply it from Ampere onwards.
+    if arch.version >= 80 {
+        device_props.register_feature(Feature::Type(Elem::Float(FloatKind::TF32)));
+    }
+    // Ada and Hopper convert from and to 8-bit floats in hardware.
+    if arch.version >= 89 {
+        device_props.register_feature(Feature::Type(Elem::Float(FloatKind::E4M3)));
+        device_props.register_feature(Feature::Type(Elem::Float(FloatKind::E5M2)));
+    }
     // NOTE: I commented that since I observed synchronisation issues with atomic add for bf16.
     // if arch.version >= 80 {
     //     device_props.register_feature(Feature::Type(Elem::AtomicFloat(FloatKind::BF16)));
@@ -132,7 +161,10 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
     device_props.register_feature(Feature::AtomicFloat(AtomicFeature::LoadStore));
     device_props.register_feature(Feature::AtomicFloat(AtomicFeature::Add));
 
-    let comp_opts = Default::default();
+    let comp_opts = CompilationOptions {
+        bounds_checks: options.bounds_checks,
+        ..Default::default()
+    };
     let cuda_ctx = CudaContext::new(memory_management, comp_opts, stream, ctx, arch);
     let server = CudaServer::new(cuda_ctx);
     ComputeClient::new(MutexComputeChannel::new(server), device_props, ())
@@ -27,8 +27,18 @@ mod tests {
     pub type TestRuntime = crate::HipRuntime;
 
     cubecl_core::testgen_all!();
+    cubecl_linalg::testgen_conv1d!([f32]);
+    cubecl_linalg::testgen_conv2d!([f32]);
+    cubecl_linalg::testgen_conv3d!([f32]);
+    cubecl_linalg::testgen_conv_pool!([f32]);
+    cubecl_linalg::testgen_conv_unfold!([f32, u32]);
     cubecl_linalg::testgen_matmul_plane!([f32]);
     cubecl_linalg::testgen_matmul_accelerated!([f32]);
+    cubecl_linalg::testgen_matmul_requantize!();
+    cubecl_linalg::testgen_matmul_spmm!();
     cubecl_linalg::testgen_tensor_identity!([f32, u32]);
+    cubecl_linalg::testgen_tensor_data!([f32, u32]);
+    cubecl_linalg::testgen_tensor_layernorm!([f32]);
+    cubecl_linalg::testgen_tensor_concat!([f32, u32]);
     cubecl_reduce::testgen_reduce!([f16, bf16, f32, f64]);
 }
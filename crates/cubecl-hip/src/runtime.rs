@@ -30,6 +30,9 @@ use crate::{
 pub struct RuntimeOptions {
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// Checks every memory access of the compiled kernels, aborting on out of bounds accesses.
+    /// Meant for debugging, see [`CompilationOptions::bounds_checks`].
+    pub bounds_checks: bool,
 }
 
 #[derive(Debug)]
@@ -52,6 +55,8 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
     #[allow(unused_assignments)]
     let mut prop_arch_name = "";
     #[allow(unused_assignments)]
+    let mut prop_name = String::new();
+    #[allow(unused_assignments)]
     let mut prop_max_shared_memory_size = 0;
     let mut max_cube_count = CubeDim::new_single();
     #[allow(unused_assignments)]
@@ -69,6 +74,9 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
         prop_arch_name = CStr::from_ptr(ll_device_props.gcnArchName.as_ptr())
             .to_str()
             .unwrap();
+        prop_name = CStr::from_ptr(ll_device_props.name.as_ptr())
+            .to_string_lossy()
+            .into_owned();
         prop_max_shared_memory_size = ll_device_props.sharedMemPerBlock;
         max_cube_count.x = ll_device_props.maxGridSize[0] as u32;
         max_cube_count.y = ll_device_props.maxGridSize[1] as u32;
@@ -114,6 +122,7 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
     let mem_properties = MemoryDeviceProperties {
         max_page_size: max_memory as u64 / 4,
         alignment: HipStorage::ALIGNMENT,
+        total_memory: Some(max_memory as u64),
     };
     let topology = HardwareProperties {
         plane_size_min: prop_warp_size as u32,
@@ -128,7 +137,15 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
     };
     let memory_management =
         MemoryManagement::from_configuration(storage, &mem_properties, options.memory_config);
-    let mut device_props = DeviceProperties::new(&[Feature::Plane], mem_properties, topology);
+    let mut device_props =
+        DeviceProperties::new(&[Feature::Plane], mem_properties, topology, prop_name);
+    let driver_version = unsafe {
+        let mut version: std::ffi::c_int = 0;
+        let status = cubecl_hip_sys::hipDriverGetVersion(&mut version);
+        assert_eq!(status, HIP_SUCCESS, "Should get the driver version");
+        version
+    };
+    device_props.register_driver_version(driver_version.to_string());
     register_supported_types(&mut device_props);
     // Not sure if there's a good way to check for support on HIP
     device_props.register_feature(Feature::Type(Elem::AtomicFloat(FloatKind::F64)));
@@ -143,6 +160,7 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
 
     let comp_opts = CompilationOptions {
         warp_size: arch.warp_size(),
+        bounds_checks: options.bounds_checks,
     };
     let hip_ctx = HipContext::new(memory_management, comp_opts, stream);
     let server = HipServer::new(hip_ctx);
@@ -159,7 +159,7 @@ use cubecl_macros_internal::TypeHash;
 pub use expand_element::*;
 
 mod expand_element {
-    use cubecl_common::{flex32, tf32};
+    use cubecl_common::{e4m3, e5m2, flex32, tf32};
     use half::{bf16, f16};
 
     use super::*;
@@ -230,6 +230,8 @@ mod expand_element {
     impl_into_expand_element!(u64);
     impl_into_expand_element!(usize);
     impl_into_expand_element!(bool);
+    impl_into_expand_element!(e4m3);
+    impl_into_expand_element!(e5m2);
     impl_into_expand_element!(flex32);
     impl_into_expand_element!(f16);
     impl_into_expand_element!(bf16);
@@ -42,6 +42,23 @@ pub enum Arithmetic {
     Normalize(UnaryOperator),
     #[operation(commutative)]
     Dot(BinaryOperator),
+    /// Integer addition clamped to the bounds of the type instead of overflowing.
+    ///
+    /// Unsigned types saturate to their maximum value. Signed types saturate to their maximum
+    /// value on positive overflow and to their minimum value on negative overflow.
+    #[operation(commutative)]
+    SaturatingAdd(BinaryOperator),
+    /// Integer subtraction clamped to the bounds of the type instead of overflowing.
+    ///
+    /// Unsigned types saturate to zero when `rhs > lhs`. Signed types saturate to their minimum
+    /// value on negative overflow and to their maximum value on positive overflow.
+    SaturatingSub(BinaryOperator),
+    /// Integer addition modulo `2^bits`, for both signed and unsigned types.
+    #[operation(commutative)]
+    WrappingAdd(BinaryOperator),
+    /// Integer multiplication modulo `2^bits`, for both signed and unsigned types.
+    #[operation(commutative)]
+    WrappingMul(BinaryOperator),
 }
 
 impl Display for Arithmetic {
@@ -79,6 +96,10 @@ impl Display for Arithmetic {
             Arithmetic::Magnitude(op) => write!(f, "{}.length()", op.input),
             Arithmetic::Normalize(op) => write!(f, "{}.normalize()", op.input),
             Arithmetic::Dot(op) => write!(f, "{}.dot({})", op.lhs, op.rhs),
+            Arithmetic::SaturatingAdd(op) => write!(f, "{}.saturating_add({})", op.lhs, op.rhs),
+            Arithmetic::SaturatingSub(op) => write!(f, "{}.saturating_sub({})", op.lhs, op.rhs),
+            Arithmetic::WrappingAdd(op) => write!(f, "{}.wrapping_add({})", op.lhs, op.rhs),
+            Arithmetic::WrappingMul(op) => write!(f, "{}.wrapping_mul({})", op.lhs, op.rhs),
         }
     }
 }
@@ -131,7 +131,11 @@ impl ScopeProcessing {
                     Arithmetic::Normalize(op) => {
                         sanitize_constant_scalar_ref_var(&mut op.input, &inst.out.unwrap());
                     }
-                    Arithmetic::Dot(op) => {
+                    Arithmetic::Dot(op)
+                    | Arithmetic::SaturatingAdd(op)
+                    | Arithmetic::SaturatingSub(op)
+                    | Arithmetic::WrappingAdd(op)
+                    | Arithmetic::WrappingMul(op) => {
                         sanitize_constant_scalar_ref_var(&mut op.lhs, &inst.out.unwrap());
                         sanitize_constant_scalar_ref_var(&mut op.rhs, &inst.out.unwrap());
                     }
@@ -56,6 +56,7 @@ impl Algorithm for ImplicitCmmaConv {
         let cubes_needed_m = (problem.m as u32).div_ceil(m_stage);
         let cubes_needed_n = (problem.n as u32).div_ceil(n_stage);
 
-        CubeCount::Static(cubes_needed_m, cubes_needed_n, 1)
+        // Each group is an independent product of its own channels.
+        CubeCount::Static(cubes_needed_m, cubes_needed_n, problem.groups as u32)
     }
 }
@@ -0,0 +1,236 @@
+use cubecl_core as cubecl;
+use cubecl_core::calculate_cube_count_elemwise;
+use cubecl_core::prelude::*;
+
+use crate::{
+    matmul::{self, Strategy},
+    tensor::{TensorHandle, into_contiguous},
+};
+
+use super::{
+    Conv2dOptions, ConvLaunchError, ConvUnimplementedError, check_output_shape, check_zero_padding,
+    conv_transpose::launch_transposed, conv2d_output_shape, unfold,
+};
+
+/// Sums the contiguous `[batch, k, n]` partial gradients over the batches in `A`, one element of
+/// the `[k, n]` gradient per unit.
+#[cube(launch_unchecked)]
+fn sum_batches_kernel<E: Numeric, A: Numeric>(partial: &Tensor<E>, output: &mut Tensor<E>) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let batch_stride = output.len();
+    let mut sum = A::from_int(0);
+    for batch in 0..partial.shape(0) {
+        sum += A::cast_from(partial[batch * batch_stride + ABSOLUTE_POS]);
+    }
+
+    output[ABSOLUTE_POS] = E::cast_from(sum);
+}
+
+/// Compute the gradient of the `[k, out_channels]` weight of a 2D convolution of the NHWC
+/// `input`, from the NHWC gradient of its output `grad_out`, using the matmul `strategy` and
+/// accumulating the batches in `A`.
+///
+/// The gradient has the layout of the weight of [launch_conv2d_nhwc](super::launch_conv2d_nhwc),
+/// k being ordered as (kernel y, kernel x, channel), for a `kernel_size` kernel.
+///
+/// Each batch is the product of its [unfolded](unfold) input, `[k, out_h * out_w]`, with its
+/// gradient, `[out_h * out_w, out_channels]`, computed for all the batches in a single batched
+/// matmul. The products are then summed over the batches in `A`.
+///
+/// Returns the errors of the unfold, e.g. when the options have no valid output, those of the
+/// matmul, [ConvLaunchError::ShapeMismatch] when `grad_out` doesn't have the shape of the
+/// output of the convolution, [ConvUnimplementedError::BackwardGroups] with more than one group
+/// and [ConvUnimplementedError::PaddingMode] when the padding isn't made of zeros.
+///
+/// # Panics
+///
+/// If `input` isn't a rank 4 tensor.
+pub fn conv2d_backward_weight<R: Runtime, E: Float, A: Numeric>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    grad_out: &TensorHandleRef<'_, R>,
+    kernel_size: (usize, usize),
+    options: &Conv2dOptions,
+) -> Result<TensorHandle<R, E>, ConvLaunchError> {
+    assert_eq!(
+        input.shape.len(),
+        4,
+        "Convolution input should be an NHWC tensor"
+    );
+
+    let Conv2dOptions {
+        stride,
+        padding,
+        dilation,
+        groups,
+        ..
+    } = *options;
+    let [batches, in_h, in_w, channels] = [0, 1, 2, 3].map(|dim| input.shape[dim]);
+    let out_channels = grad_out.shape.last().copied().unwrap_or(0);
+    let [_, _, out_h, out_w] = conv2d_output_shape(
+        [batches, channels, in_h, in_w],
+        [out_channels, channels, kernel_size.0, kernel_size.1],
+        stride,
+        padding,
+        dilation,
+    )?;
+    check_output_shape(
+        "grad_out",
+        grad_out.shape,
+        &[batches, out_h, out_w, out_channels],
+        input.shape,
+        &[out_channels, kernel_size.0, kernel_size.1, channels],
+    )?;
+
+    if groups != 1 {
+        return Err(ConvUnimplementedError::BackwardGroups { groups }.into());
+    }
+    check_zero_padding(options.padding_mode)?;
+
+    let shape_k = channels * kernel_size.0 * kernel_size.1;
+    if shape_k == 0 {
+        return Ok(TensorHandle::empty(client, vec![0, out_channels]));
+    }
+
+    let cols = unfold::<R, E>(client, input, kernel_size, stride, padding, dilation)?;
+
+    // The gradient as `[batch, out_h * out_w, out_channels]`, which needs the positions of each
+    // batch to be contiguous with each other.
+    let grad_out = match grad_out.strides[1] == grad_out.strides[2] * out_w {
+        true => TensorHandle::<R, E>::new(
+            vec![batches, out_h * out_w, out_channels],
+            vec![
+                grad_out.strides[0],
+                grad_out.strides[2],
+                grad_out.strides[3],
+            ],
+            grad_out.handle.clone(),
+        ),
+        false => {
+            let contiguous = into_contiguous::<R, E>(client, grad_out);
+            TensorHandle::new_contiguous(
+                vec![batches, out_h * out_w, out_channels],
+                contiguous.handle,
+            )
+        }
+    };
+
+    let partial = TensorHandle::<R, E>::empty(client, vec![batches, shape_k, out_channels]);
+    matmul::launch_ref::<R, E>(
+        strategy,
+        client,
+        &cols.as_ref(),
+        &grad_out.as_ref(),
+        &partial.as_ref(),
+    )?;
+
+    if batches == 1 {
+        return Ok(TensorHandle::new_contiguous(
+            vec![shape_k, out_channels],
+            partial.handle,
+        ));
+    }
+
+    let grad_weight = TensorHandle::<R, E>::empty(client, vec![shape_k, out_channels]);
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(shape_k * out_channels, cube_dim);
+
+    unsafe {
+        sum_batches_kernel::launch_unchecked::<E, A, R>(
+            client,
+            cube_count,
+            cube_dim,
+            partial.as_ref().as_tensor_arg(1),
+            grad_weight.as_ref().as_tensor_arg(1),
+        );
+    }
+
+    Ok(grad_weight)
+}
+
+/// Compute the gradient of the NHWC input of a 2D convolution into `grad_input`, from the
+/// `[kernel_h, kernel_w, channels, out_channels]` `weight` and the NHWC gradient of its output
+/// `grad_out`, accumulating in `A`.
+///
+/// The weight is the `[k, out_channels]` weight of [launch_conv2d_nhwc](super::launch_conv2d_nhwc)
+/// with k split into its kernel positions and channels, so the same buffer can be used for both.
+///
+/// This is the [transposed convolution](super::conv_transpose2d) of `grad_out` with the same
+/// options, reading the weight with its channel dimensions swapped. The shape of `grad_input`
+/// picks between the input shapes with the same convolution output, so only the begin padding is
+/// removed and no output padding is needed.
+///
+/// Returns the errors of [conv2d_output_shape], [ConvLaunchError::ShapeMismatch] when
+/// `grad_out` doesn't have the shape of the output of the convolution,
+/// [ConvUnimplementedError::BackwardGroups] with more than one group and
+/// [ConvUnimplementedError::PaddingMode] when the padding isn't made of zeros.
+///
+/// # Panics
+///
+/// If `weight` or `grad_input` isn't a rank 4 tensor.
+pub fn conv2d_backward_data<R: Runtime, E: Numeric, A: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    weight: &TensorHandleRef<'_, R>,
+    grad_out: &TensorHandleRef<'_, R>,
+    grad_input: &TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+) -> Result<(), ConvLaunchError> {
+    assert_eq!(
+        grad_input.shape.len(),
+        4,
+        "Convolution input gradient should be an NHWC tensor"
+    );
+    assert_eq!(
+        weight.shape.len(),
+        4,
+        "Convolution weight should have rank 4"
+    );
+
+    let [batches, in_h, in_w, channels] = [0, 1, 2, 3].map(|dim| grad_input.shape[dim]);
+    let [kernel_h, kernel_w, weight_channels, out_channels] =
+        [0, 1, 2, 3].map(|dim| weight.shape[dim]);
+    let [_, _, out_h, out_w] = conv2d_output_shape(
+        [batches, channels, in_h, in_w],
+        [out_channels, weight_channels, kernel_h, kernel_w],
+        options.stride,
+        options.padding,
+        options.dilation,
+    )?;
+    check_output_shape(
+        "grad_out",
+        grad_out.shape,
+        &[batches, out_h, out_w, out_channels],
+        grad_input.shape,
+        weight.shape,
+    )?;
+
+    if options.groups != 1 {
+        let groups = options.groups;
+        return Err(ConvUnimplementedError::BackwardGroups { groups }.into());
+    }
+    check_zero_padding(options.padding_mode)?;
+
+    // The `[out_channels, kernel_h, kernel_w, channels]` weight of the transposed convolution.
+    let shape = [out_channels, kernel_h, kernel_w, channels];
+    let strides = [3, 0, 1, 2].map(|dim| weight.strides[dim]);
+    let weight = unsafe {
+        TensorHandleRef::from_raw_parts(weight.handle, &strides, &shape, weight.elem_size)
+    };
+
+    launch_transposed::<R, E, A>(
+        client,
+        grad_out,
+        &weight,
+        None,
+        grad_input,
+        options.stride,
+        options.padding.begin,
+        options.dilation,
+    );
+
+    Ok(())
+}
@@ -4,11 +4,12 @@ use crate::matmul::{
         global::{AccumulatorLoader, OutputLoader},
         stage::{StageMatmul, StageMatmulFamily},
     },
-    kernels::MatmulAvailabilityError,
+    kernels::{MatmulAvailabilityError, tiling2d::MatmulEpilogue},
 };
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 use cubecl_std::tensor::r#virtual::{ReadWrite, VirtualTensor};
+use serde::{Deserialize, Serialize};
 
 use super::{ConvGemmConfig, homogeneous::base::ConvTilingLayout};
 
@@ -50,31 +51,41 @@ pub trait Convolution<CS: MatmulPrecision, SMM: StageMatmul<CS::ES, CS::EG, CS::
         #[comptime] config: Self::Config,
     );
 
+    /// Initialize the loader for the input of `group`, the offsets being relative to the group,
+    /// see [groups](ConvolutionProblem::groups).
     fn init_lhs_loader(
         lhs: VirtualTensor<CS::EG>,
         x_offset: u32,
         y_offset: u32,
+        group: u32,
         #[comptime] config: Self::Config,
     ) -> Self::LhsLoader;
 
+    /// Initialize the loader for the weight of `group`, the offsets being relative to the group.
     fn init_rhs_loader(
         rhs: VirtualTensor<CS::EG>,
         x_offset: u32,
         y_offset: u32,
+        group: u32,
         #[comptime] config: Self::Config,
     ) -> Self::RhsLoader;
 
+    /// Initialize the loader for the bias of `group`, the offset being relative to the group.
     fn init_bias_loader(
         bias: VirtualTensor<CS::EG>,
         n_offset: u32,
+        group: u32,
         #[comptime] config: Self::Config,
         #[comptime] has_bias: bool,
     ) -> Self::AccumulatorLoader;
 
+    /// Initialize the unloader for the output of `group`, the offsets being relative to the group.
     fn init_unloader(
         out: VirtualTensor<CS::EG, ReadWrite>,
         x_offset: u32,
         y_offset: u32,
+        group: u32,
+        #[comptime] config: Self::Config,
     ) -> Self::Out;
 
     fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulator;
@@ -104,7 +115,8 @@ pub trait ConvolutionConfigFactory: Send + Sync + 'static {
 
 /// Provides launch entry point to solve a matmul
 pub trait ConvolutionLaunch: ConvolutionConfigFactory {
-    /// Entry point
+    /// Entry point, with `cube_offset` added to the position of every cube when the cubes are
+    /// split across several launches
     ///
     /// # Safety
     ///
@@ -114,6 +126,7 @@ pub trait ConvolutionLaunch: ConvolutionConfigFactory {
         client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
         cube_dim: CubeDim,
         cube_count: CubeCount,
+        cube_offset: (u32, u32, u32),
         input: TensorArg<'_, R>,
         weight: TensorArg<'_, R>,
         bias: TensorArg<'_, R>,
@@ -122,6 +135,21 @@ pub trait ConvolutionLaunch: ConvolutionConfigFactory {
     );
 }
 
+#[derive(CubeType, Default, Clone, Copy, Hash, PartialEq, Eq, Debug, Serialize, Deserialize)]
+/// How the input of a convolution is extended beyond its borders.
+///
+/// `Reflect` requires the padding to be smaller than the input on each spatial dimension.
+pub enum PaddingMode {
+    /// Positions outside of the input read zeros
+    #[default]
+    Zero,
+    /// Positions outside of the input mirror the input across its border, without repeating the
+    /// border itself, e.g. `-1` reads `1`
+    Reflect,
+    /// Positions outside of the input read the closest border element
+    Replicate,
+}
+
 #[derive(Clone)]
 /// Description of a matmul problem to solve, regardless of actual data
 pub struct ConvolutionProblem {
@@ -136,14 +164,55 @@ pub struct ConvolutionProblem {
 
     pub kernel_size: (u32, u32),
     pub stride: (u32, u32),
+    /// Padding before the first row and column of the input, which offsets the positions read.
     pub padding: (i32, i32),
+    /// Padding after the last row and column of the input, which only extends the output. Reads
+    /// past the end of the input are skipped when either side is padded.
+    pub padding_end: (i32, i32),
+    /// Spacing between kernel taps. Each tap reads the input at
+    /// `out * stride + kernel * dilation - padding`, and `(1, 1)` is a regular convolution. The
+    /// output shape must account for it, see [`super::conv2d_output_shape`].
     pub dilation: (u32, u32),
+    /// How the input is extended beyond its borders when `padding` or `padding_end` is non-zero.
+    pub padding_mode: PaddingMode,
     pub out_shape_y: usize,
     pub out_shape_x: usize,
     pub has_bias: bool,
+    /// Number of groups the channels are split into, each group of output channels only reading
+    /// its own group of input channels. `1` is a regular convolution and the number of input
+    /// channels a depthwise one.
+    ///
+    /// `k` and `n` are the sizes of the product of a single group, i.e. the weight has `k` rows
+    /// and the weight, bias and output have `n * groups` columns. The channels of each group must
+    /// be divisible by the line sizes of the tensors they are vectorized along.
+    pub groups: usize,
+    /// The depth of a 3D convolution, where the input is NDHWC, m also counts the output depths
+    /// and k the kernel depths. `None` for a 2D convolution.
+    pub depth: Option<ConvolutionDepth>,
+    /// Activation applied to the output, after the bias.
+    pub epilogue: MatmulEpilogue,
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+/// The depth dimension of a 3D convolution, the other spatial dimensions being the `(y, x)` ones
+/// of the [problem](ConvolutionProblem).
+pub struct ConvolutionDepth {
+    pub kernel_size: u32,
+    pub stride: u32,
+    pub padding: i32,
+    pub dilation: u32,
+    pub out_shape: u32,
 }
 
 impl ConvolutionProblem {
+    /// Number of taps of the kernel, i.e. `k` divided by the input channels of a group.
+    pub fn kernel_volume(&self) -> usize {
+        let (kernel_h, kernel_w) = self.kernel_size;
+        let kernel_d = self.depth.map_or(1, |depth| depth.kernel_size);
+
+        (kernel_d * kernel_h * kernel_w) as usize
+    }
+
     pub fn as_matmul_problem(&self) -> MatmulProblem {
         MatmulProblem {
             m: self.m,
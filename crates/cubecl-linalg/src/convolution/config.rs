@@ -1,4 +1,6 @@
-use crate::matmul::components::global::GlobalConfig;
+use crate::matmul::{components::global::GlobalConfig, kernels::tiling2d::MatmulEpilogue};
+
+use super::base::{ConvolutionDepth, PaddingMode};
 
 /// Convolution specific config, extends regular matmul [`Config`](global::Config)
 pub trait ConvGemmConfig: GlobalConfig {
@@ -10,6 +12,16 @@ pub trait ConvGemmConfig: GlobalConfig {
     fn dilation(&self, dim: u32) -> u32;
     /// The stride of the kernel at `dim`
     fn stride(&self, dim: u32) -> u32;
-    /// The padding of the kernel at `dim`
+    /// The padding of the kernel at `dim`, before the first element of the input
     fn padding(&self, dim: u32) -> i32;
+    /// The padding of the kernel at `dim`, after the last element of the input
+    fn padding_end(&self, dim: u32) -> i32;
+    /// How the input is extended beyond its borders
+    fn padding_mode(&self) -> PaddingMode;
+    /// The number of groups the channels are split into
+    fn groups(&self) -> u32;
+    /// The depth dimension of a 3D convolution, `None` in 2D
+    fn depth(&self) -> Option<ConvolutionDepth>;
+    /// The activation applied to the output
+    fn epilogue(&self) -> MatmulEpilogue;
 }
@@ -0,0 +1,219 @@
+use cubecl_core::{prelude::*, tensor_line_size_parallel};
+
+use crate::matmul::{
+    components::{MatmulPrecision, MatrixLayout},
+    kernels::tiling2d::MatmulEpilogue,
+};
+
+use super::{
+    ConvLaunchError,
+    algorithm::Algorithm,
+    base::{ConvolutionProblem, PaddingMode},
+    check_output_shape, check_padding_mode, conv1d_output_shape,
+    conv2d::merged_stride,
+    launch::BoundBias,
+    launch_conv2d_nhwc,
+    selection::ConvSelector,
+};
+
+/// Options of a 1D convolution.
+///
+/// Padding is applied on both sides of the input, with the [padding mode](PaddingMode).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conv1dOptions {
+    pub stride: usize,
+    pub padding: usize,
+    /// How the input is extended in the padding, zeros by default
+    pub padding_mode: PaddingMode,
+    pub dilation: usize,
+    /// Number of groups the channels are split into, see
+    /// [groups](ConvolutionProblem::groups)
+    pub groups: usize,
+    /// Activation applied to the output after the bias
+    pub epilogue: MatmulEpilogue,
+}
+
+impl Default for Conv1dOptions {
+    fn default() -> Self {
+        Self {
+            stride: 1,
+            padding: 0,
+            padding_mode: PaddingMode::Zero,
+            dilation: 1,
+            groups: 1,
+            epilogue: MatmulEpilogue::None,
+        }
+    }
+}
+
+/// Perform a 1D convolution using the implicit GEMM (im2col) algorithm, like
+/// [launch_conv2d_nhwc] with a single row of input and output positions and k being
+/// `kernel_size * channels / groups`.
+///
+/// * `input` - The `[batch, length, channels]` input
+/// * `weight` - The `[out_channels, kernel_size, channels / groups]` weight
+/// * `bias` - The `[out_channels]` bias added to each channel, if any
+/// * `out` - The `[batch, out_length, out_channels]` output, of the shape given by
+///   [conv1d_output_shape], otherwise
+///   [ShapeMismatch](ConvLaunchError::ShapeMismatch) is returned
+///
+/// The weight is multiplied as a column-major `[k, out_channels]` matrix and the output written
+/// as a `[m, out_channels]` one, so the last two dimensions of the weight and the first two of
+/// the output must be contiguous, otherwise [UnsupportedLayout](ConvLaunchError::UnsupportedLayout)
+/// is returned. Each tensor is vectorized along its channels with the largest line size dividing
+/// the channels of a group.
+///
+/// A depthwise convolution, with a single input and output channel per group, uses the depthwise
+/// kernel instead, every tensor being vectorized along all of its channels with the same line
+/// size.
+///
+/// # Panics
+///
+/// When a tensor doesn't have rank 3.
+pub fn launch_conv1d_nwc<R: Runtime, SP: MatmulPrecision, Alg: Algorithm, S: ConvSelector<Alg>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    options: &Conv1dOptions,
+) -> Result<(), ConvLaunchError>
+where
+    SP::EG: Numeric,
+{
+    let [batches, length, in_channels] = rank_3(input.shape, "input");
+    let [out_channels, kernel_size, group_channels] = rank_3(weight.shape, "weight");
+    let [_, _, out_length] = conv1d_output_shape(
+        [batches, in_channels, length],
+        [out_channels, group_channels, kernel_size],
+        options,
+    )?;
+    check_output_shape(
+        "out",
+        out.shape,
+        &[batches, out_length, out_channels],
+        input.shape,
+        weight.shape,
+    )?;
+    check_padding_mode(
+        options.padding_mode,
+        &[length],
+        &[(options.padding, options.padding)],
+    )?;
+
+    let group_n = out_channels / options.groups;
+    let m = batches * out_length;
+    let k = kernel_size * group_channels;
+    let depthwise = options.groups == in_channels && options.groups == out_channels;
+
+    let weight_stride_k = merged_stride(&weight.shape[1..], &weight.strides[1..])?;
+    let out_stride_m = merged_stride(&out.shape[..2], &out.strides[..2])?;
+    let weight_shape = [k, out_channels];
+    let weight_strides = [weight_stride_k, weight.strides[0]];
+    let out_shape = [m, out_channels];
+    let out_strides = [out_stride_m, out.strides[2]];
+
+    let line_size = |size: usize, stride: usize| {
+        let elem = SP::EG::as_elem_native_unchecked();
+        tensor_line_size_parallel(R::line_size_elem(&elem), &[size], &[stride], 0)
+    };
+    let (rhs_layout, lhs_line_size, rhs_line_size, out_line_size) = match depthwise {
+        // The depthwise kernel reads the weight along its channels, like the other tensors.
+        true => {
+            let mut channel_strides = vec![input.strides[2], weight.strides[0], out.strides[2]];
+            channel_strides.extend(bias.map(|bias| bias.strides[0]));
+            let line_size = channel_strides
+                .into_iter()
+                .map(|stride| line_size(in_channels, stride))
+                .min()
+                .unwrap();
+
+            (MatrixLayout::RowMajor, line_size, line_size, line_size)
+        }
+        false => {
+            let mut out_line_size = line_size(group_n, out.strides[2]);
+            if let Some(bias) = bias {
+                // The bias is read with the line size of the output.
+                out_line_size = out_line_size.min(line_size(group_n, bias.strides[0]));
+            }
+
+            (
+                MatrixLayout::ColMajor,
+                line_size(group_channels, input.strides[2]),
+                line_size(k, weight_stride_k),
+                out_line_size,
+            )
+        }
+    };
+
+    let problem = ConvolutionProblem {
+        m,
+        n: group_n,
+        k,
+        lhs_layout: MatrixLayout::RowMajor,
+        rhs_layout,
+        lhs_line_size,
+        rhs_line_size,
+        out_line_size,
+        kernel_size: (1, kernel_size as u32),
+        stride: (1, options.stride as u32),
+        padding: (0, options.padding as i32),
+        padding_end: (0, options.padding as i32),
+        dilation: (1, options.dilation as u32),
+        padding_mode: options.padding_mode,
+        out_shape_y: 1,
+        out_shape_x: out_length,
+        has_bias: bias.is_some(),
+        groups: options.groups,
+        depth: None,
+        epilogue: options.epilogue,
+    };
+
+    // The input as `[batch, 1, length, channels]`, the single row being read at `y = 0`.
+    let input_shape = [batches, 1, length, in_channels];
+    let input_strides = [
+        input.strides[0],
+        length * input.strides[1],
+        input.strides[1],
+        input.strides[2],
+    ];
+
+    let bias = BoundBias::<R, SP::EG>::new(client, bias, out_channels);
+    let (input, weight, out) = unsafe {
+        (
+            TensorArg::from_raw_parts::<SP::EG>(
+                input.handle,
+                &input_strides,
+                &input_shape,
+                lhs_line_size,
+            ),
+            TensorArg::from_raw_parts::<SP::EG>(
+                weight.handle,
+                &weight_strides,
+                &weight_shape,
+                rhs_line_size,
+            ),
+            TensorArg::from_raw_parts::<SP::EG>(
+                out.handle,
+                &out_strides,
+                &out_shape,
+                out_line_size,
+            ),
+        )
+    };
+
+    launch_conv2d_nhwc::<R, SP, Alg, S>(
+        client,
+        input,
+        weight,
+        bias.as_arg(out_line_size),
+        out,
+        problem,
+    )
+}
+
+fn rank_3(shape: &[usize], name: &str) -> [usize; 3] {
+    shape
+        .try_into()
+        .unwrap_or_else(|_| panic!("The {name} of a 1D convolution must have 3 dimensions"))
+}
@@ -0,0 +1,344 @@
+use cubecl_core as cubecl;
+use cubecl_core::{calculate_cube_count_elemwise, prelude::*, tensor_line_size_parallel};
+
+use crate::{
+    matmul::{
+        Strategy,
+        components::{MatmulPrecision, MatrixLayout},
+        kernels::tiling2d::MatmulEpilogue,
+    },
+    tensor::{TensorHandle, index_offset_with_layout, into_contiguous},
+};
+
+use super::{
+    ConvLaunchError,
+    algorithm::Algorithm,
+    base::{ConvolutionProblem, PaddingMode},
+    check_output_shape, check_padding_mode, conv2d_grouped_output_shape,
+    launch::BoundBias,
+    launch_conv2d_nhwc,
+    selection::ConvSelector,
+    winograd::{conv2d_winograd, is_winograd_compatible},
+};
+
+/// Padding of the spatial dimensions of a 2D convolution, each side being `(height, width)`.
+///
+/// A `(height, width)` tuple converts to the same padding on both sides.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Padding2d {
+    /// Padding before the first row and column of the input
+    pub begin: (usize, usize),
+    /// Padding after the last row and column of the input
+    pub end: (usize, usize),
+}
+
+impl Padding2d {
+    /// The same `padding` on both sides of each spatial dimension.
+    pub fn symmetric(padding: (usize, usize)) -> Self {
+        Self {
+            begin: padding,
+            end: padding,
+        }
+    }
+
+    /// The `(begin, end)` padding of each spatial dimension.
+    pub(crate) fn pairs(&self) -> [(usize, usize); 2] {
+        [(self.begin.0, self.end.0), (self.begin.1, self.end.1)]
+    }
+
+    /// Padding added to the size of each spatial dimension, both sides included.
+    pub fn total(&self) -> (usize, usize) {
+        (self.begin.0 + self.end.0, self.begin.1 + self.end.1)
+    }
+}
+
+impl From<(usize, usize)> for Padding2d {
+    fn from(padding: (usize, usize)) -> Self {
+        Self::symmetric(padding)
+    }
+}
+
+/// Options of a 2D convolution, each spatial parameter being `(height, width)`.
+///
+/// The input of an output position is read from `out * stride + kernel * dilation - padding.begin`,
+/// positions in the padding being resolved with the [padding mode](PaddingMode).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conv2dOptions {
+    pub stride: (usize, usize),
+    pub padding: Padding2d,
+    /// How the input is extended in the padding, zeros by default. Only zeros are supported by
+    /// the gradients, the Winograd and the explicit im2col convolutions for now
+    pub padding_mode: PaddingMode,
+    pub dilation: (usize, usize),
+    /// Number of groups the channels are split into, see
+    /// [groups](ConvolutionProblem::groups). Only `1` is supported by the gradients for now
+    pub groups: usize,
+    /// Activation applied to the output after the bias. The gradients are those of the
+    /// convolution before the activation, which is left to the caller
+    pub epilogue: MatmulEpilogue,
+    /// Whether [launch_conv2d] may use the [Winograd](conv2d_winograd) algorithm when the
+    /// convolution is [compatible](is_winograd_compatible), `false` by default. It is faster but
+    /// less accurate than the implicit GEMM, and computes in the tensor type whatever the
+    /// precision given to the launcher
+    pub winograd: bool,
+}
+
+impl Default for Conv2dOptions {
+    fn default() -> Self {
+        Self {
+            stride: (1, 1),
+            padding: Padding2d::default(),
+            padding_mode: PaddingMode::Zero,
+            dilation: (1, 1),
+            groups: 1,
+            epilogue: MatmulEpilogue::None,
+            winograd: false,
+        }
+    }
+}
+
+/// Copies each element of the contiguous `input` to the element of `output` at the same index,
+/// `output` having the shape of `input` with any strides.
+///
+/// The input is read in lines along its last dimension, each element of a line being written
+/// with the stride of the last dimension of the unvectorized `output`.
+#[cube(launch_unchecked)]
+fn copy_to_strided_kernel<E: Numeric>(input: &Tensor<Line<E>>, output: &mut Tensor<Line<E>>) {
+    if ABSOLUTE_POS >= input.len() {
+        terminate!();
+    }
+
+    let line_size = input.line_size();
+    let rank = output.rank();
+    let position = ABSOLUTE_POS * line_size;
+    let offset = index_offset_with_layout::<E, E>(output, input, position, 0, rank, false);
+    let stride = output.stride(rank - 1);
+    let line = input[ABSOLUTE_POS];
+
+    #[unroll]
+    for i in 0..line_size {
+        output[offset + i * stride] = Line::cast_from(line[i]);
+    }
+}
+
+/// Perform a 2D convolution using the implicit GEMM (im2col) algorithm, like
+/// [launch_conv2d_nhwc] but from the tensors and options of the convolution.
+///
+/// * `input` - The `[batch, height, width, channels]` input
+/// * `weight` - The `[out_channels, kernel_h, kernel_w, channels / groups]` weight
+/// * `bias` - The `[out_channels]` bias added to each channel, if any
+/// * `out` - The `[batch, out_h, out_w, out_channels]` output, of the shape given by
+///   [conv2d_grouped_output_shape], otherwise
+///   [ShapeMismatch](ConvLaunchError::ShapeMismatch) is returned
+///
+/// The dimensions are always given in this order, their layout in memory being detected from
+/// the strides, like [matrix_layout](crate::tensor::matrix_layout) does for matmul:
+///
+/// * An NCHW input is passed as the `[batch, height, width, channels]` permutation of its tensor,
+///   and is read in place one element at a time.
+/// * An OIHW weight has its channels outside of its kernel positions, so k can't be read with a
+///   single stride and the weight is copied as OHWI first.
+/// * An NCHW output can't be written as a `[m, out_channels]` matrix, so the convolution is
+///   written to an NHWC tensor first, then copied to the output in lines of channels.
+///
+/// The NHWC input, OHWI weight and NHWC output are used in place, each tensor being vectorized
+/// along its channels with the largest line size dividing the channels of a group. The
+/// precision of `SP` is used like [launch_conv2d_nhwc] does, e.g. `(f16, f16, f32)` for f16
+/// tensors accumulated in f32.
+///
+/// When [winograd](Conv2dOptions::winograd) is enabled, a 3x3 convolution with a stride and
+/// dilation of 1, a single group and zero padding uses [conv2d_winograd] instead, with the
+/// [Auto](crate::matmul::Strategy::Auto) matmul in the global precision of `SP`, ignoring its
+/// stage and accumulator precisions.
+///
+/// Returns [ConvLaunchError::InvalidRank] when the input or the weight doesn't have rank 4.
+pub fn launch_conv2d<R: Runtime, SP: MatmulPrecision, Alg: Algorithm, S: ConvSelector<Alg>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+) -> Result<(), ConvLaunchError>
+where
+    SP::EG: Numeric,
+{
+    let [batches, in_h, in_w, in_channels] = rank_4(input.shape, "input")?;
+    let [out_channels, kernel_h, kernel_w, group_channels] = rank_4(weight.shape, "weight")?;
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, in_channels, in_h, in_w],
+        [out_channels, group_channels, kernel_h, kernel_w],
+        options.stride,
+        options.padding,
+        options.dilation,
+        options.groups,
+    )?;
+    check_output_shape(
+        "out",
+        out.shape,
+        &[batches, out_h, out_w, out_channels],
+        input.shape,
+        weight.shape,
+    )?;
+    check_padding_mode(
+        options.padding_mode,
+        &[in_h, in_w],
+        &options.padding.pairs(),
+    )?;
+
+    if options.winograd && is_winograd_compatible((kernel_h, kernel_w), options) {
+        return conv2d_winograd::<R, SP::EG>(
+            &Strategy::Auto,
+            client,
+            input,
+            weight,
+            bias,
+            out,
+            options,
+        );
+    }
+
+    let group_n = out_channels / options.groups;
+    let Padding2d { begin, end } = options.padding;
+    let m = batches * out_h * out_w;
+    let k = kernel_h * kernel_w * group_channels;
+
+    let weight_ohwi = merged_stride(&weight.shape[1..], &weight.strides[1..])
+        .is_err()
+        .then(|| into_contiguous::<R, SP::EG>(client, weight));
+    let weight_ohwi = weight_ohwi.as_ref().map(TensorHandle::as_ref);
+    let weight = weight_ohwi.as_ref().unwrap_or(weight);
+
+    let out_nhwc = merged_stride(&out.shape[..3], &out.strides[..3])
+        .is_err()
+        .then(|| TensorHandle::<R, SP::EG>::empty(client, out.shape.to_vec()));
+    let out_nhwc_ref = out_nhwc.as_ref().map(TensorHandle::as_ref);
+    let target = out_nhwc_ref.as_ref().unwrap_or(out);
+
+    let weight_stride_k = merged_stride(&weight.shape[1..], &weight.strides[1..])?;
+    let out_stride_m = merged_stride(&target.shape[..3], &target.strides[..3])?;
+    let weight_shape = [k, out_channels];
+    let weight_strides = [weight_stride_k, weight.strides[0]];
+    let out_shape = [m, out_channels];
+    let out_strides = [out_stride_m, target.strides[3]];
+
+    let line_size = |group_size: usize, stride: usize| {
+        let elem = SP::EG::as_elem_native_unchecked();
+        tensor_line_size_parallel(R::line_size_elem(&elem), &[group_size], &[stride], 0)
+    };
+    let lhs_line_size = line_size(group_channels, input.strides[3]);
+    let rhs_line_size = line_size(k, weight_stride_k);
+    let mut out_line_size = line_size(group_n, target.strides[3]);
+    if let Some(bias) = bias {
+        // The bias is read with the line size of the output.
+        out_line_size = out_line_size.min(line_size(group_n, bias.strides[0]));
+    }
+
+    let problem = ConvolutionProblem {
+        m,
+        n: group_n,
+        k,
+        lhs_layout: MatrixLayout::RowMajor,
+        rhs_layout: MatrixLayout::ColMajor,
+        lhs_line_size,
+        rhs_line_size,
+        out_line_size,
+        kernel_size: (kernel_h as u32, kernel_w as u32),
+        stride: (options.stride.0 as u32, options.stride.1 as u32),
+        padding: (begin.0 as i32, begin.1 as i32),
+        padding_end: (end.0 as i32, end.1 as i32),
+        dilation: (options.dilation.0 as u32, options.dilation.1 as u32),
+        padding_mode: options.padding_mode,
+        out_shape_y: out_h,
+        out_shape_x: out_w,
+        has_bias: bias.is_some(),
+        groups: options.groups,
+        depth: None,
+        epilogue: options.epilogue,
+    };
+
+    let bias = BoundBias::<R, SP::EG>::new(client, bias, out_channels);
+    let (weight_arg, out_arg) = unsafe {
+        (
+            TensorArg::from_raw_parts::<SP::EG>(
+                weight.handle,
+                &weight_strides,
+                &weight_shape,
+                rhs_line_size,
+            ),
+            TensorArg::from_raw_parts::<SP::EG>(
+                target.handle,
+                &out_strides,
+                &out_shape,
+                out_line_size,
+            ),
+        )
+    };
+
+    launch_conv2d_nhwc::<R, SP, Alg, S>(
+        client,
+        input.as_tensor_arg(lhs_line_size),
+        weight_arg,
+        bias.as_arg(out_line_size),
+        out_arg,
+        problem,
+    )?;
+
+    if let Some(out_nhwc) = out_nhwc {
+        launch_copy_to_strided::<R, SP::EG>(client, &out_nhwc.as_ref(), out);
+    }
+
+    Ok(())
+}
+
+/// Copy the contiguous `input` to `output`, which has the same shape with any strides.
+///
+/// The input is vectorized along its last dimension, e.g. the channels of an NHWC tensor copied
+/// to an NCHW output.
+pub(super) fn launch_copy_to_strided<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    output: &TensorHandleRef<'_, R>,
+) {
+    let rank = input.shape.len();
+    let line_size = tensor_line_size_parallel(
+        R::line_size_elem(&E::as_elem_native_unchecked()),
+        input.shape,
+        input.strides,
+        rank - 1,
+    );
+    let num_lines = input.shape.iter().product::<usize>() / line_size as usize;
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+    unsafe {
+        copy_to_strided_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size),
+            output.as_tensor_arg(1),
+        );
+    }
+}
+
+/// The dimensions of the `tensor` of a 2D convolution, which must have rank 4.
+pub(super) fn rank_4(shape: &[usize], tensor: &'static str) -> Result<[usize; 4], ConvLaunchError> {
+    shape.try_into().map_err(|_| ConvLaunchError::InvalidRank {
+        tensor,
+        shape: shape.to_vec(),
+        rank: 4,
+    })
+}
+
+/// Stride of the dimensions of `shape` merged into one, when they are contiguous with each other.
+pub(crate) fn merged_stride(shape: &[usize], strides: &[usize]) -> Result<usize, ConvLaunchError> {
+    let contiguous = (1..shape.len()).all(|i| strides[i - 1] == strides[i] * shape[i]);
+
+    match contiguous {
+        true => Ok(strides[shape.len() - 1]),
+        false => Err(ConvLaunchError::UnsupportedLayout {
+            strides: strides.to_vec(),
+        }),
+    }
+}
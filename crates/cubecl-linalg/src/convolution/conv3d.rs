@@ -0,0 +1,190 @@
+use cubecl_core::{prelude::*, tensor_line_size_parallel};
+
+use crate::matmul::{
+    components::{MatmulPrecision, MatrixLayout},
+    kernels::tiling2d::MatmulEpilogue,
+};
+
+use super::{
+    ConvLaunchError,
+    algorithm::Algorithm,
+    base::{ConvolutionDepth, ConvolutionProblem, PaddingMode},
+    check_output_shape, check_padding_mode,
+    conv2d::merged_stride,
+    conv3d_output_shape,
+    launch::BoundBias,
+    launch_conv2d_nhwc,
+    selection::ConvSelector,
+};
+
+/// Options of a 3D convolution, each spatial parameter being `[depth, height, width]`.
+///
+/// Padding is applied on both sides of each spatial dimension, with the
+/// [padding mode](PaddingMode).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conv3dOptions {
+    pub stride: [usize; 3],
+    pub padding: [usize; 3],
+    /// How the input is extended in the padding, zeros by default
+    pub padding_mode: PaddingMode,
+    pub dilation: [usize; 3],
+    /// Number of groups the channels are split into, see
+    /// [groups](ConvolutionProblem::groups)
+    pub groups: usize,
+    /// Activation applied to the output after the bias
+    pub epilogue: MatmulEpilogue,
+}
+
+impl Default for Conv3dOptions {
+    fn default() -> Self {
+        Self {
+            stride: [1, 1, 1],
+            padding: [0, 0, 0],
+            padding_mode: PaddingMode::Zero,
+            dilation: [1, 1, 1],
+            groups: 1,
+            epilogue: MatmulEpilogue::None,
+        }
+    }
+}
+
+/// Perform a 3D convolution using the implicit GEMM (im2col) algorithm, like
+/// [launch_conv2d_nhwc] with the output depths counted in m and the kernel depths in k.
+///
+/// * `input` - The `[batch, depth, height, width, channels]` input
+/// * `weight` - The `[out_channels, kernel_d, kernel_h, kernel_w, channels / groups]` weight
+/// * `bias` - The `[out_channels]` bias added to each channel, if any
+/// * `out` - The `[batch, out_d, out_h, out_w, out_channels]` output, of the shape given by
+///   [conv3d_output_shape], otherwise
+///   [ShapeMismatch](ConvLaunchError::ShapeMismatch) is returned
+///
+/// The weight is multiplied as a column-major `[k, out_channels]` matrix and the output written
+/// as a `[m, out_channels]` one, so the last four dimensions of the weight and the first four of
+/// the output must be contiguous, otherwise [UnsupportedLayout](ConvLaunchError::UnsupportedLayout)
+/// is returned. Each tensor is vectorized along its channels with the largest line size dividing
+/// the channels of a group.
+///
+/// # Panics
+///
+/// When a tensor doesn't have the rank of its layout.
+pub fn launch_conv3d_ndhwc<R: Runtime, SP: MatmulPrecision, Alg: Algorithm, S: ConvSelector<Alg>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    options: &Conv3dOptions,
+) -> Result<(), ConvLaunchError>
+where
+    SP::EG: Numeric,
+{
+    let [batches, in_d, in_h, in_w, in_channels] = rank_5(input.shape, "input");
+    let [out_channels, kernel_d, kernel_h, kernel_w, group_channels] =
+        rank_5(weight.shape, "weight");
+    let [_, _, out_d, out_h, out_w] = conv3d_output_shape(
+        [batches, in_channels, in_d, in_h, in_w],
+        [out_channels, group_channels, kernel_d, kernel_h, kernel_w],
+        options,
+    )?;
+    check_output_shape(
+        "out",
+        out.shape,
+        &[batches, out_d, out_h, out_w, out_channels],
+        input.shape,
+        weight.shape,
+    )?;
+    check_padding_mode(
+        options.padding_mode,
+        &[in_d, in_h, in_w],
+        &options.padding.map(|padding| (padding, padding)),
+    )?;
+
+    let group_n = out_channels / options.groups;
+    let m = batches * out_d * out_h * out_w;
+    let k = kernel_d * kernel_h * kernel_w * group_channels;
+
+    let weight_stride_k = merged_stride(&weight.shape[1..], &weight.strides[1..])?;
+    let out_stride_m = merged_stride(&out.shape[..4], &out.strides[..4])?;
+    let weight_shape = [k, out_channels];
+    let weight_strides = [weight_stride_k, weight.strides[0]];
+    let out_shape = [m, out_channels];
+    let out_strides = [out_stride_m, out.strides[4]];
+
+    let line_size = |group_size: usize, stride: usize| {
+        let elem = SP::EG::as_elem_native_unchecked();
+        tensor_line_size_parallel(R::line_size_elem(&elem), &[group_size], &[stride], 0)
+    };
+    let lhs_line_size = line_size(group_channels, input.strides[4]);
+    let rhs_line_size = line_size(k, weight_stride_k);
+    let mut out_line_size = line_size(group_n, out.strides[4]);
+    if let Some(bias) = bias {
+        // The bias is read with the line size of the output.
+        out_line_size = out_line_size.min(line_size(group_n, bias.strides[0]));
+    }
+
+    let [stride_d, stride_h, stride_w] = options.stride.map(|stride| stride as u32);
+    let [padding_d, padding_h, padding_w] = options.padding.map(|padding| padding as i32);
+    let [dilation_d, dilation_h, dilation_w] = options.dilation.map(|dilation| dilation as u32);
+
+    let problem = ConvolutionProblem {
+        m,
+        n: group_n,
+        k,
+        lhs_layout: MatrixLayout::RowMajor,
+        rhs_layout: MatrixLayout::ColMajor,
+        lhs_line_size,
+        rhs_line_size,
+        out_line_size,
+        kernel_size: (kernel_h as u32, kernel_w as u32),
+        stride: (stride_h, stride_w),
+        padding: (padding_h, padding_w),
+        padding_end: (padding_h, padding_w),
+        dilation: (dilation_h, dilation_w),
+        padding_mode: options.padding_mode,
+        out_shape_y: out_h,
+        out_shape_x: out_w,
+        has_bias: bias.is_some(),
+        groups: options.groups,
+        depth: Some(ConvolutionDepth {
+            kernel_size: kernel_d as u32,
+            stride: stride_d,
+            padding: padding_d,
+            dilation: dilation_d,
+            out_shape: out_d as u32,
+        }),
+        epilogue: options.epilogue,
+    };
+
+    let bias = BoundBias::<R, SP::EG>::new(client, bias, out_channels);
+    let (weight, out) = unsafe {
+        (
+            TensorArg::from_raw_parts::<SP::EG>(
+                weight.handle,
+                &weight_strides,
+                &weight_shape,
+                rhs_line_size,
+            ),
+            TensorArg::from_raw_parts::<SP::EG>(
+                out.handle,
+                &out_strides,
+                &out_shape,
+                out_line_size,
+            ),
+        )
+    };
+
+    launch_conv2d_nhwc::<R, SP, Alg, S>(
+        client,
+        input.as_tensor_arg(lhs_line_size),
+        weight,
+        bias.as_arg(out_line_size),
+        out,
+        problem,
+    )
+}
+
+fn rank_5(shape: &[usize], name: &str) -> [usize; 5] {
+    shape
+        .try_into()
+        .unwrap_or_else(|_| panic!("The {name} of a 3D convolution must have 5 dimensions"))
+}
@@ -0,0 +1,222 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_core::{CubeType, calculate_cube_count_elemwise};
+
+use crate::tensor::TensorHandle;
+
+use super::{
+    ConvLaunchError, ConvUnimplementedError, conv_transpose2d_output_shape, launch::BoundBias,
+};
+
+/// Options of a 2D transposed convolution, each spatial parameter being `(height, width)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConvTranspose2dOptions {
+    pub stride: (usize, usize),
+    /// Removed from both sides of the output, as the padding of the convolution it transposes
+    pub padding: (usize, usize),
+    /// Added to one side of the output only, smaller than either the stride or the dilation
+    pub output_padding: (usize, usize),
+    pub dilation: (usize, usize),
+    /// Number of groups the channels are split into, only `1` is supported by
+    /// [conv_transpose2d] for now
+    pub groups: usize,
+}
+
+impl Default for ConvTranspose2dOptions {
+    fn default() -> Self {
+        Self {
+            stride: (1, 1),
+            padding: (0, 0),
+            output_padding: (0, 0),
+            dilation: (1, 1),
+            groups: 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
+/// Window parameters of [conv_transpose2d] known at compile time.
+pub(crate) struct ConvTranspose2dConfig {
+    pub stride_h: u32,
+    pub stride_w: u32,
+    pub padding_h: u32,
+    pub padding_w: u32,
+    pub dilation_h: u32,
+    pub dilation_w: u32,
+    pub has_bias: bool,
+}
+
+impl Init for ConvTranspose2dConfig {
+    fn init(self, _scope: &mut Scope) -> Self {
+        self
+    }
+}
+
+/// Writes one element of the NHWC output per unit, gathering the input positions whose kernel
+/// window covers it.
+#[cube(launch_unchecked)]
+fn conv_transpose2d_kernel<E: Numeric, A: Numeric>(
+    input: &Tensor<E>,
+    weight: &Tensor<E>,
+    bias: &Tensor<E>,
+    output: &mut Tensor<E>,
+    #[comptime] config: ConvTranspose2dConfig,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let out_channels = output.shape(3);
+    let out_w = output.shape(2);
+    let out_h = output.shape(1);
+
+    let out_channel = ABSOLUTE_POS % out_channels;
+    let rem = ABSOLUTE_POS / out_channels;
+    let out_x = rem % out_w;
+    let rem = rem / out_w;
+    let out_y = rem % out_h;
+    let batch = rem / out_h;
+
+    let in_h = input.shape(1) as i32;
+    let in_w = input.shape(2) as i32;
+    let in_channels = input.shape(3);
+
+    let mut sum = A::from_int(0);
+    if comptime!(config.has_bias) {
+        sum = A::cast_from(bias[out_channel * bias.stride(0)]);
+    }
+
+    // The input at `in` reaches the output at `in * stride - padding + kernel * dilation`.
+    for kernel_y in 0..weight.shape(1) {
+        let y = (out_y + config.padding_h) as i32 - (kernel_y * config.dilation_h) as i32;
+        let in_y = y / config.stride_h as i32;
+        let y_in_bounds = y >= 0 && y % config.stride_h as i32 == 0 && in_y < in_h;
+
+        for kernel_x in 0..weight.shape(2) {
+            let x = (out_x + config.padding_w) as i32 - (kernel_x * config.dilation_w) as i32;
+            let in_x = x / config.stride_w as i32;
+            let x_in_bounds = x >= 0 && x % config.stride_w as i32 == 0 && in_x < in_w;
+
+            if y_in_bounds && x_in_bounds {
+                let input_offset = batch * input.stride(0)
+                    + in_y as u32 * input.stride(1)
+                    + in_x as u32 * input.stride(2);
+                let weight_offset = kernel_y * weight.stride(1)
+                    + kernel_x * weight.stride(2)
+                    + out_channel * weight.stride(3);
+
+                for channel in 0..in_channels {
+                    let value = input[input_offset + channel * input.stride(3)];
+                    let weight_value = weight[weight_offset + channel * weight.stride(0)];
+                    sum += A::cast_from(value) * A::cast_from(weight_value);
+                }
+            }
+        }
+    }
+
+    output[ABSOLUTE_POS] = E::cast_from(sum);
+}
+
+/// Compute the 2D transposed convolution of an NHWC `input` with a `[in_channels, kernel_h,
+/// kernel_w, out_channels]` weight and an optional `[out_channels]` bias, accumulating in `A`.
+///
+/// Each input position scatters its channels multiplied by the weight over the window of the
+/// output a convolution with the same parameters would read it from. Here each output element
+/// gathers the input positions reaching it instead, so that no two units write to the same
+/// element. The output is a new NHWC tensor of the shape given by
+/// [conv_transpose2d_output_shape].
+///
+/// Returns the errors of [conv_transpose2d_output_shape], and
+/// [ConvUnimplementedError::TransposedGroups] with more than one group.
+///
+/// # Panics
+///
+/// If `input` or `weight` isn't a rank 4 tensor.
+pub fn conv_transpose2d<R: Runtime, E: Numeric, A: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    options: &ConvTranspose2dOptions,
+) -> Result<TensorHandle<R, E>, ConvLaunchError> {
+    assert_eq!(
+        input.shape.len(),
+        4,
+        "Transposed convolution input should be an NHWC tensor"
+    );
+    assert_eq!(
+        weight.shape.len(),
+        4,
+        "Transposed convolution weight should have rank 4"
+    );
+
+    let [batches, in_h, in_w, in_channels] = [0, 1, 2, 3].map(|dim| input.shape[dim]);
+    let [weight_channels, kernel_h, kernel_w, group_out_channels] =
+        [0, 1, 2, 3].map(|dim| weight.shape[dim]);
+    let [_, out_channels, out_h, out_w] = conv_transpose2d_output_shape(
+        [batches, in_channels, in_h, in_w],
+        [weight_channels, group_out_channels, kernel_h, kernel_w],
+        options,
+    )?;
+
+    if options.groups != 1 {
+        let groups = options.groups;
+        return Err(ConvUnimplementedError::TransposedGroups { groups }.into());
+    }
+
+    let output = TensorHandle::<R, E>::empty(client, vec![batches, out_h, out_w, out_channels]);
+    launch_transposed::<R, E, A>(
+        client,
+        input,
+        weight,
+        bias,
+        &output.as_ref(),
+        options.stride,
+        options.padding,
+        options.dilation,
+    );
+
+    Ok(output)
+}
+
+/// Launch [conv_transpose2d_kernel] on tensors whose shapes were already checked, the output
+/// being of any NHWC shape: the positions no input reaches are set to the bias.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn launch_transposed<R: Runtime, E: Numeric, A: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    output: &TensorHandleRef<'_, R>,
+    stride: (usize, usize),
+    padding: (usize, usize),
+    dilation: (usize, usize),
+) {
+    let config = ConvTranspose2dConfig {
+        stride_h: stride.0 as u32,
+        stride_w: stride.1 as u32,
+        padding_h: padding.0 as u32,
+        padding_w: padding.1 as u32,
+        dilation_h: dilation.0 as u32,
+        dilation_w: dilation.1 as u32,
+        has_bias: bias.is_some(),
+    };
+
+    let bias = BoundBias::<R, E>::new(client, bias, output.shape[3]);
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(output.shape.iter().product(), cube_dim);
+
+    unsafe {
+        conv_transpose2d_kernel::launch_unchecked::<E, A, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            weight.as_tensor_arg(1),
+            bias.as_arg(1),
+            output.as_tensor_arg(1),
+            config,
+        );
+    }
+}
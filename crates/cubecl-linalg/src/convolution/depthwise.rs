@@ -0,0 +1,326 @@
+//! Depthwise convolutions, where every channel is convolved with its own kernel.
+//!
+//! The implicit GEMM would compute them as one product per channel with k equal to the kernel
+//! window, using a small part of each tile. Here each unit accumulates the kernel window of one
+//! output position directly, for a line of channels, which are contiguous in NHWC.
+use cubecl_core as cubecl;
+use cubecl_core::{prelude::*, tensor_line_size_parallel};
+
+use crate::{
+    matmul::{
+        components::MatrixLayout,
+        kernels::tiling2d::{MatmulEpilogue, activation_line},
+    },
+    tensor::{TensorHandle, into_contiguous},
+};
+
+use super::{
+    Conv2dOptions, ConvLaunchError, ConvUnimplementedError, Padding2d,
+    base::{ConvolutionProblem, PaddingMode},
+    check_output_shape, check_padding_mode,
+    conv2d::{launch_copy_to_strided, merged_stride, rank_4},
+    conv2d_grouped_output_shape,
+    launch::{BoundBias, split_cube_count},
+    reader::im2col::{im2col_input_position, resolve_padded_position},
+};
+
+/// Lines of channels along x and output positions along y of the cubes of the depthwise kernel.
+const DEPTHWISE_CUBE_DIM: (u32, u32) = (8, 32);
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+/// Depthwise convolution parameters
+pub(super) struct DepthwiseConfig {
+    kernel_size: (u32, u32),
+    stride: (u32, u32),
+    dilation: (u32, u32),
+    padding: (i32, i32),
+    padding_mode: PaddingMode,
+    has_bias: bool,
+    epilogue: MatmulEpilogue,
+}
+
+/// Whether the problem is a depthwise convolution the depthwise kernel can compute: a single
+/// input and output channel per group, and every tensor vectorized along the channels with the
+/// same line size. Only 2D convolutions are handled.
+pub(super) fn is_depthwise(problem: &ConvolutionProblem) -> bool {
+    problem.depth.is_none()
+        && problem.n == 1
+        && problem.k == problem.kernel_volume()
+        && problem.rhs_layout == MatrixLayout::RowMajor
+        && problem.lhs_line_size == problem.out_line_size
+        && problem.rhs_line_size == problem.out_line_size
+}
+
+/// Computes a line of channels of an output position per unit, with one cube per block of
+/// positions and block of channels, and one batch per cube along z. The cubes may be split across
+/// several launches, each one starting at `cube_offset`.
+#[cube(launch_unchecked)]
+fn depthwise_conv2d_kernel<E: Numeric, A: Numeric>(
+    input: &Tensor<Line<E>>,
+    weight: &Tensor<Line<E>>,
+    bias: &Tensor<Line<E>>,
+    out: &mut Tensor<Line<E>>,
+    out_shape_y: u32,
+    out_shape_x: u32,
+    cube_offset_x: u32,
+    cube_offset_y: u32,
+    cube_offset_z: u32,
+    #[comptime] config: DepthwiseConfig,
+) {
+    let line_size = out.line_size();
+    let channel = ((CUBE_POS_X + cube_offset_x) * CUBE_DIM_X + UNIT_POS_X) * line_size;
+    let position = (CUBE_POS_Y + cube_offset_y) * CUBE_DIM_Y + UNIT_POS_Y;
+    let batch = CUBE_POS_Z + cube_offset_z;
+
+    if channel >= input.shape(3) || position >= out_shape_y * out_shape_x {
+        terminate!();
+    }
+
+    let out_y = position / out_shape_x;
+    let out_x = position % out_shape_x;
+    let (shape_y, shape_x) = (input.shape(1), input.shape(2));
+    let kernel_w = comptime!(config.kernel_size.1);
+
+    let mut acc = Line::empty(line_size).fill(A::from_int(0));
+    if comptime!(config.has_bias) {
+        acc = Line::<A>::cast_from(bias[channel / line_size]);
+    }
+
+    for kernel_y in 0..comptime!(config.kernel_size.0) {
+        let y = im2col_input_position(
+            out_y,
+            kernel_y,
+            comptime!(config.stride.0),
+            comptime!(config.dilation.0),
+            comptime!(config.padding.0),
+        );
+        let y = resolve_padded_position(y, shape_y, comptime!(config.padding_mode));
+
+        for kernel_x in 0..kernel_w {
+            let x = im2col_input_position(
+                out_x,
+                kernel_x,
+                comptime!(config.stride.1),
+                comptime!(config.dilation.1),
+                comptime!(config.padding.1),
+            );
+            let x = resolve_padded_position(x, shape_x, comptime!(config.padding_mode));
+
+            // Only zero padding leaves positions outside of the input, which read zeros.
+            if y >= 0 && (y as u32) < shape_y && x >= 0 && (x as u32) < shape_x {
+                let input_index = batch * input.stride(0)
+                    + y as u32 * input.stride(1)
+                    + x as u32 * input.stride(2)
+                    + channel * input.stride(3);
+                let tap = kernel_y * kernel_w + kernel_x;
+                let weight_index = tap * weight.stride(0) + channel * weight.stride(1);
+
+                acc += Line::<A>::cast_from(input[input_index / line_size])
+                    * Line::<A>::cast_from(weight[weight_index / line_size]);
+            }
+        }
+    }
+
+    let out_row = batch * out_shape_y * out_shape_x + position;
+    let out_index = out_row * out.stride(0) + channel * out.stride(1);
+    let acc = activation_line::<A>(acc, comptime!(config.epilogue));
+    out[out_index / line_size] = Line::cast_from(acc);
+}
+
+/// Launch the depthwise kernel on a problem for which [is_depthwise] holds, accumulating in `A`.
+///
+/// The input is `[batch, height, width, channels]`, the weight `[kernel_h * kernel_w, channels]`,
+/// the bias `[channels]` and the output `[batch * out_h * out_w, channels]`, all vectorized with
+/// the output line size of the problem. The cubes are split into several launches when they don't
+/// fit in the maximum cube count of the device.
+pub(super) fn launch_depthwise_conv2d<R: Runtime, E: Numeric, A: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorArg<R>,
+    weight: TensorArg<R>,
+    bias: TensorArg<R>,
+    out: TensorArg<R>,
+    problem: &ConvolutionProblem,
+) {
+    let positions = problem.out_shape_y * problem.out_shape_x;
+    let batches = problem.m / positions;
+    let channel_lines = problem.groups.div_ceil(problem.out_line_size as usize);
+
+    let (cube_dim_x, cube_dim_y) = DEPTHWISE_CUBE_DIM;
+    let cubes_x = channel_lines.div_ceil(cube_dim_x as usize);
+    let cubes_y = positions.div_ceil(cube_dim_y as usize);
+
+    let cube_count = CubeCount::Static(cubes_x as u32, cubes_y as u32, batches as u32);
+    let max_cube_count = client.properties().hardware_properties().max_cube_count;
+
+    let config = DepthwiseConfig {
+        kernel_size: problem.kernel_size,
+        stride: problem.stride,
+        dilation: problem.dilation,
+        padding: problem.padding,
+        padding_mode: problem.padding_mode,
+        has_bias: problem.has_bias,
+        epilogue: problem.epilogue,
+    };
+
+    for (cube_offset, cube_count) in split_cube_count(cube_count, max_cube_count) {
+        unsafe {
+            depthwise_conv2d_kernel::launch_unchecked::<E, A, R>(
+                client,
+                cube_count,
+                CubeDim::new_2d(cube_dim_x, cube_dim_y),
+                input.clone(),
+                weight.clone(),
+                bias.clone(),
+                out.clone(),
+                ScalarArg::new(problem.out_shape_y as u32),
+                ScalarArg::new(problem.out_shape_x as u32),
+                ScalarArg::new(cube_offset.0),
+                ScalarArg::new(cube_offset.1),
+                ScalarArg::new(cube_offset.2),
+                config,
+            );
+        }
+    }
+}
+
+/// Perform a depthwise 2D convolution with the depthwise kernel, from the tensors and options of
+/// [launch_conv2d](super::launch_conv2d), accumulating in `A`.
+///
+/// Every group must have a single input and output channel. The weight is read in place with any
+/// strides, as a `[kernel_h * kernel_w, channels]` matrix, and an output which isn't NHWC is
+/// written to an NHWC tensor first, then copied. The tensors are vectorized along the channels
+/// when every one of them has contiguous channels.
+///
+/// Returns [ConvUnimplementedError::Depthwise] when the convolution isn't depthwise,
+/// [ConvLaunchError::InvalidRank] when the input or the weight doesn't have rank 4,
+/// [ConvLaunchError::ShapeMismatch] when the output doesn't have the shape of the
+/// convolution, and the errors of [conv2d_grouped_output_shape].
+pub fn conv2d_depthwise<R: Runtime, E: Numeric, A: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+) -> Result<(), ConvLaunchError> {
+    let [batches, in_h, in_w, channels] = rank_4(input.shape, "input")?;
+    let [out_channels, kernel_h, kernel_w, group_channels] = rank_4(weight.shape, "weight")?;
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, channels, in_h, in_w],
+        [out_channels, group_channels, kernel_h, kernel_w],
+        options.stride,
+        options.padding,
+        options.dilation,
+        options.groups,
+    )?;
+    check_output_shape(
+        "out",
+        out.shape,
+        &[batches, out_h, out_w, out_channels],
+        input.shape,
+        weight.shape,
+    )?;
+    check_padding_mode(
+        options.padding_mode,
+        &[in_h, in_w],
+        &options.padding.pairs(),
+    )?;
+
+    if options.groups != channels || options.groups != out_channels {
+        return Err(ConvUnimplementedError::Depthwise {
+            groups: options.groups,
+            in_channels: channels,
+            out_channels,
+        }
+        .into());
+    }
+
+    let m = batches * out_h * out_w;
+    let k = kernel_h * kernel_w;
+    if m == 0 || k == 0 {
+        return Ok(());
+    }
+
+    let weight_contiguous = merged_stride(&weight.shape[1..3], &weight.strides[1..3])
+        .is_err()
+        .then(|| into_contiguous::<R, E>(client, weight));
+    let weight_contiguous = weight_contiguous.as_ref().map(TensorHandle::as_ref);
+    let weight = weight_contiguous.as_ref().unwrap_or(weight);
+
+    let out_nhwc = merged_stride(&out.shape[..3], &out.strides[..3])
+        .is_err()
+        .then(|| TensorHandle::<R, E>::empty(client, out.shape.to_vec()));
+    let out_nhwc_ref = out_nhwc.as_ref().map(TensorHandle::as_ref);
+    let target = out_nhwc_ref.as_ref().unwrap_or(out);
+
+    let weight_shape = [k, channels];
+    let weight_strides = [
+        merged_stride(&weight.shape[1..3], &weight.strides[1..3])?,
+        weight.strides[0],
+    ];
+    let out_shape = [m, channels];
+    let out_strides = [
+        merged_stride(&target.shape[..3], &target.strides[..3])?,
+        target.strides[3],
+    ];
+
+    let elem = E::as_elem_native_unchecked();
+    let line_size = |stride: usize| {
+        tensor_line_size_parallel(R::line_size_elem(&elem), &[channels], &[stride], 0)
+    };
+    let mut channel_strides = vec![input.strides[3], weight.strides[0], target.strides[3]];
+    channel_strides.extend(bias.map(|bias| bias.strides[0]));
+    let line_size = channel_strides.into_iter().map(line_size).min().unwrap();
+
+    let Padding2d { begin, end } = options.padding;
+    let problem = ConvolutionProblem {
+        m,
+        n: 1,
+        k,
+        lhs_layout: MatrixLayout::RowMajor,
+        rhs_layout: MatrixLayout::RowMajor,
+        lhs_line_size: line_size,
+        rhs_line_size: line_size,
+        out_line_size: line_size,
+        kernel_size: (kernel_h as u32, kernel_w as u32),
+        stride: (options.stride.0 as u32, options.stride.1 as u32),
+        padding: (begin.0 as i32, begin.1 as i32),
+        padding_end: (end.0 as i32, end.1 as i32),
+        dilation: (options.dilation.0 as u32, options.dilation.1 as u32),
+        padding_mode: options.padding_mode,
+        out_shape_y: out_h,
+        out_shape_x: out_w,
+        has_bias: bias.is_some(),
+        groups: channels,
+        depth: None,
+        epilogue: options.epilogue,
+    };
+
+    let bias = BoundBias::<R, E>::new(client, bias, channels);
+    let (weight_arg, out_arg) = unsafe {
+        (
+            TensorArg::from_raw_parts::<E>(
+                weight.handle,
+                &weight_strides,
+                &weight_shape,
+                line_size,
+            ),
+            TensorArg::from_raw_parts::<E>(target.handle, &out_strides, &out_shape, line_size),
+        )
+    };
+
+    launch_depthwise_conv2d::<R, E, A>(
+        client,
+        input.as_tensor_arg(line_size),
+        weight_arg,
+        bias.as_arg(line_size),
+        out_arg,
+        &problem,
+    );
+
+    if let Some(out_nhwc) = out_nhwc {
+        launch_copy_to_strided::<R, E>(client, &out_nhwc.as_ref(), out);
+    }
+
+    Ok(())
+}
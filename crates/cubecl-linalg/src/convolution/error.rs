@@ -1,28 +1,171 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 use cubecl_core::tune::AutotuneError;
 
 use crate::matmul::kernels::{MatmulAvailabilityError, MatmulLaunchError};
 
+use super::base::PaddingMode;
+
+#[derive(Debug)]
 pub enum ConvLaunchError {
+    /// An error of a matmul launched by the convolution, or of its implicit GEMM.
     Matmul(MatmulLaunchError),
-    Groups(usize),
-    Unknown,
+    /// The channels of a grouped convolution can't be split evenly between its groups.
+    Groups {
+        groups: usize,
+        in_channels: usize,
+        out_channels: usize,
+    },
+    /// The convolution has no valid output, the spatial parameters having one value per spatial
+    /// dimension of the input. The padding of a 2D convolution has the [begin](super::Padding2d)
+    /// values followed by the end ones.
+    InvalidShape {
+        input_shape: Vec<usize>,
+        weight_shape: Vec<usize>,
+        stride: Vec<usize>,
+        padding: Vec<usize>,
+        dilation: Vec<usize>,
+    },
+    /// A tensor given to the launcher doesn't have the shape implied by the others, in the layout
+    /// of the launcher: the weight doesn't have the channels of a group of the input, or a tensor
+    /// doesn't have the shape of the output of the convolution, e.g. the `out` tensor or the
+    /// gradient of the output.
+    ShapeMismatch {
+        /// The tensor without the expected shape, e.g. `"weight"` or `"out"`.
+        tensor: &'static str,
+        /// Shape of the input of the convolution.
+        input: Vec<usize>,
+        /// Shape of the weight of the convolution, or its kernel size for an operation without
+        /// one, e.g. [col2im](super::col2im).
+        weight: Vec<usize>,
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
+    /// A tensor given to the launcher doesn't have the rank of the convolution, e.g. a weight of
+    /// 3 dimensions for a 2D convolution.
+    InvalidRank {
+        tensor: &'static str,
+        shape: Vec<usize>,
+        rank: usize,
+    },
+    /// A parameter of the options is invalid whatever the tensors, e.g. a stride of zero.
+    InvalidOptions { reason: String },
+    /// Dimensions of a tensor which must be read as one, e.g. the kernel positions and channels
+    /// of a weight, aren't contiguous with each other.
+    UnsupportedLayout { strides: Vec<usize> },
+    /// The convolution is valid but isn't supported by the kernels yet.
+    Unimplemented(ConvUnimplementedError),
+    /// The algorithm selected by autotune failed, see [conv2d_autotune](super::conv2d_autotune).
+    Autotune(AutotuneError),
+}
+
+pub enum ConvUnimplementedError {
+    /// Transposed convolutions with more than one group.
+    TransposedGroups { groups: usize },
+    /// Gradients of convolutions with more than one group.
+    BackwardGroups { groups: usize },
+    /// Explicit im2col convolutions with more than one group.
+    Im2colGroups { groups: usize },
+    /// Depthwise convolutions of groups without a single input and output channel.
+    Depthwise {
+        groups: usize,
+        in_channels: usize,
+        out_channels: usize,
+    },
+    /// Winograd convolutions other than those of a 3x3 kernel with a stride and dilation of 1,
+    /// a single group and zero padding.
+    Winograd {
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        dilation: (usize, usize),
+        groups: usize,
+        padding_mode: PaddingMode,
+    },
+    /// Gradients and explicit im2col of convolutions padded with another mode than zeros.
+    PaddingMode { mode: PaddingMode },
 }
 
-impl Debug for ConvLaunchError {
+impl Display for ConvLaunchError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConvLaunchError::Matmul(err) => {
-                write!(f, "{err:?}")
+                write!(f, "{err}")
+            }
+            ConvLaunchError::Groups {
+                groups,
+                in_channels,
+                out_channels,
+            } => {
+                write!(
+                    f,
+                    "Unable to launch convolution with {groups} groups because {in_channels} input channels and {out_channels} output channels can't be split evenly between them",
+                )
+            }
+            ConvLaunchError::InvalidShape {
+                input_shape,
+                weight_shape,
+                stride,
+                padding,
+                dilation,
+            } => {
+                write!(
+                    f,
+                    "Convolution of input {input_shape:?} with weight {weight_shape:?} using stride={stride:?}, padding={padding:?} and dilation={dilation:?} has no valid output shape",
+                )
             }
-            ConvLaunchError::Groups(groups) => {
-                writeln!(
+            ConvLaunchError::ShapeMismatch {
+                tensor,
+                input,
+                weight,
+                expected,
+                actual,
+            } => {
+                write!(
                     f,
-                    "Unable to launch matmul because groups must be one, is actually {groups}",
+                    "Convolution {tensor} of shape {actual:?} should have shape {expected:?} for an input of shape {input:?} and a weight of shape {weight:?}",
                 )
             }
-            ConvLaunchError::Unknown => write!(f, "Unknown"),
+            ConvLaunchError::InvalidRank {
+                tensor,
+                shape,
+                rank,
+            } => {
+                write!(
+                    f,
+                    "Convolution {tensor} of shape {shape:?} should have {rank} dimensions",
+                )
+            }
+            ConvLaunchError::InvalidOptions { reason } => {
+                write!(f, "Invalid convolution options: {reason}")
+            }
+            ConvLaunchError::UnsupportedLayout { strides } => {
+                write!(
+                    f,
+                    "Unable to launch convolution on a tensor with strides {strides:?}, whose dimensions can't be read as one",
+                )
+            }
+            ConvLaunchError::Unimplemented(err) => {
+                write!(
+                    f,
+                    "Unable to launch convolution because of an unimplemented feature: {err}"
+                )
+            }
+            ConvLaunchError::Autotune(err) => {
+                write!(
+                    f,
+                    "Unable to launch the convolution selected by autotune: {err}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConvLaunchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConvLaunchError::Matmul(err) => Some(err),
+            ConvLaunchError::Unimplemented(err) => Some(err),
+            _ => None,
         }
     }
 }
@@ -39,9 +182,67 @@ impl From<MatmulAvailabilityError> for ConvLaunchError {
     }
 }
 
+impl From<ConvUnimplementedError> for ConvLaunchError {
+    fn from(value: ConvUnimplementedError) -> Self {
+        Self::Unimplemented(value)
+    }
+}
+
+impl Display for ConvUnimplementedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConvUnimplementedError::TransposedGroups { groups } => {
+                write!(f, "Transposed convolution with {groups} groups")
+            }
+            ConvUnimplementedError::BackwardGroups { groups } => {
+                write!(f, "Gradient of a convolution with {groups} groups")
+            }
+            ConvUnimplementedError::Im2colGroups { groups } => {
+                write!(f, "Im2col convolution with {groups} groups")
+            }
+            ConvUnimplementedError::Depthwise {
+                groups,
+                in_channels,
+                out_channels,
+            } => {
+                write!(
+                    f,
+                    "Depthwise convolution of {in_channels} input and {out_channels} output channels in {groups} groups",
+                )
+            }
+            ConvUnimplementedError::Winograd {
+                kernel_size,
+                stride,
+                dilation,
+                groups,
+                padding_mode,
+            } => {
+                write!(
+                    f,
+                    "Winograd convolution of a {kernel_size:?} kernel with stride={stride:?}, dilation={dilation:?}, {groups} groups and {padding_mode:?} padding",
+                )
+            }
+            ConvUnimplementedError::PaddingMode { mode } => {
+                write!(
+                    f,
+                    "{mode:?} padding outside of the implicit GEMM and depthwise convolutions"
+                )
+            }
+        }
+    }
+}
+
+impl Debug for ConvUnimplementedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ConvUnimplementedError {}
+
 #[allow(clippy::from_over_into)]
 impl Into<AutotuneError> for ConvLaunchError {
     fn into(self) -> AutotuneError {
-        AutotuneError::Unknown(format!("{self:?}"))
+        AutotuneError::Unknown(self.to_string())
     }
 }
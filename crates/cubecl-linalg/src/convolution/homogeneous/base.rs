@@ -12,11 +12,10 @@ use crate::matmul::components::{
     Ident, InvalidConfigError, MatrixLayout,
     global::{
         self, AccumulatorLoader, GlobalConfig,
-        output_loader::Unloader,
         single_stage::{self, CyclicCoalescedLoading, SyncFullRhsLoader},
     },
     stage::{
-        self, ContiguousTilingLayout, RowMajorTilingOrder, StageMatmulFamily,
+        self, ContiguousTilingLayout, RowMajorTilingOrder, StageConfig, StageMatmulFamily,
         multi_buffer::{LhsReader, LhsReaderFamily, RhsReader, RhsReaderFamily},
     },
 };
@@ -27,9 +26,9 @@ use crate::{
             ConvolutionProblem,
         },
         config::ConvGemmConfig,
-        loader::{bias::BiasLoader, im2col::SimpleIm2colLoader},
+        loader::{bias::BiasLoader, im2col::SimpleIm2colLoader, unloader::EpilogueUnloader},
     },
-    matmul::components::MatmulPrecision,
+    matmul::{components::MatmulPrecision, kernels::MatmulAvailabilityError},
 };
 
 pub struct ImplicitGemmConvolutionFamily<SMM: StageMatmulFamily> {
@@ -76,7 +75,7 @@ where
         SyncFullRhsLoader<CS::EG, CS::ES, SMM::Config, CyclicCoalescedLoading<RowMajorTilingOrder>>;
     type AccumulatorLoader = BiasLoader<CS, SMM::Config>;
 
-    type Out = Unloader<CS::EG>;
+    type Out = EpilogueUnloader<CS>;
     type Accumulator = SMM::Accumulator;
 
     fn execute(
@@ -145,6 +144,7 @@ where
         lhs: VirtualTensor<CS::EG>,
         x_offset: u32,
         y_offset: u32,
+        group: u32,
         #[comptime] config: Self::Config,
     ) -> Self::LhsLoader {
         Self::LhsLoader::new(
@@ -153,6 +153,7 @@ where
             config.out_shape(1),
             x_offset,
             y_offset,
+            group,
             config,
         )
     }
@@ -161,26 +162,51 @@ where
         rhs: VirtualTensor<CS::EG>,
         x_offset: u32,
         y_offset: u32,
+        group: u32,
         #[comptime] config: Self::Config,
     ) -> Self::RhsLoader {
-        Self::RhsLoader::new::<Self::Config>(rhs, x_offset, y_offset, 0, config)
+        // The columns of the group start at the group offset and end at the next group.
+        let group_n = rhs.shape(1) / config.groups();
+        let group_offset = group * group_n * rhs.stride(1);
+
+        let mut loader =
+            Self::RhsLoader::new::<Self::Config>(rhs, x_offset, y_offset, group_offset, config);
+        loader.tensor_view.shape_y = group_n;
+        loader
     }
 
     fn init_bias_loader(
         bias: VirtualTensor<CS::EG>,
         n_offset: u32,
+        group: u32,
         #[comptime] config: Self::Config,
         #[comptime] has_bias: bool,
     ) -> Self::AccumulatorLoader {
-        Self::AccumulatorLoader::new(bias, n_offset, config.to_smm_config(), has_bias)
+        let smm_config = config.to_smm_config();
+        let mut loader = Self::AccumulatorLoader::new(bias, n_offset, smm_config, has_bias);
+
+        if has_bias {
+            let group_n = bias.shape(0) / config.groups();
+            loader.tensor_view.n_offset += group * group_n;
+            loader.tensor_view.shape_n = (group + 1) * group_n;
+        }
+
+        loader
     }
 
     fn init_unloader(
         out: VirtualTensor<CS::EG, ReadWrite>,
         x_offset: u32,
         y_offset: u32,
+        group: u32,
+        #[comptime] config: Self::Config,
     ) -> Self::Out {
-        Self::Out::new(out, x_offset, y_offset, 0)
+        let group_n = out.shape(1) / config.groups();
+        let group_offset = group * group_n * out.stride(1);
+
+        let mut unloader = Self::Out::new(out, x_offset, y_offset, group_offset, config.epilogue());
+        unloader.tensor_view.shape_y = group_n;
+        unloader
     }
 
     fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulator {
@@ -233,15 +259,41 @@ where
             problem.stride,
             problem.dilation,
             problem.padding,
+            problem.padding_end,
+            problem.padding_mode,
+            problem.groups as u32,
+            problem.depth,
             problem.has_bias,
+            problem.epilogue,
         )
     }
 
     fn check_availability<R: Runtime, CS: MatmulPrecision>(
         client: &ComputeClient<R::Server, R::Channel>,
         config: &Self::Config,
-    ) -> Result<(), crate::matmul::kernels::MatmulAvailabilityError> {
-        SMM::check_availability::<R, CS>(client, &config.to_smm_config())
+    ) -> Result<(), MatmulAvailabilityError> {
+        let smm_config = config.to_smm_config();
+        SMM::check_availability::<R, CS>(client, &smm_config)?;
+
+        // The input, weight and bias stages are in `CS::ES`, while the output is staged in
+        // `CS::EA` for the epilogue, see [EpilogueUnloader].
+        let lhs = smm_config.tiling_dimensions(Ident::Lhs);
+        let rhs = smm_config.tiling_dimensions(Ident::Rhs);
+        let out = smm_config.tiling_dimensions(Ident::Out);
+        let stage_elems = (lhs.total_size() + rhs.total_size() + rhs.total_col()) as usize;
+        let out_elems = (out.tile_size() * smm_config.num_planes()) as usize;
+        let required = stage_elems * CS::ES::as_elem_native_unchecked().size()
+            + out_elems * CS::EA::as_elem_native_unchecked().size();
+
+        let max = client
+            .properties()
+            .hardware_properties()
+            .max_shared_memory_size;
+        if required > max {
+            return Err(MatmulAvailabilityError::StageSharedMemoryLimitExceeded { required, max });
+        }
+
+        Ok(())
     }
 }
 
@@ -252,6 +304,7 @@ impl<SMM: StageMatmulFamily<LhsReader = LhsReaderFamily, RhsReader = RhsReaderFa
         client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
         cube_dim: CubeDim,
         cube_count: CubeCount,
+        cube_offset: (u32, u32, u32),
         input: TensorArg<'_, R>,
         weight: TensorArg<'_, R>,
         bias: TensorArg<'_, R>,
@@ -267,6 +320,9 @@ impl<SMM: StageMatmulFamily<LhsReader = LhsReaderFamily, RhsReader = RhsReaderFa
                 weight,
                 bias,
                 out,
+                ScalarArg::new(cube_offset.0),
+                ScalarArg::new(cube_offset.1),
+                ScalarArg::new(cube_offset.2),
                 config,
                 config.has_bias,
             );
@@ -286,11 +342,16 @@ pub(crate) fn implicit_conv<
     rhs: &Tensor<Line<EG>>,
     bias: &Tensor<Line<EG>>,
     out: &mut Tensor<Line<EG>>,
+    cube_offset_x: u32,
+    cube_offset_y: u32,
+    cube_offset_z: u32,
     #[comptime] config: GMM::Config,
     #[comptime] has_bias: bool,
 ) {
-    let x_offset = CUBE_POS_X * config.tiling_dimensions(Ident::Lhs).total_row();
-    let y_offset = CUBE_POS_Y * config.tiling_dimensions(Ident::Rhs).total_col();
+    // The cubes may be split across several launches, each one starting at `cube_offset`.
+    let x_offset = (CUBE_POS_X + cube_offset_x) * config.tiling_dimensions(Ident::Lhs).total_row();
+    let y_offset = (CUBE_POS_Y + cube_offset_y) * config.tiling_dimensions(Ident::Rhs).total_col();
+    let group = CUBE_POS_Z + cube_offset_z;
     let k_range = (0, rhs.shape(0));
 
     let lhs = VirtualTensor::<EG>::new::<Tensor<Line<EG>>>(lhs);
@@ -299,10 +360,10 @@ pub(crate) fn implicit_conv<
     let out = VirtualTensor::<EG, ReadWrite>::new::<Tensor<Line<EG>>>(out);
 
     GMM::Convolution::<(EG, ES, EA)>::execute(
-        GMM::Convolution::<(EG, ES, EA)>::init_lhs_loader(lhs, x_offset, k_range.0, config),
-        GMM::Convolution::<(EG, ES, EA)>::init_rhs_loader(rhs, k_range.0, y_offset, config),
-        GMM::Convolution::<(EG, ES, EA)>::init_bias_loader(bias, y_offset, config, has_bias),
-        GMM::Convolution::<(EG, ES, EA)>::init_unloader(out, x_offset, y_offset),
+        GMM::Convolution::<(EG, ES, EA)>::init_lhs_loader(lhs, x_offset, k_range.0, group, config),
+        GMM::Convolution::<(EG, ES, EA)>::init_rhs_loader(rhs, k_range.0, y_offset, group, config),
+        GMM::Convolution::<(EG, ES, EA)>::init_bias_loader(bias, y_offset, group, config, has_bias),
+        GMM::Convolution::<(EG, ES, EA)>::init_unloader(out, x_offset, y_offset, group, config),
         &mut GMM::Convolution::<(EG, ES, EA)>::init_accumulator(config),
         k_range,
         config,
@@ -313,8 +374,14 @@ pub mod config {
     use std::ops::Deref;
 
     use crate::{
-        convolution::ConvGemmConfig,
-        matmul::components::{MatmulConfig, TilingDimensions},
+        convolution::{
+            ConvGemmConfig,
+            base::{ConvolutionDepth, PaddingMode},
+        },
+        matmul::{
+            components::{MatmulConfig, TilingDimensions},
+            kernels::tiling2d::MatmulEpilogue,
+        },
     };
     use global::GlobalConfig;
 
@@ -328,7 +395,12 @@ pub mod config {
         stride: (u32, u32),
         dilation: (u32, u32),
         padding: (i32, i32),
+        padding_end: (i32, i32),
+        padding_mode: PaddingMode,
+        groups: u32,
+        depth: Option<ConvolutionDepth>,
         pub has_bias: bool,
+        epilogue: MatmulEpilogue,
     }
 
     impl<M: GlobalConfig> Deref for HomogeneousConfig<M> {
@@ -423,6 +495,30 @@ pub mod config {
                 _ => unreachable!(),
             }
         }
+
+        fn padding_end(&self, dim: u32) -> i32 {
+            match dim {
+                0 => self.padding_end.0,
+                1 => self.padding_end.1,
+                _ => unreachable!(),
+            }
+        }
+
+        fn padding_mode(&self) -> PaddingMode {
+            self.padding_mode
+        }
+
+        fn groups(&self) -> u32 {
+            self.groups
+        }
+
+        fn depth(&self) -> Option<ConvolutionDepth> {
+            self.depth
+        }
+
+        fn epilogue(&self) -> MatmulEpilogue {
+            self.epilogue
+        }
     }
 
     impl<M: GlobalConfig> MatmulConfig for HomogeneousConfig<M> {}
@@ -436,7 +532,12 @@ pub mod config {
             stride: (u32, u32),
             dilation: (u32, u32),
             padding: (i32, i32),
+            padding_end: (i32, i32),
+            padding_mode: PaddingMode,
+            groups: u32,
+            depth: Option<ConvolutionDepth>,
             has_bias: bool,
+            epilogue: MatmulEpilogue,
         ) -> Self {
             Self {
                 matmul,
@@ -445,7 +546,12 @@ pub mod config {
                 stride,
                 dilation,
                 padding,
+                padding_end,
+                padding_mode,
+                groups,
+                depth,
                 has_bias,
+                epilogue,
             }
         }
 
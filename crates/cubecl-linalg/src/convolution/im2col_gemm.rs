@@ -0,0 +1,159 @@
+use cubecl_core as cubecl;
+use cubecl_core::calculate_cube_count_elemwise;
+use cubecl_core::prelude::*;
+
+use crate::{
+    matmul::{
+        self, Strategy,
+        kernels::tiling2d::{MatmulEpilogue, activation},
+    },
+    tensor::{TensorHandle, into_contiguous},
+};
+
+use super::{
+    Conv2dOptions, ConvLaunchError, ConvUnimplementedError, check_output_shape,
+    conv2d::{merged_stride, rank_4},
+    conv2d_grouped_output_shape, im2col,
+    launch::BoundBias,
+};
+
+/// Writes one element of the `[batch * out_h * out_w, out_channels]` product per unit to the
+/// NHWC output, after adding the bias and applying the activation.
+#[cube(launch_unchecked)]
+fn im2col_output_kernel<E: Numeric>(
+    product: &Tensor<E>,
+    bias: &Tensor<E>,
+    output: &mut Tensor<E>,
+    #[comptime] has_bias: bool,
+    #[comptime] epilogue: MatmulEpilogue,
+) {
+    if ABSOLUTE_POS >= product.len() {
+        terminate!();
+    }
+
+    let out_channel = ABSOLUTE_POS % output.shape(3);
+    let position = ABSOLUTE_POS / output.shape(3);
+    let out_x = position % output.shape(2);
+    let rem = position / output.shape(2);
+    let out_y = rem % output.shape(1);
+    let batch = rem / output.shape(1);
+
+    let mut value = f32::cast_from(product[ABSOLUTE_POS]);
+    if comptime!(has_bias) {
+        value += f32::cast_from(bias[out_channel * bias.stride(0)]);
+    }
+
+    output[batch * output.stride(0)
+        + out_y * output.stride(1)
+        + out_x * output.stride(2)
+        + out_channel * output.stride(3)] = E::cast_from(activation::<f32>(value, epilogue));
+}
+
+/// Perform a 2D convolution by multiplying the [im2col] columns of the input with the weight,
+/// using the matmul `strategy`.
+///
+/// The tensors and options are those of [launch_conv2d](super::launch_conv2d). Unlike the
+/// implicit GEMM, the columns are written to global memory, which takes `kernel_h * kernel_w`
+/// times the memory of the input but lets any matmul kernel compute the product. An OIHW weight
+/// is copied as OHWI first. The bias and the activation of the options are applied in f32 while
+/// writing the product to the output, with any strides.
+///
+/// Returns [ConvUnimplementedError::Im2colGroups] with more than one group,
+/// [ConvLaunchError::InvalidRank] when the input or the weight doesn't have rank 4,
+/// [ConvLaunchError::ShapeMismatch] when the output doesn't have the shape of the
+/// convolution, the errors of [conv2d_grouped_output_shape] and those of the matmul.
+pub fn conv2d_im2col<R: Runtime, E: Numeric>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+) -> Result<(), ConvLaunchError> {
+    let [batches, in_h, in_w, channels] = rank_4(input.shape, "input")?;
+    let [out_channels, kernel_h, kernel_w, group_channels] = rank_4(weight.shape, "weight")?;
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, channels, in_h, in_w],
+        [out_channels, group_channels, kernel_h, kernel_w],
+        options.stride,
+        options.padding,
+        options.dilation,
+        options.groups,
+    )?;
+    check_output_shape(
+        "out",
+        out.shape,
+        &[batches, out_h, out_w, out_channels],
+        input.shape,
+        weight.shape,
+    )?;
+
+    if options.groups != 1 {
+        let groups = options.groups;
+        return Err(ConvUnimplementedError::Im2colGroups { groups }.into());
+    }
+
+    let shape_m = batches * out_h * out_w;
+    let shape_k = kernel_h * kernel_w * channels;
+    if shape_m == 0 || out_channels == 0 {
+        return Ok(());
+    }
+
+    let product = match shape_k {
+        // Without input channels, the product is zero.
+        0 => TensorHandle::<R, E>::zeros(client, vec![shape_m, out_channels]),
+        _ => {
+            let columns = im2col::<R, E>(client, input, (kernel_h, kernel_w), options)?;
+
+            // The `[out_channels, kernel_h, kernel_w, channels]` weight is the column-major
+            // `[k, out_channels]` weight of the product.
+            let weight = match merged_stride(&weight.shape[1..], &weight.strides[1..]) {
+                Ok(stride_k) => TensorHandle::<R, E>::new(
+                    vec![shape_k, out_channels],
+                    vec![stride_k, weight.strides[0]],
+                    weight.handle.clone(),
+                ),
+                Err(_) => {
+                    let contiguous = into_contiguous::<R, E>(client, weight);
+                    TensorHandle::new(
+                        vec![shape_k, out_channels],
+                        vec![1, shape_k],
+                        contiguous.handle,
+                    )
+                }
+            };
+
+            let product = TensorHandle::<R, E>::empty(client, vec![shape_m, out_channels]);
+            matmul::launch_ref::<R, E>(
+                strategy,
+                client,
+                &columns.as_ref(),
+                &weight.as_ref(),
+                &product.as_ref(),
+            )?;
+
+            product
+        }
+    };
+
+    let bound_bias = BoundBias::<R, E>::new(client, bias, out_channels);
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(shape_m * out_channels, cube_dim);
+
+    unsafe {
+        im2col_output_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            product.as_ref().as_tensor_arg(1),
+            bound_bias.as_arg(1),
+            out.as_tensor_arg(1),
+            bias.is_some(),
+            options.epilogue,
+        );
+    }
+
+    Ok(())
+}
@@ -1,16 +1,21 @@
-use cubecl_core::{
-    Runtime,
-    client::ComputeClient,
-    prelude::{Numeric, TensorArg},
-};
+use cubecl_core as cubecl;
+use cubecl_core::{calculate_cube_count_elemwise, prelude::*};
 
-use crate::matmul::kernels::MatmulLaunchError;
-use crate::{convolution::base::ConvolutionLaunch, matmul::components::MatmulPrecision};
+use crate::matmul::kernels::{
+    MatmulInvalidProblem, MatmulLaunchContext, MatmulLaunchError, check_cube_dim,
+    tiling2d::{MatmulEpilogue, activation_line},
+};
+use crate::{
+    convolution::base::ConvolutionLaunch,
+    matmul::components::{MatmulPrecision, MatrixLayout},
+    tensor::{TensorHandle, checked_u32_indexing},
+};
 
 use super::{
     ConvLaunchError,
     algorithm::Algorithm,
     base::{ConvolutionConfigFactory, ConvolutionProblem},
+    depthwise::{is_depthwise, launch_depthwise_conv2d},
     selection::ConvSelector,
 };
 
@@ -21,6 +26,28 @@ use super::{
 /// * `weight` - The weights (filter) applied to each kernel
 /// * `bias` - The bias added to each channel
 /// * `options` - The options to use for the convolution
+///
+/// The tensors hold `SP::EG` elements, staged in shared memory as `SP::ES`, and the products are
+/// summed as `SP::EA`, the accumulator being initialized with the bias and the epilogue applied
+/// in `SP::EA` as well. The output is only converted to `SP::EG` when written, so e.g.
+/// `(f16, f16, f32)` keeps the bandwidth and shared memory of f16 while summing a large k in f32.
+///
+/// An empty output, with no batch, position or output channel, returns without launching
+/// anything. Without input channels, k is 0 and the output is filled with the bias, or with zeros
+/// without bias.
+///
+/// A grouped convolution, see [groups](ConvolutionProblem::groups), computes every group in the
+/// same launch, the cubes of each group reading its slice of the input channels and writing its
+/// slice of the output channels. Depthwise convolutions, with a single input and output channel
+/// per group, are computed by a dedicated kernel instead when every tensor has the same line size,
+/// accumulating each kernel window directly.
+///
+/// With a [depth](ConvolutionProblem::depth), the input is NDHWC and the convolution is 3D, see
+/// [launch_conv3d_ndhwc](super::launch_conv3d_ndhwc) to launch one from its tensors.
+///
+/// When the cubes don't fit in the maximum cube count of the device, e.g. with many batches or
+/// large outputs, they are split into several launches. A tensor with more than `u32::MAX`
+/// elements returns [SizeOverflow](MatmulInvalidProblem::SizeOverflow) instead.
 pub fn launch_conv2d_nhwc<R: Runtime, SP: MatmulPrecision, Alg: Algorithm, S: ConvSelector<Alg>>(
     client: &ComputeClient<R::Server, R::Channel>,
     input: TensorArg<R>,
@@ -32,28 +59,263 @@ pub fn launch_conv2d_nhwc<R: Runtime, SP: MatmulPrecision, Alg: Algorithm, S: Co
 where
     SP::EG: Numeric,
 {
-    let plane_dim = client
-        .properties()
-        .hardware_properties()
-        .defined_plane_size()
-        .unwrap_or(32);
+    // The batches and the output positions are all part of m, each group is a separate product.
+    let with_context = |error: MatmulLaunchError| {
+        error.with_context(MatmulLaunchContext {
+            m: problem.m,
+            k: problem.k,
+            n: problem.n,
+            batches: problem.groups,
+            elem: SP::EG::as_elem_native_unchecked(),
+            config: None,
+        })
+    };
+
+    if problem.groups == 0 {
+        return Err(ConvLaunchError::Groups {
+            groups: 0,
+            in_channels: problem.k / problem.kernel_volume().max(1),
+            out_channels: problem.n,
+        });
+    }
+
+    if problem.m == 0 || problem.n == 0 {
+        return Ok(());
+    }
+    // Every kernel below indexes the tensors with u32.
+    for arg in [&input, &weight, &bias, &out] {
+        if let TensorArg::Handle { handle, .. } = arg {
+            checked_u32_indexing(handle.shape).map_err(|error| with_context(error.into()))?;
+        }
+    }
+    if problem.k == 0 {
+        launch_fill_bias::<R, SP::EG, SP::EA>(client, bias, out, &problem);
+        return Ok(());
+    }
+    if is_depthwise(&problem) {
+        launch_depthwise_conv2d::<R, SP::EG, SP::EA>(client, input, weight, bias, out, &problem);
+        return Ok(());
+    }
+
+    check_group_line_sizes(&problem).map_err(with_context)?;
+
+    let hardware = client.properties().hardware_properties();
+    let plane_dim = hardware.defined_plane_size().unwrap_or(32);
 
     let (selection, config_input) = S::select_kernel::<R, SP>(client, &problem, plane_dim);
     let cube_dim = Alg::cube_dim(&selection);
     let cube_count = Alg::cube_count(&selection, &problem);
 
+    check_cube_dim(cube_dim, hardware.max_units_per_cube)
+        .map_err(|error| with_context(error.into()))?;
+
     let config = Alg::make_config(config_input, &problem, &cube_dim, &cube_count)
-        .map_err(MatmulLaunchError::InvalidConfig)?;
+        .map_err(|error| with_context(MatmulLaunchError::InvalidConfig(error)))?;
 
     <Alg::GlobalConvolution as ConvolutionConfigFactory>::check_availability::<R, SP>(
         client, &config,
-    )?;
+    )
+    .map_err(|error| with_context(error.into()))?;
+
+    // m grows with every spatial dimension of the output, the depth included in 3D.
+    for (cube_offset, cube_count) in split_cube_count(cube_count, hardware.max_cube_count) {
+        unsafe {
+            Alg::GlobalConvolution::launch_unchecked::<SP, R>(
+                client,
+                cube_dim,
+                cube_count,
+                cube_offset,
+                input.clone(),
+                weight.clone(),
+                bias.clone(),
+                out.clone(),
+                config,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Split `cube_count` into launches of at most `max` cubes along each dimension, with the
+/// position of the first cube of each launch, to add to the cube positions of its kernel.
+pub(super) fn split_cube_count(
+    cube_count: CubeCount,
+    max: CubeDim,
+) -> Vec<((u32, u32, u32), CubeCount)> {
+    let CubeCount::Static(cubes_x, cubes_y, cubes_z) = cube_count else {
+        unreachable!("The convolution cube counts are static")
+    };
+    let ranges = |cubes: u32, max: u32| {
+        let max = max.max(1);
+        (0..cubes)
+            .step_by(max as usize)
+            .map(move |first| (first, max.min(cubes - first)))
+    };
+
+    let mut launches = Vec::new();
+    for (first_z, z) in ranges(cubes_z, max.z) {
+        for (first_y, y) in ranges(cubes_y, max.y) {
+            for (first_x, x) in ranges(cubes_x, max.x) {
+                launches.push(((first_x, first_y, first_z), CubeCount::Static(x, y, z)));
+            }
+        }
+    }
+
+    launches
+}
+
+/// The bias bound by a convolution kernel, which binds one even when it doesn't read it.
+pub(super) enum BoundBias<'a, R: Runtime, E: Numeric> {
+    /// The bias of the convolution
+    Bias(&'a TensorHandleRef<'a, R>),
+    /// An uninitialized tensor standing for the missing bias
+    Unused(TensorHandle<R, E>),
+}
+
+impl<'a, R: Runtime, E: Numeric> BoundBias<'a, R, E> {
+    /// Bind `bias`, or an unused tensor of `channels` elements without one.
+    pub(super) fn new(
+        client: &ComputeClient<R::Server, R::Channel>,
+        bias: Option<&'a TensorHandleRef<'a, R>>,
+        channels: usize,
+    ) -> Self {
+        match bias {
+            Some(bias) => Self::Bias(bias),
+            None => Self::Unused(TensorHandle::empty(client, vec![channels])),
+        }
+    }
+
+    pub(super) fn as_arg(&self, line_size: u8) -> TensorArg<'_, R> {
+        match self {
+            Self::Bias(bias) => bias.as_tensor_arg(line_size),
+            Self::Unused(bias) => bias.as_arg(line_size),
+        }
+    }
+}
+
+/// Check that the channels of every group start on a line of each tensor vectorized along them.
+fn check_group_line_sizes(problem: &ConvolutionProblem) -> Result<(), MatmulLaunchError> {
+    if problem.groups == 1 {
+        return Ok(());
+    }
+
+    let group_channels = problem.k / problem.kernel_volume().max(1);
+
+    let divides = |size: usize, line_size: u8| size % line_size as usize == 0;
+    let error = if !divides(group_channels, problem.lhs_line_size) {
+        MatmulInvalidProblem::InvalidLineSizeLhs {
+            size: group_channels as u32,
+            line_size: problem.lhs_line_size,
+        }
+    } else if problem.rhs_layout == MatrixLayout::RowMajor
+        && !divides(problem.n, problem.rhs_line_size)
+    {
+        MatmulInvalidProblem::InvalidLineSizeRhs {
+            size: problem.n as u32,
+            line_size: problem.rhs_line_size,
+        }
+    } else if !divides(problem.n, problem.out_line_size) {
+        MatmulInvalidProblem::InvalidLineSizeOut {
+            size: problem.n as u32,
+            line_size: problem.out_line_size,
+        }
+    } else {
+        return Ok(());
+    };
+
+    Err(error.into())
+}
+
+/// Writes the bias of its channel, or 0 without bias, to every line of the `[m, n]` output, where
+/// the bias holds one line per line of a row. The activation is applied in `A`, e.g. a sigmoid
+/// writes 0.5 without bias.
+#[cube(launch_unchecked)]
+fn fill_bias_kernel<E: Numeric, A: Numeric>(
+    bias: &Tensor<Line<E>>,
+    out: &mut Tensor<Line<E>>,
+    #[comptime] has_bias: bool,
+    #[comptime] epilogue: MatmulEpilogue,
+) {
+    if ABSOLUTE_POS >= out.len() {
+        terminate!();
+    }
+
+    let mut value = Line::empty(out.line_size()).fill(A::from_int(0));
+    if comptime!(has_bias) {
+        value = Line::<A>::cast_from(bias[ABSOLUTE_POS % bias.len()]);
+    }
+
+    out[ABSOLUTE_POS] = Line::cast_from(activation_line::<A>(value, epilogue));
+}
+
+/// Output of a convolution with k = 0, where no product contributes to the output, for all the
+/// groups at once.
+fn launch_fill_bias<R: Runtime, E: Numeric, A: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    bias: TensorArg<R>,
+    out: TensorArg<R>,
+    problem: &ConvolutionProblem,
+) {
+    let num_lines = problem.m * problem.n * problem.groups / problem.out_line_size as usize;
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
 
     unsafe {
-        Alg::GlobalConvolution::launch_unchecked::<SP, R>(
-            client, cube_dim, cube_count, input, weight, bias, out, config,
+        fill_bias_kernel::launch_unchecked::<E, A, R>(
+            client,
+            cube_count,
+            cube_dim,
+            bias,
+            out,
+            problem.has_bias,
+            problem.epilogue,
         );
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launches(cube_count: (u32, u32, u32), max: u32) -> Vec<((u32, u32, u32), (u32, u32, u32))> {
+        let (x, y, z) = cube_count;
+        split_cube_count(CubeCount::Static(x, y, z), CubeDim::new(max, max, max))
+            .into_iter()
+            .map(|(offset, cube_count)| match cube_count {
+                CubeCount::Static(x, y, z) => (offset, (x, y, z)),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
 
-    Ok(())
+    #[test]
+    fn cube_count_under_the_limit_uses_one_launch() {
+        assert_eq!(launches((7, 3, 2), 65535), vec![((0, 0, 0), (7, 3, 2))]);
+    }
+
+    #[test]
+    fn cube_count_over_the_limit_is_split() {
+        assert_eq!(
+            launches((10, 1, 1), 4),
+            vec![
+                ((0, 0, 0), (4, 1, 1)),
+                ((4, 0, 0), (4, 1, 1)),
+                ((8, 0, 0), (2, 1, 1))
+            ]
+        );
+    }
+
+    #[test]
+    fn cube_count_over_the_limit_is_split_along_every_dimension() {
+        assert_eq!(
+            launches((5, 2, 6), 4),
+            vec![
+                ((0, 0, 0), (4, 2, 4)),
+                ((4, 0, 0), (1, 2, 4)),
+                ((0, 0, 4), (4, 2, 2)),
+                ((4, 0, 4), (1, 2, 2))
+            ]
+        );
+    }
 }
@@ -19,6 +19,8 @@ use crate::{
 pub struct BiasLoader<CS: MatmulPrecision, G: StageConfig> {
     pub tensor_view: BiasReader<CS::EG>,
     pub stage: Stage<CS::EA, ConvTilingLayout>,
+    /// Without bias, the accumulator is zeroed and nothing is read or staged
+    #[cube(comptime)]
     pub has_bias: bool,
     #[cube(comptime)]
     _config: PhantomData<G>,
@@ -29,7 +31,7 @@ impl<CS: MatmulPrecision, G: StageConfig> AccumulatorLoader<CS::EG, CS::EA, G>
     for BiasLoader<CS, G>
 {
     fn fill_stage(this: &mut Self, #[comptime] config: G) {
-        if this.has_bias {
+        if comptime!(this.has_bias) {
             let stage_tiling = config.tiling_dimensions(Ident::Rhs);
             let line_size = config.line_size(Ident::Out);
 
@@ -56,7 +58,7 @@ impl<CS: MatmulPrecision, G: StageConfig> AccumulatorLoader<CS::EG, CS::EA, G>
         tile_n: u32,
         #[comptime] config: TMM::Config,
     ) {
-        if this.has_bias {
+        if comptime!(this.has_bias) {
             let line_size = config.line_size(Ident::Out);
             let tile_elems = config.tile_shape().n / line_size;
             let start = tile_n * tile_elems;
@@ -60,16 +60,24 @@ impl<CS: MatmulPrecision, G: ConvGemmConfig> SimpleIm2colLoader<CS, G> {
         shape_out_x: u32,
         x_offset: u32,
         y_offset: u32,
+        group: u32,
         #[comptime] config: G,
     ) -> Self {
         let stage = Stage::new::<G::SmmConfig>(Ident::Lhs, config.to_smm_config());
         let shape_batch = tensor.shape(0);
-        let shape_channel = tensor.shape(3);
-
-        let shape_m = shape_batch * shape_out_y * shape_out_x;
-        let shape_k = shape_channel * config.kernel_size(0) * config.kernel_size(1);
+        let channel_axis = comptime!(3 + config.depth().is_some() as u32);
+        // The channels of the group, the only ones multiplied by its weight.
+        let shape_channel = tensor.shape(channel_axis) / config.groups();
+
+        let mut shape_m = shape_batch * shape_out_y * shape_out_x;
+        let mut shape_k = shape_channel * config.kernel_size(0) * config.kernel_size(1);
+        if comptime!(config.depth().is_some()) {
+            let depth = comptime!(config.depth().unwrap());
+            shape_m *= depth.out_shape;
+            shape_k *= depth.kernel_size;
+        }
 
-        let tensor_view = Im2colReader::<CS::EG>::new(
+        let tensor_view = Im2colReader::<CS::EG>::new::<G>(
             tensor,
             shape_out_y,
             shape_out_x,
@@ -77,7 +85,9 @@ impl<CS: MatmulPrecision, G: ConvGemmConfig> SimpleIm2colLoader<CS, G> {
             y_offset,
             shape_k,
             shape_channel,
+            group * shape_channel,
             shape_m,
+            config,
         );
 
         SimpleIm2colLoader::<CS, G> {
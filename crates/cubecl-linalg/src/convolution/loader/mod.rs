@@ -1,2 +1,3 @@
 pub mod bias;
 pub mod im2col;
+pub mod unloader;
@@ -0,0 +1,99 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_std::tensor::r#virtual::{ReadWrite, VirtualTensor};
+
+use crate::matmul::{
+    components::{
+        Ident, MatmulPrecision,
+        global::{
+            self, GlobalConfig, tensor_view::TensorWriter, tilewise_unloading::TilewiseUnloading,
+        },
+        stage::StageWriter,
+    },
+    kernels::tiling2d::{MatmulEpilogue, activation_line},
+};
+
+/// Writes the output of the convolution like the matmul [Unloader](global::output_loader::Unloader),
+/// applying the activation of the convolution to each line on the way out.
+///
+/// The activation is applied after the bias the accumulator was initialized with, in the
+/// accumulator type, which the stage matmul stages the accumulator in for this writer. The result
+/// is only converted to the output type when written, e.g. an f16 convolution accumulated in f32
+/// rounds once. Without activation, the output is written exactly like the matmul does.
+#[derive(CubeType)]
+pub struct EpilogueUnloader<CS: MatmulPrecision> {
+    pub tensor_view: TensorWriter<CS::EG>,
+    #[cube(comptime)]
+    pub epilogue: MatmulEpilogue,
+}
+
+#[cube]
+impl<CS: MatmulPrecision> global::OutputLoader<CS::EG> for EpilogueUnloader<CS> {
+    type StageWriter = Self;
+
+    fn as_stage_writer<G: GlobalConfig>(this: Self) -> Self::StageWriter {
+        this
+    }
+}
+
+#[cube]
+impl<CS: MatmulPrecision> EpilogueUnloader<CS> {
+    pub fn new(
+        tensor: VirtualTensor<CS::EG, ReadWrite>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        #[comptime] epilogue: MatmulEpilogue,
+    ) -> Self {
+        EpilogueUnloader::<CS> {
+            tensor_view: TensorWriter::new(tensor, x_offset, y_offset, batch_offset),
+            epilogue,
+        }
+    }
+}
+
+#[cube]
+impl<CS: MatmulPrecision> StageWriter<CS::EG> for EpilogueUnloader<CS> {
+    type Staging = CS::EA;
+
+    fn write<ES: Numeric, G: GlobalConfig>(
+        this: &mut Self,
+        slice: Slice<Line<ES>>,
+        compute_plane_offset: u32,
+        accumulator_offset: u32,
+        #[comptime] config: G,
+    ) {
+        match comptime!(this.epilogue) {
+            MatmulEpilogue::None => {
+                TilewiseUnloading::unload_from_slice::<CS::EG, ES, G>(
+                    &mut this.tensor_view,
+                    slice,
+                    compute_plane_offset,
+                    accumulator_offset,
+                    config,
+                );
+            }
+            _ => {
+                let tiling = config.tiling_dimensions(Ident::Out);
+                let out_line_size = config.global_line_size(Ident::Out);
+
+                let unit_step = config.plane_dim() * out_line_size;
+                let num_unit_writes = tiling.tile_size() / unit_step;
+
+                for i in 0..num_unit_writes {
+                    let unit_write = UNIT_POS_X * out_line_size + i * unit_step;
+
+                    let value = Line::<CS::EA>::cast_from(slice[unit_write / out_line_size]);
+                    let value = activation_line::<CS::EA>(value, comptime!(this.epilogue));
+                    this.tensor_view.write_coalesced::<CS::EA, G>(
+                        compute_plane_offset,
+                        accumulator_offset,
+                        unit_write,
+                        value,
+                        config,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,45 @@
 mod config;
 
 pub mod algorithm;
+mod backward;
 pub mod base;
+mod conv1d;
+mod conv2d;
+mod conv3d;
+mod conv_transpose;
+mod depthwise;
 pub mod error;
 pub mod homogeneous;
+mod im2col_gemm;
 pub mod launch;
 pub mod loader;
+mod pool;
 pub mod reader;
 pub mod selection;
+mod shape;
+mod tune;
+mod unfold;
+mod winograd;
 
+pub use backward::*;
 pub use config::*;
+pub use conv_transpose::*;
+pub use conv1d::*;
+pub use conv2d::*;
+pub use conv3d::*;
+pub use depthwise::*;
 pub use error::*;
+pub use im2col_gemm::*;
 pub use launch::*;
+pub use pool::*;
+pub use shape::*;
+pub use tune::*;
+pub use unfold::*;
+pub use winograd::*;
+
+/// Autotune key for convolutions.
+pub mod tune_key;
+
+/// Tests for convolution primitives
+#[cfg(feature = "export_tests")]
+pub mod tests;
@@ -0,0 +1,333 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_core::{CubeType, calculate_cube_count_elemwise, tensor_line_size_parallel};
+
+use crate::tensor::TensorHandle;
+
+use super::{ConvLaunchError, pool2d_output_shape};
+
+/// Options of a 2D pooling, each spatial parameter being `(height, width)`.
+///
+/// Padding is applied on both sides of each spatial dimension and is never read: max pooling
+/// ignores it and average pooling only counts it when
+/// [count_include_pad](Pool2dOptions::count_include_pad) is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pool2dOptions {
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    /// At most half of the kernel size, so that every window reads the input
+    pub padding: (usize, usize),
+    /// Whether [avg_pool2d] divides by the size of the kernel rather than by the number of
+    /// input elements in the window. Ignored by [max_pool2d]
+    pub count_include_pad: bool,
+}
+
+impl Pool2dOptions {
+    /// Options of a pooling over non-overlapping windows of `kernel_size`, without padding.
+    pub fn new(kernel_size: (usize, usize)) -> Self {
+        Self {
+            kernel_size,
+            stride: kernel_size,
+            padding: (0, 0),
+            count_include_pad: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
+/// Window parameters of the pooling kernels known at compile time.
+struct Pool2dConfig {
+    kernel_h: u32,
+    kernel_w: u32,
+    stride_h: u32,
+    stride_w: u32,
+    padding_h: i32,
+    padding_w: i32,
+}
+
+impl Init for Pool2dConfig {
+    fn init(self, _scope: &mut Scope) -> Self {
+        self
+    }
+}
+
+impl Pool2dConfig {
+    fn new(options: &Pool2dOptions) -> Self {
+        Self {
+            kernel_h: options.kernel_size.0 as u32,
+            kernel_w: options.kernel_size.1 as u32,
+            stride_h: options.stride.0 as u32,
+            stride_w: options.stride.1 as u32,
+            padding_h: options.padding.0 as i32,
+            padding_w: options.padding.1 as i32,
+        }
+    }
+}
+
+/// Writes the maximum of one line of channels of a window per unit, and with `with_indices` the
+/// position `y * width + x` in the input of the first maximum of each channel.
+#[cube(launch_unchecked)]
+fn max_pool2d_kernel<E: Numeric>(
+    input: &Tensor<Line<E>>,
+    output: &mut Tensor<Line<E>>,
+    indices: &mut Tensor<Line<u32>>,
+    #[comptime] config: Pool2dConfig,
+    #[comptime] with_indices: bool,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let line_size = output.line_size();
+    let (batch, out_y, out_x, channel) = output_position(output);
+    let (shape_y, shape_x) = (input.shape(1), input.shape(2));
+
+    // The padding reads the minimum, so it never wins over an element of the input.
+    let mut max = Line::empty(line_size).fill(E::min_value());
+    let mut index = Line::empty(line_size).fill(0u32);
+    let mut found = false;
+
+    for kernel_y in 0..config.kernel_h {
+        let y = (out_y * config.stride_h + kernel_y) as i32 - config.padding_h;
+
+        for kernel_x in 0..config.kernel_w {
+            let x = (out_x * config.stride_w + kernel_x) as i32 - config.padding_w;
+
+            if y >= 0 && (y as u32) < shape_y && x >= 0 && (x as u32) < shape_x {
+                let value = input[(batch * input.stride(0)
+                    + y as u32 * input.stride(1)
+                    + x as u32 * input.stride(2)
+                    + channel * input.stride(3))
+                    / line_size];
+                let position = Line::empty(line_size).fill(y as u32 * shape_x + x as u32);
+
+                let mut greater = value.greater_than(max);
+                if !found {
+                    // The first element is always taken, even when it is the minimum itself.
+                    greater = Line::empty(line_size).fill(true);
+                }
+                max = select_many(greater, value, max);
+                if comptime!(with_indices) {
+                    index = select_many(greater, position, index);
+                }
+                found = true;
+            }
+        }
+    }
+
+    output[ABSOLUTE_POS] = max;
+    if comptime!(with_indices) {
+        indices[ABSOLUTE_POS] = index;
+    }
+}
+
+/// Writes the average of one line of channels of a window per unit, summed in f32.
+#[cube(launch_unchecked)]
+fn avg_pool2d_kernel<E: Numeric>(
+    input: &Tensor<Line<E>>,
+    output: &mut Tensor<Line<E>>,
+    #[comptime] config: Pool2dConfig,
+    #[comptime] count_include_pad: bool,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let line_size = output.line_size();
+    let (batch, out_y, out_x, channel) = output_position(output);
+    let (shape_y, shape_x) = (input.shape(1), input.shape(2));
+
+    let mut sum = Line::empty(line_size).fill(f32::new(0.0));
+    let mut count = 0u32;
+
+    for kernel_y in 0..config.kernel_h {
+        let y = (out_y * config.stride_h + kernel_y) as i32 - config.padding_h;
+
+        for kernel_x in 0..config.kernel_w {
+            let x = (out_x * config.stride_w + kernel_x) as i32 - config.padding_w;
+
+            if y >= 0 && (y as u32) < shape_y && x >= 0 && (x as u32) < shape_x {
+                sum += Line::<f32>::cast_from(
+                    input[(batch * input.stride(0)
+                        + y as u32 * input.stride(1)
+                        + x as u32 * input.stride(2)
+                        + channel * input.stride(3))
+                        / line_size],
+                );
+                count += 1;
+            }
+        }
+    }
+
+    // Windows never extend past the padding, so every window of the padded input has the size
+    // of the kernel.
+    let mut divisor = f32::cast_from(count);
+    if comptime!(count_include_pad) {
+        divisor = f32::cast_from(config.kernel_h * config.kernel_w);
+    }
+    let divisor = Line::empty(line_size).fill(divisor);
+
+    output[ABSOLUTE_POS] = Line::cast_from(sum / divisor);
+}
+
+/// The `(batch, out_y, out_x, channel)` position of the line of the contiguous NHWC output
+/// written by the unit.
+#[cube]
+fn output_position<E: Numeric>(output: &Tensor<Line<E>>) -> (u32, u32, u32, u32) {
+    let index = ABSOLUTE_POS * output.line_size();
+    let channel = index % output.shape(3);
+    let position = index / output.shape(3);
+    let out_x = position % output.shape(2);
+    let rem = position / output.shape(2);
+    let out_y = rem % output.shape(1);
+    let batch = rem / output.shape(1);
+
+    (batch, out_y, out_x, channel)
+}
+
+/// Compute the maximum of each window of the NHWC `input`, into a new contiguous
+/// `[batch, out_h, out_w, channels]` tensor of the shape given by
+/// [pool2d_output_shape](super::pool2d_output_shape).
+///
+/// One line of channels of an output position is computed per unit, the channels being read in
+/// lines when they are contiguous. The padding never contributes to the maximum.
+///
+/// Returns the errors of [pool2d_output_shape](super::pool2d_output_shape).
+///
+/// # Panics
+///
+/// If `input` isn't a rank 4 tensor.
+pub fn max_pool2d<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    options: &Pool2dOptions,
+) -> Result<TensorHandle<R, E>, ConvLaunchError> {
+    launch_max_pool2d::<R, E>(client, input, options, false).map(|(output, _)| output)
+}
+
+/// Compute the maximum of each window of the NHWC `input` like [max_pool2d], along with the
+/// `[batch, out_h, out_w, channels]` indices of the maxima, used to route the gradient of the
+/// output back to the input.
+///
+/// Each index is the position `y * width + x` of the maximum in the `[height, width]` plane of
+/// its batch and channel, the first one in the window when several elements are equal.
+pub fn max_pool2d_with_indices<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    options: &Pool2dOptions,
+) -> Result<(TensorHandle<R, E>, TensorHandle<R, u32>), ConvLaunchError> {
+    launch_max_pool2d::<R, E>(client, input, options, true)
+        .map(|(output, indices)| (output, indices.unwrap()))
+}
+
+fn launch_max_pool2d<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    options: &Pool2dOptions,
+    with_indices: bool,
+) -> Result<(TensorHandle<R, E>, Option<TensorHandle<R, u32>>), ConvLaunchError> {
+    let output_shape = pool2d_output_shape_nhwc(input, options)?;
+    let output = TensorHandle::<R, E>::empty(client, output_shape.to_vec());
+    // The kernel binds the indices even when it doesn't write them.
+    let indices = TensorHandle::<R, u32>::empty(
+        client,
+        match with_indices {
+            true => output_shape.to_vec(),
+            false => vec![1],
+        },
+    );
+
+    let line_size = pool_line_size::<R, E>(input);
+    // The indices are written in lines of the output, so its line size must be supported for u32
+    // as well, e.g. not 8 for f16 on a device with lines of at most 4 u32.
+    let (line_size, indices_line_size) = match with_indices {
+        true => {
+            let line_size = line_size.min(pool_line_size::<R, u32>(input));
+            (line_size, line_size)
+        }
+        false => (line_size, 1),
+    };
+    let cube_dim = CubeDim::default();
+    let num_lines = output_shape.iter().product::<usize>() / line_size as usize;
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+    unsafe {
+        max_pool2d_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size),
+            output.as_ref().as_tensor_arg(line_size),
+            indices.as_ref().as_tensor_arg(indices_line_size),
+            Pool2dConfig::new(options),
+            with_indices,
+        );
+    }
+
+    Ok((output, with_indices.then_some(indices)))
+}
+
+/// Compute the average of each window of the NHWC `input`, into a new contiguous
+/// `[batch, out_h, out_w, channels]` tensor of the shape given by
+/// [pool2d_output_shape](super::pool2d_output_shape).
+///
+/// The elements are summed in f32 and divided by the size of the kernel with
+/// [count_include_pad](Pool2dOptions::count_include_pad), otherwise by the number of input
+/// elements in the window. One line of channels of an output position is computed per unit, the
+/// channels being read in lines when they are contiguous.
+///
+/// Returns the errors of [pool2d_output_shape](super::pool2d_output_shape).
+///
+/// # Panics
+///
+/// If `input` isn't a rank 4 tensor.
+pub fn avg_pool2d<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    options: &Pool2dOptions,
+) -> Result<TensorHandle<R, E>, ConvLaunchError> {
+    let output_shape = pool2d_output_shape_nhwc(input, options)?;
+    let output = TensorHandle::<R, E>::empty(client, output_shape.to_vec());
+
+    let line_size = pool_line_size::<R, E>(input);
+    let cube_dim = CubeDim::default();
+    let num_lines = output_shape.iter().product::<usize>() / line_size as usize;
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+    unsafe {
+        avg_pool2d_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size),
+            output.as_ref().as_tensor_arg(line_size),
+            Pool2dConfig::new(options),
+            options.count_include_pad,
+        );
+    }
+
+    Ok(output)
+}
+
+/// The `[batch, out_h, out_w, channels]` shape of the pooling of the NHWC `input`.
+fn pool2d_output_shape_nhwc<R: Runtime>(
+    input: &TensorHandleRef<'_, R>,
+    options: &Pool2dOptions,
+) -> Result<[usize; 4], ConvLaunchError> {
+    assert_eq!(
+        input.shape.len(),
+        4,
+        "Pooling input should be an NHWC tensor"
+    );
+
+    let [batches, in_h, in_w, channels] = [0, 1, 2, 3].map(|dim| input.shape[dim]);
+    let [_, _, out_h, out_w] = pool2d_output_shape([batches, channels, in_h, in_w], options)?;
+
+    Ok([batches, out_h, out_w, channels])
+}
+
+/// The line size of the channels of `input` for elements of type `E`.
+fn pool_line_size<R: Runtime, E: Numeric>(input: &TensorHandleRef<'_, R>) -> u8 {
+    let elem = E::as_elem_native_unchecked();
+    tensor_line_size_parallel(R::line_size_elem(&elem), input.shape, input.strides, 3)
+}
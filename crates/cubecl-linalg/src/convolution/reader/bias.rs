@@ -35,11 +35,13 @@ impl<E: Numeric> BiasReader<E> {
         let view_n = self.n_offset + unit_id;
         let read_pos = view_n / line_size;
 
-        select(
-            view_n < self.shape_n,
-            self.read(read_pos),
-            Line::empty(line_size).fill(E::from_int(0)),
-        )
+        // The stage can be wider than the channels left, which must not be read.
+        let mut line = Line::empty(line_size).fill(E::from_int(0));
+        if view_n < self.shape_n {
+            line = self.read(read_pos);
+        }
+
+        line
     }
 
     fn read(&self, position: u32) -> Line<E> {
@@ -2,7 +2,10 @@ use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 use cubecl_std::tensor::r#virtual::VirtualTensor;
 
-use crate::{convolution::ConvGemmConfig, matmul::components::Ident};
+use crate::{
+    convolution::{ConvGemmConfig, base::PaddingMode},
+    matmul::components::Ident,
+};
 
 #[derive(CubeType)]
 /// A view of a feature map tensor that starts reading data from a specified offset.
@@ -14,14 +17,20 @@ pub struct Im2colReader<E: Numeric> {
     pub k_offset: u32,
 
     pub stride_batch: u32,
+    /// Stride of the depth of a 3D convolution, unused in 2D
+    pub stride_z: u32,
     pub stride_y: u32,
     pub stride_x: u32,
     pub stride_channel: u32,
+    /// First input channel of the group read by the view
+    pub channel_offset: u32,
 
+    pub shape_z: u32,
     pub shape_y: u32,
     pub shape_x: u32,
     pub shape_channel: u32,
 
+    pub shape_out_z: u32,
     pub shape_out_y: u32,
     pub shape_out_x: u32,
 
@@ -31,8 +40,9 @@ pub struct Im2colReader<E: Numeric> {
 
 #[cube]
 impl<E: Numeric> Im2colReader<E> {
+    /// The tensor is NHWC, or NDHWC when the config has a [depth](ConvGemmConfig::depth).
     #[allow(clippy::too_many_arguments)]
-    pub fn new(
+    pub fn new<G: ConvGemmConfig>(
         tensor: VirtualTensor<E>,
         shape_out_y: u32,
         shape_out_x: u32,
@@ -40,26 +50,43 @@ impl<E: Numeric> Im2colReader<E> {
         y_offset: u32,
         shape_k: u32,
         shape_channel: u32,
+        channel_offset: u32,
         shape_m: u32,
+        #[comptime] config: G,
     ) -> Im2colReader<E> {
+        let depth_axes = comptime!(config.depth().is_some() as u32);
+
+        let mut stride_z = 0;
+        let mut shape_z = 1;
+        let mut shape_out_z = 1;
+        if comptime!(config.depth().is_some()) {
+            stride_z = tensor.stride(1);
+            shape_z = tensor.shape(1);
+            shape_out_z = comptime!(config.depth().unwrap().out_shape);
+        }
+
         let stride_batch = tensor.stride(0);
-        let stride_y = tensor.stride(1);
-        let stride_x = tensor.stride(2);
-        let stride_channel = tensor.stride(3);
-        let shape_y = tensor.shape(1);
-        let shape_x = tensor.shape(2);
+        let stride_y = tensor.stride(1 + depth_axes);
+        let stride_x = tensor.stride(2 + depth_axes);
+        let stride_channel = tensor.stride(3 + depth_axes);
+        let shape_y = tensor.shape(1 + depth_axes);
+        let shape_x = tensor.shape(2 + depth_axes);
 
         Im2colReader::<E> {
             tensor,
             m_offset: x_offset,
             k_offset: y_offset,
             stride_batch,
+            stride_z,
             stride_y,
             stride_x,
             stride_channel,
+            channel_offset,
+            shape_z,
             shape_y,
             shape_x,
             shape_channel,
+            shape_out_z,
             shape_out_y,
             shape_out_x,
             shape_m,
@@ -87,7 +114,8 @@ impl<E: Numeric> Im2colReader<E> {
     ///
     /// # Note
     ///
-    /// Out-of-bounds reads will be translated to zeros.
+    /// Out-of-bounds reads will be translated to zeros. Reads in the padding are resolved according
+    /// to the [padding mode](PaddingMode) of the config.
     pub fn load_simple<G: ConvGemmConfig>(
         &self,
         tile_x: u32,
@@ -112,30 +140,73 @@ impl<E: Numeric> Im2colReader<E> {
         let out_x = view_m % self.shape_out_x;
         let rem = view_m / self.shape_out_x;
         let out_y = rem % self.shape_out_y;
-        let batch = rem / self.shape_out_y;
+        let mut batch = rem / self.shape_out_y;
 
         let kernel_w = config.kernel_size(1);
 
         let channel = view_k % self.shape_channel;
         let rem = view_k / self.shape_channel;
         let kernel_x = rem % kernel_w;
-        let kernel_y = rem / kernel_w;
+        let mut kernel_y = rem / kernel_w;
+
+        // In 3D, the depth is the outermost spatial dimension of both m and k.
+        let mut out_z = 0;
+        let mut kernel_z = 0;
+        if comptime!(config.depth().is_some()) {
+            let kernel_h = config.kernel_size(0);
+            out_z = batch % self.shape_out_z;
+            batch /= self.shape_out_z;
+            kernel_z = kernel_y / kernel_h;
+            kernel_y %= kernel_h;
+        }
+
+        let y = im2col_input_position(
+            out_y,
+            kernel_y,
+            config.stride(0),
+            config.dilation(0),
+            config.padding(0),
+        );
+        let x = im2col_input_position(
+            out_x,
+            kernel_x,
+            config.stride(1),
+            config.dilation(1),
+            config.padding(1),
+        );
 
-        let y =
-            (out_y * config.stride(0) + kernel_y * config.dilation(0)) as i32 - config.padding(0);
-        let x =
-            (out_x * config.stride(1) + kernel_x * config.dilation(1)) as i32 - config.padding(1);
+        // Each dimension is resolved independently, which also handles the corners.
+        let y = resolve_padded_position(y, self.shape_y, config.padding_mode());
+        let x = resolve_padded_position(x, self.shape_x, config.padding_mode());
 
         let m_in_bounds = comptime!(!config.check_row_bounds(Ident::Lhs)) || view_m < self.shape_m;
         let k_in_bounds = comptime!(!config.check_col_bounds(Ident::Lhs)) || view_k < self.shape_k;
-        let no_padding = comptime!(config.padding(0) == 0 && config.padding(1) == 0);
+        let no_padding = comptime!(
+            config.padding(0) == 0
+                && config.padding(1) == 0
+                && config.padding_end(0) == 0
+                && config.padding_end(1) == 0
+                || config.padding_mode() != PaddingMode::Zero
+        );
         let hw_in_bounds = no_padding
             || (y >= 0 && (y as u32) < self.shape_y && x >= 0 && (x as u32) < self.shape_x);
-        let in_bounds = m_in_bounds && k_in_bounds && hw_in_bounds;
-        let read_pos = batch * self.stride_batch
+        let mut in_bounds = m_in_bounds && k_in_bounds && hw_in_bounds;
+        let mut read_pos = batch * self.stride_batch
             + y as u32 * self.stride_y
             + x as u32 * self.stride_x
-            + channel * self.stride_channel;
+            + (channel + self.channel_offset) * self.stride_channel;
+
+        if comptime!(config.depth().is_some()) {
+            let depth = comptime!(config.depth().unwrap());
+            let z =
+                im2col_input_position(out_z, kernel_z, depth.stride, depth.dilation, depth.padding);
+            let z = resolve_padded_position(z, self.shape_z, config.padding_mode());
+
+            let no_padding =
+                comptime!(depth.padding == 0 || config.padding_mode() != PaddingMode::Zero);
+            in_bounds = in_bounds && (no_padding || (z >= 0 && (z as u32) < self.shape_z));
+            read_pos += z as u32 * self.stride_z;
+        }
 
         let read_pos = read_pos / line_size;
 
@@ -151,3 +222,42 @@ impl<E: Numeric> Im2colReader<E> {
         self.tensor.read(position)
     }
 }
+
+/// Position in the input read by the kernel element at `kernel_pos` for the output at `out_pos`,
+/// along one spatial dimension.
+///
+/// The position is negative or past the end of the input when it falls in the padding.
+#[cube]
+pub(crate) fn im2col_input_position(
+    out_pos: u32,
+    kernel_pos: u32,
+    #[comptime] stride: u32,
+    #[comptime] dilation: u32,
+    #[comptime] padding: i32,
+) -> i32 {
+    (out_pos * stride + kernel_pos * dilation) as i32 - padding
+}
+
+/// Map a position in the padded input to the position of the input to read.
+///
+/// Positions are left unchanged with zero padding, since the read is then skipped.
+#[cube]
+pub(crate) fn resolve_padded_position(
+    position: i32,
+    size: u32,
+    #[comptime] mode: PaddingMode,
+) -> i32 {
+    let size = size as i32;
+
+    match mode {
+        PaddingMode::Zero => position,
+        PaddingMode::Reflect => {
+            let position = select(position < 0, 0 - position, position);
+            select(position >= size, 2 * (size - 1) - position, position)
+        }
+        PaddingMode::Replicate => {
+            let position = select(position < 0, 0, position);
+            select(position >= size, size - 1, position)
+        }
+    }
+}
@@ -0,0 +1,1104 @@
+use super::{
+    Conv1dOptions, Conv2dOptions, Conv3dOptions, ConvLaunchError, ConvTranspose2dOptions,
+    ConvUnimplementedError, Padding2d, Pool2dOptions, base::PaddingMode,
+};
+
+/// Compute the `[batch, out_channels, height, width]` output shape of a 2D convolution of an
+/// NCHW `input_shape` with a `[out_channels, in_channels, kernel_h, kernel_w]` weight.
+///
+/// `stride`, `padding` and `dilation` are given as `(height, width)`, each spatial dimension
+/// being extended by the padding of both of its sides.
+///
+/// Returns [ConvLaunchError::ShapeMismatch] when the channels of the input and weight don't
+/// match, [ConvLaunchError::InvalidOptions] when a stride or dilation is zero, and
+/// [ConvLaunchError::InvalidShape] when the output would have no elements.
+pub fn conv2d_output_shape(
+    input_shape: [usize; 4],
+    weight_shape: [usize; 4],
+    stride: (usize, usize),
+    padding: Padding2d,
+    dilation: (usize, usize),
+) -> Result<[usize; 4], ConvLaunchError> {
+    conv2d_grouped_output_shape(input_shape, weight_shape, stride, padding, dilation, 1)
+}
+
+/// Compute the output shape of a 2D convolution like [conv2d_output_shape], with the channels
+/// split into `groups`, where the weight is `[out_channels, in_channels / groups, kernel_h,
+/// kernel_w]`.
+///
+/// Returns [ConvLaunchError::Groups] when `groups` is zero or doesn't divide both the input and
+/// output channels, e.g. `groups == in_channels` is a depthwise convolution.
+pub fn conv2d_grouped_output_shape(
+    input_shape: [usize; 4],
+    weight_shape: [usize; 4],
+    stride: (usize, usize),
+    padding: Padding2d,
+    dilation: (usize, usize),
+    groups: usize,
+) -> Result<[usize; 4], ConvLaunchError> {
+    let [batches, in_channels, in_h, in_w] = input_shape;
+    let [out_channels, weight_channels, kernel_h, kernel_w] = weight_shape;
+
+    if groups == 0 || in_channels % groups != 0 || out_channels % groups != 0 {
+        return Err(ConvLaunchError::Groups {
+            groups,
+            in_channels,
+            out_channels,
+        });
+    }
+
+    let invalid = || ConvLaunchError::InvalidShape {
+        input_shape: input_shape.to_vec(),
+        weight_shape: weight_shape.to_vec(),
+        stride: vec![stride.0, stride.1],
+        padding: vec![
+            padding.begin.0,
+            padding.begin.1,
+            padding.end.0,
+            padding.end.1,
+        ],
+        dilation: vec![dilation.0, dilation.1],
+    };
+
+    if in_channels / groups != weight_channels {
+        return Err(weight_channels_mismatch(
+            &input_shape,
+            &weight_shape,
+            1,
+            in_channels / groups,
+        ));
+    }
+    check_positive("stride", &[stride.0, stride.1])?;
+    check_positive("dilation", &[dilation.0, dilation.1])?;
+
+    let (padding_h, padding_w) = padding.total();
+    let out_h =
+        conv_output_size(in_h, kernel_h, stride.0, padding_h, dilation.0).ok_or_else(invalid)?;
+    let out_w =
+        conv_output_size(in_w, kernel_w, stride.1, padding_w, dilation.1).ok_or_else(invalid)?;
+
+    if batches == 0 || out_channels == 0 {
+        return Err(invalid());
+    }
+
+    Ok([batches, out_channels, out_h, out_w])
+}
+
+/// Compute the `[batch, out_channels, depth, height, width]` output shape of a 3D convolution of
+/// an NCDHW `input_shape` with a `[out_channels, in_channels / groups, kernel_d, kernel_h,
+/// kernel_w]` weight.
+///
+/// Returns the same errors as [conv2d_grouped_output_shape], for each of the three spatial
+/// dimensions.
+pub fn conv3d_output_shape(
+    input_shape: [usize; 5],
+    weight_shape: [usize; 5],
+    options: &Conv3dOptions,
+) -> Result<[usize; 5], ConvLaunchError> {
+    let [batches, in_channels, in_d, in_h, in_w] = input_shape;
+    let [out_channels, weight_channels, kernel_d, kernel_h, kernel_w] = weight_shape;
+    let Conv3dOptions {
+        stride,
+        padding,
+        dilation,
+        groups,
+        ..
+    } = *options;
+
+    if groups == 0 || in_channels % groups != 0 || out_channels % groups != 0 {
+        return Err(ConvLaunchError::Groups {
+            groups,
+            in_channels,
+            out_channels,
+        });
+    }
+
+    let invalid = || ConvLaunchError::InvalidShape {
+        input_shape: input_shape.to_vec(),
+        weight_shape: weight_shape.to_vec(),
+        stride: stride.to_vec(),
+        padding: padding.to_vec(),
+        dilation: dilation.to_vec(),
+    };
+
+    if in_channels / groups != weight_channels {
+        return Err(weight_channels_mismatch(
+            &input_shape,
+            &weight_shape,
+            1,
+            in_channels / groups,
+        ));
+    }
+    check_positive("stride", &stride)?;
+    check_positive("dilation", &dilation)?;
+
+    let sizes = [in_d, in_h, in_w];
+    let kernel_size = [kernel_d, kernel_h, kernel_w];
+    let out_size = |dim: usize| {
+        let (size, kernel_size, padding) = (sizes[dim], kernel_size[dim], 2 * padding[dim]);
+        conv_output_size(size, kernel_size, stride[dim], padding, dilation[dim]).ok_or_else(invalid)
+    };
+    let [out_d, out_h, out_w] = [out_size(0)?, out_size(1)?, out_size(2)?];
+
+    if batches == 0 || out_channels == 0 {
+        return Err(invalid());
+    }
+
+    Ok([batches, out_channels, out_d, out_h, out_w])
+}
+
+/// Compute the `[batch, out_channels, length]` output shape of a 1D convolution of an NCW
+/// `input_shape` with a `[out_channels, in_channels / groups, kernel_size]` weight.
+///
+/// Returns the same errors as [conv2d_grouped_output_shape], for the single spatial dimension.
+pub fn conv1d_output_shape(
+    input_shape: [usize; 3],
+    weight_shape: [usize; 3],
+    options: &Conv1dOptions,
+) -> Result<[usize; 3], ConvLaunchError> {
+    let [batches, in_channels, length] = input_shape;
+    let [out_channels, weight_channels, kernel_size] = weight_shape;
+    let Conv1dOptions {
+        stride,
+        padding,
+        dilation,
+        groups,
+        ..
+    } = *options;
+
+    if groups == 0 || in_channels % groups != 0 || out_channels % groups != 0 {
+        return Err(ConvLaunchError::Groups {
+            groups,
+            in_channels,
+            out_channels,
+        });
+    }
+
+    let invalid = || ConvLaunchError::InvalidShape {
+        input_shape: input_shape.to_vec(),
+        weight_shape: weight_shape.to_vec(),
+        stride: vec![stride],
+        padding: vec![padding],
+        dilation: vec![dilation],
+    };
+
+    if in_channels / groups != weight_channels {
+        return Err(weight_channels_mismatch(
+            &input_shape,
+            &weight_shape,
+            1,
+            in_channels / groups,
+        ));
+    }
+    check_positive("stride", &[stride])?;
+    check_positive("dilation", &[dilation])?;
+
+    let out_length =
+        conv_output_size(length, kernel_size, stride, 2 * padding, dilation).ok_or_else(invalid)?;
+
+    if batches == 0 || out_channels == 0 {
+        return Err(invalid());
+    }
+
+    Ok([batches, out_channels, out_length])
+}
+
+/// Compute the `[batch, out_channels, height, width]` output shape of a 2D transposed convolution
+/// of an NCHW `input_shape` with a `[in_channels, out_channels / groups, kernel_h, kernel_w]`
+/// weight.
+///
+/// Each spatial dimension of the output has `(in - 1) * stride - 2 * padding + dilation *
+/// (kernel - 1) + output_padding + 1` elements, the inverse of the output size of
+/// [conv2d_output_shape] with the same parameters, `output_padding` picking between the inputs
+/// a strided convolution maps to the same size.
+///
+/// Returns [ConvLaunchError::Groups] when `groups` doesn't divide the input channels,
+/// [ConvLaunchError::ShapeMismatch] when the channels of the input and weight don't match,
+/// [ConvLaunchError::InvalidOptions] when a stride or dilation is zero or the output padding isn't
+/// smaller than either the stride or the dilation, and [ConvLaunchError::InvalidShape] when the
+/// output would have no elements.
+pub fn conv_transpose2d_output_shape(
+    input_shape: [usize; 4],
+    weight_shape: [usize; 4],
+    options: &ConvTranspose2dOptions,
+) -> Result<[usize; 4], ConvLaunchError> {
+    let [batches, in_channels, in_h, in_w] = input_shape;
+    let [weight_channels, group_out_channels, kernel_h, kernel_w] = weight_shape;
+    let ConvTranspose2dOptions {
+        stride,
+        padding,
+        output_padding,
+        dilation,
+        groups,
+    } = *options;
+    let out_channels = group_out_channels * groups;
+
+    if groups == 0 || in_channels % groups != 0 {
+        return Err(ConvLaunchError::Groups {
+            groups,
+            in_channels,
+            out_channels,
+        });
+    }
+
+    let invalid = || ConvLaunchError::InvalidShape {
+        input_shape: input_shape.to_vec(),
+        weight_shape: weight_shape.to_vec(),
+        stride: vec![stride.0, stride.1],
+        padding: vec![padding.0, padding.1],
+        dilation: vec![dilation.0, dilation.1],
+    };
+
+    if in_channels != weight_channels {
+        return Err(weight_channels_mismatch(
+            &input_shape,
+            &weight_shape,
+            0,
+            in_channels,
+        ));
+    }
+    check_positive("stride", &[stride.0, stride.1])?;
+    check_positive("dilation", &[dilation.0, dilation.1])?;
+
+    let valid_output_padding = |output_padding: usize, stride: usize, dilation: usize| {
+        output_padding < stride || output_padding < dilation
+    };
+    if !valid_output_padding(output_padding.0, stride.0, dilation.0)
+        || !valid_output_padding(output_padding.1, stride.1, dilation.1)
+    {
+        return Err(ConvLaunchError::InvalidOptions {
+            reason: format!(
+                "The output padding {output_padding:?} must be smaller than either the stride {stride:?} or the dilation {dilation:?} in every dimension"
+            ),
+        });
+    }
+
+    if batches == 0 || out_channels == 0 {
+        return Err(invalid());
+    }
+
+    let (stride_h, stride_w) = stride;
+    let (padding_h, padding_w) = padding;
+    let (output_padding_h, output_padding_w) = output_padding;
+    let (dilation_h, dilation_w) = dilation;
+
+    let out_h = conv_transpose_output_size(
+        in_h,
+        kernel_h,
+        stride_h,
+        padding_h,
+        output_padding_h,
+        dilation_h,
+    );
+    let out_w = conv_transpose_output_size(
+        in_w,
+        kernel_w,
+        stride_w,
+        padding_w,
+        output_padding_w,
+        dilation_w,
+    );
+
+    Ok([
+        batches,
+        out_channels,
+        out_h.ok_or_else(invalid)?,
+        out_w.ok_or_else(invalid)?,
+    ])
+}
+
+/// Compute the `[batch, channels, out_h, out_w]` output shape of a 2D pooling of an NCHW
+/// `input_shape`, the output having one position per window like a convolution.
+///
+/// Returns [ConvLaunchError::InvalidOptions] when a stride is zero or the padding is larger than
+/// half of the kernel, since a window would then only read padding, and
+/// [ConvLaunchError::InvalidShape] when the output would have no elements, the window being
+/// reported as the `[channels, 1, kernel_h, kernel_w]` weight of the depthwise convolution it
+/// slides like.
+pub fn pool2d_output_shape(
+    input_shape: [usize; 4],
+    options: &Pool2dOptions,
+) -> Result<[usize; 4], ConvLaunchError> {
+    let [batches, channels, in_h, in_w] = input_shape;
+    let Pool2dOptions {
+        kernel_size,
+        stride,
+        padding,
+        ..
+    } = *options;
+
+    let invalid = || ConvLaunchError::InvalidShape {
+        input_shape: input_shape.to_vec(),
+        weight_shape: vec![channels, 1, kernel_size.0, kernel_size.1],
+        stride: vec![stride.0, stride.1],
+        padding: vec![padding.0, padding.1],
+        dilation: vec![1, 1],
+    };
+
+    check_positive("stride", &[stride.0, stride.1])?;
+    if 2 * padding.0 > kernel_size.0 || 2 * padding.1 > kernel_size.1 {
+        return Err(ConvLaunchError::InvalidOptions {
+            reason: format!(
+                "The padding {padding:?} must be at most half of the kernel size {kernel_size:?}"
+            ),
+        });
+    }
+
+    let out_h =
+        conv_output_size(in_h, kernel_size.0, stride.0, 2 * padding.0, 1).ok_or_else(invalid)?;
+    let out_w =
+        conv_output_size(in_w, kernel_size.1, stride.1, 2 * padding.1, 1).ok_or_else(invalid)?;
+
+    if batches == 0 || channels == 0 {
+        return Err(invalid());
+    }
+
+    Ok([batches, channels, out_h, out_w])
+}
+
+impl Conv2dOptions {
+    /// Compute the `[batch, out_channels, height, width]` output shape of the convolution of an
+    /// NCHW `input_shape` with a `[out_channels, in_channels / groups, kernel_h, kernel_w]` weight
+    /// using these options, see [conv2d_grouped_output_shape].
+    pub fn output_shape(
+        &self,
+        input_shape: [usize; 4],
+        weight_shape: [usize; 4],
+    ) -> Result<[usize; 4], ConvLaunchError> {
+        conv2d_grouped_output_shape(
+            input_shape,
+            weight_shape,
+            self.stride,
+            self.padding,
+            self.dilation,
+            self.groups,
+        )
+    }
+}
+
+impl Conv1dOptions {
+    /// Compute the `[batch, out_channels, length]` output shape of the convolution of an NCW
+    /// `input_shape` using these options, see [conv1d_output_shape].
+    pub fn output_shape(
+        &self,
+        input_shape: [usize; 3],
+        weight_shape: [usize; 3],
+    ) -> Result<[usize; 3], ConvLaunchError> {
+        conv1d_output_shape(input_shape, weight_shape, self)
+    }
+}
+
+impl Conv3dOptions {
+    /// Compute the `[batch, out_channels, depth, height, width]` output shape of the convolution
+    /// of an NCDHW `input_shape` using these options, see [conv3d_output_shape].
+    pub fn output_shape(
+        &self,
+        input_shape: [usize; 5],
+        weight_shape: [usize; 5],
+    ) -> Result<[usize; 5], ConvLaunchError> {
+        conv3d_output_shape(input_shape, weight_shape, self)
+    }
+}
+
+impl ConvTranspose2dOptions {
+    /// Compute the `[batch, out_channels, height, width]` output shape of the transposed
+    /// convolution of an NCHW `input_shape` using these options, see
+    /// [conv_transpose2d_output_shape].
+    pub fn output_shape(
+        &self,
+        input_shape: [usize; 4],
+        weight_shape: [usize; 4],
+    ) -> Result<[usize; 4], ConvLaunchError> {
+        conv_transpose2d_output_shape(input_shape, weight_shape, self)
+    }
+}
+
+impl Pool2dOptions {
+    /// Compute the `[batch, channels, out_h, out_w]` output shape of the pooling of an NCHW
+    /// `input_shape` using these options, see [pool2d_output_shape].
+    pub fn output_shape(&self, input_shape: [usize; 4]) -> Result<[usize; 4], ConvLaunchError> {
+        pool2d_output_shape(input_shape, self)
+    }
+}
+
+/// Check that a tensor given to a launcher has the `expected` shape of the output of the
+/// convolution, since the kernels index it with the shape of the convolution rather than its own.
+///
+/// Returns [ConvLaunchError::ShapeMismatch] otherwise.
+pub(crate) fn check_output_shape(
+    tensor: &'static str,
+    actual: &[usize],
+    expected: &[usize],
+    input: &[usize],
+    weight: &[usize],
+) -> Result<(), ConvLaunchError> {
+    match actual == expected {
+        true => Ok(()),
+        false => Err(ConvLaunchError::ShapeMismatch {
+            tensor,
+            input: input.to_vec(),
+            weight: weight.to_vec(),
+            expected: expected.to_vec(),
+            actual: actual.to_vec(),
+        }),
+    }
+}
+
+/// Size of one spatial dimension of the output of a transposed convolution, or `None` if it
+/// would be empty.
+fn conv_transpose_output_size(
+    size: usize,
+    kernel_size: usize,
+    stride: usize,
+    padding: usize,
+    output_padding: usize,
+    dilation: usize,
+) -> Option<usize> {
+    if size == 0 || kernel_size == 0 {
+        return None;
+    }
+
+    let full = (size - 1) * stride + dilation * (kernel_size - 1) + output_padding + 1;
+
+    full.checked_sub(2 * padding).filter(|&size| size > 0)
+}
+
+/// The [ConvLaunchError::ShapeMismatch] of a weight of `weight_shape` which should have `channels`
+/// along `dim` to match an input of `input_shape`.
+fn weight_channels_mismatch(
+    input_shape: &[usize],
+    weight_shape: &[usize],
+    dim: usize,
+    channels: usize,
+) -> ConvLaunchError {
+    let mut expected = weight_shape.to_vec();
+    expected[dim] = channels;
+
+    ConvLaunchError::ShapeMismatch {
+        tensor: "weight",
+        input: input_shape.to_vec(),
+        weight: weight_shape.to_vec(),
+        expected,
+        actual: weight_shape.to_vec(),
+    }
+}
+
+/// Check that a spatial parameter of the options, the stride or the dilation, is positive in
+/// every dimension.
+fn check_positive(name: &str, values: &[usize]) -> Result<(), ConvLaunchError> {
+    match values.contains(&0) {
+        true => Err(ConvLaunchError::InvalidOptions {
+            reason: format!("The {name} must be positive in every dimension, got {values:?}"),
+        }),
+        false => Ok(()),
+    }
+}
+
+/// Check that each spatial dimension of the input, of the given `sizes`, can be extended by the
+/// `(begin, end)` padding of its sides with `mode`.
+///
+/// A [reflection](PaddingMode::Reflect) mirrors the input only once, so its padding must be
+/// smaller than the input. Returns [ConvLaunchError::InvalidOptions] otherwise.
+pub(crate) fn check_padding_mode(
+    mode: PaddingMode,
+    sizes: &[usize],
+    padding: &[(usize, usize)],
+) -> Result<(), ConvLaunchError> {
+    let too_large = sizes
+        .iter()
+        .zip(padding)
+        .any(|(&size, &(begin, end))| begin.max(end) >= size);
+
+    match mode == PaddingMode::Reflect && too_large {
+        true => Err(ConvLaunchError::InvalidOptions {
+            reason: format!(
+                "The reflect padding {padding:?} must be smaller than the input {sizes:?}"
+            ),
+        }),
+        false => Ok(()),
+    }
+}
+
+/// Check that the input is padded with zeros, for the kernels that don't support the other
+/// [padding modes](PaddingMode) yet.
+///
+/// Returns [ConvUnimplementedError::PaddingMode] otherwise.
+pub(crate) fn check_zero_padding(mode: PaddingMode) -> Result<(), ConvLaunchError> {
+    match mode {
+        PaddingMode::Zero => Ok(()),
+        mode => Err(ConvUnimplementedError::PaddingMode { mode }.into()),
+    }
+}
+
+/// Size of one spatial dimension of the output, or `None` if it would be empty, `padding` being
+/// the sum of the padding of both sides.
+fn conv_output_size(
+    size: usize,
+    kernel_size: usize,
+    stride: usize,
+    padding: usize,
+    dilation: usize,
+) -> Option<usize> {
+    if kernel_size == 0 || dilation == 0 {
+        return None;
+    }
+
+    let padded = size + padding;
+    let kernel_extent = dilation * (kernel_size - 1) + 1;
+
+    padded
+        .checked_sub(kernel_extent)
+        .map(|remaining| remaining / stride + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_shape_same_padding() {
+        let shape =
+            conv2d_output_shape([2, 3, 32, 32], [8, 3, 3, 3], (1, 1), (1, 1).into(), (1, 1));
+
+        assert_eq!(shape.unwrap(), [2, 8, 32, 32]);
+    }
+
+    #[test]
+    fn output_shape_strided_and_dilated() {
+        let shape =
+            conv2d_output_shape([1, 4, 17, 20], [6, 4, 3, 5], (2, 3), (0, 2).into(), (2, 1));
+
+        // height: (17 - 5) / 2 + 1 = 7, width: (20 + 4 - 5) / 3 + 1 = 7
+        assert_eq!(shape.unwrap(), [1, 6, 7, 7]);
+    }
+
+    #[test]
+    fn output_shape_asymmetric_padding() {
+        let padding = Padding2d {
+            begin: (0, 2),
+            end: (1, 0),
+        };
+        let shape = conv2d_output_shape([1, 3, 8, 8], [4, 3, 3, 3], (2, 1), padding, (1, 1));
+
+        // height: (8 + 1 - 3) / 2 + 1 = 4, width: (8 + 2 - 3) + 1 = 8
+        assert_eq!(shape.unwrap(), [1, 4, 4, 8]);
+    }
+
+    #[test]
+    fn kernel_larger_than_input_is_invalid() {
+        let shape = conv2d_output_shape([1, 3, 4, 4], [8, 3, 5, 5], (1, 1), (0, 0).into(), (1, 1));
+
+        assert!(shape.is_err());
+    }
+
+    #[test]
+    fn mismatched_channels_are_invalid() {
+        let shape = conv2d_output_shape([1, 3, 8, 8], [8, 4, 3, 3], (1, 1), (0, 0).into(), (1, 1));
+
+        assert!(shape.is_err());
+    }
+
+    #[test]
+    fn output_shape_depthwise() {
+        let shape = conv2d_grouped_output_shape(
+            [2, 6, 9, 9],
+            [12, 1, 3, 3],
+            (2, 2),
+            (1, 1).into(),
+            (1, 1),
+            6,
+        );
+
+        assert_eq!(shape.unwrap(), [2, 12, 5, 5]);
+    }
+
+    #[test]
+    fn channels_not_divisible_by_groups_are_invalid() {
+        let shape = conv2d_grouped_output_shape(
+            [1, 6, 8, 8],
+            [8, 2, 3, 3],
+            (1, 1),
+            (0, 0).into(),
+            (1, 1),
+            4,
+        );
+
+        assert!(matches!(
+            shape,
+            Err(ConvLaunchError::Groups {
+                groups: 4,
+                in_channels: 6,
+                out_channels: 8,
+            })
+        ));
+    }
+
+    #[test]
+    fn weight_channels_must_match_the_group() {
+        let shape = conv2d_grouped_output_shape(
+            [1, 8, 8, 8],
+            [8, 8, 3, 3],
+            (1, 1),
+            (0, 0).into(),
+            (1, 1),
+            2,
+        );
+
+        assert!(matches!(shape, Err(ConvLaunchError::ShapeMismatch { .. })));
+    }
+
+    #[test]
+    fn output_shape_3d() {
+        let options = Conv3dOptions {
+            stride: [1, 2, 1],
+            padding: [1, 0, 2],
+            dilation: [1, 1, 2],
+            ..Default::default()
+        };
+        let shape = conv3d_output_shape([2, 4, 5, 9, 8], [6, 4, 3, 3, 3], &options);
+
+        // depth: (5 + 2 - 3) + 1 = 5, height: (9 - 3) / 2 + 1 = 4, width: (8 + 4 - 5) + 1 = 8
+        assert_eq!(shape.unwrap(), [2, 6, 5, 4, 8]);
+    }
+
+    #[test]
+    fn kernel_deeper_than_input_is_invalid() {
+        let shape = conv3d_output_shape([1, 3, 2, 8, 8], [8, 3, 3, 3, 3], &Default::default());
+
+        assert!(matches!(shape, Err(ConvLaunchError::InvalidShape { .. })));
+    }
+
+    #[test]
+    fn output_shape_1d() {
+        let options = Conv1dOptions {
+            stride: 2,
+            padding: 4,
+            ..Default::default()
+        };
+        let shape = conv1d_output_shape([2, 8, 37], [16, 8, 9], &options);
+
+        // (37 + 8 - 9) / 2 + 1 = 19
+        assert_eq!(shape.unwrap(), [2, 16, 19]);
+    }
+
+    #[test]
+    fn output_shape_1d_depthwise() {
+        let options = Conv1dOptions {
+            padding: 1,
+            dilation: 3,
+            groups: 8,
+            ..Default::default()
+        };
+        let shape = conv1d_output_shape([1, 8, 10], [8, 1, 3], &options);
+
+        // 10 + 2 - 7 + 1 = 6
+        assert_eq!(shape.unwrap(), [1, 8, 6]);
+    }
+
+    #[test]
+    fn output_shape_pool() {
+        let options = Pool2dOptions {
+            padding: (1, 0),
+            ..Pool2dOptions::new((3, 2))
+        };
+        let shape = pool2d_output_shape([2, 8, 9, 7], &options);
+
+        // height: (9 + 2 - 3) / 3 + 1 = 3, width: (7 - 2) / 2 + 1 = 3
+        assert_eq!(shape.unwrap(), [2, 8, 3, 3]);
+    }
+
+    #[test]
+    fn pool_padding_larger_than_half_the_kernel_is_invalid() {
+        let options = Pool2dOptions {
+            stride: (1, 1),
+            padding: (2, 1),
+            ..Pool2dOptions::new((3, 3))
+        };
+        let shape = pool2d_output_shape([1, 4, 8, 8], &options);
+
+        assert!(matches!(shape, Err(ConvLaunchError::InvalidOptions { .. })));
+    }
+
+    #[test]
+    fn output_shape_transposed() {
+        let options = ConvTranspose2dOptions {
+            stride: (2, 3),
+            padding: (1, 0),
+            output_padding: (1, 2),
+            dilation: (1, 2),
+            groups: 1,
+        };
+        let shape = conv_transpose2d_output_shape([2, 4, 5, 6], [4, 3, 3, 2], &options);
+
+        // height: 4 * 2 - 2 + 2 + 1 + 1 = 10, width: 5 * 3 + 2 + 2 + 1 = 20
+        assert_eq!(shape.unwrap(), [2, 3, 10, 20]);
+    }
+
+    #[test]
+    fn transposed_output_shape_inverts_the_convolution() {
+        let options = ConvTranspose2dOptions {
+            stride: (2, 2),
+            padding: (1, 1),
+            output_padding: (1, 0),
+            ..Default::default()
+        };
+        let shape = conv_transpose2d_output_shape([1, 8, 4, 4], [8, 3, 3, 3], &options).unwrap();
+        let back = conv2d_output_shape(shape, [8, 3, 3, 3], (2, 2), (1, 1).into(), (1, 1));
+
+        assert_eq!(shape, [1, 3, 8, 7]);
+        assert_eq!(back.unwrap(), [1, 8, 4, 4]);
+    }
+
+    #[test]
+    fn output_padding_must_be_smaller_than_stride_or_dilation() {
+        let options = ConvTranspose2dOptions {
+            stride: (2, 2),
+            output_padding: (2, 0),
+            ..Default::default()
+        };
+        let shape = conv_transpose2d_output_shape([1, 3, 4, 4], [3, 3, 3, 3], &options);
+
+        assert!(matches!(shape, Err(ConvLaunchError::InvalidOptions { .. })));
+    }
+
+    #[test]
+    fn zero_stride_is_invalid() {
+        let shape = conv2d_output_shape([1, 3, 8, 8], [8, 3, 3, 3], (0, 1), (0, 0).into(), (1, 1));
+
+        assert!(matches!(shape, Err(ConvLaunchError::InvalidOptions { .. })));
+    }
+
+    #[test]
+    fn zero_dilation_is_invalid() {
+        let shape = conv3d_output_shape(
+            [1, 3, 4, 8, 8],
+            [8, 3, 3, 3, 3],
+            &Conv3dOptions {
+                dilation: [1, 0, 1],
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(shape, Err(ConvLaunchError::InvalidOptions { .. })));
+    }
+
+    #[test]
+    fn mismatched_channels_report_the_shapes() {
+        let shape = conv2d_output_shape([1, 3, 8, 8], [8, 4, 3, 3], (1, 1), (0, 0).into(), (1, 1));
+
+        let Err(ConvLaunchError::ShapeMismatch {
+            tensor,
+            input,
+            weight,
+            expected,
+            actual,
+        }) = shape
+        else {
+            panic!("Expected a shape mismatch, got {shape:?}");
+        };
+        assert_eq!(tensor, "weight");
+        assert_eq!((input, weight), (vec![1, 3, 8, 8], vec![8, 4, 3, 3]));
+        assert_eq!(expected, vec![8, 3, 3, 3]);
+        assert_eq!(actual, vec![8, 4, 3, 3]);
+    }
+
+    #[test]
+    fn options_output_shape_matches_the_functions() {
+        let options = Conv2dOptions {
+            stride: (2, 1),
+            padding: (1, 0).into(),
+            groups: 2,
+            ..Default::default()
+        };
+        let shape = options.output_shape([1, 4, 9, 8], [6, 2, 3, 3]);
+        let expected = conv2d_grouped_output_shape(
+            [1, 4, 9, 8],
+            [6, 2, 3, 3],
+            (2, 1),
+            (1, 0).into(),
+            (1, 1),
+            2,
+        );
+
+        assert_eq!(shape.unwrap(), expected.unwrap());
+    }
+
+    #[test]
+    fn output_shape_mismatch_reports_the_shapes() {
+        let (input, weight) = ([1, 6, 7, 4], [8, 3, 3, 4]);
+        let result = check_output_shape("out", &[1, 4, 4, 8], &[1, 4, 5, 8], &input, &weight);
+
+        let Err(ConvLaunchError::ShapeMismatch {
+            tensor,
+            expected,
+            actual,
+            ..
+        }) = result
+        else {
+            panic!("Expected a shape mismatch, got {result:?}");
+        };
+        assert_eq!(tensor, "out");
+        assert_eq!(expected, vec![1, 4, 5, 8]);
+        assert_eq!(actual, vec![1, 4, 4, 8]);
+        assert!(check_output_shape("out", &[1, 4, 5, 8], &[1, 4, 5, 8], &input, &weight).is_ok());
+    }
+
+    #[test]
+    fn reflect_padding_must_be_smaller_than_the_input() {
+        let sizes = [3, 5];
+        let padding = [(2, 0), (1, 5)];
+
+        let result = check_padding_mode(PaddingMode::Reflect, &sizes, &padding);
+
+        assert!(matches!(
+            result,
+            Err(ConvLaunchError::InvalidOptions { .. })
+        ));
+        assert!(check_padding_mode(PaddingMode::Reflect, &sizes, &[(2, 2), (4, 0)]).is_ok());
+        assert!(check_padding_mode(PaddingMode::Replicate, &sizes, &padding).is_ok());
+        assert!(check_padding_mode(PaddingMode::Zero, &sizes, &padding).is_ok());
+    }
+
+    #[test]
+    fn random_conv2d_output_shapes_match_brute_force() {
+        let mut random = random_values(7);
+
+        for _ in 0..512 {
+            let groups = random(1..=4);
+            let input_shape = [random(1..=2), random(1..=8), random(1..=12), random(1..=12)];
+            let out_channels = random(1..=8);
+            let weight_channels = (input_shape[1] / groups).max(1);
+            let weight_shape = [out_channels, weight_channels, random(1..=4), random(1..=4)];
+            let options = Conv2dOptions {
+                stride: (random(1..=3), random(1..=3)),
+                padding: Padding2d {
+                    begin: (random(0..=3), random(0..=3)),
+                    end: (random(0..=3), random(0..=3)),
+                },
+                dilation: (random(1..=3), random(1..=3)),
+                groups,
+                ..Default::default()
+            };
+
+            let shape = options.output_shape(input_shape, weight_shape);
+
+            if input_shape[1] % groups != 0 || out_channels % groups != 0 {
+                assert!(matches!(shape, Err(ConvLaunchError::Groups { .. })));
+                continue;
+            }
+            let out_h = brute_force_output_size(
+                input_shape[2],
+                weight_shape[2],
+                options.stride.0,
+                options.padding.begin.0 + options.padding.end.0,
+                options.dilation.0,
+            );
+            let out_w = brute_force_output_size(
+                input_shape[3],
+                weight_shape[3],
+                options.stride.1,
+                options.padding.begin.1 + options.padding.end.1,
+                options.dilation.1,
+            );
+            match out_h * out_w {
+                0 => assert!(matches!(shape, Err(ConvLaunchError::InvalidShape { .. }))),
+                _ => assert_eq!(
+                    shape.unwrap(),
+                    [input_shape[0], out_channels, out_h, out_w],
+                    "{input_shape:?} {weight_shape:?} {options:?}"
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn random_conv1d_and_conv3d_output_shapes_match_brute_force() {
+        let mut random = random_values(11);
+
+        for _ in 0..256 {
+            let groups = random(1..=2);
+            let channels = groups * random(1..=3);
+            let out_channels = groups * random(1..=3);
+            let options = Conv3dOptions {
+                stride: [random(1..=3), random(1..=3), random(1..=3)],
+                padding: [random(0..=2), random(0..=2), random(0..=2)],
+                dilation: [random(1..=2), random(1..=2), random(1..=2)],
+                groups,
+                ..Default::default()
+            };
+            let sizes = [random(1..=9), random(1..=9), random(1..=9)];
+            let kernel_size = [random(1..=4), random(1..=4), random(1..=4)];
+            let out_size = |dim: usize| {
+                brute_force_output_size(
+                    sizes[dim],
+                    kernel_size[dim],
+                    options.stride[dim],
+                    2 * options.padding[dim],
+                    options.dilation[dim],
+                )
+            };
+            let out_size = [out_size(0), out_size(1), out_size(2)];
+
+            let shape = options.output_shape(
+                [1, channels, sizes[0], sizes[1], sizes[2]],
+                [
+                    out_channels,
+                    channels / groups,
+                    kernel_size[0],
+                    kernel_size[1],
+                    kernel_size[2],
+                ],
+            );
+            match out_size.contains(&0) {
+                true => assert!(matches!(shape, Err(ConvLaunchError::InvalidShape { .. }))),
+                false => assert_eq!(
+                    shape.unwrap(),
+                    [1, out_channels, out_size[0], out_size[1], out_size[2]]
+                ),
+            }
+
+            // The last dimension of the 3D convolution as a 1D one.
+            let options = Conv1dOptions {
+                stride: options.stride[2],
+                padding: options.padding[2],
+                dilation: options.dilation[2],
+                groups,
+                ..Default::default()
+            };
+            let shape = options.output_shape(
+                [1, channels, sizes[2]],
+                [out_channels, channels / groups, kernel_size[2]],
+            );
+            match out_size[2] {
+                0 => assert!(matches!(shape, Err(ConvLaunchError::InvalidShape { .. }))),
+                out_length => assert_eq!(shape.unwrap(), [1, out_channels, out_length]),
+            }
+        }
+    }
+
+    #[test]
+    fn random_pool2d_output_shapes_match_brute_force() {
+        let mut random = random_values(13);
+
+        for _ in 0..256 {
+            let kernel_size = (random(1..=5), random(1..=5));
+            let options = Pool2dOptions {
+                kernel_size,
+                stride: (random(1..=3), random(1..=3)),
+                padding: (random(0..=kernel_size.0 / 2), random(0..=kernel_size.1 / 2)),
+                count_include_pad: true,
+            };
+            let input_shape = [2, 3, random(1..=10), random(1..=10)];
+
+            let out_h = brute_force_output_size(
+                input_shape[2],
+                kernel_size.0,
+                options.stride.0,
+                2 * options.padding.0,
+                1,
+            );
+            let out_w = brute_force_output_size(
+                input_shape[3],
+                kernel_size.1,
+                options.stride.1,
+                2 * options.padding.1,
+                1,
+            );
+            let shape = options.output_shape(input_shape);
+            match out_h * out_w {
+                0 => assert!(matches!(shape, Err(ConvLaunchError::InvalidShape { .. }))),
+                _ => assert_eq!(shape.unwrap(), [2, 3, out_h, out_w]),
+            }
+        }
+    }
+
+    #[test]
+    fn random_transposed_output_shapes_match_brute_force() {
+        let mut random = random_values(17);
+
+        for _ in 0..256 {
+            let stride = (random(1..=3), random(1..=3));
+            let dilation = (random(1..=3), random(1..=3));
+            let options = ConvTranspose2dOptions {
+                stride,
+                padding: (random(0..=3), random(0..=3)),
+                output_padding: (
+                    random(0..=stride.0.max(dilation.0) - 1),
+                    random(0..=stride.1.max(dilation.1) - 1),
+                ),
+                dilation,
+                groups: 1,
+            };
+            let input_shape = [1, 4, random(1..=6), random(1..=6)];
+            let weight_shape = [4, random(1..=3), random(1..=4), random(1..=4)];
+
+            let out_h = brute_force_transposed_output_size(
+                input_shape[2],
+                weight_shape[2],
+                stride.0,
+                options.padding.0,
+                options.output_padding.0,
+                dilation.0,
+            );
+            let out_w = brute_force_transposed_output_size(
+                input_shape[3],
+                weight_shape[3],
+                stride.1,
+                options.padding.1,
+                options.output_padding.1,
+                dilation.1,
+            );
+            let shape = options.output_shape(input_shape, weight_shape);
+            match out_h * out_w {
+                0 => assert!(matches!(shape, Err(ConvLaunchError::InvalidShape { .. }))),
+                _ => assert_eq!(shape.unwrap(), [1, weight_shape[1], out_h, out_w]),
+            }
+        }
+    }
+
+    /// Uniform values in a range from a fixed seed, so that failures are reproducible.
+    fn random_values(mut seed: u64) -> impl FnMut(std::ops::RangeInclusive<usize>) -> usize {
+        move |range| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            range.start() + (seed >> 33) as usize % (range.end() - range.start() + 1)
+        }
+    }
+
+    /// The number of positions of a window sliding over `size` elements padded by `padding` in
+    /// total, counted one at a time.
+    fn brute_force_output_size(
+        size: usize,
+        kernel_size: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> usize {
+        let last_offset = dilation * (kernel_size - 1);
+
+        (0..)
+            .take_while(|&position| position * stride + last_offset < size + padding)
+            .count()
+    }
+
+    /// The number of output positions reached by scattering every input position over its
+    /// window, with `output_padding` added at the end and `padding` removed from both sides.
+    fn brute_force_transposed_output_size(
+        size: usize,
+        kernel_size: usize,
+        stride: usize,
+        padding: usize,
+        output_padding: usize,
+        dilation: usize,
+    ) -> usize {
+        let mut full = 0;
+        for position in 0..size {
+            for kernel in 0..kernel_size {
+                full = full.max(position * stride + kernel * dilation + 1);
+            }
+        }
+
+        (full + output_padding).saturating_sub(2 * padding)
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement,
+    prelude::{Float, Runtime},
+};
+use half::f16;
+
+use crate::{
+    convolution::{
+        Conv1dOptions, algorithm::ImplicitCmmaConv, base::PaddingMode, conv1d_output_shape,
+        launch_conv1d_nwc, selection::Large,
+    },
+    tensor::TensorHandle,
+};
+
+use super::test_utils::{ReferenceConv, is_unsupported, small_integers, tensor_from_integers};
+
+pub fn test_conv1d_kernel_3<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let options = Conv1dOptions {
+        stride: 2,
+        padding: 4,
+        ..Default::default()
+    };
+
+    test_conv1d::<R, F>(3, options, device);
+}
+
+pub fn test_conv1d_kernel_9<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let options = Conv1dOptions {
+        stride: 2,
+        padding: 4,
+        ..Default::default()
+    };
+
+    test_conv1d::<R, F>(9, options, device);
+}
+
+pub fn test_conv1d_dilated_groups_2<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = Conv1dOptions {
+        padding: 2,
+        dilation: 2,
+        groups: 2,
+        ..Default::default()
+    };
+
+    test_conv1d::<R, F>(3, options, device);
+}
+
+/// As many groups as input channels, computed by the depthwise kernel.
+pub fn test_conv1d_depthwise<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let options = Conv1dOptions {
+        stride: 2,
+        padding: 4,
+        groups: 8,
+        ..Default::default()
+    };
+
+    test_conv1d::<R, F>(9, options, device);
+}
+
+pub fn test_conv1d_reflect_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = Conv1dOptions {
+        stride: 2,
+        padding: 4,
+        padding_mode: PaddingMode::Reflect,
+        ..Default::default()
+    };
+
+    test_conv1d::<R, F>(5, options, device);
+}
+
+pub fn test_conv1d_replicate_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = Conv1dOptions {
+        padding: 6,
+        padding_mode: PaddingMode::Replicate,
+        dilation: 2,
+        groups: 2,
+        ..Default::default()
+    };
+
+    test_conv1d::<R, F>(3, options, device);
+}
+
+/// Compare the convolution of a `[2, 37, 8]` NWC input to 8 output channels with a bias, using a
+/// kernel of `kernel_size`, with a direct computation on the CPU.
+fn test_conv1d<R: Runtime, F: Float + CubeElement + Display>(
+    kernel_size: usize,
+    options: Conv1dOptions,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let [batches, length, in_channels] = [2, 37, 8];
+    let out_channels = 8;
+    let Conv1dOptions {
+        stride,
+        padding,
+        padding_mode,
+        dilation,
+        groups,
+        ..
+    } = options;
+    let group_channels = in_channels / groups;
+
+    let [_, _, out_length] = conv1d_output_shape(
+        [batches, in_channels, length],
+        [out_channels, group_channels, kernel_size],
+        &options,
+    )
+    .unwrap();
+
+    let input_shape = [batches, length, in_channels];
+    let input = small_integers(batches * length * in_channels, 1, 0);
+    let weight = small_integers(out_channels * kernel_size * group_channels, 3, 1);
+    let bias = small_integers(out_channels, 1, 2);
+
+    let reference = ReferenceConv {
+        kernel_size: vec![kernel_size],
+        stride: vec![stride],
+        padding: vec![padding],
+        padding_end: vec![padding],
+        dilation: vec![dilation],
+        padding_mode,
+        groups,
+    };
+    let expected = reference
+        .run(&input, &input_shape, &weight, &bias)
+        .into_iter()
+        .map(F::from_int)
+        .collect::<Vec<_>>();
+
+    let input = tensor_from_integers::<R, F>(&client, &input, &input_shape);
+    let weight = tensor_from_integers::<R, F>(
+        &client,
+        &weight,
+        &[out_channels, kernel_size, group_channels],
+    );
+    let bias = tensor_from_integers::<R, F>(&client, &bias, &[out_channels]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![batches, out_length, out_channels]);
+
+    let result = launch_conv1d_nwc::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        Some(&bias.as_ref()),
+        &out.as_ref(),
+        &options,
+    );
+
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    assert_eq!(out.to_vec(&client), expected, "convolution outputs differ");
+}
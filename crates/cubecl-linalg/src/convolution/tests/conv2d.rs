@@ -0,0 +1,2011 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement,
+    prelude::{ComputeClient, Float, Runtime, TensorHandleRef},
+};
+use half::f16;
+
+use crate::{
+    convolution::{
+        Conv2dAlgorithm, Conv2dOptions, ConvLaunchError, ConvTranspose2dOptions,
+        ConvUnimplementedError, Padding2d,
+        algorithm::ImplicitCmmaConv,
+        base::{ConvolutionProblem, PaddingMode},
+        conv_transpose2d, conv_transpose2d_output_shape, conv2d_autotune, conv2d_backward_data,
+        conv2d_backward_weight, conv2d_grouped_output_shape, conv2d_im2col, conv2d_winograd,
+        im2col, is_winograd_compatible,
+        launch::BoundBias,
+        launch_conv2d, launch_conv2d_nhwc,
+        selection::Large,
+    },
+    matmul::{
+        self, Strategy,
+        components::MatrixLayout,
+        kernels::{MatmulLaunchError, tiling2d::MatmulEpilogue},
+        tests::test_utils::assert_equals_approx,
+    },
+    tensor::TensorHandle,
+};
+
+use super::test_utils::{ReferenceConv, is_unsupported, small_integers, tensor_from_integers};
+
+pub fn test_conv2d_groups_2<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d::<R, F>(Conv2dTestCase::grouped(2), device);
+}
+
+pub fn test_conv2d_groups_4<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d::<R, F>(Conv2dTestCase::grouped(4), device);
+}
+
+/// As many groups as input channels.
+pub fn test_conv2d_depthwise<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d::<R, F>(Conv2dTestCase::grouped(8), device);
+}
+
+pub fn test_depthwise_conv2d_3x3_stride_2<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        stride: (2, 2),
+        line_size: 4,
+        ..Conv2dTestCase::grouped(8)
+    };
+
+    test_conv2d::<R, F>(case, device);
+}
+
+pub fn test_depthwise_conv2d_5x5_stride_2<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        kernel_size: (5, 5),
+        stride: (2, 2),
+        padding: (2, 2).into(),
+        line_size: 4,
+        ..Conv2dTestCase::grouped(8)
+    };
+
+    test_conv2d::<R, F>(case, device);
+}
+
+pub fn test_conv2d_dilation_2<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = Conv2dTestCase {
+        padding: (2, 2).into(),
+        dilation: (2, 2),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_conv2d::<R, F>(case, device);
+}
+
+pub fn test_conv2d_dilation_2_3<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = Conv2dTestCase {
+        stride: (2, 1),
+        padding: (1, 2).into(),
+        dilation: (2, 3),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_conv2d::<R, F>(case, device);
+}
+
+pub fn test_depthwise_conv2d_dilation_2_3<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        padding: (2, 3).into(),
+        dilation: (2, 3),
+        line_size: 4,
+        ..Conv2dTestCase::grouped(8)
+    };
+
+    test_conv2d::<R, F>(case, device);
+}
+
+pub fn test_conv2d_groups_line_size_too_large<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input = TensorHandle::<R, F>::zeros(&client, vec![1, 4, 4, 8]);
+    let weight = TensorHandle::<R, F>::zeros(&client, vec![2 * 9, 8]);
+    let bias = TensorHandle::<R, F>::zeros(&client, vec![8]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![16, 8]);
+
+    // 4 groups of 2 input channels, each starting in the middle of a line of 4.
+    let problem = ConvolutionProblem {
+        m: 16,
+        n: 2,
+        k: 2 * 9,
+        lhs_layout: MatrixLayout::RowMajor,
+        rhs_layout: MatrixLayout::RowMajor,
+        lhs_line_size: 4,
+        rhs_line_size: 1,
+        out_line_size: 1,
+        kernel_size: (3, 3),
+        stride: (1, 1),
+        padding: (1, 1),
+        padding_end: (1, 1),
+        dilation: (1, 1),
+        padding_mode: PaddingMode::Zero,
+        out_shape_y: 4,
+        out_shape_x: 4,
+        has_bias: true,
+        groups: 4,
+        depth: None,
+        epilogue: MatmulEpilogue::None,
+    };
+
+    let result = launch_conv2d_nhwc::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+        &client,
+        input.as_arg(4),
+        weight.as_arg(1),
+        bias.as_arg(1),
+        out.as_arg(1),
+        problem,
+    );
+
+    match result {
+        Err(ConvLaunchError::Matmul(error)) => assert!(matches!(
+            error.into_root(),
+            MatmulLaunchError::InvalidProblem(_)
+        )),
+        result => panic!("expected an invalid line size, got {result:?}"),
+    }
+}
+
+/// Odd output padding, picking the larger of the two outputs a stride of 2 can map to the input.
+pub fn test_conv_transpose2d_stride_2<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = ConvTranspose2dOptions {
+        stride: (2, 2),
+        padding: (1, 1),
+        output_padding: (1, 1),
+        ..Default::default()
+    };
+
+    test_conv_transpose2d::<R, F>((3, 3), options, device);
+}
+
+pub fn test_conv_transpose2d_dilation<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = ConvTranspose2dOptions {
+        stride: (2, 1),
+        padding: (0, 2),
+        output_padding: (1, 0),
+        dilation: (1, 2),
+        groups: 1,
+    };
+
+    test_conv_transpose2d::<R, F>((2, 3), options, device);
+}
+
+pub fn test_conv_transpose2d_groups_unimplemented<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input = TensorHandle::<R, F>::zeros(&client, vec![1, 4, 4, 4]);
+    let weight = TensorHandle::<R, F>::zeros(&client, vec![4, 3, 3, 2]);
+    let options = ConvTranspose2dOptions {
+        groups: 2,
+        ..Default::default()
+    };
+
+    let result =
+        conv_transpose2d::<R, F, f32>(&client, &input.as_ref(), &weight.as_ref(), None, &options);
+
+    assert!(matches!(
+        result,
+        Err(ConvLaunchError::Unimplemented(
+            ConvUnimplementedError::TransposedGroups { groups: 2 }
+        ))
+    ));
+}
+
+pub fn test_conv2d_backward_weight<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        stride: (2, 1),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_backward_weight::<R, F>(2, case, device);
+}
+
+/// A single batch, where the matmul computes the gradient without summing over batches.
+pub fn test_conv2d_backward_weight_single_batch<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        padding: (2, 0).into(),
+        dilation: (2, 1),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_backward_weight::<R, F>(1, case, device);
+}
+
+pub fn test_conv2d_backward_weight_groups_unimplemented<
+    R: Runtime,
+    F: Float + CubeElement + Display,
+>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input = TensorHandle::<R, F>::zeros(&client, vec![1, 4, 4, 4]);
+    let grad_out = TensorHandle::<R, F>::zeros(&client, vec![1, 2, 2, 2]);
+    let options = Conv2dOptions {
+        groups: 2,
+        ..Default::default()
+    };
+
+    let result = conv2d_backward_weight::<R, F, f32>(
+        &Strategy::Naive,
+        &client,
+        &input.as_ref(),
+        &grad_out.as_ref(),
+        (3, 3),
+        &options,
+    );
+
+    assert!(matches!(
+        result,
+        Err(ConvLaunchError::Unimplemented(
+            ConvUnimplementedError::BackwardGroups { groups: 2 }
+        ))
+    ));
+}
+
+/// The last row of the input isn't read by the convolution, so its gradient is zero.
+pub fn test_conv2d_backward_data<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = Conv2dTestCase {
+        stride: (2, 1),
+        padding: (0, 1).into(),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_backward_data::<R, F>([2, 8, 6, 3], 4, case, true, device);
+}
+
+pub fn test_conv2d_backward_data_dilated<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        padding: (2, 0).into(),
+        dilation: (2, 1),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_backward_data::<R, F>([1, 7, 6, 3], 4, case, true, device);
+}
+
+/// Random shapes and options, compared with the CPU reference only.
+pub fn test_conv2d_backward_data_fuzz<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let mut seed = 42u64;
+    let mut random = |range: std::ops::RangeInclusive<usize>| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        range.start() + (seed >> 33) as usize % (range.end() - range.start() + 1)
+    };
+
+    let mut cases = 0;
+    while cases < 16 {
+        let input_shape = [random(1..=2), random(1..=9), random(1..=9), random(1..=5)];
+        let out_channels = random(1..=5);
+        let case = Conv2dTestCase {
+            kernel_size: (random(1..=3), random(1..=3)),
+            stride: (random(1..=3), random(1..=3)),
+            padding: Padding2d {
+                begin: (random(0..=2), random(0..=2)),
+                end: (random(0..=2), random(0..=2)),
+            },
+            dilation: (random(1..=2), random(1..=2)),
+            ..Conv2dTestCase::grouped(1)
+        };
+
+        let [batches, in_h, in_w, in_channels] = input_shape;
+        let (kernel_h, kernel_w) = case.kernel_size;
+        let valid = conv2d_grouped_output_shape(
+            [batches, in_channels, in_h, in_w],
+            [out_channels, in_channels, kernel_h, kernel_w],
+            case.stride,
+            case.padding,
+            case.dilation,
+            1,
+        )
+        .is_ok();
+
+        if valid {
+            test_backward_data::<R, F>(input_shape, out_channels, case, false, device);
+            cases += 1;
+        }
+    }
+}
+
+pub fn test_conv2d_backward_data_groups_unimplemented<
+    R: Runtime,
+    F: Float + CubeElement + Display,
+>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let weight = TensorHandle::<R, F>::zeros(&client, vec![3, 3, 2, 2]);
+    let grad_out = TensorHandle::<R, F>::zeros(&client, vec![1, 2, 2, 2]);
+    let grad_input = TensorHandle::<R, F>::empty(&client, vec![1, 4, 4, 4]);
+    let options = Conv2dOptions {
+        groups: 2,
+        ..Default::default()
+    };
+
+    let result = conv2d_backward_data::<R, F, f32>(
+        &client,
+        &weight.as_ref(),
+        &grad_out.as_ref(),
+        &grad_input.as_ref(),
+        &options,
+    );
+
+    assert!(matches!(
+        result,
+        Err(ConvLaunchError::Unimplemented(
+            ConvUnimplementedError::BackwardGroups { groups: 2 }
+        ))
+    ));
+}
+
+pub fn test_conv2d_memory_layouts<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        stride: (2, 1),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_conv2d_layouts::<R, F>(case, 8, true, device);
+}
+
+pub fn test_conv2d_memory_layouts_groups_2<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_layouts::<R, F>(Conv2dTestCase::grouped(2), 8, true, device);
+}
+
+/// Fewer output channels than the columns of a stage, read as lines of 2, so the bias must only
+/// be read up to its last line.
+pub fn test_conv2d_bias_channels_tail<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_layouts::<R, F>(Conv2dTestCase::grouped(1), 6, true, device);
+}
+
+/// An odd number of output channels, read one at a time.
+pub fn test_conv2d_bias_odd_channels<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_layouts::<R, F>(Conv2dTestCase::grouped(1), 5, true, device);
+}
+
+pub fn test_conv2d_without_bias<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d_layouts::<R, F>(Conv2dTestCase::grouped(1), 8, false, device);
+}
+
+/// Only the last rows and columns are padded, so the windows read past the end of the input
+/// without starting before it.
+pub fn test_conv2d_trailing_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        stride: (2, 1),
+        padding: Padding2d {
+            begin: (0, 0),
+            end: (1, 2),
+        },
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_conv2d_layouts::<R, F>(case, 8, true, device);
+}
+
+pub fn test_depthwise_conv2d_asymmetric_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        padding: Padding2d {
+            begin: (2, 0),
+            end: (0, 1),
+        },
+        line_size: 4,
+        ..Conv2dTestCase::grouped(8)
+    };
+
+    test_conv2d::<R, F>(case, device);
+}
+
+/// Reflect padding on every side, the corners being mirrored along both dimensions. The 3x3
+/// kernel doesn't use the Winograd convolution, which only pads with zeros.
+pub fn test_conv2d_reflect_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        padding: Padding2d {
+            begin: (2, 1),
+            end: (1, 2),
+        },
+        padding_mode: PaddingMode::Reflect,
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_conv2d_launcher::<R, F>(case, device, |client, input, weight, bias, out, options| {
+        launch_conv2d::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+            client, input, weight, bias, out, options,
+        )
+    });
+}
+
+/// Replicate padding larger than the kernel reach on some sides, with a stride and dilation.
+pub fn test_conv2d_replicate_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        stride: (2, 1),
+        padding: Padding2d {
+            begin: (3, 0),
+            end: (1, 4),
+        },
+        padding_mode: PaddingMode::Replicate,
+        dilation: (1, 2),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_conv2d_launcher::<R, F>(case, device, |client, input, weight, bias, out, options| {
+        launch_conv2d::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+            client, input, weight, bias, out, options,
+        )
+    });
+}
+
+pub fn test_depthwise_conv2d_reflect_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        stride: (2, 2),
+        padding: Padding2d {
+            begin: (2, 2),
+            end: (1, 0),
+        },
+        padding_mode: PaddingMode::Reflect,
+        line_size: 4,
+        ..Conv2dTestCase::grouped(8)
+    };
+
+    test_conv2d::<R, F>(case, device);
+}
+
+/// A reflection can't cross the input more than once.
+pub fn test_conv2d_reflect_padding_too_large<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let options = Conv2dOptions {
+        padding: (1, 3).into(),
+        padding_mode: PaddingMode::Reflect,
+        ..Default::default()
+    };
+    let input = TensorHandle::<R, F>::zeros(&client, vec![1, 4, 3, 4]);
+    let weight = TensorHandle::<R, F>::zeros(&client, vec![4, 3, 3, 4]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![1, 4, 7, 4]);
+
+    let result = launch_conv2d::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        None,
+        &out.as_ref(),
+        &options,
+    );
+
+    assert!(matches!(
+        result,
+        Err(ConvLaunchError::InvalidOptions { .. })
+    ));
+}
+
+/// Enough batches for the cubes of the implicit GEMM along m to exceed the maximum cube count of
+/// the device, 65535 on wgpu, so that they are split into several launches.
+pub fn test_conv2d_cube_count_above_device_limit<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let max_cube_count = client.properties().hardware_properties().max_cube_count;
+    // A stage covers at most 256 rows of the output.
+    let rows = (max_cube_count.x as usize + 1) * 256;
+    if rows > 1 << 24 {
+        println!("Skipped - the device allows too many cubes to exceed them");
+        return;
+    }
+
+    // The padding makes the output larger than the input, 7x7 positions per batch.
+    let case = Conv2dTestCase {
+        kernel_size: (2, 2),
+        padding: Padding2d::symmetric((3, 3)),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_conv2d_input::<R, F>(case, [rows.div_ceil(49), 2, 2, 1], device);
+}
+
+/// Enough output positions for the cubes of the depthwise kernel to exceed the maximum cube count
+/// of the device along y, 65535 on wgpu and CUDA, so that they are split into several launches.
+pub fn test_depthwise_conv2d_cube_count_above_device_limit<
+    R: Runtime,
+    F: Float + CubeElement + Display,
+>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let max_cube_count = client.properties().hardware_properties().max_cube_count;
+    // A cube covers 32 output positions.
+    let positions = (max_cube_count.y as usize + 1) * 32;
+    if positions > 1 << 22 {
+        println!("Skipped - the device allows too many cubes to exceed them");
+        return;
+    }
+
+    let case = Conv2dTestCase {
+        line_size: 4,
+        ..Conv2dTestCase::grouped(4)
+    };
+
+    test_conv2d_input::<R, F>(case, [1, 1024, positions / 1024 + 1, 4], device);
+}
+
+pub fn test_conv2d_relu_epilogue<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d_activation::<R, F>(MatmulEpilogue::Relu, 1, |x| x.max(0.0), device);
+}
+
+pub fn test_conv2d_relu6_epilogue<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_activation::<R, F>(MatmulEpilogue::Relu6, 1, |x| x.clamp(0.0, 6.0), device);
+}
+
+pub fn test_conv2d_silu_epilogue<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d_activation::<R, F>(MatmulEpilogue::Silu, 1, |x| x / (1.0 + (-x).exp()), device);
+}
+
+pub fn test_depthwise_conv2d_relu6_epilogue<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_activation::<R, F>(MatmulEpilogue::Relu6, 8, |x| x.clamp(0.0, 6.0), device);
+}
+
+/// The product of the [im2col] columns of the input with the
+/// `[k, out_channels]` weight is the convolution computed by [launch_conv2d].
+pub fn test_im2col_matmul_matches_conv2d<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input_shape = [2, 9, 11, 8];
+    let [batches, in_h, in_w, channels] = input_shape;
+    let out_channels = 8;
+    let kernel_size = (3, 3);
+    let options = Conv2dOptions {
+        stride: (2, 1),
+        padding: (1, 1).into(),
+        ..Default::default()
+    };
+
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, channels, in_h, in_w],
+        [out_channels, channels, kernel_size.0, kernel_size.1],
+        options.stride,
+        options.padding,
+        options.dilation,
+        options.groups,
+    )
+    .unwrap();
+    let shape_m = batches * out_h * out_w;
+    let shape_k = kernel_size.0 * kernel_size.1 * channels;
+
+    let input = small_integers(batches * in_h * in_w * channels, 1, 0);
+    let weight = small_integers(out_channels * shape_k, 3, 1);
+    let input = tensor_from_integers::<R, F>(&client, &input, &input_shape);
+    // The `[out_channels, kernel_h, kernel_w, channels]` weight, which is the column-major
+    // `[k, out_channels]` weight of the product.
+    let weight = tensor_from_integers::<R, F>(
+        &client,
+        &weight,
+        &[out_channels, kernel_size.0, kernel_size.1, channels],
+    );
+    let weight_k = TensorHandle::<R, F>::new(
+        vec![shape_k, out_channels],
+        vec![1, shape_k],
+        weight.handle.clone(),
+    );
+
+    let out = TensorHandle::<R, F>::empty(&client, vec![batches, out_h, out_w, out_channels]);
+    let result = launch_conv2d::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        None,
+        &out.as_ref(),
+        &options,
+    );
+
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    let columns = im2col::<R, F>(&client, &input.as_ref(), kernel_size, &options).unwrap();
+    let product = TensorHandle::<R, F>::empty(&client, vec![shape_m, out_channels]);
+    matmul::launch_ref::<R, F>(
+        &Strategy::Naive,
+        &client,
+        &columns.as_ref(),
+        &weight_k.as_ref(),
+        &product.as_ref(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        product.to_vec(&client),
+        out.to_vec(&client),
+        "im2col and matmul differ from the convolution"
+    );
+}
+
+pub fn test_conv2d_winograd<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_conv2d_winograd_case::<R, F>(
+        [2, 7, 9, 5],
+        6,
+        (1, 1).into(),
+        true,
+        MatmulEpilogue::None,
+        device,
+    );
+}
+
+pub fn test_conv2d_winograd_no_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_winograd_case::<R, F>(
+        [1, 8, 10, 8],
+        4,
+        (0, 0).into(),
+        false,
+        MatmulEpilogue::Relu6,
+        device,
+    );
+}
+
+pub fn test_conv2d_winograd_trailing_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let padding = Padding2d {
+        begin: (0, 0),
+        end: (2, 1),
+    };
+
+    test_conv2d_winograd_case::<R, F>([2, 7, 8, 4], 5, padding, true, MatmulEpilogue::None, device);
+}
+
+pub fn test_conv2d_winograd_unsupported<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let options = Conv2dOptions {
+        stride: (2, 1),
+        padding: (1, 1).into(),
+        ..Default::default()
+    };
+    let input = TensorHandle::<R, F>::zeros(&client, vec![1, 9, 9, 4]);
+    let weight = TensorHandle::<R, F>::zeros(&client, vec![4, 3, 3, 4]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![1, 5, 9, 4]);
+
+    let result = conv2d_winograd::<R, F>(
+        &Strategy::Naive,
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        None,
+        &out.as_ref(),
+        &options,
+    );
+
+    assert!(matches!(
+        result,
+        Err(ConvLaunchError::Unimplemented(
+            ConvUnimplementedError::Winograd { stride: (2, 1), .. }
+        ))
+    ));
+}
+
+/// Compare the Winograd convolution of an NHWC input of `input_shape` with a 3x3 kernel to
+/// `out_channels`, with a bias if `has_bias`, with a direct computation on the CPU.
+fn test_conv2d_winograd_case<R: Runtime, F: Float + CubeElement + Display>(
+    input_shape: [usize; 4],
+    out_channels: usize,
+    padding: Padding2d,
+    has_bias: bool,
+    epilogue: MatmulEpilogue,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let [batches, in_h, in_w, channels] = input_shape;
+    let options = Conv2dOptions {
+        padding,
+        epilogue,
+        ..Default::default()
+    };
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, channels, in_h, in_w],
+        [out_channels, channels, 3, 3],
+        options.stride,
+        options.padding,
+        options.dilation,
+        options.groups,
+    )
+    .unwrap();
+
+    // Values in `[-1, 1)` which aren't small integers, so that the transforms are rounded.
+    let values = |len: usize, scale: usize, offset: usize| {
+        (0..len)
+            .map(|i| F::new(((i * scale + offset) * 37 % 101) as f32 / 50.0 - 1.0))
+            .collect::<Vec<_>>()
+    };
+    let input = values(batches * in_h * in_w * channels, 1, 0);
+    let weight_shape = [out_channels, 3, 3, channels];
+    let weight = values(weight_shape.iter().product(), 7, 3);
+    let bias = match has_bias {
+        true => values(out_channels, 1, 11),
+        false => vec![F::new(0.0); out_channels],
+    };
+
+    let case = Conv2dTestCase {
+        padding,
+        ..Conv2dTestCase::grouped(1)
+    };
+    let to_f64 = |data: &[F]| data.iter().map(|v| v.to_f64().unwrap()).collect::<Vec<_>>();
+    let expected = case
+        .reference()
+        .run(
+            &to_f64(&input),
+            &input_shape,
+            &to_f64(&weight),
+            &to_f64(&bias),
+        )
+        .into_iter()
+        .map(|sum| match epilogue {
+            MatmulEpilogue::Relu6 => sum.clamp(0.0, 6.0),
+            _ => sum,
+        })
+        .map(|sum| F::new(sum as f32))
+        .collect::<Vec<_>>();
+
+    let input = TensorHandle::<R, F>::from_data(&client, &input, &input_shape);
+    let weight = TensorHandle::<R, F>::from_data(&client, &weight, &weight_shape);
+    let bias = has_bias.then(|| TensorHandle::<R, F>::from_data(&client, &bias, &[out_channels]));
+    let out = TensorHandle::<R, F>::empty(&client, vec![batches, out_h, out_w, out_channels]);
+
+    let result = conv2d_winograd::<R, F>(
+        &Strategy::Tiling2D(Default::default()),
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        bias.as_ref().map(TensorHandle::as_ref).as_ref(),
+        &out.as_ref(),
+        &options,
+    );
+
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 1e-4) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_conv2d_im2col_gemm<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = Conv2dTestCase {
+        stride: (2, 1),
+        ..Conv2dTestCase::grouped(1)
+    };
+
+    test_conv2d_launcher::<R, F>(case, device, |client, input, weight, bias, out, options| {
+        conv2d_im2col::<R, F>(&Strategy::Naive, client, input, weight, bias, out, options)
+    });
+}
+
+pub fn test_conv2d_autotune<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let mut selected = None;
+    test_conv2d_launcher::<R, F>(
+        Conv2dTestCase::grouped(1),
+        device,
+        |client, input, weight, bias, out, options| {
+            selected = Some(conv2d_autotune::<R, F>(
+                client, device, input, weight, bias, out, options,
+            )?);
+            Ok(())
+        },
+    );
+
+    // The explicit im2col is a candidate on every device, so the convolution is never skipped
+    // and its output has been compared with the reference.
+    let algorithm = selected.expect("The autotuned convolution should be launched");
+    // Every algorithm but the depthwise one applies to a 3x3 convolution with a stride of 1.
+    assert_ne!(algorithm, Conv2dAlgorithm::Depthwise);
+}
+
+pub fn test_conv2d_autotune_depthwise<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = Conv2dTestCase {
+        stride: (2, 2),
+        ..Conv2dTestCase::grouped(8)
+    };
+
+    let mut selected = None;
+    test_conv2d_launcher::<R, F>(case, device, |client, input, weight, bias, out, options| {
+        selected = Some(conv2d_autotune::<R, F>(
+            client, device, input, weight, bias, out, options,
+        )?);
+        Ok(())
+    });
+
+    // The depthwise kernel is the only candidate, so it is launched without benchmarking.
+    assert_eq!(selected, Some(Conv2dAlgorithm::Depthwise));
+}
+
+/// A 7x7 convolution of 512 channels, k being 25088, accumulated in the tensor type and in f32.
+pub fn test_conv2d_mixed_accuracy_large_k<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let [batches, in_h, in_w, in_channels] = [1, 8, 8, 512];
+    let (kernel_h, kernel_w) = (7, 7);
+    let out_channels = 16;
+    let options = Conv2dOptions {
+        padding: Padding2d::symmetric((3, 3)),
+        ..Default::default()
+    };
+
+    // Values spread over [-1, 1], so that the sums are far larger than each product.
+    let value = |i: usize| F::new((i * 7919 % 257) as f32 / 128.0 - 1.0);
+    let input = (0..batches * in_h * in_w * in_channels)
+        .map(value)
+        .collect::<Vec<_>>();
+    let weight = (0..out_channels * kernel_h * kernel_w * in_channels)
+        .map(|i| value(i * 3 + 1))
+        .collect::<Vec<_>>();
+    let bias = (0..out_channels).map(|i| value(i + 2)).collect::<Vec<_>>();
+
+    let case = Conv2dTestCase {
+        kernel_size: (kernel_h, kernel_w),
+        padding: options.padding,
+        ..Conv2dTestCase::grouped(1)
+    };
+    let to_f64 = |data: &[F]| data.iter().map(|v| v.to_f64().unwrap()).collect::<Vec<_>>();
+    let reference = case.reference().run(
+        &to_f64(&input),
+        &[batches, in_h, in_w, in_channels],
+        &to_f64(&weight),
+        &to_f64(&bias),
+    );
+
+    let input =
+        TensorHandle::<R, F>::from_data(&client, &input, &[batches, in_h, in_w, in_channels]);
+    let weight = TensorHandle::<R, F>::from_data(
+        &client,
+        &weight,
+        &[out_channels, kernel_h, kernel_w, in_channels],
+    );
+    let bias = TensorHandle::<R, F>::from_data(&client, &bias, &[out_channels]);
+    let out_shape = vec![batches, in_h, in_w, out_channels];
+
+    let pure = TensorHandle::<R, F>::empty(&client, out_shape.clone());
+    let mixed = TensorHandle::<R, F>::empty(&client, out_shape);
+    let result = launch_conv2d::<R, (F, F, F), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        Some(&bias.as_ref()),
+        &pure.as_ref(),
+        &options,
+    )
+    .and_then(|_| {
+        launch_conv2d::<R, (F, F, f32), ImplicitCmmaConv, Large>(
+            &client,
+            &input.as_ref(),
+            &weight.as_ref(),
+            Some(&bias.as_ref()),
+            &mixed.as_ref(),
+            &options,
+        )
+    });
+
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    let max_error = |out: &TensorHandle<R, F>| {
+        out.to_vec(&client)
+            .iter()
+            .zip(&reference)
+            .map(|(actual, expected)| (actual.to_f64().unwrap() - expected).abs())
+            .fold(0.0, f64::max)
+    };
+    let pure_error = max_error(&pure);
+    let mixed_error = max_error(&mixed);
+    let max_reference = reference
+        .iter()
+        .map(|value| value.abs())
+        .fold(0.0, f64::max);
+
+    // Only the rounding of the output remains with an f32 accumulator.
+    assert!(
+        mixed_error <= 1e-2 * max_reference,
+        "mixed error {mixed_error} for results up to {max_reference}"
+    );
+    // Accumulating f16 or bf16 over a long k loses precision on every sum.
+    if size_of::<F>() < size_of::<f32>() {
+        assert!(
+            mixed_error * 10.0 <= pure_error,
+            "mixed error {mixed_error} not an order of magnitude below pure error {pure_error}"
+        );
+    }
+}
+
+/// A 3x3 convolution, which Winograd could compute, of tensors accumulated in f32 with the default
+/// options is only off by the rounding of the output to the tensor type.
+pub fn test_conv2d_default_accumulates_in_f32<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input_shape = [1, 8, 8, 256];
+    let [batches, in_h, in_w, in_channels] = input_shape;
+    let out_channels = 8;
+    let options = Conv2dOptions {
+        padding: Padding2d::symmetric((1, 1)),
+        ..Default::default()
+    };
+    assert!(is_winograd_compatible((3, 3), &options));
+
+    let value = |i: usize| F::new((i * 7919 % 257) as f32 / 128.0 - 1.0);
+    let input = (0..input_shape.iter().product())
+        .map(value)
+        .collect::<Vec<_>>();
+    let weight = (0..out_channels * 9 * in_channels)
+        .map(|i| value(i * 3 + 1))
+        .collect::<Vec<_>>();
+    let bias = (0..out_channels).map(|i| value(i + 2)).collect::<Vec<_>>();
+
+    let case = Conv2dTestCase {
+        kernel_size: (3, 3),
+        padding: options.padding,
+        ..Conv2dTestCase::grouped(1)
+    };
+    let to_f64 = |data: &[F]| data.iter().map(|v| v.to_f64().unwrap()).collect::<Vec<_>>();
+    let reference = case.reference().run(
+        &to_f64(&input),
+        &input_shape,
+        &to_f64(&weight),
+        &to_f64(&bias),
+    );
+
+    let input = TensorHandle::<R, F>::from_data(&client, &input, &input_shape);
+    let weight =
+        TensorHandle::<R, F>::from_data(&client, &weight, &[out_channels, 3, 3, in_channels]);
+    let bias = TensorHandle::<R, F>::from_data(&client, &bias, &[out_channels]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![batches, in_h, in_w, out_channels]);
+
+    let result = launch_conv2d::<R, (F, F, f32), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        Some(&bias.as_ref()),
+        &out.as_ref(),
+        &options,
+    );
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    let max_error = out
+        .to_vec(&client)
+        .iter()
+        .zip(&reference)
+        .map(|(actual, expected)| (actual.to_f64().unwrap() - expected).abs())
+        .fold(0.0, f64::max);
+    let max_reference = reference
+        .iter()
+        .map(|value| value.abs())
+        .fold(0.0, f64::max);
+
+    // A rounding of the output is at most half an epsilon, the slack covering the f32 sums.
+    let tolerance = (F::EPSILON.to_f64().unwrap() + 1e-4) * max_reference;
+    assert!(
+        max_error <= tolerance,
+        "error {max_error} above {tolerance} for results up to {max_reference}"
+    );
+}
+
+/// Compare the convolution of `case`, with a bias, computed by `launch` from NHWC, OHWI and
+/// NHWC tensors, with a direct computation on the CPU.
+fn test_conv2d_launcher<R: Runtime, F: Float + CubeElement + Display>(
+    case: Conv2dTestCase,
+    device: &R::Device,
+    launch: impl FnOnce(
+        &ComputeClient<R::Server, R::Channel>,
+        &TensorHandleRef<'_, R>,
+        &TensorHandleRef<'_, R>,
+        Option<&TensorHandleRef<'_, R>>,
+        &TensorHandleRef<'_, R>,
+        &Conv2dOptions,
+    ) -> Result<(), ConvLaunchError>,
+) {
+    let client = R::client(device);
+    let input_shape = [2, 9, 11, 8];
+    let [batches, in_h, in_w, in_channels] = input_shape;
+    let out_channels = 8;
+    let (kernel_h, kernel_w) = case.kernel_size;
+    let group_channels = in_channels / case.groups;
+    let options = Conv2dOptions {
+        stride: case.stride,
+        padding: case.padding,
+        padding_mode: case.padding_mode,
+        dilation: case.dilation,
+        groups: case.groups,
+        ..Default::default()
+    };
+
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, in_channels, in_h, in_w],
+        [out_channels, group_channels, kernel_h, kernel_w],
+        case.stride,
+        case.padding,
+        case.dilation,
+        case.groups,
+    )
+    .unwrap();
+    let weight_shape = [out_channels, kernel_h, kernel_w, group_channels];
+
+    let input = small_integers(input_shape.iter().product(), 1, 0);
+    let weight = small_integers(weight_shape.iter().product(), 3, 1);
+    let bias = small_integers(out_channels, 1, 2);
+
+    let expected = case
+        .reference()
+        .run(&input, &input_shape, &weight, &bias)
+        .into_iter()
+        .map(F::from_int)
+        .collect::<Vec<_>>();
+
+    let input = tensor_from_integers::<R, F>(&client, &input, &input_shape);
+    let weight = tensor_from_integers::<R, F>(&client, &weight, &weight_shape);
+    let bias = tensor_from_integers::<R, F>(&client, &bias, &[out_channels]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![batches, out_h, out_w, out_channels]);
+
+    let result = launch(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        Some(&bias.as_ref()),
+        &out.as_ref(),
+        &options,
+    );
+
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    // The Winograd transforms aren't exact, even on small integers.
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+/// A convolution of an `[2, 9, 11, 8]` NHWC input to 8 output channels with a bias.
+#[derive(Clone, Copy)]
+struct Conv2dTestCase {
+    groups: usize,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding: Padding2d,
+    padding_mode: PaddingMode,
+    dilation: (usize, usize),
+    /// Line size of every tensor, along the channels
+    line_size: u8,
+}
+
+impl Conv2dTestCase {
+    /// A 3x3 convolution with a padding of 1, read one element at a time.
+    fn grouped(groups: usize) -> Self {
+        Self {
+            groups,
+            kernel_size: (3, 3),
+            stride: (1, 1),
+            padding: Padding2d::symmetric((1, 1)),
+            padding_mode: PaddingMode::Zero,
+            dilation: (1, 1),
+            line_size: 1,
+        }
+    }
+
+    /// The convolution computed on the CPU, with an `[out_channels, kernel_h, kernel_w,
+    /// group_channels]` weight.
+    fn reference(&self) -> ReferenceConv {
+        ReferenceConv {
+            kernel_size: vec![self.kernel_size.0, self.kernel_size.1],
+            stride: vec![self.stride.0, self.stride.1],
+            padding: vec![self.padding.begin.0, self.padding.begin.1],
+            padding_end: vec![self.padding.end.0, self.padding.end.1],
+            dilation: vec![self.dilation.0, self.dilation.1],
+            padding_mode: self.padding_mode,
+            groups: self.groups,
+        }
+    }
+}
+
+/// Compare the convolution of `case` with a direct computation on the CPU.
+fn test_conv2d<R: Runtime, F: Float + CubeElement + Display>(
+    case: Conv2dTestCase,
+    device: &R::Device,
+) {
+    test_conv2d_input::<R, F>(case, [2, 9, 11, 8], device);
+}
+
+/// Compare the convolution of `case` of an NHWC input of `input_shape` to as many output channels
+/// as input channels, with a direct computation on the CPU.
+fn test_conv2d_input<R: Runtime, F: Float + CubeElement + Display>(
+    case: Conv2dTestCase,
+    input_shape: [usize; 4],
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let [batches, in_h, in_w, in_channels] = input_shape;
+    let out_channels = in_channels;
+    let (kernel_h, kernel_w) = case.kernel_size;
+    let (stride, padding, dilation) = (case.stride, case.padding, case.dilation);
+    let group_channels = in_channels / case.groups;
+    let group_n = out_channels / case.groups;
+
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, in_channels, in_h, in_w],
+        [out_channels, group_channels, kernel_h, kernel_w],
+        stride,
+        padding,
+        dilation,
+        case.groups,
+    )
+    .unwrap();
+    let shape_k = kernel_h * kernel_w * group_channels;
+
+    let input = small_integers(input_shape.iter().product(), 1, 0);
+    let weight = small_integers(out_channels * shape_k, 3, 1);
+    let bias = small_integers(out_channels, 1, 2);
+
+    let expected = case
+        .reference()
+        .run(&input, &input_shape, &weight, &bias)
+        .into_iter()
+        .map(F::from_int)
+        .collect::<Vec<_>>();
+
+    let input = tensor_from_integers::<R, F>(&client, &input, &input_shape);
+    let weight = tensor_from_integers::<R, F>(
+        &client,
+        &k_major(&weight, out_channels),
+        &[shape_k, out_channels],
+    );
+    let bias = tensor_from_integers::<R, F>(&client, &bias, &[out_channels]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![batches * out_h * out_w, out_channels]);
+
+    let problem = ConvolutionProblem {
+        m: batches * out_h * out_w,
+        n: group_n,
+        k: shape_k,
+        lhs_layout: MatrixLayout::RowMajor,
+        rhs_layout: MatrixLayout::RowMajor,
+        lhs_line_size: case.line_size,
+        rhs_line_size: case.line_size,
+        out_line_size: case.line_size,
+        kernel_size: (kernel_h as u32, kernel_w as u32),
+        stride: (stride.0 as u32, stride.1 as u32),
+        padding: (padding.begin.0 as i32, padding.begin.1 as i32),
+        padding_end: (padding.end.0 as i32, padding.end.1 as i32),
+        dilation: (dilation.0 as u32, dilation.1 as u32),
+        padding_mode: case.padding_mode,
+        out_shape_y: out_h,
+        out_shape_x: out_w,
+        has_bias: true,
+        groups: case.groups,
+        depth: None,
+        epilogue: MatmulEpilogue::None,
+    };
+
+    let line_size = case.line_size;
+    let result = launch_conv2d_nhwc::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+        &client,
+        input.as_arg(line_size),
+        weight.as_arg(line_size),
+        bias.as_arg(line_size),
+        out.as_arg(line_size),
+        problem,
+    );
+
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    assert_eq!(out.to_vec(&client), expected, "convolution outputs differ");
+}
+
+/// Compare the convolution of `case` to `out_channels`, with a bias if `has_bias`, launched on
+/// NHWC, OHWI and NHWC tensors, then on NCHW, OIHW and NCHW tensors holding the same logical
+/// values, with a direct computation on the CPU.
+fn test_conv2d_layouts<R: Runtime, F: Float + CubeElement + Display>(
+    case: Conv2dTestCase,
+    out_channels: usize,
+    has_bias: bool,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input_shape = [2, 9, 11, 8];
+    let [batches, in_h, in_w, in_channels] = input_shape;
+    let (kernel_h, kernel_w) = case.kernel_size;
+    let group_channels = in_channels / case.groups;
+    let options = Conv2dOptions {
+        stride: case.stride,
+        padding: case.padding,
+        dilation: case.dilation,
+        groups: case.groups,
+        ..Default::default()
+    };
+
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, in_channels, in_h, in_w],
+        [out_channels, group_channels, kernel_h, kernel_w],
+        case.stride,
+        case.padding,
+        case.dilation,
+        case.groups,
+    )
+    .unwrap();
+    let weight_shape = [out_channels, kernel_h, kernel_w, group_channels];
+
+    let input = small_integers(input_shape.iter().product(), 1, 0);
+    let weight = small_integers(weight_shape.iter().product(), 3, 1);
+    let bias = match has_bias {
+        true => small_integers(out_channels, 1, 2),
+        false => vec![0; out_channels],
+    };
+
+    let expected = case
+        .reference()
+        .run(&input, &input_shape, &weight, &bias)
+        .into_iter()
+        .map(F::from_int)
+        .collect::<Vec<_>>();
+
+    // A tensor of `shape` holding the row-major `data`, stored with its dimensions in `order`.
+    let permuted = |data: &[i64], shape: [usize; 4], order: [usize; 4]| {
+        let memory_shape = order.map(|dim| shape[dim]);
+        let mut strides = [0; 4];
+        let mut stride = 1;
+        for &dim in order.iter().rev() {
+            strides[dim] = stride;
+            stride *= shape[dim];
+        }
+
+        let mut memory = vec![F::from_int(0); data.len()];
+        for (index, &value) in data.iter().enumerate() {
+            let offset = (0..4).fold(0, |offset, dim| {
+                let size_after = shape[dim + 1..].iter().product::<usize>();
+                offset + index / size_after % shape[dim] * strides[dim]
+            });
+            memory[offset] = F::from_int(value);
+        }
+
+        let memory = TensorHandle::<R, F>::from_data(&client, &memory, &memory_shape);
+        TensorHandle::<R, F>::new(shape.to_vec(), strides.to_vec(), memory.handle)
+    };
+    let bias = tensor_from_integers::<R, F>(&client, &bias, &[out_channels]);
+
+    // NCHW and OIHW both store the channels right after the first dimension.
+    for (layout, order) in [("NHWC", [0, 1, 2, 3]), ("NCHW", [0, 3, 1, 2])] {
+        let input = permuted(&input, input_shape, order);
+        let weight = permuted(&weight, weight_shape, order);
+        let out_shape = [batches, out_h, out_w, out_channels];
+        let out = permuted(&vec![0; expected.len()], out_shape, order);
+
+        let result = launch_conv2d::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+            &client,
+            &input.as_ref(),
+            &weight.as_ref(),
+            has_bias.then(|| bias.as_ref()).as_ref(),
+            &out.as_ref(),
+            &options,
+        );
+
+        if is_unsupported(&result) {
+            println!("Skipped - not supported: {result:?}");
+            return;
+        }
+        result.unwrap();
+
+        assert_eq!(
+            out.to_vec(&client),
+            expected,
+            "{layout} convolution outputs differ"
+        );
+    }
+}
+
+/// Compare a 1x1 convolution of `groups` with an activation to the `reference` activation of the
+/// convolution computed on the CPU.
+///
+/// Each output channel reads a single input channel with a weight of 1, so the outputs before the
+/// activation are the inputs plus the bias, ranging from -5 to 9 on both sides of 0 and 6.
+fn test_conv2d_activation<R: Runtime, F: Float + CubeElement + Display>(
+    epilogue: MatmulEpilogue,
+    groups: usize,
+    reference: impl Fn(f32) -> f32,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input_shape = [2, 9, 11, 8];
+    let [batches, in_h, in_w, channels] = input_shape;
+    let case = Conv2dTestCase {
+        kernel_size: (1, 1),
+        padding: (0, 0).into(),
+        ..Conv2dTestCase::grouped(groups)
+    };
+    let group_channels = channels / groups;
+    let m = batches * in_h * in_w;
+
+    let input = (0..m * channels)
+        .map(|i| (i % 13) as i64 - 4)
+        .collect::<Vec<_>>();
+    // The `[channels, group_channels]` weight.
+    let weight = (0..channels * group_channels)
+        .map(|i| (i % group_channels == i / group_channels % group_channels) as i64)
+        .collect::<Vec<_>>();
+    let bias = (0..channels)
+        .map(|o| (o % 3) as i64 - 1)
+        .collect::<Vec<_>>();
+
+    let expected = case
+        .reference()
+        .run(&input, &input_shape, &weight, &bias)
+        .into_iter()
+        .map(|value| F::new(reference(value as f32)))
+        .collect::<Vec<_>>();
+
+    let input = tensor_from_integers::<R, F>(&client, &input, &input_shape);
+    let weight = tensor_from_integers::<R, F>(
+        &client,
+        &k_major(&weight, channels),
+        &[group_channels, channels],
+    );
+    let bias = tensor_from_integers::<R, F>(&client, &bias, &[channels]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![m, channels]);
+
+    let problem = ConvolutionProblem {
+        m,
+        n: channels / groups,
+        k: group_channels,
+        lhs_layout: MatrixLayout::RowMajor,
+        rhs_layout: MatrixLayout::RowMajor,
+        lhs_line_size: 1,
+        rhs_line_size: 1,
+        out_line_size: 1,
+        kernel_size: (1, 1),
+        stride: (1, 1),
+        padding: (0, 0),
+        padding_end: (0, 0),
+        dilation: (1, 1),
+        padding_mode: PaddingMode::Zero,
+        out_shape_y: in_h,
+        out_shape_x: in_w,
+        has_bias: true,
+        groups,
+        depth: None,
+        epilogue,
+    };
+
+    let result = launch_conv2d_nhwc::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+        &client,
+        input.as_arg(1),
+        weight.as_arg(1),
+        bias.as_arg(1),
+        out.as_arg(1),
+        problem,
+    );
+
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+/// The `[k, out_channels]` weight read by [launch_conv2d_nhwc], k being ordered as (kernel y,
+/// kernel x, channel), holding the same values as an `[out_channels, k]` weight.
+fn k_major(weight: &[i64], out_channels: usize) -> Vec<i64> {
+    let shape_k = weight.len() / out_channels;
+
+    (0..shape_k * out_channels)
+        .map(|i| weight[i % out_channels * shape_k + i / out_channels])
+        .collect()
+}
+
+/// Compare the transposed convolution of a `[2, 5, 6, 4]` NHWC input to 3 output channels with a
+/// bias, using a `kernel_size` kernel, with the scatter of each input position on the CPU.
+fn test_conv_transpose2d<R: Runtime, F: Float + CubeElement + Display>(
+    kernel_size: (usize, usize),
+    options: ConvTranspose2dOptions,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let [batches, in_h, in_w, in_channels] = [2, 5, 6, 4];
+    let out_channels = 3;
+    let (kernel_h, kernel_w) = kernel_size;
+    let (stride, padding, dilation) = (options.stride, options.padding, options.dilation);
+
+    let [_, _, out_h, out_w] = conv_transpose2d_output_shape(
+        [batches, in_channels, in_h, in_w],
+        [in_channels, out_channels, kernel_h, kernel_w],
+        &options,
+    )
+    .unwrap();
+
+    let input = small_integers(batches * in_h * in_w * in_channels, 1, 0);
+    // The `[in_channels, kernel_h, kernel_w, out_channels]` weight.
+    let weight = small_integers(in_channels * kernel_h * kernel_w * out_channels, 3, 1);
+    let bias = small_integers(out_channels, 1, 2);
+
+    let mut expected = (0..batches * out_h * out_w * out_channels)
+        .map(|i| bias[i % out_channels])
+        .collect::<Vec<_>>();
+    for b in 0..batches {
+        for iy in 0..in_h {
+            for ix in 0..in_w {
+                for ky in 0..kernel_h {
+                    for kx in 0..kernel_w {
+                        let y = (iy * stride.0 + ky * dilation.0) as i64 - padding.0 as i64;
+                        let x = (ix * stride.1 + kx * dilation.1) as i64 - padding.1 as i64;
+                        if y < 0 || y >= out_h as i64 || x < 0 || x >= out_w as i64 {
+                            continue;
+                        }
+
+                        let pixel = (b * out_h + y as usize) * out_w + x as usize;
+                        for c in 0..in_channels {
+                            let input = input[((b * in_h + iy) * in_w + ix) * in_channels + c];
+                            for o in 0..out_channels {
+                                let k = (c * kernel_h + ky) * kernel_w + kx;
+                                expected[pixel * out_channels + o] +=
+                                    input * weight[k * out_channels + o];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let expected = expected.into_iter().map(F::from_int).collect::<Vec<_>>();
+
+    let input = tensor_from_integers::<R, F>(&client, &input, &[batches, in_h, in_w, in_channels]);
+    let weight = tensor_from_integers::<R, F>(
+        &client,
+        &weight,
+        &[in_channels, kernel_h, kernel_w, out_channels],
+    );
+    let bias = tensor_from_integers::<R, F>(&client, &bias, &[out_channels]);
+
+    let out = conv_transpose2d::<R, F, f32>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        Some(&bias.as_ref()),
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(out.shape, [batches, out_h, out_w, out_channels]);
+    assert_eq!(
+        out.to_vec(&client),
+        expected,
+        "transposed convolution outputs differ"
+    );
+}
+
+/// Check the weight gradient of the convolution of `case`, on a `[batches, 7, 6, 3]` input with 4
+/// output channels, against its definition computed on the CPU, then against finite differences
+/// of `loss = sum(conv(input, weight) * grad_out)` computed with the forward kernel.
+///
+/// The convolution is linear in the weight, so the difference of the loss between `weight` and
+/// `weight + direction` is exactly the dot product of the gradient with the direction.
+fn test_backward_weight<R: Runtime, F: Float + CubeElement + Display>(
+    batches: usize,
+    case: Conv2dTestCase,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let [in_h, in_w, in_channels] = [7, 6, 3];
+    let out_channels = 4;
+    let (kernel_h, kernel_w) = case.kernel_size;
+    let (stride, padding, dilation) = (case.stride, case.padding, case.dilation);
+    let options = Conv2dOptions {
+        stride,
+        padding,
+        dilation,
+        ..Default::default()
+    };
+
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, in_channels, in_h, in_w],
+        [out_channels, in_channels, kernel_h, kernel_w],
+        stride,
+        padding,
+        dilation,
+        1,
+    )
+    .unwrap();
+    let shape_k = kernel_h * kernel_w * in_channels;
+    let shape_m = batches * out_h * out_w;
+
+    let input = small_integers(batches * in_h * in_w * in_channels, 1, 0);
+    let grad_out = small_integers(shape_m * out_channels, 5, 2);
+
+    // The `[shape_m, shape_k]` unfolded input, k being ordered as (kernel y, kernel x, channel).
+    let mut cols = vec![0; shape_m * shape_k];
+    for b in 0..batches {
+        for oy in 0..out_h {
+            for ox in 0..out_w {
+                for ky in 0..kernel_h {
+                    for kx in 0..kernel_w {
+                        let y = (oy * stride.0 + ky * dilation.0) as i64 - padding.begin.0 as i64;
+                        let x = (ox * stride.1 + kx * dilation.1) as i64 - padding.begin.1 as i64;
+                        if y < 0 || y >= in_h as i64 || x < 0 || x >= in_w as i64 {
+                            continue;
+                        }
+
+                        let pixel = (b * in_h + y as usize) * in_w + x as usize;
+                        let m = (b * out_h + oy) * out_w + ox;
+                        for c in 0..in_channels {
+                            let k = (ky * kernel_w + kx) * in_channels + c;
+                            cols[m * shape_k + k] = input[pixel * in_channels + c];
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut expected = vec![0; shape_k * out_channels];
+    for m in 0..shape_m {
+        for k in 0..shape_k {
+            for o in 0..out_channels {
+                expected[k * out_channels + o] +=
+                    cols[m * shape_k + k] * grad_out[m * out_channels + o];
+            }
+        }
+    }
+
+    let input = tensor_from_integers::<R, F>(&client, &input, &[batches, in_h, in_w, in_channels]);
+    let grad_out =
+        tensor_from_integers::<R, F>(&client, &grad_out, &[batches, out_h, out_w, out_channels]);
+
+    let grad_weight = conv2d_backward_weight::<R, F, f32>(
+        &Strategy::Naive,
+        &client,
+        &input.as_ref(),
+        &grad_out.as_ref(),
+        case.kernel_size,
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(grad_weight.shape, [shape_k, out_channels]);
+    let grad_weight = grad_weight.to_vec(&client);
+    let expected = expected.into_iter().map(F::from_int).collect::<Vec<_>>();
+    assert_eq!(grad_weight, expected, "weight gradients differ");
+
+    let unused_bias = BoundBias::<R, F>::new(&client, None, out_channels);
+    let grad_out_values = grad_out.to_vec(&client);
+    for seed in 0..3 {
+        let direction = small_integers(shape_k * out_channels, 11, seed);
+
+        // conv(input, weight + direction) - conv(input, weight) = conv(input, direction)
+        let out = TensorHandle::<R, F>::empty(&client, vec![shape_m, out_channels]);
+        let problem = ConvolutionProblem {
+            m: shape_m,
+            n: out_channels,
+            k: shape_k,
+            lhs_layout: MatrixLayout::RowMajor,
+            rhs_layout: MatrixLayout::RowMajor,
+            lhs_line_size: 1,
+            rhs_line_size: 1,
+            out_line_size: 1,
+            kernel_size: (kernel_h as u32, kernel_w as u32),
+            stride: (stride.0 as u32, stride.1 as u32),
+            padding: (padding.begin.0 as i32, padding.begin.1 as i32),
+            padding_end: (padding.end.0 as i32, padding.end.1 as i32),
+            dilation: (dilation.0 as u32, dilation.1 as u32),
+            padding_mode: PaddingMode::Zero,
+            out_shape_y: out_h,
+            out_shape_x: out_w,
+            has_bias: false,
+            groups: 1,
+            depth: None,
+            epilogue: MatmulEpilogue::None,
+        };
+        let weight = tensor_from_integers::<R, F>(&client, &direction, &[shape_k, out_channels]);
+        let result = launch_conv2d_nhwc::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+            &client,
+            input.as_arg(1),
+            weight.as_arg(1),
+            unused_bias.as_arg(1),
+            out.as_arg(1),
+            problem,
+        );
+
+        if is_unsupported(&result) {
+            println!("Skipped finite differences - not supported: {result:?}");
+            return;
+        }
+        result.unwrap();
+
+        let to_f64 = |value: &F| value.to_f64().unwrap();
+        let loss_difference = out
+            .to_vec(&client)
+            .iter()
+            .zip(grad_out_values.iter())
+            .map(|(out, grad)| to_f64(out) * to_f64(grad))
+            .sum::<f64>();
+        let directional_derivative = grad_weight
+            .iter()
+            .zip(direction.iter())
+            .map(|(grad, &direction)| to_f64(grad) * direction as f64)
+            .sum::<f64>();
+
+        assert_eq!(
+            loss_difference, directional_derivative,
+            "finite differences along direction {seed} differ from the gradient"
+        );
+    }
+}
+
+/// Check the input gradient of the convolution of `case`, on an NHWC input of `input_shape` with
+/// `out_channels` output channels, against the scatter of the output gradient on the CPU.
+///
+/// With `check_gradient`, it is then compared with finite differences of `loss = sum(conv(input,
+/// weight) * grad_out)` computed with the forward kernel, like [test_backward_weight] but with the
+/// convolution being linear in the input.
+fn test_backward_data<R: Runtime, F: Float + CubeElement + Display>(
+    input_shape: [usize; 4],
+    out_channels: usize,
+    case: Conv2dTestCase,
+    check_gradient: bool,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let [batches, in_h, in_w, in_channels] = input_shape;
+    let (kernel_h, kernel_w) = case.kernel_size;
+    let (stride, padding, dilation) = (case.stride, case.padding, case.dilation);
+    let options = Conv2dOptions {
+        stride,
+        padding,
+        dilation,
+        ..Default::default()
+    };
+
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, in_channels, in_h, in_w],
+        [out_channels, in_channels, kernel_h, kernel_w],
+        stride,
+        padding,
+        dilation,
+        1,
+    )
+    .unwrap();
+    let shape_k = kernel_h * kernel_w * in_channels;
+    let shape_m = batches * out_h * out_w;
+
+    // The `[kernel_h, kernel_w, in_channels, out_channels]` weight.
+    let weight_values = small_integers(shape_k * out_channels, 3, 1);
+    let grad_out = small_integers(shape_m * out_channels, 5, 2);
+
+    let mut expected = vec![0; batches * in_h * in_w * in_channels];
+    for b in 0..batches {
+        for oy in 0..out_h {
+            for ox in 0..out_w {
+                for ky in 0..kernel_h {
+                    for kx in 0..kernel_w {
+                        let y = (oy * stride.0 + ky * dilation.0) as i64 - padding.begin.0 as i64;
+                        let x = (ox * stride.1 + kx * dilation.1) as i64 - padding.begin.1 as i64;
+                        if y < 0 || y >= in_h as i64 || x < 0 || x >= in_w as i64 {
+                            continue;
+                        }
+
+                        let pixel = (b * in_h + y as usize) * in_w + x as usize;
+                        let m = (b * out_h + oy) * out_w + ox;
+                        for c in 0..in_channels {
+                            let k = (ky * kernel_w + kx) * in_channels + c;
+                            for o in 0..out_channels {
+                                expected[pixel * in_channels + c] += grad_out[m * out_channels + o]
+                                    * weight_values[k * out_channels + o];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let weight = tensor_from_integers::<R, F>(
+        &client,
+        &weight_values,
+        &[kernel_h, kernel_w, in_channels, out_channels],
+    );
+    let grad_out =
+        tensor_from_integers::<R, F>(&client, &grad_out, &[batches, out_h, out_w, out_channels]);
+    let grad_input = TensorHandle::<R, F>::empty(&client, input_shape.to_vec());
+
+    conv2d_backward_data::<R, F, f32>(
+        &client,
+        &weight.as_ref(),
+        &grad_out.as_ref(),
+        &grad_input.as_ref(),
+        &options,
+    )
+    .unwrap();
+
+    let grad_input = grad_input.to_vec(&client);
+    let expected = expected.into_iter().map(F::from_int).collect::<Vec<_>>();
+    assert_eq!(
+        grad_input, expected,
+        "input gradients differ for {input_shape:?} to {out_channels} channels with {options:?}"
+    );
+
+    if !check_gradient {
+        return;
+    }
+
+    // The `[k, out_channels]` layout of the same weight, for the forward kernel.
+    let weight = tensor_from_integers::<R, F>(&client, &weight_values, &[shape_k, out_channels]);
+    let unused_bias = BoundBias::<R, F>::new(&client, None, out_channels);
+    let grad_out_values = grad_out.to_vec(&client);
+    for seed in 0..3 {
+        let direction = small_integers(batches * in_h * in_w * in_channels, 11, seed);
+
+        // conv(input + direction, weight) - conv(input, weight) = conv(direction, weight)
+        let input = tensor_from_integers::<R, F>(&client, &direction, &input_shape);
+        let out = TensorHandle::<R, F>::empty(&client, vec![shape_m, out_channels]);
+        let problem = ConvolutionProblem {
+            m: shape_m,
+            n: out_channels,
+            k: shape_k,
+            lhs_layout: MatrixLayout::RowMajor,
+            rhs_layout: MatrixLayout::RowMajor,
+            lhs_line_size: 1,
+            rhs_line_size: 1,
+            out_line_size: 1,
+            kernel_size: (kernel_h as u32, kernel_w as u32),
+            stride: (stride.0 as u32, stride.1 as u32),
+            padding: (padding.begin.0 as i32, padding.begin.1 as i32),
+            padding_end: (padding.end.0 as i32, padding.end.1 as i32),
+            dilation: (dilation.0 as u32, dilation.1 as u32),
+            padding_mode: PaddingMode::Zero,
+            out_shape_y: out_h,
+            out_shape_x: out_w,
+            has_bias: false,
+            groups: 1,
+            depth: None,
+            epilogue: MatmulEpilogue::None,
+        };
+        let result = launch_conv2d_nhwc::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+            &client,
+            input.as_arg(1),
+            weight.as_arg(1),
+            unused_bias.as_arg(1),
+            out.as_arg(1),
+            problem,
+        );
+
+        if is_unsupported(&result) {
+            println!("Skipped finite differences - not supported: {result:?}");
+            return;
+        }
+        result.unwrap();
+
+        let to_f64 = |value: &F| value.to_f64().unwrap();
+        let loss_difference = out
+            .to_vec(&client)
+            .iter()
+            .zip(grad_out_values.iter())
+            .map(|(out, grad)| to_f64(out) * to_f64(grad))
+            .sum::<f64>();
+        let directional_derivative = grad_input
+            .iter()
+            .zip(direction.iter())
+            .map(|(grad, &direction)| to_f64(grad) * direction as f64)
+            .sum::<f64>();
+
+        assert_eq!(
+            loss_difference, directional_derivative,
+            "finite differences along direction {seed} differ from the gradient"
+        );
+    }
+}
+
+/// An output with one row too few is rejected before anything is launched.
+pub fn test_conv2d_output_shape_mismatch<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let options = Conv2dOptions {
+        padding: (1, 1).into(),
+        ..Default::default()
+    };
+    let input = TensorHandle::<R, F>::zeros(&client, vec![1, 9, 9, 4]);
+    let weight = TensorHandle::<R, F>::zeros(&client, vec![4, 3, 3, 4]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![1, 8, 9, 4]);
+
+    let result = launch_conv2d::<R, (F, F, f32), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        None,
+        &out.as_ref(),
+        &options,
+    );
+
+    let Err(ConvLaunchError::ShapeMismatch {
+        tensor,
+        input,
+        weight,
+        expected,
+        actual,
+    }) = result
+    else {
+        panic!("Expected a shape mismatch, got {result:?}");
+    };
+    assert_eq!(tensor, "out");
+    assert_eq!((input, weight), (vec![1, 9, 9, 4], vec![4, 3, 3, 4]));
+    assert_eq!(expected, vec![1, 9, 9, 4]);
+    assert_eq!(actual, vec![1, 8, 9, 4]);
+}
+
+/// A weight without its batch of output channels is rejected instead of panicking.
+pub fn test_conv2d_invalid_rank<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let input = TensorHandle::<R, F>::zeros(&client, vec![1, 8, 8, 4]);
+    let weight = TensorHandle::<R, F>::zeros(&client, vec![3, 3, 4]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![1, 6, 6, 4]);
+
+    let result = launch_conv2d::<R, (F, F, f32), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        None,
+        &out.as_ref(),
+        &Default::default(),
+    );
+
+    let Err(ConvLaunchError::InvalidRank {
+        tensor,
+        shape,
+        rank,
+    }) = result
+    else {
+        panic!("Expected an invalid rank, got {result:?}");
+    };
+    assert_eq!((tensor, shape, rank), ("weight", vec![3, 3, 4], 4));
+}
+
+/// Without input channels, k is 0 and every output position holds the bias of its channel.
+pub fn test_conv2d_without_input_channels<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_empty_k::<R, F>(true, device);
+}
+
+/// Without input channels nor bias, the output is filled with zeros.
+pub fn test_conv2d_without_input_channels_or_bias<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_conv2d_empty_k::<R, F>(false, device);
+}
+
+fn test_conv2d_empty_k<R: Runtime, F: Float + CubeElement + Display>(
+    has_bias: bool,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let out_channels = 4;
+    let options = Conv2dOptions {
+        padding: (1, 1).into(),
+        ..Default::default()
+    };
+    let input = TensorHandle::<R, F>::empty(&client, vec![2, 5, 5, 0]);
+    let weight = TensorHandle::<R, F>::empty(&client, vec![out_channels, 3, 3, 0]);
+    let bias_data: Vec<F> = (1..=out_channels as i64).map(F::from_int).collect();
+    let bias = TensorHandle::<R, F>::from_data(&client, &bias_data, &[out_channels]);
+    // The output starts with values the launch has to overwrite.
+    let out_shape = [2, 5, 5, out_channels];
+    let num_elements = out_shape.iter().product();
+    let out =
+        TensorHandle::<R, F>::from_data(&client, &vec![F::from_int(-1); num_elements], &out_shape);
+
+    launch_conv2d::<R, (F, F, f32), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        has_bias.then(|| bias.as_ref()).as_ref(),
+        &out.as_ref(),
+        &options,
+    )
+    .unwrap();
+
+    let expected: Vec<F> = (0..num_elements)
+        .map(|index| match has_bias {
+            true => bias_data[index % out_channels],
+            false => F::from_int(0),
+        })
+        .collect();
+    assert_eq!(out.to_vec(&client), expected);
+}
+
+/// Outputs without batch or without output channel have nothing to compute.
+pub fn test_conv2d_empty_output<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let options = Conv2dOptions {
+        padding: (1, 1).into(),
+        ..Default::default()
+    };
+
+    for (batches, out_channels) in [(0, 4), (2, 0)] {
+        let input = TensorHandle::<R, F>::zeros(&client, vec![batches, 5, 5, 4]);
+        let weight = TensorHandle::<R, F>::zeros(&client, vec![out_channels, 3, 3, 4]);
+        let out = TensorHandle::<R, F>::empty(&client, vec![batches, 5, 5, out_channels]);
+
+        let result = launch_conv2d::<R, (F, F, f32), ImplicitCmmaConv, Large>(
+            &client,
+            &input.as_ref(),
+            &weight.as_ref(),
+            None,
+            &out.as_ref(),
+            &options,
+        );
+
+        assert!(
+            result.is_ok(),
+            "{batches} batches and {out_channels} output channels: {result:?}"
+        );
+    }
+}
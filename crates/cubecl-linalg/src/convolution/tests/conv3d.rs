@@ -0,0 +1,149 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement,
+    prelude::{Float, Runtime},
+};
+use half::f16;
+
+use crate::{
+    convolution::{
+        Conv3dOptions, algorithm::ImplicitCmmaConv, base::PaddingMode, conv3d_output_shape,
+        launch_conv3d_ndhwc, selection::Large,
+    },
+    tensor::TensorHandle,
+};
+
+use super::test_utils::{ReferenceConv, is_unsupported, small_integers, tensor_from_integers};
+
+pub fn test_conv3d_padding<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let options = Conv3dOptions {
+        padding: [1, 1, 1],
+        ..Default::default()
+    };
+
+    test_conv3d::<R, F>([3, 3, 3], options, device);
+}
+
+pub fn test_conv3d_strided_dilated<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = Conv3dOptions {
+        stride: [2, 1, 2],
+        padding: [0, 2, 1],
+        dilation: [1, 2, 1],
+        ..Default::default()
+    };
+
+    test_conv3d::<R, F>([2, 3, 3], options, device);
+}
+
+pub fn test_conv3d_groups_2<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let options = Conv3dOptions {
+        padding: [1, 1, 1],
+        groups: 2,
+        ..Default::default()
+    };
+
+    test_conv3d::<R, F>([3, 3, 3], options, device);
+}
+
+/// Replicate padding along the three spatial dimensions, the corners reading the closest voxel.
+pub fn test_conv3d_replicate_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = Conv3dOptions {
+        stride: [1, 2, 1],
+        padding: [2, 1, 3],
+        padding_mode: PaddingMode::Replicate,
+        ..Default::default()
+    };
+
+    test_conv3d::<R, F>([3, 3, 3], options, device);
+}
+
+pub fn test_conv3d_reflect_padding<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = Conv3dOptions {
+        padding: [2, 1, 2],
+        padding_mode: PaddingMode::Reflect,
+        dilation: [1, 2, 1],
+        ..Default::default()
+    };
+
+    test_conv3d::<R, F>([3, 3, 3], options, device);
+}
+
+/// Compare the convolution of a `[2, 5, 6, 7, 4]` NDHWC input to 8 output channels with a bias,
+/// using a `kernel_size` kernel, with a direct computation on the CPU.
+fn test_conv3d<R: Runtime, F: Float + CubeElement + Display>(
+    kernel_size: [usize; 3],
+    options: Conv3dOptions,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let [batches, in_d, in_h, in_w, in_channels] = [2, 5, 6, 7, 4];
+    let out_channels = 8;
+    let [kernel_d, kernel_h, kernel_w] = kernel_size;
+    let Conv3dOptions {
+        stride,
+        padding,
+        padding_mode,
+        dilation,
+        groups,
+        ..
+    } = options;
+    let group_channels = in_channels / groups;
+
+    let [_, _, out_d, out_h, out_w] = conv3d_output_shape(
+        [batches, in_channels, in_d, in_h, in_w],
+        [out_channels, group_channels, kernel_d, kernel_h, kernel_w],
+        &options,
+    )
+    .unwrap();
+
+    let input_shape = [batches, in_d, in_h, in_w, in_channels];
+    let input = small_integers(input_shape.iter().product(), 1, 0);
+    let weight_shape = [out_channels, kernel_d, kernel_h, kernel_w, group_channels];
+    let weight = small_integers(weight_shape.iter().product(), 3, 1);
+    let bias = small_integers(out_channels, 1, 2);
+
+    let reference = ReferenceConv {
+        kernel_size: kernel_size.to_vec(),
+        stride: stride.to_vec(),
+        padding: padding.to_vec(),
+        padding_end: padding.to_vec(),
+        dilation: dilation.to_vec(),
+        padding_mode,
+        groups,
+    };
+    let expected = reference
+        .run(&input, &input_shape, &weight, &bias)
+        .into_iter()
+        .map(F::from_int)
+        .collect::<Vec<_>>();
+
+    let input = tensor_from_integers::<R, F>(&client, &input, &input_shape);
+    let weight = tensor_from_integers::<R, F>(&client, &weight, &weight_shape);
+    let bias = tensor_from_integers::<R, F>(&client, &bias, &[out_channels]);
+    let out =
+        TensorHandle::<R, F>::empty(&client, vec![batches, out_d, out_h, out_w, out_channels]);
+
+    let result = launch_conv3d_ndhwc::<R, (F, f16, f32), ImplicitCmmaConv, Large>(
+        &client,
+        &input.as_ref(),
+        &weight.as_ref(),
+        Some(&bias.as_ref()),
+        &out.as_ref(),
+        &options,
+    );
+
+    if is_unsupported(&result) {
+        println!("Skipped - not supported: {result:?}");
+        return;
+    }
+    result.unwrap();
+
+    assert_eq!(out.to_vec(&client), expected, "convolution outputs differ");
+}
@@ -0,0 +1,8 @@
+pub mod conv1d;
+pub mod conv2d;
+pub mod conv3d;
+pub mod pool;
+pub mod test_utils;
+pub mod unfold;
+
+mod test_macros;
@@ -0,0 +1,183 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement,
+    prelude::{Float, Runtime},
+};
+
+use crate::{
+    convolution::{self, ConvLaunchError, Pool2dOptions},
+    matmul::tests::test_utils::assert_equals_approx,
+    tensor::TensorHandle,
+};
+
+const INPUT_SHAPE: [usize; 4] = [2, 9, 11, 8];
+
+pub fn test_max_pool2d<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let options = Pool2dOptions {
+        stride: (2, 2),
+        padding: (1, 1),
+        ..Pool2dOptions::new((3, 3))
+    };
+    let data = input_data();
+    let input = TensorHandle::<R, F>::from_data(&client, &to_elems(&data), &INPUT_SHAPE);
+
+    let (output, indices) =
+        convolution::max_pool2d_with_indices::<R, F>(&client, &input.as_ref(), &options).unwrap();
+    let (expected_output, expected_indices) = max_pool2d_reference(&data, &options);
+
+    assert_eq!(
+        output.to_vec(&client),
+        to_elems(&expected_output),
+        "pooled data are not equal."
+    );
+    assert_eq!(
+        indices.to_vec(&client),
+        expected_indices,
+        "indices of the maxima are not equal."
+    );
+
+    let output = convolution::max_pool2d::<R, F>(&client, &input.as_ref(), &options).unwrap();
+    assert_eq!(
+        output.to_vec(&client),
+        to_elems(&expected_output),
+        "pooled data without indices are not equal."
+    );
+}
+
+pub fn test_avg_pool2d_count_include_pad<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = Pool2dOptions {
+        stride: (2, 1),
+        padding: (1, 1),
+        ..Pool2dOptions::new((3, 2))
+    };
+
+    test_avg_pool2d::<R, F>(options, device);
+}
+
+pub fn test_avg_pool2d_exclude_pad<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let options = Pool2dOptions {
+        stride: (2, 1),
+        padding: (1, 1),
+        count_include_pad: false,
+        ..Pool2dOptions::new((3, 2))
+    };
+
+    test_avg_pool2d::<R, F>(options, device);
+}
+
+pub fn test_pool2d_padding_too_large<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input = TensorHandle::<R, F>::zeros(&client, vec![1, 4, 4, 2]);
+    let options = Pool2dOptions {
+        padding: (2, 0),
+        ..Pool2dOptions::new((3, 3))
+    };
+
+    let result = convolution::avg_pool2d::<R, F>(&client, &input.as_ref(), &options);
+
+    assert!(matches!(
+        result,
+        Err(ConvLaunchError::InvalidOptions { .. })
+    ));
+}
+
+fn test_avg_pool2d<R: Runtime, F: Float + CubeElement + Display>(
+    options: Pool2dOptions,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let data = input_data();
+    let input = TensorHandle::<R, F>::from_data(&client, &to_elems(&data), &INPUT_SHAPE);
+
+    let output = convolution::avg_pool2d::<R, F>(&client, &input.as_ref(), &options).unwrap();
+
+    let mut expected = Vec::new();
+    for_each_window(&options, |b, c, window| {
+        let sum = window
+            .iter()
+            .map(|&(y, x)| data[index(b, y, x, c)])
+            .sum::<i64>();
+        let count = match options.count_include_pad {
+            true => options.kernel_size.0 * options.kernel_size.1,
+            false => window.len(),
+        };
+        expected.push(F::new(sum as f32 / count as f32));
+    });
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, output.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+/// The maxima of each window and the `y * width + x` position of the first one.
+fn max_pool2d_reference(data: &[i64], options: &Pool2dOptions) -> (Vec<i64>, Vec<u32>) {
+    let mut maxima = Vec::new();
+    let mut indices = Vec::new();
+    for_each_window(options, |b, c, window| {
+        let (mut max, mut position) = (i64::MIN, 0);
+        for &(y, x) in window {
+            if data[index(b, y, x, c)] > max {
+                max = data[index(b, y, x, c)];
+                position = y * INPUT_SHAPE[2] + x;
+            }
+        }
+        maxima.push(max);
+        indices.push(position as u32);
+    });
+
+    (maxima, indices)
+}
+
+/// Call `f` with the valid `(y, x)` input positions of each window, in the NHWC order of the
+/// output.
+fn for_each_window(options: &Pool2dOptions, mut f: impl FnMut(usize, usize, &[(usize, usize)])) {
+    let [batches, in_h, in_w, channels] = INPUT_SHAPE;
+    let [_, _, out_h, out_w] =
+        convolution::pool2d_output_shape([batches, channels, in_h, in_w], options).unwrap();
+    let (kernel_h, kernel_w) = options.kernel_size;
+
+    for b in 0..batches {
+        for oy in 0..out_h {
+            for ox in 0..out_w {
+                let mut window = Vec::new();
+                for ky in 0..kernel_h {
+                    for kx in 0..kernel_w {
+                        let y = (oy * options.stride.0 + ky) as i64 - options.padding.0 as i64;
+                        let x = (ox * options.stride.1 + kx) as i64 - options.padding.1 as i64;
+                        if y >= 0 && y < in_h as i64 && x >= 0 && x < in_w as i64 {
+                            window.push((y as usize, x as usize));
+                        }
+                    }
+                }
+
+                for c in 0..channels {
+                    f(b, c, &window);
+                }
+            }
+        }
+    }
+}
+
+/// Small negative and positive integers, exact in f16, with ties inside the windows.
+fn input_data() -> Vec<i64> {
+    (0..INPUT_SHAPE.iter().product::<usize>())
+        .map(|i| (i * 37 % 23) as i64 - 15)
+        .collect()
+}
+
+fn index(b: usize, y: usize, x: usize, c: usize) -> usize {
+    let [_, in_h, in_w, channels] = INPUT_SHAPE;
+    ((b * in_h + y) * in_w + x) * channels + c
+}
+
+fn to_elems<F: Float>(data: &[i64]) -> Vec<F> {
+    data.iter().map(|&v| F::from_int(v)).collect()
+}
@@ -0,0 +1,57 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_conv1d {
+    () => {
+        mod conv1d {
+            $crate::testgen_conv1d!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_conv1d_kernel_3() {
+                cubecl_linalg::convolution::tests::conv1d::test_conv1d_kernel_3::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv1d_kernel_9() {
+                cubecl_linalg::convolution::tests::conv1d::test_conv1d_kernel_9::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv1d_dilated_groups_2() {
+                cubecl_linalg::convolution::tests::conv1d::test_conv1d_dilated_groups_2::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv1d_depthwise() {
+                cubecl_linalg::convolution::tests::conv1d::test_conv1d_depthwise::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv1d_reflect_padding() {
+                cubecl_linalg::convolution::tests::conv1d::test_conv1d_reflect_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv1d_replicate_padding() {
+                cubecl_linalg::convolution::tests::conv1d::test_conv1d_replicate_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod conv1d {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_conv1d!($float);
+                })*
+            }
+        }
+    };
+}
@@ -0,0 +1,282 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_conv2d {
+    () => {
+        mod conv2d {
+            $crate::testgen_conv2d!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_conv2d_groups_2() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_groups_2::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_groups_4() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_groups_4::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_depthwise() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_depthwise::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_depthwise_conv2d_3x3_stride_2() {
+                cubecl_linalg::convolution::tests::conv2d::test_depthwise_conv2d_3x3_stride_2::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_depthwise_conv2d_5x5_stride_2() {
+                cubecl_linalg::convolution::tests::conv2d::test_depthwise_conv2d_5x5_stride_2::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_dilation_2() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_dilation_2::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_dilation_2_3() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_dilation_2_3::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_depthwise_conv2d_dilation_2_3() {
+                cubecl_linalg::convolution::tests::conv2d::test_depthwise_conv2d_dilation_2_3::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv_transpose2d_stride_2() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv_transpose2d_stride_2::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv_transpose2d_dilation() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv_transpose2d_dilation::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv_transpose2d_groups_unimplemented() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv_transpose2d_groups_unimplemented::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_backward_weight() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_backward_weight::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_backward_weight_single_batch() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_backward_weight_single_batch::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_backward_weight_groups_unimplemented() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_backward_weight_groups_unimplemented::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_backward_data() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_backward_data::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_backward_data_dilated() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_backward_data_dilated::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_backward_data_fuzz() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_backward_data_fuzz::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_backward_data_groups_unimplemented() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_backward_data_groups_unimplemented::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_memory_layouts() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_memory_layouts::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_memory_layouts_groups_2() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_memory_layouts_groups_2::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_bias_channels_tail() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_bias_channels_tail::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_bias_odd_channels() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_bias_odd_channels::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_without_bias() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_without_bias::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_relu_epilogue() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_relu_epilogue::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_relu6_epilogue() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_relu6_epilogue::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_silu_epilogue() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_silu_epilogue::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_depthwise_conv2d_relu6_epilogue() {
+                cubecl_linalg::convolution::tests::conv2d::test_depthwise_conv2d_relu6_epilogue::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_im2col_matmul_matches_conv2d() {
+                cubecl_linalg::convolution::tests::conv2d::test_im2col_matmul_matches_conv2d::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_winograd() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_winograd::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_winograd_no_padding() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_winograd_no_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_winograd_unsupported() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_winograd_unsupported::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_im2col_gemm() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_im2col_gemm::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_autotune() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_autotune::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_autotune_depthwise() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_autotune_depthwise::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_trailing_padding() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_trailing_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_depthwise_conv2d_asymmetric_padding() {
+                cubecl_linalg::convolution::tests::conv2d::test_depthwise_conv2d_asymmetric_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_reflect_padding() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_reflect_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_replicate_padding() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_replicate_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_depthwise_conv2d_reflect_padding() {
+                cubecl_linalg::convolution::tests::conv2d::test_depthwise_conv2d_reflect_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_reflect_padding_too_large() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_reflect_padding_too_large::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_default_accumulates_in_f32() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_default_accumulates_in_f32::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_mixed_accuracy_large_k() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_mixed_accuracy_large_k::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_output_shape_mismatch() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_output_shape_mismatch::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_invalid_rank() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_invalid_rank::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_without_input_channels() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_without_input_channels::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_without_input_channels_or_bias() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_without_input_channels_or_bias::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_empty_output() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_empty_output::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_cube_count_above_device_limit() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_cube_count_above_device_limit::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_depthwise_conv2d_cube_count_above_device_limit() {
+                cubecl_linalg::convolution::tests::conv2d::test_depthwise_conv2d_cube_count_above_device_limit::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_winograd_trailing_padding() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_winograd_trailing_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv2d_groups_line_size_too_large() {
+                cubecl_linalg::convolution::tests::conv2d::test_conv2d_groups_line_size_too_large::<TestRuntime, FloatT>(&Default::default());
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod conv2d {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_conv2d!($float);
+                })*
+            }
+        }
+    };
+}
@@ -0,0 +1,52 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_conv3d {
+    () => {
+        mod conv3d {
+            $crate::testgen_conv3d!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_conv3d_padding() {
+                cubecl_linalg::convolution::tests::conv3d::test_conv3d_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv3d_strided_dilated() {
+                cubecl_linalg::convolution::tests::conv3d::test_conv3d_strided_dilated::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv3d_groups_2() {
+                cubecl_linalg::convolution::tests::conv3d::test_conv3d_groups_2::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv3d_replicate_padding() {
+                cubecl_linalg::convolution::tests::conv3d::test_conv3d_replicate_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_conv3d_reflect_padding() {
+                cubecl_linalg::convolution::tests::conv3d::test_conv3d_reflect_padding::<TestRuntime, FloatT>(&Default::default());
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod conv3d {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_conv3d!($float);
+                })*
+            }
+        }
+    };
+}
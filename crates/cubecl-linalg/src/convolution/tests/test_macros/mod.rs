@@ -0,0 +1,5 @@
+mod conv1d;
+mod conv2d;
+mod conv3d;
+mod pool;
+mod unfold;
@@ -0,0 +1,47 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_conv_pool {
+    () => {
+        mod conv_pool {
+            $crate::testgen_conv_pool!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_max_pool2d() {
+                cubecl_linalg::convolution::tests::pool::test_max_pool2d::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_avg_pool2d_count_include_pad() {
+                cubecl_linalg::convolution::tests::pool::test_avg_pool2d_count_include_pad::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_avg_pool2d_exclude_pad() {
+                cubecl_linalg::convolution::tests::pool::test_avg_pool2d_exclude_pad::<TestRuntime, FloatT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_pool2d_padding_too_large() {
+                cubecl_linalg::convolution::tests::pool::test_pool2d_padding_too_large::<TestRuntime, FloatT>(&Default::default());
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod conv_pool {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_conv_pool!($float);
+                })*
+            }
+        }
+    };
+}
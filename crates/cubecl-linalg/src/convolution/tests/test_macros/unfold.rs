@@ -0,0 +1,57 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_conv_unfold {
+    () => {
+        mod conv_unfold {
+            $crate::testgen_conv_unfold!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_unfold() {
+                cubecl_linalg::convolution::tests::unfold::test_unfold::<TestRuntime, NumericT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_unfold_kernel_too_large() {
+                cubecl_linalg::convolution::tests::unfold::test_unfold_kernel_too_large::<TestRuntime, NumericT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_unfold_without_channels() {
+                cubecl_linalg::convolution::tests::unfold::test_unfold_without_channels::<TestRuntime, NumericT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_im2col() {
+                cubecl_linalg::convolution::tests::unfold::test_im2col::<TestRuntime, NumericT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_col2im_round_trip() {
+                cubecl_linalg::convolution::tests::unfold::test_col2im_round_trip::<TestRuntime, NumericT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_col2im_columns_shape_mismatch() {
+                cubecl_linalg::convolution::tests::unfold::test_col2im_columns_shape_mismatch::<TestRuntime, NumericT>(&Default::default());
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod conv_unfold {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_conv_unfold!($numeric);
+                })*
+            }
+        }
+    };
+}
@@ -0,0 +1,155 @@
+use std::ops::{AddAssign, Mul};
+
+use cubecl_core::{
+    CubeElement,
+    prelude::{ComputeClient, Numeric, Runtime},
+};
+
+use crate::{
+    convolution::{ConvLaunchError, base::PaddingMode},
+    matmul::kernels::MatmulLaunchError,
+    tensor::TensorHandle,
+};
+
+/// `len` small integers in `[-3, 3]`, the `i`th one depending on `i * scale + offset`, so that
+/// every product and sum of the tests is exact in f16 and in 8-bit floats.
+pub fn small_integers(len: usize, scale: usize, offset: usize) -> Vec<i64> {
+    (0..len)
+        .map(|i| ((i * scale + offset) % 7) as i64 - 3)
+        .collect()
+}
+
+/// A contiguous tensor of `shape` holding the row-major `data` converted to `E`.
+pub fn tensor_from_integers<R: Runtime, E: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    data: &[i64],
+    shape: &[usize],
+) -> TensorHandle<R, E> {
+    let data = data.iter().map(|&v| E::from_int(v)).collect::<Vec<_>>();
+    TensorHandle::<R, E>::from_data(client, &data, shape)
+}
+
+/// A grouped convolution of a channels-last input, computed directly on the CPU.
+///
+/// Every field but the groups and the padding mode has a value per spatial dimension, in the
+/// order of the dimensions of the input.
+#[derive(Clone, Debug)]
+pub struct ReferenceConv {
+    pub kernel_size: Vec<usize>,
+    pub stride: Vec<usize>,
+    /// Padding before the input
+    pub padding: Vec<usize>,
+    /// Padding after the input, which only changes the output shape
+    pub padding_end: Vec<usize>,
+    pub dilation: Vec<usize>,
+    pub padding_mode: PaddingMode,
+    pub groups: usize,
+}
+
+impl ReferenceConv {
+    /// The `[batches, spatial.., out_channels]` output of the convolution of an input of
+    /// `input_shape`, `[batches, spatial.., in_channels]`, with an `[out_channels, kernel..,
+    /// group_channels]` weight and a bias of `out_channels` values.
+    pub fn run<T: Copy + AddAssign + Mul<Output = T>>(
+        &self,
+        input: &[T],
+        input_shape: &[usize],
+        weight: &[T],
+        bias: &[T],
+    ) -> Vec<T> {
+        let rank = input_shape.len() - 2;
+        let batches = input_shape[0];
+        let in_size = &input_shape[1..rank + 1];
+        let in_channels = input_shape[rank + 1];
+        let out_channels = bias.len();
+        let group_channels = in_channels / self.groups;
+        let group_n = out_channels / self.groups;
+
+        let out_size = (0..rank)
+            .map(|dim| {
+                let padded = in_size[dim] + self.padding[dim] + self.padding_end[dim];
+                let extent = self.dilation[dim] * (self.kernel_size[dim] - 1) + 1;
+                (padded - extent) / self.stride[dim] + 1
+            })
+            .collect::<Vec<_>>();
+        let num_positions = out_size.iter().product::<usize>();
+        let num_taps = self.kernel_size.iter().product::<usize>();
+
+        // The coordinates of `index` in a row-major grid of `shape`.
+        let coordinates = |mut index: usize, shape: &[usize]| {
+            let mut coordinates = vec![0; shape.len()];
+            for dim in (0..shape.len()).rev() {
+                coordinates[dim] = index % shape[dim];
+                index /= shape[dim];
+            }
+            coordinates
+        };
+
+        let mut output = Vec::with_capacity(batches * num_positions * out_channels);
+        for b in 0..batches {
+            for position in 0..num_positions {
+                let out_pos = coordinates(position, &out_size);
+
+                for o in 0..out_channels {
+                    let group = o / group_n;
+                    let mut sum = bias[o];
+
+                    for tap in 0..num_taps {
+                        let kernel_pos = coordinates(tap, &self.kernel_size);
+                        let in_pos = (0..rank)
+                            .map(|dim| {
+                                let pos = out_pos[dim] * self.stride[dim]
+                                    + kernel_pos[dim] * self.dilation[dim];
+                                let pos = pos as i64 - self.padding[dim] as i64;
+                                padded_position(pos, in_size[dim], self.padding_mode)
+                            })
+                            .collect::<Option<Vec<_>>>();
+                        let Some(in_pos) = in_pos else {
+                            continue;
+                        };
+
+                        let pixel =
+                            (0..rank).fold(b, |pixel, dim| pixel * in_size[dim] + in_pos[dim]);
+                        for c in 0..group_channels {
+                            let channel = group * group_channels + c;
+                            let weight_index = (o * num_taps + tap) * group_channels + c;
+                            sum += input[pixel * in_channels + channel] * weight[weight_index];
+                        }
+                    }
+
+                    output.push(sum);
+                }
+            }
+        }
+
+        output
+    }
+}
+
+/// The position of the input read at `position` of the input padded with `mode`, along a spatial
+/// dimension of `size`, or `None` when a zero is read.
+pub fn padded_position(position: i64, size: usize, mode: PaddingMode) -> Option<usize> {
+    let size = size as i64;
+    let position = match mode {
+        PaddingMode::Zero => position,
+        PaddingMode::Reflect => match position.abs() {
+            position if position >= size => 2 * (size - 1) - position,
+            position => position,
+        },
+        PaddingMode::Replicate => position.clamp(0, size - 1),
+    };
+
+    (0..size).contains(&position).then_some(position as usize)
+}
+
+/// Whether the convolution failed because the device can't run its matmul, seen through the
+/// context of the error, in which case the test is skipped.
+pub fn is_unsupported<T>(result: &Result<T, ConvLaunchError>) -> bool {
+    match result {
+        Err(ConvLaunchError::Matmul(error)) => matches!(
+            error.root(),
+            MatmulLaunchError::Unavailable(_) | MatmulLaunchError::InvalidConfig(_)
+        ),
+        _ => false,
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement,
+    prelude::{Numeric, Runtime},
+};
+
+use crate::{
+    convolution::{self, Conv2dOptions, ConvLaunchError},
+    tensor::TensorHandle,
+};
+
+use super::test_utils::tensor_from_integers;
+
+pub fn test_unfold<R: Runtime, C: Numeric + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let [batches, in_h, in_w, channels] = [2, 5, 6, 3];
+    let (kernel_h, kernel_w) = (3, 2);
+    let (stride, padding, dilation) = ((2, 1), (1, 1), (1, 2));
+
+    let data = (0..batches * in_h * in_w * channels)
+        .map(|i| C::from_int(i as i64 % 97 + 1))
+        .collect::<Vec<_>>();
+    let input = TensorHandle::<R, C>::from_data(&client, &data, &[batches, in_h, in_w, channels]);
+
+    let output = convolution::unfold::<R, C>(
+        &client,
+        &input.as_ref(),
+        (kernel_h, kernel_w),
+        stride,
+        padding.into(),
+        dilation,
+    )
+    .unwrap();
+
+    let (out_h, out_w) = (3, 6);
+    let shape_k = channels * kernel_h * kernel_w;
+    assert_eq!(output.shape, vec![batches, shape_k, out_h * out_w]);
+
+    let mut expected = vec![C::from_int(0); batches * shape_k * out_h * out_w];
+    for b in 0..batches {
+        for ky in 0..kernel_h {
+            for kx in 0..kernel_w {
+                for c in 0..channels {
+                    let k = (ky * kernel_w + kx) * channels + c;
+                    for oy in 0..out_h {
+                        for ox in 0..out_w {
+                            let y = (oy * stride.0 + ky * dilation.0) as i64 - padding.0 as i64;
+                            let x = (ox * stride.1 + kx * dilation.1) as i64 - padding.1 as i64;
+                            if y < 0 || y >= in_h as i64 || x < 0 || x >= in_w as i64 {
+                                continue;
+                            }
+
+                            let (y, x) = (y as usize, x as usize);
+                            let l = oy * out_w + ox;
+                            expected[(b * shape_k + k) * out_h * out_w + l] =
+                                data[((b * in_h + y) * in_w + x) * channels + c];
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    assert_eq!(
+        output.to_vec(&client),
+        expected,
+        "unfolded data are not equal."
+    );
+}
+
+pub fn test_unfold_kernel_too_large<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input = TensorHandle::<R, C>::zeros(&client, vec![1, 4, 4, 2]);
+
+    let result = convolution::unfold::<R, C>(
+        &client,
+        &input.as_ref(),
+        (5, 5),
+        (1, 1),
+        (0, 0).into(),
+        (1, 1),
+    );
+
+    assert!(matches!(result, Err(ConvLaunchError::InvalidShape { .. })));
+}
+
+pub fn test_unfold_without_channels<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input = TensorHandle::<R, C>::empty(&client, vec![2, 4, 4, 0]);
+
+    let output = convolution::unfold::<R, C>(
+        &client,
+        &input.as_ref(),
+        (3, 3),
+        (1, 1),
+        (1, 1).into(),
+        (1, 1),
+    )
+    .unwrap();
+
+    assert_eq!(output.shape, vec![2, 0, 16]);
+}
+
+pub fn test_im2col<R: Runtime, C: Numeric + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let input_shape = [2, 5, 6, 4];
+    let kernel_size = (3, 2);
+    let options = Conv2dOptions {
+        stride: (2, 1),
+        padding: (1, 1).into(),
+        dilation: (1, 2),
+        ..Default::default()
+    };
+
+    let data = (0..input_shape.iter().product::<usize>())
+        .map(|i| i as i64 % 97 + 1)
+        .collect::<Vec<_>>();
+    let input = tensor_from_integers::<R, C>(&client, &data, &input_shape);
+
+    let columns =
+        convolution::im2col::<R, C>(&client, &input.as_ref(), kernel_size, &options).unwrap();
+
+    let (out_h, out_w) = (3, 6);
+    let shape_k = 4 * kernel_size.0 * kernel_size.1;
+    assert_eq!(columns.shape, vec![2 * out_h * out_w, shape_k]);
+
+    let expected = im2col_indices(input_shape, kernel_size, &options)
+        .into_iter()
+        .map(|index| C::from_int(index.map_or(0, |index| data[index])))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        columns.to_vec(&client),
+        expected,
+        "im2col data are not equal."
+    );
+}
+
+/// Folding the columns back sums each input element once per window reading it.
+pub fn test_col2im_round_trip<R: Runtime, C: Numeric + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let input_shape = [2, 7, 6, 4];
+    let kernel_size = (3, 3);
+    let options = Conv2dOptions {
+        stride: (2, 1),
+        padding: (1, 0).into(),
+        ..Default::default()
+    };
+
+    let data = (0..input_shape.iter().product::<usize>())
+        .map(|i| i as i64 % 5 + 1)
+        .collect::<Vec<_>>();
+    let input = tensor_from_integers::<R, C>(&client, &data, &input_shape);
+
+    let columns =
+        convolution::im2col::<R, C>(&client, &input.as_ref(), kernel_size, &options).unwrap();
+    let output = convolution::col2im::<R, C>(
+        &client,
+        &columns.as_ref(),
+        input_shape,
+        kernel_size,
+        &options,
+    )
+    .unwrap();
+
+    let mut windows = vec![0; data.len()];
+    for index in im2col_indices(input_shape, kernel_size, &options)
+        .into_iter()
+        .flatten()
+    {
+        windows[index] += 1;
+    }
+    let expected = data
+        .iter()
+        .zip(windows)
+        .map(|(&value, windows)| C::from_int(value * windows))
+        .collect::<Vec<_>>();
+
+    assert_eq!(output.shape, input_shape.to_vec());
+    assert_eq!(
+        output.to_vec(&client),
+        expected,
+        "col2im data are not equal."
+    );
+}
+
+pub fn test_col2im_columns_shape_mismatch<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let input_shape = [1, 4, 4, 2];
+    let kernel_size = (3, 3);
+    let options = Conv2dOptions::default();
+
+    // The columns of a 2x2 output are `[4, 18]`.
+    let columns = TensorHandle::<R, C>::zeros(&client, vec![4, 16]);
+    let result = convolution::col2im::<R, C>(
+        &client,
+        &columns.as_ref(),
+        input_shape,
+        kernel_size,
+        &options,
+    );
+
+    let Err(ConvLaunchError::ShapeMismatch {
+        tensor,
+        expected,
+        actual,
+        ..
+    }) = result
+    else {
+        panic!("Expected a shape mismatch, got {:?}", result.map(|_| ()));
+    };
+    assert_eq!(tensor, "columns");
+    assert_eq!(expected, vec![4, 18]);
+    assert_eq!(actual, vec![4, 16]);
+}
+
+/// The index in the NHWC input of each element of its row-major columns, `None` in the padding.
+fn im2col_indices(
+    input_shape: [usize; 4],
+    kernel_size: (usize, usize),
+    options: &Conv2dOptions,
+) -> Vec<Option<usize>> {
+    let [batches, in_h, in_w, channels] = input_shape;
+    let (kernel_h, kernel_w) = kernel_size;
+    let Conv2dOptions {
+        stride,
+        padding,
+        dilation,
+        ..
+    } = *options;
+    let [_, _, out_h, out_w] = convolution::conv2d_output_shape(
+        [batches, channels, in_h, in_w],
+        [1, channels, kernel_h, kernel_w],
+        stride,
+        padding,
+        dilation,
+    )
+    .unwrap();
+
+    let mut indices = Vec::with_capacity(batches * out_h * out_w * kernel_h * kernel_w * channels);
+    for b in 0..batches {
+        for oy in 0..out_h {
+            for ox in 0..out_w {
+                for ky in 0..kernel_h {
+                    for kx in 0..kernel_w {
+                        let y = (oy * stride.0 + ky * dilation.0) as i64 - padding.begin.0 as i64;
+                        let x = (ox * stride.1 + kx * dilation.1) as i64 - padding.begin.1 as i64;
+                        let in_bounds = y >= 0 && y < in_h as i64 && x >= 0 && x < in_w as i64;
+                        let pixel = in_bounds.then(|| (b * in_h + y as usize) * in_w + x as usize);
+
+                        for c in 0..channels {
+                            indices.push(pixel.map(|pixel| pixel * channels + c));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    indices
+}
@@ -0,0 +1,289 @@
+use core::marker::PhantomData;
+
+use cubecl_core::{
+    CubeTuneId,
+    ir::{Elem, FloatKind},
+    prelude::*,
+};
+use cubecl_runtime::tune::{AutotuneError, LocalTuner, Tunable, TunableSet};
+
+use crate::{
+    matmul::{Strategy, kernels::accelerated_available},
+    tensor::TensorHandle,
+};
+
+use super::{
+    Conv2dOptions, ConvLaunchError, algorithm::ImplicitCmmaConv, check_output_shape,
+    conv2d::rank_4, conv2d_depthwise, conv2d_grouped_output_shape, conv2d_im2col, conv2d_winograd,
+    launch_conv2d, selection::Large, tune_key::Conv2dAutotuneKey,
+};
+
+type Conv2dInputs<R, F> = (
+    TensorHandle<R, F>,
+    TensorHandle<R, F>,
+    Option<TensorHandle<R, F>>,
+    TensorHandle<R, F>,
+);
+
+/// Algorithm computing a 2D convolution, as selected by [conv2d_autotune].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Conv2dAlgorithm {
+    /// The implicit GEMM of [launch_conv2d], using the tensor cores of the device.
+    ImplicitGemm,
+    /// The depthwise kernel, for a single input and output channel per group, see
+    /// [conv2d_depthwise].
+    Depthwise,
+    /// The [im2col] columns multiplied with the weight, see [conv2d_im2col].
+    ///
+    /// [im2col]: super::im2col
+    Im2col,
+    /// The Winograd F(2x2, 3x3) algorithm, see [conv2d_winograd].
+    Winograd,
+}
+
+impl Conv2dAlgorithm {
+    /// Name of the algorithm, as logged by autotune.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Conv2dAlgorithm::ImplicitGemm => "implicit_gemm",
+            Conv2dAlgorithm::Depthwise => "depthwise",
+            Conv2dAlgorithm::Im2col => "im2col",
+            Conv2dAlgorithm::Winograd => "winograd",
+        }
+    }
+}
+
+/// Perform a 2D convolution with the algorithm selected by autotune, returning the algorithm
+/// that computed it.
+///
+/// The tensors and options are those of [launch_conv2d], [winograd](Conv2dOptions::winograd)
+/// being ignored. Only the algorithms which can compute the convolution are candidates:
+///
+/// * [Im2col](Conv2dAlgorithm::Im2col) and [Winograd](Conv2dAlgorithm::Winograd) need a single
+///   group, and Winograd a 3x3 kernel with a stride and dilation of 1.
+/// * [Depthwise](Conv2dAlgorithm::Depthwise) needs a single input and output channel per group,
+///   and replaces [ImplicitGemm](Conv2dAlgorithm::ImplicitGemm) then.
+/// * The implicit GEMM with a single group is left out when the device has no tensor cores for
+///   the element type.
+///
+/// With a single candidate, it is launched directly and its errors are returned. Otherwise, the
+/// first call for a given device and [key](Conv2dAutotuneKey) benchmarks the candidates on the
+/// given tensors, and later calls with the same key launch the fastest one after a single lookup.
+/// A candidate returning a [ConvLaunchError] while benchmarking is never selected. The implicit
+/// GEMM stages in `F`, it and the depthwise kernel accumulate in f32, and the other algorithms use
+/// the [Auto](Strategy::Auto) matmul.
+///
+/// Returns [ConvLaunchError::InvalidRank] before any launch when the input or the weight doesn't
+/// have rank 4, and [ConvLaunchError::ShapeMismatch] when the output doesn't have the shape of
+/// the convolution.
+pub fn conv2d_autotune<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    device: &R::Device,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+) -> Result<Conv2dAlgorithm, ConvLaunchError> {
+    static TUNER: LocalTuner<Conv2dAutotuneKey, CubeTuneId> = LocalTuner::new(module_path!());
+
+    let [batches, in_h, in_w, channels] = rank_4(input.shape, "input")?;
+    let [out_channels, kernel_h, kernel_w, group_channels] = rank_4(weight.shape, "weight")?;
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, channels, in_h, in_w],
+        [out_channels, group_channels, kernel_h, kernel_w],
+        options.stride,
+        options.padding,
+        options.dilation,
+        options.groups,
+    )?;
+    check_output_shape(
+        "out",
+        out.shape,
+        &[batches, out_h, out_w, out_channels],
+        input.shape,
+        weight.shape,
+    )?;
+
+    let options = Conv2dOptions {
+        winograd: false,
+        ..*options
+    };
+    let key = Conv2dAutotuneKey::generate(
+        input.shape,
+        weight.shape,
+        input.strides,
+        weight.strides,
+        out.strides,
+        bias.is_some(),
+        &options,
+        F::as_elem_native_unchecked(),
+    );
+    let algorithms = candidate_algorithms::<R, F>(client, &key);
+
+    if let [algorithm] = algorithms[..] {
+        launch_algorithm::<R, F>(client, algorithm, input, weight, bias, out, &options)?;
+        return Ok(algorithm);
+    }
+
+    let owned = |tensor: &TensorHandleRef<'_, R>| {
+        TensorHandle::<R, F>::new(
+            tensor.shape.to_vec(),
+            tensor.strides.to_vec(),
+            tensor.handle.clone(),
+        )
+    };
+    let inputs = (owned(input), owned(weight), bias.map(owned), owned(out));
+
+    // The key has every dimension of the problem, so the candidates are benchmarked on the given
+    // tensors, the output being written again by the selected algorithm.
+    let mut tunables = TunableSet::new(
+        move |input: &TensorHandle<R, F>,
+              weight: &TensorHandle<R, F>,
+              bias: &Option<TensorHandle<R, F>>,
+              out: &TensorHandle<R, F>| {
+            Conv2dAutotuneKey::generate(
+                &input.shape,
+                &weight.shape,
+                &input.strides,
+                &weight.strides,
+                &out.strides,
+                bias.is_some(),
+                &options,
+                F::as_elem_native_unchecked(),
+            )
+        },
+        |_key: &Conv2dAutotuneKey,
+         input: &TensorHandle<R, F>,
+         weight: &TensorHandle<R, F>,
+         bias: &Option<TensorHandle<R, F>>,
+         out: &TensorHandle<R, F>| {
+            (input.clone(), weight.clone(), bias.clone(), out.clone())
+        },
+    );
+    for algorithm in algorithms {
+        tunables = tunables.with_tunable(Conv2dTunable::<R, F>::new(
+            client.clone(),
+            algorithm,
+            options,
+        ));
+    }
+
+    TUNER
+        .execute(
+            &CubeTuneId::new::<R>(client, device),
+            client,
+            &tunables,
+            inputs,
+        )
+        .map_err(ConvLaunchError::Autotune)
+}
+
+/// Algorithms that can compute convolutions of `key` on the device, [Im2col] first when it
+/// applies since it runs with any matmul.
+///
+/// Only the device and the key decide which algorithms are kept, so that every problem of the
+/// key benchmarks the same set and the selected index stays valid.
+///
+/// [Im2col]: Conv2dAlgorithm::Im2col
+fn candidate_algorithms<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    key: &Conv2dAutotuneKey,
+) -> Vec<Conv2dAlgorithm> {
+    let tensor_cores = accelerated_available::<R>(
+        client,
+        F::as_elem_native_unchecked(),
+        Elem::Float(FloatKind::F32),
+    );
+
+    let mut algorithms = Vec::new();
+    if key.groups() == 1 && key.is_zero_padded() {
+        algorithms.push(Conv2dAlgorithm::Im2col);
+    }
+    if key.is_depthwise() {
+        algorithms.push(Conv2dAlgorithm::Depthwise);
+    } else if key.groups() != 1 || !key.is_zero_padded() || tensor_cores.is_ok() {
+        // With groups or another padding than zeros, the implicit GEMM is the only algorithm
+        // left, so it reports its errors.
+        algorithms.push(Conv2dAlgorithm::ImplicitGemm);
+    }
+    if key.is_winograd_compatible() {
+        algorithms.push(Conv2dAlgorithm::Winograd);
+    }
+
+    algorithms
+}
+
+fn launch_algorithm<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    algorithm: Conv2dAlgorithm,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+) -> Result<(), ConvLaunchError> {
+    match algorithm {
+        Conv2dAlgorithm::ImplicitGemm => launch_conv2d::<R, (F, F, f32), ImplicitCmmaConv, Large>(
+            client, input, weight, bias, out, options,
+        ),
+        Conv2dAlgorithm::Depthwise => {
+            conv2d_depthwise::<R, F, f32>(client, input, weight, bias, out, options)
+        }
+        Conv2dAlgorithm::Im2col => {
+            conv2d_im2col::<R, F>(&Strategy::Auto, client, input, weight, bias, out, options)
+        }
+        Conv2dAlgorithm::Winograd => {
+            conv2d_winograd::<R, F>(&Strategy::Auto, client, input, weight, bias, out, options)
+        }
+    }
+}
+
+/// Convolution launch with a fixed algorithm, returning it once launched.
+struct Conv2dTunable<R: Runtime, F: Float> {
+    client: ComputeClient<R::Server, R::Channel>,
+    algorithm: Conv2dAlgorithm,
+    options: Conv2dOptions,
+    _elem: PhantomData<F>,
+}
+
+impl<R: Runtime, F: Float> Conv2dTunable<R, F> {
+    fn new(
+        client: ComputeClient<R::Server, R::Channel>,
+        algorithm: Conv2dAlgorithm,
+        options: Conv2dOptions,
+    ) -> Self {
+        Self {
+            client,
+            algorithm,
+            options,
+            _elem: PhantomData,
+        }
+    }
+}
+
+impl<R: Runtime, F: Float> Tunable for Conv2dTunable<R, F> {
+    type Inputs = Conv2dInputs<R, F>;
+    type Output = Conv2dAlgorithm;
+
+    fn execute(
+        &self,
+        (input, weight, bias, out): Self::Inputs,
+    ) -> Result<Self::Output, AutotuneError> {
+        launch_algorithm::<R, F>(
+            &self.client,
+            self.algorithm,
+            &input.as_ref(),
+            &weight.as_ref(),
+            bias.as_ref().map(TensorHandle::as_ref).as_ref(),
+            &out.as_ref(),
+            &self.options,
+        )
+        .map(|_| self.algorithm)
+        .map_err(Into::into)
+    }
+
+    fn name(&self) -> &str {
+        self.algorithm.name()
+    }
+}
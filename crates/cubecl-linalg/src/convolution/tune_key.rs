@@ -0,0 +1,210 @@
+use cubecl_core as cubecl;
+
+use cubecl_core::{AutotuneKey, ir::Elem};
+use serde::{Deserialize, Serialize};
+
+use super::{Conv2dOptions, Padding2d, base::PaddingMode, is_winograd_compatible};
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Serialize, Deserialize, AutotuneKey)]
+/// Autotune key of the selection between 2D convolution algorithms.
+///
+/// Every dimension is kept as is, since which algorithms apply depends on the exact kernel size,
+/// stride and groups, and the layers of a model are usually launched with the same shapes. The
+/// layout of each tensor is kept as well, since e.g. an NCHW output is copied from an NHWC one.
+pub struct Conv2dAutotuneKey {
+    batch: usize,
+    in_h: usize,
+    in_w: usize,
+    channels: usize,
+    out_channels: usize,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding_begin: (usize, usize),
+    padding_end: (usize, usize),
+    padding_mode: PaddingMode,
+    dilation: (usize, usize),
+    groups: usize,
+    has_bias: bool,
+    input_channels_last: bool,
+    weight_channels_last: bool,
+    out_channels_last: bool,
+    elem: Elem,
+}
+
+impl Conv2dAutotuneKey {
+    /// Create the autotune key of the convolution of a `[batch, height, width, channels]` input of
+    /// `input_shape` with an `[out_channels, kernel_h, kernel_w, channels / groups]` weight of
+    /// `weight_shape`, the strides of each tensor being given in the same order.
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate(
+        input_shape: &[usize],
+        weight_shape: &[usize],
+        input_strides: &[usize],
+        weight_strides: &[usize],
+        out_strides: &[usize],
+        has_bias: bool,
+        options: &Conv2dOptions,
+        elem: Elem,
+    ) -> Self {
+        Self::new(
+            input_shape[0],
+            input_shape[1],
+            input_shape[2],
+            input_shape[3],
+            weight_shape[0],
+            (weight_shape[1], weight_shape[2]),
+            options.stride,
+            options.padding.begin,
+            options.padding.end,
+            options.padding_mode,
+            options.dilation,
+            options.groups,
+            has_bias,
+            is_channels_last(input_strides),
+            is_channels_last(weight_strides),
+            is_channels_last(out_strides),
+            elem,
+        )
+    }
+
+    /// Number of groups the channels are split into.
+    pub fn groups(&self) -> usize {
+        self.groups
+    }
+
+    /// Whether each group has a single input and output channel.
+    pub fn is_depthwise(&self) -> bool {
+        self.groups == self.channels && self.groups == self.out_channels
+    }
+
+    /// Whether the input is padded with zeros, the only mode of the explicit im2col and Winograd
+    /// convolutions.
+    pub fn is_zero_padded(&self) -> bool {
+        self.padding_mode == PaddingMode::Zero
+    }
+
+    /// Whether the convolution can use the Winograd algorithm, see [is_winograd_compatible].
+    pub fn is_winograd_compatible(&self) -> bool {
+        let options = Conv2dOptions {
+            stride: self.stride,
+            padding: Padding2d {
+                begin: self.padding_begin,
+                end: self.padding_end,
+            },
+            padding_mode: self.padding_mode,
+            dilation: self.dilation,
+            groups: self.groups,
+            ..Default::default()
+        };
+
+        is_winograd_compatible(self.kernel_size, &options)
+    }
+}
+
+/// Whether the channels of a tensor with the `strides` of its `[batch, height, width, channels]`
+/// dimensions are contiguous and vary fastest, e.g. NHWC rather than NCHW.
+fn is_channels_last(strides: &[usize]) -> bool {
+    strides[3] == 1 && strides[2] >= strides[3] && strides[1] >= strides[2]
+}
+
+#[cfg(test)]
+mod tests {
+    use cubecl_core::ir::FloatKind;
+
+    use super::*;
+
+    fn contiguous(shape: &[usize]) -> Vec<usize> {
+        (0..shape.len())
+            .map(|dim| shape[dim + 1..].iter().product())
+            .collect()
+    }
+
+    /// Key of the convolution of contiguous NHWC, OHWI and NHWC tensors, only the order of the
+    /// strides of the output mattering to the key.
+    fn key(
+        input_shape: &[usize],
+        weight_shape: &[usize],
+        has_bias: bool,
+        options: &Conv2dOptions,
+    ) -> Conv2dAutotuneKey {
+        let out_strides = contiguous(input_shape);
+        key_with_out_strides(input_shape, weight_shape, &out_strides, has_bias, options)
+    }
+
+    fn key_with_out_strides(
+        input_shape: &[usize],
+        weight_shape: &[usize],
+        out_strides: &[usize],
+        has_bias: bool,
+        options: &Conv2dOptions,
+    ) -> Conv2dAutotuneKey {
+        Conv2dAutotuneKey::generate(
+            input_shape,
+            weight_shape,
+            &contiguous(input_shape),
+            &contiguous(weight_shape),
+            out_strides,
+            has_bias,
+            options,
+            Elem::Float(FloatKind::F32),
+        )
+    }
+
+    #[test]
+    fn conv2d_autotune_key_depthwise() {
+        let options = Conv2dOptions {
+            groups: 8,
+            ..Default::default()
+        };
+        let key = key(&[2, 9, 11, 8], &[8, 3, 3, 1], true, &options);
+
+        assert!(key.is_depthwise());
+        assert!(!key.is_winograd_compatible());
+    }
+
+    #[test]
+    fn conv2d_autotune_key_winograd() {
+        let options = Conv2dOptions {
+            padding: (1, 1).into(),
+            ..Default::default()
+        };
+        let strided = key(
+            &[2, 9, 11, 8],
+            &[4, 3, 3, 8],
+            false,
+            &Conv2dOptions {
+                stride: (2, 2),
+                ..options
+            },
+        );
+        let key = key(&[2, 9, 11, 8], &[4, 3, 3, 8], false, &options);
+
+        assert!(!key.is_depthwise());
+        assert!(key.is_winograd_compatible());
+        assert!(!strided.is_winograd_compatible());
+        assert_ne!(key, strided);
+    }
+
+    #[test]
+    fn conv2d_autotune_key_layout() {
+        let options = Conv2dOptions::default();
+        // The `[batch, out_h, out_w, out_channels]` strides of NHWC and NCHW outputs.
+        let nhwc = key_with_out_strides(
+            &[2, 9, 11, 8],
+            &[4, 3, 3, 8],
+            &[252, 36, 4, 1],
+            false,
+            &options,
+        );
+        let nchw = key_with_out_strides(
+            &[2, 9, 11, 8],
+            &[4, 3, 3, 8],
+            &[252, 9, 1, 63],
+            false,
+            &options,
+        );
+
+        assert_eq!(nhwc, key(&[2, 9, 11, 8], &[4, 3, 3, 8], false, &options));
+        assert_ne!(nhwc, nchw);
+    }
+}
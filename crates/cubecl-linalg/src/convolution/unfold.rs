@@ -0,0 +1,443 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_core::{CubeType, calculate_cube_count_elemwise, tensor_line_size_parallel};
+
+use crate::tensor::TensorHandle;
+
+use super::{
+    Conv2dOptions, ConvLaunchError, Padding2d, check_output_shape, check_zero_padding,
+    conv2d_output_shape, reader::im2col::im2col_input_position,
+};
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
+/// Window parameters of [unfold], [im2col] and [col2im] known at compile time.
+pub(crate) struct UnfoldConfig {
+    pub kernel_h: u32,
+    pub kernel_w: u32,
+    pub stride_h: u32,
+    pub stride_w: u32,
+    pub padding_h: i32,
+    pub padding_w: i32,
+    pub dilation_h: u32,
+    pub dilation_w: u32,
+}
+
+impl Init for UnfoldConfig {
+    fn init(self, _scope: &mut Scope) -> Self {
+        self
+    }
+}
+
+impl UnfoldConfig {
+    fn new(kernel_size: (usize, usize), options: &Conv2dOptions) -> Self {
+        Self {
+            kernel_h: kernel_size.0 as u32,
+            kernel_w: kernel_size.1 as u32,
+            stride_h: options.stride.0 as u32,
+            stride_w: options.stride.1 as u32,
+            padding_h: options.padding.begin.0 as i32,
+            padding_w: options.padding.begin.1 as i32,
+            dilation_h: options.dilation.0 as u32,
+            dilation_w: options.dilation.1 as u32,
+        }
+    }
+}
+
+/// Writes one element of the `[batch, k, l]` unfolded matrix per unit.
+#[cube(launch_unchecked)]
+fn unfold_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    output: &mut Tensor<E>,
+    out_w: u32,
+    #[comptime] config: UnfoldConfig,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let shape_l = output.shape(2);
+    let shape_k = output.shape(1);
+    let shape_y = input.shape(1);
+    let shape_x = input.shape(2);
+    let shape_channel = input.shape(3);
+
+    let view_l = ABSOLUTE_POS % shape_l;
+    let rem = ABSOLUTE_POS / shape_l;
+    let view_k = rem % shape_k;
+    let batch = rem / shape_k;
+
+    let out_x = view_l % out_w;
+    let out_y = view_l / out_w;
+
+    // Same order along k as the implicit GEMM of the convolution.
+    let kernel_w = config.kernel_w;
+    let channel = view_k % shape_channel;
+    let rem = view_k / shape_channel;
+    let kernel_x = rem % kernel_w;
+    let kernel_y = rem / kernel_w;
+
+    let y = im2col_input_position(
+        out_y,
+        kernel_y,
+        config.stride_h,
+        config.dilation_h,
+        config.padding_h,
+    );
+    let x = im2col_input_position(
+        out_x,
+        kernel_x,
+        config.stride_w,
+        config.dilation_w,
+        config.padding_w,
+    );
+
+    let mut value = E::from_int(0);
+    if y >= 0 && (y as u32) < shape_y && x >= 0 && (x as u32) < shape_x {
+        value = input[batch * input.stride(0)
+            + y as u32 * input.stride(1)
+            + x as u32 * input.stride(2)
+            + channel * input.stride(3)];
+    }
+
+    output[ABSOLUTE_POS] = value;
+}
+
+/// Unfold the sliding windows of an NHWC `input` into the `[batch, channels * kernel_h * kernel_w,
+/// out_h * out_w]` matrix multiplied by the weights in the implicit GEMM of the convolution.
+///
+/// Column `l` holds the window of the output position `(l / out_w, l % out_w)`. Along the rows,
+/// the channel varies fastest, then the kernel column, then the kernel row, the order the
+/// convolution uses for its weights. `kernel_size`, `stride`, `dilation` and each side of
+/// `padding` are given as `(height, width)` and elements read in the padding are zeros, as in the
+/// convolution with zero padding.
+///
+/// Returns an error when the window doesn't fit in the padded input or a stride or dilation is
+/// zero.
+///
+/// # Panics
+///
+/// If `input` isn't a rank 4 tensor.
+pub fn unfold<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding: Padding2d,
+    dilation: (usize, usize),
+) -> Result<TensorHandle<R, E>, ConvLaunchError> {
+    assert_eq!(
+        input.shape.len(),
+        4,
+        "Unfold input should be an NHWC tensor"
+    );
+
+    let [batches, in_h, in_w, channels] = [0, 1, 2, 3].map(|dim| input.shape[dim]);
+    let [_, _, out_h, out_w] = conv2d_output_shape(
+        [batches, channels, in_h, in_w],
+        [1, channels, kernel_size.0, kernel_size.1],
+        stride,
+        padding,
+        dilation,
+    )?;
+
+    let shape_k = channels * kernel_size.0 * kernel_size.1;
+    let output = TensorHandle::<R, E>::empty(client, vec![batches, shape_k, out_h * out_w]);
+
+    // Without input channels, there is nothing to unfold.
+    if shape_k == 0 {
+        return Ok(output);
+    }
+
+    let config = UnfoldConfig {
+        kernel_h: kernel_size.0 as u32,
+        kernel_w: kernel_size.1 as u32,
+        stride_h: stride.0 as u32,
+        stride_w: stride.1 as u32,
+        padding_h: padding.begin.0 as i32,
+        padding_w: padding.begin.1 as i32,
+        dilation_h: dilation.0 as u32,
+        dilation_w: dilation.1 as u32,
+    };
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(batches * shape_k * out_h * out_w, cube_dim);
+
+    unsafe {
+        unfold_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            output.as_ref().as_tensor_arg(1),
+            ScalarArg::new(out_w as u32),
+            config,
+        );
+    }
+
+    Ok(output)
+}
+
+/// Writes one line of channels of the `[batch * out_h * out_w, k]` columns per unit.
+#[cube(launch_unchecked)]
+fn im2col_kernel<N: Numeric>(
+    input: &Tensor<Line<N>>,
+    columns: &mut Tensor<Line<N>>,
+    out_h: u32,
+    out_w: u32,
+    #[comptime] config: UnfoldConfig,
+) {
+    if ABSOLUTE_POS >= columns.len() {
+        terminate!();
+    }
+
+    let line_size = columns.line_size();
+    let shape_k = columns.shape(1);
+    let shape_channel = input.shape(3);
+
+    let index = ABSOLUTE_POS * line_size;
+    let row = index / shape_k;
+    let view_k = index % shape_k;
+
+    let out_x = row % out_w;
+    let rem = row / out_w;
+    let out_y = rem % out_h;
+    let batch = rem / out_h;
+
+    let kernel_w = config.kernel_w;
+    let channel = view_k % shape_channel;
+    let rem = view_k / shape_channel;
+    let kernel_x = rem % kernel_w;
+    let kernel_y = rem / kernel_w;
+
+    let y = im2col_input_position(
+        out_y,
+        kernel_y,
+        config.stride_h,
+        config.dilation_h,
+        config.padding_h,
+    );
+    let x = im2col_input_position(
+        out_x,
+        kernel_x,
+        config.stride_w,
+        config.dilation_w,
+        config.padding_w,
+    );
+
+    let mut value = Line::empty(line_size).fill(N::from_int(0));
+    if y >= 0 && (y as u32) < input.shape(1) && x >= 0 && (x as u32) < input.shape(2) {
+        let offset = batch * input.stride(0)
+            + y as u32 * input.stride(1)
+            + x as u32 * input.stride(2)
+            + channel * input.stride(3);
+        value = input[offset / line_size];
+    }
+
+    columns[ABSOLUTE_POS] = value;
+}
+
+/// Writes one line of channels of the NHWC output per unit, summing the columns of every window
+/// covering its position.
+#[cube(launch_unchecked)]
+fn col2im_kernel<N: Numeric>(
+    columns: &Tensor<Line<N>>,
+    output: &mut Tensor<Line<N>>,
+    out_h: u32,
+    out_w: u32,
+    #[comptime] config: UnfoldConfig,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let line_size = output.line_size();
+    let shape_channel = output.shape(3);
+    let shape_x = output.shape(2);
+    let shape_y = output.shape(1);
+
+    let index = ABSOLUTE_POS * line_size;
+    let channel = index % shape_channel;
+    let rem = index / shape_channel;
+    let x = rem % shape_x;
+    let rem = rem / shape_x;
+    let y = rem % shape_y;
+    let batch = rem / shape_y;
+
+    let mut sum = Line::empty(line_size).fill(N::from_int(0));
+
+    // The position is read by the window at `out` when `out * stride + kernel * dilation -
+    // padding` reaches it.
+    for kernel_y in 0..config.kernel_h {
+        let pos_y = (y as i32 + config.padding_h) - (kernel_y * config.dilation_h) as i32;
+        let out_y = pos_y / config.stride_h as i32;
+        let y_in_bounds = pos_y >= 0 && pos_y % config.stride_h as i32 == 0 && out_y < out_h as i32;
+
+        for kernel_x in 0..config.kernel_w {
+            let pos_x = (x as i32 + config.padding_w) - (kernel_x * config.dilation_w) as i32;
+            let out_x = pos_x / config.stride_w as i32;
+            let x_in_bounds =
+                pos_x >= 0 && pos_x % config.stride_w as i32 == 0 && out_x < out_w as i32;
+
+            if y_in_bounds && x_in_bounds {
+                let row = (batch * out_h + out_y as u32) * out_w + out_x as u32;
+                let view_k = (kernel_y * config.kernel_w + kernel_x) * shape_channel + channel;
+                let offset = row * columns.stride(0) + view_k * columns.stride(1);
+                sum += columns[offset / line_size];
+            }
+        }
+    }
+
+    output[ABSOLUTE_POS] = sum;
+}
+
+/// Unfold the sliding windows of the `kernel_size` kernel of a 2D convolution over an NHWC
+/// `input` into a `[batch * out_h * out_w, kernel_h * kernel_w * channels]` matrix, so that
+/// multiplying it with the `[k, out_channels]` weight of
+/// [launch_conv2d_nhwc](super::launch_conv2d_nhwc) computes the convolution.
+///
+/// Row `m` holds the window of the output position `m`, in the order of the NHWC output, so each
+/// batch is the transpose of its [unfolded](unfold) matrix. Along k, the channel varies fastest,
+/// then the kernel column, then the kernel row, like the implicit GEMM of the convolution.
+/// Elements read in the padding are zeros.
+///
+/// Only the stride, padding and dilation of `options` are used. With groups, the rows still hold
+/// all the channels, the columns of a group being its channels at each kernel position. The input
+/// is read in lines of channels when they are contiguous.
+///
+/// Returns the errors of [conv2d_output_shape], and
+/// [ConvUnimplementedError::PaddingMode](super::ConvUnimplementedError::PaddingMode) when the
+/// padding isn't made of zeros.
+///
+/// # Panics
+///
+/// If `input` isn't a rank 4 tensor.
+pub fn im2col<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    kernel_size: (usize, usize),
+    options: &Conv2dOptions,
+) -> Result<TensorHandle<R, N>, ConvLaunchError> {
+    assert_eq!(
+        input.shape.len(),
+        4,
+        "Im2col input should be an NHWC tensor"
+    );
+
+    let [batches, in_h, in_w, channels] = [0, 1, 2, 3].map(|dim| input.shape[dim]);
+    let [_, _, out_h, out_w] = conv2d_output_shape(
+        [batches, channels, in_h, in_w],
+        [1, channels, kernel_size.0, kernel_size.1],
+        options.stride,
+        options.padding,
+        options.dilation,
+    )?;
+    check_zero_padding(options.padding_mode)?;
+
+    let shape_m = batches * out_h * out_w;
+    let shape_k = kernel_size.0 * kernel_size.1 * channels;
+    let columns = TensorHandle::<R, N>::empty(client, vec![shape_m, shape_k]);
+
+    // Without input channels, there is nothing to unfold.
+    if shape_k == 0 {
+        return Ok(columns);
+    }
+
+    let elem = N::as_elem_native_unchecked();
+    let line_size =
+        tensor_line_size_parallel(R::line_size_elem(&elem), input.shape, input.strides, 3);
+
+    let cube_dim = CubeDim::default();
+    let num_lines = shape_m * shape_k / line_size as usize;
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+    unsafe {
+        im2col_kernel::launch_unchecked::<N, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size),
+            columns.as_ref().as_tensor_arg(line_size),
+            ScalarArg::new(out_h as u32),
+            ScalarArg::new(out_w as u32),
+            UnfoldConfig::new(kernel_size, options),
+        );
+    }
+
+    Ok(columns)
+}
+
+/// Fold the `[batch * out_h * out_w, kernel_h * kernel_w * channels]` `columns` of [im2col] back
+/// into a new NHWC tensor of `input_shape`, `[batch, height, width, channels]`, summing the
+/// elements of the windows that overlap.
+///
+/// This is the adjoint of [im2col] with the same `kernel_size` and `options`, which turns the
+/// gradient of the columns into the gradient of the input. The positions no window reads, e.g.
+/// the last rows of a strided convolution, are zeros. The output is written in lines of channels.
+///
+/// Returns the errors of [im2col], and
+/// [ShapeMismatch](ConvLaunchError::ShapeMismatch) when `columns` doesn't have the
+/// shape of the columns of an input of `input_shape`.
+pub fn col2im<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    columns: &TensorHandleRef<'_, R>,
+    input_shape: [usize; 4],
+    kernel_size: (usize, usize),
+    options: &Conv2dOptions,
+) -> Result<TensorHandle<R, N>, ConvLaunchError> {
+    let [batches, in_h, in_w, channels] = input_shape;
+    let [_, _, out_h, out_w] = conv2d_output_shape(
+        [batches, channels, in_h, in_w],
+        [1, channels, kernel_size.0, kernel_size.1],
+        options.stride,
+        options.padding,
+        options.dilation,
+    )?;
+    check_zero_padding(options.padding_mode)?;
+
+    let shape_k = kernel_size.0 * kernel_size.1 * channels;
+    check_output_shape(
+        "columns",
+        columns.shape,
+        &[batches * out_h * out_w, shape_k],
+        &input_shape,
+        &[kernel_size.0, kernel_size.1],
+    )?;
+
+    let output = TensorHandle::<R, N>::empty(client, input_shape.to_vec());
+
+    if channels == 0 {
+        return Ok(output);
+    }
+
+    // The lines must not cross channels of different positions, in the columns nor the output.
+    let elem = N::as_elem_native_unchecked();
+    let line_size = R::line_size_elem(&elem)
+        .filter(|&line_size| {
+            let line_size = line_size as usize;
+            line_size == 1
+                || (columns.strides[1] == 1
+                    && channels % line_size == 0
+                    && columns.strides[0] % line_size == 0)
+        })
+        .max()
+        .unwrap_or(1);
+
+    let cube_dim = CubeDim::default();
+    let num_lines = batches * in_h * in_w * channels / line_size as usize;
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+    unsafe {
+        col2im_kernel::launch_unchecked::<N, R>(
+            client,
+            cube_count,
+            cube_dim,
+            columns.as_tensor_arg(line_size),
+            output.as_ref().as_tensor_arg(line_size),
+            ScalarArg::new(out_h as u32),
+            ScalarArg::new(out_w as u32),
+            UnfoldConfig::new(kernel_size, options),
+        );
+    }
+
+    Ok(output)
+}
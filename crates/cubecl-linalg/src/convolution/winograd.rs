@@ -0,0 +1,357 @@
+//! Winograd F(2x2, 3x3) convolutions, for 3x3 kernels with a stride and dilation of 1.
+//!
+//! Each 2x2 block of output positions is computed from the 4x4 input tile covering its windows.
+//! The tiles and the kernels are transformed so that the convolution of a tile becomes an
+//! element-wise product of two 4x4 matrices, which for all the channels is a matmul per element:
+//! `M[t] = V[t] x U[t]` for each of the 16 elements `t`, with `V` the `[tiles, channels]` input
+//! tiles and `U` the `[channels, out_channels]` kernels. This takes 16 products per 2x2 block
+//! instead of the 36 of the direct convolution.
+//!
+//! With `B`, `G` and `A` the matrices of the algorithm, the transforms are `V = B^T d B` for an
+//! input tile `d`, `U = G g G^T` for a kernel `g`, and the output block is `A^T M A`. The
+//! transforms are computed in f32. `G` has halves, so the transformed kernels aren't exact in
+//! every element type, which makes the result slightly less accurate than the direct
+//! convolution.
+use cubecl_core as cubecl;
+use cubecl_core::calculate_cube_count_elemwise;
+use cubecl_core::prelude::*;
+
+use crate::{
+    matmul::{
+        self, Strategy,
+        kernels::tiling2d::{MatmulEpilogue, activation},
+    },
+    tensor::TensorHandle,
+};
+
+use super::{
+    Conv2dOptions, ConvLaunchError, ConvUnimplementedError, base::PaddingMode, check_output_shape,
+    conv2d::rank_4, conv2d_grouped_output_shape, launch::BoundBias,
+};
+
+/// Number of elements of a transformed tile.
+const TILE_ELEMS: usize = 16;
+
+/// Writes the 16 elements of `U = G g G^T` for one kernel `g` per unit, the `[16, channels,
+/// out_channels]` output holding the `[channels, out_channels]` matrix of each element.
+#[cube(launch_unchecked)]
+fn winograd_weight_kernel<E: Numeric>(weight: &Tensor<E>, transformed: &mut Tensor<E>) {
+    let out_channels = weight.shape(0);
+    let channels = weight.shape(3);
+    let matrix_size = channels * out_channels;
+
+    if ABSOLUTE_POS >= matrix_size {
+        terminate!();
+    }
+
+    let out_channel = ABSOLUTE_POS % out_channels;
+    let channel = ABSOLUTE_POS / out_channels;
+    let half = f32::new(0.5);
+
+    // `G g`, 4x3, one column of the kernel at a time.
+    let mut rows = Array::<f32>::new(12);
+    for x in 0..3 {
+        let offset =
+            out_channel * weight.stride(0) + x * weight.stride(2) + channel * weight.stride(3);
+        let g0 = f32::cast_from(weight[offset]);
+        let g1 = f32::cast_from(weight[offset + weight.stride(1)]);
+        let g2 = f32::cast_from(weight[offset + 2 * weight.stride(1)]);
+
+        rows[x] = g0;
+        rows[3 + x] = (g0 + g1 + g2) * half;
+        rows[6 + x] = (g0 - g1 + g2) * half;
+        rows[9 + x] = g2;
+    }
+
+    // `(G g) G^T`, 4x4, one row at a time.
+    for y in 0..4 {
+        let t0 = rows[y * 3];
+        let t1 = rows[y * 3 + 1];
+        let t2 = rows[y * 3 + 2];
+        let offset = y * 4 * matrix_size + ABSOLUTE_POS;
+
+        transformed[offset] = E::cast_from(t0);
+        transformed[offset + matrix_size] = E::cast_from((t0 + t1 + t2) * half);
+        transformed[offset + 2 * matrix_size] = E::cast_from((t0 - t1 + t2) * half);
+        transformed[offset + 3 * matrix_size] = E::cast_from(t2);
+    }
+}
+
+/// Writes the 16 elements of `V = B^T d B` for one channel of one input tile `d` per unit, the
+/// `[16, tiles, channels]` output holding the `[tiles, channels]` matrix of each element.
+///
+/// The tile of the output block `(tile_y, tile_x)` starts at `2 * tile - padding` in the input,
+/// `padding` being the begin padding, reading zeros outside of it.
+#[cube(launch_unchecked)]
+fn winograd_input_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    transformed: &mut Tensor<E>,
+    tiles_h: u32,
+    tiles_w: u32,
+    padding_h: i32,
+    padding_w: i32,
+) {
+    let channels = input.shape(3);
+    let matrix_size = input.shape(0) * tiles_h * tiles_w * channels;
+
+    if ABSOLUTE_POS >= matrix_size {
+        terminate!();
+    }
+
+    let channel = ABSOLUTE_POS % channels;
+    let tile = ABSOLUTE_POS / channels;
+    let tile_x = tile % tiles_w;
+    let rem = tile / tiles_w;
+    let tile_y = rem % tiles_h;
+    let batch = rem / tiles_h;
+
+    let start_y = (tile_y * 2) as i32 - padding_h;
+    let start_x = (tile_x * 2) as i32 - padding_w;
+
+    let mut patch = Array::<f32>::new(16);
+    for i in 0..4 {
+        let y = start_y + i as i32;
+
+        for j in 0..4 {
+            let x = start_x + j as i32;
+
+            let mut value = f32::new(0.0);
+            if y >= 0 && (y as u32) < input.shape(1) && x >= 0 && (x as u32) < input.shape(2) {
+                value = f32::cast_from(
+                    input[batch * input.stride(0)
+                        + y as u32 * input.stride(1)
+                        + x as u32 * input.stride(2)
+                        + channel * input.stride(3)],
+                );
+            }
+            patch[i * 4 + j] = value;
+        }
+    }
+
+    // `B^T d`, one column at a time.
+    let mut rows = Array::<f32>::new(16);
+    for j in 0..4 {
+        let d0 = patch[j];
+        let d1 = patch[4 + j];
+        let d2 = patch[8 + j];
+        let d3 = patch[12 + j];
+
+        rows[j] = d0 - d2;
+        rows[4 + j] = d1 + d2;
+        rows[8 + j] = d2 - d1;
+        rows[12 + j] = d1 - d3;
+    }
+
+    // `(B^T d) B`, one row at a time.
+    for i in 0..4 {
+        let t0 = rows[i * 4];
+        let t1 = rows[i * 4 + 1];
+        let t2 = rows[i * 4 + 2];
+        let t3 = rows[i * 4 + 3];
+        let offset = i * 4 * matrix_size + ABSOLUTE_POS;
+
+        transformed[offset] = E::cast_from(t0 - t2);
+        transformed[offset + matrix_size] = E::cast_from(t1 + t2);
+        transformed[offset + 2 * matrix_size] = E::cast_from(t2 - t1);
+        transformed[offset + 3 * matrix_size] = E::cast_from(t1 - t3);
+    }
+}
+
+/// Writes the 2x2 output block `A^T M A` of one output channel of one tile per unit, from the
+/// `[16, tiles, out_channels]` products, then adds the bias and applies the activation. The
+/// positions past the last row or column of the output are skipped.
+#[cube(launch_unchecked)]
+fn winograd_output_kernel<E: Numeric>(
+    products: &Tensor<E>,
+    bias: &Tensor<E>,
+    output: &mut Tensor<E>,
+    tiles_h: u32,
+    tiles_w: u32,
+    #[comptime] has_bias: bool,
+    #[comptime] epilogue: MatmulEpilogue,
+) {
+    let out_channels = output.shape(3);
+    let matrix_size = output.shape(0) * tiles_h * tiles_w * out_channels;
+
+    if ABSOLUTE_POS >= matrix_size {
+        terminate!();
+    }
+
+    let out_channel = ABSOLUTE_POS % out_channels;
+    let tile = ABSOLUTE_POS / out_channels;
+    let tile_x = tile % tiles_w;
+    let rem = tile / tiles_w;
+    let tile_y = rem % tiles_h;
+    let batch = rem / tiles_h;
+
+    // `A^T M`, 2x4, one column at a time.
+    let mut rows = Array::<f32>::new(8);
+    for j in 0..4 {
+        let m0 = f32::cast_from(products[j * matrix_size + ABSOLUTE_POS]);
+        let m1 = f32::cast_from(products[(4 + j) * matrix_size + ABSOLUTE_POS]);
+        let m2 = f32::cast_from(products[(8 + j) * matrix_size + ABSOLUTE_POS]);
+        let m3 = f32::cast_from(products[(12 + j) * matrix_size + ABSOLUTE_POS]);
+
+        rows[j] = m0 + m1 + m2;
+        rows[4 + j] = m1 - m2 - m3;
+    }
+
+    let mut bias_value = f32::new(0.0);
+    if comptime!(has_bias) {
+        bias_value = f32::cast_from(bias[out_channel * bias.stride(0)]);
+    }
+
+    // `(A^T M) A`, one row at a time.
+    for i in 0..2 {
+        let out_y = tile_y * 2 + i;
+        let t0 = rows[i * 4];
+        let t1 = rows[i * 4 + 1];
+        let t2 = rows[i * 4 + 2];
+        let t3 = rows[i * 4 + 3];
+
+        let mut block_row = Array::<f32>::new(2);
+        block_row[0] = t0 + t1 + t2;
+        block_row[1] = t1 - t2 - t3;
+
+        for j in 0..2 {
+            let out_x = tile_x * 2 + j;
+
+            if out_y < output.shape(1) && out_x < output.shape(2) {
+                let value = activation::<f32>(block_row[j] + bias_value, epilogue);
+                output[batch * output.stride(0)
+                    + out_y * output.stride(1)
+                    + out_x * output.stride(2)
+                    + out_channel * output.stride(3)] = E::cast_from(value);
+            }
+        }
+    }
+}
+
+/// Whether a convolution of a `kernel_size` kernel with `options` can use [conv2d_winograd]: a
+/// 3x3 kernel, a stride and dilation of 1, a single group and zero padding.
+pub fn is_winograd_compatible(kernel_size: (usize, usize), options: &Conv2dOptions) -> bool {
+    kernel_size == (3, 3)
+        && options.stride == (1, 1)
+        && options.dilation == (1, 1)
+        && options.groups == 1
+        && options.padding_mode == PaddingMode::Zero
+}
+
+/// Perform a 2D convolution with the Winograd F(2x2, 3x3) algorithm, computing the products of
+/// the 16 transformed elements with a batched matmul of the given `strategy`.
+///
+/// The tensors and options are those of [launch_conv2d](super::launch_conv2d), each tensor
+/// being read or written in place with any strides. The transforms and the bias are computed in
+/// f32 and the activation of the options is applied after the bias, but the transformed tiles and
+/// their products are stored in `E`.
+///
+/// Returns [ConvUnimplementedError::Winograd] when the convolution isn't
+/// [compatible](is_winograd_compatible), [ConvLaunchError::InvalidRank] when the input or the
+/// weight doesn't have rank 4, [ConvLaunchError::ShapeMismatch] when the output doesn't have the
+/// shape of the convolution, the errors of [conv2d_grouped_output_shape] and those of the
+/// matmul.
+pub fn conv2d_winograd<R: Runtime, E: Numeric>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    weight: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+) -> Result<(), ConvLaunchError> {
+    let [batches, in_h, in_w, channels] = rank_4(input.shape, "input")?;
+    let [out_channels, kernel_h, kernel_w, group_channels] = rank_4(weight.shape, "weight")?;
+    let [_, _, out_h, out_w] = conv2d_grouped_output_shape(
+        [batches, channels, in_h, in_w],
+        [out_channels, group_channels, kernel_h, kernel_w],
+        options.stride,
+        options.padding,
+        options.dilation,
+        options.groups,
+    )?;
+    check_output_shape(
+        "out",
+        out.shape,
+        &[batches, out_h, out_w, out_channels],
+        input.shape,
+        weight.shape,
+    )?;
+
+    if !is_winograd_compatible((kernel_h, kernel_w), options) {
+        return Err(ConvUnimplementedError::Winograd {
+            kernel_size: (kernel_h, kernel_w),
+            stride: options.stride,
+            dilation: options.dilation,
+            groups: options.groups,
+            padding_mode: options.padding_mode,
+        }
+        .into());
+    }
+
+    let (tiles_h, tiles_w) = (out_h.div_ceil(2), out_w.div_ceil(2));
+    let tiles = batches * tiles_h * tiles_w;
+    let cube_dim = CubeDim::default();
+
+    let products = match channels {
+        // Without input channels, every product is zero.
+        0 => TensorHandle::<R, E>::zeros(client, vec![TILE_ELEMS, tiles, out_channels]),
+        _ => {
+            let weights =
+                TensorHandle::<R, E>::empty(client, vec![TILE_ELEMS, channels, out_channels]);
+            let tiles_input =
+                TensorHandle::<R, E>::empty(client, vec![TILE_ELEMS, tiles, channels]);
+            let products =
+                TensorHandle::<R, E>::empty(client, vec![TILE_ELEMS, tiles, out_channels]);
+
+            unsafe {
+                winograd_weight_kernel::launch_unchecked::<E, R>(
+                    client,
+                    calculate_cube_count_elemwise(channels * out_channels, cube_dim),
+                    cube_dim,
+                    weight.as_tensor_arg(1),
+                    weights.as_ref().as_tensor_arg(1),
+                );
+                winograd_input_kernel::launch_unchecked::<E, R>(
+                    client,
+                    calculate_cube_count_elemwise(tiles * channels, cube_dim),
+                    cube_dim,
+                    input.as_tensor_arg(1),
+                    tiles_input.as_ref().as_tensor_arg(1),
+                    ScalarArg::new(tiles_h as u32),
+                    ScalarArg::new(tiles_w as u32),
+                    ScalarArg::new(options.padding.begin.0 as i32),
+                    ScalarArg::new(options.padding.begin.1 as i32),
+                );
+            }
+
+            matmul::launch_ref::<R, E>(
+                strategy,
+                client,
+                &tiles_input.as_ref(),
+                &weights.as_ref(),
+                &products.as_ref(),
+            )?;
+
+            products
+        }
+    };
+
+    let bound_bias = BoundBias::<R, E>::new(client, bias, out_channels);
+
+    unsafe {
+        winograd_output_kernel::launch_unchecked::<E, R>(
+            client,
+            calculate_cube_count_elemwise(tiles * out_channels, cube_dim),
+            cube_dim,
+            products.as_ref().as_tensor_arg(1),
+            bound_bias.as_arg(1),
+            out.as_tensor_arg(1),
+            ScalarArg::new(tiles_h as u32),
+            ScalarArg::new(tiles_w as u32),
+            bias.is_some(),
+            options.epilogue,
+        );
+    }
+
+    Ok(())
+}
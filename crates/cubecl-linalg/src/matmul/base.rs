@@ -1,7 +1,12 @@
-use cubecl_core::{Runtime, client::ComputeClient, prelude::TensorHandleRef};
+use cubecl_core::{
+    Runtime,
+    client::ComputeClient,
+    prelude::{CubePrimitive, TensorHandleRef},
+    server::Handle,
+};
 use cubecl_std::MaybeQuantized;
 
-use crate::tensor::TensorHandle;
+use crate::tensor::{ClientScratch, ScratchProvider, TensorHandle};
 
 use super::{
     components::{
@@ -13,7 +18,7 @@ use super::{
         tile::accelerated::Accelerated,
     },
     kernels::{
-        MatmulLaunchError,
+        MatmulInvalidProblem, MatmulLaunchError,
         matmul::{
             self, double_buffering::DoubleBufferingAlgorithm, simple::SimpleAlgorithm,
             simple_barrier::SimpleBarrierAlgorithm, simple_pipelined::SimplePipelinedAlgorithm,
@@ -76,82 +81,112 @@ pub fn launch_ref<R: Runtime, EG: MaybeQuantized>(
     lhs: &TensorHandleRef<R>,
     rhs: &TensorHandleRef<R>,
     out: &TensorHandleRef<R>,
+) -> Result<(), MatmulLaunchError> {
+    launch_ref_with_scratch::<R, EG, ClientScratch>(strategy, client, lhs, rhs, out, &ClientScratch)
+}
+
+/// Launch a matmul, allocating every temporary buffer, such as the contiguous copies of the
+/// inputs, with the given scratch provider.
+pub fn launch_ref_with_scratch<R: Runtime, EG: MaybeQuantized, S: ScratchProvider<R>>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<R>,
+    rhs: &TensorHandleRef<R>,
+    out: &TensorHandleRef<R>,
+    scratch: &S,
 ) -> Result<(), MatmulLaunchError> {
     match strategy {
         Strategy::Simple(loading_strategy) => match loading_strategy {
             SyncLoadingStrategy::Cyclic => {
-                matmul::launch_ref::<R, EG, SimpleAlgorithm<Accelerated>>(client, lhs, rhs, out)
+                matmul::launch_ref_with_scratch::<R, EG, SimpleAlgorithm<Accelerated>, S>(
+                    client, lhs, rhs, out, scratch,
+                )
             }
-            SyncLoadingStrategy::Strided => matmul::launch_ref::<
+            SyncLoadingStrategy::Strided => matmul::launch_ref_with_scratch::<
                 R,
                 EG,
                 SimpleAlgorithm<Accelerated, StridedCoalescedLoading, StridedCoalescedLoading>,
-            >(client, lhs, rhs, out),
+                S,
+            >(client, lhs, rhs, out, scratch),
         },
         Strategy::SimpleBarrier(loading_strategy) => match loading_strategy {
-            AsyncLoadingStrategy::Cooperative => matmul::launch_ref::<
+            AsyncLoadingStrategy::Cooperative => matmul::launch_ref_with_scratch::<
                 R,
                 EG,
                 SimpleBarrierAlgorithm<Accelerated, WindowCooperativeLoading>,
-            >(client, lhs, rhs, out),
-            AsyncLoadingStrategy::Cyclic => matmul::launch_ref::<
+                S,
+            >(client, lhs, rhs, out, scratch),
+            AsyncLoadingStrategy::Cyclic => matmul::launch_ref_with_scratch::<
                 R,
                 EG,
                 SimpleBarrierAlgorithm<Accelerated, CyclicWindowLoading<ColMajorTilingOrder>>,
-            >(client, lhs, rhs, out),
-            AsyncLoadingStrategy::MaximizeSliceLength => matmul::launch_ref::<
-                R,
-                EG,
-                SimpleBarrierAlgorithm<Accelerated, MaximizeSliceLengthLoading>,
-            >(client, lhs, rhs, out),
-            AsyncLoadingStrategy::MaximizeUnitCount => matmul::launch_ref::<
+                S,
+            >(client, lhs, rhs, out, scratch),
+            AsyncLoadingStrategy::MaximizeSliceLength => {
+                matmul::launch_ref_with_scratch::<
+                    R,
+                    EG,
+                    SimpleBarrierAlgorithm<Accelerated, MaximizeSliceLengthLoading>,
+                    S,
+                >(client, lhs, rhs, out, scratch)
+            }
+            AsyncLoadingStrategy::MaximizeUnitCount => matmul::launch_ref_with_scratch::<
                 R,
                 EG,
                 SimpleBarrierAlgorithm<Accelerated, MaximizeUnitCountLoading>,
-            >(client, lhs, rhs, out),
+                S,
+            >(client, lhs, rhs, out, scratch),
         },
         Strategy::SimplePipelined => {
-            matmul::launch_ref::<R, EG, SimplePipelinedAlgorithm<Accelerated>>(
-                client, lhs, rhs, out,
+            matmul::launch_ref_with_scratch::<R, EG, SimplePipelinedAlgorithm<Accelerated>, S>(
+                client, lhs, rhs, out, scratch,
             )
         }
         Strategy::DoubleBuffering => {
-            matmul::launch_ref::<R, EG, DoubleBufferingAlgorithm<Accelerated>>(
-                client, lhs, rhs, out,
+            matmul::launch_ref_with_scratch::<R, EG, DoubleBufferingAlgorithm<Accelerated>, S>(
+                client, lhs, rhs, out, scratch,
             )
         }
         Strategy::Specialized => {
-            matmul::launch_ref::<R, EG, SpecializedAlgorithm<Accelerated>>(client, lhs, rhs, out)
-        }
-        #[cfg(any(test, feature = "export_tests"))]
-        Strategy::PlaneMma => {
-            matmul::launch_ref::<R, EG, SimpleAlgorithm<super::components::tile::plane::PlaneMma>>(
-                client, lhs, rhs, out,
+            matmul::launch_ref_with_scratch::<R, EG, SpecializedAlgorithm<Accelerated>, S>(
+                client, lhs, rhs, out, scratch,
             )
         }
-        Strategy::Tiling2D(config) => {
-            tiling2d::launch_ref::<R, EG::Numeric>(client, lhs, rhs, out, config.clone());
-            Ok(())
-        }
+        #[cfg(any(test, feature = "export_tests"))]
+        Strategy::PlaneMma => matmul::launch_ref_with_scratch::<
+            R,
+            EG,
+            SimpleAlgorithm<super::components::tile::plane::PlaneMma>,
+            S,
+        >(client, lhs, rhs, out, scratch),
+        Strategy::Tiling2D(config) => tiling2d::launch_ref_with_scratch::<R, EG::Numeric, S>(
+            client,
+            lhs,
+            rhs,
+            out,
+            config.clone(),
+            scratch,
+        ),
         Strategy::Naive => {
-            naive::launch_ref::<R, EG::Numeric>(client, lhs, rhs, out)?;
+            naive::launch_ref_with_scratch::<R, EG::Numeric, S>(client, lhs, rhs, out, scratch)?;
             Ok(())
         }
         Strategy::Auto => {
             if let Err(err) =
-                matmul::launch_ref::<R, EG, SimpleAlgorithm<Accelerated>>(client, lhs, rhs, out)
+                matmul::launch_ref_with_scratch::<R, EG, SimpleAlgorithm<Accelerated>, S>(
+                    client, lhs, rhs, out, scratch,
+                )
             {
-                match err {
-                    super::kernels::MatmulLaunchError::Unavailable(_) => {
-                        tiling2d::launch_ref::<R, EG::Numeric>(
-                            client,
-                            lhs,
-                            rhs,
-                            out,
-                            Tiling2dConfig::default(),
-                        )
-                    }
-                    _ => panic!("{err:?}"),
+                match err.is_unavailable() {
+                    true => tiling2d::launch_ref_with_scratch::<R, EG::Numeric, S>(
+                        client,
+                        lhs,
+                        rhs,
+                        out,
+                        Tiling2dConfig::default(),
+                        scratch,
+                    )?,
+                    false => panic!("{err:?}"),
                 }
             }
 
@@ -159,3 +194,89 @@ pub fn launch_ref<R: Runtime, EG: MaybeQuantized>(
         }
     }
 }
+
+/// A matmul operand stored in a region of a shared buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct InterleavedOperand<'a> {
+    /// Position of the first element of the operand in the buffer, in elements.
+    pub offset: usize,
+    /// Shape of the operand, with the matrix in the last two dimensions.
+    pub shape: &'a [usize],
+    /// Strides of the operand in elements, relative to its first element.
+    pub strides: &'a [usize],
+}
+
+/// Launch a matmul where lhs and rhs are read from the same `buffer`, without splitting it into
+/// separate allocations.
+///
+/// Both operands are bound as kernel inputs, so they are only ever read and may overlap.
+/// The output must not alias `buffer`.
+///
+/// # Errors
+///
+/// If an operand has strides of another rank than its shape, if it does not fit in the buffer, or
+/// if its offset in bytes is not a multiple of the device memory alignment.
+pub fn launch_interleaved<R: Runtime, EG: MaybeQuantized>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    buffer: &Handle,
+    lhs: InterleavedOperand<'_>,
+    rhs: InterleavedOperand<'_>,
+    out: &TensorHandleRef<R>,
+) -> Result<(), MatmulLaunchError> {
+    let elem_size = EG::Numeric::size().expect("Element should have a size");
+    let alignment = client.properties().memory_properties().alignment;
+
+    let lhs_handle = interleaved_handle(buffer, &lhs, elem_size, alignment)?;
+    let rhs_handle = interleaved_handle(buffer, &rhs, elem_size, alignment)?;
+
+    // Safety: the operand regions are checked to be inside the buffer.
+    let (lhs, rhs) = unsafe {
+        (
+            TensorHandleRef::<R>::from_raw_parts(&lhs_handle, lhs.strides, lhs.shape, elem_size),
+            TensorHandleRef::<R>::from_raw_parts(&rhs_handle, rhs.strides, rhs.shape, elem_size),
+        )
+    };
+
+    launch_ref::<R, EG>(strategy, client, &lhs, &rhs, out)
+}
+
+fn interleaved_handle(
+    buffer: &Handle,
+    operand: &InterleavedOperand<'_>,
+    elem_size: usize,
+    alignment: u64,
+) -> Result<Handle, MatmulLaunchError> {
+    if operand.shape.len() != operand.strides.len() {
+        return Err(MatmulLaunchError::InvalidLayout {
+            strides: operand.strides.to_vec(),
+        });
+    }
+
+    // Saturating, so that an operand too large to be addressed is reported as out of the buffer.
+    let offset = (operand.offset as u64).saturating_mul(elem_size as u64);
+    if offset % alignment != 0 {
+        return Err(MatmulInvalidProblem::MisalignedOperand { offset, alignment }.into());
+    }
+
+    let num_elems = if operand.shape.contains(&0) {
+        0
+    } else {
+        operand
+            .shape
+            .iter()
+            .zip(operand.strides)
+            .map(|(shape, stride)| ((shape - 1) as u64).saturating_mul(*stride as u64))
+            .fold(1u64, u64::saturating_add)
+    };
+    let end = offset.saturating_add(num_elems.saturating_mul(elem_size as u64));
+    if end > buffer.size() {
+        return Err(MatmulInvalidProblem::OperandOutOfBounds {
+            end,
+            size: buffer.size(),
+        }
+        .into());
+    }
+
+    Ok(buffer.clone().offset_start(offset))
+}
@@ -0,0 +1,244 @@
+use std::{
+    fmt::{Debug, Display},
+    time::{Duration, Instant},
+};
+
+use cubecl_core::{
+    CubeElement, Runtime,
+    benchmark::{BenchmarkComputations, BenchmarkDurations, TimingMethod},
+    client::ComputeClient,
+    future,
+    prelude::Float,
+};
+
+use crate::tensor::TensorHandle;
+
+use super::{
+    Strategy,
+    kernels::{MatmulLaunchError, naive},
+    launch_ref,
+};
+
+/// How [benchmark_matmul] measures each problem.
+#[derive(Clone, Debug)]
+pub struct MatmulBenchmarkOptions {
+    /// Launches before the timed ones, e.g. to compile the kernels
+    pub warmup: usize,
+    /// Timed launches of each problem, at least one, otherwise the benchmark fails with
+    /// [NoSamples](MatmulBenchmarkError::NoSamples)
+    pub samples: usize,
+    /// Compare the output of the benchmarked strategy with the naive kernel before timing it
+    pub verify: bool,
+    /// Largest difference allowed when verifying, relative to the expected value when it is above
+    /// one
+    pub tolerance: f32,
+}
+
+impl Default for MatmulBenchmarkOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            samples: 10,
+            verify: false,
+            tolerance: 1e-2,
+        }
+    }
+}
+
+/// Timings of a problem benchmarked by [benchmark_matmul].
+#[derive(Clone, Debug)]
+pub struct MatmulBenchmarkResult {
+    /// Number of matrices in each operand
+    pub batch: usize,
+    /// Rows of lhs and of the output
+    pub m: usize,
+    /// Columns of lhs and rows of rhs
+    pub k: usize,
+    /// Columns of rhs and of the output
+    pub n: usize,
+    /// Wall time of every timed launch, up to the end of its synchronization
+    pub durations: BenchmarkDurations,
+    /// Median of the [durations](Self::durations), the one [gflops](Self::gflops) is derived from
+    pub median: Duration,
+    /// Shortest of the [durations](Self::durations)
+    pub min: Duration,
+}
+
+impl MatmulBenchmarkResult {
+    /// Floating point operations of the problem, a multiplication and an addition per term.
+    pub fn flops(&self) -> f64 {
+        2.0 * (self.batch * self.m * self.k * self.n) as f64
+    }
+
+    /// Throughput of the median launch, in billions of floating point operations per second.
+    pub fn gflops(&self) -> f64 {
+        self.flops() / self.median.as_secs_f64() / 1e9
+    }
+}
+
+/// Errors that stop [benchmark_matmul].
+pub enum MatmulBenchmarkError {
+    /// The options ask for no timed launch, so there is nothing to report.
+    NoSamples,
+    /// The benchmarked strategy or the naive kernel verifying it failed to launch.
+    Launch(MatmulLaunchError),
+    /// The benchmarked strategy and the naive kernel disagree on an element of the output.
+    Mismatch {
+        batch: usize,
+        m: usize,
+        k: usize,
+        n: usize,
+        index: usize,
+        expected: f32,
+        actual: f32,
+    },
+}
+
+impl From<MatmulLaunchError> for MatmulBenchmarkError {
+    fn from(value: MatmulLaunchError) -> Self {
+        Self::Launch(value)
+    }
+}
+
+impl Display for MatmulBenchmarkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatmulBenchmarkError::NoSamples => {
+                write!(f, "At least one sample is required to benchmark a matmul")
+            }
+            MatmulBenchmarkError::Launch(err) => Display::fmt(err, f),
+            MatmulBenchmarkError::Mismatch {
+                batch,
+                m,
+                k,
+                n,
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Output differs from the naive kernel for {batch}x{m}x{k}x{n}: index={index} actual={actual}, expected={expected}"
+            ),
+        }
+    }
+}
+
+impl Debug for MatmulBenchmarkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for MatmulBenchmarkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MatmulBenchmarkError::Launch(err) => Some(err),
+            MatmulBenchmarkError::NoSamples | MatmulBenchmarkError::Mismatch { .. } => None,
+        }
+    }
+}
+
+/// Benchmark the matmul `strategy` with `F` elements on each `(batch, m, k, n)` problem, in order.
+///
+/// The operands are contiguous `[batch, m, k]` and `[batch, k, n]` tensors filled with the same
+/// values on every run, so results are comparable across devices and commits. Each problem is
+/// launched `warmup` times, then `samples` times waiting for the device after every launch, the
+/// returned durations being wall times.
+///
+/// The first error stops the benchmark, either from a launch or, with
+/// [verify](MatmulBenchmarkOptions::verify), from the comparison with the naive kernel.
+pub fn benchmark_matmul<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    strategy: &Strategy,
+    problems: &[(usize, usize, usize, usize)],
+    options: &MatmulBenchmarkOptions,
+) -> Result<Vec<MatmulBenchmarkResult>, MatmulBenchmarkError> {
+    if options.samples == 0 {
+        return Err(MatmulBenchmarkError::NoSamples);
+    }
+
+    problems
+        .iter()
+        .map(|&(batch, m, k, n)| {
+            let lhs = benchmark_operand::<R, F>(client, vec![batch, m, k], 1);
+            let rhs = benchmark_operand::<R, F>(client, vec![batch, k, n], 2);
+            let out = TensorHandle::<R, F>::empty(client, vec![batch, m, n]);
+            let (lhs_ref, rhs_ref, out_ref) = (lhs.as_ref(), rhs.as_ref(), out.as_ref());
+            let launch = || launch_ref::<R, F>(strategy, client, &lhs_ref, &rhs_ref, &out_ref);
+
+            if options.verify {
+                let expected = TensorHandle::<R, F>::empty(client, vec![batch, m, n]);
+                naive::launch_ref::<R, F>(client, &lhs_ref, &rhs_ref, &expected.as_ref())?;
+                launch()?;
+
+                let expected = expected.to_vec(client);
+                let actual = out.to_vec(client);
+                for (index, (e, a)) in expected.iter().zip(actual.iter()).enumerate() {
+                    let (expected, actual) = (e.to_f32().unwrap(), a.to_f32().unwrap());
+                    let error = (expected - actual).abs();
+
+                    if error.is_nan() || error > options.tolerance * expected.abs().max(1.0) {
+                        return Err(MatmulBenchmarkError::Mismatch {
+                            batch,
+                            m,
+                            k,
+                            n,
+                            index,
+                            expected,
+                            actual,
+                        });
+                    }
+                }
+            }
+
+            for _ in 0..options.warmup {
+                launch()?;
+            }
+            future::block_on(client.sync());
+
+            let mut durations = Vec::with_capacity(options.samples);
+            for _ in 0..options.samples {
+                let start = Instant::now();
+                launch()?;
+                future::block_on(client.sync());
+                durations.push(start.elapsed());
+            }
+
+            let durations = BenchmarkDurations {
+                timing_method: TimingMethod::Full,
+                durations,
+            };
+            let computed = BenchmarkComputations::new(&durations);
+
+            Ok(MatmulBenchmarkResult {
+                batch,
+                m,
+                k,
+                n,
+                median: computed.median,
+                min: computed.min,
+                durations,
+            })
+        })
+        .collect()
+}
+
+/// A contiguous tensor of values in `[-1, 1]` depending only on `seed` and the index.
+fn benchmark_operand<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: Vec<usize>,
+    seed: u64,
+) -> TensorHandle<R, F> {
+    let num_elements = shape.iter().product::<usize>();
+    let mut state = seed;
+    let data = (0..num_elements)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            F::new(((state >> 40) as f32 / (1u64 << 23) as f32) - 1.0)
+        })
+        .collect::<Vec<_>>();
+
+    TensorHandle::from_data(client, &data, &shape)
+}
@@ -7,15 +7,15 @@ use crate::matmul::kernels::MatmulAvailabilityError;
 
 use super::{MatmulPrecision, MatmulProblem, MatmulSize};
 
-pub type InvalidConfigError = Box<dyn Display>;
+pub type InvalidConfigError = Box<dyn Display + Send + Sync>;
 
 pub struct FormattedConfigError {
-    func: Box<dyn Fn() -> String>,
+    func: Box<dyn Fn() -> String + Send + Sync>,
 }
 
 impl FormattedConfigError {
     #[allow(clippy::new_ret_no_self)]
-    pub fn new<F: Fn() -> String + 'static>(func: F) -> Box<dyn Display> {
+    pub fn new<F: Fn() -> String + Send + Sync + 'static>(func: F) -> InvalidConfigError {
         Box::new(Self {
             func: Box::new(func),
         })
@@ -7,7 +7,7 @@ pub mod tensor_view;
 mod accumulator_loader;
 mod base;
 mod config;
-mod tilewise_unloading;
+pub(crate) mod tilewise_unloading;
 
 pub mod output_loader;
 
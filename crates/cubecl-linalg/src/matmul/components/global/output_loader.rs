@@ -37,6 +37,8 @@ impl<EG: Numeric> Unloader<EG> {
 
 #[cube]
 impl<EG: Numeric> StageWriter<EG> for Unloader<EG> {
+    type Staging = EG;
+
     fn write<ES: Numeric, G: global::GlobalConfig>(
         this: &mut Self,
         slice: Slice<Line<ES>>,
@@ -1,4 +1,4 @@
-use crate::matmul::kernels::MatmulInvalidProblem;
+use crate::{matmul::kernels::MatmulInvalidProblem, tensor::checked_u32_indexing};
 
 use super::{MatrixLayout, batch};
 
@@ -38,6 +38,7 @@ impl MatmulProblem {
     ///
     ///  - If dimensions of the problem are larger than allowed by the config
     ///  - If line sizes do not divide well the dimension in which they are aligned
+    ///  - If a matrix has more elements than `u32` can index
     pub fn check_config<B: batch::BatchConfig>(
         &self,
         config: &B,
@@ -56,6 +57,16 @@ impl MatmulProblem {
             });
         }
 
+        let lhs_batches = self.batches.0.iter().product();
+        let rhs_batches = self.batches.1.iter().product();
+        for shape in [
+            [lhs_batches, self.m, self.k],
+            [rhs_batches, self.k, self.n],
+            [self.num_batches(), self.m, self.n],
+        ] {
+            checked_u32_indexing(&shape).map_err(MatmulInvalidProblem::SizeOverflow)?;
+        }
+
         if self.num_batches() > config.max_batches() as usize {
             return Err(MatmulInvalidProblem::ExceededBatchSize {
                 b: self.num_batches() as u32,
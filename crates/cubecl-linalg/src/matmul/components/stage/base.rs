@@ -131,6 +131,10 @@ pub trait StageReader<ES: Numeric>: CubeType {
 /// Responsible of writing the accumulated stage matmul output
 /// to global memory
 pub trait StageWriter<EG: Numeric>: CubeType + 'static + Send + Sync {
+    /// The type the stage matmul reads its accumulators to shared memory in, before handing them
+    /// to [write](StageWriter::write).
+    type Staging: Numeric;
+
     /// Writes the given slice to global memory, at a position that depends on
     /// plane and accumulator indexes.
     fn write<ES: Numeric, G: global::GlobalConfig>(
@@ -197,7 +197,7 @@ where
                 }
             }
             _ => {
-                let mut out_smem = SharedMemory::<EG>::new_lined(
+                let mut out_smem = SharedMemory::<SW::Staging>::new_lined(
                     num_tile_lines * stage_config.num_planes(),
                     out_smem_line_size,
                 );
@@ -211,7 +211,7 @@ where
                         &mut smem_slice,
                         stage_config.to_tmm_config(),
                     );
-                    SW::write::<EG, G>(
+                    SW::write::<SW::Staging, G>(
                         out,
                         smem_slice.to_slice(),
                         UNIT_POS_Y,
@@ -204,7 +204,7 @@ where
             stage_config.tiling_dimensions(Ident::Out).tile_size() / out_smem_line_size;
 
         let start = num_tile_lines * UNIT_POS_Y;
-        let mut out_smem = SharedMemory::<EG>::new_lined(
+        let mut out_smem = SharedMemory::<SW::Staging>::new_lined(
             num_tile_lines * stage_config.num_planes(),
             out_smem_line_size,
         );
@@ -214,7 +214,7 @@ where
             let accumulator = acc.index(accumulator_iter);
             let mut smem_slice = out_smem.slice_mut(start, start + num_tile_lines);
             TMM::read_accumulator(accumulator, &mut smem_slice, stage_config.to_tmm_config());
-            SW::write::<EG, G>(
+            SW::write::<SW::Staging, G>(
                 out,
                 smem_slice.to_slice(),
                 UNIT_POS_Y,
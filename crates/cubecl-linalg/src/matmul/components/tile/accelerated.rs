@@ -142,6 +142,13 @@ impl MatmulConfigFactory for Accelerated {
             _ => o_elem,
         };
 
+        if !(MP::ES::is_supported(client) && MP::EG::is_supported(client)) {
+            return Err(MatmulAvailabilityError::TypesUnavailable {
+                input: i_elem,
+                output: o_elem,
+            });
+        }
+
         let size = config.size;
         if !client.properties().feature_enabled(Feature::Cmma {
             a: i_elem,
@@ -160,13 +167,6 @@ impl MatmulConfigFactory for Accelerated {
             });
         }
 
-        if !(MP::ES::is_supported(client) && MP::EG::is_supported(client)) {
-            return Err(MatmulAvailabilityError::TypesUnavailable {
-                input: i_elem,
-                output: o_elem,
-            });
-        }
-
         Ok(())
     }
 
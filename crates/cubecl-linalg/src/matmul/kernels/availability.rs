@@ -0,0 +1,77 @@
+use cubecl_core::{
+    Feature, Runtime,
+    client::ComputeClient,
+    ir::{Elem, FloatKind},
+};
+
+use super::MatmulAvailabilityError;
+
+/// Instruction shapes `(m, n, k)` the accelerated kernels may select for a problem.
+const INSTRUCTION_SHAPES: [(u8, u8, u8); 4] = [(16, 16, 16), (32, 8, 16), (8, 32, 16), (16, 16, 8)];
+
+/// Whether the accelerated matmul, running on the tensor cores, can launch on the device of
+/// `client` with operands multiplied as `input` and an output of `output` elements, without
+/// building a kernel.
+///
+/// `input` is the element type of the stage, e.g. `f16` for `f16` operands. The launcher
+/// multiplies `f32` operands as `tf32` when the device supports it, and as `f16` otherwise, while
+/// [launch_ref_tf32](super::matmul::launch_ref_tf32) always multiplies them as `tf32`.
+///
+/// The checks are the ones of the launcher, in this order:
+/// - the plane size must be known and equal to 32, otherwise the error is
+///   [PlaneDimUnknown](MatmulAvailabilityError::PlaneDimUnknown) or
+///   [PlaneDimUnsupported](MatmulAvailabilityError::PlaneDimUnsupported);
+/// - both types must be supported, otherwise the error is
+///   [TypesUnavailable](MatmulAvailabilityError::TypesUnavailable);
+/// - the device must have a matrix instruction for the types with one of the shapes the launcher
+///   selects, otherwise the error is
+///   [CmmaInstructionUnavailable](MatmulAvailabilityError::CmmaInstructionUnavailable) for the
+///   default `16x16x16` shape.
+pub fn accelerated_available<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: Elem,
+    output: Elem,
+) -> Result<(), MatmulAvailabilityError> {
+    let properties = client.properties();
+
+    // The accelerated config is only valid for planes of 32 units.
+    match properties.hardware_properties().defined_plane_size() {
+        Some(32) => {}
+        Some(plane_dim) => return Err(MatmulAvailabilityError::PlaneDimUnsupported { plane_dim }),
+        None => return Err(MatmulAvailabilityError::PlaneDimUnknown),
+    }
+
+    let native = |elem: Elem| match elem {
+        Elem::Float(FloatKind::Flex32) => Elem::Float(FloatKind::F32),
+        _ => elem,
+    };
+    let (input, output) = (native(input), native(output));
+
+    if !(properties.feature_enabled(Feature::Type(input))
+        && properties.feature_enabled(Feature::Type(output)))
+    {
+        return Err(MatmulAvailabilityError::TypesUnavailable { input, output });
+    }
+
+    let instruction_available = INSTRUCTION_SHAPES.iter().any(|&(m, n, k)| {
+        properties.feature_enabled(Feature::Cmma {
+            a: input,
+            b: input,
+            c: output,
+            m,
+            k,
+            n,
+        })
+    });
+    if !instruction_available {
+        return Err(MatmulAvailabilityError::CmmaInstructionUnavailable {
+            input,
+            output,
+            m: 16,
+            n: 16,
+            k: 16,
+        });
+    }
+
+    Ok(())
+}
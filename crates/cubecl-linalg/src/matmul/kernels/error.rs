@@ -1,18 +1,130 @@
-use cubecl_core::{CubeCount, ir::Elem};
-use std::fmt::Debug;
+use cubecl_core::{CubeCount, CubeDim, ir::Elem, prelude::CubeDimError, tune::AutotuneError};
+use std::fmt::{Debug, Display};
 
-use crate::matmul::components::InvalidConfigError;
+use crate::{matmul::components::InvalidConfigError, tensor::SizeError};
+
+use super::tiling2d::Tiling2dConfig;
 
 pub enum MatmulLaunchError {
     Unavailable(MatmulAvailabilityError),
     InvalidProblem(MatmulInvalidProblem),
     InvalidConfig(InvalidConfigError),
     Unimplemented(MatmulUnimplementedError),
+    /// The output buffer is smaller than the result, in bytes.
+    OutputTooSmall {
+        required: u64,
+        actual: u64,
+    },
+    /// A tensor has a memory layout the kernel can't handle, e.g. a highly permuted operand.
+    InvalidLayout {
+        strides: Vec<usize>,
+    },
+    /// The operands can't be multiplied: a rank below 2, different ranks or a different k.
+    InvalidShape {
+        lhs: Vec<usize>,
+        rhs: Vec<usize>,
+    },
+    /// The kernel selected by autotune failed, see [matmul_autotune](crate::matmul::matmul_autotune).
+    Autotune(AutotuneError),
+    /// An error with the problem of the launch that returned it, see
+    /// [with_context](MatmulLaunchError::with_context).
+    WithContext {
+        context: Box<MatmulLaunchContext>,
+        error: Box<MatmulLaunchError>,
+    },
+}
+
+/// The problem of a failed launch, reported with its error.
+#[derive(Clone, Debug)]
+pub struct MatmulLaunchContext {
+    pub m: usize,
+    pub k: usize,
+    pub n: usize,
+    /// Number of matrices in the output, the product of its batch dimensions
+    pub batches: usize,
+    /// Element type of the operands
+    pub elem: Elem,
+    /// Config of the tiling 2d kernels, `None` for the other ones
+    pub config: Option<Tiling2dConfig>,
+}
+
+impl MatmulLaunchContext {
+    /// The context of a launch with the given lhs and output shapes, read from the output so that
+    /// it is also available for the problems rejected by the launch. A missing dimension, for a
+    /// rank below 2, is reported as 1.
+    pub fn new(
+        lhs_shape: &[usize],
+        out_shape: &[usize],
+        elem: Elem,
+        config: Option<Tiling2dConfig>,
+    ) -> Self {
+        let from_end = |shape: &[usize], index: usize| {
+            shape.len().checked_sub(index).map_or(1, |axis| shape[axis])
+        };
+        let batch_rank = out_shape.len().saturating_sub(2);
+
+        Self {
+            m: from_end(out_shape, 2),
+            k: from_end(lhs_shape, 1),
+            n: from_end(out_shape, 1),
+            batches: out_shape[..batch_rank].iter().product(),
+            elem,
+            config,
+        }
+    }
+}
+
+impl MatmulLaunchError {
+    /// Attach the problem of the launch to the error. An error that already has a context keeps
+    /// it, since it comes from the launch closest to the failure.
+    pub fn with_context(self, context: MatmulLaunchContext) -> Self {
+        match self {
+            MatmulLaunchError::WithContext { .. } => self,
+            error => MatmulLaunchError::WithContext {
+                context: Box::new(context),
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// The problem of the launch that returned the error, if known.
+    pub fn context(&self) -> Option<&MatmulLaunchContext> {
+        match self {
+            MatmulLaunchError::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The error without its context, e.g. to match on its cause.
+    pub fn root(&self) -> &Self {
+        match self {
+            MatmulLaunchError::WithContext { error, .. } => error.root(),
+            error => error,
+        }
+    }
+
+    /// Whether the device can't run the kernel, seen through the context of the error, e.g. to
+    /// fall back to another kernel.
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self.root(), MatmulLaunchError::Unavailable(_))
+    }
+
+    /// Owned version of [root](MatmulLaunchError::root).
+    pub fn into_root(self) -> Self {
+        match self {
+            MatmulLaunchError::WithContext { error, .. } => error.into_root(),
+            error => error,
+        }
+    }
 }
 
 pub enum MatmulAvailabilityError {
     PlaneDimUnknown,
     CubeCountTooBig(CubeCount),
+    CubeDimTooBig {
+        cube_dim: CubeDim,
+        max_units: u32,
+    },
     PlaneDimUnsupported {
         plane_dim: u32,
     },
@@ -30,6 +142,47 @@ pub enum MatmulAvailabilityError {
     },
     PipelineUnavailable,
     BarrierUnavailable,
+    /// The block sizes of the config aren't non-zero multiples of the tile size, or the one along
+    /// k is larger than those along m or n.
+    InvalidBlockSizes {
+        block_size_m: usize,
+        block_size_k: usize,
+        block_size_n: usize,
+        tile_size: usize,
+    },
+    /// The shared memory needed by the block sizes of the config, in bytes, exceeds the device
+    /// limit.
+    SharedMemoryLimitExceeded {
+        required: usize,
+        max: usize,
+        block_size_m: usize,
+        block_size_k: usize,
+        block_size_n: usize,
+    },
+    /// The cube count policy of the config pads the number of cubes up to a multiple of zero.
+    InvalidCubeCountMultiple,
+    /// The shared memory needed by the stages of the config, in bytes, exceeds the device limit.
+    StageSharedMemoryLimitExceeded {
+        required: usize,
+        max: usize,
+    },
+}
+
+/// Check that the device can launch cubes of `cube_dim` with at most `max_units` units each,
+/// without overflowing their number of units, see [CubeDim::try_new].
+pub(crate) fn check_cube_dim(
+    cube_dim: CubeDim,
+    max_units: u32,
+) -> Result<(), MatmulAvailabilityError> {
+    match CubeDim::try_new(cube_dim.x, cube_dim.y, cube_dim.z, max_units) {
+        Ok(_) => Ok(()),
+        Err(CubeDimError::TooManyUnits { .. } | CubeDimError::Overflow { .. }) => {
+            Err(MatmulAvailabilityError::CubeDimTooBig {
+                cube_dim,
+                max_units,
+            })
+        }
+    }
 }
 
 pub enum MatmulInvalidProblem {
@@ -39,6 +192,18 @@ pub enum MatmulInvalidProblem {
     InvalidLineSizeLhs { size: u32, line_size: u8 },
     InvalidLineSizeRhs { size: u32, line_size: u8 },
     InvalidLineSizeOut { size: u32, line_size: u8 },
+    InvalidScalesLength { len: u32, n: u32 },
+    InvalidMaskLength { len: u32, m: u32, n: u32 },
+    InvalidBiasLength { len: u32, n: u32 },
+    MismatchedN { rhs_n: u32, out_n: u32 },
+    MismatchedRank { lhs: u32, rhs: u32, out: u32 },
+    MismatchedBatch { dim: u32, lhs: u32, rhs: u32 },
+    InvalidRowPtrLength { len: u32, m: u32 },
+    MismatchedNonZeros { col_idx: u32, values: u32 },
+    SizeOverflow(SizeError),
+    UnrepresentableScalar { value: f32, elem: Elem },
+    MisalignedOperand { offset: u64, alignment: u64 },
+    OperandOutOfBounds { end: u64, size: u64 },
 }
 
 impl From<MatmulInvalidProblem> for MatmulLaunchError {
@@ -47,6 +212,12 @@ impl From<MatmulInvalidProblem> for MatmulLaunchError {
     }
 }
 
+impl From<SizeError> for MatmulLaunchError {
+    fn from(value: SizeError) -> Self {
+        Self::InvalidProblem(MatmulInvalidProblem::SizeOverflow(value))
+    }
+}
+
 impl From<MatmulAvailabilityError> for MatmulLaunchError {
     fn from(value: MatmulAvailabilityError) -> Self {
         Self::Unavailable(value)
@@ -65,42 +236,109 @@ impl From<MatmulUnimplementedError> for MatmulLaunchError {
     }
 }
 
-impl Debug for MatmulLaunchError {
+impl Display for MatmulLaunchError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MatmulLaunchError::Unavailable(err) => {
-                writeln!(
+                write!(
                     f,
-                    "Unable to launch matmul because a required feature is unavailable: {:?}",
+                    "Unable to launch matmul because a required feature is unavailable: {}",
                     err
                 )
             }
             MatmulLaunchError::InvalidProblem(err) => {
-                writeln!(
+                write!(
                     f,
-                    "Unable to launch matmul because the problem isn't correctly defined: {:?}",
+                    "Unable to launch matmul because the problem isn't correctly defined: {}",
                     err
                 )
             }
             MatmulLaunchError::InvalidConfig(err) => {
-                writeln!(
+                write!(
                     f,
-                    "Unable to launch matmul because the config is invalid: {:?}",
-                    err.to_string()
+                    "Unable to launch matmul because the config is invalid: {}",
+                    err
                 )
             }
             MatmulLaunchError::Unimplemented(err) => {
-                writeln!(
+                write!(
                     f,
-                    "Unable to launch matmul because the feature is not ready: {:?}",
+                    "Unable to launch matmul because the feature is not ready: {}",
                     err
                 )
             }
+            MatmulLaunchError::OutputTooSmall { required, actual } => {
+                write!(
+                    f,
+                    "Unable to launch matmul because the output buffer holds {actual} bytes but {required} are required"
+                )
+            }
+            MatmulLaunchError::InvalidLayout { strides } => {
+                write!(
+                    f,
+                    "Unable to launch matmul because a tensor with strides {strides:?} has an unsupported layout"
+                )
+            }
+            MatmulLaunchError::InvalidShape { lhs, rhs } => {
+                write!(
+                    f,
+                    "Unable to launch matmul because an lhs of shape {lhs:?} can't be multiplied by an rhs of shape {rhs:?}"
+                )
+            }
+            MatmulLaunchError::Autotune(err) => {
+                write!(f, "Unable to launch the matmul selected by autotune: {err}")
+            }
+            MatmulLaunchError::WithContext { context, error } => {
+                write!(f, "{error}, while launching {context}")
+            }
         }
     }
 }
 
-impl Debug for MatmulInvalidProblem {
+impl Debug for MatmulLaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for MatmulLaunchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MatmulLaunchError::Unavailable(err) => Some(err),
+            MatmulLaunchError::InvalidProblem(err) => Some(err),
+            MatmulLaunchError::Unimplemented(err) => Some(err),
+            MatmulLaunchError::WithContext { error, .. } => Some(error.as_ref()),
+            MatmulLaunchError::InvalidConfig(_)
+            | MatmulLaunchError::OutputTooSmall { .. }
+            | MatmulLaunchError::InvalidLayout { .. }
+            | MatmulLaunchError::InvalidShape { .. }
+            | MatmulLaunchError::Autotune(_) => None,
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<AutotuneError> for MatmulLaunchError {
+    fn into(self) -> AutotuneError {
+        AutotuneError::Unknown(self.to_string())
+    }
+}
+
+impl Display for MatmulLaunchContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "m={}, k={}, n={} with {} batches of {:?}",
+            self.m, self.k, self.n, self.batches, self.elem
+        )?;
+        match &self.config {
+            Some(config) => write!(f, " and {config:?}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for MatmulInvalidProblem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MatmulInvalidProblem::ExceededMSize { m, max_m } => write!(
@@ -130,30 +368,95 @@ impl Debug for MatmulInvalidProblem {
                 f,
                 "The out tensor can't be written with line size={line_size} and dimension={size}"
             ),
+            MatmulInvalidProblem::InvalidScalesLength { len, n } => write!(
+                f,
+                "Expected one scale per output column (n={n}), but got {len} scales"
+            ),
+            MatmulInvalidProblem::InvalidMaskLength { len, m, n } => write!(
+                f,
+                "Expected one mask element per output element (m={m}, n={n}), but got {len}"
+            ),
+            MatmulInvalidProblem::InvalidBiasLength { len, n } => write!(
+                f,
+                "Expected one bias per output column (n={n}), but got {len} biases"
+            ),
+            MatmulInvalidProblem::MismatchedN { rhs_n, out_n } => {
+                write!(f, "The rhs has n={rhs_n} but the output has n={out_n}")
+            }
+            MatmulInvalidProblem::MismatchedRank { lhs, rhs, out } => write!(
+                f,
+                "The output should have the rank of the lhs and rhs, but got {out} for {lhs} and {rhs}"
+            ),
+            MatmulInvalidProblem::MismatchedBatch { dim, lhs, rhs } => write!(
+                f,
+                "The lhs has {lhs} batches but the rhs has {rhs} in dimension {dim}, which can't be \
+                 broadcast since neither of them is 1"
+            ),
+            MatmulInvalidProblem::InvalidRowPtrLength { len, m } => write!(
+                f,
+                "Expected one row offset per row plus one (m={m}), but got {len} offsets"
+            ),
+            MatmulInvalidProblem::MismatchedNonZeros { col_idx, values } => write!(
+                f,
+                "Expected one column index per non-zero value ({values} values), but got {col_idx}"
+            ),
+            MatmulInvalidProblem::SizeOverflow(err) => write!(f, "{err}"),
+            MatmulInvalidProblem::UnrepresentableScalar { value, elem } => {
+                write!(f, "The scalar {value} can't be represented as {elem}")
+            }
+            MatmulInvalidProblem::MisalignedOperand { offset, alignment } => write!(
+                f,
+                "An operand starting at byte {offset} isn't aligned to the {alignment} bytes of the device memory"
+            ),
+            MatmulInvalidProblem::OperandOutOfBounds { end, size } => write!(
+                f,
+                "An operand ending at byte {end} doesn't fit in its buffer of {size} bytes"
+            ),
         }
     }
 }
 
-impl Debug for MatmulAvailabilityError {
+impl Debug for MatmulInvalidProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for MatmulInvalidProblem {}
+
+impl Display for MatmulAvailabilityError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MatmulAvailabilityError::PlaneOperationsUnavailable => {
-                writeln!(f, "Plane operations not supported.")
+                write!(f, "Plane operations not supported.")
             }
             MatmulAvailabilityError::CubeCountTooBig(count) => {
-                writeln!(f, "Cube count too big {count:?}")
+                write!(f, "Cube count too big {count:?}")
             }
+            MatmulAvailabilityError::CubeDimTooBig {
+                cube_dim,
+                max_units,
+            } => match cube_dim.checked_num_elems() {
+                Some(num_elems) => write!(
+                    f,
+                    "Cube dim too big {cube_dim:?}, it has {num_elems} units but at most {max_units} are supported"
+                ),
+                None => write!(
+                    f,
+                    "Cube dim too big {cube_dim:?}, its number of units overflows a u32"
+                ),
+            },
             MatmulAvailabilityError::PlaneDimUnknown => {
-                writeln!(f, "Plane dimension unknown.")
+                write!(f, "Plane dimension unknown.")
             }
             MatmulAvailabilityError::PlaneDimUnsupported { plane_dim } => {
-                writeln!(
+                write!(
                     f,
                     "Plane dimension unsupported: {plane_dim}. Only 32 & 64 are supported."
                 )
             }
             MatmulAvailabilityError::TypesUnavailable { input, output } => {
-                writeln!(
+                write!(
                     f,
                     "Types input={:?} and/or output={:?} not supported.",
                     input, output,
@@ -165,31 +468,99 @@ impl Debug for MatmulAvailabilityError {
                 m,
                 n,
                 k,
-            } => writeln!(
+            } => write!(
                 f,
                 "Cmma on inputs {:?} and outputs {:?} with shape m={:?}, n={:?}, k={:?} not supported.",
                 input, output, m, n, k
             ),
             MatmulAvailabilityError::PipelineUnavailable => {
-                writeln!(f, "Pipeline is not available.")
+                write!(f, "Pipeline is not available.")
             }
             MatmulAvailabilityError::BarrierUnavailable => {
-                writeln!(f, "Barrier is not available.")
+                write!(f, "Barrier is not available.")
+            }
+            MatmulAvailabilityError::InvalidBlockSizes {
+                block_size_m,
+                block_size_k,
+                block_size_n,
+                tile_size,
+            } => write!(
+                f,
+                "Block sizes m={block_size_m}, k={block_size_k}, n={block_size_n} should be non-zero multiples of the tile size {tile_size}, with k at most m and n."
+            ),
+            MatmulAvailabilityError::SharedMemoryLimitExceeded {
+                required,
+                max,
+                block_size_m,
+                block_size_k,
+                block_size_n,
+            } => write!(
+                f,
+                "Block sizes m={block_size_m}, k={block_size_k}, n={block_size_n} need {required} bytes of shared memory, but at most {max} are available."
+            ),
+            MatmulAvailabilityError::InvalidCubeCountMultiple => {
+                write!(f, "Can't pad the cube count to a multiple of zero.")
             }
+            MatmulAvailabilityError::StageSharedMemoryLimitExceeded { required, max } => write!(
+                f,
+                "The stages need {required} bytes of shared memory, but at most {max} are available."
+            ),
         }
     }
 }
 
+impl Debug for MatmulAvailabilityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for MatmulAvailabilityError {}
+
 pub enum MatmulUnimplementedError {
     Quantization,
 }
 
-impl Debug for MatmulUnimplementedError {
+impl Display for MatmulUnimplementedError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MatmulUnimplementedError::Quantization => {
-                writeln!(f, "Quantization")
+                write!(f, "Quantization")
             }
         }
     }
 }
+
+impl Debug for MatmulUnimplementedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for MatmulUnimplementedError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cube_dim_overflowing_a_u32_is_too_big() {
+        let cube_dim = CubeDim::new(u32::MAX, 2, 1);
+        let error = check_cube_dim(cube_dim, 1024).unwrap_err();
+
+        assert!(matches!(
+            error,
+            MatmulAvailabilityError::CubeDimTooBig {
+                max_units: 1024,
+                ..
+            }
+        ));
+        assert!(error.to_string().contains("overflows a u32"));
+    }
+
+    #[test]
+    fn cube_dim_within_the_limit_is_valid() {
+        assert!(check_cube_dim(CubeDim::new(32, 32, 1), 1024).is_ok());
+        assert!(check_cube_dim(CubeDim::new(32, 32, 2), 1024).is_err());
+    }
+}
@@ -6,9 +6,13 @@ use crate::matmul::components::{
     OutputRuntimeArg, SingleMatmulSpec,
 };
 use crate::matmul::kernels::{
-    MatmulAvailabilityError, MatmulLaunchError, MatmulUnimplementedError,
+    MatmulAvailabilityError, MatmulLaunchContext, MatmulLaunchError, MatmulUnimplementedError,
+    check_cube_dim,
+};
+use crate::tensor::{
+    ClientScratch, MatrixLayout, ScratchProvider, TensorHandle, into_contiguous_with_scratch,
+    matrix_layout,
 };
-use crate::tensor::{MatrixLayout, TensorHandle, into_contiguous, matrix_layout};
 use core::any::TypeId;
 use cubecl_core::prelude::*;
 use cubecl_core::{
@@ -46,54 +50,96 @@ pub fn launch_ref<R: Runtime, EG: MaybeQuantized, A: Algorithm>(
     rhs: &TensorHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
 ) -> Result<(), MatmulLaunchError> {
+    launch_ref_with_scratch::<R, EG, A, ClientScratch>(client, lhs, rhs, out, &ClientScratch)
+}
+
+/// Launch a matrix multiplication kernel, allocating temporary buffers with the given scratch
+/// provider.
+pub fn launch_ref_with_scratch<
+    R: Runtime,
+    EG: MaybeQuantized,
+    A: Algorithm,
+    S: ScratchProvider<R>,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    scratch: &S,
+) -> Result<(), MatmulLaunchError> {
+    launch_ref_inner::<R, EG, A, S>(client, lhs, rhs, out, scratch, false)
+}
+
+/// Launch a matrix multiplication of `f32` tensors on the tensor cores, the operands being
+/// multiplied as [tf32] and accumulated in `f32`.
+///
+/// Unlike [launch_ref], the operands are always multiplied as `tf32`, which only keeps 10 bits of
+/// mantissa: when the device doesn't support it, the error is
+/// [TypesUnavailable](MatmulAvailabilityError::TypesUnavailable) rather than a fallback on another
+/// type.
+pub fn launch_ref_tf32<R: Runtime, A: Algorithm>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    launch_ref_inner::<R, f32, A, ClientScratch>(client, lhs, rhs, out, &ClientScratch, true)
+}
+
+fn launch_ref_inner<R: Runtime, EG: MaybeQuantized, A: Algorithm, S: ScratchProvider<R>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    scratch: &S,
+    force_tf32: bool,
+) -> Result<(), MatmulLaunchError> {
+    let context = || {
+        let elem = EG::Numeric::as_elem_native_unchecked();
+        MatmulLaunchContext::new(lhs.shape, out.shape, elem, None)
+    };
+
     if EG::QUANTIZED {
-        return Err(MatmulLaunchError::Unimplemented(
-            MatmulUnimplementedError::Quantization,
-        ));
+        let error = MatmulLaunchError::Unimplemented(MatmulUnimplementedError::Quantization);
+        return Err(error.with_context(context()));
     }
 
-    let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.strides) {
-        MatrixLayout::Contiguous => (false, false),
-        MatrixLayout::MildlyPermuted {
-            transposed,
-            batch_swap: _,
-        } => (false, transposed),
-        MatrixLayout::HighlyPermuted => (true, false),
-    };
+    let check_layout =
+        |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.shape, tensor.strides) {
+            MatrixLayout::Contiguous => (false, false),
+            MatrixLayout::MildlyPermuted {
+                transposed,
+                batch_swap: _,
+                batch_broadcast: _,
+            } => (false, transposed),
+            MatrixLayout::HighlyPermuted => (true, false),
+        };
 
     let (lhs_make_contiguous, lhs_transposed) = check_layout(lhs);
     let (rhs_make_contiguous, rhs_transposed) = check_layout(rhs);
 
-    match (lhs_make_contiguous, rhs_make_contiguous) {
-        (false, false) => matmul_cmma_ref_no_check::<R, EG, A>(
-            client,
-            lhs,
-            rhs,
-            out,
-            (lhs_transposed, rhs_transposed),
-        ),
-        (false, true) => matmul_cmma_ref_no_check::<R, EG, A>(
-            client,
-            lhs,
-            &into_contiguous::<R, EG::Numeric>(client, rhs).as_ref(),
-            out,
-            (lhs_transposed, rhs_transposed),
-        ),
-        (true, false) => matmul_cmma_ref_no_check::<R, EG, A>(
-            client,
-            &into_contiguous::<R, EG::Numeric>(client, lhs).as_ref(),
-            rhs,
-            out,
-            (lhs_transposed, rhs_transposed),
-        ),
-        (true, true) => matmul_cmma_ref_no_check::<R, EG, A>(
-            client,
-            &into_contiguous::<R, EG::Numeric>(client, lhs).as_ref(),
-            &into_contiguous::<R, EG::Numeric>(client, rhs).as_ref(),
-            out,
-            (lhs_transposed, rhs_transposed),
-        ),
+    let lhs_contiguous = lhs_make_contiguous
+        .then(|| into_contiguous_with_scratch::<R, EG::Numeric, S>(client, lhs, scratch));
+    let rhs_contiguous = rhs_make_contiguous
+        .then(|| into_contiguous_with_scratch::<R, EG::Numeric, S>(client, rhs, scratch));
+
+    let lhs_contiguous_ref = lhs_contiguous.as_ref().map(TensorHandle::as_ref);
+    let rhs_contiguous_ref = rhs_contiguous.as_ref().map(TensorHandle::as_ref);
+
+    let result = matmul_cmma_ref_no_check::<R, EG, A>(
+        client,
+        lhs_contiguous_ref.as_ref().unwrap_or(lhs),
+        rhs_contiguous_ref.as_ref().unwrap_or(rhs),
+        out,
+        (lhs_transposed, rhs_transposed),
+        force_tf32,
+    );
+
+    for tensor in [lhs_contiguous, rhs_contiguous].into_iter().flatten() {
+        scratch.release(tensor.handle);
     }
+
+    result.map_err(|error| error.with_context(context()))
 }
 
 fn matmul_cmma_ref_no_check<R: Runtime, EG: MaybeQuantized, A: Algorithm>(
@@ -102,6 +148,7 @@ fn matmul_cmma_ref_no_check<R: Runtime, EG: MaybeQuantized, A: Algorithm>(
     rhs: &TensorHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
     transposed: (bool, bool),
+    force_tf32: bool,
 ) -> Result<(), MatmulLaunchError> {
     let rank = lhs.strides.len();
     let eg_elem = EG::Numeric::as_elem_native().expect("To be a native type");
@@ -189,9 +236,11 @@ fn matmul_cmma_ref_no_check<R: Runtime, EG: MaybeQuantized, A: Algorithm>(
         (lhs_line_size, rhs_line_size, out_line_size),
         problem,
         plane_dim,
+        force_tf32,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn matmul_launch_kernel<R: Runtime, EG: MaybeQuantized, A: Algorithm>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: &TensorHandleRef<'_, R>,
@@ -200,8 +249,21 @@ fn matmul_launch_kernel<R: Runtime, EG: MaybeQuantized, A: Algorithm>(
     (lhs_line_size, rhs_line_size, out_line_size): (u8, u8, u8),
     problem: MatmulProblem,
     plane_dim: u32,
+    force_tf32: bool,
 ) -> Result<(), MatmulLaunchError> {
-    if EG::QUANTIZED {
+    if force_tf32 {
+        select_kernel::<SingleMatmulSpec<EG::Numeric, tf32, f32>, R, A>(
+            client,
+            TensorInputsLaunch::new(
+                lhs.as_tensor_arg(lhs_line_size),
+                rhs.as_tensor_arg(rhs_line_size),
+            ),
+            out.as_tensor_arg(out_line_size),
+            problem,
+            plane_dim,
+            false,
+        )
+    } else if EG::QUANTIZED {
         select_kernel::<SingleMatmulSpec<i8, i8, i32>, R, A>(
             client,
             TensorInputsLaunch::new(
@@ -315,6 +377,9 @@ fn launch_matmul<'a, MS: MatmulSpec, R: Runtime, D: Algorithm>(
     config_input: <D::BatchMatmul as MatmulConfigFactory>::Input,
     quantized: bool,
 ) -> Result<(), MatmulLaunchError> {
+    let max_units = client.properties().hardware_properties().max_units_per_cube;
+    check_cube_dim(cube_dim, max_units)?;
+
     let config = D::make_config(config_input, &problem, &cube_dim, &cube_count, quantized)?;
     D::check_availability::<R, (MS::EG, MS::ES, MS::EA)>(client, &config)?;
 
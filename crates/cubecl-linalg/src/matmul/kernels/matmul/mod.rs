@@ -3,4 +3,4 @@ mod base;
 mod algorithm;
 
 pub use algorithm::*;
-pub use base::{launch, launch_ref};
+pub use base::{launch, launch_ref, launch_ref_tf32, launch_ref_with_scratch};
@@ -2,9 +2,13 @@
 pub mod matmul;
 /// Naive non-cooperative matmul without tiling that can be very fast on small matrices.
 pub mod naive;
+/// Sparse CSR matrix times dense matrix.
+pub mod spmm;
 /// Non-cooperative Matmul
 pub mod tiling2d;
 
+mod availability;
 mod error;
 
+pub use availability::*;
 pub use error::*;
@@ -4,15 +4,20 @@
 use cubecl::prelude::*;
 use cubecl_core as cubecl;
 
-use crate::tensor::{MatrixLayout, TensorHandle, into_contiguous, matrix_layout};
+use crate::tensor::{
+    ClientScratch, MatrixLayout, ScratchProvider, TensorHandle, into_contiguous_with_scratch,
+    matrix_layout,
+};
 
-use super::MatmulLaunchError;
+use super::{MatmulLaunchContext, MatmulLaunchError};
 
 #[cube(launch_unchecked)]
 fn matmul_kernel<N: Numeric>(
     lhs: &Tensor<Line<N>>,
     rhs: &Tensor<Line<N>>,
     out: &mut Tensor<N>,
+    // first batch of the launch
+    batch_offset: u32,
     // number of dimensions not involved in the matmul
     #[comptime] num_batches: Option<u32>,
 ) {
@@ -24,7 +29,7 @@ fn matmul_kernel<N: Numeric>(
     let n_cols = rhs.shape(rank - 1);
     let mut k = rhs.shape(rank - 2);
 
-    let batch_pos = ABSOLUTE_POS_Z;
+    let batch_pos = ABSOLUTE_POS_Z + batch_offset;
     let row = CUBE_DIM_X * CUBE_POS_X + UNIT_POS_X;
     let col = CUBE_DIM_Y * CUBE_POS_Y + UNIT_POS_Y;
 
@@ -85,13 +90,25 @@ pub fn launch_ref<R: Runtime, E: Numeric>(
     lhs: &TensorHandleRef<'_, R>,
     rhs: &TensorHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    launch_ref_with_scratch::<R, E, ClientScratch>(client, lhs, rhs, out, &ClientScratch)
+}
+
+/// Matrix multiplication using memory coalescing algorithm, allocating temporary buffers with the
+/// given scratch provider.
+pub fn launch_ref_with_scratch<R: Runtime, E: Numeric, S: ScratchProvider<R>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    scratch: &S,
 ) -> Result<(), MatmulLaunchError> {
     let lhs =
         TensorHandle::<R, E>::new(lhs.shape.to_vec(), lhs.strides.to_vec(), lhs.handle.clone());
     let rhs =
         TensorHandle::<R, E>::new(rhs.shape.to_vec(), rhs.strides.to_vec(), rhs.handle.clone());
 
-    launch(client, lhs, rhs, out)
+    launch_with_scratch(client, lhs, rhs, out, scratch)
 }
 
 pub fn launch<R: Runtime, E: Numeric>(
@@ -99,17 +116,28 @@ pub fn launch<R: Runtime, E: Numeric>(
     lhs: TensorHandle<R, E>,
     rhs: TensorHandle<R, E>,
     out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    launch_with_scratch(client, lhs, rhs, out, &ClientScratch)
+}
+
+pub fn launch_with_scratch<R: Runtime, E: Numeric, S: ScratchProvider<R>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, E>,
+    rhs: TensorHandle<R, E>,
+    out: &TensorHandleRef<'_, R>,
+    scratch: &S,
 ) -> Result<(), MatmulLaunchError> {
     let (cube_dim_x, cube_dim_y) = (32, 8);
     let ndims = lhs.shape.len();
     let dim1 = ndims - 1;
     let dim2 = ndims - 2;
 
-    let lhs_layout = matrix_layout(&lhs.strides);
-    let rhs_layout = matrix_layout(&rhs.strides);
+    let lhs_layout = matrix_layout(&lhs.shape, &lhs.strides);
+    let rhs_layout = matrix_layout(&rhs.shape, &rhs.strides);
 
-    let lhs = if !matches!(lhs_layout, MatrixLayout::Contiguous) {
-        into_contiguous::<R, E>(client, &lhs.as_ref())
+    let lhs_is_scratch = !matches!(lhs_layout, MatrixLayout::Contiguous);
+    let lhs = if lhs_is_scratch {
+        into_contiguous_with_scratch::<R, E, S>(client, &lhs.as_ref(), scratch)
     } else {
         lhs
     };
@@ -122,20 +150,21 @@ pub fn launch<R: Runtime, E: Numeric>(
         rhs.strides.swap(dim1, dim2);
         rhs.shape.swap(dim1, dim2);
 
-        let rhs = into_contiguous::<R, E>(client, &rhs.as_ref());
+        let rhs = into_contiguous_with_scratch::<R, E, S>(client, &rhs.as_ref(), scratch);
 
-        (rhs_original_shape, rhs)
+        (rhs_original_shape, rhs, true)
     };
 
-    let (rhs_original_shape, rhs) = match rhs_layout {
+    let (rhs_original_shape, rhs, rhs_is_scratch) = match rhs_layout {
         MatrixLayout::Contiguous => correct_rhs_layout(rhs),
         MatrixLayout::MildlyPermuted {
             transposed,
             batch_swap,
+            batch_broadcast,
         } => {
-            if transposed && !batch_swap {
+            if transposed && !batch_swap && !batch_broadcast {
                 let rhs_original_shape = rhs.shape.clone();
-                (rhs_original_shape, rhs)
+                (rhs_original_shape, rhs, false)
             } else {
                 correct_rhs_layout(rhs)
             }
@@ -143,67 +172,103 @@ pub fn launch<R: Runtime, E: Numeric>(
         MatrixLayout::HighlyPermuted => correct_rhs_layout(rhs),
     };
 
-    let cube_count = simple_cube_count(
+    let launches = simple_cube_launches::<R>(
+        client,
         &lhs.shape,
         &rhs_original_shape,
         out.shape,
         cube_dim_x,
         cube_dim_y,
-    )?;
+    )
+    .map_err(|error| {
+        let context =
+            MatmulLaunchContext::new(&lhs.shape, out.shape, E::as_elem_native_unchecked(), None);
+        error.with_context(context)
+    })?;
 
     let vectorization_factor = match lhs.shape[ndims - 1] % 4 == 0 {
         true => 4,
         false => 1,
     };
 
-    unsafe {
-        matmul_kernel::launch_unchecked::<E, R>(
-            client,
-            cube_count,
-            CubeDim::new(cube_dim_x as u32, cube_dim_y as u32, 1),
-            lhs.as_arg(vectorization_factor),
-            TensorArg::from_raw_parts::<E>(
-                &rhs.handle,
-                &rhs.strides,
-                &rhs_original_shape, // We need the original shape.
-                vectorization_factor,
-            ),
-            out.as_tensor_arg(1),
-            Some(ndims as u32 - 2),
-        );
-    };
+    for (batch_offset, cube_count) in launches {
+        unsafe {
+            matmul_kernel::launch_unchecked::<E, R>(
+                client,
+                cube_count,
+                CubeDim::new(cube_dim_x as u32, cube_dim_y as u32, 1),
+                lhs.as_arg(vectorization_factor),
+                TensorArg::from_raw_parts::<E>(
+                    &rhs.handle,
+                    &rhs.strides,
+                    &rhs_original_shape, // We need the original shape.
+                    vectorization_factor,
+                ),
+                out.as_tensor_arg(1),
+                ScalarArg::new(batch_offset),
+                Some(ndims as u32 - 2),
+            );
+        };
+    }
+
+    if lhs_is_scratch {
+        scratch.release(lhs.handle);
+    }
+    if rhs_is_scratch {
+        scratch.release(rhs.handle);
+    }
 
     Ok(())
 }
 
-fn simple_cube_count(
+/// Cube counts covering the output with one cube per `cube_dim_x` rows and `cube_dim_y` columns
+/// and one batch per cube along `z`, each with the index of its first batch.
+///
+/// Batches that don't fit in the maximum cube count of the device along `z` are spread over
+/// sequential launches, so that e.g. 70 000 small matrices still run on wgpu, limited to 65 535
+/// cubes per dimension. Only rows or columns exceeding the limits along `x` or `y` return
+/// [CubeCountTooBig](super::MatmulAvailabilityError::CubeCountTooBig). Empty outputs have no
+/// launch at all.
+fn simple_cube_launches<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
     lhs_shape: &[usize],
     rhs_shape: &[usize],
     output_shape: &[usize],
     cube_dim_x: usize,
     cube_dim_y: usize,
-) -> Result<CubeCount, MatmulLaunchError> {
+) -> Result<Vec<(u32, CubeCount)>, MatmulLaunchError> {
     let ndims = lhs_shape.len();
     let num_rows = lhs_shape[ndims - 2];
     let num_cols = rhs_shape[ndims - 1];
 
-    let cubes_x = f32::ceil(num_rows as f32 / cube_dim_x as f32) as u32;
-    let cubes_y = f32::ceil(num_cols as f32 / cube_dim_y as f32) as u32;
-    let mut num_iter = 1u32;
+    let cubes_x = num_rows.div_ceil(cube_dim_x);
+    let cubes_y = num_cols.div_ceil(cube_dim_y);
+    let num_batches = output_shape[..ndims - 2].iter().product::<usize>();
 
-    #[allow(clippy::needless_range_loop)]
-    for i in 0..ndims - 2 {
-        num_iter *= output_shape[i] as u32;
-    }
+    let max_cube_count = client.properties().hardware_properties().max_cube_count;
 
-    let result = CubeCount::Static(cubes_x, cubes_y, num_iter);
-    let max_cube_count = u16::MAX as u32;
+    if cubes_x > max_cube_count.x as usize || cubes_y > max_cube_count.y as usize {
+        let count = CubeCount::Static(cubes_x as u32, cubes_y as u32, num_batches as u32);
 
-    if cubes_x > max_cube_count || cubes_y > max_cube_count || num_iter > max_cube_count {
         return Err(MatmulLaunchError::Unavailable(
-            super::MatmulAvailabilityError::CubeCountTooBig(result),
+            super::MatmulAvailabilityError::CubeCountTooBig(count),
         ));
     }
 
-    Ok(result)
+    // An empty output needs no launch.
+    if cubes_x == 0 || cubes_y == 0 {
+        return Ok(Vec::new());
+    }
+
+    let max_batches = max_cube_count.z as usize;
+
+    Ok((0..num_batches)
+        .step_by(max_batches)
+        .map(|offset| {
+            let batches = max_batches.min(num_batches - offset);
+            let count = CubeCount::Static(cubes_x as u32, cubes_y as u32, batches as u32);
+
+            (offset as u32, count)
+        })
+        .collect())
 }
@@ -0,0 +1,103 @@
+//! Sparse CSR matrix times dense matrix
+//!
+//! Each cube handles a range of rows of the sparse matrix over a range of output columns, and each
+//! local unit computes a single element of the output by walking the non-zeros of its row.
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::{MatmulInvalidProblem, MatmulLaunchError};
+
+/// Rows of the sparse matrix handled by each cube.
+const ROWS_PER_CUBE: u32 = 8;
+/// Output columns handled by each cube.
+const COLS_PER_CUBE: u32 = 32;
+
+#[cube(launch_unchecked)]
+fn spmm_csr_kernel<N: Numeric>(
+    row_ptr: &Tensor<u32>,
+    col_idx: &Tensor<u32>,
+    values: &Tensor<N>,
+    dense: &Tensor<N>,
+    out: &mut Tensor<N>,
+) {
+    let row = CUBE_POS_Y * CUBE_DIM_Y + UNIT_POS_Y;
+    let col = CUBE_POS_X * CUBE_DIM_X + UNIT_POS_X;
+
+    if row >= out.shape(0) || col >= out.shape(1) {
+        terminate!();
+    }
+
+    let start = row_ptr[row];
+    let end = row_ptr[row + 1];
+    let mut acc = N::from_int(0);
+
+    for j in start..end {
+        let dense_index = col_idx[j] * dense.stride(0) + col * dense.stride(1);
+        acc += values[j] * dense[dense_index];
+    }
+
+    out[row * out.stride(0) + col * out.stride(1)] = acc;
+}
+
+/// Multiply a sparse `[m, k]` matrix in CSR format by a dense `[k, n]` matrix into a dense
+/// `[m, n]` output.
+///
+/// `row_ptr` holds the `m + 1` offsets delimiting the non-zeros of each row in `col_idx` and
+/// `values`, which hold the column and value of every non-zero. The three are contiguous, the
+/// offsets are non-decreasing and the column indices smaller than `k`, otherwise the result is
+/// undefined. Rows without non-zeros are written as zeros.
+pub fn spmm_csr<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    row_ptr: &TensorHandleRef<'_, R>,
+    col_idx: &TensorHandleRef<'_, R>,
+    values: &TensorHandleRef<'_, R>,
+    dense: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    let m = out.shape[0];
+    let n = out.shape[1];
+
+    if row_ptr.size() != m + 1 {
+        return Err(MatmulInvalidProblem::InvalidRowPtrLength {
+            len: row_ptr.size() as u32,
+            m: m as u32,
+        }
+        .into());
+    }
+    if col_idx.size() != values.size() {
+        return Err(MatmulInvalidProblem::MismatchedNonZeros {
+            col_idx: col_idx.size() as u32,
+            values: values.size() as u32,
+        }
+        .into());
+    }
+    if dense.shape[1] != n {
+        return Err(MatmulInvalidProblem::MismatchedN {
+            rhs_n: dense.shape[1] as u32,
+            out_n: n as u32,
+        }
+        .into());
+    }
+
+    let cube_dim = CubeDim::new_2d(COLS_PER_CUBE, ROWS_PER_CUBE);
+    let cube_count = CubeCount::Static(
+        (n as u32).div_ceil(COLS_PER_CUBE),
+        (m as u32).div_ceil(ROWS_PER_CUBE),
+        1,
+    );
+
+    unsafe {
+        spmm_csr_kernel::launch_unchecked::<N, R>(
+            client,
+            cube_count,
+            cube_dim,
+            row_ptr.as_tensor_arg(1),
+            col_idx.as_tensor_arg(1),
+            values.as_tensor_arg(1),
+            dense.as_tensor_arg(1),
+            out.as_tensor_arg(1),
+        );
+    };
+
+    Ok(())
+}
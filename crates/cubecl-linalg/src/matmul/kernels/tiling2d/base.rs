@@ -1,25 +1,54 @@
 use cubecl_core::prelude::*;
 use cubecl_core::{self as cubecl, CubeType};
+use cubecl_std::CubeOption;
 
-use super::{block_loop::block_loop, config::CubeTiling2dConfig};
+use super::{block_loop::block_loop, config::CubeTiling2dConfig, epilogue::CustomEpilogue};
 
 /// Most common tile size, the one used in most tests.
 pub(crate) const TILE_SIZE: usize = 4;
 
-#[cube(launch_unchecked)]
+/// Operands of `N` elements, accumulated as `A` and written to an output of `O` elements.
+#[cube(launch_unchecked, fast_math = config.float_mode.fast_math())]
 #[allow(unused_mut)]
-pub fn tiling2d_cube_kernel<N: Numeric>(
+pub fn tiling2d_cube_kernel<N: Numeric, A: Numeric, O: Numeric>(
     lhs: &Tensor<Line<N>>,
     rhs: &Tensor<Line<N>>,
-    out: &mut Tensor<Line<N>>,
+    out: &mut Tensor<Line<O>>,
     #[comptime] config: CubeTiling2dConfig,
+    #[comptime] epilogue: CustomEpilogue,
+    mask: CubeOption<Array<u32>>,
+    bias: CubeOption<Tensor<Line<O>>>,
+    scales: CubeOption<Tensor<Line<f32>>>,
+    alpha: A,
+    beta: O,
+    batch_offset: u32,
+    k_splits: u32,
+    k_per_split: u32,
+    split_stride: u32,
 ) {
     let dims = get_dims::<N>(lhs, rhs);
     let coordinates = calculate_coordinates(CUBE_POS_X, CUBE_POS_Y, UNIT_POS, config);
-    let offsets = calculate_batch_offsets::<N>(lhs, rhs, out, CUBE_POS_Z);
+
+    if comptime!(config.guard_cubes) {
+        // The whole cube exits together, so no unit is left waiting at a sync.
+        if coordinates.skip_row >= dims.m || coordinates.skip_col >= dims.n {
+            terminate!();
+        }
+    }
+
+    // The batches may be split across several launches, each one starting at `batch_offset`.
+    // With split-k, consecutive cubes along z compute the ranges of k of the same batch, each
+    // one writing its partial result to its own copy of the output, `split_stride` apart.
+    let cube_pos_z = CUBE_POS_Z + batch_offset;
+    let split = cube_pos_z % k_splits;
+    let mut offsets = calculate_batch_offsets::<N, O>(lhs, rhs, out, cube_pos_z / k_splits);
+    offsets.out += split * split_stride;
+
+    let k_start = split * k_per_split;
+    let k_end = Min::min(k_start + k_per_split, dims.k);
     let shared_memories = make_shared_memories::<N>(config);
 
-    block_loop::<N>(
+    block_loop::<N, A, O>(
         lhs,
         rhs,
         out,
@@ -28,6 +57,14 @@ pub fn tiling2d_cube_kernel<N: Numeric>(
         shared_memories,
         config,
         dims,
+        epilogue,
+        mask,
+        bias,
+        scales,
+        alpha,
+        beta,
+        k_start,
+        k_end,
     );
 }
 
@@ -106,10 +143,10 @@ fn calculate_coordinates(
 
 #[cube]
 #[allow(unused_mut)]
-fn calculate_batch_offsets<N: Numeric>(
+fn calculate_batch_offsets<N: Numeric, O: Numeric>(
     lhs: &Tensor<Line<N>>,
     rhs: &Tensor<Line<N>>,
-    out: &Tensor<Line<N>>,
+    out: &Tensor<Line<O>>,
     batch_number: u32,
 ) -> BatchOffsets {
     let rank = out.rank();
@@ -122,7 +159,8 @@ fn calculate_batch_offsets<N: Numeric>(
     let mut offset_lhs = 0;
     let mut offset_rhs = 0;
 
-    // Batch offset for lhs, rhs
+    // Batch offset for lhs, rhs. Broadcast dimensions, of size 1 or with a stride of 0, stay at
+    // the offset of their first batch.
     for b in 0..rank - 2 {
         let tmp = offset_out / out.stride(b);
         offset_lhs += tmp % lhs.shape(b) * lhs.stride(b);
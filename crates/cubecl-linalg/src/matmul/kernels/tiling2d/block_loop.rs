@@ -1,32 +1,42 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
+use cubecl_std::CubeOption;
 
 use super::{
     base::{BatchOffsets, Coordinates, Dimensions, SharedMemories},
     compute_loop::compute_loop,
     config::CubeTiling2dConfig,
+    epilogue::{CustomEpilogue, apply_epilogue},
     load_shared_memory::load_to_shared_memories,
     tile::{loader::TileLoader, writer::TileWriter},
     write_output::write_to_output,
 };
 
 #[cube]
-pub(crate) fn block_loop<N: Numeric>(
+pub(crate) fn block_loop<N: Numeric, A: Numeric, O: Numeric>(
     lhs: &Tensor<Line<N>>,
     rhs: &Tensor<Line<N>>,
-    out: &mut Tensor<Line<N>>,
+    out: &mut Tensor<Line<O>>,
     coordinates: Coordinates,
     offsets: BatchOffsets,
     shared: SharedMemories<N>,
     #[comptime] config: CubeTiling2dConfig,
     dims: Dimensions,
+    #[comptime] epilogue: CustomEpilogue,
+    mask: CubeOption<Array<u32>>,
+    bias: CubeOption<Tensor<Line<O>>>,
+    scales: CubeOption<Tensor<Line<f32>>>,
+    alpha: A,
+    beta: O,
+    k_start: u32,
+    k_end: u32,
 ) {
-    let mut results = init_results::<N>(config);
+    let mut results = init_results::<A>(config);
     let block_size_k = config.block_size_k;
-    let n_loops = (dims.k + block_size_k - 1) / block_size_k;
+    let n_loops = (k_end - k_start + block_size_k - 1) / block_size_k;
 
     for k in 0..n_loops {
-        let k = k * block_size_k;
+        let k = k_start + k * block_size_k;
 
         load_to_shared_memories::<N, TileLoader<N>>(
             lhs,
@@ -41,12 +51,24 @@ pub(crate) fn block_loop<N: Numeric>(
 
         sync_units();
 
-        compute_loop::<N>(coordinates, shared.lhs, shared.rhs, &mut results, config);
+        compute_loop::<N, A>(coordinates, shared.lhs, shared.rhs, &mut results, config);
 
         sync_units();
     }
 
-    write_to_output::<N, TileWriter<N>>(out, &results, coordinates, offsets.out, dims, config);
+    let output = apply_epilogue::<A, O>(
+        &mut results,
+        coordinates,
+        dims,
+        config,
+        epilogue,
+        mask,
+        bias,
+        scales,
+        alpha,
+    );
+
+    write_to_output::<O, TileWriter<O>>(out, &output, coordinates, offsets.out, dims, beta, config);
 }
 
 #[cube]
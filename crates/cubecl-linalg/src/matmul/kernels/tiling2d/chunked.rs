@@ -0,0 +1,164 @@
+use cubecl_core::{CubeElement, prelude::*};
+
+use crate::{
+    matmul::kernels::MatmulLaunchError,
+    tensor::{SizeError, TensorHandle},
+};
+
+use super::{config::Tiling2dConfig, launch::matmul_tiling_2d_ref};
+
+/// Matrix multiplication of host matrices whose operands don't fit in device memory together,
+/// using the tiling 2d algorithm on blocks of the output.
+///
+/// `lhs` is a row-major `[m, k]` matrix and `rhs` a row-major `[k, n]` matrix. The output is
+/// split into blocks of rows and columns, so that the lhs rows, rhs columns and output block of
+/// each launch fit in `max_device_bytes`. Partial blocks at the edges are smaller. The padding
+/// added by the allocator is not accounted for. The output is always a new matrix, so
+/// [Tiling2dConfig::accumulate] and [Tiling2dConfig::beta] are ignored.
+///
+/// Returns an error if the chunk sizes can't be computed without overflowing, e.g. for a huge
+/// `k`, or if a launch fails.
+///
+/// # Panics
+///
+/// If the shapes don't match the data, or if a single row and column don't fit in the budget.
+pub fn matmul_chunked<R: Runtime, N: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &[N],
+    lhs_shape: [usize; 2],
+    rhs: &[N],
+    rhs_shape: [usize; 2],
+    max_device_bytes: usize,
+    config: Tiling2dConfig,
+) -> Result<Vec<N>, MatmulLaunchError> {
+    let [m, k] = lhs_shape;
+    let [k_rhs, n] = rhs_shape;
+
+    assert_eq!(k, k_rhs, "Lhs and rhs should have the same k dimension");
+    assert_eq!(lhs.len(), m * k, "Lhs data should match its shape");
+    assert_eq!(rhs.len(), k * n, "Rhs data should match its shape");
+
+    let mut output = vec![N::from_int(0); m * n];
+    if output.is_empty() {
+        return Ok(output);
+    }
+
+    let (chunk_m, chunk_n) = chunk_sizes(m, k, n, max_device_bytes / N::size().unwrap())
+        .ok_or_else(|| SizeError::Overflow {
+            shape: vec![k, k],
+            elem: N::as_elem_native_unchecked(),
+        })?;
+    assert!(
+        chunk_m > 0 && chunk_n > 0,
+        "A budget of {max_device_bytes} bytes can't fit a single row and column of k={k}"
+    );
+
+    let config = Tiling2dConfig {
+        accumulate: false,
+        beta: 0.0,
+        ..config
+    };
+    let mut rhs_chunk = Vec::with_capacity(k * chunk_n);
+
+    for row_start in (0..m).step_by(chunk_m) {
+        let rows = chunk_m.min(m - row_start);
+        let lhs_device = TensorHandle::<R, N>::from_data(
+            client,
+            &lhs[row_start * k..(row_start + rows) * k],
+            &[rows, k],
+        );
+
+        for col_start in (0..n).step_by(chunk_n) {
+            let cols = chunk_n.min(n - col_start);
+
+            rhs_chunk.clear();
+            for row in rhs.chunks_exact(n) {
+                rhs_chunk.extend_from_slice(&row[col_start..col_start + cols]);
+            }
+            let rhs_device = TensorHandle::<R, N>::from_data(client, &rhs_chunk, &[k, cols]);
+            let out_device = TensorHandle::<R, N>::empty(client, vec![rows, cols]);
+
+            matmul_tiling_2d_ref::<R, N>(
+                client,
+                &lhs_device.as_ref(),
+                &rhs_device.as_ref(),
+                &out_device.as_ref(),
+                config.clone(),
+            )?;
+
+            let out_chunk = out_device.to_vec(client);
+            for (row, values) in out_chunk.chunks_exact(cols).enumerate() {
+                let start = (row_start + row) * n + col_start;
+                output[start..start + cols].copy_from_slice(values);
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Largest number of output rows and columns per launch so that
+/// `chunk_m * k + k * chunk_n + chunk_m * chunk_n` elements fit in `budget`.
+///
+/// The budget is split evenly between rows and columns, then what is left when one of the
+/// dimensions is small is given to the other. Returns `None` if `k * k + budget` overflows.
+fn chunk_sizes(m: usize, k: usize, n: usize, budget: usize) -> Option<(usize, usize)> {
+    // Solution of c^2 + 2kc = budget.
+    let discriminant = k.checked_mul(k)?.checked_add(budget)?;
+    let square = (discriminant as f64).sqrt() as usize - k;
+
+    let chunk_m = square.min(m);
+    let chunk_n = budget
+        .saturating_sub(chunk_m * k)
+        .checked_div(k + chunk_m)
+        .unwrap_or(0)
+        .min(n);
+
+    Some((chunk_m, chunk_n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fits(m: usize, k: usize, n: usize, budget: usize) -> bool {
+        m * k + k * n + m * n <= budget
+    }
+
+    #[test]
+    fn chunks_fit_in_budget() {
+        for (m, k, n, budget) in [(1000, 64, 1000, 10_000), (8192, 64, 8192, 1 << 20)] {
+            let (chunk_m, chunk_n) = chunk_sizes(m, k, n, budget).unwrap();
+
+            assert!(chunk_m > 0 && chunk_n > 0);
+            assert!(fits(chunk_m, k, chunk_n, budget));
+        }
+    }
+
+    #[test]
+    fn small_dimension_gives_budget_to_the_other() {
+        let (chunk_m, chunk_n) = chunk_sizes(4, 16, 10_000, 4096).unwrap();
+
+        assert_eq!(chunk_m, 4);
+        assert!(chunk_n > 64);
+        assert!(fits(chunk_m, 16, chunk_n, 4096));
+    }
+
+    #[test]
+    fn whole_problem_fits() {
+        assert_eq!(chunk_sizes(32, 32, 32, 1 << 20), Some((32, 32)));
+    }
+
+    #[test]
+    fn budget_too_small() {
+        let (_, chunk_n) = chunk_sizes(32, 1024, 32, 100).unwrap();
+
+        assert_eq!(chunk_n, 0);
+    }
+
+    #[test]
+    fn huge_k_overflows() {
+        assert_eq!(chunk_sizes(32, usize::MAX / 2, 32, 1 << 20), None);
+        assert_eq!(chunk_sizes(32, 1 << 16, 32, usize::MAX), None);
+    }
+}
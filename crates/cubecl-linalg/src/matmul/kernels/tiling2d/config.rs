@@ -1,8 +1,11 @@
 use cubecl_core::{
     self as cubecl, CubeDim,
-    prelude::{Init, Scope},
+    prelude::{CubePrimitive, FastMath, Init, Numeric, Scope},
 };
-use cubecl_core::{CubeCount, CubeType};
+use cubecl_core::{CubeCount, CubeType, Runtime, client::ComputeClient};
+use cubecl_runtime::memory_management::HardwareProperties;
+
+use crate::matmul::kernels::{MatmulAvailabilityError, MatmulInvalidProblem, check_cube_dim};
 
 use super::base::TILE_SIZE;
 
@@ -19,6 +22,54 @@ pub struct Tiling2dConfig {
     pub tile_size: usize,
     /// Loop unrolling
     pub unroll: bool,
+    /// Mask applied to the output during write-back
+    pub mask: MatmulMask,
+    /// Activation applied to the output during write-back
+    pub epilogue: MatmulEpilogue,
+    /// Factor multiplying the accumulated result before the epilogue and the write-back, e.g.
+    /// `1/sqrt(d_k)` for attention scores. It is converted to the accumulator type on launch.
+    pub alpha: f32,
+    /// How the number of cubes is derived from the output shape
+    pub cube_count_policy: CubeCountPolicy,
+    /// Handling of denormals and ordering of float operations
+    pub float_mode: FloatMode,
+    /// Maximum number of batches computed by a single launch. Larger batches are split into
+    /// sequential launches, which also happens when the batches exceed the maximum cube count of
+    /// the device along `z`. `None` only applies the device limit.
+    pub max_batches_per_launch: Option<usize>,
+    /// Add the result to the values already in the output instead of overwriting them, e.g. to
+    /// sum the products of several launches. The first launch should not accumulate, so that it
+    /// initializes the output.
+    ///
+    /// Each output element is read, summed and written back by the single unit computing it,
+    /// without atomics, so a launch must not run concurrently with another one writing the same
+    /// output. Launches on the same client are ordered, and float sums are then deterministic
+    /// for a given launch order, but they round differently when the launches are reordered.
+    /// The alpha, epilogue and mask apply to the result of the launch before it is added.
+    pub accumulate: bool,
+    /// Factor multiplying the values already in the output before the result is added to them,
+    /// as in `out = alpha * lhs @ rhs + beta * out`. It is converted to the output type on
+    /// launch.
+    ///
+    /// With the default of 0 the output is overwritten without being read, so values such as
+    /// NaN in the previous output don't propagate. Ignored when [accumulate](Self::accumulate)
+    /// is set, which is the same as a beta of 1.
+    pub beta: f32,
+    /// Line size of lhs, read along its contiguous axis: k, or m when it is transposed. `None`
+    /// selects the largest line size of the runtime dividing both that axis and the tile size.
+    ///
+    /// An override must be one of the line sizes of the runtime for the element type, and divide
+    /// the axis and the tile size, otherwise the launch fails with
+    /// [InvalidLineSizeLhs](crate::matmul::kernels::MatmulInvalidProblem::InvalidLineSizeLhs).
+    /// It is only used by the tiled kernel, the matrix-vector and small-matrix products reading
+    /// single elements, but it is validated whichever kernel computes the product.
+    pub lhs_line_size: Option<u8>,
+    /// Line size of rhs, read along n, or k when it is transposed, see
+    /// [lhs_line_size](Self::lhs_line_size).
+    pub rhs_line_size: Option<u8>,
+    /// Line size of the output, written along n, or m when it is transposed, see
+    /// [lhs_line_size](Self::lhs_line_size).
+    pub out_line_size: Option<u8>,
 }
 
 impl Default for Tiling2dConfig {
@@ -29,10 +80,210 @@ impl Default for Tiling2dConfig {
             block_size_n: 64,
             tile_size: TILE_SIZE,
             unroll: false,
+            mask: MatmulMask::None,
+            epilogue: MatmulEpilogue::None,
+            alpha: 1.0,
+            cube_count_policy: CubeCountPolicy::Exact,
+            float_mode: FloatMode::default(),
+            max_batches_per_launch: None,
+            accumulate: false,
+            beta: 0.0,
+            lhs_line_size: None,
+            rhs_line_size: None,
+            out_line_size: None,
         }
     }
 }
 
+impl Tiling2dConfig {
+    /// Whether the kernel reads the values already in the output, because of
+    /// [accumulate](Self::accumulate) or a non-zero [beta](Self::beta).
+    pub fn reads_output(&self) -> bool {
+        self.accumulate || self.beta != 0.0
+    }
+
+    /// The [alpha](Self::alpha) in the accumulator type `A` and the [beta](Self::beta) in the
+    /// output type `O`, the types of the values they multiply in the kernels.
+    pub(crate) fn scalars<A: Numeric, O: Numeric>(&self) -> Result<(A, O), MatmulInvalidProblem> {
+        let alpha = A::from(self.alpha).ok_or(MatmulInvalidProblem::UnrepresentableScalar {
+            value: self.alpha,
+            elem: A::as_elem_native_unchecked(),
+        })?;
+        let beta = O::from(self.beta).ok_or(MatmulInvalidProblem::UnrepresentableScalar {
+            value: self.beta,
+            elem: O::as_elem_native_unchecked(),
+        })?;
+
+        Ok((alpha, beta))
+    }
+
+    /// Whether the values read from the output are multiplied by [beta](Self::beta), which is
+    /// skipped when they are added as they are.
+    pub(crate) fn scales_existing(&self) -> bool {
+        !self.accumulate && self.beta != 0.0 && self.beta != 1.0
+    }
+
+    /// Check that the config can be launched on the device of `client`, with operands of
+    /// `elem_size` bytes, which is what the checked launchers do before launching anything.
+    ///
+    /// The block sizes must be non-zero multiples of the tile size, with the block size along k
+    /// at most those along m and n so that every unit loads part of the shared memory blocks.
+    /// These blocks must fit in the shared memory of the device, and the cube of
+    /// `(block_size_m / tile_size, block_size_n / tile_size)` units in its cube dim limits. A
+    /// [PadUp](CubeCountPolicy::PadUp) cube count policy must pad to a non-zero multiple.
+    pub fn validate<R: Runtime>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+        elem_size: usize,
+    ) -> Result<(), MatmulAvailabilityError> {
+        self.validate_on(client.properties().hardware_properties(), elem_size)
+    }
+
+    /// [validate](Self::validate) against the limits of a device.
+    pub(crate) fn validate_on(
+        &self,
+        hardware: &HardwareProperties,
+        elem_size: usize,
+    ) -> Result<(), MatmulAvailabilityError> {
+        let divisible = |block_size: usize| block_size > 0 && block_size % self.tile_size == 0;
+        if self.tile_size == 0
+            || !divisible(self.block_size_m)
+            || !divisible(self.block_size_k)
+            || !divisible(self.block_size_n)
+            || self.block_size_k > self.block_size_m
+            || self.block_size_k > self.block_size_n
+        {
+            return Err(MatmulAvailabilityError::InvalidBlockSizes {
+                block_size_m: self.block_size_m,
+                block_size_k: self.block_size_k,
+                block_size_n: self.block_size_n,
+                tile_size: self.tile_size,
+            });
+        }
+
+        if let CubeCountPolicy::PadUp { multiple: 0 } = self.cube_count_policy {
+            return Err(MatmulAvailabilityError::InvalidCubeCountMultiple);
+        }
+
+        // Block sizes large enough to overflow bust the limit as well.
+        let required = tiling2d_shared_memory_size(self, elem_size).unwrap_or(usize::MAX);
+        if required > hardware.max_shared_memory_size {
+            return Err(MatmulAvailabilityError::SharedMemoryLimitExceeded {
+                required,
+                max: hardware.max_shared_memory_size,
+                block_size_m: self.block_size_m,
+                block_size_k: self.block_size_k,
+                block_size_n: self.block_size_n,
+            });
+        }
+
+        let cube_dim = tiling2d_cube_dim(self);
+        if cube_dim.x > hardware.max_cube_dim.x || cube_dim.y > hardware.max_cube_dim.y {
+            return Err(MatmulAvailabilityError::CubeDimTooBig {
+                cube_dim,
+                max_units: hardware.max_units_per_cube,
+            });
+        }
+
+        check_cube_dim(cube_dim, hardware.max_units_per_cube)
+    }
+}
+
+#[derive(Default, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+/// Rounding of the number of cubes launched along the rows and columns of the output.
+pub enum CubeCountPolicy {
+    /// One cube per output block, the last ones only partially covering the output when the
+    /// block size doesn't divide it
+    #[default]
+    Exact,
+    /// The number of cubes along rows and along columns is rounded up to a multiple of `multiple`,
+    /// e.g. so that the grid fills whole waves of the device. Cubes past the output exit right
+    /// away without reading or writing anything.
+    PadUp { multiple: u32 },
+}
+
+#[derive(CubeType, Default, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+/// Triangular mask applied to each output matrix, e.g. for causal attention scores.
+///
+/// Masked elements are set to negative infinity for floats, and to the lowest value of the element
+/// type for integers.
+pub enum MatmulMask {
+    /// The output is left unchanged
+    #[default]
+    None,
+    /// Elements above the diagonal (`col > row`) are masked
+    CausalUpper,
+    /// Elements below the diagonal (`col < row`) are masked
+    CausalLower,
+}
+
+impl MatmulMask {
+    /// The same mask on the transposed output, where it masks the opposite triangle.
+    pub(crate) fn transposed(self) -> Self {
+        match self {
+            MatmulMask::None => MatmulMask::None,
+            MatmulMask::CausalUpper => MatmulMask::CausalLower,
+            MatmulMask::CausalLower => MatmulMask::CausalUpper,
+        }
+    }
+}
+
+#[derive(CubeType, Default, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+/// Element-wise activation applied to each output element, e.g. after the bias of a linear layer.
+///
+/// It is applied after the alpha and the bias, and before the masks, so masked elements keep their
+/// masked value. Activations other than ReLU and ReLU6 are evaluated in f32
+/// and converted back to the element type, so they are meant for float elements.
+pub enum MatmulEpilogue {
+    /// The output is left unchanged
+    #[default]
+    None,
+    /// `max(x, 0)`
+    Relu,
+    /// `min(max(x, 0), 6)`
+    Relu6,
+    /// `0.5 * x * (1 + tanh(sqrt(2 / pi) * (x + 0.044715 * x^3)))`, the tanh approximation of
+    /// GELU
+    Gelu,
+    /// `1 / (1 + exp(-x))`
+    Sigmoid,
+    /// `x / (1 + exp(-x))`, also known as swish
+    Silu,
+}
+
+#[derive(CubeType, Default, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+/// Float optimizations the backend compiler is allowed to make for the kernel.
+///
+/// The default is the precise mode: denormals are kept, NaNs propagate and float operations are
+/// evaluated in program order, so results only depend on the backend's arithmetic. Flags are
+/// ignored by backends that don't support them.
+///
+/// The tiling 2d kernel has no tree reduction: each output element is accumulated along k in
+/// increasing order by a single unit, so the accumulation order never depends on the config.
+/// Only [split-k](super::launch_split_k) changes it, by summing partial results over
+/// ranges of k, and matrix-vector products with planes, which sum the strided partial results
+/// of a row with a plane reduction.
+pub struct FloatMode {
+    /// Denormal values may be flushed to zero, e.g. CUDA `--ftz=true`
+    pub flush_denormals: bool,
+    /// The compiler may reorder float operations, changing the rounding of the result
+    pub allow_reassociation: bool,
+}
+
+impl FloatMode {
+    /// Fast math flags passed to the compiler for this mode.
+    pub fn fast_math(&self) -> FastMath {
+        let mut mode = FastMath::None;
+        if self.flush_denormals {
+            mode |= FastMath::FlushDenormals;
+        }
+        if self.allow_reassociation {
+            mode |= FastMath::AllowReassociation;
+        }
+        mode
+    }
+}
+
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
 /// Tiling 2D parameters
 pub struct CubeTiling2dConfig {
@@ -58,6 +309,24 @@ pub struct CubeTiling2dConfig {
     pub lhs_transposed: bool,
     /// Rhs is transposed in global memory
     pub rhs_transposed: bool,
+    /// Out is transposed in global memory
+    pub out_transposed: bool,
+    /// Mask applied to the output during write-back
+    pub mask: MatmulMask,
+    /// Activation applied to the output during write-back
+    pub epilogue: MatmulEpilogue,
+    /// The accumulated result is multiplied by the runtime alpha. Not set when alpha is 1, so the
+    /// output is left exactly unchanged in that case.
+    pub scale_output: bool,
+    /// The grid may hold cubes entirely outside the output, which must exit before doing any work
+    pub guard_cubes: bool,
+    /// Float optimizations allowed when compiling the kernel
+    pub float_mode: FloatMode,
+    /// The result is added to the output instead of overwriting it
+    pub accumulate: bool,
+    /// The output is multiplied by the runtime beta before the result is added to it. Not set
+    /// when beta is 1, so accumulating leaves the previous output exactly unchanged.
+    pub scale_existing: bool,
 }
 
 impl Init for CubeTiling2dConfig {
@@ -67,6 +336,7 @@ impl Init for CubeTiling2dConfig {
 }
 
 impl CubeTiling2dConfig {
+    /// Kernel config of a launch, for a `config` that passes [Tiling2dConfig::validate].
     pub fn new(
         config: &Tiling2dConfig,
         m: usize,
@@ -74,19 +344,8 @@ impl CubeTiling2dConfig {
         n: usize,
         lhs_transposed: bool,
         rhs_transposed: bool,
+        out_transposed: bool,
     ) -> Self {
-        assert!(
-            config.block_size_k <= config.block_size_m
-                && config.block_size_k <= config.block_size_n,
-            "Larger block size in k than m or n results in unfilled shared memory."
-        );
-        assert!(
-            config.block_size_m % config.tile_size == 0
-                && config.block_size_k % config.tile_size == 0
-                && config.block_size_n % config.tile_size == 0,
-            "Tiling 2d algorithm assumes tile size divides block size perfectly. "
-        );
-
         CubeTiling2dConfig {
             block_size_m: config.block_size_m as u32,
             block_size_k: config.block_size_k as u32,
@@ -99,17 +358,31 @@ impl CubeTiling2dConfig {
             tile_size: config.tile_size as u32,
             lhs_transposed,
             rhs_transposed,
+            out_transposed,
+            mask: config.mask,
+            epilogue: config.epilogue,
+            scale_output: config.alpha != 1.0,
+            guard_cubes: matches!(config.cube_count_policy, CubeCountPolicy::PadUp { .. }),
+            float_mode: config.float_mode,
+            accumulate: config.reads_output(),
+            scale_existing: config.scales_existing(),
         }
     }
 }
 
+/// The multiple of a [PadUp](CubeCountPolicy::PadUp) policy must be non-zero, which
+/// [Tiling2dConfig::validate] checks.
 pub fn tiling2d_cube_count(output_shape: &[usize], config: &Tiling2dConfig) -> CubeCount {
     let rank = output_shape.len();
     let num_rows = *output_shape.get(rank - 2).unwrap();
     let num_cols = *output_shape.get(rank - 1).unwrap();
 
-    let cubes_x = f32::ceil(num_rows as f32 / config.block_size_m as f32) as u32;
-    let cubes_y = f32::ceil(num_cols as f32 / config.block_size_n as f32) as u32;
+    let mut cubes_x = f32::ceil(num_rows as f32 / config.block_size_m as f32) as u32;
+    let mut cubes_y = f32::ceil(num_cols as f32 / config.block_size_n as f32) as u32;
+    if let CubeCountPolicy::PadUp { multiple } = config.cube_count_policy {
+        cubes_x = cubes_x.div_ceil(multiple) * multiple;
+        cubes_y = cubes_y.div_ceil(multiple) * multiple;
+    }
     let mut num_iter = 1;
     for shape in output_shape.iter().take(rank - 2) {
         num_iter *= shape;
@@ -118,6 +391,68 @@ pub fn tiling2d_cube_count(output_shape: &[usize], config: &Tiling2dConfig) -> C
     CubeCount::Static(cubes_x, cubes_y, num_iter as u32)
 }
 
+/// Split the batches covered by `cube_count` into launches of at most `max_batches` batches, with
+/// the index of the first batch of each launch.
+pub fn tiling2d_batch_launches(cube_count: CubeCount, max_batches: u32) -> Vec<(u32, CubeCount)> {
+    let CubeCount::Static(cubes_x, cubes_y, num_batches) = cube_count else {
+        unreachable!("The tiling 2d cube count is static")
+    };
+    let max_batches = max_batches.max(1);
+
+    (0..num_batches)
+        .step_by(max_batches as usize)
+        .map(|first| {
+            let batches = max_batches.min(num_batches - first);
+            (first, CubeCount::Static(cubes_x, cubes_y, batches))
+        })
+        .collect()
+}
+
+/// Partition of k into ranges computed by different cubes along `z`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct KSplit {
+    /// Number of ranges, each with its own cubes for every batch
+    pub splits: u32,
+    /// Length of every range but the last one, which may be shorter
+    pub k_per_split: u32,
+}
+
+impl KSplit {
+    /// A single range covering all of k.
+    pub fn none() -> Self {
+        Self {
+            splits: 1,
+            k_per_split: u32::MAX,
+        }
+    }
+
+    /// Split `k` into at most `splits` ranges whose length is a multiple of `block_size_k`, so
+    /// that only the last block of the last range may be partial. Fewer ranges are used when
+    /// more would leave some of them empty.
+    pub fn new(k: usize, splits: usize, block_size_k: usize) -> Self {
+        if k == 0 || splits <= 1 {
+            return Self::none();
+        }
+
+        let k_per_split = k.div_ceil(splits).div_ceil(block_size_k) * block_size_k;
+
+        Self {
+            splits: k.div_ceil(k_per_split) as u32,
+            k_per_split: k_per_split as u32,
+        }
+    }
+
+    /// Cube count launching every range of every batch of `cube_count`, the ranges of a batch
+    /// being consecutive along `z`.
+    pub fn cube_count(&self, cube_count: CubeCount) -> CubeCount {
+        let CubeCount::Static(cubes_x, cubes_y, num_batches) = cube_count else {
+            unreachable!("The tiling 2d cube count is static")
+        };
+
+        CubeCount::Static(cubes_x, cubes_y, num_batches * self.splits)
+    }
+}
+
 pub fn tiling2d_cube_dim(config: &Tiling2dConfig) -> CubeDim {
     CubeDim::new(
         (config.block_size_m / config.tile_size) as u32,
@@ -125,3 +460,174 @@ pub fn tiling2d_cube_dim(config: &Tiling2dConfig) -> CubeDim {
         1,
     )
 }
+
+/// Shared memory needed by the lhs and rhs blocks of `config`, in bytes, or `None` when it
+/// overflows.
+pub fn tiling2d_shared_memory_size(config: &Tiling2dConfig, elem_size: usize) -> Option<usize> {
+    config
+        .block_size_m
+        .checked_add(config.block_size_n)
+        .and_then(|block_size| block_size.checked_mul(config.block_size_k))
+        .and_then(|elems| elems.checked_mul(elem_size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launches(num_batches: u32, max_batches: u32) -> Vec<(u32, u32)> {
+        tiling2d_batch_launches(CubeCount::Static(2, 3, num_batches), max_batches)
+            .into_iter()
+            .map(|(first, cube_count)| match cube_count {
+                CubeCount::Static(2, 3, batches) => (first, batches),
+                _ => panic!("Only the batches should be split"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn batches_under_the_limit_use_one_launch() {
+        assert_eq!(launches(7, 65535), vec![(0, 7)]);
+    }
+
+    #[test]
+    fn batches_over_the_limit_are_split() {
+        assert_eq!(launches(10, 4), vec![(0, 4), (4, 4), (8, 2)]);
+    }
+
+    #[test]
+    fn k_split_ranges_are_multiples_of_block_size_k() {
+        let split = KSplit::new(4096, 3, 32);
+
+        assert_eq!(split.k_per_split, 1376);
+        assert_eq!(split.splits, 3);
+    }
+
+    #[test]
+    fn k_split_skips_empty_ranges() {
+        let split = KSplit::new(64, 4, 32);
+
+        assert_eq!(split.k_per_split, 32);
+        assert_eq!(split.splits, 2);
+    }
+
+    #[test]
+    fn single_k_split_covers_all_of_k() {
+        assert_eq!(KSplit::new(4096, 1, 32), KSplit::none());
+        assert_eq!(KSplit::new(0, 8, 32), KSplit::none());
+    }
+
+    fn hardware() -> HardwareProperties {
+        HardwareProperties {
+            plane_size_min: 32,
+            plane_size_max: 32,
+            max_bindings: 8,
+            max_shared_memory_size: 48 * 1024,
+            max_cube_count: CubeDim::new(65535, 65535, 65535),
+            max_units_per_cube: 1024,
+            max_cube_dim: CubeDim::new(1024, 1024, 64),
+        }
+    }
+
+    fn validate(
+        block_size_m: usize,
+        block_size_k: usize,
+        block_size_n: usize,
+        tile_size: usize,
+    ) -> Result<(), MatmulAvailabilityError> {
+        let config = Tiling2dConfig {
+            block_size_m,
+            block_size_k,
+            block_size_n,
+            tile_size,
+            ..Default::default()
+        };
+
+        config.validate_on(&hardware(), 4)
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        let config = Tiling2dConfig::default();
+
+        assert!(config.validate_on(&hardware(), 4).is_ok());
+    }
+
+    #[test]
+    fn block_sizes_must_be_multiples_of_the_tile_size() {
+        for (m, k, n, tile_size) in [(64, 32, 62, 4), (64, 32, 64, 0), (0, 0, 64, 4)] {
+            assert!(matches!(
+                validate(m, k, n, tile_size),
+                Err(MatmulAvailabilityError::InvalidBlockSizes { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn block_size_k_must_not_exceed_m_or_n() {
+        assert!(matches!(
+            validate(32, 64, 64, 4),
+            Err(MatmulAvailabilityError::InvalidBlockSizes { .. })
+        ));
+    }
+
+    #[test]
+    fn blocks_must_fit_in_shared_memory() {
+        assert!(matches!(
+            validate(128, 64, 128, 8),
+            Err(MatmulAvailabilityError::SharedMemoryLimitExceeded {
+                required: 65536,
+                max: 49152,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn cube_count_must_pad_to_a_non_zero_multiple() {
+        let config = Tiling2dConfig {
+            cube_count_policy: CubeCountPolicy::PadUp { multiple: 0 },
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate_on(&hardware(), 4),
+            Err(MatmulAvailabilityError::InvalidCubeCountMultiple)
+        ));
+    }
+
+    #[test]
+    fn scalars_are_converted_to_their_element_types() {
+        let config = Tiling2dConfig {
+            alpha: 0.5,
+            beta: 2.0,
+            ..Default::default()
+        };
+
+        assert_eq!(config.scalars::<f64, i32>().unwrap(), (0.5, 2));
+    }
+
+    #[test]
+    fn scalars_must_be_representable() {
+        let config = Tiling2dConfig {
+            alpha: 1e10,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.scalars::<i32, f32>(),
+            Err(MatmulInvalidProblem::UnrepresentableScalar { .. })
+        ));
+    }
+
+    #[test]
+    fn cube_must_fit_in_the_cube_dim_limits() {
+        assert!(matches!(
+            validate(256, 4, 256, 4),
+            Err(MatmulAvailabilityError::CubeDimTooBig {
+                max_units: 1024,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,471 @@
+use std::{fmt::Debug, hash::Hash, sync::Arc};
+
+use cubecl_core::ir::{Elem, ExpandElement, FloatKind, Instruction, Operation, Variable};
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, KernelId};
+use cubecl_std::{CubeOption, read_packed_bool};
+use half::{bf16, f16};
+
+use super::{
+    base::{Coordinates, Dimensions},
+    config::{CubeTiling2dConfig, MatmulEpilogue, MatmulMask, Tiling2dConfig},
+};
+
+/// Applies the element-wise operations that must happen on the accumulated results of a unit,
+/// right before they are written to the output, and converts them to the output type.
+///
+/// The alpha, bias, activation and custom epilogue are applied in the accumulator type `A`. The
+/// per-column scales, if any, are applied while converting, see [convert_scaled]. The masks are
+/// applied once converted, so that masked elements hold the [masked value](masked_value) of the
+/// output type.
+#[cube]
+pub(crate) fn apply_epilogue<A: Numeric, O: Numeric>(
+    results: &mut Array<A>,
+    coordinates: Coordinates,
+    dims: Dimensions,
+    #[comptime] config: CubeTiling2dConfig,
+    #[comptime] custom: CustomEpilogue,
+    mask: CubeOption<Array<u32>>,
+    bias: CubeOption<Tensor<Line<O>>>,
+    scales: CubeOption<Tensor<Line<f32>>>,
+    alpha: A,
+) -> Array<O> {
+    let row = coordinates.skip_row + coordinates.unit_row;
+    let col = coordinates.skip_col + coordinates.unit_col;
+
+    if comptime!(config.scale_output) {
+        let unroll = config.unroll_tile;
+
+        #[unroll(unroll)]
+        for i in 0..config.tile_size * config.tile_size {
+            results[i] *= alpha;
+        }
+    }
+
+    match bias {
+        CubeOption::Some(bias) => add_bias::<A, O>(results, &bias, col, dims, config),
+        CubeOption::None => {}
+    }
+
+    match config.epilogue {
+        MatmulEpilogue::None => {}
+        _ => {
+            let unroll = config.unroll_tile;
+
+            #[unroll(unroll)]
+            for i in 0..config.tile_size * config.tile_size {
+                results[i] = activation::<A>(results[i], config.epilogue);
+            }
+        }
+    }
+
+    if comptime!(custom.is_some()) {
+        let unroll = config.unroll_tile;
+
+        #[unroll(unroll)]
+        for i in 0..config.tile_size * config.tile_size {
+            results[i] = CustomEpilogue::apply::<A>(custom, results[i]);
+        }
+    }
+
+    let mut output = Array::<O>::new(config.tile_size * config.tile_size);
+
+    match scales {
+        CubeOption::Some(scales) => {
+            convert_scaled::<A, O>(results, &mut output, &scales, col, dims, config)
+        }
+        CubeOption::None => {
+            let unroll = config.unroll_tile;
+
+            #[unroll(unroll)]
+            for i in 0..config.tile_size * config.tile_size {
+                output[i] = O::cast_from(results[i]);
+            }
+        }
+    }
+
+    match mask {
+        CubeOption::Some(words) => mask_packed::<O>(&mut output, &words, row, col, dims, config),
+        CubeOption::None => {}
+    }
+
+    match config.mask {
+        MatmulMask::None => {}
+        MatmulMask::CausalUpper => mask_triangle::<O>(&mut output, row, col, true, config),
+        MatmulMask::CausalLower => mask_triangle::<O>(&mut output, row, col, false, config),
+    }
+
+    output
+}
+
+/// Adds the bias of each output column to the results.
+///
+/// The bias is read in lines of the output vectorization, which divides both the tile size and
+/// n, so a line is either entirely inside the output or entirely past its last column.
+#[cube]
+fn add_bias<A: Numeric, O: Numeric>(
+    results: &mut Array<A>,
+    bias: &Tensor<Line<O>>,
+    col: u32,
+    dims: Dimensions,
+    #[comptime] config: CubeTiling2dConfig,
+) {
+    let tile_size = config.tile_size;
+    let unroll = config.unroll_tile;
+    let line_size = bias.line_size();
+
+    #[unroll(unroll)]
+    for line_index in 0..comptime!(tile_size / line_size) {
+        let line_col = col + line_index * line_size;
+
+        if line_col < dims.n {
+            let line = bias[line_col / line_size];
+
+            #[unroll(unroll)]
+            for i in 0..tile_size {
+                #[unroll(unroll)]
+                for j in 0..line_size {
+                    results[i * tile_size + line_index * line_size + j] += A::cast_from(line[j]);
+                }
+            }
+        }
+    }
+}
+
+/// Converts the results to the output type, multiplied by the `f32` scale of their column, e.g. to
+/// requantize the `i32` accumulator of an integer matmul.
+///
+/// The product is computed in `f32` and rounded to the nearest integer when the output is an
+/// integer type. The scales are read in lines like the bias, see [add_bias].
+#[cube]
+fn convert_scaled<A: Numeric, O: Numeric>(
+    results: &Array<A>,
+    output: &mut Array<O>,
+    scales: &Tensor<Line<f32>>,
+    col: u32,
+    dims: Dimensions,
+    #[comptime] config: CubeTiling2dConfig,
+) {
+    let tile_size = config.tile_size;
+    let unroll = config.unroll_tile;
+    let line_size = scales.line_size();
+    let round = comptime!(O::as_elem_native_unchecked().is_int());
+
+    #[unroll(unroll)]
+    for line_index in 0..comptime!(tile_size / line_size) {
+        let line_col = col + line_index * line_size;
+
+        // Columns past n are never written, so they are left unset.
+        if line_col < dims.n {
+            let line = scales[line_col / line_size];
+
+            #[unroll(unroll)]
+            for i in 0..tile_size {
+                #[unroll(unroll)]
+                for j in 0..line_size {
+                    let index = i * tile_size + line_index * line_size + j;
+                    let value = f32::cast_from(results[index]) * line[j];
+
+                    if comptime!(round) {
+                        output[index] = O::cast_from(f32::round(value));
+                    } else {
+                        output[index] = O::cast_from(value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// How the kernels computing a single output element per unit write it: with the alpha, the
+/// activation and the beta of the config, but without masks or bias.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
+pub(super) struct ElementWrite {
+    /// Activation applied to the output during write-back
+    pub epilogue: MatmulEpilogue,
+    /// The result is multiplied by the runtime alpha
+    pub scale_output: bool,
+    /// The result is added to the output
+    pub accumulate: bool,
+    /// The output is multiplied by the runtime beta before the result is added to it
+    pub scale_existing: bool,
+}
+
+impl ElementWrite {
+    pub fn new(config: &Tiling2dConfig) -> Self {
+        Self {
+            epilogue: config.epilogue,
+            scale_output: config.alpha != 1.0,
+            accumulate: config.reads_output(),
+            scale_existing: config.scales_existing(),
+        }
+    }
+}
+
+/// Applies the alpha and the activation to the result, then writes it like the tiled kernel.
+#[cube]
+pub(super) fn write_element<A: Numeric, O: Numeric>(
+    out: &mut Tensor<O>,
+    index: u32,
+    value: A,
+    alpha: A,
+    beta: O,
+    #[comptime] write: ElementWrite,
+) {
+    let mut value = value;
+    if comptime!(write.scale_output) {
+        value *= alpha;
+    }
+    let output = O::cast_from(activation::<A>(value, write.epilogue));
+
+    if comptime!(write.scale_existing) {
+        out[index] = out[index] * beta + output;
+    } else if comptime!(write.accumulate) {
+        out[index] = out[index] + output;
+    } else {
+        out[index] = output;
+    }
+}
+
+/// Applies `epilogue` to a single result, in the element type `N`.
+///
+/// Activations other than ReLU and ReLU6 need a float type: they are evaluated in `N` for floats,
+/// and in f32 for integers, which have no float type of their own.
+#[cube]
+pub(crate) fn activation<N: Numeric>(value: N, #[comptime] epilogue: MatmulEpilogue) -> N {
+    let mut result = value;
+
+    match epilogue {
+        MatmulEpilogue::None => {}
+        MatmulEpilogue::Relu => {
+            result = Max::max(value, N::from_int(0));
+        }
+        MatmulEpilogue::Relu6 => {
+            result = Min::min(Max::max(value, N::from_int(0)), N::from_int(6));
+        }
+        _ => {
+            let elem = comptime!(N::as_elem_native_unchecked());
+
+            if comptime!(elem == Elem::Float(FloatKind::F16)) {
+                result = N::cast_from(float_activation::<f16>(f16::cast_from(value), epilogue));
+            } else if comptime!(elem == Elem::Float(FloatKind::BF16)) {
+                result = N::cast_from(float_activation::<bf16>(bf16::cast_from(value), epilogue));
+            } else if comptime!(elem == Elem::Float(FloatKind::F64)) {
+                result = N::cast_from(float_activation::<f64>(f64::cast_from(value), epilogue));
+            } else {
+                result = N::cast_from(float_activation::<f32>(f32::cast_from(value), epilogue));
+            }
+        }
+    }
+
+    result
+}
+
+/// The activations of [activation] evaluated in the float type `F`.
+///
+/// The GELU approximation factors `x` out of its cube, so that it doesn't overflow in f16 for
+/// values where the tanh is already saturated.
+#[cube]
+fn float_activation<F: Float>(x: F, #[comptime] epilogue: MatmulEpilogue) -> F {
+    let one = F::new(1.0);
+    let mut result = x;
+
+    match epilogue {
+        MatmulEpilogue::Gelu => {
+            // sqrt(2 / pi)
+            let inner = F::new(0.7978846) * x * (one + F::new(0.044715) * x * x);
+            result = F::new(0.5) * x * (one + F::tanh(inner));
+        }
+        MatmulEpilogue::Sigmoid => {
+            result = one / (one + F::exp(-x));
+        }
+        MatmulEpilogue::Silu => {
+            result = x / (one + F::exp(-x));
+        }
+        _ => {}
+    }
+
+    result
+}
+
+/// Applies `epilogue` to each element of a line, see [activation].
+#[cube]
+pub(crate) fn activation_line<N: Numeric>(
+    value: Line<N>,
+    #[comptime] epilogue: MatmulEpilogue,
+) -> Line<N> {
+    let mut result = value;
+
+    match epilogue {
+        MatmulEpilogue::None => {}
+        _ => {
+            for i in 0..value.size() {
+                result[i] = activation::<N>(value[i], epilogue);
+            }
+        }
+    }
+
+    result
+}
+
+/// Replaces the results that are strictly above (`upper`) or below the diagonal with the
+/// [masked value](masked_value) of the element type.
+///
+/// Positions are computed from the global output coordinates, so the mask is independent of the
+/// tiling and is the same for every batch.
+#[cube]
+fn mask_triangle<N: Numeric>(
+    results: &mut Array<N>,
+    row: u32,
+    col: u32,
+    #[comptime] upper: bool,
+    #[comptime] config: CubeTiling2dConfig,
+) {
+    let tile_size = config.tile_size;
+    let unroll = config.unroll_tile;
+
+    #[unroll(unroll)]
+    for i in 0..tile_size {
+        #[unroll(unroll)]
+        for j in 0..tile_size {
+            let position = i * tile_size + j;
+
+            if comptime!(upper) {
+                if col + j > row + i {
+                    results[position] = masked_value::<N>();
+                }
+            } else if col + j < row + i {
+                results[position] = masked_value::<N>();
+            }
+        }
+    }
+}
+
+/// Replaces the results whose bit is unset in the packed `mask` with the
+/// [masked value](masked_value) of the element type.
+///
+/// The mask holds one bit per output element of a single `[m, n]` matrix in row-major order, so
+/// the same mask applies to every batch. Positions outside of the output are skipped, since they
+/// are never written and have no bit in the mask.
+#[cube]
+fn mask_packed<N: Numeric>(
+    results: &mut Array<N>,
+    mask: &Array<u32>,
+    row: u32,
+    col: u32,
+    dims: Dimensions,
+    #[comptime] config: CubeTiling2dConfig,
+) {
+    let tile_size = config.tile_size;
+    let unroll = config.unroll_tile;
+
+    #[unroll(unroll)]
+    for i in 0..tile_size {
+        #[unroll(unroll)]
+        for j in 0..tile_size {
+            let mask_row = row + i;
+            let mask_col = col + j;
+
+            if mask_row < dims.m && mask_col < dims.n {
+                let keep = read_packed_bool::<Array<u32>>(mask, mask_row * dims.n + mask_col);
+                if !keep {
+                    results[i * tile_size + j] = masked_value::<N>();
+                }
+            }
+        }
+    }
+}
+
+/// The value of masked elements: negative infinity for floats, so that a softmax over the masked
+/// scores gives them no weight, and the lowest value of the type for integers.
+#[cube]
+pub(crate) fn masked_value<N: Numeric>() -> N {
+    let mut value = N::min_value();
+
+    if comptime!(matches!(N::as_elem_native_unchecked(), Elem::Float(_))) {
+        value = N::cast_from(f32::neg_infinity());
+    }
+
+    value
+}
+
+type EpilogueFn = dyn Fn(Variable, &mut Scope) -> Variable + Send + Sync;
+
+/// Element-wise expression injected in the write-back, see
+/// [matmul_tiling_2d_with_epilogue](super::launch::matmul_tiling_2d_with_epilogue).
+///
+/// Kernels are cached by the type of the closure and its key, so two epilogues of the same
+/// closure type and key are assumed to generate the same code.
+#[derive(Clone)]
+pub(crate) struct CustomEpilogue {
+    func: Option<Arc<EpilogueFn>>,
+    id: KernelId,
+}
+
+impl CustomEpilogue {
+    /// Leaves the output unchanged.
+    pub fn none() -> Self {
+        Self {
+            func: None,
+            id: KernelId::new::<()>(),
+        }
+    }
+
+    /// `key` holds the values captured by `func` that change the instructions it emits.
+    pub fn new<F, K>(key: K, func: F) -> Self
+    where
+        F: Fn(Variable, &mut Scope) -> Variable + Send + Sync + 'static,
+        K: Hash + Eq + Debug + Send + Sync + 'static,
+    {
+        Self {
+            func: Some(Arc::new(func)),
+            id: KernelId::new::<F>().info(key),
+        }
+    }
+
+    pub fn is_some(&self) -> bool {
+        self.func.is_some()
+    }
+
+    pub fn __expand_apply<N: Numeric>(
+        scope: &mut Scope,
+        epilogue: Self,
+        value: ExpandElementTyped<N>,
+    ) -> ExpandElementTyped<N> {
+        let Some(func) = epilogue.func else {
+            return value;
+        };
+
+        let value = ExpandElement::from(value);
+        let result = func(*value, scope);
+
+        // Copy into a local we own, since the variable returned by the closure may be released as
+        // soon as it returns.
+        let out = scope.create_local(value.item);
+        scope.register(Instruction::new(Operation::Copy(result), *out));
+
+        out.into()
+    }
+}
+
+impl PartialEq for CustomEpilogue {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for CustomEpilogue {}
+
+impl Hash for CustomEpilogue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Debug for CustomEpilogue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomEpilogue")
+            .field("id", &self.id)
+            .finish()
+    }
+}
@@ -0,0 +1,223 @@
+//! Matrix-vector products, computed instead of the tiled kernel when the output has a single row
+//! or column.
+//!
+//! The tiled kernel computes whole tiles of the output, so a `[m, 1]` output wastes all but one
+//! column of every tile and can't be vectorized. Here each output element is computed by a plane
+//! when the matrix is contiguous along k, its units reading consecutive elements of k before the
+//! partial sums are merged with a plane reduction. Otherwise, or without plane support, each unit
+//! computes one output element, so that consecutive units read consecutive rows of the matrix.
+use cubecl_core as cubecl;
+use cubecl_core::{Feature, prelude::*};
+
+use crate::matmul::kernels::MatmulLaunchError;
+
+use super::{
+    config::{FloatMode, MatmulMask, Tiling2dConfig, tiling2d_batch_launches},
+    epilogue::{ElementWrite, write_element},
+};
+
+/// Rows of the matrix reduced by each cube with plane reductions, one per plane.
+const PLANE_COUNT: u32 = 8;
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
+/// Matrix-vector parameters
+pub(super) struct GemvConfig {
+    /// The matrix is rhs, with a row per column of the output, and lhs the vector
+    pub lhs_is_vector: bool,
+    /// Each row is reduced by a plane instead of a single unit
+    pub plane_reduce: bool,
+    /// How the results are written to the output
+    pub write: ElementWrite,
+    /// Float optimizations allowed to the compiler
+    pub float_mode: FloatMode,
+}
+
+/// Computes `out = matrix * vector` for each batch, with a row of the matrix per output element.
+#[cube(launch_unchecked, fast_math = config.float_mode.fast_math())]
+fn gemv_kernel<N: Numeric, A: Numeric, O: Numeric>(
+    matrix: &Tensor<N>,
+    vector: &Tensor<N>,
+    out: &mut Tensor<O>,
+    alpha: A,
+    beta: O,
+    batch_offset: u32,
+    #[comptime] config: GemvConfig,
+) {
+    let rank = out.rank();
+
+    // Axes of the rows and of k in the matrix, and of k in the vector.
+    let mut row_axis = rank - 2;
+    let mut k_axis = rank - 1;
+    let mut vector_axis = rank - 2;
+    if comptime!(config.lhs_is_vector) {
+        row_axis = rank - 1;
+        k_axis = rank - 2;
+        vector_axis = rank - 1;
+    }
+
+    let num_rows = matrix.shape(row_axis);
+    let k = matrix.shape(k_axis);
+    let row_stride = matrix.stride(row_axis);
+    let k_stride = matrix.stride(k_axis);
+    let vector_stride = vector.stride(vector_axis);
+
+    // The output is contiguous with a single row or column, so a batch holds `num_rows` elements.
+    let out_offset = (CUBE_POS_Z + batch_offset) * num_rows;
+    let mut matrix_offset = 0;
+    let mut vector_offset = 0;
+    for b in 0..rank - 2 {
+        let tmp = out_offset / out.stride(b);
+        matrix_offset += tmp % matrix.shape(b) * matrix.stride(b);
+        vector_offset += tmp % vector.shape(b) * vector.stride(b);
+    }
+
+    if comptime!(config.plane_reduce) {
+        let row = CUBE_POS_X * CUBE_DIM_Y + UNIT_POS_Y;
+
+        // The whole plane takes the same branch, so the plane reduction stays uniform.
+        if row < num_rows {
+            let row_offset = matrix_offset + row * row_stride;
+            let mut sum = A::from_int(0);
+
+            let mut index = UNIT_POS_X;
+            while index < k {
+                sum += A::cast_from(matrix[row_offset + index * k_stride])
+                    * A::cast_from(vector[vector_offset + index * vector_stride]);
+                index += CUBE_DIM_X;
+            }
+
+            let sum = plane_sum(sum);
+            if UNIT_POS_X == 0 {
+                write_element::<A, O>(out, out_offset + row, sum, alpha, beta, config.write);
+            }
+        }
+    } else {
+        let row = CUBE_POS_X * CUBE_DIM + UNIT_POS;
+
+        if row < num_rows {
+            let row_offset = matrix_offset + row * row_stride;
+            let mut sum = A::from_int(0);
+
+            for index in 0..k {
+                sum += A::cast_from(matrix[row_offset + index * k_stride])
+                    * A::cast_from(vector[vector_offset + index * vector_stride]);
+            }
+
+            write_element::<A, O>(out, out_offset + row, sum, alpha, beta, config.write);
+        }
+    }
+}
+
+/// Launch parameters of a matrix-vector product, see [gemv_launch].
+pub(super) struct GemvLaunch {
+    config: GemvConfig,
+    cube_dim: CubeDim,
+    cubes: u32,
+}
+
+/// How to compute the product with the matrix-vector kernel, or `None` when the tiled kernel
+/// must be used: when neither m nor n is 1, with a triangular mask, or when the rows don't fit in
+/// the cube count of the device.
+///
+/// The operands must be at most mildly permuted, and the output contiguous.
+pub(super) fn gemv_launch<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    config: &Tiling2dConfig,
+) -> Option<GemvLaunch> {
+    let rank = lhs.shape.len();
+    let m = lhs.shape[rank - 2];
+    let n = rhs.shape[rank - 1];
+
+    if (m != 1 && n != 1) || config.mask != MatmulMask::None {
+        return None;
+    }
+
+    // A dot product, with m and n both 1, is computed as a single row of lhs.
+    let lhs_is_vector = n != 1;
+    let (num_rows, k_contiguous) = match lhs_is_vector {
+        true => (n, rhs.strides[rank - 2] == 1),
+        false => (m, lhs.strides[rank - 1] == 1),
+    };
+
+    let properties = client.properties();
+    let hardware = properties.hardware_properties();
+    let plane_reduce = k_contiguous
+        && properties.feature_enabled(Feature::Plane)
+        && hardware.plane_size_min == hardware.plane_size_max;
+
+    let cube_dim = match plane_reduce {
+        true => CubeDim::new_2d(hardware.plane_size_min, PLANE_COUNT),
+        false => CubeDim::default(),
+    };
+    let rows_per_cube = match plane_reduce {
+        true => cube_dim.y,
+        false => cube_dim.num_elems(),
+    };
+    let cubes = num_rows.div_ceil(rows_per_cube as usize);
+    if cubes > hardware.max_cube_count.x as usize {
+        return None;
+    }
+
+    Some(GemvLaunch {
+        config: GemvConfig {
+            lhs_is_vector,
+            plane_reduce,
+            write: ElementWrite::new(config),
+            float_mode: config.float_mode,
+        },
+        cube_dim,
+        cubes: cubes as u32,
+    })
+}
+
+/// # Safety
+///
+/// Same invariants as [matmul_tiling_2d_unchecked](super::launch::matmul_tiling_2d_unchecked),
+/// with an output of `O` elements, and a launch returned by [gemv_launch] for these operands.
+pub(super) unsafe fn launch_gemv<R: Runtime, N: Numeric, A: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: &Tiling2dConfig,
+    launch: GemvLaunch,
+) -> Result<(), MatmulLaunchError> {
+    let rank = out.shape.len();
+    let num_batches = out.shape[..rank - 2].iter().product::<usize>() as u32;
+
+    // Same batch limits as the tiled kernel.
+    let max_cube_count_z = client.properties().hardware_properties().max_cube_count.z;
+    let max_batches = match config.max_batches_per_launch {
+        Some(max_batches) => max_batches.min(max_cube_count_z as usize) as u32,
+        None => max_cube_count_z,
+    };
+    let cube_count = CubeCount::Static(launch.cubes, 1, num_batches);
+
+    let (alpha, beta) = config.scalars::<A, O>()?;
+
+    let (matrix, vector) = match launch.config.lhs_is_vector {
+        true => (rhs, lhs),
+        false => (lhs, rhs),
+    };
+
+    for (batch_offset, cube_count) in tiling2d_batch_launches(cube_count, max_batches) {
+        unsafe {
+            gemv_kernel::launch_unchecked::<N, A, O, R>(
+                client,
+                cube_count,
+                launch.cube_dim,
+                matrix.as_tensor_arg(1),
+                vector.as_tensor_arg(1),
+                out.as_tensor_arg(1),
+                ScalarArg::new(alpha),
+                ScalarArg::new(beta),
+                ScalarArg::new(batch_offset),
+                launch.config,
+            );
+        }
+    }
+
+    Ok(())
+}
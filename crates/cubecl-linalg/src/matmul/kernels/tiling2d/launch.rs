@@ -1,142 +1,1084 @@
-use std::cmp::max;
+use core::{cell::RefCell, fmt::Debug, hash::Hash};
 
+use cubecl_core::ir::Variable;
 use cubecl_core::prelude::*;
+use cubecl_core::server::Handle;
+use cubecl_std::CubeOptionArgs;
 
 use crate::{
-    matmul::kernels::tiling2d::{
-        base::tiling2d_cube_kernel,
-        config::{CubeTiling2dConfig, tiling2d_cube_count, tiling2d_cube_dim},
+    matmul::kernels::{
+        MatmulAvailabilityError, MatmulInvalidProblem, MatmulLaunchContext, MatmulLaunchError,
+        tiling2d::{
+            base::tiling2d_cube_kernel,
+            config::{
+                CubeTiling2dConfig, KSplit, tiling2d_batch_launches, tiling2d_cube_count,
+                tiling2d_cube_dim,
+            },
+            epilogue::CustomEpilogue,
+            gemv::{gemv_launch, launch_gemv},
+            micro::{launch_micro, micro_launch},
+            split_k::{launch_empty_k, launch_split_k},
+        },
+    },
+    tensor::{
+        BitTensor, ClientScratch, MatrixLayout, ScratchProvider, TensorHandle,
+        checked_tensor_bytes, into_contiguous_with_scratch, matrix_layout, strides_are_contiguous,
     },
-    tensor::{MatrixLayout, TensorHandle, into_contiguous, matrix_layout},
 };
 
 use super::config::Tiling2dConfig;
 
 /// Matrix multiplication using tiling 2d algorithm.
-pub fn matmul_tiling_2d<R: Runtime, F: Float>(
+pub fn matmul_tiling_2d<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, N>,
+    rhs: TensorHandle<R, N>,
+    out: TensorHandle<R, N>,
+    config: Tiling2dConfig,
+) -> Result<TensorHandle<R, N>, MatmulLaunchError> {
+    matmul_tiling_2d_ref::<R, N>(client, &lhs.as_ref(), &rhs.as_ref(), &out.as_ref(), config)?;
+
+    Ok(out)
+}
+
+/// Matrix multiplication using tiling 2d algorithm, allocating a contiguous output on the client.
+///
+/// The output has shape `[.., m, n]`, each batch dimension being the larger of those of the
+/// operands, which are broadcast when one of them is 1. Operands with a rank below 2, different ranks or a different k return
+/// [MatmulLaunchError::InvalidShape] without allocating anything.
+pub fn matmul_tiling_2d_alloc<R: Runtime, N: Numeric>(
     client: &ComputeClient<R::Server, R::Channel>,
-    lhs: TensorHandle<R, F>,
-    rhs: TensorHandle<R, F>,
-    out: TensorHandle<R, F>,
+    lhs: TensorHandle<R, N>,
+    rhs: TensorHandle<R, N>,
     config: Tiling2dConfig,
-) -> TensorHandle<R, F> {
-    matmul_tiling_2d_ref::<R, F>(client, &lhs.as_ref(), &rhs.as_ref(), &out.as_ref(), config);
+) -> Result<TensorHandle<R, N>, MatmulLaunchError> {
+    check_operand_shapes(&lhs.shape, &rhs.shape)?;
 
-    out
+    let rank = lhs.shape.len();
+    let mut out_shape = broadcast_batches(&lhs.shape, &rhs.shape)?;
+    out_shape.extend([lhs.shape[rank - 2], rhs.shape[rank - 1]]);
+    let out = TensorHandle::empty(client, out_shape);
+
+    matmul_tiling_2d::<R, N>(client, lhs, rhs, out, config)
 }
 
 /// Matrix multiplication using tiling 2d algorithm.
+///
+/// The operands have any rank of at least 2, all leading dimensions being batches, and the output
+/// is either contiguous or transposed. Matrix-vector and small products are computed by dedicated
+/// kernels, and empty problems don't launch the tiled kernel.
+///
+/// # Errors
+///
+/// Errors are wrapped in [MatmulLaunchError::WithContext], see [MatmulLaunchError::into_root].
+///
+/// - [MatmulLaunchError::InvalidShape] if the operands don't have the same rank of at least 2 and
+///   the same k.
+/// - [MatmulLaunchError::InvalidProblem] if their batches or the rank of the output don't match.
+/// - [MatmulLaunchError::OutputTooSmall] if the output buffer can't hold the result.
+/// - [MatmulLaunchError::InvalidLayout] if the output is neither contiguous nor transposed.
+/// - [MatmulAvailabilityError::TypesUnavailable] if the device doesn't support `N`.
+/// - [MatmulLaunchError::Unavailable] or [MatmulLaunchError::InvalidConfig] if the config doesn't
+///   fit the device or the problem.
 pub fn matmul_tiling_2d_ref<R: Runtime, N: Numeric>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: &TensorHandleRef<'_, R>,
     rhs: &TensorHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
     config: Tiling2dConfig,
-) {
-    assert!(
-        N::size().unwrap() * config.block_size_k * max(config.block_size_m, config.block_size_n)
-            <= client
-                .properties()
-                .hardware_properties()
-                .max_shared_memory_size,
-        "Shared memory limit will be busted. "
-    );
-    let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.strides) {
-        MatrixLayout::Contiguous => true,
-        MatrixLayout::MildlyPermuted {
-            transposed: _,
-            batch_swap: _,
-        } => true,
-        MatrixLayout::HighlyPermuted => false,
+) -> Result<(), MatmulLaunchError> {
+    matmul_tiling_2d_ref_with_scratch::<R, N, ClientScratch>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        &ClientScratch,
+    )
+}
+
+/// Matrix multiplication using tiling 2d algorithm, allocating temporary buffers with the given
+/// scratch provider.
+pub fn matmul_tiling_2d_ref_with_scratch<R: Runtime, N: Numeric, S: ScratchProvider<R>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+    scratch: &S,
+) -> Result<(), MatmulLaunchError> {
+    launch_checked::<R, N, N, N, S, S>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        scratch,
+        scratch,
+        FusedOperands::default(),
+        1,
+    )
+}
+
+/// Contiguous copies of the operands of [matmul_tiling_2d_ref_with_buffers], kept by the caller
+/// across launches.
+pub struct ContiguousBuffers<R: Runtime, N: Numeric> {
+    /// Contiguous copy of the last lhs that needed one.
+    pub lhs: Option<TensorHandle<R, N>>,
+    /// Contiguous copy of the last rhs that needed one.
+    pub rhs: Option<TensorHandle<R, N>>,
+}
+
+impl<R: Runtime, N: Numeric> Default for ContiguousBuffers<R, N> {
+    fn default() -> Self {
+        Self {
+            lhs: None,
+            rhs: None,
+        }
+    }
+}
+
+/// Matrix multiplication using tiling 2d algorithm, copying highly permuted operands into the
+/// given buffers.
+///
+/// Mildly permuted operands, e.g. transposed or with swapped or broadcast batch dimensions, are
+/// read in place through their strides and never need a buffer.
+///
+/// A buffer is reused when its shape matches the operand, otherwise a new one is allocated and
+/// replaces it, so repeated launches on operands of the same shape don't allocate in steady
+/// state. Operands that don't need a copy leave their buffer untouched.
+pub fn matmul_tiling_2d_ref_with_buffers<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+    buffers: &mut ContiguousBuffers<R, N>,
+) -> Result<(), MatmulLaunchError> {
+    let lhs_scratch = BufferScratch::new(&buffers.lhs, lhs.shape);
+    let rhs_scratch = BufferScratch::new(&buffers.rhs, rhs.shape);
+
+    launch_checked::<R, N, N, N, _, _>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        &lhs_scratch,
+        &rhs_scratch,
+        FusedOperands::default(),
+        1,
+    )?;
+
+    if let Some(handle) = lhs_scratch.allocated.into_inner() {
+        buffers.lhs = Some(TensorHandle::new_contiguous(lhs.shape.to_vec(), handle));
+    }
+    if let Some(handle) = rhs_scratch.allocated.into_inner() {
+        buffers.rhs = Some(TensorHandle::new_contiguous(rhs.shape.to_vec(), handle));
+    }
+
+    Ok(())
+}
+
+/// Hands out the handle of a caller's buffer when it has the shape of the operand, and keeps
+/// track of the buffer allocated otherwise.
+struct BufferScratch {
+    reused: Option<Handle>,
+    allocated: RefCell<Option<Handle>>,
+}
+
+impl BufferScratch {
+    fn new<R: Runtime, N: Numeric>(buffer: &Option<TensorHandle<R, N>>, shape: &[usize]) -> Self {
+        let reused = buffer
+            .as_ref()
+            .filter(|buffer| buffer.shape == shape)
+            .map(|buffer| buffer.handle.clone());
+
+        Self {
+            reused,
+            allocated: RefCell::new(None),
+        }
+    }
+}
+
+impl<R: Runtime> ScratchProvider<R> for BufferScratch {
+    fn alloc(&self, client: &ComputeClient<R::Server, R::Channel>, size: usize) -> Handle {
+        if let Some(handle) = &self.reused {
+            return handle.clone();
+        }
+
+        let handle = client.empty(size);
+        *self.allocated.borrow_mut() = Some(handle.clone());
+        handle
+    }
+}
+
+/// Matrix multiplication using tiling 2d algorithm, transforming each output element with
+/// `epilogue` before it is written.
+///
+/// The closure receives the accumulated element and the kernel scope, and returns the element to
+/// store. It runs once when the kernel is built, not per element at runtime: it emits the
+/// instructions of the expression in the scope. The element is scaled by the alpha and goes
+/// through the activation of the config before the closure, and the causal mask of the config is
+/// applied after it.
+///
+/// Compiled kernels are cached by the type of the closure and by `key`, which must hold every
+/// value captured by the closure that changes the instructions it emits, e.g. a constant or a
+/// flag. Closures without such captures can use `()`.
+pub fn matmul_tiling_2d_with_epilogue<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+    key: impl Hash + Eq + Debug + Send + Sync + 'static,
+    epilogue: impl Fn(Variable, &mut Scope) -> Variable + Send + Sync + 'static,
+) -> Result<(), MatmulLaunchError> {
+    launch_checked::<R, N, N, N, ClientScratch, ClientScratch>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        &ClientScratch,
+        &ClientScratch,
+        FusedOperands {
+            epilogue: CustomEpilogue::new(key, epilogue),
+            ..Default::default()
+        },
+        1,
+    )
+}
+
+/// Matrix multiplication using tiling 2d algorithm, replacing the output elements masked out by
+/// `mask` with negative infinity, or the lowest value of integer types, e.g. for attention scores.
+///
+/// The mask has one element per output element of a single `[m, n]` matrix, in row-major order,
+/// and is shared by every batch. Elements whose boolean is `false` are masked. It is applied after
+/// the triangular mask of the config, so both can be combined.
+pub fn matmul_tiling_2d_with_packed_mask<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+    mask: &BitTensor<R>,
+) -> Result<(), MatmulLaunchError> {
+    check_operand_shapes(lhs.shape, rhs.shape).map_err(|error| {
+        error.with_context(launch_context::<R, N>(lhs, out, Some(config.clone())))
+    })?;
+
+    let rank = lhs.shape.len();
+    let m = lhs.shape[rank - 2];
+    let n = rhs.shape[rank - 1];
+
+    if mask.num_elements() != m * n {
+        let error: MatmulLaunchError = MatmulInvalidProblem::InvalidMaskLength {
+            len: mask.num_elements() as u32,
+            m: m as u32,
+            n: n as u32,
+        }
+        .into();
+        return Err(error.with_context(launch_context::<R, N>(lhs, out, Some(config))));
+    }
+
+    launch_checked::<R, N, N, N, ClientScratch, ClientScratch>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        &ClientScratch,
+        &ClientScratch,
+        FusedOperands {
+            mask: Some(mask),
+            ..Default::default()
+        },
+        1,
+    )
+}
+
+/// Matrix multiplication using tiling 2d algorithm, adding `bias[col]` to every output element of
+/// column `col`, e.g. for the bias of a linear layer.
+///
+/// The bias is a contiguous tensor of n elements, usually of shape `[n]`, shared by every row and
+/// every batch. It is added after the alpha of the config and before the masks, and is read in
+/// lines of the output vectorization, so fusing it doesn't read or write the output one more
+/// time.
+pub fn matmul_tiling_2d_with_bias<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    bias: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+) -> Result<(), MatmulLaunchError> {
+    check_operand_shapes(lhs.shape, rhs.shape).map_err(|error| {
+        error.with_context(launch_context::<R, N>(lhs, out, Some(config.clone())))
+    })?;
+
+    let rank = rhs.shape.len();
+    let n = rhs.shape[rank - 1];
+
+    let error: Option<MatmulLaunchError> = if bias.size() != n {
+        Some(
+            MatmulInvalidProblem::InvalidBiasLength {
+                len: bias.size() as u32,
+                n: n as u32,
+            }
+            .into(),
+        )
+    } else if !strides_are_contiguous(bias.shape, bias.strides) {
+        Some(MatmulLaunchError::InvalidLayout {
+            strides: bias.strides.to_vec(),
+        })
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        return Err(error.with_context(launch_context::<R, N>(lhs, out, Some(config))));
+    }
+
+    launch_checked::<R, N, N, N, ClientScratch, ClientScratch>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        &ClientScratch,
+        &ClientScratch,
+        FusedOperands {
+            bias: Some(bias),
+            ..Default::default()
+        },
+        1,
+    )
+}
+
+/// Matrix multiplication using tiling 2d algorithm, with operands of `N` elements accumulated as
+/// `A` and written to an output of `O` elements, e.g. `f16` operands with an `f32` accumulator,
+/// or `i8` operands with an `i32` accumulator and output for quantized inference.
+///
+/// Global loads and shared memory stay in `N`, so the bandwidth and the shared memory of the
+/// launch are those of `N`: only the products and their sums are computed in `A`. The alpha and
+/// the activation of the config are applied in `A` as well, before converting to `O`, which can
+/// be either the operand type or the accumulator type, e.g. `f16` or `f32`.
+pub fn matmul_tiling_2d_mixed<R: Runtime, N: Numeric, A: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+) -> Result<(), MatmulLaunchError> {
+    launch_checked::<R, N, A, O, ClientScratch, ClientScratch>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        &ClientScratch,
+        &ClientScratch,
+        FusedOperands::default(),
+        1,
+    )
+}
+
+/// Quantized matrix multiplication using tiling 2d algorithm: integer operands of `N` elements are
+/// accumulated as `i32`, and the accumulator is requantized to an output of `O` elements with one
+/// `f32` scale per output column, `out[.., row, col] = round(acc[.., row, col] * scales[col])`.
+///
+/// The scales are a contiguous tensor of n elements, usually of shape `[n]`, shared by every row
+/// and every batch, e.g. the per-output-channel scales of a quantized linear layer. The value is
+/// only rounded when `O` is an integer type. The scales are applied when converting the
+/// accumulator to `O`, after the alpha and the activation of the config.
+pub fn matmul_tiling_2d_requantize<R: Runtime, N: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    scales: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+) -> Result<(), MatmulLaunchError> {
+    check_operand_shapes(lhs.shape, rhs.shape).map_err(|error| {
+        error.with_context(launch_context::<R, N>(lhs, out, Some(config.clone())))
+    })?;
+
+    let rank = rhs.shape.len();
+    let n = rhs.shape[rank - 1];
+
+    let error: Option<MatmulLaunchError> = if scales.size() != n {
+        Some(
+            MatmulInvalidProblem::InvalidScalesLength {
+                len: scales.size() as u32,
+                n: n as u32,
+            }
+            .into(),
+        )
+    } else if !strides_are_contiguous(scales.shape, scales.strides) {
+        Some(MatmulLaunchError::InvalidLayout {
+            strides: scales.strides.to_vec(),
+        })
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        return Err(error.with_context(launch_context::<R, N>(lhs, out, Some(config))));
+    }
+
+    launch_checked::<R, N, i32, O, ClientScratch, ClientScratch>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        &ClientScratch,
+        &ClientScratch,
+        FusedOperands {
+            scales: Some(scales),
+            ..Default::default()
+        },
+        1,
+    )
+}
+
+/// The operands applied by the epilogue of the tiled kernel on top of the config, none by
+/// default.
+pub(super) struct FusedOperands<'a, R: Runtime> {
+    pub epilogue: CustomEpilogue,
+    pub mask: Option<&'a BitTensor<R>>,
+    pub bias: Option<&'a TensorHandleRef<'a, R>>,
+    pub scales: Option<&'a TensorHandleRef<'a, R>>,
+}
+
+impl<R: Runtime> FusedOperands<'_, R> {
+    /// Whether nothing is fused, so that the untiled kernels can be used.
+    fn is_empty(&self) -> bool {
+        !self.epilogue.is_some()
+            && self.mask.is_none()
+            && self.bias.is_none()
+            && self.scales.is_none()
+    }
+}
+
+impl<R: Runtime> Default for FusedOperands<'_, R> {
+    fn default() -> Self {
+        Self {
+            epilogue: CustomEpilogue::none(),
+            mask: None,
+            bias: None,
+            scales: None,
+        }
+    }
+}
+
+/// Validate the launch, copy highly permuted operands and launch the kernel.
+///
+/// The operands hold `N` elements, which are accumulated as `A` and written to an output of `O`
+/// elements.
+///
+/// With more than one split of k, the fused operands must be empty: the partial results are
+/// reduced by [launch_split_k], which only applies the alpha, the activation, the triangular mask
+/// and the beta of the config.
+///
+/// Empty outputs, with m, n or a batch of 0, return without launching anything, and outputs with
+/// k = 0 are computed by [launch_empty_k] unless there are fused operands.
+///
+/// Errors carry the problem and the config of the launch, see [MatmulLaunchContext].
+#[allow(clippy::too_many_arguments)]
+pub(super) fn launch_checked<
+    R: Runtime,
+    N: Numeric,
+    A: Numeric,
+    O: Numeric,
+    SL: ScratchProvider<R>,
+    SR: ScratchProvider<R>,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+    lhs_scratch: &SL,
+    rhs_scratch: &SR,
+    fused: FusedOperands<'_, R>,
+    k_splits: usize,
+) -> Result<(), MatmulLaunchError> {
+    launch_checked_inner::<R, N, A, O, SL, SR>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config.clone(),
+        lhs_scratch,
+        rhs_scratch,
+        fused,
+        k_splits,
+    )
+    .map_err(|error| error.with_context(launch_context::<R, N>(lhs, out, Some(config))))
+}
+
+/// The problem of a launch with operands of `N` elements, for its errors.
+pub(crate) fn launch_context<R: Runtime, N: Numeric>(
+    lhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Option<Tiling2dConfig>,
+) -> MatmulLaunchContext {
+    MatmulLaunchContext::new(lhs.shape, out.shape, N::as_elem_native_unchecked(), config)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn launch_checked_inner<
+    R: Runtime,
+    N: Numeric,
+    A: Numeric,
+    O: Numeric,
+    SL: ScratchProvider<R>,
+    SR: ScratchProvider<R>,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+    lhs_scratch: &SL,
+    rhs_scratch: &SR,
+    fused: FusedOperands<'_, R>,
+    k_splits: usize,
+) -> Result<(), MatmulLaunchError> {
+    check_launch::<R, N, A, O>(client, lhs, rhs, out)?;
+
+    config.validate::<R>(client, N::size().unwrap())?;
+
+    // An empty output leaves nothing to compute, and launching no cube fails on some backends.
+    if out.shape.contains(&0) {
+        return Ok(());
+    }
+    // Without k, every product is 0 and the operands have nothing to read. The tiled kernel is
+    // kept for what only it applies, and doesn't load anything either.
+    let rank = lhs.shape.len();
+    if lhs.shape[rank - 1] == 0 && fused.is_empty() {
+        return unsafe { launch_empty_k::<R, A, O>(client, out, config) };
+    }
+    check_line_sizes::<R, N, O>(lhs, rhs, out, &config)?;
+
+    let check_layout =
+        |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.shape, tensor.strides) {
+            MatrixLayout::Contiguous => true,
+            MatrixLayout::MildlyPermuted {
+                transposed: _,
+                batch_swap: _,
+                batch_broadcast: _,
+            } => true,
+            MatrixLayout::HighlyPermuted => false,
+        };
+    let lhs_contiguous = (!check_layout(lhs))
+        .then(|| into_contiguous_with_scratch::<R, N, SL>(client, lhs, lhs_scratch));
+    let rhs_contiguous = (!check_layout(rhs))
+        .then(|| into_contiguous_with_scratch::<R, N, SR>(client, rhs, rhs_scratch));
+
+    let lhs_contiguous_ref = lhs_contiguous.as_ref().map(TensorHandle::as_ref);
+    let rhs_contiguous_ref = rhs_contiguous.as_ref().map(TensorHandle::as_ref);
+
+    let lhs = lhs_contiguous_ref.as_ref().unwrap_or(lhs);
+    let rhs = rhs_contiguous_ref.as_ref().unwrap_or(rhs);
+
+    // Matrix-vector and small products don't need tiles, unless the tiled kernel has to apply a
+    // custom epilogue, a packed mask, a bias or scales.
+    let untiled = k_splits <= 1 && fused.is_empty();
+    let gemv = match untiled {
+        true => gemv_launch(client, lhs, rhs, &config),
+        false => None,
+    };
+    let micro = match untiled && gemv.is_none() {
+        true => micro_launch(lhs, rhs, &config),
+        false => None,
     };
-    let lhs_correct_layout = check_layout(lhs);
-    let rhs_correct_layout = check_layout(rhs);
 
-    match (lhs_correct_layout, rhs_correct_layout) {
-        (true, true) => matmul_tiling_2d_ref_no_check::<R, N>(client, lhs, rhs, out, config),
-        (true, false) => matmul_tiling_2d_ref_no_check::<R, N>(
+    // Safety: the problem and the config were checked above and the operands are made at most
+    // mildly permuted.
+    let result = unsafe {
+        match (gemv, micro, k_splits) {
+            (Some(gemv), _, _) => launch_gemv::<R, N, A, O>(client, lhs, rhs, out, &config, gemv),
+            (None, Some(micro), _) => {
+                launch_micro::<R, N, A, O>(client, lhs, rhs, out, &config, micro)
+            }
+            (None, None, 0 | 1) => {
+                launch_kernel::<R, N, A, O>(client, lhs, rhs, out, config, fused, KSplit::none())
+            }
+            (None, None, _) => {
+                launch_split_k::<R, N, A, O>(client, lhs, rhs, out, config, k_splits)
+            }
+        }
+    };
+
+    if let Some(tensor) = lhs_contiguous {
+        lhs_scratch.release(tensor.handle);
+    }
+    if let Some(tensor) = rhs_contiguous {
+        rhs_scratch.release(tensor.handle);
+    }
+
+    result
+}
+
+/// Validate the line size overrides of the config for the operands as they are read, whichever
+/// kernel computes the product, since only the tiled kernel uses them.
+fn check_line_sizes<R: Runtime, N: Numeric, O: Numeric>(
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: &Tiling2dConfig,
+) -> Result<(), MatmulLaunchError> {
+    // Highly permuted operands are made contiguous before the launch.
+    let is_transposed =
+        |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.shape, tensor.strides) {
+            MatrixLayout::MildlyPermuted { transposed, .. } => transposed,
+            MatrixLayout::Contiguous | MatrixLayout::HighlyPermuted => false,
+        };
+
+    let rank = lhs.shape.len();
+    operand_line_sizes::<R, N, O>(
+        config,
+        lhs.shape[rank - 2],
+        lhs.shape[rank - 1],
+        rhs.shape[rank - 1],
+        is_transposed(lhs),
+        is_transposed(rhs),
+        check_output_layout(out)?,
+    )?;
+
+    Ok(())
+}
+
+/// Validate everything that doesn't depend on the config: the problem, the element types and
+/// the output layout.
+pub(crate) fn check_launch<R: Runtime, N: Numeric, A: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    check_problem::<R, O>(lhs, rhs, out)?;
+    check_types::<R, N, A, O>(client)?;
+    check_output_layout(out)?;
+
+    Ok(())
+}
+
+/// Make sure the device can represent every element type of the launch, since a kernel using an
+/// unsupported one only fails when its shader is compiled.
+///
+/// Floats other than f32, like f16, bf16 or f64, and integer types, 64-bit ones in particular,
+/// aren't available on every backend, e.g. wgpu without the matching shader extensions. The
+/// accumulator type is reported as the output when it is the missing one.
+fn check_types<R: Runtime, N: Numeric, A: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Result<(), MatmulLaunchError> {
+    if !N::is_supported(client) || !O::is_supported(client) {
+        return Err(MatmulAvailabilityError::TypesUnavailable {
+            input: N::as_elem_native_unchecked(),
+            output: O::as_elem_native_unchecked(),
+        }
+        .into());
+    }
+    if !A::is_supported(client) {
+        return Err(MatmulAvailabilityError::TypesUnavailable {
+            input: N::as_elem_native_unchecked(),
+            output: A::as_elem_native_unchecked(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Whether the output is transposed, i.e. a contiguous `[.., n, m]` tensor viewed as
+/// `[.., m, n]`, e.g. a column-major output. Other layouts than contiguous or transposed are
+/// rejected: the output is written tile by tile, and batch offsets are derived from its contiguous
+/// strides.
+pub(super) fn check_output_layout<R: Runtime>(
+    out: &TensorHandleRef<'_, R>,
+) -> Result<bool, MatmulLaunchError> {
+    if strides_are_contiguous(out.shape, out.strides) {
+        return Ok(false);
+    }
+
+    let rank = out.shape.len();
+    let mut shape = out.shape.to_vec();
+    let mut strides = out.strides.to_vec();
+    shape.swap(rank - 2, rank - 1);
+    strides.swap(rank - 2, rank - 1);
+
+    match strides_are_contiguous(&shape, &strides) {
+        true => Ok(true),
+        false => Err(MatmulLaunchError::InvalidLayout {
+            strides: out.strides.to_vec(),
+        }),
+    }
+}
+
+/// Make sure the operands agree on k and on their batches and that the output buffer of `O`
+/// elements can hold the whole result, since the kernel doesn't check its writes against the
+/// allocation.
+///
+/// Batch dimensions of size 1 are broadcast against the other operand, e.g. `[1, m, k]` times
+/// `[b, k, n]` gives `[b, m, n]`. Expanded views, with a stride of 0 along the broadcast
+/// dimension, are read in place as well.
+fn check_problem<R: Runtime, O: Numeric>(
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    check_operand_shapes(lhs.shape, rhs.shape)?;
+
+    let rank = lhs.shape.len();
+    if out.shape.len() != rank {
+        return Err(MatmulInvalidProblem::MismatchedRank {
+            lhs: rank as u32,
+            rhs: rhs.shape.len() as u32,
+            out: out.shape.len() as u32,
+        }
+        .into());
+    }
+
+    let m = lhs.shape[rank - 2];
+    let n = rhs.shape[rank - 1];
+
+    let mut out_shape = broadcast_batches(lhs.shape, rhs.shape)?;
+    out_shape.extend([m, n]);
+
+    let required = checked_tensor_bytes(&out_shape, O::as_elem_native_unchecked())? as u64;
+    let actual = out.handle.size();
+
+    if actual < required {
+        return Err(MatmulLaunchError::OutputTooSmall { required, actual });
+    }
+
+    Ok(())
+}
+
+/// Make sure the operands can be multiplied: both have the same rank of at least 2 and the same k,
+/// otherwise [MatmulLaunchError::InvalidShape] is returned with both shapes.
+pub(crate) fn check_operand_shapes(lhs: &[usize], rhs: &[usize]) -> Result<(), MatmulLaunchError> {
+    let rank = lhs.len();
+
+    if rank < 2 || rhs.len() != rank || lhs[rank - 1] != rhs[rank - 2] {
+        return Err(MatmulLaunchError::InvalidShape {
+            lhs: lhs.to_vec(),
+            rhs: rhs.to_vec(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Batch dimensions of the output, the larger of those of the operands, which have the same rank
+/// of at least 2. Only batches of size 1 are broadcast.
+fn broadcast_batches(lhs: &[usize], rhs: &[usize]) -> Result<Vec<usize>, MatmulLaunchError> {
+    let rank = lhs.len();
+    let mut batches = Vec::with_capacity(rank);
+
+    for dim in 0..rank - 2 {
+        let (lhs_batch, rhs_batch) = (lhs[dim], rhs[dim]);
+        if lhs_batch != rhs_batch && lhs_batch != 1 && rhs_batch != 1 {
+            return Err(MatmulInvalidProblem::MismatchedBatch {
+                dim: dim as u32,
+                lhs: lhs_batch as u32,
+                rhs: rhs_batch as u32,
+            }
+            .into());
+        }
+        // A batch of 0 broadcast against 1 stays empty.
+        batches.push(match lhs_batch {
+            1 => rhs_batch,
+            _ => lhs_batch,
+        });
+    }
+
+    Ok(batches)
+}
+
+/// Matrix multiplication using tiling 2d algorithm, launching the kernel directly without
+/// validating the problem or copying the operands.
+///
+/// Meant for hot paths where the shapes were already validated once, e.g. with
+/// [matmul_tiling_2d_ref] on the first iteration. The tiled kernel is always used, even for
+/// matrix-vector products. Only the layouts and the element type are
+/// checked: a highly permuted operand or an output neither contiguous nor transposed returns
+/// [MatmulLaunchError::InvalidLayout], and an element type the device doesn't support returns
+/// [MatmulAvailabilityError::TypesUnavailable].
+///
+/// # Safety
+///
+/// The caller must make sure that:
+/// - `lhs`, `rhs` and `out` have the same rank, at least 2, with shapes `[.., m, k]`,
+///   `[.., k, n]` and `[.., m, n]`, and batch dimensions of the operands that are either equal
+///   or 1, the output having the larger of the two.
+/// - The buffer of `out` can hold the whole result, since writes are not checked against it.
+/// - `config` passes [Tiling2dConfig::validate] on the device, e.g. its shared memory of
+///   `size_of::<N>() * block_size_k * (block_size_m + block_size_n)` bytes fits on the device.
+/// - The output doesn't alias the operands.
+pub unsafe fn matmul_tiling_2d_unchecked<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+) -> Result<(), MatmulLaunchError> {
+    let context = || launch_context::<R, N>(lhs, out, Some(config.clone()));
+    check_types::<R, N, N, N>(client).map_err(|error| error.with_context(context()))?;
+
+    unsafe {
+        launch_kernel::<R, N, N, N>(
             client,
             lhs,
-            &into_contiguous::<R, N>(client, rhs).as_ref(),
-            out,
-            config,
-        ),
-        (false, true) => matmul_tiling_2d_ref_no_check::<R, N>(
-            client,
-            &into_contiguous::<R, N>(client, lhs).as_ref(),
             rhs,
             out,
-            config,
-        ),
-        (false, false) => matmul_tiling_2d_ref_no_check::<R, N>(
-            client,
-            &into_contiguous::<R, N>(client, lhs).as_ref(),
-            &into_contiguous::<R, N>(client, rhs).as_ref(),
-            out,
-            config,
-        ),
+            config.clone(),
+            FusedOperands::default(),
+            KSplit::none(),
+        )
     }
+    .map_err(|error| error.with_context(context()))
 }
 
-/// Matrix multiplication using tiling 2d algorithm.
-fn matmul_tiling_2d_ref_no_check<R: Runtime, N: Numeric>(
+/// # Safety
+///
+/// Same invariants as [matmul_tiling_2d_unchecked], with an output of `O` elements. With more than
+/// one split of k, the buffer of `out` must hold one copy of the output per split, since each
+/// split writes its partial result to its own copy.
+pub(super) unsafe fn launch_kernel<R: Runtime, N: Numeric, A: Numeric, O: Numeric>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: &TensorHandleRef<'_, R>,
     rhs: &TensorHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
     config: Tiling2dConfig,
-) {
+    fused: FusedOperands<'_, R>,
+    split: KSplit,
+) -> Result<(), MatmulLaunchError> {
+    if out.shape.contains(&0) {
+        return Ok(());
+    }
+
     let rank = lhs.strides.len();
 
     let m = lhs.shape[rank - 2];
     let k = lhs.shape[rank - 1];
     let n = rhs.shape[rank - 1];
 
-    let check_layout = |strides: &[usize]| match matrix_layout(strides) {
-        MatrixLayout::Contiguous => false,
-        MatrixLayout::MildlyPermuted {
-            transposed,
-            batch_swap: _,
-        } => transposed,
-        MatrixLayout::HighlyPermuted => {
-            panic!("Can't run on highly permuted tensor")
+    let check_layout =
+        |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.shape, tensor.strides) {
+            MatrixLayout::Contiguous => Ok(false),
+            MatrixLayout::MildlyPermuted {
+                transposed,
+                batch_swap: _,
+                batch_broadcast: _,
+            } => Ok(transposed),
+            MatrixLayout::HighlyPermuted => Err(MatmulLaunchError::InvalidLayout {
+                strides: tensor.strides.to_vec(),
+            }),
+        };
+    let lhs_transposed = check_layout(lhs)?;
+    let rhs_transposed = check_layout(rhs)?;
+
+    let out_transposed = check_output_layout(out)?;
+
+    let cube_count = split.cube_count(tiling2d_cube_count(out.shape, &config));
+    let cube_dim = tiling2d_cube_dim(&config);
+    let cube_config = CubeTiling2dConfig::new(
+        &config,
+        m,
+        k,
+        n,
+        lhs_transposed,
+        rhs_transposed,
+        out_transposed,
+    );
+
+    let (lhs_vectorization, rhs_vectorization, out_vectorization) = operand_line_sizes::<R, N, O>(
+        &config,
+        m,
+        k,
+        n,
+        lhs_transposed,
+        rhs_transposed,
+        out_transposed,
+    )?;
+    // The bias always has one element per column, whatever the layout of the output.
+    let bias_vectorization = vectorization(
+        R::line_size_elem(&O::as_elem_native_unchecked()),
+        n,
+        config.tile_size,
+    );
+    let scales_vectorization = vectorization(
+        R::line_size_elem(&f32::as_elem_native_unchecked()),
+        n,
+        config.tile_size,
+    );
+
+    // Batches beyond the device limit along z are computed by sequential launches. The splits of
+    // k of a batch take one cube each along z, so a batch counts as many cubes as it has splits.
+    let max_cube_count_z = client.properties().hardware_properties().max_cube_count.z;
+    let max_batches = match config.max_batches_per_launch {
+        Some(max_batches) => max_batches
+            .saturating_mul(split.splits as usize)
+            .min(max_cube_count_z as usize) as u32,
+        None => max_cube_count_z,
+    };
+    let split_stride = out.shape.iter().product::<usize>() as u32;
+    let (alpha, beta) = config.scalars::<A, O>()?;
+
+    for (batch_offset, cube_count) in tiling2d_batch_launches(cube_count, max_batches) {
+        let mask = match fused.mask {
+            Some(mask) => CubeOptionArgs::Some(mask.as_array_arg()),
+            None => CubeOptionArgs::None,
+        };
+        let bias = match fused.bias {
+            Some(bias) => CubeOptionArgs::Some(bias.as_tensor_arg(bias_vectorization)),
+            None => CubeOptionArgs::None,
+        };
+        let scales = match fused.scales {
+            Some(scales) => CubeOptionArgs::Some(scales.as_tensor_arg(scales_vectorization)),
+            None => CubeOptionArgs::None,
+        };
+
+        unsafe {
+            tiling2d_cube_kernel::launch_unchecked::<N, A, O, R>(
+                client,
+                cube_count,
+                cube_dim,
+                TensorArg::from_raw_parts::<N>(
+                    lhs.handle,
+                    lhs.strides,
+                    lhs.shape,
+                    lhs_vectorization,
+                ),
+                TensorArg::from_raw_parts::<N>(
+                    rhs.handle,
+                    rhs.strides,
+                    rhs.shape,
+                    rhs_vectorization,
+                ),
+                TensorArg::from_raw_parts::<O>(
+                    out.handle,
+                    out.strides,
+                    out.shape,
+                    out_vectorization,
+                ),
+                cube_config,
+                fused.epilogue.clone(),
+                mask,
+                bias,
+                scales,
+                ScalarArg::new(alpha),
+                ScalarArg::new(beta),
+                ScalarArg::new(batch_offset),
+                ScalarArg::new(split.splits),
+                ScalarArg::new(split.k_per_split),
+                ScalarArg::new(split_stride),
+            );
         }
+    }
+
+    Ok(())
+}
+
+/// The line sizes of the lhs, rhs and output of a kernel launched with `config`, in this order.
+///
+/// Lines are limited to 16 bytes, so f64 operands are read 2 elements at a time.
+///
+/// An override of the config that isn't supported or doesn't divide its axis returns the matching
+/// invalid line size error.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn operand_line_sizes<R: Runtime, N: Numeric, O: Numeric>(
+    config: &Tiling2dConfig,
+    m: usize,
+    k: usize,
+    n: usize,
+    lhs_transposed: bool,
+    rhs_transposed: bool,
+    out_transposed: bool,
+) -> Result<(u8, u8, u8), MatmulInvalidProblem> {
+    // Lines must divide both the contiguous axis and the tiles they are read into, so lines of 8
+    // elements need a tile size of 8. Sizes are limited to the 16-byte loads of the runtime, e.g.
+    // 8 for f16 on CUDA but at most 4 for f32, and i8 lines of 4 elements are loaded as 32 bits.
+    let input_line_sizes = || R::line_size_elem(&N::as_elem_native_unchecked());
+    let output_line_sizes = || R::line_size_elem(&O::as_elem_native_unchecked());
+
+    // Each operand is vectorized along its contiguous axis: m or n when it is read as plain, k
+    // when it is read transposed. Transposed reads load whole lines along k and transpose them in
+    // registers, e.g. for a pre-transposed `[n, k]` weight as rhs.
+    let lhs_axis = match lhs_transposed {
+        true => m,
+        false => k,
     };
-    let lhs_transposed = check_layout(lhs.strides);
-    let rhs_transposed = check_layout(rhs.strides);
-
-    let vectorization = |shape: usize| {
-        [4, 2]
-            .into_iter()
-            .filter(|v| shape % v == 0)
-            .map(|v| v as u8)
-            .next()
-            .unwrap_or(1)
+    let rhs_axis = match rhs_transposed {
+        true => k,
+        false => n,
     };
-
-    let lhs_vectorization = match lhs_transposed {
-        true => vectorization(m),
-        false => 1,
+    // A transposed output is written along m.
+    let out_axis = match out_transposed {
+        true => m,
+        false => n,
     };
-    let rhs_vectorization = match rhs_transposed {
-        true => 1,
-        false => vectorization(n),
+
+    let lhs = line_size(
+        config.lhs_line_size,
+        input_line_sizes(),
+        lhs_axis,
+        config.tile_size,
+    )
+    .ok_or(MatmulInvalidProblem::InvalidLineSizeLhs {
+        size: lhs_axis as u32,
+        line_size: config.lhs_line_size.unwrap_or_default(),
+    })?;
+    let rhs = line_size(
+        config.rhs_line_size,
+        input_line_sizes(),
+        rhs_axis,
+        config.tile_size,
+    )
+    .ok_or(MatmulInvalidProblem::InvalidLineSizeRhs {
+        size: rhs_axis as u32,
+        line_size: config.rhs_line_size.unwrap_or_default(),
+    })?;
+    let out = line_size(
+        config.out_line_size,
+        output_line_sizes(),
+        out_axis,
+        config.tile_size,
+    )
+    .ok_or(MatmulInvalidProblem::InvalidLineSizeOut {
+        size: out_axis as u32,
+        line_size: config.out_line_size.unwrap_or_default(),
+    })?;
+
+    Ok((lhs, rhs, out))
+}
+
+/// The line size of an operand read along an axis of `shape` elements: the override of the config
+/// when it is one of `line_sizes`, or 1, and divides both `shape` and the tile size, `None` when
+/// it doesn't, and the [vectorization] of the axis without an override.
+fn line_size(
+    line_size: Option<u8>,
+    mut line_sizes: impl Iterator<Item = u8>,
+    shape: usize,
+    tile_size: usize,
+) -> Option<u8> {
+    let Some(line_size) = line_size else {
+        return Some(vectorization(line_sizes, shape, tile_size));
     };
-    let out_vectorization = vectorization(n);
 
-    let cube_count = tiling2d_cube_count(out.shape, &config);
-    let cube_dim = tiling2d_cube_dim(&config);
-    let cube_config = CubeTiling2dConfig::new(&config, m, k, n, lhs_transposed, rhs_transposed);
+    let supported = line_size == 1 || line_sizes.any(|v| v == line_size);
+    let divides = |size: usize| line_size > 0 && size % line_size as usize == 0;
 
-    unsafe {
-        tiling2d_cube_kernel::launch_unchecked::<N, R>(
-            client,
-            cube_count,
-            cube_dim,
-            TensorArg::from_raw_parts::<N>(lhs.handle, lhs.strides, lhs.shape, lhs_vectorization),
-            TensorArg::from_raw_parts::<N>(rhs.handle, rhs.strides, rhs.shape, rhs_vectorization),
-            TensorArg::from_raw_parts::<N>(out.handle, out.strides, out.shape, out_vectorization),
-            cube_config,
-        );
-    }
+    (supported && divides(shape) && divides(tile_size)).then_some(line_size)
+}
+
+/// Largest of `line_sizes` dividing both `shape` and the tile size, or 1 when none does.
+fn vectorization(line_sizes: impl Iterator<Item = u8>, shape: usize, tile_size: usize) -> u8 {
+    line_sizes
+        .filter(|v| shape % *v as usize == 0 && tile_size % *v as usize == 0)
+        .max()
+        .unwrap_or(1)
 }
@@ -0,0 +1,171 @@
+//! Products of small matrices, computed instead of the tiled kernel when m, n and k are all small
+//! compared to the tiles of the config.
+//!
+//! The tiled kernel stages blocks of the operands in shared memory and synchronizes the cube
+//! around every block, which costs more than the products themselves when a single block covers
+//! the whole problem. Here each unit computes one output element, reading its row of lhs and its
+//! column of rhs directly from global memory, without shared memory or synchronization.
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use crate::matmul::kernels::MatmulLaunchError;
+
+use super::{
+    config::{FloatMode, MatmulMask, Tiling2dConfig, tiling2d_batch_launches},
+    epilogue::{ElementWrite, write_element},
+    gemv::gemv_launch,
+};
+
+/// Number of tiles of the config along m, n and k up to which the problem is computed by the
+/// small-matrix kernel, e.g. 32 elements with the default tile size of 4.
+const MICRO_TILES: usize = 8;
+
+/// Output elements along each side of the square cubes of the small-matrix kernel.
+const MICRO_CUBE_SIZE: u32 = 16;
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
+/// Small-matrix parameters
+pub(super) struct MicroConfig {
+    /// How the results are written to the output
+    pub write: ElementWrite,
+    /// Float optimizations allowed to the compiler
+    pub float_mode: FloatMode,
+}
+
+/// Computes `out = lhs * rhs` for each batch, with an output element per unit reading its row and
+/// column from global memory, without shared memory.
+#[cube(launch_unchecked, fast_math = config.float_mode.fast_math())]
+fn micro_kernel<N: Numeric, A: Numeric, O: Numeric>(
+    lhs: &Tensor<N>,
+    rhs: &Tensor<N>,
+    out: &mut Tensor<O>,
+    alpha: A,
+    beta: O,
+    batch_offset: u32,
+    #[comptime] config: MicroConfig,
+) {
+    let rank = out.rank();
+
+    let m = lhs.shape(rank - 2);
+    let k = lhs.shape(rank - 1);
+    let n = rhs.shape(rank - 1);
+
+    let row = CUBE_POS_Y * CUBE_DIM_Y + UNIT_POS_Y;
+    let col = CUBE_POS_X * CUBE_DIM_X + UNIT_POS_X;
+
+    if row < m && col < n {
+        // The output is contiguous or transposed, so a batch holds `m * n` elements either way.
+        let out_offset = (CUBE_POS_Z + batch_offset) * m * n;
+        let mut lhs_offset = row * lhs.stride(rank - 2);
+        let mut rhs_offset = col * rhs.stride(rank - 1);
+        for b in 0..rank - 2 {
+            let tmp = out_offset / out.stride(b);
+            lhs_offset += tmp % lhs.shape(b) * lhs.stride(b);
+            rhs_offset += tmp % rhs.shape(b) * rhs.stride(b);
+        }
+
+        let lhs_stride = lhs.stride(rank - 1);
+        let rhs_stride = rhs.stride(rank - 2);
+        let mut sum = A::from_int(0);
+
+        for index in 0..k {
+            sum += A::cast_from(lhs[lhs_offset + index * lhs_stride])
+                * A::cast_from(rhs[rhs_offset + index * rhs_stride]);
+        }
+
+        let out_index = out_offset + row * out.stride(rank - 2) + col * out.stride(rank - 1);
+        write_element::<A, O>(out, out_index, sum, alpha, beta, config.write);
+    }
+}
+
+/// Launch parameters of a small-matrix product, see [micro_launch].
+pub(super) struct MicroLaunch {
+    config: MicroConfig,
+    cubes_x: u32,
+    cubes_y: u32,
+}
+
+/// How to compute the product with the small-matrix kernel, or `None` when the tiled kernel must
+/// be used: when m, n or k exceeds [MICRO_TILES] tiles of the config, or with a triangular mask.
+///
+/// The operands must be at most mildly permuted, and the output contiguous or transposed.
+pub(super) fn micro_launch<R: Runtime>(
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    config: &Tiling2dConfig,
+) -> Option<MicroLaunch> {
+    let rank = lhs.shape.len();
+    let m = lhs.shape[rank - 2];
+    let k = lhs.shape[rank - 1];
+    let n = rhs.shape[rank - 1];
+
+    let max_size = config.tile_size * MICRO_TILES;
+    if m > max_size || k > max_size || n > max_size || config.mask != MatmulMask::None {
+        return None;
+    }
+
+    Some(MicroLaunch {
+        config: MicroConfig {
+            write: ElementWrite::new(config),
+            float_mode: config.float_mode,
+        },
+        cubes_x: (n as u32).div_ceil(MICRO_CUBE_SIZE),
+        cubes_y: (m as u32).div_ceil(MICRO_CUBE_SIZE),
+    })
+}
+
+/// Whether a product without epilogue, mask, bias or splits of k is computed by the small-matrix
+/// kernel, i.e. it fits [micro_launch] and isn't a matrix-vector product.
+pub(crate) fn uses_micro_kernel<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    config: &Tiling2dConfig,
+) -> bool {
+    gemv_launch(client, lhs, rhs, config).is_none() && micro_launch(lhs, rhs, config).is_some()
+}
+
+/// # Safety
+///
+/// Same invariants as [matmul_tiling_2d_unchecked](super::launch::matmul_tiling_2d_unchecked),
+/// with an output of `O` elements, and a launch returned by [micro_launch] for these operands.
+pub(super) unsafe fn launch_micro<R: Runtime, N: Numeric, A: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: &Tiling2dConfig,
+    launch: MicroLaunch,
+) -> Result<(), MatmulLaunchError> {
+    let rank = out.shape.len();
+    let num_batches = out.shape[..rank - 2].iter().product::<usize>() as u32;
+
+    // Same batch limits as the tiled kernel.
+    let max_cube_count_z = client.properties().hardware_properties().max_cube_count.z;
+    let max_batches = match config.max_batches_per_launch {
+        Some(max_batches) => max_batches.min(max_cube_count_z as usize) as u32,
+        None => max_cube_count_z,
+    };
+    let cube_count = CubeCount::Static(launch.cubes_x, launch.cubes_y, num_batches);
+    let cube_dim = CubeDim::new_2d(MICRO_CUBE_SIZE, MICRO_CUBE_SIZE);
+    let (alpha, beta) = config.scalars::<A, O>()?;
+
+    for (batch_offset, cube_count) in tiling2d_batch_launches(cube_count, max_batches) {
+        unsafe {
+            micro_kernel::launch_unchecked::<N, A, O, R>(
+                client,
+                cube_count,
+                cube_dim,
+                lhs.as_tensor_arg(1),
+                rhs.as_tensor_arg(1),
+                out.as_tensor_arg(1),
+                ScalarArg::new(alpha),
+                ScalarArg::new(beta),
+                ScalarArg::new(batch_offset),
+                launch.config,
+            );
+        }
+    }
+
+    Ok(())
+}
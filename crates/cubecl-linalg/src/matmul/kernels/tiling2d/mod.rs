@@ -1,13 +1,38 @@
 mod base;
 mod block_loop;
+mod chunked;
 mod compute_loop;
 mod config;
+mod epilogue;
+mod gemv;
 mod launch;
 mod load_shared_memory;
+mod micro;
+mod multi_device;
 mod outer_product;
+mod split_k;
 mod tile;
+mod tune;
 mod write_output;
 
-pub use config::Tiling2dConfig;
+pub use chunked::matmul_chunked;
+pub use config::{CubeCountPolicy, FloatMode, MatmulEpilogue, MatmulMask, Tiling2dConfig};
+pub use launch::ContiguousBuffers;
 pub use launch::matmul_tiling_2d as launch;
+pub use launch::matmul_tiling_2d_alloc as launch_alloc;
+pub use launch::matmul_tiling_2d_mixed as launch_mixed;
 pub use launch::matmul_tiling_2d_ref as launch_ref;
+pub use launch::matmul_tiling_2d_ref_with_buffers as launch_ref_with_buffers;
+pub use launch::matmul_tiling_2d_ref_with_scratch as launch_ref_with_scratch;
+pub use launch::matmul_tiling_2d_requantize as launch_requantize;
+pub use launch::matmul_tiling_2d_unchecked as launch_unchecked;
+pub use launch::matmul_tiling_2d_with_bias as launch_with_bias;
+pub use launch::matmul_tiling_2d_with_epilogue as launch_with_epilogue;
+pub use launch::matmul_tiling_2d_with_packed_mask as launch_with_packed_mask;
+pub use multi_device::matmul_multi_device;
+pub use split_k::matmul_tiling_2d_split_k as launch_split_k;
+pub use tune::matmul_tiling_2d_autotune as launch_autotune;
+
+pub(crate) use epilogue::{activation, activation_line};
+pub(crate) use launch::{check_launch, check_operand_shapes, launch_context, operand_line_sizes};
+pub(crate) use micro::uses_micro_kernel;
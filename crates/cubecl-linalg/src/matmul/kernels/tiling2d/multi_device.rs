@@ -0,0 +1,158 @@
+use cubecl_core::{CubeElement, prelude::*};
+
+use crate::{
+    matmul::kernels::{MatmulLaunchContext, MatmulLaunchError},
+    tensor::TensorHandle,
+};
+
+use super::{
+    config::Tiling2dConfig,
+    launch::{check_operand_shapes, matmul_tiling_2d_ref},
+};
+
+/// Batched matrix multiplication of host matrices split across several devices, using the
+/// tiling 2d algorithm on each of them.
+///
+/// `lhs` is a row-major `[.., m, k]` tensor and `rhs` a row-major `[.., k, n]` tensor with the
+/// same batch dimensions, the result is written to `out` in row-major `[.., m, n]` order.
+///
+/// The batches are split in contiguous ranges, one per client. When the number of batches doesn't
+/// divide evenly, the remainder goes to the first client, and clients left without batches are
+/// not used. Every device receives its own copy of its operand slices, and all launches are
+/// queued before any result is read back, so the devices run concurrently. When the config
+/// accumulates or has a non-zero beta, the current values of `out` are uploaded as well and the
+/// results added to them.
+///
+/// # Panics
+///
+/// If no client is given, if the shapes don't match the data or if the batch dimensions differ.
+pub fn matmul_multi_device<R: Runtime, N: Numeric + CubeElement>(
+    clients: &[ComputeClient<R::Server, R::Channel>],
+    lhs: &[N],
+    lhs_shape: &[usize],
+    rhs: &[N],
+    rhs_shape: &[usize],
+    out: &mut [N],
+    config: Tiling2dConfig,
+) -> Result<(), MatmulLaunchError> {
+    assert!(!clients.is_empty(), "At least one client is needed");
+
+    let rank = lhs_shape.len();
+    assert!(rank >= 2, "Operands should have at least 2 dimensions");
+    assert_eq!(
+        rank,
+        rhs_shape.len(),
+        "Lhs and rhs should have the same rank"
+    );
+    assert_eq!(
+        lhs_shape[..rank - 2],
+        rhs_shape[..rank - 2],
+        "Lhs and rhs should have the same batch dimensions"
+    );
+
+    let [m, k] = [lhs_shape[rank - 2], lhs_shape[rank - 1]];
+    let n = rhs_shape[rank - 1];
+    let batch: usize = lhs_shape[..rank - 2].iter().product();
+
+    assert_eq!(lhs.len(), batch * m * k, "Lhs data should match its shape");
+    assert_eq!(rhs.len(), batch * k * n, "Rhs data should match its shape");
+
+    let context = || {
+        let out_shape = [&lhs_shape[..rank - 2], &[m, n]].concat();
+        MatmulLaunchContext::new(
+            lhs_shape,
+            &out_shape,
+            N::as_elem_native_unchecked(),
+            Some(config.clone()),
+        )
+    };
+
+    if let Err(error) = check_operand_shapes(lhs_shape, rhs_shape) {
+        return Err(error.with_context(context()));
+    }
+
+    let required = batch * m * n;
+    if out.len() < required {
+        let size = N::size().unwrap() as u64;
+        let error = MatmulLaunchError::OutputTooSmall {
+            required: required as u64 * size,
+            actual: out.len() as u64 * size,
+        };
+        return Err(error.with_context(context()));
+    }
+
+    let mut launched = Vec::with_capacity(clients.len());
+    let mut batch_start = 0;
+
+    for (client, batches) in clients.iter().zip(batch_split(batch, clients.len())) {
+        if batches == 0 {
+            continue;
+        }
+        let range = batch_start..batch_start + batches;
+        batch_start = range.end;
+
+        let lhs_device = TensorHandle::<R, N>::from_data(
+            client,
+            &lhs[range.start * m * k..range.end * m * k],
+            &[batches, m, k],
+        );
+        let rhs_device = TensorHandle::<R, N>::from_data(
+            client,
+            &rhs[range.start * k * n..range.end * k * n],
+            &[batches, k, n],
+        );
+        let out_shape = vec![batches, m, n];
+        let out_device = match config.reads_output() {
+            true => TensorHandle::<R, N>::from_data(
+                client,
+                &out[range.start * m * n..range.end * m * n],
+                &out_shape,
+            ),
+            false => TensorHandle::<R, N>::empty(client, out_shape),
+        };
+
+        matmul_tiling_2d_ref::<R, N>(
+            client,
+            &lhs_device.as_ref(),
+            &rhs_device.as_ref(),
+            &out_device.as_ref(),
+            config.clone(),
+        )?;
+
+        launched.push((client, range, out_device));
+    }
+
+    for (client, range, out_device) in launched {
+        let values = out_device.to_vec(client);
+        out[range.start * m * n..range.end * m * n].copy_from_slice(&values);
+    }
+
+    Ok(())
+}
+
+/// Number of batches given to each of `devices`, the remainder going to the first one.
+fn batch_split(batch: usize, devices: usize) -> Vec<usize> {
+    let mut split = vec![batch / devices; devices];
+    split[0] += batch % devices;
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn even_split() {
+        assert_eq!(batch_split(8, 4), vec![2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn remainder_goes_to_first_device() {
+        assert_eq!(batch_split(7, 3), vec![3, 2, 2]);
+    }
+
+    #[test]
+    fn fewer_batches_than_devices() {
+        assert_eq!(batch_split(1, 3), vec![1, 0, 0]);
+    }
+}
@@ -4,10 +4,10 @@ use cubecl_core::prelude::*;
 use super::config::CubeTiling2dConfig;
 
 #[cube]
-pub(crate) fn tile_outer_product<N: Numeric>(
+pub(crate) fn tile_outer_product<N: Numeric, A: Numeric>(
     register_m: Line<N>,
     register_n: Line<N>,
-    results: &mut Array<N>,
+    results: &mut Array<A>,
     #[comptime] config: CubeTiling2dConfig,
 ) {
     let tile_size = config.tile_size;
@@ -18,7 +18,7 @@ pub(crate) fn tile_outer_product<N: Numeric>(
         let res_pos_base = res_idx_m * tile_size;
         #[unroll(unroll)]
         for res_idx_n in 0..register_n.size() {
-            let mul: N = register_m[res_idx_m] * register_n[res_idx_n];
+            let mul: A = A::cast_from(register_m[res_idx_m]) * A::cast_from(register_n[res_idx_n]);
             results[res_pos_base + res_idx_n] += mul;
         }
     }
@@ -0,0 +1,306 @@
+use cubecl::{calculate_cube_count_elemwise, tensor_line_size_parallel};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_core::server::Handle;
+
+use crate::{
+    matmul::kernels::MatmulLaunchError,
+    tensor::{ClientScratch, checked_tensor_bytes, strides_are_contiguous},
+};
+
+use super::{
+    config::{KSplit, MatmulEpilogue, MatmulMask, Tiling2dConfig},
+    epilogue::{activation, masked_value},
+    launch::{FusedOperands, launch_checked, launch_kernel},
+};
+
+/// Sums the partial results of every split of k into `out`, then applies the alpha, the
+/// activation, the triangular mask, the beta and the accumulation of the config, in the same order
+/// as the single-pass kernel.
+///
+/// The partial results of split `s` are at `s * num_lines` in `workspace`, with the contiguous
+/// layout of `out`. Each unit reduces one line of the output. The sum, alpha and activation are
+/// computed in the accumulator type `A`, the masks once converted to the output type `O`. Without
+/// any split, the sum is 0 and the workspace isn't read.
+#[cube(launch_unchecked)]
+fn split_k_reduce_kernel<A: Numeric, O: Numeric>(
+    workspace: &Tensor<Line<A>>,
+    out: &mut Tensor<Line<O>>,
+    splits: u32,
+    alpha: A,
+    beta: O,
+    #[comptime] mask: MatmulMask,
+    #[comptime] epilogue: MatmulEpilogue,
+    #[comptime] scale_output: bool,
+    #[comptime] accumulate: bool,
+    #[comptime] scale_existing: bool,
+) {
+    let num_lines = out.len();
+    if ABSOLUTE_POS >= num_lines {
+        terminate!();
+    }
+
+    let line_size = out.line_size();
+
+    // Splits are summed in increasing order of k.
+    let mut sum = Line::empty(line_size).fill(A::from_int(0));
+    for split in 0..splits {
+        sum += workspace[split * num_lines + ABSOLUTE_POS];
+    }
+    if comptime!(scale_output) {
+        sum *= Line::empty(line_size).fill(alpha);
+    }
+
+    match epilogue {
+        MatmulEpilogue::None => {}
+        _ => {
+            for j in 0..line_size {
+                sum[j] = activation::<A>(sum[j], epilogue);
+            }
+        }
+    }
+
+    let mut output = Line::empty(line_size).fill(O::from_int(0));
+    for j in 0..line_size {
+        output[j] = O::cast_from(sum[j]);
+    }
+
+    // Lines run along n, so all the elements of a line are on the same row.
+    let rank = out.rank();
+    let first = ABSOLUTE_POS * line_size;
+    let row = (first / out.shape(rank - 1)) % out.shape(rank - 2);
+    let col = first % out.shape(rank - 1);
+
+    match mask {
+        MatmulMask::None => {}
+        MatmulMask::CausalUpper => {
+            for j in 0..line_size {
+                if col + j > row {
+                    output[j] = masked_value::<O>();
+                }
+            }
+        }
+        MatmulMask::CausalLower => {
+            for j in 0..line_size {
+                if col + j < row {
+                    output[j] = masked_value::<O>();
+                }
+            }
+        }
+    }
+
+    if comptime!(scale_existing) {
+        let beta = Line::empty(line_size).fill(beta);
+        out[ABSOLUTE_POS] = out[ABSOLUTE_POS] * beta + output;
+    } else if comptime!(accumulate) {
+        out[ABSOLUTE_POS] = out[ABSOLUTE_POS] + output;
+    } else {
+        out[ABSOLUTE_POS] = output;
+    }
+}
+
+/// Matrix multiplication using tiling 2d algorithm, with k split into up to `splits` ranges
+/// computed by different cubes.
+///
+/// Meant for small outputs with a large k, e.g. `[64, 64]` from `[64, 16384]` times `[16384, 64]`,
+/// where the single-pass kernel launches too few cubes to fill the device. Each range of k is
+/// computed like a full matmul into its own copy of the output, in a workspace of `splits` times
+/// the output allocated through the client, and a second kernel sums the copies into `out`.
+///
+/// Ranges are multiples of the block size along k, so fewer splits are used when k is too small
+/// for all of them, and a single one behaves exactly like [matmul_tiling_2d_ref]. The splits of a
+/// batch are launched along `z`, within the maximum cube count of the device: batches are spread
+/// over sequential launches otherwise.
+///
+/// The alpha, activation, triangular mask, beta and accumulation of the config apply to the sum
+/// of the partial results. Float results differ from the single-pass kernel only by the rounding
+/// of summing the partial results, which is deterministic for a given number of splits.
+///
+/// [matmul_tiling_2d_ref]: super::launch::matmul_tiling_2d_ref
+pub fn matmul_tiling_2d_split_k<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+    splits: usize,
+) -> Result<(), MatmulLaunchError> {
+    launch_checked::<R, N, N, N, ClientScratch, ClientScratch>(
+        client,
+        lhs,
+        rhs,
+        out,
+        config,
+        &ClientScratch,
+        &ClientScratch,
+        FusedOperands::default(),
+        splits,
+    )
+}
+
+/// The partial results are stored as `A` in the workspace, and converted to `O` by the reduction.
+///
+/// # Safety
+///
+/// Same invariants as [matmul_tiling_2d_unchecked](super::launch::matmul_tiling_2d_unchecked),
+/// with an output of `O` elements.
+pub(super) unsafe fn launch_split_k<R: Runtime, N: Numeric, A: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+    splits: usize,
+) -> Result<(), MatmulLaunchError> {
+    let rank = lhs.shape.len();
+    let k = lhs.shape[rank - 1];
+    let split = KSplit::new(k, splits, config.block_size_k);
+
+    if split.splits == 1 {
+        return unsafe {
+            launch_kernel::<R, N, A, O>(
+                client,
+                lhs,
+                rhs,
+                out,
+                config,
+                FusedOperands::default(),
+                split,
+            )
+        };
+    }
+
+    let acc_elem = A::as_elem_native_unchecked();
+    let num_elements = out.shape.iter().product::<usize>();
+    let workspace_shape = [split.splits as usize * num_elements];
+    let workspace = client.empty(checked_tensor_bytes(&workspace_shape, acc_elem)?);
+
+    // The partial results are plain products, everything else is applied once they are summed.
+    // They have the accumulator type, so the line size of the output doesn't apply to them.
+    let partial_config = Tiling2dConfig {
+        mask: MatmulMask::None,
+        epilogue: MatmulEpilogue::None,
+        alpha: 1.0,
+        accumulate: false,
+        beta: 0.0,
+        out_line_size: None,
+        ..config.clone()
+    };
+    let partials = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(&workspace, out.strides, out.shape, acc_elem.size())
+    };
+    unsafe {
+        launch_kernel::<R, N, A, A>(
+            client,
+            lhs,
+            rhs,
+            &partials,
+            partial_config,
+            FusedOperands::default(),
+            split,
+        )?;
+    }
+
+    unsafe { launch_reduce::<R, A, O>(client, &workspace, split.splits, out, &config) }
+}
+
+/// Computes an output with k = 0, where every product is 0 but the alpha, activation, mask, beta
+/// and accumulation of the config still apply, without reading the empty operands.
+///
+/// # Safety
+///
+/// `out` must be contiguous, with `O` elements.
+pub(super) unsafe fn launch_empty_k<R: Runtime, A: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    out: &TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+) -> Result<(), MatmulLaunchError> {
+    // Nothing is read from the workspace, which is still bound with the shape of a single split.
+    let size = checked_tensor_bytes(out.shape, A::as_elem_native_unchecked())?;
+    let workspace = client.empty(size);
+
+    unsafe { launch_reduce::<R, A, O>(client, &workspace, 0, out, &config) }
+}
+
+/// Sums the `splits` partial results in `workspace` into `out`, see [split_k_reduce_kernel].
+unsafe fn launch_reduce<R: Runtime, A: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    workspace: &Handle,
+    splits: u32,
+    out: &TensorHandleRef<'_, R>,
+    config: &Tiling2dConfig,
+) -> Result<(), MatmulLaunchError> {
+    let (alpha, beta) = config.scalars::<A, O>()?;
+    let (shape, strides, mask) = memory_order(out, config.mask);
+    let out_view = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(out.handle, &strides, &shape, O::size().unwrap())
+    };
+    let out = &out_view;
+
+    let line_size = reduce_line_size::<R, A, O>(out);
+    let num_elements = out.shape.iter().product::<usize>();
+    let num_lines = num_elements / line_size as usize;
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+    let workspace_shape = [splits.max(1) as usize * num_elements];
+    let workspace_strides = [1];
+
+    unsafe {
+        split_k_reduce_kernel::launch_unchecked::<A, O, R>(
+            client,
+            cube_count,
+            cube_dim,
+            TensorArg::from_raw_parts::<A>(
+                workspace,
+                &workspace_strides,
+                &workspace_shape,
+                line_size,
+            ),
+            out.as_tensor_arg(line_size),
+            ScalarArg::new(splits),
+            ScalarArg::new(alpha),
+            ScalarArg::new(beta),
+            mask,
+            config.epilogue,
+            config.alpha != 1.0,
+            config.reads_output(),
+            config.scales_existing(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Shape and strides of the output in memory order, with its mask. A transposed output is
+/// reduced as the contiguous `[.., n, m]` tensor it is in memory, where the mask applies to the
+/// opposite triangle, like its partial results written by the tiled kernel.
+fn memory_order<R: Runtime>(
+    out: &TensorHandleRef<'_, R>,
+    mask: MatmulMask,
+) -> (Vec<usize>, Vec<usize>, MatmulMask) {
+    let rank = out.shape.len();
+    let mut shape = out.shape.to_vec();
+    let mut strides = out.strides.to_vec();
+
+    match strides_are_contiguous(out.shape, out.strides) {
+        true => (shape, strides, mask),
+        false => {
+            shape.swap(rank - 2, rank - 1);
+            strides.swap(rank - 2, rank - 1);
+            (shape, strides, mask.transposed())
+        }
+    }
+}
+
+/// The workspace and the output are read with the same line size, limited by the larger type.
+fn reduce_line_size<R: Runtime, A: Numeric, O: Numeric>(out: &TensorHandleRef<'_, R>) -> u8 {
+    let acc_elem = A::as_elem_native_unchecked();
+    let out_elem = O::as_elem_native_unchecked();
+    let elem = match acc_elem.size() >= out_elem.size() {
+        true => acc_elem,
+        false => out_elem,
+    };
+    let rank = out.shape.len();
+
+    tensor_line_size_parallel(R::line_size_elem(&elem), out.shape, out.strides, rank - 1)
+}
@@ -30,7 +30,7 @@ pub(crate) trait BlockWriter<N: Numeric>: Send + Sync + 'static {
     fn write_output<A: ContiguousAccess<N>>(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        write_tile_info: WriteTileInfo,
+        write_tile_info: WriteTileInfo<N>,
         #[comptime] config: CubeTiling2dConfig,
         check_bounds: CheckBounds,
     );
@@ -72,3 +72,64 @@ pub(crate) fn all_zeros_comptime<N: Numeric>(
         shared_memory[sm_position] = zeros;
     }
 }
+
+/// Reads a tile whose rows lie along the contiguous axis of global memory one line at a time,
+/// then transposes it in registers so that each column becomes a line of shared memory.
+///
+/// With `check`, lines outside of the tensor are replaced by zeros. A line is either entirely in
+/// or out of bounds, since the line size divides the contiguous dimension.
+#[cube]
+pub(crate) fn load_tile_transposed_lines<N: Numeric>(
+    tensor: &Tensor<Line<N>>,
+    shared_memory: &mut SharedMemory<Line<N>>,
+    info: ReadTileInfo,
+    check_bounds: CheckBounds,
+    #[comptime] check: bool,
+    #[comptime] config: CubeTiling2dConfig,
+) {
+    let tile_size = config.tile_size;
+    let unroll = config.unroll_tile;
+    let line_size = tensor.line_size();
+    let row_base = check_bounds.skip_row + info.read_row;
+    let col_base = check_bounds.skip_col + info.read_col;
+
+    let mut tile = Array::<N>::new(comptime!(tile_size * tile_size));
+
+    #[unroll(unroll)]
+    for row in 0..tile_size {
+        let gm_position = (info.gm_position_base + row * info.gm_stride) / line_size;
+
+        #[unroll(unroll)]
+        for i in 0..comptime!(tile_size / line_size) {
+            let mut line = Line::empty(line_size).fill(N::from_int(0));
+
+            if comptime!(check) {
+                if row_base + row < check_bounds.dim_vertical
+                    && col_base + i * line_size < check_bounds.dim_horizontal
+                {
+                    line = tensor[gm_position + i];
+                }
+            } else {
+                line = tensor[gm_position + i];
+            }
+
+            #[unroll(unroll)]
+            for j in 0..comptime!(line_size) {
+                tile[(i * line_size + j) * tile_size + row] = line[j];
+            }
+        }
+    }
+
+    #[unroll(unroll)]
+    for col in 0..tile_size {
+        let mut vertical = Line::empty(tile_size);
+
+        #[unroll(unroll)]
+        for row in 0..tile_size {
+            vertical[row] = tile[col * tile_size + row];
+        }
+
+        let sm_position = (info.sm_position_base + col * info.sm_stride) / tile_size;
+        shared_memory[sm_position] = vertical;
+    }
+}
@@ -10,7 +10,9 @@ use crate::matmul::kernels::tiling2d::{
     write_output::WriteTileInfo,
 };
 
-use super::base::{BlockLoader, BlockWriter, all_zeros_comptime, all_zeros_runtime};
+use super::base::{
+    BlockLoader, BlockWriter, all_zeros_comptime, all_zeros_runtime, load_tile_transposed_lines,
+};
 
 pub(crate) struct HorizontalCheckBlockIO;
 
@@ -49,34 +51,40 @@ impl<N: Numeric> BlockLoader<N> for HorizontalCheckBlockIO {
         #[comptime] config: CubeTiling2dConfig,
         check_bounds: CheckBounds,
     ) {
-        let tile_size = config.tile_size;
+        let line_size = tensor.line_size();
 
-        let mut num_reads = 0;
-        let col = check_bounds.skip_col + info.read_col;
-        let dim_horizontal = check_bounds.dim_horizontal;
-        if dim_horizontal > col {
-            num_reads = Min::min(dim_horizontal - col, tile_size);
-        }
+        if comptime!(line_size == 1) {
+            let tile_size = config.tile_size;
+
+            let mut num_reads = 0;
+            let col = check_bounds.skip_col + info.read_col;
+            let dim_horizontal = check_bounds.dim_horizontal;
+            if dim_horizontal > col {
+                num_reads = Min::min(dim_horizontal - col, tile_size);
+            }
+
+            for i in 0..num_reads {
+                let gm_position = info.gm_position_base + i;
+                let sm_position = (info.sm_position_base + i * info.sm_stride) / tile_size;
 
-        for i in 0..num_reads {
-            let gm_position = info.gm_position_base + i;
-            let sm_position = (info.sm_position_base + i * info.sm_stride) / tile_size;
+                shared_memory[sm_position] = UnmatchingVectorization::read_strided_unchecked(
+                    tensor,
+                    gm_position,
+                    info.gm_stride,
+                    config,
+                );
+            }
 
-            shared_memory[sm_position] = UnmatchingVectorization::read_strided_unchecked(
-                tensor,
-                gm_position,
-                info.gm_stride,
+            all_zeros_runtime(
+                shared_memory,
+                num_reads,
+                info.sm_position_base,
+                info.sm_stride,
                 config,
             );
+        } else {
+            load_tile_transposed_lines(tensor, shared_memory, info, check_bounds, true, config);
         }
-
-        all_zeros_runtime(
-            shared_memory,
-            num_reads,
-            info.sm_position_base,
-            info.sm_stride,
-            config,
-        );
     }
 }
 
@@ -85,7 +93,7 @@ impl<N: Numeric> BlockWriter<N> for HorizontalCheckBlockIO {
     fn write_output<A: ContiguousAccess<N>>(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        info: WriteTileInfo,
+        info: WriteTileInfo<N>,
         #[comptime] config: CubeTiling2dConfig,
         check_bounds: CheckBounds,
     ) {
@@ -104,6 +112,7 @@ impl<N: Numeric> BlockWriter<N> for HorizontalCheckBlockIO {
                 let positions = WritePositions {
                     result: result_index * tile_size,
                     out: out_position_base + result_index * info.out_stride,
+                    beta: info.beta,
                 };
 
                 A::write_contiguous_checked(out, results, positions, check_bounds, col, config);
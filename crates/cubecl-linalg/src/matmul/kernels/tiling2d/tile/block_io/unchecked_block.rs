@@ -10,7 +10,7 @@ use crate::matmul::kernels::tiling2d::{
     write_output::WriteTileInfo,
 };
 
-use super::base::{BlockLoader, BlockWriter};
+use super::base::{BlockLoader, BlockWriter, load_tile_transposed_lines};
 
 /// Assumes block sizes divide tensor shape
 pub(crate) struct UncheckedBlockIO;
@@ -42,22 +42,27 @@ impl<N: Numeric> BlockLoader<N> for UncheckedBlockIO {
         shared_memory: &mut SharedMemory<Line<N>>,
         info: ReadTileInfo,
         #[comptime] config: CubeTiling2dConfig,
-        _check_bounds: CheckBounds,
+        check_bounds: CheckBounds,
     ) {
         let tile_size = config.tile_size;
         let unroll = config.unroll_tile;
+        let line_size = tensor.line_size();
 
-        #[unroll(unroll)]
-        for i in 0..tile_size {
-            let gm_position = info.gm_position_base + i;
-            let sm_position = (info.sm_position_base + i * info.sm_stride) / tile_size;
+        if comptime!(line_size == 1) {
+            #[unroll(unroll)]
+            for i in 0..tile_size {
+                let gm_position = info.gm_position_base + i;
+                let sm_position = (info.sm_position_base + i * info.sm_stride) / tile_size;
 
-            shared_memory[sm_position] = UnmatchingVectorization::read_strided_unchecked(
-                tensor,
-                gm_position,
-                info.gm_stride,
-                config,
-            );
+                shared_memory[sm_position] = UnmatchingVectorization::read_strided_unchecked(
+                    tensor,
+                    gm_position,
+                    info.gm_stride,
+                    config,
+                );
+            }
+        } else {
+            load_tile_transposed_lines(tensor, shared_memory, info, check_bounds, false, config);
         }
     }
 }
@@ -67,7 +72,7 @@ impl<N: Numeric> BlockWriter<N> for UncheckedBlockIO {
     fn write_output<A: ContiguousAccess<N>>(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        info: WriteTileInfo,
+        info: WriteTileInfo<N>,
         #[comptime] config: CubeTiling2dConfig,
         _check_bounds: CheckBounds,
     ) {
@@ -84,6 +89,7 @@ impl<N: Numeric> BlockWriter<N> for UncheckedBlockIO {
             let positions = WritePositions {
                 result: result_index * tile_size,
                 out: out_position_base + result_index * info.out_stride,
+                beta: info.beta,
             };
 
             A::write_contiguous_unchecked(out, results, positions, config);
@@ -10,7 +10,7 @@ use crate::matmul::kernels::tiling2d::{
     write_output::WriteTileInfo,
 };
 
-use super::base::{BlockLoader, BlockWriter, all_zeros_runtime};
+use super::base::{BlockLoader, BlockWriter, all_zeros_runtime, load_tile_transposed_lines};
 
 pub(crate) struct VerticalCheckBlockIO;
 
@@ -55,22 +55,28 @@ impl<N: Numeric> BlockLoader<N> for VerticalCheckBlockIO {
         #[comptime] config: CubeTiling2dConfig,
         check_bounds: CheckBounds,
     ) {
-        let tile_size = config.tile_size;
-        let unroll = config.unroll_tile;
-
-        #[unroll(unroll)]
-        for i in 0..tile_size {
-            let gm_position = info.gm_position_base + i;
-            let sm_position = (info.sm_position_base + i * info.sm_stride) / tile_size;
-
-            shared_memory[sm_position] = UnmatchingVectorization::read_strided_checked(
-                tensor,
-                gm_position,
-                info.gm_stride,
-                check_bounds,
-                info,
-                config,
-            );
+        let line_size = tensor.line_size();
+
+        if comptime!(line_size == 1) {
+            let tile_size = config.tile_size;
+            let unroll = config.unroll_tile;
+
+            #[unroll(unroll)]
+            for i in 0..tile_size {
+                let gm_position = info.gm_position_base + i;
+                let sm_position = (info.sm_position_base + i * info.sm_stride) / tile_size;
+
+                shared_memory[sm_position] = UnmatchingVectorization::read_strided_checked(
+                    tensor,
+                    gm_position,
+                    info.gm_stride,
+                    check_bounds,
+                    info,
+                    config,
+                );
+            }
+        } else {
+            load_tile_transposed_lines(tensor, shared_memory, info, check_bounds, true, config);
         }
     }
 }
@@ -80,7 +86,7 @@ impl<N: Numeric> BlockWriter<N> for VerticalCheckBlockIO {
     fn write_output<A: ContiguousAccess<N>>(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        info: WriteTileInfo,
+        info: WriteTileInfo<N>,
         #[comptime] config: CubeTiling2dConfig,
         check_bounds: CheckBounds,
     ) {
@@ -100,6 +106,7 @@ impl<N: Numeric> BlockWriter<N> for VerticalCheckBlockIO {
             let positions = WritePositions {
                 result: result_index * tile_size,
                 out: out_position_base + result_index * info.out_stride,
+                beta: info.beta,
             };
 
             A::write_contiguous_unchecked(out, results, positions, config);
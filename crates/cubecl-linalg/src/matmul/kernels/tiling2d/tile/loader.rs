@@ -12,8 +12,8 @@ use super::{
     memory_access::{MatchingVectorization, UnmatchingVectorization},
 };
 
-// Transposed tensor's vectorization must be 1
-// Plain tensor's vectorization must equal tile size
+// Plain tensor's vectorization must divide tile size
+// Transposed tensor's vectorization must divide both tile size and the contiguous dimension
 pub(crate) struct TileLoader<N: Numeric> {
     _f: PhantomData<N>,
 }
@@ -57,7 +57,7 @@ impl<N: Numeric> Loader<N> for TileLoader<N> {
         let load_indices = LoadIndices {
             offset: coordinates.skip_row + load_info.k * gm_stride + load_info.batch_offset,
             gm_stride,
-            sm_stride: config.block_size_n,
+            sm_stride: config.block_size_m,
         };
         let check_bounds = CheckBounds {
             dim_vertical: dims.k,
@@ -66,7 +66,14 @@ impl<N: Numeric> Loader<N> for TileLoader<N> {
             skip_col: coordinates.skip_row,
         };
 
-        load_plain::<N, B>(lhs, load_info, load_indices, check_bounds, config);
+        load_plain::<N, B>(
+            lhs,
+            load_info,
+            load_indices,
+            check_bounds,
+            config.block_size_m,
+            config,
+        );
     }
 
     fn load_lhs_transposed<B: BlockLoader<N>>(
@@ -90,7 +97,14 @@ impl<N: Numeric> Loader<N> for TileLoader<N> {
             skip_col: load_info.k,
         };
 
-        load_transposed::<N, B>(lhs, load_info, load_indices, check_bounds, config);
+        load_transposed::<N, B>(
+            lhs,
+            load_info,
+            load_indices,
+            check_bounds,
+            config.block_size_m,
+            config,
+        );
     }
 
     fn load_rhs_plain<B: BlockLoader<N>>(
@@ -114,7 +128,14 @@ impl<N: Numeric> Loader<N> for TileLoader<N> {
             skip_col: coordinates.skip_col,
         };
 
-        load_plain::<N, B>(rhs, load_info, load_indices, check_bounds, config);
+        load_plain::<N, B>(
+            rhs,
+            load_info,
+            load_indices,
+            check_bounds,
+            config.block_size_n,
+            config,
+        );
     }
 
     fn load_rhs_transposed<B: BlockLoader<N>>(
@@ -138,7 +159,14 @@ impl<N: Numeric> Loader<N> for TileLoader<N> {
             skip_col: load_info.k,
         };
 
-        load_transposed::<N, B>(rhs, load_info, load_indices, check_bounds, config);
+        load_transposed::<N, B>(
+            rhs,
+            load_info,
+            load_indices,
+            check_bounds,
+            config.block_size_n,
+            config,
+        );
     }
 }
 
@@ -148,18 +176,22 @@ pub(crate) fn load_plain<N: Numeric, L: BlockLoader<N>>(
     load_info: LoadInfo<N>,
     load_indices: LoadIndices,
     check_bounds: CheckBounds,
+    // block size of the dimension that is not k
+    #[comptime] block_size: u32,
     #[comptime] config: CubeTiling2dConfig,
 ) {
-    let coordinates = load_info.coordinates;
-
     let line_size = tensor.line_size();
     let tile_size = config.tile_size;
     let sm_dim_vertical = config.block_size_k;
 
-    let read_row = coordinates.unit_row;
-    let read_col = coordinates.unit_col;
-    let write_row = coordinates.unit_row;
-    let write_col = coordinates.unit_col;
+    // Units are spread over rows of `block_size` elements, which may differ from the layout of the
+    // units in the output when block sizes in m and n are not equal.
+    let tiles_per_row = block_size / tile_size;
+
+    let read_row = (UNIT_POS / tiles_per_row) * tile_size;
+    let read_col = (UNIT_POS % tiles_per_row) * tile_size;
+    let write_row = read_row;
+    let write_col = read_col;
 
     let gm_position_base = read_row * load_indices.gm_stride + read_col + load_indices.offset;
     let sm_position_base = write_row * load_indices.sm_stride + write_col;
@@ -201,16 +233,22 @@ pub(crate) fn load_transposed<N: Numeric, L: BlockLoader<N>>(
     load_info: LoadInfo<N>,
     load_indices: LoadIndices,
     check_bounds: CheckBounds,
+    // block size of the dimension that is not k
+    #[comptime] block_size: u32,
     #[comptime] config: CubeTiling2dConfig,
 ) {
-    let coordinates = load_info.coordinates;
-
+    let tile_size = config.tile_size;
     let sm_dim_vertical = config.block_size_k;
 
-    let read_row = coordinates.unit_row;
-    let read_col = coordinates.unit_col;
-    let write_row = coordinates.unit_col;
-    let write_col = coordinates.unit_row;
+    // Units are spread over `block_size` rows, so that every row of the block is read even when
+    // block sizes in m and n are not equal.
+    let num_units = (config.block_size_m / tile_size) * (config.block_size_n / tile_size);
+    let tiles_per_row = num_units / (block_size / tile_size);
+
+    let read_row = (UNIT_POS / tiles_per_row) * tile_size;
+    let read_col = (UNIT_POS % tiles_per_row) * tile_size;
+    let write_row = read_col;
+    let write_col = read_row;
 
     let gm_position_base = read_row * load_indices.gm_stride + read_col + load_indices.offset;
     let sm_position_base = write_row * load_indices.sm_stride + write_col;
@@ -6,9 +6,11 @@ use crate::matmul::kernels::tiling2d::config::CubeTiling2dConfig;
 use super::loader::{CheckBounds, ReadTileInfo};
 
 #[derive(CubeType)]
-pub(crate) struct WritePositions {
+pub(crate) struct WritePositions<N: Numeric> {
     pub out: u32,
     pub result: u32,
+    /// Factor of the current output values, when the config scales them
+    pub beta: N,
 }
 
 #[cube]
@@ -30,14 +32,14 @@ pub(crate) trait ContiguousAccess<N: Numeric>: Send + Sync + 'static {
     fn write_contiguous_unchecked(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        positions: WritePositions,
+        positions: WritePositions<N>,
         #[comptime] config: CubeTiling2dConfig,
     );
 
     fn write_contiguous_checked(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        positions: WritePositions,
+        positions: WritePositions<N>,
         check_bounds: CheckBounds,
         write_col: u32,
         #[comptime] config: CubeTiling2dConfig,
@@ -63,6 +65,26 @@ pub(crate) trait StridedAccess<N: Numeric>: Send + Sync + 'static {
     ) -> Line<N>;
 }
 
+/// Store a line of results, added to the current value when the config accumulates. The current
+/// value is multiplied by `beta` first when the config scales it.
+#[cube]
+fn write_line<N: Numeric>(
+    out: &mut Tensor<Line<N>>,
+    index: u32,
+    value: Line<N>,
+    beta: N,
+    #[comptime] config: CubeTiling2dConfig,
+) {
+    if comptime!(config.scale_existing) {
+        let beta = Line::empty(out.line_size()).fill(beta);
+        out[index] = out[index] * beta + value;
+    } else if comptime!(config.accumulate) {
+        out[index] = out[index] + value;
+    } else {
+        out[index] = value;
+    }
+}
+
 /// When vectorization == tile_size
 pub(crate) struct MatchingVectorization;
 
@@ -93,7 +115,7 @@ impl<N: Numeric> ContiguousAccess<N> for MatchingVectorization {
     fn write_contiguous_unchecked(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        positions: WritePositions,
+        positions: WritePositions<N>,
         #[comptime] config: CubeTiling2dConfig,
     ) {
         let tile_size = config.tile_size;
@@ -106,13 +128,19 @@ impl<N: Numeric> ContiguousAccess<N> for MatchingVectorization {
             output_elem[i] = results[positions.result + i];
         }
 
-        out[positions.out / tile_size] = output_elem;
+        write_line(
+            out,
+            positions.out / tile_size,
+            output_elem,
+            positions.beta,
+            config,
+        );
     }
 
     fn write_contiguous_checked(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        positions: WritePositions,
+        positions: WritePositions<N>,
         _check_bounds: CheckBounds,
         _write_col: u32,
         #[comptime] config: CubeTiling2dConfig,
@@ -192,7 +220,7 @@ impl<N: Numeric> ContiguousAccess<N> for UnmatchingVectorization {
     fn write_contiguous_unchecked(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        positions: WritePositions,
+        positions: WritePositions<N>,
         #[comptime] config: CubeTiling2dConfig,
     ) {
         let tile_size = config.tile_size;
@@ -203,7 +231,13 @@ impl<N: Numeric> ContiguousAccess<N> for UnmatchingVectorization {
         #[unroll(unroll)]
         for i in 0..comptime!(tile_size / line_size) {
             if comptime!(is_scalar) {
-                out[i + positions.out] = Line::new(results[positions.result + i]);
+                write_line(
+                    out,
+                    i + positions.out,
+                    Line::new(results[positions.result + i]),
+                    positions.beta,
+                    config,
+                );
             } else {
                 let mut output_elem = Line::empty(line_size);
 
@@ -213,7 +247,13 @@ impl<N: Numeric> ContiguousAccess<N> for UnmatchingVectorization {
                     output_elem[j] = results[positions.result + index];
                 }
 
-                out[i + positions.out / line_size] = output_elem;
+                write_line(
+                    out,
+                    i + positions.out / line_size,
+                    output_elem,
+                    positions.beta,
+                    config,
+                );
             }
         }
     }
@@ -221,7 +261,7 @@ impl<N: Numeric> ContiguousAccess<N> for UnmatchingVectorization {
     fn write_contiguous_checked(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        positions: WritePositions,
+        positions: WritePositions<N>,
         check_bounds: CheckBounds,
         write_col: u32,
         #[comptime] config: CubeTiling2dConfig,
@@ -240,7 +280,13 @@ impl<N: Numeric> ContiguousAccess<N> for UnmatchingVectorization {
             let unroll = config.unroll_tile;
 
             if comptime!(is_scalar) {
-                out[i + positions.out] = Line::new(results[positions.result + i]);
+                write_line(
+                    out,
+                    i + positions.out,
+                    Line::new(results[positions.result + i]),
+                    positions.beta,
+                    config,
+                );
             } else {
                 let mut output_elem = Line::empty(line_size);
 
@@ -250,7 +296,13 @@ impl<N: Numeric> ContiguousAccess<N> for UnmatchingVectorization {
                     output_elem[j] = results[positions.result + index];
                 }
 
-                out[i + positions.out / line_size] = output_elem;
+                write_line(
+                    out,
+                    i + positions.out / line_size,
+                    output_elem,
+                    positions.beta,
+                    config,
+                );
             }
         }
     }
@@ -24,7 +24,7 @@ impl<N: Numeric> OutputWriter<N> for TileWriter<N> {
     fn write_output<B: BlockWriter<N>>(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        write_info: WriteTileInfo,
+        write_info: WriteTileInfo<N>,
         dims: Dimensions,
         #[comptime] config: CubeTiling2dConfig,
     ) {
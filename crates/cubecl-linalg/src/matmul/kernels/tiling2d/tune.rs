@@ -0,0 +1,291 @@
+use core::marker::PhantomData;
+
+use cubecl_core::{CubeTuneId, prelude::*};
+use cubecl_runtime::{
+    memory_management::HardwareProperties,
+    tune::{AutotuneError, LocalTuner, Tunable, TunableSet},
+};
+
+use crate::{
+    matmul::{kernels::MatmulLaunchError, tune_key::MatmulAutotuneKey},
+    tensor::TensorHandle,
+};
+
+use super::{
+    config::Tiling2dConfig,
+    launch::{check_launch, launch_context, matmul_tiling_2d_ref},
+};
+
+/// Block sizes along m and n tried by autotune.
+const BLOCK_SIZES_MN: [usize; 3] = [32, 64, 128];
+/// Block sizes along k tried by autotune.
+const BLOCK_SIZES_K: [usize; 3] = [16, 32, 64];
+/// Tile sizes tried by autotune, when the runtime supports lines of that size.
+const TILE_SIZES: [usize; 2] = [4, 8];
+
+type Tiling2dInputs<R, N> = (TensorHandle<R, N>, TensorHandle<R, N>, TensorHandle<R, N>);
+
+/// Matrix multiplication using tiling 2d algorithm, with the block and tile sizes of the config
+/// selected by autotune.
+///
+/// The first call for a given device and [key](MatmulAutotuneKey) benchmarks the candidate
+/// configs on zeroed operands of the largest shapes of the key, and later calls with the same
+/// key reuse the fastest one without measuring again. Candidates that wouldn't launch on the
+/// device, because of their shared memory, cube dim or cube count, are left out before
+/// benchmarking. The other fields of the candidates are the defaults of [Tiling2dConfig].
+pub fn matmul_tiling_2d_autotune<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    device: &R::Device,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    static TUNER: LocalTuner<MatmulAutotuneKey, CubeTuneId> = LocalTuner::new(module_path!());
+
+    // The candidates only differ by their config, which is valid for the device by construction,
+    // so the selected one can't fail on these tensors once they are validated.
+    check_launch::<R, N, N, N>(client, lhs, rhs, out)
+        .map_err(|error| error.with_context(launch_context::<R, N>(lhs, out, None)))?;
+
+    let owned = |tensor: &TensorHandleRef<'_, R>| {
+        TensorHandle::<R, N>::new(
+            tensor.shape.to_vec(),
+            tensor.strides.to_vec(),
+            tensor.handle.clone(),
+        )
+    };
+    let inputs = (owned(lhs), owned(rhs), owned(out));
+
+    let key = tiling2d_autotune_key(&inputs.0, &inputs.1, &inputs.2);
+    let rank = lhs.shape.len();
+    let (lhs_shape, rhs_shape) = key.tuning_shapes(lhs.shape, rhs.shape);
+    let hardware = client.properties().hardware_properties();
+
+    let configs = candidate_configs(
+        lhs_shape[rank - 2],
+        rhs_shape[rank - 1],
+        N::size().unwrap(),
+        R::supported_line_sizes(),
+        hardware,
+    );
+
+    let input_client = client.clone();
+    let mut tunables = TunableSet::new(
+        tiling2d_autotune_key::<R, N>,
+        move |key: &MatmulAutotuneKey,
+              lhs: &TensorHandle<R, N>,
+              rhs: &TensorHandle<R, N>,
+              out: &TensorHandle<R, N>| {
+            let (lhs_shape, rhs_shape) = key.tuning_shapes(&lhs.shape, &rhs.shape);
+            let mut out_shape = out.shape.clone();
+            out_shape[rank - 2] = lhs_shape[rank - 2];
+            out_shape[rank - 1] = rhs_shape[rank - 1];
+
+            (
+                TensorHandle::zeros(&input_client, lhs_shape),
+                TensorHandle::zeros(&input_client, rhs_shape),
+                TensorHandle::empty(&input_client, out_shape),
+            )
+        },
+    );
+    for config in configs {
+        tunables = tunables.with_tunable(Tiling2dTunable::<R, N>::new(client.clone(), config));
+    }
+
+    TUNER
+        .execute(
+            &CubeTuneId::new::<R>(client, device),
+            client,
+            &tunables,
+            inputs,
+        )
+        .map_err(MatmulLaunchError::Autotune)
+}
+
+fn tiling2d_autotune_key<R: Runtime, N: Numeric>(
+    lhs: &TensorHandle<R, N>,
+    rhs: &TensorHandle<R, N>,
+    _out: &TensorHandle<R, N>,
+) -> MatmulAutotuneKey {
+    let elem = N::as_elem_native_unchecked();
+
+    MatmulAutotuneKey::generate(
+        &lhs.shape,
+        &rhs.shape,
+        &lhs.strides,
+        &rhs.strides,
+        elem,
+        elem,
+        elem,
+    )
+}
+
+/// Configs benchmarked for an output of `m` rows and `n` columns, the default one first.
+///
+/// Configs are left out when their tile size isn't a supported line size, when their shared
+/// memory or cube dim exceed the device limits, or when covering the output would take more cubes
+/// than the device can launch along x or y.
+fn candidate_configs(
+    m: usize,
+    n: usize,
+    elem_size: usize,
+    line_sizes: &[u8],
+    hardware: &HardwareProperties,
+) -> Vec<Tiling2dConfig> {
+    let default = Tiling2dConfig::default();
+    let grid = TILE_SIZES.into_iter().flat_map(|tile_size| {
+        BLOCK_SIZES_MN.into_iter().flat_map(move |block_size_m| {
+            BLOCK_SIZES_MN.into_iter().flat_map(move |block_size_n| {
+                BLOCK_SIZES_K
+                    .into_iter()
+                    .map(move |block_size_k| Tiling2dConfig {
+                        block_size_m,
+                        block_size_k,
+                        block_size_n,
+                        tile_size,
+                        ..Default::default()
+                    })
+            })
+        })
+    });
+
+    let is_default = |config: &Tiling2dConfig| {
+        config.block_size_m == default.block_size_m
+            && config.block_size_k == default.block_size_k
+            && config.block_size_n == default.block_size_n
+            && config.tile_size == default.tile_size
+    };
+
+    core::iter::once(default.clone())
+        .chain(grid.filter(|config| !is_default(config)))
+        .filter(|config| launches_on(config, m, n, elem_size, line_sizes, hardware))
+        .collect()
+}
+
+fn launches_on(
+    config: &Tiling2dConfig,
+    m: usize,
+    n: usize,
+    elem_size: usize,
+    line_sizes: &[u8],
+    hardware: &HardwareProperties,
+) -> bool {
+    if !line_sizes.contains(&(config.tile_size as u8)) {
+        return false;
+    }
+
+    // Same constraints as the checked launchers, and enough cubes for the output.
+    config.validate_on(hardware, elem_size).is_ok()
+        && m.div_ceil(config.block_size_m) <= hardware.max_cube_count.x as usize
+        && n.div_ceil(config.block_size_n) <= hardware.max_cube_count.y as usize
+}
+
+/// Tiling 2d launch with a fixed config, named after its block and tile sizes.
+struct Tiling2dTunable<R: Runtime, N: Numeric> {
+    client: ComputeClient<R::Server, R::Channel>,
+    config: Tiling2dConfig,
+    name: String,
+    _elem: PhantomData<N>,
+}
+
+impl<R: Runtime, N: Numeric> Tiling2dTunable<R, N> {
+    fn new(client: ComputeClient<R::Server, R::Channel>, config: Tiling2dConfig) -> Self {
+        let name = format!(
+            "tiling2d-m{}-k{}-n{}-t{}",
+            config.block_size_m, config.block_size_k, config.block_size_n, config.tile_size
+        );
+
+        Self {
+            client,
+            config,
+            name,
+            _elem: PhantomData,
+        }
+    }
+}
+
+impl<R: Runtime, N: Numeric> Tunable for Tiling2dTunable<R, N> {
+    type Inputs = Tiling2dInputs<R, N>;
+    type Output = ();
+
+    fn execute(&self, (lhs, rhs, out): Self::Inputs) -> Result<(), AutotuneError> {
+        matmul_tiling_2d_ref::<R, N>(
+            &self.client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            self.config.clone(),
+        )
+        .map_err(Into::into)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cubecl_core::CubeDim;
+
+    use super::*;
+    use crate::matmul::kernels::tiling2d::config::tiling2d_shared_memory_size;
+
+    fn hardware(max_shared_memory_size: usize, max_cube_count: u32) -> HardwareProperties {
+        HardwareProperties {
+            plane_size_min: 32,
+            plane_size_max: 32,
+            max_bindings: 8,
+            max_shared_memory_size,
+            max_cube_count: CubeDim::new(max_cube_count, max_cube_count, max_cube_count),
+            max_units_per_cube: 1024,
+            max_cube_dim: CubeDim::new(1024, 1024, 64),
+        }
+    }
+
+    #[test]
+    fn default_config_comes_first() {
+        let configs = candidate_configs(512, 512, 4, &[4, 2, 1], &hardware(48 * 1024, 65535));
+
+        assert_eq!(configs[0].block_size_m, 64);
+        assert_eq!(configs[0].block_size_k, 32);
+        assert_eq!(configs[0].block_size_n, 64);
+        assert_eq!(configs[0].tile_size, 4);
+    }
+
+    #[test]
+    fn unsupported_tile_sizes_are_filtered() {
+        let configs = candidate_configs(512, 512, 4, &[4, 2, 1], &hardware(48 * 1024, 65535));
+
+        assert!(configs.iter().all(|config| config.tile_size == 4));
+    }
+
+    #[test]
+    fn configs_exceeding_shared_memory_are_filtered() {
+        let max_shared_memory_size = 16 * 1024;
+        let configs = candidate_configs(
+            512,
+            512,
+            4,
+            &[8, 4],
+            &hardware(max_shared_memory_size, 65535),
+        );
+
+        assert!(!configs.is_empty());
+        assert!(configs.iter().all(|config| {
+            tiling2d_shared_memory_size(config, 4).unwrap() <= max_shared_memory_size
+        }));
+    }
+
+    #[test]
+    fn configs_exceeding_cube_count_are_filtered() {
+        let configs = candidate_configs(4096, 4096, 4, &[4], &hardware(48 * 1024, 32));
+
+        assert!(!configs.is_empty());
+        assert!(
+            configs
+                .iter()
+                .all(|config| config.block_size_m == 128 && config.block_size_n == 128)
+        );
+    }
+}
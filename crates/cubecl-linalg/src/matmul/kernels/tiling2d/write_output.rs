@@ -12,10 +12,11 @@ use super::{
 };
 
 #[derive(CubeType)]
-pub(crate) struct WriteTileInfo {
+pub(crate) struct WriteTileInfo<N: Numeric> {
     pub coordinates: Coordinates,
     pub offset_output: u32,
     pub out_stride: u32,
+    pub beta: N,
 }
 
 #[cube]
@@ -23,12 +24,17 @@ pub(crate) trait OutputWriter<N: Numeric>: Sync + Send + 'static {
     fn write_output<B: BlockWriter<N>>(
         out: &mut Tensor<Line<N>>,
         results: &Array<N>,
-        write_tile_info: WriteTileInfo,
+        write_tile_info: WriteTileInfo<N>,
         dims: Dimensions,
         #[comptime] config: CubeTiling2dConfig,
     );
 }
 
+/// Writes the results of the tile of each unit to the output.
+///
+/// A transposed output is stored as the contiguous `[n, m]` matrix it is in memory: the tile is
+/// transposed in registers, then written as the tile of that layout, with the rows and columns of
+/// its coordinates, dimensions and bounds checks swapped.
 #[cube]
 pub(crate) fn write_to_output<N: Numeric, W: OutputWriter<N>>(
     out: &mut Tensor<Line<N>>,
@@ -36,26 +42,100 @@ pub(crate) fn write_to_output<N: Numeric, W: OutputWriter<N>>(
     coordinates: Coordinates,
     offset_output: u32,
     dims: Dimensions,
+    beta: N,
     #[comptime] config: CubeTiling2dConfig,
 ) {
-    let check_m_bounds = config.check_m_bounds;
-    let check_n_bounds = config.check_n_bounds;
+    if comptime!(config.out_transposed) {
+        let transposed = transpose_tile::<N>(results, config);
+        let coordinates = Coordinates {
+            unit_row: coordinates.unit_col,
+            unit_col: coordinates.unit_row,
+            skip_row: coordinates.skip_col,
+            skip_col: coordinates.skip_row,
+        };
+        let dims = Dimensions {
+            m: dims.n,
+            k: dims.k,
+            n: dims.m,
+        };
 
+        write_tile::<N, W>(
+            out,
+            &transposed,
+            coordinates,
+            offset_output,
+            dims,
+            beta,
+            config.check_n_bounds,
+            config.check_m_bounds,
+            config,
+        );
+    } else {
+        write_tile::<N, W>(
+            out,
+            results,
+            coordinates,
+            offset_output,
+            dims,
+            beta,
+            config.check_m_bounds,
+            config.check_n_bounds,
+            config,
+        );
+    }
+}
+
+#[cube]
+#[allow(clippy::too_many_arguments)]
+fn write_tile<N: Numeric, W: OutputWriter<N>>(
+    out: &mut Tensor<Line<N>>,
+    results: &Array<N>,
+    coordinates: Coordinates,
+    offset_output: u32,
+    dims: Dimensions,
+    beta: N,
+    #[comptime] check_row_bounds: bool,
+    #[comptime] check_col_bounds: bool,
+    #[comptime] config: CubeTiling2dConfig,
+) {
     let write_info = WriteTileInfo {
         coordinates,
         offset_output,
         out_stride: dims.n,
+        beta,
     };
 
-    if check_m_bounds {
-        if check_n_bounds {
+    if check_row_bounds {
+        if check_col_bounds {
             W::write_output::<WholeCheckBlockIO>(out, results, write_info, dims, config);
         } else {
             W::write_output::<VerticalCheckBlockIO>(out, results, write_info, dims, config);
         }
-    } else if check_n_bounds {
+    } else if check_col_bounds {
         W::write_output::<HorizontalCheckBlockIO>(out, results, write_info, dims, config);
     } else {
         W::write_output::<UncheckedBlockIO>(out, results, write_info, dims, config);
     }
 }
+
+/// Swaps the rows and columns of a tile of results.
+#[cube]
+fn transpose_tile<N: Numeric>(
+    results: &Array<N>,
+    #[comptime] config: CubeTiling2dConfig,
+) -> Array<N> {
+    let tile_size = config.tile_size;
+    let unroll = config.unroll_tile;
+
+    let mut transposed = Array::<N>::new(comptime!(tile_size * tile_size));
+
+    #[unroll(unroll)]
+    for row in 0..tile_size {
+        #[unroll(unroll)]
+        for col in 0..tile_size {
+            transposed[col * tile_size + row] = results[row * tile_size + col];
+        }
+    }
+
+    transposed
+}
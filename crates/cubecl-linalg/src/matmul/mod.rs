@@ -1,4 +1,6 @@
 mod base;
+/// Benchmarks of matmul kernels
+pub mod benchmark;
 /// Components for matrix multiplication
 pub mod components;
 /// Contains matmul kernels
@@ -6,8 +8,10 @@ pub mod kernels;
 /// Tests for matmul kernels
 #[cfg(feature = "export_tests")]
 pub mod tests;
+mod tune;
 
 pub use base::*;
+pub use tune::matmul_autotune;
 
 /// Autotune key for matmul.
 pub mod tune_key;
@@ -1,7 +1,9 @@
 #![allow(missing_docs)]
 
 pub mod cmma_matmul;
+pub mod requantize;
 pub mod simple;
+pub mod spmm;
 mod test_macros;
 pub mod test_utils;
 pub mod tiling2d;
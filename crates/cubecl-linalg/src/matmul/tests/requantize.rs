@@ -0,0 +1,171 @@
+use cubecl_core::{
+    CubeElement, Runtime,
+    prelude::{ComputeClient, Numeric},
+};
+
+use crate::{
+    matmul::kernels::{MatmulInvalidProblem, MatmulLaunchError, tiling2d},
+    tensor::TensorHandle,
+};
+
+use super::test_utils::assert_equals_approx;
+
+struct RequantizeCase {
+    batch: usize,
+    m: usize,
+    k: usize,
+    n: usize,
+}
+
+impl RequantizeCase {
+    fn lhs(&self) -> Vec<i64> {
+        (0..self.batch * self.m * self.k)
+            .map(|i| (i % 7) as i64 - 3)
+            .collect()
+    }
+
+    fn rhs(&self) -> Vec<i64> {
+        (0..self.batch * self.k * self.n)
+            .map(|i| (i % 5) as i64 - 2)
+            .collect()
+    }
+
+    /// The scaled accumulators, `acc[.., row, col] * scales[col]` computed in `f32`.
+    fn expected(&self, scales: &[f32]) -> Vec<f32> {
+        let (batch, m, k, n) = (self.batch, self.m, self.k, self.n);
+        let (lhs, rhs) = (self.lhs(), self.rhs());
+        let mut expected = vec![0.0; batch * m * n];
+
+        for b in 0..batch {
+            for row in 0..m {
+                for col in 0..n {
+                    let acc: i64 = (0..k)
+                        .map(|i| lhs[b * m * k + row * k + i] * rhs[b * k * n + i * n + col])
+                        .sum();
+                    expected[b * m * n + row * n + col] = acc as f32 * scales[col];
+                }
+            }
+        }
+
+        expected
+    }
+
+    fn launch<R: Runtime, I: Numeric + CubeElement, O: Numeric>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+        scales: &[f32],
+    ) -> (TensorHandle<R, O>, Result<(), MatmulLaunchError>) {
+        let (batch, m, k, n) = (self.batch, self.m, self.k, self.n);
+        let to_handle = |data: Vec<i64>, shape: &[usize]| {
+            let data: Vec<I> = data.into_iter().map(I::from_int).collect();
+            TensorHandle::<R, I>::from_data(client, &data, shape)
+        };
+
+        let lhs = to_handle(self.lhs(), &[batch, m, k]);
+        let rhs = to_handle(self.rhs(), &[batch, k, n]);
+        let scales = TensorHandle::<R, f32>::from_data(client, scales, &[scales.len()]);
+        let out = TensorHandle::<R, O>::empty(client, vec![batch, m, n]);
+
+        let result = tiling2d::launch_requantize::<R, I, O>(
+            client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &scales.as_ref(),
+            &out.as_ref(),
+            Default::default(),
+        );
+
+        (out, result)
+    }
+}
+
+pub fn test_per_channel_scales<R: Runtime, I: Numeric + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let case = RequantizeCase {
+        batch: 2,
+        m: 8,
+        k: 16,
+        n: 12,
+    };
+    let scales: Vec<f32> = (0..case.n).map(|col| 0.5 + col as f32 * 0.25).collect();
+
+    let (out, result) = case.launch::<R, I, f32>(&client, &scales);
+    result.unwrap();
+
+    if let Err(e) =
+        assert_equals_approx::<R, f32>(&client, out.handle, &case.expected(&scales), 0.001)
+    {
+        panic!("{}", e);
+    }
+}
+
+/// An integer output gets the scaled accumulator rounded to the nearest integer.
+pub fn test_per_channel_scales_int_output<R: Runtime, I: Numeric + CubeElement>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let case = RequantizeCase {
+        batch: 1,
+        m: 12,
+        k: 20,
+        n: 16,
+    };
+    let scales: Vec<f32> = (0..case.n).map(|col| 0.1 + col as f32 * 0.15).collect();
+
+    let (out, result) = case.launch::<R, I, i32>(&client, &scales);
+    result.unwrap();
+
+    let actual = i32::from_bytes(&client.read_one(out.handle.binding())).to_vec();
+    let expected: Vec<i32> = case
+        .expected(&scales)
+        .into_iter()
+        .map(|value| value.round() as i32)
+        .collect();
+
+    assert_eq!(actual, expected);
+}
+
+pub fn test_invalid_scales_length<R: Runtime, I: Numeric + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+    let case = RequantizeCase {
+        batch: 1,
+        m: 4,
+        k: 4,
+        n: 4,
+    };
+
+    let (_, result) = case.launch::<R, I, f32>(&client, &[1.0; 3]);
+
+    assert!(matches!(
+        result.map_err(MatmulLaunchError::into_root),
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::InvalidScalesLength { len: 3, n: 4 }
+        ))
+    ));
+}
+
+pub fn test_invalid_rank<R: Runtime, I: Numeric + CubeElement>(device: &R::Device) {
+    let client = R::client(device);
+
+    // The scales are sized from the last dimension of rhs, so a scalar rhs is rejected first.
+    let lhs = TensorHandle::<R, I>::from_data(&client, &[I::from_int(1); 16], &[4, 4]);
+    let rhs = TensorHandle::<R, I>::from_data(&client, &[I::from_int(1)], &[]);
+    let scales = TensorHandle::<R, f32>::from_data(&client, &[1.0; 4], &[4]);
+    let out = TensorHandle::<R, f32>::empty(&client, vec![4, 4]);
+
+    let result = tiling2d::launch_requantize::<R, I, f32>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &scales.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    );
+
+    match result.map_err(MatmulLaunchError::into_root) {
+        Err(MatmulLaunchError::InvalidShape { lhs, rhs }) => {
+            assert_eq!((lhs, rhs), (vec![4, 4], Vec::<usize>::new()))
+        }
+        _ => panic!("Expected an invalid shape error"),
+    }
+}
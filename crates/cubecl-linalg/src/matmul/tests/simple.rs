@@ -1,8 +1,26 @@
-use std::fmt::Display;
+use std::{cell::Cell, fmt::Display};
 
-use cubecl_core::{CubeElement, Runtime, prelude::Float};
+use cubecl_core::{
+    CubeElement, Feature, Runtime,
+    client::ComputeClient,
+    ir::{Elem, FloatKind},
+    prelude::{CubePrimitive, Float},
+    server::Handle,
+};
 
-use crate::{matmul::kernels::naive, tensor::TensorHandle};
+use crate::{
+    matmul::{
+        InterleavedOperand,
+        components::tile::accelerated::Accelerated,
+        kernels::{
+            MatmulAvailabilityError, MatmulInvalidProblem, MatmulLaunchError,
+            accelerated_available,
+            matmul::{self, simple::SimpleAlgorithm},
+            naive,
+        },
+    },
+    tensor::{ScratchProvider, TensorHandle},
+};
 
 use super::test_utils::{MatmulTestCase, Sample, assert_equals_approx};
 
@@ -54,6 +72,276 @@ pub fn test_with_batches<R: Runtime, F: Float + CubeElement + Display + Sample>(
     test_simple::<R, F>(case, device);
 }
 
+/// More batches than wgpu launches cubes along `z`, which are spread over several launches.
+pub fn test_more_batches_than_cube_count<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 8,
+        k: 8,
+        n: 8,
+        batch: 70_000,
+    };
+
+    test_simple::<R, F>(case, device);
+}
+
+/// The availability query agrees with the accelerated launcher for `f16` operands, which are
+/// multiplied as `f16` into an `f16` output.
+pub fn test_accelerated_available<R: Runtime>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 1,
+    };
+    let client = R::client(device);
+    let elem = half::f16::as_elem_native_unchecked();
+
+    let lhs = TensorHandle::<R, half::f16>::zeros(&client, vec![case.batch, case.m, case.k]);
+    let rhs = TensorHandle::<R, half::f16>::zeros(&client, vec![case.batch, case.k, case.n]);
+    let out = TensorHandle::<R, half::f16>::empty(&client, vec![case.batch, case.m, case.n]);
+
+    let available = accelerated_available::<R>(&client, elem, elem);
+    let result = matmul::launch_ref::<R, half::f16, SimpleAlgorithm<Accelerated>>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+    );
+
+    // With planes of 64 units, the launcher rejects the accelerated config instead.
+    match (available, result) {
+        (Ok(()), Ok(())) | (Err(_), Err(_)) => {}
+        (available, result) => {
+            panic!("Availability {available:?} doesn't match the launch result {result:?}")
+        }
+    }
+}
+
+/// The accelerated matmul of `f32` operands multiplied as `tf32` matches the `f32` matmul to
+/// about 1e-3, the operands being positive so that the error stays relative to the output.
+pub fn test_tf32_matches_f32<R: Runtime>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 1,
+    };
+    let client = R::client(device);
+    let positive = |shape: Vec<usize>, seed| {
+        let data = f32::sample(shape.iter().product(), seed)
+            .into_iter()
+            .map(f32::abs)
+            .collect::<Vec<_>>();
+        TensorHandle::<R, f32>::new_contiguous(shape, client.create(f32::as_bytes(&data)))
+    };
+    let lhs = positive(vec![case.batch, case.m, case.k], 1234);
+    let rhs = positive(vec![case.batch, case.k, case.n], 5678);
+
+    let expected = case.matmul_cpu::<R, f32>(&lhs, &rhs, &client);
+
+    let out: TensorHandle<R, f32> = case.empty_out(&client);
+    let result = matmul::launch_ref_tf32::<R, SimpleAlgorithm<Accelerated>>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+    );
+    // The launch may only be refused for the plane size, checked before the types, or for the
+    // types on a device without tf32. Any other error is a failure.
+    let tf32 = Feature::Type(Elem::Float(FloatKind::TF32));
+    match result.map_err(MatmulLaunchError::into_root) {
+        Ok(()) => {}
+        Err(MatmulLaunchError::Unavailable(
+            MatmulAvailabilityError::PlaneDimUnknown
+            | MatmulAvailabilityError::PlaneDimUnsupported { .. },
+        )) => return,
+        Err(MatmulLaunchError::Unavailable(MatmulAvailabilityError::TypesUnavailable {
+            ..
+        })) if !client.properties().feature_enabled(tf32) => return,
+        Err(err) => panic!("{err:?}"),
+    }
+
+    if let Err(e) = assert_equals_approx::<R, f32>(&client, out.handle, &expected, 1e-3) {
+        panic!("{}", e);
+    }
+}
+
+/// Both operands are read from regions of a single buffer, the rhs starting at the first aligned
+/// position after the lhs.
+pub fn test_interleaved<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 2,
+    };
+    let client = R::client(device);
+    let elem_size = core::mem::size_of::<F>();
+    let alignment = client.properties().memory_properties().alignment as usize / elem_size;
+    let alignment = alignment.max(1);
+
+    let lhs = F::sample(case.batch * case.m * case.k, 1234);
+    let rhs = F::sample(case.batch * case.k * case.n, 5678);
+    let rhs_offset = lhs.len().div_ceil(alignment) * alignment;
+    let mut data = lhs.clone();
+    data.resize(rhs_offset, F::from_int(0));
+    data.extend_from_slice(&rhs);
+    let buffer = client.create(F::as_bytes(&data));
+
+    let expected = case.matmul_cpu_algorithm(&lhs, &rhs);
+    let out: TensorHandle<R, F> = case.empty_out(&client);
+
+    crate::matmul::launch_interleaved::<R, F>(
+        &crate::matmul::Strategy::Tiling2D(Default::default()),
+        &client,
+        &buffer,
+        InterleavedOperand {
+            offset: 0,
+            shape: &[case.batch, case.m, case.k],
+            strides: &[case.m * case.k, case.k, 1],
+        },
+        InterleavedOperand {
+            offset: rhs_offset,
+            shape: &[case.batch, case.k, case.n],
+            strides: &[case.k * case.n, case.n, 1],
+        },
+        &out.as_ref(),
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 10e-4) {
+        panic!("{}", e);
+    }
+}
+
+/// Operands that don't describe a region of the buffer are rejected before launching.
+pub fn test_interleaved_invalid_operands<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let elem_size = core::mem::size_of::<F>();
+    let alignment = client.properties().memory_properties().alignment;
+
+    let data = F::sample(64 * 64, 1234);
+    let buffer = client.create(F::as_bytes(&data));
+    let out: TensorHandle<R, F> = TensorHandle::empty(&client, vec![32, 32]);
+
+    let operand = |offset, shape: &'static [usize], strides: &'static [usize]| InterleavedOperand {
+        offset,
+        shape,
+        strides,
+    };
+    let launch = |lhs: InterleavedOperand<'_>| {
+        crate::matmul::launch_interleaved::<R, F>(
+            &crate::matmul::Strategy::Tiling2D(Default::default()),
+            &client,
+            &buffer,
+            lhs,
+            operand(0, &[32, 32], &[32, 1]),
+            &out.as_ref(),
+        )
+        .map_err(MatmulLaunchError::into_root)
+    };
+
+    match launch(operand(0, &[32, 32], &[1])) {
+        Err(MatmulLaunchError::InvalidLayout { strides }) => assert_eq!(strides, vec![1]),
+        _ => panic!("Expected an invalid layout error"),
+    }
+
+    // The operand has one more row than the buffer.
+    let end = (65 * 64 * elem_size) as u64;
+    match launch(operand(0, &[65, 64], &[64, 1])) {
+        Err(MatmulLaunchError::InvalidProblem(MatmulInvalidProblem::OperandOutOfBounds {
+            end: actual,
+            size,
+        })) => assert_eq!((actual, size), (end, buffer.size())),
+        _ => panic!("Expected an out of bounds error"),
+    }
+
+    // An offset of one element is only misaligned when the alignment is larger than an element.
+    if alignment > elem_size as u64 {
+        match launch(operand(1, &[32, 32], &[32, 1])) {
+            Err(MatmulLaunchError::InvalidProblem(MatmulInvalidProblem::MisalignedOperand {
+                offset,
+                alignment: actual,
+            })) => assert_eq!((offset, actual), (elem_size as u64, alignment)),
+            _ => panic!("Expected a misaligned operand error"),
+        }
+    }
+}
+
+/// Counts the buffers handed out and given back by the launcher.
+#[derive(Default)]
+struct CountingScratch {
+    allocated: Cell<usize>,
+    released: Cell<usize>,
+}
+
+impl<R: Runtime> ScratchProvider<R> for CountingScratch {
+    fn alloc(&self, client: &ComputeClient<R::Server, R::Channel>, size: usize) -> Handle {
+        self.allocated.set(self.allocated.get() + 1);
+        client.empty(size)
+    }
+
+    fn release(&self, handle: Handle) {
+        self.released.set(self.released.get() + 1);
+        core::mem::drop(handle);
+    }
+}
+
+/// A highly permuted lhs is copied into a buffer of the scratch provider, which is given back
+/// once the matmul is launched.
+pub fn test_scratch_provider<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 32,
+        k: 48,
+        n: 40,
+        batch: 3,
+    };
+    let client = R::client(device);
+
+    // The lhs is stored as [m, batch, k], so the batch is swapped with the rows.
+    let lhs_data = F::sample(case.batch * case.m * case.k, 1234);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let rhs_data = F::from_bytes(&client.read_one(rhs.handle.clone().binding())).to_vec();
+    let mut lhs_contiguous = Vec::with_capacity(lhs_data.len());
+    for b in 0..case.batch {
+        for row in 0..case.m {
+            let start = (row * case.batch + b) * case.k;
+            lhs_contiguous.extend_from_slice(&lhs_data[start..start + case.k]);
+        }
+    }
+    let expected = case.matmul_cpu_algorithm(&lhs_contiguous, &rhs_data);
+
+    let lhs = TensorHandle::<R, F>::new(
+        vec![case.batch, case.m, case.k],
+        vec![case.k, case.batch * case.k, 1],
+        client.create(F::as_bytes(&lhs_data)),
+    );
+    let out: TensorHandle<R, F> = case.empty_out(&client);
+    let scratch = CountingScratch::default();
+
+    crate::matmul::launch_ref_with_scratch::<R, F, _>(
+        &crate::matmul::Strategy::Tiling2D(Default::default()),
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        &scratch,
+    )
+    .unwrap();
+
+    assert_eq!(scratch.allocated.get(), 1);
+    assert_eq!(scratch.released.get(), 1);
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 10e-4) {
+        panic!("{}", e);
+    }
+}
+
 fn test_simple<R: Runtime, F: Float + CubeElement + Display + Sample>(
     case: MatmulTestCase,
     device: &R::Device,
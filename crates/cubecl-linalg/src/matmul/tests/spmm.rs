@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use cubecl_core::{CubeElement, Runtime, prelude::Float};
+
+use crate::{
+    matmul::kernels::{MatmulInvalidProblem, MatmulLaunchError, spmm},
+    tensor::TensorHandle,
+};
+
+use super::test_utils::{Sample, assert_equals_approx};
+
+pub fn test_spmm_csr<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let client = R::client(device);
+    let (m, k, n) = (37, 29, 70);
+
+    // Every sixth row is empty, the others have a varying number of non-zeros.
+    let mut row_ptr = vec![0u32];
+    let mut col_idx = Vec::new();
+    for row in 0..m {
+        if row % 6 != 5 {
+            col_idx.extend((0..k as u32).filter(|col| (row as u32 * 7 + col * 3) % 5 == 0));
+        }
+        row_ptr.push(col_idx.len() as u32);
+    }
+    let values = F::sample(col_idx.len(), 42);
+    let dense = F::sample(k * n, 1234);
+
+    let mut expected = vec![F::new(0.0); m * n];
+    for row in 0..m {
+        for j in row_ptr[row] as usize..row_ptr[row + 1] as usize {
+            let dense_row = col_idx[j] as usize;
+            for col in 0..n {
+                expected[row * n + col] += values[j] * dense[dense_row * n + col];
+            }
+        }
+    }
+
+    let row_ptr = TensorHandle::<R, u32>::from_data(&client, &row_ptr, &[m + 1]);
+    let col_idx_handle = TensorHandle::<R, u32>::from_data(&client, &col_idx, &[col_idx.len()]);
+    let values = TensorHandle::<R, F>::from_data(&client, &values, &[col_idx.len()]);
+    let dense = TensorHandle::<R, F>::from_data(&client, &dense, &[k, n]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![m, n]);
+
+    spmm::spmm_csr::<R, F>(
+        &client,
+        &row_ptr.as_ref(),
+        &col_idx_handle.as_ref(),
+        &values.as_ref(),
+        &dense.as_ref(),
+        &out.as_ref(),
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_invalid_row_ptr_length<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+
+    let row_ptr = TensorHandle::<R, u32>::zeros(&client, vec![4]);
+    let col_idx = TensorHandle::<R, u32>::zeros(&client, vec![2]);
+    let values = TensorHandle::<R, F>::zeros(&client, vec![2]);
+    let dense = TensorHandle::<R, F>::zeros(&client, vec![3, 5]);
+    let out = TensorHandle::<R, F>::empty(&client, vec![4, 5]);
+
+    let result = spmm::spmm_csr::<R, F>(
+        &client,
+        &row_ptr.as_ref(),
+        &col_idx.as_ref(),
+        &values.as_ref(),
+        &dense.as_ref(),
+        &out.as_ref(),
+    );
+
+    assert!(matches!(
+        result,
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::InvalidRowPtrLength { len: 4, m: 4 }
+        ))
+    ));
+}
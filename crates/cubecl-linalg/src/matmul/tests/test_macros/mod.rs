@@ -1,3 +1,5 @@
 pub mod cmma;
+mod requantize;
 mod simple;
+mod spmm;
 mod tiling2d;
@@ -0,0 +1,59 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_requantize {
+    () => {
+        mod matmul_requantize {
+            $crate::testgen_matmul_requantize!(i32);
+        }
+    };
+    ($int:ident) => {
+            use super::*;
+
+            pub type IntT = $int;
+
+            #[test]
+            pub fn test_per_channel_scales() {
+                cubecl_linalg::matmul::tests::requantize::test_per_channel_scales::<
+                    TestRuntime,
+                    IntT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_per_channel_scales_int_output() {
+                cubecl_linalg::matmul::tests::requantize::test_per_channel_scales_int_output::<
+                    TestRuntime,
+                    IntT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_invalid_scales_length() {
+                cubecl_linalg::matmul::tests::requantize::test_invalid_scales_length::<
+                    TestRuntime,
+                    IntT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_invalid_rank() {
+                cubecl_linalg::matmul::tests::requantize::test_invalid_rank::<
+                    TestRuntime,
+                    IntT,
+                >(&Default::default())
+            }
+    };
+    ([$($int:ident),*]) => {
+        mod matmul_requantize {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$int _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_requantize!($int);
+                })*
+            }
+        }
+    };
+}
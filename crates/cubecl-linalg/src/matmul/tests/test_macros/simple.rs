@@ -28,6 +28,42 @@ macro_rules! testgen_matmul_simple {
                 )
             }
 
+            #[test]
+            pub fn test_accelerated_available() {
+                cubecl_linalg::matmul::tests::simple::test_accelerated_available::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_tf32_matches_f32() {
+                cubecl_linalg::matmul::tests::simple::test_tf32_matches_f32::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_interleaved() {
+                cubecl_linalg::matmul::tests::simple::test_interleaved::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_interleaved_invalid_operands() {
+                cubecl_linalg::matmul::tests::simple::test_interleaved_invalid_operands::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_scratch_provider() {
+                cubecl_linalg::matmul::tests::simple::test_scratch_provider::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
             #[test]
             pub fn test_with_check_bounds() {
                 cubecl_linalg::matmul::tests::simple::test_with_check_bounds::<
@@ -43,6 +79,14 @@ macro_rules! testgen_matmul_simple {
                     FloatT,
                 >(&Default::default())
             }
+
+            #[test]
+            pub fn test_more_batches_than_cube_count() {
+                cubecl_linalg::matmul::tests::simple::test_more_batches_than_cube_count::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
     };
     ([$($float:ident),*]) => {
         mod simple {
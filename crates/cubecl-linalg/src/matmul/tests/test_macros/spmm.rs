@@ -0,0 +1,43 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_spmm {
+    () => {
+        mod matmul_spmm {
+            $crate::testgen_matmul_spmm!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_spmm_csr() {
+                cubecl_linalg::matmul::tests::spmm::test_spmm_csr::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_invalid_row_ptr_length() {
+                cubecl_linalg::matmul::tests::spmm::test_invalid_row_ptr_length::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod matmul_spmm {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_spmm!($float);
+                })*
+            }
+        }
+    };
+}
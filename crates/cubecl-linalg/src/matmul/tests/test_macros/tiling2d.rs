@@ -5,8 +5,52 @@ macro_rules! testgen_matmul_tiling2d {
     () => {
         mod matmul_tiling2d {
             $crate::testgen_matmul_tiling2d!(f32);
+            $crate::testgen_matmul_tiling2d!(@fixed_types);
         }
     };
+    (@fixed_types) => {
+            #[test]
+            pub fn test_integer_i32() {
+                cubecl_linalg::matmul::tests::tiling2d::test_integer::<TestRuntime, i32>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_integer_i64() {
+                cubecl_linalg::matmul::tests::tiling2d::test_integer::<TestRuntime, i64>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_f64() {
+                cubecl_linalg::matmul::tests::tiling2d::test_f64::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_i8_with_i32_accumulation() {
+                cubecl_linalg::matmul::tests::tiling2d::test_i8_with_i32_accumulation::<
+                    TestRuntime,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fp8_e4m3_with_f16_output() {
+                cubecl_linalg::matmul::tests::tiling2d::test_fp8_with_f16_output::<
+                    TestRuntime,
+                    cubecl_core::e4m3,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fp8_e5m2_with_f16_output() {
+                cubecl_linalg::matmul::tests::tiling2d::test_fp8_with_f16_output::<
+                    TestRuntime,
+                    cubecl_core::e5m2,
+                >(&Default::default())
+            }
+    };
     ($float:ident) => {
             use super::*;
             use cubecl_linalg::matmul::tests;
@@ -45,6 +89,597 @@ macro_rules! testgen_matmul_tiling2d {
                     FloatT,
                 >(&Default::default())
             }
+
+            #[test]
+            pub fn test_asymmetric_blocks() {
+                cubecl_linalg::matmul::tests::tiling2d::test_asymmetric_blocks::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_asymmetric_blocks_transposed() {
+                cubecl_linalg::matmul::tests::tiling2d::test_asymmetric_blocks_transposed::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_line_size_8() {
+                cubecl_linalg::matmul::tests::tiling2d::test_line_size_8::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_line_size_8_transposed_lhs() {
+                cubecl_linalg::matmul::tests::tiling2d::test_line_size_8_transposed_lhs::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_plain_operands() {
+                cubecl_linalg::matmul::tests::tiling2d::test_plain_operands::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_transposed_lhs() {
+                cubecl_linalg::matmul::tests::tiling2d::test_transposed_lhs::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_transposed_rhs() {
+                cubecl_linalg::matmul::tests::tiling2d::test_transposed_rhs::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_transposed_both() {
+                cubecl_linalg::matmul::tests::tiling2d::test_transposed_both::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_transposed_rhs_with_check_bounds() {
+                cubecl_linalg::matmul::tests::tiling2d::test_transposed_rhs_with_check_bounds::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_chunked() {
+                cubecl_linalg::matmul::tests::tiling2d::test_chunked::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_multi_device() {
+                cubecl_linalg::matmul::tests::tiling2d::test_multi_device::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_with_buffers() {
+                cubecl_linalg::matmul::tests::tiling2d::test_with_buffers::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_output_size_overflow() {
+                cubecl_linalg::matmul::tests::tiling2d::test_output_size_overflow::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_invalid_block_sizes() {
+                cubecl_linalg::matmul::tests::tiling2d::test_invalid_block_sizes::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_shared_memory_limit_exceeded() {
+                cubecl_linalg::matmul::tests::tiling2d::test_shared_memory_limit_exceeded::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_output_too_small() {
+                cubecl_linalg::matmul::tests::tiling2d::test_output_too_small::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_permuted_output() {
+                cubecl_linalg::matmul::tests::tiling2d::test_permuted_output::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_error_context() {
+                cubecl_linalg::matmul::tests::tiling2d::test_error_context::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_mismatched_k() {
+                cubecl_linalg::matmul::tests::tiling2d::test_mismatched_k::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_unchecked() {
+                cubecl_linalg::matmul::tests::tiling2d::test_unchecked::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_split_k() {
+                cubecl_linalg::matmul::tests::tiling2d::test_split_k::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_split_k_with_alpha_and_mask() {
+                cubecl_linalg::matmul::tests::tiling2d::test_split_k_with_alpha_and_mask::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_autotune() {
+                cubecl_linalg::matmul::tests::tiling2d::test_autotune::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_matmul_autotune() {
+                cubecl_linalg::matmul::tests::tiling2d::test_matmul_autotune::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_rank_4_swapped_batches() {
+                cubecl_linalg::matmul::tests::tiling2d::test_rank_4_swapped_batches::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_line_size_override() {
+                cubecl_linalg::matmul::tests::tiling2d::test_line_size_override::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_invalid_line_size_override() {
+                cubecl_linalg::matmul::tests::tiling2d::test_invalid_line_size_override::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_micro() {
+                cubecl_linalg::matmul::tests::tiling2d::test_micro::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_micro_many_batches() {
+                cubecl_linalg::matmul::tests::tiling2d::test_micro_many_batches::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_micro_with_beta_and_activation() {
+                cubecl_linalg::matmul::tests::tiling2d::test_micro_with_beta_and_activation::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_benchmark() {
+                cubecl_linalg::matmul::tests::tiling2d::test_benchmark::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_benchmark_without_samples() {
+                cubecl_linalg::matmul::tests::tiling2d::test_benchmark_without_samples::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_with_epilogue() {
+                cubecl_linalg::matmul::tests::tiling2d::test_with_epilogue::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_epilogue_key() {
+                cubecl_linalg::matmul::tests::tiling2d::test_epilogue_key::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_with_bias() {
+                cubecl_linalg::matmul::tests::tiling2d::test_with_bias::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_bias_invalid_length() {
+                cubecl_linalg::matmul::tests::tiling2d::test_bias_invalid_length::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_bias_invalid_rank() {
+                cubecl_linalg::matmul::tests::tiling2d::test_bias_invalid_rank::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_float_type_availability() {
+                cubecl_linalg::matmul::tests::tiling2d::test_float_type_availability::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_mixed_f32_output() {
+                cubecl_linalg::matmul::tests::tiling2d::test_mixed_f32_output::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_mixed_accuracy_large_k() {
+                cubecl_linalg::matmul::tests::tiling2d::test_mixed_accuracy_large_k::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_broadcast_batch() {
+                cubecl_linalg::matmul::tests::tiling2d::test_broadcast_batch::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_alloc_output() {
+                cubecl_linalg::matmul::tests::tiling2d::test_alloc_output::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_alloc_invalid_shape() {
+                cubecl_linalg::matmul::tests::tiling2d::test_alloc_invalid_shape::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_broadcast_stride_zero() {
+                cubecl_linalg::matmul::tests::tiling2d::test_broadcast_stride_zero::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_mismatched_batch() {
+                cubecl_linalg::matmul::tests::tiling2d::test_mismatched_batch::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_gemv_column() {
+                cubecl_linalg::matmul::tests::tiling2d::test_gemv_column::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_gemv_row() {
+                cubecl_linalg::matmul::tests::tiling2d::test_gemv_row::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_gemv_transposed() {
+                cubecl_linalg::matmul::tests::tiling2d::test_gemv_transposed::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_gemv_with_beta_and_activation() {
+                cubecl_linalg::matmul::tests::tiling2d::test_gemv_with_beta_and_activation::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_empty_output() {
+                cubecl_linalg::matmul::tests::tiling2d::test_empty_output::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_zero_k() {
+                cubecl_linalg::matmul::tests::tiling2d::test_zero_k::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_transposed_output() {
+                cubecl_linalg::matmul::tests::tiling2d::test_transposed_output::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_transposed_output_with_mask_and_split_k() {
+                cubecl_linalg::matmul::tests::tiling2d::test_transposed_output_with_mask_and_split_k::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_with_packed_mask() {
+                cubecl_linalg::matmul::tests::tiling2d::test_with_packed_mask::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_packed_mask_invalid_rank() {
+                cubecl_linalg::matmul::tests::tiling2d::test_packed_mask_invalid_rank::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_padded_cube_count() {
+                cubecl_linalg::matmul::tests::tiling2d::test_padded_cube_count::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_padded_cube_count_without_check_bounds() {
+                cubecl_linalg::matmul::tests::tiling2d::test_padded_cube_count_without_check_bounds::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fast_float_mode() {
+                cubecl_linalg::matmul::tests::tiling2d::test_fast_float_mode::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_precise_float_mode_is_reproducible() {
+                cubecl_linalg::matmul::tests::tiling2d::test_precise_float_mode_is_reproducible::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_batches_split_across_launches() {
+                cubecl_linalg::matmul::tests::tiling2d::test_batches_split_across_launches::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_accumulate_across_launches() {
+                cubecl_linalg::matmul::tests::tiling2d::test_accumulate_across_launches::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_accumulate_distinct_products() {
+                cubecl_linalg::matmul::tests::tiling2d::test_accumulate_distinct_products::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_with_alpha() {
+                cubecl_linalg::matmul::tests::tiling2d::test_with_alpha::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_beta_zero_overwrites_output() {
+                cubecl_linalg::matmul::tests::tiling2d::test_beta_zero_overwrites_output::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_beta_one_accumulates() {
+                cubecl_linalg::matmul::tests::tiling2d::test_beta_one_accumulates::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fractional_alpha_and_beta() {
+                cubecl_linalg::matmul::tests::tiling2d::test_fractional_alpha_and_beta::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_relu_epilogue() {
+                cubecl_linalg::matmul::tests::tiling2d::test_relu_epilogue::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_relu6_epilogue() {
+                cubecl_linalg::matmul::tests::tiling2d::test_relu6_epilogue::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_gelu_epilogue() {
+                cubecl_linalg::matmul::tests::tiling2d::test_gelu_epilogue::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_sigmoid_epilogue() {
+                cubecl_linalg::matmul::tests::tiling2d::test_sigmoid_epilogue::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_silu_epilogue() {
+                cubecl_linalg::matmul::tests::tiling2d::test_silu_epilogue::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_no_epilogue_is_unchanged() {
+                cubecl_linalg::matmul::tests::tiling2d::test_no_epilogue_is_unchanged::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_with_causal_mask() {
+                cubecl_linalg::matmul::tests::tiling2d::test_with_causal_mask::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_with_causal_mask_lower() {
+                cubecl_linalg::matmul::tests::tiling2d::test_with_causal_mask_lower::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
     };
     ([$($float:ident),*]) => {
         mod matmul_tiling2d {
@@ -56,6 +691,8 @@ macro_rules! testgen_matmul_tiling2d {
                     $crate::testgen_matmul_tiling2d!($float);
                 })*
             }
+
+            $crate::testgen_matmul_tiling2d!(@fixed_types);
         }
     };
 }
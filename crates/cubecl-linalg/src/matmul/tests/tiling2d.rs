@@ -1,8 +1,26 @@
 use std::fmt::Display;
 
-use cubecl_core::{CubeElement, Runtime, prelude::Float};
+use cubecl_core::{
+    CubeElement, Runtime,
+    ir::{Arithmetic, BinaryOperator, Instruction, Scope, Variable},
+    prelude::{CubePrimitive, Float, Int},
+};
+use half::f16;
 
-use crate::matmul::kernels::tiling2d;
+use crate::{
+    convolution::tests::test_utils::{small_integers, tensor_from_integers},
+    matmul::{
+        Strategy,
+        benchmark::{MatmulBenchmarkError, MatmulBenchmarkOptions, benchmark_matmul},
+        kernels::{
+            MatmulAvailabilityError, MatmulInvalidProblem, MatmulLaunchError,
+            tiling2d::{
+                self, CubeCountPolicy, FloatMode, MatmulEpilogue, MatmulMask, Tiling2dConfig,
+            },
+        },
+    },
+    tensor::{BitTensor, TensorHandle},
+};
 
 use super::test_utils::{MatmulTestCase, Sample, assert_equals_approx};
 
@@ -56,15 +74,2137 @@ pub fn test_with_batches<R: Runtime, F: Float + CubeElement + Display + Sample>(
     test_tiling2d::<R, F>(case, device);
 }
 
-fn test_tiling2d<R: Runtime, F: Float + CubeElement + Display + Sample>(
+pub fn test_batches_split_across_launches<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 5,
+    };
+
+    test_tiling2d_with_config::<R, F>(
+        case,
+        Tiling2dConfig {
+            max_batches_per_launch: Some(2),
+            mask: MatmulMask::CausalUpper,
+            ..Default::default()
+        },
+        device,
+    );
+}
+
+pub fn test_accumulate_across_launches<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let out = case.empty_out::<R, F>(&client);
+    let expected: Vec<F> = case
+        .matmul_cpu::<R, F>(&lhs, &rhs, &client)
+        .into_iter()
+        .map(|value| value * F::new(3.0))
+        .collect();
+
+    for accumulate in [false, true, true] {
+        tiling2d::launch_ref::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            Tiling2dConfig {
+                accumulate,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    }
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.03) {
+        panic!("{}", e);
+    }
+}
+
+/// Gradient accumulation, `out += lhs_i @ rhs_i` over three different products, starting from
+/// values already in the output.
+pub fn test_accumulate_distinct_products<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs_data = case.random_lhs::<R, F>(&client).to_vec(&client);
+    let rhs_data = case.random_rhs::<R, F>(&client).to_vec(&client);
+    let out = case.random_tensor::<R, F>(&client, vec![case.batch, case.m, case.n]);
+    let mut expected = out.to_vec(&client);
+
+    for step in 0..3 {
+        // Every step scales lhs and rotates rhs, so that each product differs.
+        let lhs_step: Vec<F> = lhs_data
+            .iter()
+            .map(|value| *value * F::new(1.0 + step as f32 * 0.5))
+            .collect();
+        let mut rhs_step = rhs_data.clone();
+        rhs_step.rotate_left(step * 7);
+
+        let product = case.matmul_cpu_algorithm(&lhs_step, &rhs_step);
+        for (expected, value) in expected.iter_mut().zip(product) {
+            *expected = *expected + value;
+        }
+
+        let lhs =
+            TensorHandle::<R, F>::from_data(&client, &lhs_step, &[case.batch, case.m, case.k]);
+        let rhs =
+            TensorHandle::<R, F>::from_data(&client, &rhs_step, &[case.batch, case.k, case.n]);
+        tiling2d::launch_ref::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            Tiling2dConfig {
+                accumulate: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    }
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.03) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_beta_zero_overwrites_output<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_beta::<R, F>(1.0, 0.0, device);
+}
+
+pub fn test_beta_one_accumulates<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_beta::<R, F>(1.0, 1.0, device);
+}
+
+pub fn test_fractional_alpha_and_beta<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_beta::<R, F>(0.5, 0.25, device);
+}
+
+pub fn test_relu_epilogue<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_activation::<R, F>(MatmulEpilogue::Relu, |x| x.max(0.0), device);
+}
+
+pub fn test_relu6_epilogue<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_activation::<R, F>(MatmulEpilogue::Relu6, |x| x.clamp(0.0, 6.0), device);
+}
+
+pub fn test_gelu_epilogue<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_activation::<R, F>(
+        MatmulEpilogue::Gelu,
+        |x| {
+            let inner = (2.0 / core::f32::consts::PI).sqrt() * (x + 0.044715 * x.powi(3));
+            0.5 * x * (1.0 + inner.tanh())
+        },
+        device,
+    );
+}
+
+pub fn test_sigmoid_epilogue<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_activation::<R, F>(
+        MatmulEpilogue::Sigmoid,
+        |x| 1.0 / (1.0 + (-x).exp()),
+        device,
+    );
+}
+
+pub fn test_silu_epilogue<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_activation::<R, F>(MatmulEpilogue::Silu, |x| x / (1.0 + (-x).exp()), device);
+}
+
+pub fn test_no_epilogue_is_unchanged<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_activation::<R, F>(MatmulEpilogue::None, |x| x, device);
+}
+
+pub fn test_with_causal_mask<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+
+    test_tiling2d_with_config::<R, F>(
+        case,
+        Tiling2dConfig {
+            mask: MatmulMask::CausalUpper,
+            ..Default::default()
+        },
+        device,
+    );
+}
+
+pub fn test_with_causal_mask_lower<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+
+    test_tiling2d_with_config::<R, F>(
+        case,
+        Tiling2dConfig {
+            mask: MatmulMask::CausalLower,
+            ..Default::default()
+        },
+        device,
+    );
+}
+
+pub fn test_with_alpha<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+
+    test_tiling2d_with_config::<R, F>(
+        case,
+        Tiling2dConfig {
+            alpha: 0.125,
+            mask: MatmulMask::CausalLower,
+            ..Default::default()
+        },
+        device,
+    );
+}
+
+pub fn test_padded_cube_count<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 60,
+        n: 130,
+        batch: 2,
+    };
+
+    test_tiling2d_with_config::<R, F>(case, padded_config(), device);
+}
+
+pub fn test_padded_cube_count_without_check_bounds<
+    R: Runtime,
+    F: Float + CubeElement + Display + Sample,
+>(
+    device: &R::Device,
+) {
+    // The padded cubes are the only ones outside the output, no block is partial.
+    test_tiling2d_with_config::<R, F>(divisible_case(), padded_config(), device);
+}
+
+pub fn test_fast_float_mode<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+
+    test_tiling2d_with_config::<R, F>(
+        case,
+        Tiling2dConfig {
+            float_mode: FloatMode {
+                flush_denormals: true,
+                allow_reassociation: true,
+            },
+            ..Default::default()
+        },
+        device,
+    );
+}
+
+pub fn test_precise_float_mode_is_reproducible<
+    R: Runtime,
+    F: Float + CubeElement + Display + Sample,
+>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 96,
+        n: 68,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let config = Tiling2dConfig {
+        float_mode: FloatMode::default(),
+        ..Default::default()
+    };
+
+    let outputs = [0, 1].map(|_| {
+        let out = case.empty_out::<R, F>(&client);
+        tiling2d::launch_ref::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            config.clone(),
+        )
+        .unwrap();
+        client.read_one(out.handle.binding())
+    });
+
+    assert_eq!(
+        outputs[0], outputs[1],
+        "precise results differ between launches."
+    );
+}
+
+pub fn test_asymmetric_blocks<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 96,
+        k: 64,
+        n: 192,
+        batch: 2,
+    };
+
+    test_tiling2d_with_config::<R, F>(case, asymmetric_config(), device);
+}
+
+pub fn test_asymmetric_blocks_transposed<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 96,
+        k: 64,
+        n: 192,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = transposed::<R, F>(case.random_tensor(&client, vec![case.batch, case.k, case.m]));
+    let rhs = transposed::<R, F>(case.random_tensor(&client, vec![case.batch, case.n, case.k]));
+
+    let expected = case.matmul_cpu_algorithm(&lhs.to_vec(&client), &rhs.to_vec(&client));
+
+    let out = tiling2d::launch::<R, F>(
+        &client,
+        lhs,
+        rhs,
+        case.empty_out(&client),
+        asymmetric_config(),
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_line_size_8<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    // n is divisible by 8 but not by 16.
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 72,
+        batch: 2,
+    };
+
+    if !R::line_size_elem(&F::as_elem_native_unchecked()).any(|v| v == 8) {
+        return;
+    }
+
+    let line_sizes = tiling2d::operand_line_sizes::<R, F, F>(
+        &line_size_8_config(),
+        case.m,
+        case.k,
+        case.n,
+        false,
+        false,
+        false,
+    );
+    assert!(matches!(line_sizes, Ok((8, 8, 8))));
+
+    test_tiling2d_with_config::<R, F>(case, line_size_8_config(), device);
+}
+
+pub fn test_line_size_8_transposed_lhs<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    // The transposed lhs is vectorized along m, divisible by 8 but not by 16.
+    let case = MatmulTestCase {
+        m: 72,
+        k: 64,
+        n: 64,
+        batch: 2,
+    };
+
+    if !R::line_size_elem(&F::as_elem_native_unchecked()).any(|v| v == 8) {
+        return;
+    }
+
+    let line_sizes = tiling2d::operand_line_sizes::<R, F, F>(
+        &line_size_8_config(),
+        case.m,
+        case.k,
+        case.n,
+        true,
+        false,
+        false,
+    );
+    assert!(matches!(line_sizes, Ok((8, 8, 8))));
+
+    let client = R::client(device);
+    let lhs = transposed::<R, F>(case.random_tensor(&client, vec![case.batch, case.k, case.m]));
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected = case.matmul_cpu_algorithm(&lhs.to_vec(&client), &rhs.to_vec(&client));
+
+    let out = tiling2d::launch::<R, F>(
+        &client,
+        lhs,
+        rhs,
+        case.empty_out(&client),
+        line_size_8_config(),
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_plain_operands<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_layouts::<R, F>(divisible_case(), false, false, device);
+}
+
+pub fn test_transposed_lhs<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_layouts::<R, F>(divisible_case(), true, false, device);
+}
+
+pub fn test_transposed_rhs<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_layouts::<R, F>(divisible_case(), false, true, device);
+}
+
+pub fn test_transposed_both<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_with_layouts::<R, F>(divisible_case(), true, true, device);
+}
+
+/// Overridden line sizes, with a transposed lhs read as single elements, give the same result
+/// as the automatic ones.
+pub fn test_line_size_override<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = divisible_case();
+    let client = R::client(device);
+
+    let lhs = transposed::<R, F>(case.random_tensor(&client, vec![case.batch, case.k, case.m]));
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected = case.matmul_cpu_algorithm(&lhs.to_vec(&client), &rhs.to_vec(&client));
+
+    let config = Tiling2dConfig {
+        lhs_line_size: Some(1),
+        rhs_line_size: Some(1),
+        out_line_size: Some(1),
+        ..Default::default()
+    };
+    let out = tiling2d::launch::<R, F>(&client, lhs, rhs, case.empty_out(&client), config).unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+/// A line size that doesn't divide the axis it is read along is rejected before launching, also
+/// for the matrix-vector and small-matrix products that don't use it.
+pub fn test_invalid_line_size_override<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+
+    for (m, n) in [(64, 64), (1, 300), (20, 28)] {
+        let case = MatmulTestCase {
+            m,
+            k: 30,
+            n,
+            batch: 1,
+        };
+
+        let lhs = case.random_lhs::<R, F>(&client);
+        let rhs = case.random_rhs::<R, F>(&client);
+        let config = Tiling2dConfig {
+            lhs_line_size: Some(4),
+            ..Default::default()
+        };
+        let result = tiling2d::launch::<R, F>(&client, lhs, rhs, case.empty_out(&client), config);
+
+        assert!(matches!(
+            result.map_err(MatmulLaunchError::into_root),
+            Err(MatmulLaunchError::InvalidProblem(
+                MatmulInvalidProblem::InvalidLineSizeLhs {
+                    size: 30,
+                    line_size: 4
+                }
+            ))
+        ));
+    }
+}
+
+/// Block sizes don't divide the problem, so the transposed rhs is read as lines along k with
+/// bounds checks.
+pub fn test_transposed_rhs_with_check_bounds<
+    R: Runtime,
+    F: Float + CubeElement + Display + Sample,
+>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 36,
+        n: 68,
+        batch: 2,
+    };
+
+    test_tiling2d_with_layouts::<R, F>(case, false, true, device);
+}
+
+pub fn test_chunked<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 70,
+        k: 40,
+        n: 90,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    let lhs = F::sample(case.m * case.k, 999);
+    let rhs = F::sample(case.k * case.n, 998);
+    let expected = case.matmul_cpu_algorithm(&lhs, &rhs);
+
+    // Small enough to need several blocks of rows and columns.
+    let max_device_bytes = 10_000 * core::mem::size_of::<F>();
+    let out = tiling2d::matmul_chunked::<R, F>(
+        &client,
+        &lhs,
+        [case.m, case.k],
+        &rhs,
+        [case.k, case.n],
+        max_device_bytes,
+        Default::default(),
+    )
+    .unwrap();
+
+    let out = TensorHandle::<R, F>::from_data(&client, &out, &[case.m, case.n]);
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_multi_device<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 40,
+        k: 24,
+        n: 36,
+        batch: 5,
+    };
+    // The same device twice, which still goes through the split and gather.
+    let clients = [R::client(device), R::client(device)];
+
+    let lhs = F::sample(case.batch * case.m * case.k, 999);
+    let rhs = F::sample(case.batch * case.k * case.n, 998);
+    let expected = case.matmul_cpu_algorithm(&lhs, &rhs);
+
+    let mut out = vec![F::from_int(0); case.batch * case.m * case.n];
+    tiling2d::matmul_multi_device::<R, F>(
+        &clients,
+        &lhs,
+        &[case.batch, case.m, case.k],
+        &rhs,
+        &[case.batch, case.k, case.n],
+        &mut out,
+        Default::default(),
+    )
+    .unwrap();
+
+    let out = TensorHandle::<R, F>::from_data(&clients[0], &out, &[case.batch, case.m, case.n]);
+    if let Err(e) = assert_equals_approx::<R, F>(&clients[0], out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_with_buffers<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 32,
+        n: 64,
+        batch: 3,
+    };
+    let client = R::client(device);
+
+    // Batches interleaved with rows, which needs a contiguous copy.
+    let mut lhs = case.random_tensor::<R, F>(&client, vec![case.m, case.batch, case.k]);
+    lhs.shape.swap(0, 1);
+    lhs.strides.swap(0, 1);
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    let expected = case.matmul_cpu_algorithm(&lhs.to_vec(&client), &rhs.to_vec(&client));
+    let mut buffers = tiling2d::ContiguousBuffers::default();
+
+    for _ in 0..2 {
+        let out = case.empty_out::<R, F>(&client);
+        tiling2d::launch_ref_with_buffers::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            Default::default(),
+            &mut buffers,
+        )
+        .unwrap();
+
+        if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+            panic!("{}", e);
+        }
+    }
+
+    let lhs_buffer = buffers.lhs.expect("Lhs should have been copied");
+    assert_eq!(lhs_buffer.shape, lhs.shape);
+    assert!(buffers.rhs.is_none(), "Rhs doesn't need a copy");
+}
+
+/// Rank 4 operands are multiplied in place, the batch offsets being computed from the strides of
+/// every batch dimension, even when they are swapped in memory.
+pub fn test_rank_4_swapped_batches<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let (batch_0, batch_1) = (2, 3);
+    let case = MatmulTestCase {
+        m: 40,
+        k: 24,
+        n: 36,
+        batch: batch_0 * batch_1,
+    };
+    let client = R::client(device);
+
+    // A `[batch_1, batch_0, m, k]` tensor viewed as `[batch_0, batch_1, m, k]`.
+    let mut lhs = case.random_tensor::<R, F>(&client, vec![batch_1, batch_0, case.m, case.k]);
+    lhs.shape.swap(0, 1);
+    lhs.strides.swap(0, 1);
+    let rhs = case.random_tensor::<R, F>(&client, vec![batch_0, batch_1, case.k, case.n]);
+
+    let expected = case.matmul_cpu_algorithm(&lhs.to_vec(&client), &rhs.to_vec(&client));
+    let mut buffers = tiling2d::ContiguousBuffers::default();
+
+    let out = TensorHandle::<R, F>::empty(&client, vec![batch_0, batch_1, case.m, case.n]);
+    tiling2d::launch_ref_with_buffers::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+        &mut buffers,
+    )
+    .unwrap();
+
+    assert!(buffers.lhs.is_none(), "Swapped batches don't need a copy");
+    assert!(buffers.rhs.is_none(), "Rhs doesn't need a copy");
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_integer<R: Runtime, I: Int + CubeElement + Display>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 20,
+        k: 36,
+        n: 28,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    // Small values with both signs, so the sums are exact in any integer type.
+    let sample = |len: usize, seed: i64| {
+        (0..len as i64)
+            .map(|i| I::from_int((i * 7 + seed) % 11 - 5))
+            .collect::<Vec<_>>()
+    };
+    let lhs_data = sample(case.batch * case.m * case.k, 3);
+    let rhs_data = sample(case.batch * case.k * case.n, 5);
+
+    let mut expected = vec![I::from_int(0); case.batch * case.m * case.n];
+    for b in 0..case.batch {
+        for i in 0..case.m {
+            for j in 0..case.n {
+                let mut sum = I::from_int(0);
+                for l in 0..case.k {
+                    let lhs = lhs_data[(b * case.m + i) * case.k + l];
+                    let rhs = rhs_data[(b * case.k + l) * case.n + j];
+                    sum += lhs * rhs;
+                }
+                expected[(b * case.m + i) * case.n + j] = sum;
+            }
+        }
+    }
+
+    let lhs = TensorHandle::<R, I>::from_data(&client, &lhs_data, &[case.batch, case.m, case.k]);
+    let rhs = TensorHandle::<R, I>::from_data(&client, &rhs_data, &[case.batch, case.k, case.n]);
+    let out = TensorHandle::<R, I>::empty(&client, vec![case.batch, case.m, case.n]);
+
+    let result = tiling2d::launch::<R, I>(&client, lhs, rhs, out, Default::default());
+    match result.map_err(MatmulLaunchError::into_root) {
+        Ok(out) => assert_eq!(out.to_vec(&client), expected),
+        // Some backends don't support every integer type, e.g. 64-bit ones.
+        Err(MatmulLaunchError::Unavailable(MatmulAvailabilityError::TypesUnavailable {
+            ..
+        })) => {}
+        Err(err) => panic!("{err}"),
+    }
+}
+
+pub fn test_f64<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+
+    // Lines hold at most 2 f64 elements, the dimensions divisible by 4 checking that wider lines
+    // aren't used. The single column is computed as a matrix-vector product.
+    for (m, k, n) in [(64, 64, 64), (20, 36, 28), (40, 32, 1)] {
+        let case = MatmulTestCase { m, k, n, batch: 2 };
+        let lhs = case.random_lhs::<R, f64>(&client);
+        let rhs = case.random_rhs::<R, f64>(&client);
+        let expected = matmul_cpu_f64(&case, &lhs.to_vec(&client), &rhs.to_vec(&client));
+        let out = case.empty_out::<R, f64>(&client);
+
+        let result = tiling2d::launch::<R, f64>(&client, lhs, rhs, out, Default::default());
+
+        match result.map_err(MatmulLaunchError::into_root) {
+            Ok(out) => {
+                for (actual, expected) in out.to_vec(&client).iter().zip(&expected) {
+                    assert!((actual - expected).abs() <= 1e-12, "{actual} != {expected}");
+                }
+            }
+            // f64 isn't available on every backend, e.g. wgpu without the shader extension.
+            Err(MatmulLaunchError::Unavailable(MatmulAvailabilityError::TypesUnavailable {
+                input,
+                output,
+            })) => {
+                assert!(!f64::is_supported(&client));
+                assert_eq!((input, output), (f64::as_elem_native_unchecked(), input));
+            }
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+pub fn test_i8_with_i32_accumulation<R: Runtime>(device: &R::Device) {
+    // k and n are multiples of 4, so the operands are loaded in lines of four i8.
+    let case = MatmulTestCase {
+        m: 24,
+        k: 64,
+        n: 40,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    // The whole i8 range, so the sums overflow i8 and i16 but not i32.
+    let sample = |len: usize, seed: i64| {
+        (0..len as i64)
+            .map(|i| ((i * 37 + seed) % 256 - 128) as i8)
+            .collect::<Vec<_>>()
+    };
+    let lhs_data = sample(case.batch * case.m * case.k, 3);
+    let rhs_data = sample(case.batch * case.k * case.n, 5);
+
+    let mut expected = vec![0i32; case.batch * case.m * case.n];
+    for b in 0..case.batch {
+        for i in 0..case.m {
+            for j in 0..case.n {
+                let mut sum = 0i32;
+                for l in 0..case.k {
+                    let lhs = lhs_data[(b * case.m + i) * case.k + l] as i32;
+                    let rhs = rhs_data[(b * case.k + l) * case.n + j] as i32;
+                    sum += lhs * rhs;
+                }
+                expected[(b * case.m + i) * case.n + j] = sum;
+            }
+        }
+    }
+
+    let lhs = TensorHandle::<R, i8>::from_data(&client, &lhs_data, &[case.batch, case.m, case.k]);
+    let rhs = TensorHandle::<R, i8>::from_data(&client, &rhs_data, &[case.batch, case.k, case.n]);
+    let out = TensorHandle::<R, i32>::empty(&client, vec![case.batch, case.m, case.n]);
+
+    let result = tiling2d::launch_mixed::<R, i8, i32, i32>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    );
+
+    match result.map_err(MatmulLaunchError::into_root) {
+        Ok(()) => assert_eq!(out.to_vec(&client), expected),
+        // i8 isn't available on every backend, e.g. wgpu without the shader extensions.
+        Err(MatmulLaunchError::Unavailable(MatmulAvailabilityError::TypesUnavailable {
+            ..
+        })) => {}
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// 8-bit float operands, converted to the `f32` accumulator when multiplied and written as `f16`.
+///
+/// Skipped on devices without the 8-bit float type, where the launch returns
+/// [TypesUnavailable](MatmulAvailabilityError::TypesUnavailable) before compiling anything.
+pub fn test_fp8_with_f16_output<R: Runtime, F: Float + CubeElement>(device: &R::Device) {
+    // k and n are multiples of 4, so the operands are loaded in lines of four 8-bit floats.
+    let case = MatmulTestCase {
+        m: 24,
+        k: 64,
+        n: 40,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs_data = small_integers(case.batch * case.m * case.k, 37, 3);
+    let rhs_data = small_integers(case.batch * case.k * case.n, 37, 5);
+
+    let mut expected = vec![f16::from_f32(0.0); case.batch * case.m * case.n];
+    for b in 0..case.batch {
+        for i in 0..case.m {
+            for j in 0..case.n {
+                let mut sum = 0;
+                for l in 0..case.k {
+                    let lhs = lhs_data[(b * case.m + i) * case.k + l];
+                    let rhs = rhs_data[(b * case.k + l) * case.n + j];
+                    sum += lhs * rhs;
+                }
+                expected[(b * case.m + i) * case.n + j] = f16::from_f32(sum as f32);
+            }
+        }
+    }
+
+    let lhs = tensor_from_integers::<R, F>(&client, &lhs_data, &[case.batch, case.m, case.k]);
+    let rhs = tensor_from_integers::<R, F>(&client, &rhs_data, &[case.batch, case.k, case.n]);
+    let out = TensorHandle::<R, f16>::empty(&client, vec![case.batch, case.m, case.n]);
+
+    let result = tiling2d::launch_mixed::<R, F, f32, f16>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    );
+
+    match result.map_err(MatmulLaunchError::into_root) {
+        Ok(()) => assert_eq!(out.to_vec(&client), expected),
+        Err(MatmulLaunchError::Unavailable(MatmulAvailabilityError::TypesUnavailable {
+            ..
+        })) => println!("Skipped - 8-bit floats not supported"),
+        Err(err) => panic!("{err}"),
+    }
+}
+
+pub fn test_float_type_availability<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let out = case.empty_out::<R, F>(&client);
+
+    let result = tiling2d::launch_ref::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    );
+    // Safety: the problem is valid and the default config fits on every device.
+    let unchecked_result = unsafe {
+        tiling2d::launch_unchecked::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            Default::default(),
+        )
+    };
+
+    // Unsupported types are reported before any kernel is compiled.
+    let elem = F::as_elem_native_unchecked();
+    for result in [result, unchecked_result] {
+        match F::is_supported(&client) {
+            true => assert!(result.is_ok()),
+            false => assert!(matches!(
+                result.map_err(MatmulLaunchError::into_root),
+                Err(MatmulLaunchError::Unavailable(
+                    MatmulAvailabilityError::TypesUnavailable { input, output }
+                )) if input == elem && output == elem
+            )),
+        }
+    }
+}
+
+pub fn test_output_too_small<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    // Only room for the first batch.
+    let out = TensorHandle::<R, F>::new_contiguous(
+        vec![case.batch, case.m, case.n],
+        client.empty(case.m * case.n * core::mem::size_of::<F>()),
+    );
+
+    let result = tiling2d::launch::<R, F>(&client, lhs, rhs, out, Default::default());
+
+    match result.map_err(MatmulLaunchError::into_root) {
+        Err(MatmulLaunchError::OutputTooSmall { required, actual }) => {
+            let expected = case.batch * case.m * case.n * core::mem::size_of::<F>();
+            assert_eq!(required, expected as u64);
+            assert!(actual < required);
+        }
+        _ => panic!("Expected the output to be rejected"),
+    }
+}
+
+pub fn test_output_size_overflow<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+
+    // Only the shapes are inspected, the problem is rejected before any buffer is read.
+    let huge = usize::MAX / 2;
+    let lhs = TensorHandle::<R, F>::new(vec![huge, 4], vec![4, 1], client.empty(16));
+    let rhs = TensorHandle::<R, F>::new(vec![4, 4], vec![4, 1], client.empty(16));
+    let out = TensorHandle::<R, F>::new(vec![huge, 4], vec![4, 1], client.empty(16));
+
+    let result = tiling2d::launch::<R, F>(&client, lhs, rhs, out, Default::default());
+
+    assert!(matches!(
+        result.map_err(MatmulLaunchError::into_root),
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::SizeOverflow(_)
+        ))
+    ));
+}
+
+pub fn test_invalid_block_sizes<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let out = case.empty_out(&client);
+    let config = Tiling2dConfig {
+        block_size_m: 64,
+        block_size_k: 32,
+        block_size_n: 62,
+        ..Default::default()
+    };
+    let elem_size = core::mem::size_of::<F>();
+    assert!(config.validate::<R>(&client, elem_size).is_err());
+
+    let result = tiling2d::launch::<R, F>(&client, lhs, rhs, out, config);
+
+    assert!(matches!(
+        result.map_err(MatmulLaunchError::into_root),
+        Err(MatmulLaunchError::Unavailable(
+            MatmulAvailabilityError::InvalidBlockSizes {
+                block_size_n: 62,
+                tile_size: 4,
+                ..
+            }
+        ))
+    ));
+}
+
+pub fn test_shared_memory_limit_exceeded<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let out = case.empty_out(&client);
+    let config = Tiling2dConfig {
+        block_size_m: 1 << 16,
+        block_size_k: 1 << 16,
+        block_size_n: 1 << 16,
+        ..Default::default()
+    };
+
+    let result = tiling2d::launch::<R, F>(&client, lhs, rhs, out, config);
+
+    assert!(matches!(
+        result.map_err(MatmulLaunchError::into_root),
+        Err(MatmulLaunchError::Unavailable(
+            MatmulAvailabilityError::SharedMemoryLimitExceeded {
+                block_size_m: 65536,
+                block_size_k: 65536,
+                block_size_n: 65536,
+                ..
+            }
+        ))
+    ));
+}
+
+pub fn test_permuted_output<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    // The batch dimension is swapped with the rows.
+    let strides = vec![case.n, case.batch * case.n, 1];
+    let out = TensorHandle::<R, F>::new(
+        vec![case.batch, case.m, case.n],
+        strides.clone(),
+        case.empty_out::<R, F>(&client).handle,
+    );
+
+    let result = tiling2d::launch::<R, F>(&client, lhs, rhs, out, Default::default());
+
+    match result.map_err(MatmulLaunchError::into_root) {
+        Err(MatmulLaunchError::InvalidLayout { strides: actual }) => assert_eq!(actual, strides),
+        _ => panic!("Expected an invalid layout error"),
+    }
+}
+
+pub fn test_error_context<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 16,
+        k: 24,
+        n: 8,
+        batch: 3,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_tensor::<R, F>(&client, vec![case.batch, case.k / 2, case.n]);
+    let out = case.empty_out(&client);
+    let config = Tiling2dConfig {
+        block_size_k: 16,
+        ..Default::default()
+    };
+
+    let error = tiling2d::launch::<R, F>(&client, lhs, rhs, out, config)
+        .err()
+        .expect("Mismatched k should be rejected");
+
+    let context = error.context().expect("Missing context");
+    assert_eq!((context.m, context.k, context.n), (16, 24, 8));
+    assert_eq!(context.batches, 3);
+    assert_eq!(context.elem, F::as_elem_native_unchecked());
+    let config = context.config.as_ref().expect("Missing config");
+    assert_eq!(config.block_size_k, 16);
+
+    let message = error.to_string();
+    assert!(message.contains("[3, 16, 24]"), "{message}");
+    assert!(message.contains("m=16, k=24, n=8"), "{message}");
+    assert!(message.contains("3 batches"), "{message}");
+    assert!(message.contains("block_size_k: 16"), "{message}");
+
+    // The context doesn't change the cause the error is matched on.
+    match error.into_root() {
+        MatmulLaunchError::InvalidShape { lhs, rhs } => {
+            assert_eq!((lhs, rhs), (vec![3, 16, 24], vec![3, 12, 8]))
+        }
+        error => panic!("Expected an invalid shape error, got {error}"),
+    }
+}
+
+pub fn test_mismatched_k<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_tensor::<R, F>(&client, vec![case.batch, case.k / 2, case.n]);
+
+    let out = case.empty_out(&client);
+    let result = tiling2d::launch::<R, F>(&client, lhs, rhs, out, Default::default());
+
+    match result.map_err(MatmulLaunchError::into_root) {
+        Err(MatmulLaunchError::InvalidShape { lhs, rhs }) => {
+            assert_eq!((lhs, rhs), (vec![1, 64, 64], vec![1, 32, 64]))
+        }
+        _ => panic!("Expected an invalid shape error"),
+    }
+}
+
+pub fn test_broadcast_batch<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    test_tiling2d_broadcast::<R, F>(&[1], &[3], device);
+    test_tiling2d_broadcast::<R, F>(&[3], &[1], device);
+    test_tiling2d_broadcast::<R, F>(&[2, 1], &[1, 3], device);
+}
+
+pub fn test_alloc_output<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 6,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_tensor::<R, F>(&client, vec![2, 1, case.m, case.k]);
+    let rhs = case.random_tensor::<R, F>(&client, vec![1, 3, case.k, case.n]);
+    let expected = matmul_cpu_broadcast(
+        &case,
+        &lhs.to_vec(&client),
+        &[2, 1],
+        &rhs.to_vec(&client),
+        &[1, 3],
+    );
+
+    let out = tiling2d::launch_alloc::<R, F>(&client, lhs, rhs, Default::default()).unwrap();
+
+    assert_eq!(out.shape, vec![2, 3, case.m, case.n]);
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_alloc_invalid_shape<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 8,
+        k: 8,
+        n: 8,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    for (lhs_shape, rhs_shape) in [
+        (vec![8, 8], vec![4, 8]),
+        (vec![8], vec![8, 8]),
+        (vec![2, 8, 8], vec![8, 8]),
+    ] {
+        let lhs = case.random_tensor::<R, F>(&client, lhs_shape.clone());
+        let rhs = case.random_tensor::<R, F>(&client, rhs_shape.clone());
+
+        match tiling2d::launch_alloc::<R, F>(&client, lhs, rhs, Default::default()) {
+            Err(MatmulLaunchError::InvalidShape { lhs, rhs }) => {
+                assert_eq!((lhs, rhs), (lhs_shape, rhs_shape))
+            }
+            _ => panic!("Expected an invalid shape error"),
+        }
+    }
+}
+
+pub fn test_broadcast_stride_zero<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 3,
+    };
+    let client = R::client(device);
+
+    // A single lhs matrix expanded to every batch without copying it.
+    let lhs_matrix = case.random_tensor::<R, F>(&client, vec![case.m, case.k]);
+    let lhs = TensorHandle::<R, F>::new(
+        vec![case.batch, case.m, case.k],
+        vec![0, case.k, 1],
+        lhs_matrix.handle.clone(),
+    );
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    let expected = matmul_cpu_broadcast(
+        &case,
+        &lhs_matrix.to_vec(&client),
+        &[1],
+        &rhs.to_vec(&client),
+        &[case.batch],
+    );
+    let out = case.empty_out::<R, F>(&client);
+
+    tiling2d::launch_ref::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_mismatched_batch<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 3,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_tensor::<R, F>(&client, vec![2, case.m, case.k]);
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    let out = case.empty_out(&client);
+    let result = tiling2d::launch::<R, F>(&client, lhs, rhs, out, Default::default());
+
+    assert!(matches!(
+        result.map_err(MatmulLaunchError::into_root),
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::MismatchedBatch {
+                dim: 0,
+                lhs: 2,
+                rhs: 3
+            }
+        ))
+    ));
+}
+
+pub fn test_unchecked<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+    let out = case.empty_out::<R, F>(&client);
+
+    // Safety: the tensors are contiguous with matching shapes, and the default config fits in
+    // shared memory.
+    unsafe {
+        tiling2d::launch_unchecked::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            Default::default(),
+        )
+        .unwrap();
+    }
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_autotune<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 100,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+
+    // The second launch reuses the config selected by the first one.
+    for _ in 0..2 {
+        let out = case.empty_out::<R, F>(&client);
+        tiling2d::launch_autotune::<R, F>(
+            &client,
+            device,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+        )
+        .unwrap();
+
+        if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+            panic!("{}", e);
+        }
+    }
+}
+
+pub fn test_matmul_autotune<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 100,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+
+    // The second launch reuses the kernel selected by the first one.
+    for _ in 0..2 {
+        let out = case.empty_out::<R, F>(&client);
+        crate::matmul::matmul_autotune::<R, F>(
+            &client,
+            device,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+        )
+        .unwrap();
+
+        if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+            panic!("{}", e);
+        }
+    }
+
+    // A transposed output has its own key, without the naive kernel.
+    let mut column_major = expected.clone();
+    for b in 0..case.batch {
+        for row in 0..case.m {
+            for col in 0..case.n {
+                column_major[(b * case.n + col) * case.m + row] =
+                    expected[(b * case.m + row) * case.n + col];
+            }
+        }
+    }
+
+    let out = transposed::<R, F>(TensorHandle::empty(
+        &client,
+        vec![case.batch, case.n, case.m],
+    ));
+    crate::matmul::matmul_autotune::<R, F>(
+        &client,
+        device,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &column_major, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_split_k<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    // The last split only covers part of its block along k.
+    let case = MatmulTestCase {
+        m: 40,
+        k: 600,
+        n: 36,
+        batch: 2,
+    };
+
+    test_split_k_with_config::<R, F>(case, Default::default(), 4, device);
+}
+
+pub fn test_split_k_with_alpha_and_mask<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 40,
+        k: 512,
+        n: 40,
+        batch: 2,
+    };
+
+    test_split_k_with_config::<R, F>(
+        case,
+        Tiling2dConfig {
+            alpha: 0.125,
+            mask: MatmulMask::CausalUpper,
+            ..Default::default()
+        },
+        3,
+        device,
+    );
+}
+
+pub fn test_with_epilogue<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected: Vec<F> = case
+        .matmul_cpu::<R, F>(&lhs, &rhs, &client)
+        .into_iter()
+        .map(|value| value * value)
+        .collect();
+    let out = case.empty_out::<R, F>(&client);
+
+    tiling2d::launch_with_epilogue::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+        (),
+        |value, scope| {
+            let squared = *scope.create_local(value.item);
+            scope.register(Instruction::new(
+                Arithmetic::Mul(BinaryOperator {
+                    lhs: value,
+                    rhs: value,
+                }),
+                squared,
+            ));
+            squared
+        },
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+/// Two epilogues of the same closure type that emit different instructions, depending on a
+/// captured flag, are compiled separately thanks to their key.
+pub fn test_epilogue_key<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 32,
+        k: 32,
+        n: 32,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let product = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+
+    let square_if = |square: bool| {
+        move |value: Variable, scope: &mut Scope| {
+            if !square {
+                return value;
+            }
+            let squared = *scope.create_local(value.item);
+            scope.register(Instruction::new(
+                Arithmetic::Mul(BinaryOperator {
+                    lhs: value,
+                    rhs: value,
+                }),
+                squared,
+            ));
+            squared
+        }
+    };
+
+    for square in [true, false] {
+        let expected: Vec<F> = match square {
+            true => product.iter().map(|value| *value * *value).collect(),
+            false => product.clone(),
+        };
+        let out = case.empty_out::<R, F>(&client);
+
+        tiling2d::launch_with_epilogue::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            Default::default(),
+            square,
+            square_if(square),
+        )
+        .unwrap();
+
+        if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+            panic!("{}", e);
+        }
+    }
+}
+
+pub fn test_with_packed_mask<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    // m * n isn't a multiple of 32, so the last word is only partially used.
+    let keep = (0..case.m * case.n)
+        .map(|index| (index / case.n * 7 + index % case.n * 3) % 5 != 0)
+        .collect::<Vec<_>>();
+    let mask = BitTensor::<R>::from_bools(&client, &keep, vec![case.m, case.n]);
+
+    let mut expected = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+    for (index, value) in expected.iter_mut().enumerate() {
+        if !keep[index % (case.m * case.n)] {
+            *value = F::NEG_INFINITY;
+        }
+    }
+    let out = case.empty_out::<R, F>(&client);
+
+    tiling2d::launch_with_packed_mask::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+        &mask,
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_packed_mask_invalid_rank<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 8,
+        k: 8,
+        n: 8,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    // The mask is sized from the operands, so their shapes are checked first.
+    let lhs = case.random_tensor::<R, F>(&client, vec![case.k]);
+    let rhs = case.random_tensor::<R, F>(&client, vec![case.k, case.n]);
+    let mask = BitTensor::<R>::from_bools(&client, &[true; 8], vec![case.n]);
+    let out = case.empty_out::<R, F>(&client);
+
+    let result = tiling2d::launch_with_packed_mask::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+        &mask,
+    );
+
+    match result.map_err(MatmulLaunchError::into_root) {
+        Err(MatmulLaunchError::InvalidShape { lhs, rhs }) => {
+            assert_eq!((lhs, rhs), (vec![case.k], vec![case.k, case.n]))
+        }
+        _ => panic!("Expected an invalid shape error"),
+    }
+}
+
+pub fn test_with_bias<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    // n isn't a multiple of the block size, so the last cubes only read part of the bias.
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let bias = case.random_tensor::<R, F>(&client, vec![case.n]);
+
+    let bias_values = bias.to_vec(&client);
+    let expected: Vec<F> = case
+        .matmul_cpu::<R, F>(&lhs, &rhs, &client)
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| value * F::new(0.5) + bias_values[index % case.n])
+        .collect();
+    let out = case.empty_out::<R, F>(&client);
+
+    tiling2d::launch_with_bias::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &bias.as_ref(),
+        &out.as_ref(),
+        Tiling2dConfig {
+            alpha: 0.5,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_bias_invalid_length<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let bias = case.random_tensor::<R, F>(&client, vec![case.n + 1]);
+    let out = case.empty_out::<R, F>(&client);
+
+    let result = tiling2d::launch_with_bias::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &bias.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    );
+
+    assert!(matches!(
+        result.map_err(MatmulLaunchError::into_root),
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::InvalidBiasLength { len: 65, n: 64 }
+        ))
+    ));
+}
+
+pub fn test_bias_invalid_rank<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 8,
+        k: 8,
+        n: 8,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    // The bias is sized from the last dimension of rhs, so a scalar rhs is rejected first.
+    let lhs = case.random_tensor::<R, F>(&client, vec![case.m, case.k]);
+    let rhs = case.random_tensor::<R, F>(&client, vec![]);
+    let bias = case.random_tensor::<R, F>(&client, vec![case.n]);
+    let out = case.empty_out::<R, F>(&client);
+
+    let result = tiling2d::launch_with_bias::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &bias.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    );
+
+    match result.map_err(MatmulLaunchError::into_root) {
+        Err(MatmulLaunchError::InvalidShape { lhs, rhs }) => {
+            assert_eq!((lhs, rhs), (vec![case.m, case.k], Vec::<usize>::new()))
+        }
+        _ => panic!("Expected an invalid shape error"),
+    }
+}
+
+pub fn test_mixed_f32_output<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected: Vec<f32> = matmul_cpu_f64(&case, &lhs.to_vec(&client), &rhs.to_vec(&client))
+        .into_iter()
+        .map(|value| value as f32)
+        .collect();
+    let out = case.empty_out::<R, f32>(&client);
+
+    tiling2d::launch_mixed::<R, F, f32, f32>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, f32>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_mixed_accuracy_large_k<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 16,
+        k: 8192,
+        n: 16,
+        batch: 1,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let reference = matmul_cpu_f64(&case, &lhs.to_vec(&client), &rhs.to_vec(&client));
+
+    let pure = case.empty_out::<R, F>(&client);
+    tiling2d::launch_ref::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &pure.as_ref(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let mixed = case.empty_out::<R, F>(&client);
+    tiling2d::launch_mixed::<R, F, f32, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &mixed.as_ref(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let max_error = |out: &TensorHandle<R, F>| {
+        out.to_vec(&client)
+            .iter()
+            .zip(&reference)
+            .map(|(actual, expected)| (actual.to_f64().unwrap() - expected).abs())
+            .fold(0.0, f64::max)
+    };
+    let pure_error = max_error(&pure);
+    let mixed_error = max_error(&mixed);
+    let max_reference = reference
+        .iter()
+        .map(|value| value.abs())
+        .fold(0.0, f64::max);
+
+    // Only the rounding of the output remains with an f32 accumulator.
+    assert!(
+        mixed_error <= 1e-2 * max_reference,
+        "mixed error {mixed_error} for results up to {max_reference}"
+    );
+    // Accumulating f16 or bf16 over a long k loses precision on every sum.
+    if size_of::<F>() < size_of::<f32>() {
+        assert!(
+            mixed_error <= pure_error,
+            "mixed error {mixed_error} above pure error {pure_error}"
+        );
+    }
+}
+
+pub fn test_gemv_column<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 300,
+        k: 200,
+        n: 1,
+        batch: 2,
+    };
+
+    test_untiled::<R, F>(case, false, false, Default::default(), false, device);
+}
+
+pub fn test_gemv_row<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 1,
+        k: 200,
+        n: 300,
+        batch: 2,
+    };
+
+    test_untiled::<R, F>(case, false, false, Default::default(), false, device);
+}
+
+pub fn test_gemv_transposed<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let layouts = [(true, false), (false, true), (true, true)];
+
+    for (m, n) in [(300, 1), (1, 300)] {
+        for (lhs_transposed, rhs_transposed) in layouts {
+            let case = MatmulTestCase {
+                m,
+                k: 200,
+                n,
+                batch: 2,
+            };
+
+            test_untiled::<R, F>(
+                case,
+                lhs_transposed,
+                rhs_transposed,
+                Default::default(),
+                false,
+                device,
+            );
+        }
+    }
+}
+
+pub fn test_gemv_with_beta_and_activation<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let config = Tiling2dConfig {
+        alpha: 0.5,
+        beta: 2.0,
+        epilogue: MatmulEpilogue::Relu,
+        ..Default::default()
+    };
+
+    for (m, n) in [(300, 1), (1, 300)] {
+        let case = MatmulTestCase {
+            m,
+            k: 200,
+            n,
+            batch: 2,
+        };
+
+        test_untiled::<R, F>(case, false, false, config.clone(), false, device);
+    }
+}
+
+pub fn test_micro<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let layouts = [(false, false), (true, false), (false, true), (true, true)];
+
+    for (lhs_transposed, rhs_transposed) in layouts {
+        let case = MatmulTestCase {
+            m: 20,
+            k: 12,
+            n: 28,
+            batch: 3,
+        };
+
+        test_untiled::<R, F>(
+            case,
+            lhs_transposed,
+            rhs_transposed,
+            Default::default(),
+            true,
+            device,
+        );
+    }
+}
+
+/// Many batches of 16x16 matrices, each one a single cube of the small-matrix kernel.
+pub fn test_micro_many_batches<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 16,
+        k: 16,
+        n: 16,
+        batch: 512,
+    };
+
+    test_untiled::<R, F>(case, false, false, Default::default(), true, device);
+}
+
+pub fn test_micro_with_beta_and_activation<
+    R: Runtime,
+    F: Float + CubeElement + Display + Sample,
+>(
+    device: &R::Device,
+) {
+    let config = Tiling2dConfig {
+        alpha: 0.5,
+        beta: 2.0,
+        epilogue: MatmulEpilogue::Relu,
+        ..Default::default()
+    };
+    let case = MatmulTestCase {
+        m: 32,
+        k: 32,
+        n: 30,
+        batch: 2,
+    };
+
+    test_untiled::<R, F>(case, false, false, config, true, device);
+}
+
+pub fn test_empty_output<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+
+    for [batch, m, k, n] in [[2, 0, 16, 24], [2, 24, 16, 0], [0, 24, 16, 24]] {
+        let lhs = TensorHandle::<R, F>::empty(&client, vec![batch, m, k]);
+        let rhs = TensorHandle::<R, F>::empty(&client, vec![batch, k, n]);
+        let out = TensorHandle::<R, F>::empty(&client, vec![batch, m, n]);
+
+        tiling2d::launch_ref::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            Default::default(),
+        )
+        .unwrap();
+    }
+}
+
+pub fn test_zero_k<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 24,
+        k: 0,
+        n: 40,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = TensorHandle::<R, F>::empty(&client, vec![case.batch, case.m, case.k]);
+    let rhs = TensorHandle::<R, F>::empty(&client, vec![case.batch, case.k, case.n]);
+
+    // Zeros overwrite the output.
+    let out = case.random_tensor::<R, F>(&client, vec![case.batch, case.m, case.n]);
+    tiling2d::launch_ref::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let expected = vec![F::new(0.0); case.batch * case.m * case.n];
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+
+    // The activation and beta still apply to the zero products.
+    let out = case.random_tensor::<R, F>(&client, vec![case.batch, case.m, case.n]);
+    let expected: Vec<F> = out
+        .to_vec(&client)
+        .into_iter()
+        .map(|previous| F::new(0.5) + previous * F::new(2.0))
+        .collect();
+    tiling2d::launch_ref::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Tiling2dConfig {
+            epilogue: MatmulEpilogue::Sigmoid,
+            beta: 2.0,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.02) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_transposed_output<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let unaligned = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 2,
+    };
+
+    test_tiling2d_transposed_output::<R, F>(divisible_case(), Default::default(), 1, device);
+    test_tiling2d_transposed_output::<R, F>(unaligned, Default::default(), 1, device);
+}
+
+pub fn test_transposed_output_with_mask_and_split_k<
+    R: Runtime,
+    F: Float + CubeElement + Display + Sample,
+>(
+    device: &R::Device,
+) {
+    let config = Tiling2dConfig {
+        alpha: 0.5,
+        mask: MatmulMask::CausalUpper,
+        ..Default::default()
+    };
+    let case = MatmulTestCase {
+        m: 60,
+        k: 256,
+        n: 68,
+        batch: 2,
+    };
+
+    test_tiling2d_transposed_output::<R, F>(case, config, 4, device);
+}
+
+fn asymmetric_config() -> Tiling2dConfig {
+    Tiling2dConfig {
+        block_size_m: 32,
+        block_size_k: 16,
+        block_size_n: 128,
+        ..Default::default()
+    }
+}
+
+fn line_size_8_config() -> Tiling2dConfig {
+    Tiling2dConfig {
+        block_size_m: 64,
+        block_size_k: 32,
+        block_size_n: 64,
+        tile_size: 8,
+        ..Default::default()
+    }
+}
+
+fn padded_config() -> Tiling2dConfig {
+    Tiling2dConfig {
+        cube_count_policy: CubeCountPolicy::PadUp { multiple: 4 },
+        ..Default::default()
+    }
+}
+
+/// Swap the two last dimensions of a contiguous tensor without moving its data.
+fn transposed<R: Runtime, F: Float>(mut tensor: TensorHandle<R, F>) -> TensorHandle<R, F> {
+    let rank = tensor.shape.len();
+    tensor.shape.swap(rank - 2, rank - 1);
+    tensor.strides.swap(rank - 2, rank - 1);
+    tensor
+}
+
+/// Block sizes divide the problem, so that no bounds are checked.
+fn divisible_case() -> MatmulTestCase {
+    MatmulTestCase {
+        m: 64,
+        k: 96,
+        n: 128,
+        batch: 2,
+    }
+}
+
+fn test_tiling2d_with_layouts<R: Runtime, F: Float + CubeElement + Display + Sample>(
     case: MatmulTestCase,
+    lhs_transposed: bool,
+    rhs_transposed: bool,
     device: &R::Device,
 ) {
     let client = R::client(device);
-    let lhs = case.random_lhs::<R, F>(&client);
-    let rhs = case.random_rhs::<R, F>(&client);
 
-    let expected = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+    let lhs = match lhs_transposed {
+        true => transposed::<R, F>(case.random_tensor(&client, vec![case.batch, case.k, case.m])),
+        false => case.random_lhs::<R, F>(&client),
+    };
+    let rhs = match rhs_transposed {
+        true => transposed::<R, F>(case.random_tensor(&client, vec![case.batch, case.n, case.k])),
+        false => case.random_rhs::<R, F>(&client),
+    };
+
+    let expected = case.matmul_cpu_algorithm(&lhs.to_vec(&client), &rhs.to_vec(&client));
 
     let out = tiling2d::launch::<R, F>(
         &client,
@@ -72,9 +2212,439 @@ fn test_tiling2d<R: Runtime, F: Float + CubeElement + Display + Sample>(
         rhs,
         case.empty_out(&client),
         Default::default(),
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+fn test_tiling2d<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    case: MatmulTestCase,
+    device: &R::Device,
+) {
+    test_tiling2d_with_config::<R, F>(case, Default::default(), device);
+}
+
+fn test_tiling2d_with_config<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    case: MatmulTestCase,
+    config: Tiling2dConfig,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    let mut expected: Vec<F> = case
+        .matmul_cpu::<R, F>(&lhs, &rhs, &client)
+        .into_iter()
+        .map(|value| value * F::new(config.alpha))
+        .collect();
+    apply_mask_cpu(&mut expected, &case, config.mask);
+
+    let out = tiling2d::launch::<R, F>(&client, lhs, rhs, case.empty_out(&client), config).unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+fn test_tiling2d_with_activation<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    epilogue: MatmulEpilogue,
+    reference: impl Fn(f32) -> f32,
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected: Vec<F> = case
+        .matmul_cpu::<R, F>(&lhs, &rhs, &client)
+        .into_iter()
+        .map(|value| F::new(reference(value.to_f32().unwrap())))
+        .collect();
+    let out = case.empty_out::<R, F>(&client);
+
+    tiling2d::launch_ref::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Tiling2dConfig {
+            epilogue,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+        panic!("{}", e);
+    }
+}
+
+fn test_tiling2d_with_beta<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    alpha: f32,
+    beta: f32,
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: 2,
+    };
+    let client = R::client(device);
+
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let out = case.random_tensor::<R, F>(&client, vec![case.batch, case.m, case.n]);
+    let expected: Vec<F> = case
+        .matmul_cpu::<R, F>(&lhs, &rhs, &client)
+        .into_iter()
+        .zip(out.to_vec(&client))
+        .map(|(value, previous)| value * F::new(alpha) + previous * F::new(beta))
+        .collect();
+
+    tiling2d::launch_ref::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        Tiling2dConfig {
+            alpha,
+            beta,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.02) {
+        panic!("{}", e);
+    }
+}
+
+fn test_split_k_with_config<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    case: MatmulTestCase,
+    config: Tiling2dConfig,
+    splits: usize,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    let mut expected: Vec<F> = case
+        .matmul_cpu::<R, F>(&lhs, &rhs, &client)
+        .into_iter()
+        .map(|value| value * F::new(config.alpha))
+        .collect();
+    apply_mask_cpu(&mut expected, &case, config.mask);
+
+    let out = case.empty_out::<R, F>(&client);
+    tiling2d::launch_split_k::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        config,
+        splits,
+    )
+    .unwrap();
+
+    // The partial sums of each split round differently than a single pass along k.
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.03) {
+        panic!("{}", e);
+    }
+}
+
+/// Compares a product computed without tiles, a matrix-vector or a small-matrix one, with
+/// operands transposed as requested and an output filled with random values, against the tiled
+/// kernel on the same inputs.
+///
+/// `micro` tells which of the two the product is expected to be computed by.
+fn test_untiled<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    case: MatmulTestCase,
+    lhs_transposed: bool,
+    rhs_transposed: bool,
+    config: Tiling2dConfig,
+    micro: bool,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+
+    let lhs = match lhs_transposed {
+        true => transposed::<R, F>(case.random_tensor(&client, vec![case.batch, case.k, case.m])),
+        false => case.random_lhs::<R, F>(&client),
+    };
+    let rhs = match rhs_transposed {
+        true => transposed::<R, F>(case.random_tensor(&client, vec![case.batch, case.n, case.k])),
+        false => case.random_rhs::<R, F>(&client),
+    };
+    let out_shape = vec![case.batch, case.m, case.n];
+    let previous = case
+        .random_tensor::<R, F>(&client, out_shape.clone())
+        .to_vec(&client);
+    let out = TensorHandle::<R, F>::from_data(&client, &previous, &out_shape);
+    let reference = TensorHandle::<R, F>::from_data(&client, &previous, &out_shape);
+
+    assert_eq!(
+        tiling2d::uses_micro_kernel(&client, &lhs.as_ref(), &rhs.as_ref(), &config),
+        micro
+    );
+
+    tiling2d::launch_ref::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        config.clone(),
+    )
+    .unwrap();
+
+    // Safety: the operands are at most transposed with matching shapes, and the default block
+    // sizes fit in shared memory.
+    unsafe {
+        tiling2d::launch_unchecked::<R, F>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &reference.as_ref(),
+            config,
+        )
+        .unwrap();
+    }
+
+    // The plane reduction sums k in a different order than the tiled kernel.
+    let expected = reference.to_vec(&client);
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.02) {
+        panic!("{}", e);
+    }
+}
+
+/// Writes the product into a column-major output, a contiguous `[batch, n, m]` tensor viewed as
+/// `[batch, m, n]`, and checks its buffer in place.
+fn test_tiling2d_transposed_output<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    case: MatmulTestCase,
+    config: Tiling2dConfig,
+    splits: usize,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    let mut expected: Vec<F> = case
+        .matmul_cpu::<R, F>(&lhs, &rhs, &client)
+        .into_iter()
+        .map(|value| value * F::new(config.alpha))
+        .collect();
+    apply_mask_cpu(&mut expected, &case, config.mask);
+
+    let mut column_major = expected.clone();
+    for b in 0..case.batch {
+        for row in 0..case.m {
+            for col in 0..case.n {
+                column_major[(b * case.n + col) * case.m + row] =
+                    expected[(b * case.m + row) * case.n + col];
+            }
+        }
+    }
+
+    let out = transposed::<R, F>(TensorHandle::empty(
+        &client,
+        vec![case.batch, case.n, case.m],
+    ));
+
+    tiling2d::launch_split_k::<R, F>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        config,
+        splits,
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &column_major, 0.02) {
+        panic!("{}", e);
+    }
+}
+
+fn test_tiling2d_broadcast<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    lhs_batches: &[usize],
+    rhs_batches: &[usize],
+    device: &R::Device,
+) {
+    let out_batches: Vec<usize> = lhs_batches
+        .iter()
+        .zip(rhs_batches)
+        .map(|(lhs, rhs)| usize::max(*lhs, *rhs))
+        .collect();
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 68,
+        batch: out_batches.iter().product(),
+    };
+    let client = R::client(device);
+
+    let shape = |batches: &[usize], rows: usize, cols: usize| {
+        let mut shape = batches.to_vec();
+        shape.extend([rows, cols]);
+        shape
+    };
+    let lhs = case.random_tensor::<R, F>(&client, shape(lhs_batches, case.m, case.k));
+    let rhs = case.random_tensor::<R, F>(&client, shape(rhs_batches, case.k, case.n));
+
+    let expected = matmul_cpu_broadcast(
+        &case,
+        &lhs.to_vec(&client),
+        lhs_batches,
+        &rhs.to_vec(&client),
+        rhs_batches,
     );
+    let out = TensorHandle::<R, F>::empty(&client, shape(&out_batches, case.m, case.n));
+
+    let out = tiling2d::launch::<R, F>(&client, lhs, rhs, out, Default::default()).unwrap();
 
     if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
         panic!("{}", e);
     }
 }
+
+/// Reference matmul of contiguous operands whose batch dimensions of size 1 are broadcast to the
+/// `case.batch` batches of the output.
+fn matmul_cpu_broadcast<F: Float>(
+    case: &MatmulTestCase,
+    lhs: &[F],
+    lhs_batches: &[usize],
+    rhs: &[F],
+    rhs_batches: &[usize],
+) -> Vec<F> {
+    let mut out = vec![F::new(0.0); case.batch * case.m * case.n];
+
+    for batch in 0..case.batch {
+        // Walk the batch dimensions from the last one, like the strides of the output.
+        let (mut remaining, mut lhs_batch, mut rhs_batch) = (batch, 0, 0);
+        let (mut lhs_stride, mut rhs_stride) = (1, 1);
+        for (lhs_size, rhs_size) in lhs_batches.iter().zip(rhs_batches).rev() {
+            let out_size = usize::max(*lhs_size, *rhs_size);
+            let index = remaining % out_size;
+            remaining /= out_size;
+
+            lhs_batch += (index % lhs_size) * lhs_stride;
+            rhs_batch += (index % rhs_size) * rhs_stride;
+            lhs_stride *= lhs_size;
+            rhs_stride *= rhs_size;
+        }
+
+        for i in 0..case.m {
+            for j in 0..case.n {
+                let mut sum = 0.0;
+                for k in 0..case.k {
+                    let l = lhs[(lhs_batch * case.m + i) * case.k + k].to_f32().unwrap();
+                    let r = rhs[(rhs_batch * case.k + k) * case.n + j].to_f32().unwrap();
+                    sum += l * r;
+                }
+                out[(batch * case.m + i) * case.n + j] = F::new(sum);
+            }
+        }
+    }
+
+    out
+}
+
+/// Reference matmul of contiguous `[batch, m, k]` and `[batch, k, n]` operands, accumulated in f64.
+fn matmul_cpu_f64<F: Float>(case: &MatmulTestCase, lhs: &[F], rhs: &[F]) -> Vec<f64> {
+    let mut out = vec![0.0; case.batch * case.m * case.n];
+
+    for b in 0..case.batch {
+        for i in 0..case.m {
+            for j in 0..case.n {
+                let mut sum = 0.0;
+                for k in 0..case.k {
+                    let l = lhs[(b * case.m + i) * case.k + k].to_f64().unwrap();
+                    let r = rhs[(b * case.k + k) * case.n + j].to_f64().unwrap();
+                    sum += l * r;
+                }
+                out[(b * case.m + i) * case.n + j] = sum;
+            }
+        }
+    }
+
+    out
+}
+
+fn apply_mask_cpu<F: Float>(values: &mut [F], case: &MatmulTestCase, mask: MatmulMask) {
+    for (index, value) in values.iter_mut().enumerate() {
+        let row = (index / case.n) % case.m;
+        let col = index % case.n;
+
+        let masked = match mask {
+            MatmulMask::None => false,
+            MatmulMask::CausalUpper => col > row,
+            MatmulMask::CausalLower => col < row,
+        };
+
+        if masked {
+            *value = F::NEG_INFINITY;
+        }
+    }
+}
+
+pub fn test_benchmark<R: Runtime, F: Float + CubeElement + Display + Sample>(device: &R::Device) {
+    let client = R::client(device);
+    let options = MatmulBenchmarkOptions {
+        warmup: 1,
+        samples: 3,
+        verify: true,
+        ..Default::default()
+    };
+    let problems = [(1, 64, 64, 64), (2, 20, 12, 28)];
+
+    let results = benchmark_matmul::<R, F>(
+        &client,
+        &Strategy::Tiling2D(Default::default()),
+        &problems,
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(results.len(), problems.len());
+    for (result, &(batch, m, k, n)) in results.iter().zip(problems.iter()) {
+        assert_eq!(
+            (result.batch, result.m, result.k, result.n),
+            (batch, m, k, n)
+        );
+        assert_eq!(result.durations.durations.len(), 3);
+        assert!(result.min <= result.median);
+        assert_eq!(result.flops(), (2 * batch * m * k * n) as f64);
+        assert!(result.gflops() > 0.0);
+    }
+}
+
+pub fn test_benchmark_without_samples<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let options = MatmulBenchmarkOptions {
+        samples: 0,
+        ..Default::default()
+    };
+
+    let result = benchmark_matmul::<R, F>(
+        &client,
+        &Strategy::Tiling2D(Default::default()),
+        &[(1, 64, 64, 64)],
+        &options,
+    );
+
+    assert!(matches!(result, Err(MatmulBenchmarkError::NoSamples)));
+}
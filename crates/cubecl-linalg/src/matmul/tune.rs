@@ -0,0 +1,204 @@
+use core::marker::PhantomData;
+
+use cubecl_core::{
+    CubeTuneId,
+    ir::{Elem, FloatKind},
+    prelude::*,
+};
+use cubecl_runtime::tune::{AutotuneError, LocalTuner, Tunable, TunableSet};
+
+use crate::tensor::TensorHandle;
+
+use super::{
+    components::tile::accelerated::Accelerated,
+    kernels::{
+        MatmulLaunchError, accelerated_available,
+        matmul::{self, simple::SimpleAlgorithm},
+        naive,
+        tiling2d::{self, check_launch, launch_context},
+    },
+    tune_key::MatmulKernelAutotuneKey,
+};
+
+type MatmulInputs<R, F> = (TensorHandle<R, F>, TensorHandle<R, F>, TensorHandle<R, F>);
+
+/// Matrix multiplication with the kernel family selected by autotune: the tiling 2d kernel with
+/// its default config, the naive kernel, or the accelerated kernel using the tensor cores of the
+/// device.
+///
+/// The first call for a given device and [key](MatmulKernelAutotuneKey) benchmarks the kernels on
+/// zeroed operands of the largest shapes of the key, and later calls with the same key launch the
+/// fastest one after a single lookup. Kernels that can't run are left out: the accelerated kernel
+/// when [accelerated_available] fails, and the naive kernel for a transposed output. A kernel
+/// returning [MatmulLaunchError::Unavailable] while benchmarking, e.g. without tensor cores for the
+/// element type, is never selected. The error of the selected kernel, if it fails anyway, is
+/// returned as [MatmulLaunchError::Autotune].
+///
+/// The problem is validated as for [tiling2d::launch_ref], so the tiling 2d kernel is always
+/// available once it passes.
+pub fn matmul_autotune<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    device: &R::Device,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    static TUNER: LocalTuner<MatmulKernelAutotuneKey, CubeTuneId> = LocalTuner::new(module_path!());
+
+    check_launch::<R, F, F, F>(client, lhs, rhs, out)
+        .map_err(|error| error.with_context(launch_context::<R, F>(lhs, out, None)))?;
+
+    let owned = |tensor: &TensorHandleRef<'_, R>| {
+        TensorHandle::<R, F>::new(
+            tensor.shape.to_vec(),
+            tensor.strides.to_vec(),
+            tensor.handle.clone(),
+        )
+    };
+    let inputs = (owned(lhs), owned(rhs), owned(out));
+    let key = matmul_autotune_key(&inputs.0, &inputs.1, &inputs.2);
+
+    let rank = lhs.shape.len();
+    let input_client = client.clone();
+    let mut tunables = TunableSet::new(
+        matmul_autotune_key::<R, F>,
+        move |key: &MatmulKernelAutotuneKey,
+              lhs: &TensorHandle<R, F>,
+              rhs: &TensorHandle<R, F>,
+              out: &TensorHandle<R, F>| {
+            let (lhs_shape, rhs_shape) = key.matmul().tuning_shapes(&lhs.shape, &rhs.shape);
+            let mut out_shape = out.shape.clone();
+            out_shape[rank - 2] = lhs_shape[rank - 2];
+            out_shape[rank - 1] = rhs_shape[rank - 1];
+
+            // A transposed output is allocated as a contiguous `[.., n, m]` tensor.
+            let out = match key.out_transposed() {
+                true => {
+                    out_shape.swap(rank - 2, rank - 1);
+                    let mut out = TensorHandle::empty(&input_client, out_shape);
+                    out.shape.swap(rank - 2, rank - 1);
+                    out.strides.swap(rank - 2, rank - 1);
+                    out
+                }
+                false => TensorHandle::empty(&input_client, out_shape),
+            };
+
+            (
+                TensorHandle::zeros(&input_client, lhs_shape),
+                TensorHandle::zeros(&input_client, rhs_shape),
+                out,
+            )
+        },
+    );
+    for kernel in candidate_kernels(client, &key) {
+        tunables = tunables.with_tunable(MatmulKernelTunable::<R, F>::new(client.clone(), kernel));
+    }
+
+    TUNER
+        .execute(
+            &CubeTuneId::new::<R>(client, device),
+            client,
+            &tunables,
+            inputs,
+        )
+        .map_err(MatmulLaunchError::Autotune)
+}
+
+fn matmul_autotune_key<R: Runtime, F: Float>(
+    lhs: &TensorHandle<R, F>,
+    rhs: &TensorHandle<R, F>,
+    out: &TensorHandle<R, F>,
+) -> MatmulKernelAutotuneKey {
+    MatmulKernelAutotuneKey::generate(
+        &lhs.shape,
+        &rhs.shape,
+        &lhs.strides,
+        &rhs.strides,
+        &out.shape,
+        &out.strides,
+        F::as_elem_native_unchecked(),
+    )
+}
+
+/// Kernel family benchmarked by [matmul_autotune].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MatmulKernel {
+    Tiling2d,
+    Naive,
+    Accelerated,
+}
+
+/// Kernels that can run on the device for problems of `key`, the tiling 2d kernel first.
+///
+/// Only the device and the key decide which kernels are kept, so that every problem of the key
+/// benchmarks the same set and the selected index stays valid.
+fn candidate_kernels<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    key: &MatmulKernelAutotuneKey,
+) -> Vec<MatmulKernel> {
+    // The f16 instructions stand for every float type, the accelerated kernel failing anyway
+    // when benchmarked is never selected.
+    let accelerated = accelerated_available::<R>(
+        client,
+        Elem::Float(FloatKind::F16),
+        Elem::Float(FloatKind::F32),
+    );
+
+    let mut kernels = vec![MatmulKernel::Tiling2d];
+    if !key.out_transposed() {
+        kernels.push(MatmulKernel::Naive);
+    }
+    if accelerated.is_ok() {
+        kernels.push(MatmulKernel::Accelerated);
+    }
+
+    kernels
+}
+
+/// Matmul launch with a fixed kernel family.
+struct MatmulKernelTunable<R: Runtime, F: Float> {
+    client: ComputeClient<R::Server, R::Channel>,
+    kernel: MatmulKernel,
+    _elem: PhantomData<F>,
+}
+
+impl<R: Runtime, F: Float> MatmulKernelTunable<R, F> {
+    fn new(client: ComputeClient<R::Server, R::Channel>, kernel: MatmulKernel) -> Self {
+        Self {
+            client,
+            kernel,
+            _elem: PhantomData,
+        }
+    }
+}
+
+impl<R: Runtime, F: Float> Tunable for MatmulKernelTunable<R, F> {
+    type Inputs = MatmulInputs<R, F>;
+    type Output = ();
+
+    fn execute(&self, (lhs, rhs, out): Self::Inputs) -> Result<(), AutotuneError> {
+        let (lhs, rhs, out) = (lhs.as_ref(), rhs.as_ref(), out.as_ref());
+
+        match self.kernel {
+            MatmulKernel::Tiling2d => {
+                tiling2d::launch_ref::<R, F>(&self.client, &lhs, &rhs, &out, Default::default())
+            }
+            MatmulKernel::Naive => naive::launch_ref::<R, F>(&self.client, &lhs, &rhs, &out),
+            MatmulKernel::Accelerated => matmul::launch_ref::<R, F, SimpleAlgorithm<Accelerated>>(
+                &self.client,
+                &lhs,
+                &rhs,
+                &out,
+            ),
+        }
+        .map_err(Into::into)
+    }
+
+    fn name(&self) -> &str {
+        match self.kernel {
+            MatmulKernel::Tiling2d => "tiling2d",
+            MatmulKernel::Naive => "naive",
+            MatmulKernel::Accelerated => "accelerated",
+        }
+    }
+}
@@ -2,23 +2,19 @@ use std::cmp::max;
 
 use cubecl_core as cubecl;
 
-use cubecl_core::{AutotuneKey, ir::Elem};
+use cubecl_core::{AutotuneKey, ir::Elem, tune::anchor};
 use serde::{Deserialize, Serialize};
 
-use crate::tensor::{MatrixLayout, matrix_layout};
+use crate::tensor::{MatrixLayout, matrix_layout, strides_are_contiguous};
 
 #[derive(Hash, Eq, PartialEq, Debug, Clone, Serialize, Deserialize, AutotuneKey)]
 /// Autotune key representative of matmul versions
 pub struct MatmulAutotuneKey {
-    round: bool,     // True when all matmul dims are multiples of 64
     broadcast: bool, // True when there are differences in batch size
-    #[autotune(anchor)]
+    // The bucket of the problem, see [MatmulAutotuneKey::bucket].
     m: usize,
-    #[autotune(anchor)]
     k: usize,
-    #[autotune(anchor)]
     n: usize,
-    #[autotune(anchor(max = 256))]
     batch: usize,
     elem_inputs: Elem,
     elem_stage: Elem,
@@ -56,19 +52,17 @@ impl MatmulAutotuneKey {
             }
         }
         let batch_product = max(batch_product_lhs, batch_product_rhs);
+        let [m, k, n, batch] = Self::bucket(m, k, n, batch_product);
 
-        let round = m % 64 == 0 && k % 64 == 0 && n % 64 == 0;
-
-        let matrix_layout_lhs = matrix_layout(lhs_strides);
-        let matrix_layout_rhs = matrix_layout(rhs_strides);
+        let matrix_layout_lhs = matrix_layout(lhs_shape, lhs_strides);
+        let matrix_layout_rhs = matrix_layout(rhs_shape, rhs_strides);
 
         Self::new(
-            round,
             broadcast,
             m,
             k,
             n,
-            batch_product,
+            batch,
             elem_inputs,
             elem_state,
             elem_output,
@@ -76,6 +70,94 @@ impl MatmulAutotuneKey {
             matrix_layout_rhs,
         )
     }
+
+    /// The `[m, k, n, batch]` bucket of a problem with these dimensions, `batch` being the number
+    /// of batches of the output.
+    ///
+    /// Keys anchor m, k and n to the next power of two and the batch count as well, up to 256.
+    /// The bucket is the only part of the key depending on the dimensions: problems in the same
+    /// bucket share their tuned kernel when their element types, layouts and broadcasting also
+    /// match, so this tells which shapes are benchmarked together.
+    pub fn bucket(m: usize, k: usize, n: usize, batch: usize) -> [usize; 4] {
+        [
+            anchor(m, None),
+            anchor(k, None),
+            anchor(n, None),
+            anchor(batch, Some(256)),
+        ]
+    }
+
+    /// The `[m, k, n, batch]` bucket of this key, see [bucket](Self::bucket).
+    pub fn anchored_problem(&self) -> [usize; 4] {
+        [self.m, self.k, self.n, self.batch]
+    }
+
+    /// Shapes of lhs and rhs at the upper bound of the bucket of this key.
+    ///
+    /// The key anchors m, k and n to the next power of two, so every problem sharing it is at
+    /// most as large in those dimensions. Tuning on inputs of these shapes, e.g. from the input
+    /// generator of a tunable set, makes sure the chosen kernel launches for any problem of the
+    /// bucket. Batch dimensions are kept from the given shapes.
+    pub fn tuning_shapes(
+        &self,
+        lhs_shape: &[usize],
+        rhs_shape: &[usize],
+    ) -> (Vec<usize>, Vec<usize>) {
+        let ndims = lhs_shape.len();
+
+        let mut lhs = lhs_shape.to_vec();
+        let mut rhs = rhs_shape.to_vec();
+        lhs[ndims - 2] = self.m;
+        lhs[ndims - 1] = self.k;
+        rhs[ndims - 2] = self.k;
+        rhs[ndims - 1] = self.n;
+
+        (lhs, rhs)
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Serialize, Deserialize, AutotuneKey)]
+/// Autotune key of the selection between matmul kernel families, which also depends on the
+/// layout of the output since not every family can write a transposed one.
+pub struct MatmulKernelAutotuneKey {
+    matmul: MatmulAutotuneKey,
+    out_transposed: bool,
+}
+
+impl MatmulKernelAutotuneKey {
+    /// Create the autotune key of a matmul writing to an output with the given shape and strides,
+    /// see [MatmulAutotuneKey::generate].
+    pub fn generate(
+        lhs_shape: &[usize],
+        rhs_shape: &[usize],
+        lhs_strides: &[usize],
+        rhs_strides: &[usize],
+        out_shape: &[usize],
+        out_strides: &[usize],
+        elem: Elem,
+    ) -> Self {
+        let matmul = MatmulAutotuneKey::generate(
+            lhs_shape,
+            rhs_shape,
+            lhs_strides,
+            rhs_strides,
+            elem,
+            elem,
+            elem,
+        );
+
+        Self::new(matmul, !strides_are_contiguous(out_shape, out_strides))
+    }
+
+    /// The key of the problem, without the output layout.
+    pub fn matmul(&self) -> &MatmulAutotuneKey {
+        &self.matmul
+    }
+
+    /// Whether the output is a transposed view of a contiguous tensor.
+    pub fn out_transposed(&self) -> bool {
+        self.out_transposed
+    }
 }
 
 #[cfg(test)]
@@ -85,7 +167,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn matmul_autotune_key_all_same_and_round() {
+    fn matmul_autotune_key_all_same() {
         let lhs_shape = [4, 512, 512];
         let rhs_shape = [4, 512, 512];
         // Not useful for the test.
@@ -102,7 +184,6 @@ mod tests {
             elem,
         );
 
-        assert!(key.round);
         assert!(!key.broadcast);
         assert_eq!(key.m, 512);
         assert_eq!(key.k, 512);
@@ -127,7 +208,6 @@ mod tests {
             elem,
         );
 
-        assert!(!key.round);
         assert!(key.broadcast);
         assert_eq!(key.m, 512);
         assert_eq!(key.k, 512);
@@ -155,4 +235,52 @@ mod tests {
 
         assert_eq!(key.batch, 256);
     }
+
+    #[test]
+    fn matmul_autotune_key_tuning_shapes_bound_the_bucket() {
+        let lhs_shape = [2, 300, 100];
+        let rhs_shape = [2, 100, 70];
+        // Not useful for the test.
+        let lhs_strides = [1];
+        let rhs_strides = [1];
+        let elem = Elem::Float(FloatKind::F32);
+        let key = MatmulAutotuneKey::generate(
+            &lhs_shape,
+            &rhs_shape,
+            &lhs_strides,
+            &rhs_strides,
+            elem,
+            elem,
+            elem,
+        );
+
+        let (lhs, rhs) = key.tuning_shapes(&lhs_shape, &rhs_shape);
+
+        assert_eq!(lhs, vec![2, 512, 128]);
+        assert_eq!(rhs, vec![2, 128, 128]);
+    }
+
+    #[test]
+    fn matmul_autotune_key_bucket_matches_the_key() {
+        let lhs_shape = [300, 3, 100];
+        let rhs_shape = [300, 100, 70];
+        // Not useful for the test.
+        let lhs_strides = [1];
+        let rhs_strides = [1];
+        let elem = Elem::Float(FloatKind::F32);
+        let key = MatmulAutotuneKey::generate(
+            &lhs_shape,
+            &rhs_shape,
+            &lhs_strides,
+            &rhs_strides,
+            elem,
+            elem,
+            elem,
+        );
+
+        let bucket = MatmulAutotuneKey::bucket(3, 100, 70, 300);
+
+        assert_eq!(bucket, [4, 128, 128, 256]);
+        assert_eq!(key.anchored_problem(), bucket);
+    }
 }
@@ -1,10 +1,16 @@
-use cubecl_core::Runtime;
 use cubecl_core::calculate_cube_count_elemwise;
 use cubecl_core::prelude::*;
 use cubecl_core::tensor_line_size_parallel;
+use cubecl_core::{CubeElement, Runtime};
 use cubecl_runtime::server::Handle;
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
+use super::{
+    MatrixLayout, checked_tensor_bytes, contiguous_strides, into_contiguous, matrix_layout,
+    strides_are_contiguous,
+};
+
 /// Tensor representation containing a [server handle](Handle) as well as basic tensor metadata.,
 pub struct TensorHandle<R, E>
 where
@@ -70,7 +76,7 @@ where
 
     /// Create a new tensor with a contiguous memory layout.
     pub fn new_contiguous(shape: Vec<usize>, handle: Handle) -> Self {
-        let strides = Self::contiguous_strides(&shape);
+        let strides = contiguous_strides(&shape);
 
         Self {
             handle,
@@ -86,6 +92,14 @@ where
         self.handle.can_mut()
     }
 
+    /// Check if the tensor has a [contiguous](MatrixLayout::Contiguous) matrix layout.
+    pub fn is_contiguous(&self) -> bool {
+        matches!(
+            matrix_layout(&self.shape, &self.strides),
+            MatrixLayout::Contiguous
+        )
+    }
+
     pub fn as_ref(&self) -> TensorHandleRef<'_, R> {
         TensorHandleRef {
             handle: &self.handle,
@@ -109,30 +123,23 @@ where
             )
         }
     }
-
-    fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
-        let mut strides = Vec::with_capacity(shape.len());
-
-        let mut current = 1;
-        shape.iter().enumerate().rev().for_each(|(_, val)| {
-            strides.push(current);
-            current *= val;
-        });
-        strides.reverse();
-        strides
-    }
 }
 impl<R, E> TensorHandle<R, E>
 where
     R: Runtime,
     E: Numeric,
 {
+    /// Create a new uninitialized contiguous tensor.
+    ///
+    /// # Panics
+    ///
+    /// If the size of the tensor in bytes overflows a `usize`.
     pub fn empty(client: &ComputeClient<R::Server, R::Channel>, shape: Vec<usize>) -> Self {
-        let num_elements: usize = shape.iter().product();
-        let size = E::size().expect("To be a native type");
+        let size = checked_tensor_bytes(&shape, E::as_elem_native_unchecked())
+            .unwrap_or_else(|err| panic!("{err}"));
 
-        let handle = client.empty(size * num_elements);
-        let strides = Self::contiguous_strides(&shape);
+        let handle = client.empty(size);
+        let strides = contiguous_strides(&shape);
 
         Self::new(shape, strides, handle)
     }
@@ -164,6 +171,70 @@ where
 
         output
     }
+
+    /// Get a contiguous version of the tensor.
+    ///
+    /// The tensor is borrowed as is when it's already [contiguous](Self::is_contiguous),
+    /// otherwise its data are copied into a new contiguous tensor.
+    pub fn as_contiguous(&self, client: &ComputeClient<R::Server, R::Channel>) -> Cow<'_, Self> {
+        if self.is_contiguous() {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(into_contiguous::<R, E>(client, &self.as_ref()))
+        }
+    }
+}
+
+impl<R, E> TensorHandle<R, E>
+where
+    R: Runtime,
+    E: Numeric + CubeElement,
+{
+    /// Create a new contiguous tensor from host data laid out in row-major order.
+    pub fn from_data(
+        client: &ComputeClient<R::Server, R::Channel>,
+        data: &[E],
+        shape: &[usize],
+    ) -> Self {
+        let num_elements: usize = shape.iter().product();
+        assert_eq!(
+            data.len(),
+            num_elements,
+            "Data length should match the number of elements of shape {shape:?}"
+        );
+
+        let handle = client.create(E::as_bytes(data));
+
+        Self::new_contiguous(shape.to_vec(), handle)
+    }
+
+    /// Read the tensor back to host memory.
+    ///
+    /// The result is always in row-major order, elements are gathered according to the strides
+    /// when the tensor isn't contiguous.
+    pub fn to_vec(&self, client: &ComputeClient<R::Server, R::Channel>) -> Vec<E> {
+        let bytes = client.read_one(self.handle.clone().binding());
+        let data = E::from_bytes(&bytes);
+        let num_elements: usize = self.shape.iter().product();
+
+        if strides_are_contiguous(&self.shape, &self.strides) {
+            return data[..num_elements].to_vec();
+        }
+
+        (0..num_elements)
+            .map(|index| {
+                let mut remaining = index;
+                let mut offset = 0;
+
+                for (shape, stride) in self.shape.iter().zip(self.strides.iter()).rev() {
+                    offset += (remaining % shape) * stride;
+                    remaining /= shape;
+                }
+
+                data[offset]
+            })
+            .collect()
+    }
 }
 
 pub(crate) mod init {
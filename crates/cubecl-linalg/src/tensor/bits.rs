@@ -0,0 +1,80 @@
+use cubecl_core::CubeElement;
+use cubecl_core::prelude::*;
+use cubecl_runtime::server::Handle;
+use cubecl_std::{pack_bools, packed_len, unpack_bools};
+
+/// Boolean tensor packed 32 elements per `u32` word, e.g. an attention mask.
+///
+/// Elements are packed in row-major order, element `i` being bit `i % 32` of word `i / 32` counted
+/// from the least significant bit, as described in [cubecl_std::pack_bools]. Kernels read it as an
+/// array of words with [cubecl_std::read_packed_bool].
+pub struct BitTensor<R: Runtime> {
+    /// The buffer where the words are stored.
+    pub handle: Handle,
+    /// The shape of the tensor, in booleans.
+    pub shape: Vec<usize>,
+    runtime: core::marker::PhantomData<R>,
+}
+
+impl<R: Runtime> Clone for BitTensor<R> {
+    fn clone(&self) -> Self {
+        Self {
+            handle: self.handle.clone(),
+            shape: self.shape.clone(),
+            runtime: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<R: Runtime> core::fmt::Debug for BitTensor<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("BitTensor {{ shape: {:?} }}", self.shape))
+    }
+}
+
+impl<R: Runtime> BitTensor<R> {
+    /// Packs host booleans laid out in row-major order into a new tensor.
+    pub fn from_bools(
+        client: &ComputeClient<R::Server, R::Channel>,
+        data: &[bool],
+        shape: Vec<usize>,
+    ) -> Self {
+        let num_elements: usize = shape.iter().product();
+        assert_eq!(
+            data.len(),
+            num_elements,
+            "Data length should match the number of elements of shape {shape:?}"
+        );
+
+        let handle = client.create(u32::as_bytes(&pack_bools(data)));
+
+        Self {
+            handle,
+            shape,
+            runtime: core::marker::PhantomData,
+        }
+    }
+
+    /// Number of booleans in the tensor.
+    pub fn num_elements(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Number of `u32` words backing the tensor.
+    pub fn num_words(&self) -> usize {
+        packed_len(self.num_elements())
+    }
+
+    /// Read the tensor back to host memory, unpacked in row-major order.
+    pub fn to_bools(&self, client: &ComputeClient<R::Server, R::Channel>) -> Vec<bool> {
+        let bytes = client.read_one(self.handle.clone().binding());
+
+        unpack_bools(u32::from_bytes(&bytes), self.num_elements())
+    }
+
+    /// Return the words of the tensor as an array argument.
+    pub fn as_array_arg(&self) -> ArrayArg<'_, R> {
+        // Safety: the buffer was created with exactly `num_words` words.
+        unsafe { ArrayArg::from_raw_parts::<u32>(&self.handle, self.num_words(), 1) }
+    }
+}
@@ -0,0 +1,181 @@
+use core::fmt;
+
+use cubecl::frontend::TensorHandleRef;
+use cubecl::prelude::*;
+use cubecl::{calculate_cube_count_elemwise, tensor_line_size_parallel};
+use cubecl_core as cubecl;
+
+use super::TensorHandle;
+
+/// Copies `input` into `output`, shifted by `offset` along `axis`.
+///
+/// Each unit copies one line of the input. Lines run along the last dimension, which must not be
+/// the concatenation axis when they hold more than one element.
+#[cube(launch_unchecked)]
+fn concat_kernel<N: CubePrimitive>(
+    input: &Tensor<Line<N>>,
+    output: &mut Tensor<Line<N>>,
+    axis: u32,
+    offset: u32,
+    num_lines: u32,
+) {
+    if ABSOLUTE_POS < num_lines {
+        let line_size = input.line_size();
+        let mut remaining = ABSOLUTE_POS * line_size;
+        let mut input_offset = 0;
+        let mut output_offset = 0;
+
+        for i in 0..input.rank() {
+            let dim = input.rank() - 1 - i;
+            let shape = input.shape(dim);
+            let mut coordinate = remaining % shape;
+            remaining /= shape;
+
+            input_offset += coordinate * input.stride(dim);
+            if dim == axis {
+                coordinate += offset;
+            }
+            output_offset += coordinate * output.stride(dim);
+        }
+
+        output[output_offset / line_size] = input[input_offset / line_size];
+    }
+}
+
+/// Error returned by [concat] when the inputs can't be concatenated.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ConcatError {
+    /// No input was given.
+    NoInputs,
+    /// The axis is not smaller than the rank of the inputs.
+    InvalidAxis { axis: usize, rank: usize },
+    /// An input doesn't have the same rank as the first one.
+    MismatchedRank {
+        input: usize,
+        rank: usize,
+        expected: usize,
+    },
+    /// An input differs from the first one in a dimension other than the concatenation axis.
+    MismatchedShape {
+        input: usize,
+        shape: Vec<usize>,
+        expected: Vec<usize>,
+        axis: usize,
+    },
+}
+
+impl fmt::Display for ConcatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoInputs => write!(f, "At least one tensor is needed to concatenate."),
+            Self::InvalidAxis { axis, rank } => write!(
+                f,
+                "The provided axis ({axis}) must be smaller than the rank of the inputs ({rank})."
+            ),
+            Self::MismatchedRank {
+                input,
+                rank,
+                expected,
+            } => write!(
+                f,
+                "Input {input} has rank {rank}, but the first input has rank {expected}."
+            ),
+            Self::MismatchedShape {
+                input,
+                shape,
+                expected,
+                axis,
+            } => write!(
+                f,
+                "Input {input} has shape {shape:?}, which differs from the shape of the first \
+                 input {expected:?} in a dimension other than the concatenation axis ({axis})."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConcatError {}
+
+/// Concatenates `inputs` along `axis` into a new contiguous tensor.
+///
+/// All inputs must have the same rank and the same shape in every dimension but `axis`, their
+/// layouts may differ. The inputs are copied in order, one kernel per input, each into its slice
+/// of the output along `axis`.
+pub fn concat<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    inputs: &[&TensorHandleRef<'_, R>],
+    axis: usize,
+) -> Result<TensorHandle<R, N>, ConcatError> {
+    let first = inputs.first().ok_or(ConcatError::NoInputs)?;
+    let rank = first.shape.len();
+    if axis >= rank {
+        return Err(ConcatError::InvalidAxis { axis, rank });
+    }
+
+    let mut shape = first.shape.to_vec();
+    shape[axis] = 0;
+
+    for (index, input) in inputs.iter().enumerate() {
+        if input.shape.len() != rank {
+            return Err(ConcatError::MismatchedRank {
+                input: index,
+                rank: input.shape.len(),
+                expected: rank,
+            });
+        }
+
+        let matches = (0..rank).all(|dim| dim == axis || input.shape[dim] == first.shape[dim]);
+        if !matches {
+            return Err(ConcatError::MismatchedShape {
+                input: index,
+                shape: input.shape.to_vec(),
+                expected: first.shape.to_vec(),
+                axis,
+            });
+        }
+
+        shape[axis] += input.shape[axis];
+    }
+
+    let output = TensorHandle::<R, N>::empty(client, shape);
+    let mut offset = 0;
+
+    for input in inputs {
+        let num_elements: usize = input.shape.iter().product();
+        if num_elements == 0 {
+            continue;
+        }
+
+        // Lines along the last dimension would straddle two inputs when concatenating along it.
+        let line_size = match axis == rank - 1 {
+            true => 1,
+            false => tensor_line_size_parallel(
+                R::supported_line_sizes().iter().cloned(),
+                input.shape,
+                input.strides,
+                rank - 1,
+            ),
+        };
+
+        let num_lines = num_elements / line_size as usize;
+        let cube_dim = CubeDim::default();
+        let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+        unsafe {
+            concat_kernel::launch_unchecked::<N, R>(
+                client,
+                cube_count,
+                cube_dim,
+                input.as_tensor_arg(line_size),
+                output.as_ref().as_tensor_arg(line_size),
+                ScalarArg::new(axis as u32),
+                ScalarArg::new(offset as u32),
+                ScalarArg::new(num_lines as u32),
+            );
+        }
+
+        offset += input.shape[axis];
+    }
+
+    Ok(output)
+}
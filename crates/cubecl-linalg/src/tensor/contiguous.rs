@@ -1,6 +1,12 @@
-use super::TensorHandle;
+use super::{
+    ClientScratch, ScratchProvider, TensorHandle, checked_tensor_bytes, checked_u32_indexing,
+};
 use cubecl::prelude::*;
-use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, tensor_line_size_parallel};
+use cubecl_core::{
+    self as cubecl, Feature, calculate_cube_count_elemwise,
+    ir::{Elem, UIntKind},
+    tensor_line_size_parallel,
+};
 
 /// Returns the offset of the tensor corresponding to the layout tensor.
 #[cube]
@@ -24,6 +30,32 @@ pub fn index_offset_with_layout<N: CubePrimitive, L: CubePrimitive>(
     offset / tensor.line_size()
 }
 
+/// Returns the offset of the tensor corresponding to the layout tensor, like
+/// [index_offset_with_layout] but computed in u64 for tensors with more than `u32::MAX` elements.
+///
+/// Each shape and stride still fits in u32, only their products are widened.
+#[cube]
+pub fn index_offset_with_layout_u64<N: CubePrimitive, L: CubePrimitive>(
+    tensor: &Tensor<Line<N>>,
+    layout: &Tensor<Line<L>>,
+    offset_layout: u64,
+    dim_start: u32,
+    dim_end: u32,
+    #[comptime] unroll: bool,
+) -> u64 {
+    let line_size = u64::cast_from(tensor.line_size());
+    let offset_ref = offset_layout * line_size;
+    let mut offset = u64::new(0);
+
+    #[unroll(unroll)]
+    for i in dim_start..dim_end {
+        let ogwl = offset_ref / u64::cast_from(layout.stride(i));
+        offset += ogwl % u64::cast_from(tensor.shape(i)) * u64::cast_from(tensor.stride(i));
+    }
+
+    offset / line_size
+}
+
 #[cube(launch)]
 fn into_contiguous_kernel<N: CubePrimitive>(
     input: &Tensor<Line<N>>,
@@ -56,10 +88,52 @@ fn into_contiguous_kernel<N: CubePrimitive>(
     }
 }
 
+/// Same as [into_contiguous_kernel] with 64-bit offsets. The lengths of the tensors are saturated
+/// at `u32::MAX` in their metadata, so the number of lines is passed instead to stay in bounds.
+#[cube(launch_unchecked)]
+fn into_contiguous_kernel_u64<N: CubePrimitive>(
+    input: &Tensor<Line<N>>,
+    output: &mut Tensor<Line<N>>,
+    num_lines: u64,
+    #[comptime] rank: Option<u32>,
+    #[comptime] elems_per_thread: u32,
+) {
+    let offset_output = u64::cast_from(ABSOLUTE_POS) * u64::cast_from(elems_per_thread);
+
+    #[unroll]
+    for i in 0..elems_per_thread {
+        let offset = offset_output + u64::cast_from(i);
+
+        if offset < num_lines {
+            let offset_input = index_offset_with_layout_u64::<N, N>(
+                input,
+                output,
+                offset,
+                0,
+                rank.unwrap_or_else(|| output.rank()),
+                rank.is_some(),
+            );
+
+            unsafe {
+                output.index_assign_unchecked_u64(offset, *input.index_unchecked_u64(offset_input))
+            };
+        }
+    }
+}
+
 /// Make a jit tensor contiguous.
 pub fn into_contiguous<R: Runtime, E: CubePrimitive>(
     client: &ComputeClient<R::Server, R::Channel>,
     input: &TensorHandleRef<'_, R>,
+) -> TensorHandle<R, E> {
+    into_contiguous_with_scratch(client, input, &ClientScratch)
+}
+
+/// Make a jit tensor contiguous, allocating the output with the given scratch provider.
+pub fn into_contiguous_with_scratch<R: Runtime, E: CubePrimitive, S: ScratchProvider<R>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    scratch: &S,
 ) -> TensorHandle<R, E> {
     let num_elems: usize = input.shape.iter().product();
     // Vectorization is only enabled when the last dimension is contiguous.
@@ -72,8 +146,8 @@ pub fn into_contiguous<R: Runtime, E: CubePrimitive>(
     );
     let num_vecs = num_elems / vectorization_factor as usize;
     let approx_sm = 64;
-    let approx_simul_vecs = approx_sm * CubeDim::default().num_elems();
-    let elems_per_unit = match num_vecs as u32 / approx_simul_vecs {
+    let approx_simul_vecs = approx_sm * CubeDim::default().num_elems() as usize;
+    let elems_per_unit = match num_vecs / approx_simul_vecs {
         0..2 => 1,
         2..4 => 2,
         4..8 => 4,
@@ -81,7 +155,7 @@ pub fn into_contiguous<R: Runtime, E: CubePrimitive>(
     };
 
     // TODO: Benchmark to find good default prefetch, for now preserve existing behaviour
-    into_contiguous_prefetch(client, input, elems_per_unit)
+    launch_into_contiguous(client, input, elems_per_unit, scratch)
 }
 
 /// Make a jit tensor contiguous.
@@ -89,6 +163,15 @@ pub fn into_contiguous_prefetch<R: Runtime, E: CubePrimitive>(
     client: &ComputeClient<R::Server, R::Channel>,
     input: &TensorHandleRef<'_, R>,
     elems_per_unit: u32,
+) -> TensorHandle<R, E> {
+    launch_into_contiguous(client, input, elems_per_unit, &ClientScratch)
+}
+
+fn launch_into_contiguous<R: Runtime, E: CubePrimitive, S: ScratchProvider<R>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    elems_per_unit: u32,
+    scratch: &S,
 ) -> TensorHandle<R, E> {
     // Vectorization is only enabled when the last dimension is contiguous.
     let rank = input.strides.len();
@@ -105,9 +188,37 @@ pub fn into_contiguous_prefetch<R: Runtime, E: CubePrimitive>(
     let cube_dim = CubeDim::default();
     let cube_count =
         calculate_cube_count_elemwise(num_elems.div_ceil(num_elems_per_unit as usize), cube_dim);
-    let handle = client.empty(num_elems * E::size().expect("To be a native type"));
+    let size = checked_tensor_bytes(input.shape, E::as_elem_native_unchecked())
+        .unwrap_or_else(|err| panic!("{err}"));
+    let handle = scratch.alloc(client, size);
     let output = TensorHandle::new_contiguous(input.shape.to_vec(), handle);
 
+    // The offsets are promoted to u64 when they would wrap around in u32.
+    if checked_u32_indexing(input.shape).is_err() {
+        assert!(
+            client
+                .properties()
+                .feature_enabled(Feature::Type(Elem::UInt(UIntKind::U64))),
+            "A tensor of shape {:?} needs 64-bit indices, which the device doesn't support",
+            input.shape
+        );
+
+        unsafe {
+            into_contiguous_kernel_u64::launch_unchecked::<Line<E>, R>(
+                client,
+                cube_count,
+                cube_dim,
+                input.as_tensor_arg(vectorization_factor),
+                output.as_ref().as_tensor_arg(vectorization_factor),
+                ScalarArg::new((num_elems / vectorization_factor as usize) as u64),
+                Some(rank as u32),
+                elems_per_unit,
+            )
+        };
+
+        return output;
+    }
+
     into_contiguous_kernel::launch::<Line<E>, R>(
         client,
         cube_count,
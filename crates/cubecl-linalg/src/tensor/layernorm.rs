@@ -0,0 +1,146 @@
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::frontend::TensorHandleRef;
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, Feature};
+use cubecl_reduce::ReduceError;
+
+/// Number of rows normalized by each cube, one per plane.
+const PLANE_COUNT: u32 = 8;
+
+/// Normalizes one row of `input` along `axis` per plane.
+///
+/// Every unit of the plane accumulates the statistics of a strided subset of the row with Welford's
+/// algorithm, then the partial statistics are merged across the plane. Statistics are kept in
+/// `f32` whatever the element type, so half precision inputs don't lose accuracy in the sums.
+#[cube(launch_unchecked)]
+fn layernorm_kernel<F: Float>(
+    input: &Tensor<F>,
+    gamma: &Tensor<F>,
+    beta: &Tensor<F>,
+    output: &mut Tensor<F>,
+    axis: u32,
+    num_rows: u32,
+    eps: f32,
+) {
+    let row = CUBE_POS * CUBE_DIM_Y + UNIT_POS_Y;
+
+    // The whole plane takes the same branch, so the plane reductions stay uniform.
+    if row < num_rows {
+        let mut input_offset = 0;
+        let mut output_offset = 0;
+        let mut remaining = row;
+
+        for i in 0..input.rank() {
+            let dim = input.rank() - 1 - i;
+            if dim != axis {
+                let shape = input.shape(dim);
+                let coordinate = remaining % shape;
+                remaining /= shape;
+                input_offset += coordinate * input.stride(dim);
+                output_offset += coordinate * output.stride(dim);
+            }
+        }
+
+        let length = input.shape(axis);
+        let input_stride = input.stride(axis);
+        let output_stride = output.stride(axis);
+
+        let mut count = f32::new(0.0);
+        let mut mean = f32::new(0.0);
+        let mut m2 = f32::new(0.0);
+
+        let mut index = UNIT_POS_X;
+        while index < length {
+            let value = f32::cast_from(input[input_offset + index * input_stride]);
+            count += f32::new(1.0);
+            let delta = value - mean;
+            mean += delta / count;
+            m2 += delta * (value - mean);
+            index += CUBE_DIM_X;
+        }
+
+        // Chan's parallel merge of the per-unit statistics.
+        let row_mean = plane_sum(count * mean) / f32::cast_from(length);
+        let deviation = mean - row_mean;
+        let row_m2 = plane_sum(m2 + count * deviation * deviation);
+        let inv_std = f32::new(1.0) / f32::sqrt(row_m2 / f32::cast_from(length) + eps);
+
+        let mut index = UNIT_POS_X;
+        while index < length {
+            let value = f32::cast_from(input[input_offset + index * input_stride]);
+            let scale = f32::cast_from(gamma[index * gamma.stride(0)]);
+            let shift = f32::cast_from(beta[index * beta.stride(0)]);
+
+            output[output_offset + index * output_stride] =
+                F::cast_from((value - row_mean) * inv_std * scale + shift);
+            index += CUBE_DIM_X;
+        }
+    }
+}
+
+/// Applies layer normalization to `input` along `axis`, writing the result to `out`.
+///
+/// Every row along `axis` is normalized to zero mean and unit variance, using the biased variance
+/// with `eps` added for stability, then scaled by `gamma` and shifted by `beta`. Both are vectors
+/// with as many elements as `input` has along `axis`, and `out` must have the shape of `input`.
+///
+/// Each row is handled by a single plane looping over the axis, so the axis can be of any length.
+/// The statistics are accumulated in `f32` for every element type.
+pub fn layernorm<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    gamma: &TensorHandleRef<R>,
+    beta: &TensorHandleRef<R>,
+    out: &TensorHandleRef<R>,
+    axis: usize,
+    eps: f32,
+) -> Result<(), ReduceError> {
+    let rank = input.shape.len();
+    if axis >= rank {
+        return Err(ReduceError::InvalidAxis { axis, rank });
+    }
+    if out.shape != input.shape {
+        return Err(ReduceError::MismatchShape {
+            expected_shape: input.shape.to_vec(),
+            output_shape: out.shape.to_vec(),
+        });
+    }
+    for affine in [gamma, beta] {
+        if affine.shape != [input.shape[axis]] {
+            return Err(ReduceError::MismatchShape {
+                expected_shape: vec![input.shape[axis]],
+                output_shape: affine.shape.to_vec(),
+            });
+        }
+    }
+
+    let properties = client.properties();
+    if !properties.feature_enabled(Feature::Plane) {
+        return Err(ReduceError::PlanesUnavailable);
+    }
+    let hardware = properties.hardware_properties();
+    if hardware.plane_size_min != hardware.plane_size_max {
+        return Err(ReduceError::ImprecisePlaneDim);
+    }
+
+    let num_rows = input.shape.iter().product::<usize>() / input.shape[axis];
+    let cube_dim = CubeDim::new_2d(hardware.plane_size_min, PLANE_COUNT);
+    let cube_count = calculate_cube_count_elemwise(num_rows * cube_dim.x as usize, cube_dim);
+
+    unsafe {
+        layernorm_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            gamma.as_tensor_arg(1),
+            beta.as_tensor_arg(1),
+            out.as_tensor_arg(1),
+            ScalarArg::new(axis as u32),
+            ScalarArg::new(num_rows as u32),
+            ScalarArg::new(eps),
+        );
+    }
+
+    Ok(())
+}
@@ -12,40 +12,59 @@ pub enum MatrixLayout {
         transposed: bool,
         /// Some permutations exist in batch dimensions
         batch_swap: bool,
+        /// Some batch dimensions are broadcast, with a stride of 0
+        batch_broadcast: bool,
     },
     /// Permutations happened between batch dimensions and last two dims
     HighlyPermuted,
 }
 
-/// Return the layout of a matrix given the strides.
-pub fn matrix_layout(strides: &[usize]) -> MatrixLayout {
+/// Return the layout of a matrix given its shape and strides.
+///
+/// Dimensions of size 1 are never stepped over, so their stride is ignored, e.g. for a squeezed
+/// dimension or a single row. Batch dimensions with a stride of 0 are broadcast views, which
+/// kernels can read in place by using the strides to compute batch offsets.
+pub fn matrix_layout(shape: &[usize], strides: &[usize]) -> MatrixLayout {
     let rank = strides.len();
     if rank <= 1 {
         return MatrixLayout::Contiguous;
     }
 
-    let mut transposed = false;
-    let mut batch_swap = false;
+    let rows = shape[rank - 2];
+    let cols = shape[rank - 1];
     let row_stride = strides[rank - 2];
     let col_stride = strides[rank - 1];
-    if row_stride == 0 || col_stride == 0 {
+    if (rows != 1 && row_stride == 0) || (cols != 1 && col_stride == 0) {
         // Broadcasted last two dims
         return MatrixLayout::HighlyPermuted;
     }
-    if row_stride < col_stride {
-        transposed = true;
-    }
-    let mut previous_stride = row_stride;
+
+    let transposed = rows != 1 && cols != 1 && row_stride < col_stride;
+    let mut batch_swap = false;
+    let mut batch_broadcast = false;
+
+    // Batch dims must be outside of the largest stride of the matrix dims that are stepped over.
+    let matrix_stride = match (rows != 1, cols != 1) {
+        (true, true) => usize::max(row_stride, col_stride),
+        (true, false) => row_stride,
+        (false, true) => col_stride,
+        (false, false) => 0,
+    };
+    let mut previous_stride = matrix_stride;
 
     for d in 0..rank - 2 {
-        let current_stride = strides[rank - 3 - d];
-        if current_stride < row_stride || current_stride < col_stride {
-            if current_stride == 0 {
-                // Broadcasted batch dim
-                batch_swap = true;
-            } else {
-                return MatrixLayout::HighlyPermuted;
-            }
+        let dim = rank - 3 - d;
+        let current_stride = strides[dim];
+        if shape[dim] == 1 {
+            continue;
+        }
+        if current_stride == 0 {
+            // Broadcasted batch dim
+            batch_broadcast = true;
+            continue;
+        }
+        if current_stride < matrix_stride {
+            return MatrixLayout::HighlyPermuted;
         }
         if current_stride < previous_stride {
             batch_swap = true;
@@ -54,10 +73,11 @@ pub fn matrix_layout(strides: &[usize]) -> MatrixLayout {
         previous_stride = current_stride;
     }
 
-    if transposed || batch_swap {
+    if transposed || batch_swap || batch_broadcast {
         MatrixLayout::MildlyPermuted {
             transposed,
             batch_swap,
+            batch_broadcast,
         }
     } else {
         MatrixLayout::Contiguous
@@ -70,118 +90,170 @@ mod tests {
 
     #[test]
     fn layout_is_contiguous() {
+        let shape = &[2, 2, 2, 2];
         let strides = &[8, 4, 2, 1];
-        assert_eq!(matrix_layout(strides), MatrixLayout::Contiguous);
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::Contiguous);
     }
 
     #[test]
     fn vector_is_contiguous() {
+        let shape = &[4];
         let strides = &[1];
-        assert_eq!(matrix_layout(strides), MatrixLayout::Contiguous)
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::Contiguous)
     }
 
     #[test]
     fn layout_is_transposed_only() {
+        let shape = &[2, 2, 2, 2];
         let strides = &[8, 4, 1, 2];
-        if let MatrixLayout::MildlyPermuted {
-            transposed,
-            batch_swap,
-        } = matrix_layout(strides)
-        {
-            assert!(transposed && !batch_swap);
-        } else {
-            unreachable!()
-        }
+        assert_eq!(
+            matrix_layout(shape, strides),
+            MatrixLayout::MildlyPermuted {
+                transposed: true,
+                batch_swap: false,
+                batch_broadcast: false,
+            }
+        );
     }
 
     #[test]
     fn layout_has_swapped_batches_only() {
+        let shape = &[2, 2, 2, 2];
         let strides = &[4, 8, 2, 1];
-        if let MatrixLayout::MildlyPermuted {
-            transposed,
-            batch_swap,
-        } = matrix_layout(strides)
-        {
-            assert!(!transposed && batch_swap);
-        } else {
-            unreachable!()
-        }
+        assert_eq!(
+            matrix_layout(shape, strides),
+            MatrixLayout::MildlyPermuted {
+                transposed: false,
+                batch_swap: true,
+                batch_broadcast: false,
+            }
+        );
     }
 
     #[test]
     fn layout_has_swapped_batches_and_is_transposed() {
+        let shape = &[2, 2, 2, 2];
         let strides = &[4, 8, 1, 2];
-        if let MatrixLayout::MildlyPermuted {
-            transposed,
-            batch_swap,
-        } = matrix_layout(strides)
-        {
-            assert!(transposed && batch_swap);
-        } else {
-            unreachable!()
-        }
+        assert_eq!(
+            matrix_layout(shape, strides),
+            MatrixLayout::MildlyPermuted {
+                transposed: true,
+                batch_swap: true,
+                batch_broadcast: false,
+            }
+        );
     }
 
     #[test]
     fn layout_has_batch_swapped_with_row() {
+        let shape = &[2, 2, 2, 2];
         let strides = &[8, 2, 4, 1];
-        assert_eq!(matrix_layout(strides), MatrixLayout::HighlyPermuted);
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::HighlyPermuted);
     }
 
     #[test]
     fn layout_has_batch_swapped_with_col() {
+        let shape = &[2, 2, 2, 2];
         let strides = &[1, 4, 2, 8];
-        assert_eq!(matrix_layout(strides), MatrixLayout::HighlyPermuted);
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::HighlyPermuted);
     }
 
     #[test]
     fn layout_has_multiple_broadcasted_dims() {
         // E.g., tensor w/ shape [1, 4] expanded to [2, 3, 4]
+        let shape = &[2, 3, 4];
         let strides = &[0, 0, 1];
-        assert_eq!(matrix_layout(strides), MatrixLayout::HighlyPermuted);
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::HighlyPermuted);
     }
 
     #[test]
     fn layout_has_row_broadcasted() {
         // E.g., tensor w/ shape [1, 4] expanded to [3, 4]
+        let shape = &[3, 4];
         let strides = &[0, 1];
-        assert_eq!(matrix_layout(strides), MatrixLayout::HighlyPermuted);
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::HighlyPermuted);
     }
 
     #[test]
     fn layout_has_col_broadcasted() {
         // E.g., tensor w/ shape [2, 1] expanded to [2, 3]
+        let shape = &[2, 3];
         let strides = &[1, 0];
-        assert_eq!(matrix_layout(strides), MatrixLayout::HighlyPermuted);
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::HighlyPermuted);
     }
 
     #[test]
     fn layout_has_batch_broadcasted() {
-        // E.g., tensor w/ shape [2, 4] expanded to [2, 2, 4]
+        // E.g., tensor w/ shape [1, m, k] expanded to [8, m, k]
+        let shape = &[8, 3, 4];
         let strides = &[0, 4, 1];
-        if let MatrixLayout::MildlyPermuted {
-            transposed,
-            batch_swap,
-        } = matrix_layout(strides)
-        {
-            assert!(!transposed && batch_swap);
-        } else {
-            unreachable!()
-        }
+        assert_eq!(
+            matrix_layout(shape, strides),
+            MatrixLayout::MildlyPermuted {
+                transposed: false,
+                batch_swap: false,
+                batch_broadcast: true,
+            }
+        );
     }
 
     #[test]
     fn layout_has_multiple_batch_broadcasted() {
         // E.g., tensor w/ shape [2, 4] expanded to [2, 2, 2, 4]
+        let shape = &[2, 2, 2, 4];
         let strides = &[0, 0, 4, 1];
-        if let MatrixLayout::MildlyPermuted {
-            transposed,
-            batch_swap,
-        } = matrix_layout(strides)
-        {
-            assert!(!transposed && batch_swap);
-        } else {
-            unreachable!()
-        }
+        assert_eq!(
+            matrix_layout(shape, strides),
+            MatrixLayout::MildlyPermuted {
+                transposed: false,
+                batch_swap: false,
+                batch_broadcast: true,
+            }
+        );
+    }
+
+    #[test]
+    fn layout_has_transposed_batch_broadcasted() {
+        // E.g., transposed tensor w/ shape [3, 4] expanded to [2, 3, 4]
+        let shape = &[2, 3, 4];
+        let strides = &[0, 1, 3];
+        assert_eq!(
+            matrix_layout(shape, strides),
+            MatrixLayout::MildlyPermuted {
+                transposed: true,
+                batch_swap: false,
+                batch_broadcast: true,
+            }
+        );
+    }
+
+    #[test]
+    fn layout_ignores_squeezed_batch_dims() {
+        // E.g., tensor w/ shape [2, 4, 3] unsqueezed to [2, 1, 4, 3]
+        let shape = &[2, 1, 4, 3];
+        let strides = &[12, 1, 3, 1];
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::Contiguous);
+    }
+
+    #[test]
+    fn layout_ignores_size_one_matrix_dims() {
+        // E.g., batched column vectors, whose size 1 column may have any stride
+        let shape = &[2, 4, 1];
+        let strides = &[4, 1, 5];
+        assert_eq!(matrix_layout(shape, strides), MatrixLayout::Contiguous);
+    }
+
+    #[test]
+    fn layout_with_squeezed_dims_is_transposed() {
+        let shape = &[2, 1, 4, 3];
+        let strides = &[12, 7, 1, 4];
+        assert_eq!(
+            matrix_layout(shape, strides),
+            MatrixLayout::MildlyPermuted {
+                transposed: true,
+                batch_swap: false,
+                batch_broadcast: false,
+            }
+        );
     }
 }
@@ -1,12 +1,24 @@
 mod base;
+mod bits;
+mod concat;
 mod contiguous;
 pub mod identity;
+mod layernorm;
 mod layout;
+mod scratch;
+mod size;
+mod strides;
 
 pub use base::*;
+pub use bits::*;
+pub use concat::*;
 pub use contiguous::*;
 pub use identity::*;
+pub use layernorm::*;
 pub use layout::*;
+pub use scratch::*;
+pub use size::*;
+pub use strides::*;
 
 /// Tests for tensor kernels
 #[cfg(feature = "export_tests")]
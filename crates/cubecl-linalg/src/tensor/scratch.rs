@@ -0,0 +1,29 @@
+use cubecl_core::{Runtime, client::ComputeClient, server::Handle};
+
+/// Provides the temporary buffers allocated by the kernels of this crate, such as the contiguous
+/// copies made before a matmul.
+///
+/// Every buffer obtained with [alloc](ScratchProvider::alloc) is handed back with
+/// [release](ScratchProvider::release) once the kernels using it have been launched, so that
+/// pooled buffers can be reused.
+pub trait ScratchProvider<R: Runtime> {
+    /// Allocate an uninitialized buffer of `size` bytes.
+    fn alloc(&self, client: &ComputeClient<R::Server, R::Channel>, size: usize) -> Handle;
+
+    /// Give back a buffer that is no longer needed by the launcher.
+    ///
+    /// Kernels may still be executing on the buffer, but they are already queued on the client.
+    fn release(&self, handle: Handle) {
+        core::mem::drop(handle);
+    }
+}
+
+/// Allocates scratch buffers with the memory management of the client.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ClientScratch;
+
+impl<R: Runtime> ScratchProvider<R> for ClientScratch {
+    fn alloc(&self, client: &ComputeClient<R::Server, R::Channel>, size: usize) -> Handle {
+        client.empty(size)
+    }
+}
@@ -0,0 +1,113 @@
+use core::fmt;
+
+use cubecl_core::ir::Elem;
+
+/// Error returned by [checked_tensor_bytes] and [checked_u32_indexing] when the size of a tensor
+/// can't be represented.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum SizeError {
+    /// The number of bytes of the tensor doesn't fit in a `usize`.
+    Overflow { shape: Vec<usize>, elem: Elem },
+    /// The number of elements of the tensor doesn't fit in the `u32` indices of a kernel.
+    ExceededIndexRange { shape: Vec<usize> },
+}
+
+impl fmt::Display for SizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow { shape, elem } => write!(
+                f,
+                "A tensor of shape {shape:?} and element {elem} doesn't fit in addressable memory."
+            ),
+            Self::ExceededIndexRange { shape } => write!(
+                f,
+                "A tensor of shape {shape:?} has more than u32::MAX elements, which can't be \
+                 indexed by a kernel using 32-bit indices."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SizeError {}
+
+/// Number of bytes of a contiguous tensor of `shape` holding `elem`.
+///
+/// Unlike multiplying the dimensions directly, an overflow is reported instead of wrapping to a
+/// smaller size, which would make allocations too small on 32-bit targets or for huge shapes.
+pub fn checked_tensor_bytes(shape: &[usize], elem: Elem) -> Result<usize, SizeError> {
+    shape
+        .iter()
+        .try_fold(elem.size(), |bytes, dim| bytes.checked_mul(*dim))
+        .ok_or_else(|| SizeError::Overflow {
+            shape: shape.to_vec(),
+            elem,
+        })
+}
+
+/// Checks that a tensor of `shape` can be indexed with `u32`.
+///
+/// Kernels compute their offsets in `u32`, which would silently wrap around for the elements
+/// past `u32::MAX`. Only kernels written with 64-bit indices, e.g.
+/// [into_contiguous](super::into_contiguous), can handle larger tensors.
+pub fn checked_u32_indexing(shape: &[usize]) -> Result<(), SizeError> {
+    shape
+        .iter()
+        .try_fold(1usize, |elems, dim| elems.checked_mul(*dim))
+        .filter(|elems| *elems <= u32::MAX as usize)
+        .map(|_| ())
+        .ok_or_else(|| SizeError::ExceededIndexRange {
+            shape: shape.to_vec(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use cubecl_core::ir::FloatKind;
+
+    use super::*;
+
+    #[test]
+    fn bytes_of_small_tensor() {
+        let bytes = checked_tensor_bytes(&[2, 3, 4], Elem::Float(FloatKind::F32));
+
+        assert_eq!(bytes, Ok(96));
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let shape = [usize::MAX / 2, 3];
+        let bytes = checked_tensor_bytes(&shape, Elem::Float(FloatKind::F16));
+
+        assert_eq!(
+            bytes,
+            Err(SizeError::Overflow {
+                shape: shape.to_vec(),
+                elem: Elem::Float(FloatKind::F16),
+            })
+        );
+    }
+
+    #[test]
+    fn overflow_through_element_size_is_reported() {
+        let bytes = checked_tensor_bytes(&[usize::MAX / 2 + 1], Elem::Float(FloatKind::F16));
+
+        assert!(bytes.is_err());
+    }
+
+    #[test]
+    fn u32_indexing_accepts_u32_max_elements() {
+        assert_eq!(checked_u32_indexing(&[3, 5, 17, 257, 65537]), Ok(()));
+    }
+
+    #[test]
+    fn u32_indexing_rejects_more_elements() {
+        let shape = [2, 1 << 31];
+
+        assert_eq!(
+            checked_u32_indexing(&shape),
+            Err(SizeError::ExceededIndexRange {
+                shape: shape.to_vec(),
+            })
+        );
+    }
+}
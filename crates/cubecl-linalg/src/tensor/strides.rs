@@ -0,0 +1,76 @@
+/// Row-major strides of a contiguous tensor with the given shape, in elements.
+///
+/// The last dimension has a stride of 1 and each other dimension the product of the shape of the
+/// dimensions after it.
+pub fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+
+    for dim in (0..shape.len().saturating_sub(1)).rev() {
+        strides[dim] = strides[dim + 1] * shape[dim + 1];
+    }
+
+    strides
+}
+
+/// Check if `strides` describe a contiguous row-major layout of `shape`.
+///
+/// The stride of a dimension of size 1 doesn't change the layout, so it isn't checked.
+pub fn strides_are_contiguous(shape: &[usize], strides: &[usize]) -> bool {
+    if shape.len() != strides.len() {
+        return false;
+    }
+
+    let mut expected = 1;
+
+    for (shape, stride) in shape.iter().zip(strides).rev() {
+        if *shape != 1 && *stride != expected {
+            return false;
+        }
+        expected *= shape;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contiguous_strides_are_row_major() {
+        assert_eq!(contiguous_strides(&[2, 3, 4]), vec![12, 4, 1]);
+        assert_eq!(contiguous_strides(&[5]), vec![1]);
+        assert_eq!(contiguous_strides(&[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn contiguous_strides_ignore_the_first_dimension() {
+        assert_eq!(contiguous_strides(&[usize::MAX, 2]), vec![2, 1]);
+    }
+
+    #[test]
+    fn contiguous_strides_are_contiguous() {
+        let shape = [3, 1, 5, 2];
+        assert!(strides_are_contiguous(&shape, &contiguous_strides(&shape)));
+    }
+
+    #[test]
+    fn transposed_strides_are_not_contiguous() {
+        assert!(!strides_are_contiguous(&[2, 3], &[1, 2]));
+    }
+
+    #[test]
+    fn broadcasted_strides_are_not_contiguous() {
+        assert!(!strides_are_contiguous(&[2, 3], &[0, 1]));
+    }
+
+    #[test]
+    fn size_one_dimensions_may_have_any_stride() {
+        assert!(strides_are_contiguous(&[1, 3, 1], &[7, 1, 42]));
+    }
+
+    #[test]
+    fn mismatched_rank_is_not_contiguous() {
+        assert!(!strides_are_contiguous(&[2, 3], &[3, 1, 1]));
+    }
+}
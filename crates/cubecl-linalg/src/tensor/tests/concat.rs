@@ -0,0 +1,74 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement,
+    prelude::{Numeric, Runtime},
+};
+
+use super::test_utils::concat_cpu;
+use crate::tensor::{self, ConcatError, TensorHandle};
+
+pub fn test_concat<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+    shapes: Vec<Vec<usize>>,
+    axis: usize,
+) {
+    let client = R::client(device);
+
+    let mut start = 0;
+    let inputs = shapes
+        .iter()
+        .map(|shape| {
+            let num_elements: usize = shape.iter().product();
+            let data = (start..start + num_elements)
+                .map(|i| C::from_int(i as i64))
+                .collect::<Vec<_>>();
+            start += num_elements;
+            data
+        })
+        .collect::<Vec<_>>();
+    let expected = concat_cpu(&inputs, &shapes, axis);
+
+    let handles = inputs
+        .iter()
+        .zip(&shapes)
+        .map(|(data, shape)| TensorHandle::<R, C>::from_data(&client, data, shape))
+        .collect::<Vec<_>>();
+    let refs = handles
+        .iter()
+        .map(|handle| handle.as_ref())
+        .collect::<Vec<_>>();
+    let refs = refs.iter().collect::<Vec<_>>();
+
+    let output = tensor::concat::<R, C>(&client, &refs, axis).unwrap();
+
+    let mut expected_shape = shapes[0].clone();
+    expected_shape[axis] = shapes.iter().map(|shape| shape[axis]).sum();
+    assert_eq!(output.shape, expected_shape);
+    assert_eq!(
+        output.to_vec(&client),
+        expected,
+        "concatenated data are not equal."
+    );
+}
+
+pub fn test_concat_mismatched_shape<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+
+    let lhs = TensorHandle::<R, C>::zeros(&client, vec![2, 3]);
+    let rhs = TensorHandle::<R, C>::zeros(&client, vec![3, 4]);
+
+    let result = tensor::concat::<R, C>(&client, &[&lhs.as_ref(), &rhs.as_ref()], 1);
+
+    assert_eq!(
+        result.err(),
+        Some(ConcatError::MismatchedShape {
+            input: 1,
+            shape: vec![3, 4],
+            expected: vec![2, 3],
+            axis: 1,
+        })
+    );
+}
@@ -0,0 +1,139 @@
+use std::{borrow::Cow, fmt::Display};
+
+use cubecl_core as cubecl;
+use cubecl_core::{CubeElement, prelude::*};
+use cubecl_std::{BITS_PER_WORD, read_packed_bool, write_packed_bool};
+
+use crate::tensor::{BitTensor, TensorHandle};
+
+pub fn test_round_trip<R: Runtime, C: Numeric + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+
+    let data = (0..24).map(|i| C::from_int(i)).collect::<Vec<_>>();
+    let tensor = TensorHandle::<R, C>::from_data(&client, &data, &[2, 3, 4]);
+
+    assert_eq!(tensor.strides, vec![12, 4, 1]);
+    assert_eq!(
+        tensor.to_vec(&client),
+        data,
+        "round trip data are not equal."
+    );
+}
+
+pub fn test_bit_round_trip<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+
+    let data = (0..40).map(|i| i % 3 == 0).collect::<Vec<_>>();
+    let tensor = BitTensor::<R>::from_bools(&client, &data, vec![4, 10]);
+
+    // Element i is bit i % 32 of word i / 32, starting from the least significant bit.
+    let words = u32::from_bytes(&client.read_one(tensor.handle.clone().binding())).to_vec();
+    assert_eq!(
+        words,
+        vec![0x4924_9249, 0x92],
+        "packed words are not equal."
+    );
+    assert_eq!(
+        tensor.to_bools(&client),
+        data,
+        "round trip data are not equal."
+    );
+}
+
+/// Each unit packs the booleans of a whole word, since units writing to the same word would race.
+#[cube(launch)]
+fn write_packed_kernel(values: &Array<u32>, words: &mut Array<u32>) {
+    if ABSOLUTE_POS < words.len() {
+        for bit in 0..BITS_PER_WORD {
+            let index = ABSOLUTE_POS * BITS_PER_WORD + bit;
+            if index < values.len() {
+                write_packed_bool(words, index, values[index] == 1);
+            }
+        }
+    }
+}
+
+#[cube(launch)]
+fn read_packed_kernel(words: &Array<u32>, values: &mut Array<u32>) {
+    if ABSOLUTE_POS < values.len() {
+        values[ABSOLUTE_POS] = u32::cast_from(read_packed_bool(words, ABSOLUTE_POS));
+    }
+}
+
+pub fn test_packed_bool_kernel_round_trip<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+
+    let data = (0..40).map(|i| i % 3 == 0).collect::<Vec<_>>();
+    let values = data.iter().map(|value| *value as u32).collect::<Vec<_>>();
+    let values = client.create(u32::as_bytes(&values));
+    // Start from alternating bits so both setting and clearing a bit are covered.
+    let initial = (0..40).map(|i| i % 2 == 1).collect::<Vec<_>>();
+    let tensor = BitTensor::<R>::from_bools(&client, &initial, vec![4, 10]);
+
+    write_packed_kernel::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(tensor.num_words() as u32),
+        unsafe { ArrayArg::from_raw_parts::<u32>(&values, data.len(), 1) },
+        tensor.as_array_arg(),
+    );
+
+    assert_eq!(
+        tensor.to_bools(&client),
+        data,
+        "written data are not equal."
+    );
+
+    let output = client.empty(data.len() * size_of::<u32>());
+    read_packed_kernel::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(data.len() as u32),
+        tensor.as_array_arg(),
+        unsafe { ArrayArg::from_raw_parts::<u32>(&output, data.len(), 1) },
+    );
+
+    let actual = u32::from_bytes(&client.read_one(output.binding()))
+        .iter()
+        .map(|value| *value == 1)
+        .collect::<Vec<_>>();
+    assert_eq!(actual, data, "read data are not equal.");
+}
+
+pub fn test_read_transposed<R: Runtime, C: Numeric + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+
+    let data = (0..6).map(|i| C::from_int(i)).collect::<Vec<_>>();
+    let tensor = TensorHandle::<R, C>::from_data(&client, &data, &[2, 3]);
+    let transposed = TensorHandle::<R, C>::new(vec![3, 2], vec![1, 3], tensor.handle.clone());
+
+    let expected = [0, 3, 1, 4, 2, 5].map(|i| C::from_int(i));
+
+    assert_eq!(
+        &transposed.to_vec(&client)[..],
+        &expected[..],
+        "transposed data are not equal."
+    );
+}
+
+pub fn test_as_contiguous<R: Runtime, C: Numeric + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+
+    let data = (0..6).map(|i| C::from_int(i)).collect::<Vec<_>>();
+    let tensor = TensorHandle::<R, C>::from_data(&client, &data, &[2, 3]);
+    let transposed = TensorHandle::<R, C>::new(vec![3, 2], vec![1, 3], tensor.handle.clone());
+
+    assert!(tensor.is_contiguous());
+    assert!(!transposed.is_contiguous());
+    assert!(matches!(tensor.as_contiguous(&client), Cow::Borrowed(_)));
+
+    let contiguous = transposed.as_contiguous(&client);
+    let expected = [0, 3, 1, 4, 2, 5].map(|i| C::from_int(i));
+
+    assert!(contiguous.is_contiguous());
+    assert_eq!(
+        &contiguous.to_vec(&client)[..],
+        &expected[..],
+        "contiguous data are not equal."
+    );
+}
@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement,
+    prelude::{Float, Runtime},
+};
+use cubecl_reduce::ReduceError;
+
+use super::test_utils::layernorm_cpu;
+use crate::{
+    matmul::tests::test_utils::{Sample, assert_equals_approx},
+    tensor::{self, TensorHandle},
+};
+
+pub fn test_layernorm<R: Runtime, F: Float + CubeElement + Display + Sample>(
+    device: &R::Device,
+    shape: Vec<usize>,
+    axis: usize,
+) {
+    let client = R::client(device);
+    let eps = 1e-5;
+
+    let input = F::sample(shape.iter().product(), 1234);
+    let gamma = F::sample(shape[axis], 42);
+    let beta = F::sample(shape[axis], 7);
+    let expected = layernorm_cpu(&input, &gamma, &beta, &shape, axis, eps);
+
+    let input_handle = TensorHandle::<R, F>::from_data(&client, &input, &shape);
+    let gamma_handle = TensorHandle::<R, F>::from_data(&client, &gamma, &[shape[axis]]);
+    let beta_handle = TensorHandle::<R, F>::from_data(&client, &beta, &[shape[axis]]);
+    let out = TensorHandle::<R, F>::empty(&client, shape);
+
+    let result = tensor::layernorm::<R, F>(
+        &client,
+        &input_handle.as_ref(),
+        &gamma_handle.as_ref(),
+        &beta_handle.as_ref(),
+        &out.as_ref(),
+        axis,
+        eps,
+    );
+    if result
+        .is_err_and(|e| e == ReduceError::PlanesUnavailable || e == ReduceError::ImprecisePlaneDim)
+    {
+        return; // We don't test in that case.
+    }
+
+    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 1e-4) {
+        panic!("{}", e);
+    }
+}
@@ -1,4 +1,7 @@
+pub mod concat;
+pub mod data;
 pub mod identity;
+pub mod layernorm;
 
 mod test_macros;
 mod test_utils;
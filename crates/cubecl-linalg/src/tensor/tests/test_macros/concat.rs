@@ -0,0 +1,47 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_concat {
+    () => {
+        mod concat {
+            $crate::testgen_tensor_concat!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_last_axis() {
+                cubecl_linalg::tensor::tests::concat::test_concat::<TestRuntime, NumericT>(&Default::default(), vec![vec![2, 3], vec![2, 5]], 1);
+            }
+
+            #[test]
+            pub fn test_first_axis() {
+                cubecl_linalg::tensor::tests::concat::test_concat::<TestRuntime, NumericT>(&Default::default(), vec![vec![2, 4], vec![3, 4]], 0);
+            }
+
+            #[test]
+            pub fn test_inner_axis_three_inputs() {
+                cubecl_linalg::tensor::tests::concat::test_concat::<TestRuntime, NumericT>(&Default::default(), vec![vec![2, 1, 8], vec![2, 3, 8], vec![2, 2, 8]], 1);
+            }
+
+            #[test]
+            pub fn test_mismatched_shape() {
+                cubecl_linalg::tensor::tests::concat::test_concat_mismatched_shape::<TestRuntime, NumericT>(&Default::default());
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod concat {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_concat!($numeric);
+                })*
+            }
+        }
+    };
+}
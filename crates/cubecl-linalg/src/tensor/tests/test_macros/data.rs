@@ -0,0 +1,53 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_data {
+    () => {
+        mod data {
+            $crate::testgen_tensor_data!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+            use cubecl_core::flex32;
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_round_trip() {
+                cubecl_linalg::tensor::tests::data::test_round_trip::<TestRuntime, NumericT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_bit_round_trip() {
+                cubecl_linalg::tensor::tests::data::test_bit_round_trip::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_packed_bool_kernel_round_trip() {
+                cubecl_linalg::tensor::tests::data::test_packed_bool_kernel_round_trip::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_read_transposed() {
+                cubecl_linalg::tensor::tests::data::test_read_transposed::<TestRuntime, NumericT>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_as_contiguous() {
+                cubecl_linalg::tensor::tests::data::test_as_contiguous::<TestRuntime, NumericT>(&Default::default());
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod data {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_data!($numeric);
+                })*
+            }
+        }
+    };
+}
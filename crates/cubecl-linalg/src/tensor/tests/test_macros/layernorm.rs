@@ -0,0 +1,42 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_layernorm {
+    () => {
+        mod layernorm {
+            $crate::testgen_tensor_layernorm!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_last_axis() {
+                cubecl_linalg::tensor::tests::layernorm::test_layernorm::<TestRuntime, FloatT>(&Default::default(), vec![4, 96], 1);
+            }
+
+            #[test]
+            pub fn test_inner_axis() {
+                cubecl_linalg::tensor::tests::layernorm::test_layernorm::<TestRuntime, FloatT>(&Default::default(), vec![2, 64, 3], 1);
+            }
+
+            #[test]
+            pub fn test_axis_longer_than_cube() {
+                cubecl_linalg::tensor::tests::layernorm::test_layernorm::<TestRuntime, FloatT>(&Default::default(), vec![3, 4100], 1);
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod layernorm {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_layernorm!($float);
+                })*
+            }
+        }
+    };
+}
@@ -1 +1,4 @@
+mod concat;
+mod data;
 mod identity;
+mod layernorm;
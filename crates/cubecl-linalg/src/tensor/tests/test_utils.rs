@@ -1,4 +1,7 @@
-use cubecl_core::{CubeElement, prelude::Numeric};
+use cubecl_core::{
+    CubeElement,
+    prelude::{Float, Numeric},
+};
 
 pub(crate) fn identity_cpu<E: Numeric + CubeElement>(dim: usize) -> Vec<E> {
     let num_elements = dim * dim;
@@ -12,3 +15,54 @@ pub(crate) fn identity_cpu<E: Numeric + CubeElement>(dim: usize) -> Vec<E> {
 
     result
 }
+
+pub(crate) fn layernorm_cpu<F: Float + CubeElement>(
+    input: &[F],
+    gamma: &[F],
+    beta: &[F],
+    shape: &[usize],
+    axis: usize,
+    eps: f32,
+) -> Vec<F> {
+    let length = shape[axis];
+    let stride: usize = shape[axis + 1..].iter().product();
+    let num_rows = input.len() / length;
+    let mut result = vec![F::from_int(0); input.len()];
+
+    for row in 0..num_rows {
+        let offset = (row / stride) * stride * length + row % stride;
+        let values = (0..length)
+            .map(|i| input[offset + i * stride].to_f64().unwrap())
+            .collect::<Vec<_>>();
+
+        let mean = values.iter().sum::<f64>() / length as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / length as f64;
+        let inv_std = 1.0 / (variance + eps as f64).sqrt();
+
+        for (i, value) in values.iter().enumerate() {
+            let normalized = (value - mean) * inv_std;
+            let scaled = normalized * gamma[i].to_f64().unwrap() + beta[i].to_f64().unwrap();
+            result[offset + i * stride] = F::new(scaled as f32);
+        }
+    }
+
+    result
+}
+
+pub(crate) fn concat_cpu<E: Numeric + CubeElement>(
+    inputs: &[Vec<E>],
+    shapes: &[Vec<usize>],
+    axis: usize,
+) -> Vec<E> {
+    let num_outer: usize = shapes[0][..axis].iter().product();
+    let mut result = Vec::new();
+
+    for outer in 0..num_outer {
+        for (input, shape) in inputs.iter().zip(shapes) {
+            let chunk: usize = shape[axis..].iter().product();
+            result.extend_from_slice(&input[outer * chunk..(outer + 1) * chunk]);
+        }
+    }
+
+    result
+}
@@ -5,7 +5,7 @@ use quote::{ToTokens, format_ident, quote};
 use syn::{DeriveInput, Index, Member};
 
 use crate::{
-    parse::autotune::{AutotuneKey, AutotuneKeyField},
+    parse::autotune::{Anchor, AutotuneKey, AutotuneKeyField},
     paths::tune_type,
 };
 
@@ -126,19 +126,28 @@ impl AutotuneKey {
 }
 
 fn field_init(field: &AutotuneKeyField, member: Member) -> TokenStream {
-    let anchor_fn = tune_type("anchor");
     field
         .anchor
         .as_ref()
         .map(|anchor| {
             let max = anchor.max();
-            quote![#anchor_fn(#member, #max)]
+            match anchor {
+                Anchor::Multiple(multiple) => {
+                    let anchor_fn = tune_type("anchor_multiple");
+                    quote![#anchor_fn(#member, #multiple, #max)]
+                }
+                _ => {
+                    let anchor_fn = tune_type("anchor");
+                    quote![#anchor_fn(#member, #max)]
+                }
+            }
         })
         .unwrap_or_else(|| member.to_token_stream())
 }
 
 pub fn generate_autotune_key(input: DeriveInput) -> syn::Result<TokenStream> {
     let key = AutotuneKey::from_derive_input(&input)?;
+    key.validate()?;
     let display = key.generate_fmt();
     let new = key.generate_new();
     let key_impl = key.generate_key_impl();
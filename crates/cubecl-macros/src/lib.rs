@@ -206,8 +206,9 @@ pub fn terminate(input: TokenStream) -> TokenStream {
 ///
 /// # Helper
 ///
-/// Use the `#[autotune]` helper attribute to anchor fields to the next power of two, or rename
-/// the fields for the display implementation.
+/// Use the `#[autotune]` helper attribute to anchor fields to the next power of two, or to the
+/// next multiple of a given number with `anchor(multiple = ..)`, so nearby sizes share a key.
+/// A multiple of zero is rejected at compile time. The helper can also rename the fields for the display implementation.
 ///
 /// # Example
 /// ```ignore
@@ -220,6 +221,8 @@ pub fn terminate(input: TokenStream) -> TokenStream {
 ///     height: usize,
 ///     #[autotune(anchor)]
 ///     width: usize,
+///     #[autotune(anchor(multiple = 64))]
+///     sequence_length: usize,
 /// }
 /// ```
 #[proc_macro_derive(AutotuneKey, attributes(autotune))]
@@ -1,7 +1,7 @@
 use darling::{FromDeriveInput, FromField, FromMeta, ast::Data};
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Generics, Ident, Type, Visibility};
+use syn::{Generics, Ident, Type, Visibility, spanned::Spanned};
 
 #[derive(FromDeriveInput)]
 #[darling(supports(struct_any))]
@@ -16,6 +16,26 @@ impl AutotuneKey {
     pub fn is_tuple(&self) -> bool {
         self.data.as_ref().take_struct().unwrap().is_tuple()
     }
+
+    /// Reject the attribute values that parse fine but can't be used to anchor a field.
+    pub fn validate(&self) -> syn::Result<()> {
+        let fields = self.data.as_ref().take_struct().unwrap();
+
+        for field in fields.iter() {
+            if let Some(Anchor::Multiple(0)) = field.anchor {
+                let span = match &field.ident {
+                    Some(ident) => ident.span(),
+                    None => field.ty.span(),
+                };
+                return Err(syn::Error::new(
+                    span,
+                    "Can't anchor to a multiple of zero, `multiple` must be at least 1",
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(FromField)]
@@ -32,12 +52,13 @@ pub enum Anchor {
     #[darling(word)]
     Unlimited,
     Max(usize),
+    Multiple(usize),
 }
 
 impl Anchor {
     pub fn max(&self) -> TokenStream {
         match self {
-            Anchor::Unlimited => quote![None],
+            Anchor::Unlimited | Anchor::Multiple(_) => quote![None],
             Anchor::Max(value) => quote![Some(#value)],
         }
     }
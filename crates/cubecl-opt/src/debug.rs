@@ -277,6 +277,8 @@ impl Display for Constant {
             Constant::Int(val, IntKind::I16) => write!(f, "{val}i16"),
             Constant::Int(val, IntKind::I32) => write!(f, "{val}i32"),
             Constant::Int(val, IntKind::I64) => write!(f, "{val}i64"),
+            Constant::Float(val, FloatKind::E4M3) => write!(f, "{}e4m3", val.0),
+            Constant::Float(val, FloatKind::E5M2) => write!(f, "{}e5m2", val.0),
             Constant::Float(val, FloatKind::BF16) => write!(f, "{}bf16", val.0),
             Constant::Float(val, FloatKind::F16) => write!(f, "{}f16", val.0),
             Constant::Float(val, FloatKind::Flex32) => write!(f, "{}minf16", val.0),
@@ -420,7 +420,13 @@ fn try_const_eval_arithmetic(op: &mut Arithmetic) -> Option<ConstantScalarValue>
                 }
             })
         }
-        Arithmetic::Erf(_) | Arithmetic::Magnitude(_) | Arithmetic::Normalize(_) => None,
+        Arithmetic::Erf(_)
+        | Arithmetic::Magnitude(_)
+        | Arithmetic::Normalize(_)
+        | Arithmetic::SaturatingAdd(_)
+        | Arithmetic::SaturatingSub(_)
+        | Arithmetic::WrappingAdd(_)
+        | Arithmetic::WrappingMul(_) => None,
     }
 }
 
@@ -9,11 +9,20 @@ use cubecl_std::tensor::r#virtual::{
 pub trait ReduceDType {
     type In: Numeric;
     type Out: Numeric;
+    /// The type input elements are cast into when they are read.
+    type Acc: Numeric;
 }
 
 impl<In: Numeric, Out: Numeric> ReduceDType for (In, Out) {
     type In = In;
     type Out = Out;
+    type Acc = In;
+}
+
+impl<In: Numeric, Out: Numeric, Acc: Numeric> ReduceDType for (In, Out, Acc) {
+    type In = In;
+    type Out = Out;
+    type Acc = Acc;
 }
 
 #[cube]
@@ -51,19 +60,21 @@ pub trait ReduceArgs: Send + Sync + 'static + Clone {
     fn stride_output<P: ReduceDType>(state: &Self::State<P>, dim: u32) -> u32;
 }
 
+/// Create the virtual tensors of a reduction, where the input elements are cast to `Acc` when they
+/// are read.
 #[cube]
-pub fn init_tensors<RA: ReduceArgs, In: Numeric, Out: Numeric>(
+pub fn init_tensors<RA: ReduceArgs, In: Numeric, Out: Numeric, Acc: Numeric>(
     input: &RA::Input<In>,
     output: &mut RA::Output<Out>,
-) -> (VirtualTensor<In>, VirtualTensor<Out, ReadWrite>) {
-    let mut state = RA::init_state::<(In, Out)>(input, output);
+) -> (VirtualTensor<Acc>, VirtualTensor<Out, ReadWrite>) {
+    let mut state = RA::init_state::<(In, Out, Acc)>(input, output);
 
     let input = TensorArg::new_input(&state);
     let mut output = TensorArg::new_output(&mut state);
 
-    let input = VirtualTensor::<In>::new::<TensorArg<(In, Out), RA, Input>>(&input);
+    let input = VirtualTensor::<Acc>::new::<TensorArg<(In, Out, Acc), RA, Input>>(&input);
     let output =
-        VirtualTensor::<Out, ReadWrite>::new::<TensorArg<(In, Out), RA, Output>>(&mut output);
+        VirtualTensor::<Out, ReadWrite>::new::<TensorArg<(In, Out, Acc), RA, Output>>(&mut output);
 
     (input, output)
 }
@@ -180,24 +191,26 @@ impl<P: ReduceDType, RA: ReduceArgs> TensorArg<P, RA, Output> {
 }
 
 impl<P: ReduceDType, RA: ReduceArgs> VirtualTensorOperations<P::Out> for TensorArg<P, RA, Output> {}
-impl<P: ReduceDType, RA: ReduceArgs> VirtualTensorOperations<P::In> for TensorArg<P, RA, Input> {}
+impl<P: ReduceDType, RA: ReduceArgs> VirtualTensorOperations<P::Acc> for TensorArg<P, RA, Input> {}
 
-impl<P: ReduceDType, RA: ReduceArgs> VirtualTensorOperationsExpand<P::In>
+impl<P: ReduceDType, RA: ReduceArgs> VirtualTensorOperationsExpand<P::Acc>
     for TensorArgExpand<P, RA, Input>
 {
     fn __expand_read_method(
         &self,
         scope: &mut Scope,
         index: ExpandElementTyped<u32>,
-    ) -> ExpandElementTyped<Line<P::In>> {
-        RA::__expand_read_input(scope, self.state.clone(), index)
+    ) -> ExpandElementTyped<Line<P::Acc>> {
+        let value = RA::__expand_read_input(scope, self.state.clone(), index);
+        // No-op when the input is already of the accumulator type.
+        Line::<P::Acc>::__expand_cast_from(scope, value)
     }
 
     fn __expand_write_method(
         &self,
         _scope: &mut Scope,
         _index: ExpandElementTyped<u32>,
-        _value: ExpandElementTyped<Line<P::In>>,
+        _value: ExpandElementTyped<Line<P::Acc>>,
     ) {
         unreachable!("Can't write to input")
     }
@@ -233,7 +246,7 @@ impl<P: ReduceDType, RA: ReduceArgs> VirtualTensorOperationsExpand<P::In>
         _context: &mut Scope,
         _start: ExpandElementTyped<u32>,
         _end: ExpandElementTyped<u32>,
-    ) -> ExpandElementTyped<Slice<Line<P::In>>> {
+    ) -> ExpandElementTyped<Slice<Line<P::Acc>>> {
         panic!("Unsupported")
     }
 }
@@ -34,6 +34,7 @@ impl ArgMax {
 
 impl Reduce for ArgMax {
     type Instruction<In: Numeric> = Self;
+    type Accumulator<In: Numeric, Out: Numeric> = In;
 }
 
 #[cube]
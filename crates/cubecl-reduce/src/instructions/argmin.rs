@@ -12,6 +12,7 @@ pub struct ArgMin;
 
 impl Reduce for ArgMin {
     type Instruction<In: Numeric> = Self;
+    type Accumulator<In: Numeric, Out: Numeric> = In;
 }
 
 #[cube]
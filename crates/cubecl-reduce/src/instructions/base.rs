@@ -3,6 +3,13 @@ use cubecl_core::prelude::*;
 
 pub trait Reduce: Send + Sync + 'static + std::fmt::Debug {
     type Instruction<In: Numeric>: ReduceInstruction<In>;
+
+    /// The element type into which the input elements are cast before being reduced, given the
+    /// input and output element types of the reduction.
+    ///
+    /// Accumulating into a type narrower than the input, e.g. summing `f32` into `f16`, loses
+    /// precision on every element rather than only once on the result.
+    type Accumulator<In: Numeric, Out: Numeric>: Numeric;
 }
 
 /// An instruction for a reduce algorithm that works with [`Line`].
@@ -10,6 +10,7 @@ pub struct MaxAbs;
 
 impl Reduce for MaxAbs {
     type Instruction<In: Numeric> = Self;
+    type Accumulator<In: Numeric, Out: Numeric> = In;
 }
 
 #[cube]
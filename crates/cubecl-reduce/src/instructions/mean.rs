@@ -8,6 +8,7 @@ pub struct Mean;
 
 impl Reduce for Mean {
     type Instruction<In: Numeric> = Self;
+    type Accumulator<In: Numeric, Out: Numeric> = Out;
 }
 
 #[cube]
@@ -8,6 +8,7 @@ pub struct Prod;
 
 impl Reduce for Prod {
     type Instruction<In: Numeric> = Self;
+    type Accumulator<In: Numeric, Out: Numeric> = Out;
 }
 
 #[cube]
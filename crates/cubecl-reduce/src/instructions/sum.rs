@@ -8,6 +8,7 @@ pub struct Sum;
 
 impl Reduce for Sum {
     type Instruction<In: Numeric> = Self;
+    type Accumulator<In: Numeric, Out: Numeric> = Out;
 }
 
 #[cube]
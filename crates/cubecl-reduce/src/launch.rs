@@ -38,7 +38,7 @@ pub(crate) fn launch_reduce<Run: Runtime, In: Numeric, Out: Numeric, Rd: Reduce>
         bound_checks_inner: config.bound_checks_inner,
     };
     unsafe {
-        reduce_kernel::launch_unchecked::<In, Out, Rd, TensorArgs, Run>(
+        reduce_kernel::launch_unchecked::<In, Out, Rd::Accumulator<In, Out>, Rd, TensorArgs, Run>(
             client,
             config.cube_count,
             config.cube_dim,
@@ -61,14 +61,15 @@ pub struct ReduceParams {
     pub bound_checks_inner: BoundChecksInner,
 }
 
+/// Reduce kernel reading `In` elements, accumulating them as `Acc` and writing `Out` elements.
 #[cube(launch_unchecked)]
-pub fn reduce_kernel<In: Numeric, Out: Numeric, R: Reduce, RA: ReduceArgs>(
+pub fn reduce_kernel<In: Numeric, Out: Numeric, Acc: Numeric, R: Reduce, RA: ReduceArgs>(
     input: &RA::Input<In>,
     output: &mut RA::Output<Out>,
     axis_reduce: u32,
     #[comptime] params: ReduceParams,
 ) {
-    let (input, mut output) = init_tensors::<RA, In, Out>(input, output);
+    let (input, mut output) = init_tensors::<RA, In, Out, Acc>(input, output);
     let reduce_index = get_reduce_index(params);
 
     if comptime![params.bound_checks]
@@ -77,7 +78,7 @@ pub fn reduce_kernel<In: Numeric, Out: Numeric, R: Reduce, RA: ReduceArgs>(
         terminate!();
     }
 
-    let range = ReduceRange::new::<In, Out>(
+    let range = ReduceRange::new::<Acc, Out>(
         reduce_index,
         &input,
         &mut output,
@@ -88,7 +89,7 @@ pub fn reduce_kernel<In: Numeric, Out: Numeric, R: Reduce, RA: ReduceArgs>(
 
     let accumulator = match comptime!((params.shared, params.use_planes)) {
         (Some(accumulator_size), use_planes) => {
-            let mut accumulator = reduce_slice_shared::<In, VirtualTensor<In>, R::Instruction<In>>(
+            let mut accumulator = reduce_slice_shared::<Acc, VirtualTensor<Acc>, R::Instruction<Acc>>(
                 &input,
                 range,
                 accumulator_size,
@@ -98,16 +99,16 @@ pub fn reduce_kernel<In: Numeric, Out: Numeric, R: Reduce, RA: ReduceArgs>(
                 params.bound_checks_inner,
             );
             sync_units();
-            reduce_tree::<In, R::Instruction<In>>(&mut accumulator, accumulator_size)
+            reduce_tree::<Acc, R::Instruction<Acc>>(&mut accumulator, accumulator_size)
         }
-        (None, true) => reduce_slice_plane::<In, VirtualTensor<In>, R::Instruction<In>>(
+        (None, true) => reduce_slice_plane::<Acc, VirtualTensor<Acc>, R::Instruction<Acc>>(
             &input,
             range,
             params.line_size_input,
             params.line_mode,
             params.bound_checks_inner,
         ),
-        (None, false) => reduce_slice::<In, VirtualTensor<In>, R::Instruction<In>>(
+        (None, false) => reduce_slice::<Acc, VirtualTensor<Acc>, R::Instruction<Acc>>(
             &input,
             range,
             params.line_size_input,
@@ -116,7 +117,7 @@ pub fn reduce_kernel<In: Numeric, Out: Numeric, R: Reduce, RA: ReduceArgs>(
     };
 
     if elected_writer(params) {
-        write_to_output::<In, Out, R::Instruction<In>>(
+        write_to_output::<Acc, Out, R::Instruction<Acc>>(
             &mut output,
             accumulator,
             reduce_index,
@@ -46,6 +46,12 @@ use cubecl_core::prelude::*;
 /// Also returns an error if the `axis` is larger than the `input` rank or if the shape of `output` is invalid.
 /// The shape of `output` must be the same as input except with a value of 1 for the given `axis`.
 ///
+/// Input elements are cast to the [accumulator type](Reduce::Accumulator) of the instruction when
+/// they are read. For [`Sum`](instructions::Sum), [`Mean`](instructions::Mean) and
+/// [`Prod`](instructions::Prod), this is `Out`, so reducing `f16` values into an `f32` output
+/// accumulates in `f32`. An `Out` narrower than `In` loses precision on every element, not only on
+/// the final result.
+///
 ///
 /// # Example
 ///
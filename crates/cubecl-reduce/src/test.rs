@@ -307,6 +307,17 @@ macro_rules! impl_test_reduce_with_strategy {
                     };
                     test.test_sum::<$float, TestRuntime>(&Default::default());
                 }
+
+                #[test]
+                pub fn [< sum_accumulate_f32_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_sum_accumulate_f32::<$float, TestRuntime>(&Default::default());
+                }
             )*
         }
     };
@@ -417,6 +428,17 @@ impl TestCase {
         self.run_reduce_test::<F, F, R, Sum>(device, input_values, expected_values)
     }
 
+    pub fn test_sum_accumulate_f32<F, R>(&self, device: &R::Device)
+    where
+        F: Float + CubeElement + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F> = self.random_input_values();
+        let widened: Vec<f32> = input_values.iter().map(|v| v.to_f32().unwrap()).collect();
+        let expected_values = self.cpu_sum(&widened);
+        self.run_reduce_test::<F, f32, R, Sum>(device, input_values, expected_values)
+    }
+
     fn cpu_sum<F: Float>(&self, values: &[F]) -> Vec<F> {
         let mut expected = vec![F::new(0.0); self.num_output_values()];
 
@@ -14,6 +14,7 @@ fn main() {
     let mem_props = MemoryDeviceProperties {
         max_page_size: 2048 * MB,
         alignment: 32,
+        total_memory: None,
     };
     let mut mm = MemoryManagement::from_configuration(storage, &mem_props, config);
     let mut handles = LinkedList::new();
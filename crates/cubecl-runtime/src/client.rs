@@ -7,8 +7,10 @@ use crate::{
     server::{Binding, ComputeServer, CubeCount, Handle},
     storage::{BindingResource, ComputeStorage},
 };
+use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::time::Duration;
 use cubecl_common::{ExecutionMode, benchmark::TimestampsResult};
 
 /// The ComputeClient is the entry point to require tasks from the ComputeServer.
@@ -196,6 +198,26 @@ where
         output
     }
 
+    /// Start a [profiling session](ProfilingSession) to measure the duration of individual
+    /// scopes of work.
+    ///
+    /// Like [profile](Self::profile), the session holds the profiling lock until it is dropped,
+    /// so only one session or profile scope can be active on a client at a time.
+    pub async fn profiling_session(&self) -> ProfilingSession<'_, Server, Channel> {
+        let guard = self.state.timestamp_lock.lock().await;
+
+        self.channel.enable_timestamps();
+
+        // Reset the client's timestamp state.
+        self.sync_elapsed().await.ok();
+
+        ProfilingSession {
+            client: self,
+            scopes: Vec::new(),
+            _guard: guard,
+        }
+    }
+
     /// Enable timestamp collection on the server for performance profiling.
     ///
     /// This feature records precise timing data for server operations, which can be used
@@ -218,3 +240,77 @@ where
         self.channel.enable_timestamps();
     }
 }
+
+/// Measures the duration of named scopes of work on a [client](ComputeClient).
+///
+/// Each scope is synchronized before and after it runs, so its duration only covers the tasks
+/// queued inside of it. This adds a synchronization per scope, which makes it a debugging tool
+/// rather than something to keep in production.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut session = client.profiling_session().await;
+/// session.scope("into_contiguous", || into_contiguous(&client, &input)).await;
+/// session.scope("matmul", || matmul(&client, &lhs, &rhs, &out)).await;
+///
+/// for scope in session.finish() {
+///     println!("{}: {:?}", scope.name, scope.duration);
+/// }
+/// ```
+pub struct ProfilingSession<'a, Server: ComputeServer, Channel: ComputeChannel<Server>> {
+    client: &'a ComputeClient<Server, Channel>,
+    scopes: Vec<ProfiledScope>,
+    _guard: async_lock::MutexGuard<'a, ()>,
+}
+
+/// Duration of a scope measured by a [profiling session](ProfilingSession).
+#[derive(Debug, Clone)]
+pub struct ProfiledScope {
+    /// Name given to the scope.
+    pub name: String,
+    /// Time spent executing the tasks of the scope, `None` when the backend can't measure it.
+    pub duration: Option<Duration>,
+}
+
+impl<Server, Channel> ProfilingSession<'_, Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    /// Execute `func` and record the duration of the tasks it queued under `name`.
+    pub async fn scope<O>(&mut self, name: impl Into<String>, func: impl FnOnce() -> O) -> O {
+        // Exclude the tasks queued before the scope.
+        self.client.sync_elapsed().await.ok();
+
+        let output = func();
+        let duration = self.client.sync_elapsed().await.ok();
+
+        self.scopes.push(ProfiledScope {
+            name: name.into(),
+            duration,
+        });
+
+        output
+    }
+
+    /// The scopes recorded so far, in the order they were executed.
+    pub fn scopes(&self) -> &[ProfiledScope] {
+        &self.scopes
+    }
+
+    /// End the session and return the recorded scopes, in the order they were executed.
+    pub fn finish(mut self) -> Vec<ProfiledScope> {
+        core::mem::take(&mut self.scopes)
+    }
+}
+
+impl<Server, Channel> Drop for ProfilingSession<'_, Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    fn drop(&mut self) {
+        self.client.channel.disable_timestamps();
+    }
+}
@@ -1,5 +1,6 @@
 use crate::memory_management::{HardwareProperties, MemoryDeviceProperties};
 use alloc::collections::BTreeSet;
+use alloc::string::String;
 
 /// Properties of what the device can do, like what `Feature` are
 /// supported by it and what its memory properties are.
@@ -8,14 +9,20 @@ pub struct DeviceProperties<Feature: Ord + Copy> {
     set: alloc::collections::BTreeSet<Feature>,
     memory: MemoryDeviceProperties,
     hardware: HardwareProperties,
+    device_name: String,
+    driver_version: String,
 }
 
 impl<Feature: Ord + Copy> DeviceProperties<Feature> {
     /// Create a new feature set with the given features and memory properties.
+    ///
+    /// The device name should identify the physical device, e.g. the GPU model, and stay the same
+    /// across runs on the same hardware.
     pub fn new(
         features: &[Feature],
         memory_props: MemoryDeviceProperties,
         hardware: HardwareProperties,
+        device_name: String,
     ) -> Self {
         let mut set = BTreeSet::new();
         for feature in features {
@@ -26,6 +33,8 @@ impl<Feature: Ord + Copy> DeviceProperties<Feature> {
             set,
             memory: memory_props,
             hardware,
+            device_name,
+            driver_version: String::new(),
         }
     }
 
@@ -41,6 +50,13 @@ impl<Feature: Ord + Copy> DeviceProperties<Feature> {
         self.set.insert(feature)
     }
 
+    /// Register the version of the driver running the device.
+    ///
+    /// This should only be used by a [runtime](cubecl_core::Runtime) when initializing a device.
+    pub fn register_driver_version(&mut self, driver_version: String) {
+        self.driver_version = driver_version;
+    }
+
     /// The memory properties of this client.
     pub fn memory_properties(&self) -> &MemoryDeviceProperties {
         &self.memory
@@ -50,4 +66,18 @@ impl<Feature: Ord + Copy> DeviceProperties<Feature> {
     pub fn hardware_properties(&self) -> &HardwareProperties {
         &self.hardware
     }
+
+    /// The name of the physical device, e.g. the GPU model.
+    ///
+    /// It is stable across runs on the same hardware, so it can be used with the
+    /// [runtime name](cubecl_core::Runtime::name) in cache keys and logs.
+    pub fn device_name(&self) -> String {
+        self.device_name.clone()
+    }
+
+    /// The version of the driver running the device, or an empty string if the runtime doesn't
+    /// expose it.
+    pub fn driver_version(&self) -> String {
+        self.driver_version.clone()
+    }
 }
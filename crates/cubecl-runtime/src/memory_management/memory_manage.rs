@@ -367,6 +367,7 @@ mod tests {
     const DUMMY_MEM_PROPS: MemoryDeviceProperties = MemoryDeviceProperties {
         max_page_size: 128 * 1024 * 1024,
         alignment: 32,
+        total_memory: None,
     };
 
     // Test pools with slices.
@@ -510,6 +511,7 @@ mod tests {
             &MemoryDeviceProperties {
                 max_page_size: page_size,
                 alignment: 50,
+                total_memory: None,
             },
             MemoryConfiguration::Custom {
                 pool_options: vec![MemoryPoolOptions {
@@ -548,6 +550,7 @@ mod tests {
             &MemoryDeviceProperties {
                 max_page_size: 128 * 1024 * 1024,
                 alignment: 10,
+                total_memory: None,
             },
             MemoryConfiguration::Custom {
                 pool_options: pools,
@@ -572,6 +575,7 @@ mod tests {
             &MemoryDeviceProperties {
                 max_page_size: 128 * 1024 * 1024,
                 alignment: 32,
+                total_memory: None,
             },
             MemoryConfiguration::SubSlices,
         );
@@ -601,6 +605,7 @@ mod tests {
             &MemoryDeviceProperties {
                 max_page_size: 128 * 1024 * 1024,
                 alignment: 32,
+                total_memory: None,
             },
             MemoryConfiguration::SubSlices,
         );
@@ -632,6 +637,7 @@ mod tests {
             &(MemoryDeviceProperties {
                 max_page_size: 128 * 1024 * 1024,
                 alignment: 32,
+                total_memory: None,
             }),
             MemoryConfiguration::ExclusivePages,
         );
@@ -725,6 +731,7 @@ mod tests {
             &MemoryDeviceProperties {
                 max_page_size: DUMMY_MEM_PROPS.max_page_size,
                 alignment: 50,
+                total_memory: None,
             },
             MemoryConfiguration::Custom {
                 pool_options: vec![MemoryPoolOptions {
@@ -760,6 +767,7 @@ mod tests {
             &MemoryDeviceProperties {
                 max_page_size: DUMMY_MEM_PROPS.max_page_size,
                 alignment: 10,
+                total_memory: None,
             },
             MemoryConfiguration::Custom {
                 pool_options: pools,
@@ -780,6 +788,7 @@ mod tests {
             &MemoryDeviceProperties {
                 max_page_size: 128 * 1024 * 1024,
                 alignment: 32,
+                total_memory: None,
             },
             MemoryConfiguration::ExclusivePages,
         );
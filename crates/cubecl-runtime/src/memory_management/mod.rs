@@ -80,6 +80,8 @@ pub struct MemoryDeviceProperties {
     pub max_page_size: u64,
     /// The required memory offset alignment in bytes.
     pub alignment: u64,
+    /// The total nr. of bytes of memory of the device, when the runtime can query it.
+    pub total_memory: Option<u64>,
 }
 
 /// Properties of the device related to the accelerator hardware.
@@ -96,7 +98,7 @@ pub struct MemoryDeviceProperties {
 /// For Intel GPUs, this is variable based on the number of registers used in the kernel. No way to
 /// query this at compile time is currently available. As a result, the minimum value should usually
 /// be assumed.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash)]
 pub struct HardwareProperties {
     /// The minimum size of a plane on this device
     pub plane_size_min: u32,
@@ -1,4 +1,4 @@
-use super::{AutotuneKey, TunableSet, Tuner};
+use super::{AutotuneError, AutotuneKey, TunableSet, Tuner};
 use crate::{
     channel::ComputeChannel, client::ComputeClient, server::ComputeServer, tune::TuneCacheResult,
 };
@@ -43,14 +43,15 @@ impl<AK: AutotuneKey + 'static, ID: Hash + PartialEq + Eq + Clone + Display> Loc
         *state = None;
     }
 
-    /// Execute the best operation in the provided [tunable set](TunableSet)
+    /// Execute the best operation in the provided [tunable set](TunableSet), returning the error
+    /// of the operation when it fails.
     pub fn execute<S, C, In: Send + Clone + 'static, Out: Send + 'static>(
         &self,
         id: &ID,
         client: &ComputeClient<S, C>,
         operations: &TunableSet<AK, In, Out>,
         inputs: In,
-    ) -> Out
+    ) -> Result<Out, AutotuneError>
     where
         S: ComputeServer + 'static,
         C: ComputeChannel<S> + 'static,
@@ -62,9 +63,7 @@ impl<AK: AutotuneKey + 'static, ID: Hash + PartialEq + Eq + Clone + Display> Loc
             if let Some(tuner) = map.get(id) {
                 if let TuneCacheResult::Hit { fastest_index } = tuner.fastest(&key) {
                     let op = operations.fastest(fastest_index);
-                    return op
-                        .execute(inputs)
-                        .expect("Should run when selected by autotune.");
+                    return op.execute(inputs);
                 }
             }
         }
@@ -100,10 +99,7 @@ impl<AK: AutotuneKey + 'static, ID: Hash + PartialEq + Eq + Clone + Display> Loc
 
         match fastest {
             TuneCacheResult::Hit { fastest_index } => {
-                return operations
-                    .fastest(fastest_index)
-                    .execute(inputs)
-                    .expect("Should run when selected by autotune.");
+                return operations.fastest(fastest_index).execute(inputs);
             }
             TuneCacheResult::Miss => {
                 if run_autotune {
@@ -173,9 +169,6 @@ impl<AK: AutotuneKey + 'static, ID: Hash + PartialEq + Eq + Clone + Display> Loc
             }
         };
 
-        operations
-            .fastest(fastest)
-            .execute(inputs)
-            .expect("Should run when selected by autotune.")
+        operations.fastest(fastest).execute(inputs)
     }
 }
@@ -19,7 +19,7 @@
 //!         .with_tunable(kernel_2.ok())
 //!         .with_tunable(kernel_3);
 //!    
-//!     TUNER.execute("hello".to_string(), &lhs.client, &tunables, (lhs, rhs));
+//!     TUNER.execute("hello".to_string(), &lhs.client, &tunables, (lhs, rhs)).unwrap()
 //! }
 //! ```
 //!
@@ -61,6 +61,14 @@ pub enum AutotuneError {
     Unknown(String),
 }
 
+impl core::fmt::Display for AutotuneError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AutotuneError::Unknown(message) => write!(f, "{message}"),
+        }
+    }
+}
+
 impl From<String> for AutotuneError {
     fn from(value: String) -> Self {
         Self::Unknown(value)
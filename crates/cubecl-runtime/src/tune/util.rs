@@ -2,13 +2,55 @@ use core::cmp::min;
 
 /// Anchor a number to a power of 2.
 ///
-/// Useful when creating autotune keys.
+/// Useful when creating autotune keys. The anchor is the upper bound of the bucket of `x`, it is
+/// never smaller than `x` unless capped by `max`.
 pub fn anchor(x: usize, max: Option<usize>) -> usize {
-    let exp = f32::ceil(f32::log2(x as f32)) as u32;
-    let power_of_2 = 2_u32.pow(exp) as usize;
+    let power_of_2 = x.next_power_of_two();
     if let Some(max) = max {
         min(power_of_2, max)
     } else {
         power_of_2
     }
 }
+
+/// Anchor a number to the next multiple of `multiple`.
+///
+/// Finer grained than [anchor] for large numbers, when nearby sizes still need distinct tuned
+/// kernels. As with [anchor], the result is never smaller than `x` unless capped by `max`.
+pub fn anchor_multiple(x: usize, multiple: usize, max: Option<usize>) -> usize {
+    assert!(multiple > 0, "Can't anchor to a multiple of zero");
+
+    let anchored = usize::max(x.div_ceil(multiple), 1) * multiple;
+    if let Some(max) = max {
+        min(anchored, max)
+    } else {
+        anchored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchor_rounds_up_to_power_of_two() {
+        assert_eq!(anchor(1, None), 1);
+        assert_eq!(anchor(100, None), 128);
+        assert_eq!(anchor(128, None), 128);
+        assert_eq!(anchor(1000, Some(256)), 256);
+    }
+
+    #[test]
+    fn anchor_is_exact_for_large_numbers() {
+        let x = (1 << 24) + 1;
+        assert_eq!(anchor(x, None), 1 << 25);
+    }
+
+    #[test]
+    fn anchor_multiple_rounds_up() {
+        assert_eq!(anchor_multiple(0, 64, None), 64);
+        assert_eq!(anchor_multiple(65, 64, None), 128);
+        assert_eq!(anchor_multiple(128, 64, None), 128);
+        assert_eq!(anchor_multiple(1000, 64, Some(512)), 512);
+    }
+}
@@ -27,7 +27,9 @@ pub fn autotune_execute(
     set: &TunableSet<String, Vec<Binding>, ()>,
     inputs: Vec<Binding>,
 ) {
-    TEST_TUNER.execute(&TUNER_DEVICE_ID.to_string(), client, set, inputs)
+    TEST_TUNER
+        .execute(&TUNER_DEVICE_ID.to_string(), client, set, inputs)
+        .expect("Should run when selected by autotune.")
 }
 
 pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServer>> {
@@ -35,6 +37,7 @@ pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServ
     let mem_properties = MemoryDeviceProperties {
         max_page_size: 1024 * 1024 * 512,
         alignment: 32,
+        total_memory: None,
     };
     let topology = HardwareProperties {
         plane_size_min: 32,
@@ -54,7 +57,7 @@ pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServ
     let channel = MutexComputeChannel::new(server);
     ComputeClient::new(
         channel,
-        DeviceProperties::new(&[], mem_properties, topology),
+        DeviceProperties::new(&[], mem_properties, topology, "dummy".to_string()),
         (),
     )
 }
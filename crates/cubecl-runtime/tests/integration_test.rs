@@ -34,6 +34,42 @@ fn empty_allocates_memory() {
     assert_eq!(empty_resource.len(), 4);
 }
 
+#[test]
+fn device_name_is_reported() {
+    let client = client(&DummyDevice);
+
+    assert_eq!(client.properties().device_name(), "dummy");
+}
+
+#[test]
+fn profiling_session_records_every_scope() {
+    let client = client(&DummyDevice);
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    let scopes = cubecl_common::future::block_on(async {
+        let mut session = client.profiling_session().await;
+
+        session
+            .scope("addition", || {
+                client.execute(
+                    Arc::new(DummyElementwiseAddition),
+                    CubeCount::Static(1, 1, 1),
+                    vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+                )
+            })
+            .await;
+        session.scope("empty", || {}).await;
+
+        session.finish()
+    });
+
+    let names: Vec<_> = scopes.iter().map(|scope| scope.name.as_str()).collect();
+    assert_eq!(names, ["addition", "empty"]);
+    assert!(scopes.iter().all(|scope| scope.duration.is_some()));
+}
+
 #[test]
 fn execute_elementwise_addition() {
     let client = client(&DummyDevice);
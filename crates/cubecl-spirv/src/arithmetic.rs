@@ -4,7 +4,7 @@ use crate::{
     variable::ConstVal,
 };
 use cubecl_core::ir::{self as core, Arithmetic};
-use rspirv::spirv::{Capability, Decoration};
+use rspirv::spirv::{Capability, Decoration, Word};
 
 impl<T: SpirvTarget> SpirvCompiler<T> {
     pub fn compile_arithmetic(
@@ -421,6 +421,91 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                     _ => unreachable!(),
                 },
             ),
+            Arithmetic::SaturatingAdd(op) => {
+                self.compile_binary_op(op, out, uniform, |b, out_ty, ty, lhs, rhs, out| {
+                    let sum = b.i_add(ty, None, lhs, rhs).unwrap();
+                    let bool_ty = out_ty.same_vectorization(Elem::Bool).id(b);
+                    match out_ty.elem() {
+                        Elem::Int(width, true) => {
+                            // Overflow happened if the sum has a different sign than both operands
+                            let lhs_sign = b.bitwise_xor(ty, None, lhs, sum).unwrap();
+                            let rhs_sign = b.bitwise_xor(ty, None, rhs, sum).unwrap();
+                            let signs = b.bitwise_and(ty, None, lhs_sign, rhs_sign).unwrap();
+                            let zero = out_ty.constant(b, int_const(width, 0));
+                            let overflow = b.s_less_than(bool_ty, None, signs, zero).unwrap();
+                            let bound = signed_bound(b, &out_ty, bool_ty, width, lhs, zero);
+                            b.select(ty, Some(out), overflow, bound, sum).unwrap();
+                        }
+                        Elem::Int(width, false) => {
+                            let overflow = b.u_less_than(bool_ty, None, sum, lhs).unwrap();
+                            let max = out_ty.constant(b, int_const(width, u64::MAX));
+                            b.select(ty, Some(out), overflow, max, sum).unwrap();
+                        }
+                        _ => unreachable!(),
+                    };
+                });
+            }
+            Arithmetic::SaturatingSub(op) => {
+                self.compile_binary_op(op, out, uniform, |b, out_ty, ty, lhs, rhs, out| {
+                    let diff = b.i_sub(ty, None, lhs, rhs).unwrap();
+                    let bool_ty = out_ty.same_vectorization(Elem::Bool).id(b);
+                    match out_ty.elem() {
+                        Elem::Int(width, true) => {
+                            // Overflow happened if the operands have different signs and the
+                            // difference doesn't have the sign of lhs
+                            let operand_sign = b.bitwise_xor(ty, None, lhs, rhs).unwrap();
+                            let diff_sign = b.bitwise_xor(ty, None, lhs, diff).unwrap();
+                            let signs = b.bitwise_and(ty, None, operand_sign, diff_sign).unwrap();
+                            let zero = out_ty.constant(b, int_const(width, 0));
+                            let overflow = b.s_less_than(bool_ty, None, signs, zero).unwrap();
+                            let bound = signed_bound(b, &out_ty, bool_ty, width, lhs, zero);
+                            b.select(ty, Some(out), overflow, bound, diff).unwrap();
+                        }
+                        Elem::Int(width, false) => {
+                            let overflow = b.u_less_than(bool_ty, None, lhs, rhs).unwrap();
+                            let zero = out_ty.constant(b, int_const(width, 0));
+                            b.select(ty, Some(out), overflow, zero, diff).unwrap();
+                        }
+                        _ => unreachable!(),
+                    };
+                });
+            }
+            Arithmetic::WrappingAdd(op) => {
+                self.compile_binary_op(op, out, uniform, |b, _, ty, lhs, rhs, out| {
+                    b.i_add(ty, Some(out), lhs, rhs).unwrap();
+                });
+            }
+            Arithmetic::WrappingMul(op) => {
+                self.compile_binary_op(op, out, uniform, |b, _, ty, lhs, rhs, out| {
+                    b.i_mul(ty, Some(out), lhs, rhs).unwrap();
+                });
+            }
         }
     }
 }
+
+/// Creates an integer constant of `width` bits, truncating `value` to fit.
+fn int_const(width: u32, value: u64) -> ConstVal {
+    let value = value & (u64::MAX >> (64 - width));
+    match width {
+        64 => ConstVal::Bit64(value),
+        _ => ConstVal::Bit32(value as u32),
+    }
+}
+
+/// Selects the bound a signed overflow saturates to: the minimum when `lhs` is negative, the
+/// maximum otherwise.
+fn signed_bound<T: SpirvTarget>(
+    b: &mut SpirvCompiler<T>,
+    ty: &Item,
+    bool_ty: Word,
+    width: u32,
+    lhs: Word,
+    zero: Word,
+) -> Word {
+    let min = ty.constant(b, int_const(width, (i64::MIN >> (64 - width)) as u64));
+    let max = ty.constant(b, int_const(width, (i64::MAX >> (64 - width)) as u64));
+    let negative = b.s_less_than(bool_ty, None, lhs, zero).unwrap();
+    let ty_id = ty.id(b);
+    b.select(ty_id, None, negative, min, max).unwrap()
+}
@@ -306,6 +306,8 @@ impl Elem {
 impl<T: SpirvTarget> SpirvCompiler<T> {
     pub fn compile_item(&mut self, item: core::Item) -> Item {
         let elem = match item.elem {
+            core::Elem::Float(FloatKind::E4M3) => panic!("E4M3 not supported in SPIR-V"),
+            core::Elem::Float(FloatKind::E5M2) => panic!("E5M2 not supported in SPIR-V"),
             core::Elem::Float(core::FloatKind::BF16) => panic!("BFloat16 not supported in SPIR-V"),
             core::Elem::Float(FloatKind::F16) => {
                 self.capabilities.insert(Capability::Float16);
@@ -334,6 +336,8 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
             core::Elem::AtomicFloat(core::FloatKind::TF32) => {
                 panic!("TF32 not supported in SPIR-V")
             }
+            core::Elem::AtomicFloat(FloatKind::E4M3) => panic!("E4M3 not supported in SPIR-V"),
+            core::Elem::AtomicFloat(FloatKind::E5M2) => panic!("E5M2 not supported in SPIR-V"),
             core::Elem::Int(IntKind::I8) => {
                 self.capabilities.insert(Capability::Int8);
                 Elem::Int(8, true)
@@ -158,6 +158,9 @@ impl From<ConstantScalarValue> for ConstVal {
             ConstantScalarValue::Float(_, FloatKind::BF16) => {
                 panic!("bf16 not supported in SPIR-V")
             }
+            ConstantScalarValue::Float(_, kind @ (FloatKind::E4M3 | FloatKind::E5M2)) => {
+                panic!("{kind:?} not supported in SPIR-V")
+            }
             ConstantScalarValue::Float(val, _) => ConstVal::from_float(val, width),
             ConstantScalarValue::UInt(val, _) => ConstVal::from_uint(val, width),
             ConstantScalarValue::Bool(val) => ConstVal::from_bool(val),
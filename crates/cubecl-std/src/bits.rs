@@ -0,0 +1,74 @@
+//! Booleans packed 32 per `u32` word.
+//!
+//! Boolean `i` is bit `i % 32` of word `i / 32`, counting from the least significant bit, so the
+//! first boolean of a word is its lowest bit. Words are stored as plain `u32` in the native byte
+//! order of the device, which is little endian on every supported backend. Unused high bits of the
+//! last word are zero.
+
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// Number of booleans stored in a single word.
+pub const BITS_PER_WORD: u32 = 32;
+
+/// Number of words needed to pack `len` booleans.
+pub fn packed_len(len: usize) -> usize {
+    len.div_ceil(BITS_PER_WORD as usize)
+}
+
+/// Packs `values` into words.
+///
+/// Boolean `i` is bit `i % 32` of word `i / 32`, counting from the least significant bit. Unused
+/// high bits of the last word are zero.
+pub fn pack_bools(values: &[bool]) -> Vec<u32> {
+    let mut words = vec![0u32; packed_len(values.len())];
+
+    for (i, value) in values.iter().enumerate() {
+        if *value {
+            words[i / BITS_PER_WORD as usize] |= 1 << (i % BITS_PER_WORD as usize);
+        }
+    }
+
+    words
+}
+
+/// Unpacks the first `len` booleans of `words`.
+///
+/// # Panics
+///
+/// If `words` holds less than `len` booleans.
+pub fn unpack_bools(words: &[u32], len: usize) -> Vec<bool> {
+    assert!(
+        words.len() >= packed_len(len),
+        "Not enough words to unpack {len} booleans"
+    );
+
+    (0..len)
+        .map(|i| {
+            let word = words[i / BITS_PER_WORD as usize];
+            (word >> (i % BITS_PER_WORD as usize)) & 1 == 1
+        })
+        .collect()
+}
+
+/// Reads boolean `index` from packed `words`.
+#[cube]
+pub fn read_packed_bool<L: List<u32>>(words: &L, index: u32) -> bool {
+    let word = words.read(index / BITS_PER_WORD);
+    (word >> (index % BITS_PER_WORD)) & 1 == 1
+}
+
+/// Writes boolean `index` into packed `words`.
+///
+/// The word is read, updated and written back without synchronization, so a word must only be
+/// written by a single unit at a time: concurrent writes to booleans of the same word race even
+/// when their indices differ.
+#[cube]
+pub fn write_packed_bool<L: ListMut<u32>>(words: &mut L, index: u32, value: bool) {
+    let word_index = index / BITS_PER_WORD;
+    let bit = 1u32 << (index % BITS_PER_WORD);
+    let word = words.read(word_index);
+    let updated = select(value, word | bit, word & (u32::MAX ^ bit));
+
+    words.write(word_index, updated);
+}
@@ -6,6 +6,9 @@ pub use quantization::*;
 mod option;
 pub use option::*;
 
+mod bits;
+pub use bits::*;
+
 pub mod tensor;
 
 use cubecl::prelude::*;
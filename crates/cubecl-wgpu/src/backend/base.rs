@@ -151,6 +151,20 @@ pub fn register_features(
     }
 }
 
+/// Whether plane operations can be emulated with shared memory on the adapter, which only the WGSL
+/// compiler supports.
+#[cfg(not(feature = "spirv"))]
+pub fn supports_plane_emulation(_adapter: &Adapter) -> bool {
+    true
+}
+
+/// Whether plane operations can be emulated with shared memory on the adapter, which only the WGSL
+/// compiler supports.
+#[cfg(feature = "spirv")]
+pub fn supports_plane_emulation(adapter: &Adapter) -> bool {
+    !is_vulkan(adapter)
+}
+
 #[cfg(feature = "spirv")]
 fn is_vulkan(adapter: &Adapter) -> bool {
     unsafe { adapter.as_hal::<wgpu::hal::api::Vulkan, _, _>(|adapter| adapter.is_some()) }
@@ -44,7 +44,11 @@ pub fn register_types(props: &mut DeviceProperties<Feature>) {
     use cubecl_core::ir::{Elem, FloatKind, IntKind};
 
     let supported_types = [
+        Elem::UInt(UIntKind::U8),
+        Elem::UInt(UIntKind::U16),
         Elem::UInt(UIntKind::U32),
+        Elem::Int(IntKind::I8),
+        Elem::Int(IntKind::I16),
         Elem::Int(IntKind::I32),
         Elem::AtomicInt(IntKind::I32),
         Elem::AtomicUInt(UIntKind::U32),
@@ -63,6 +63,10 @@ pub enum Elem {
     AtomicI32,
     U32,
     AtomicU32,
+    I8,
+    U8,
+    I16,
+    U16,
     Bool,
 }
 
@@ -182,6 +186,20 @@ impl Variable {
         *self.item().elem()
     }
 
+    /// Whether the variable is an array of narrow integers in memory, packed in `atomic<u32>`
+    /// words. Writes to a word are atomic since neighbouring items may be written concurrently.
+    pub fn is_packed(&self) -> bool {
+        let is_memory = match self {
+            Variable::GlobalInputArray(_, _)
+            | Variable::GlobalOutputArray(_, _)
+            | Variable::SharedMemory(_, _, _)
+            | Variable::Slice { .. } => true,
+            Variable::Named { is_array, .. } => *is_array,
+            _ => false,
+        };
+        is_memory && self.elem().packed_bits().is_some()
+    }
+
     pub fn fmt_cast_to(&self, item: Item) -> String {
         if self.item() != item {
             format!("{item}({self})")
@@ -228,6 +246,10 @@ impl Elem {
             Self::AtomicI32 => core::mem::size_of::<i32>(),
             Self::U32 => core::mem::size_of::<u32>(),
             Self::AtomicU32 => core::mem::size_of::<u32>(),
+            Self::I8 => core::mem::size_of::<i8>(),
+            Self::U8 => core::mem::size_of::<u8>(),
+            Self::I16 => core::mem::size_of::<i16>(),
+            Self::U16 => core::mem::size_of::<u16>(),
             Self::Bool => core::mem::size_of::<bool>(),
         }
     }
@@ -235,6 +257,20 @@ impl Elem {
     pub fn is_atomic(&self) -> bool {
         matches!(self, Self::AtomicI32 | Self::AtomicU32 | Self::AtomicF32)
     }
+
+    /// The width of the integers WGSL doesn't have. They are widened to 32 bits in registers and
+    /// packed in `u32` words in memory.
+    pub fn packed_bits(&self) -> Option<u32> {
+        match self {
+            Self::I8 | Self::U8 => Some(8),
+            Self::I16 | Self::U16 => Some(16),
+            _ => None,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self, Self::I32 | Self::AtomicI32 | Self::I8 | Self::I16)
+    }
 }
 
 impl Display for Elem {
@@ -246,6 +282,10 @@ impl Display for Elem {
             Self::AtomicI32 => f.write_str("atomic<i32>"),
             Self::U32 => f.write_str("u32"),
             Self::AtomicU32 => f.write_str("atomic<u32>"),
+            Self::I8 => f.write_str("i32"),
+            Self::U8 => f.write_str("u32"),
+            Self::I16 => f.write_str("i32"),
+            Self::U16 => f.write_str("u32"),
             Self::Bool => f.write_str("bool"),
         }
     }
@@ -263,6 +303,19 @@ impl Display for Item {
 }
 
 fn format_number(num: f64) -> String {
+    // WGSL has no literal for non-finite values, so they are built from their bit patterns.
+    if num.is_nan() {
+        return "bitcast<f32>(0x7fc00000u)".to_string();
+    }
+    if num.is_infinite() {
+        let bits = if num.is_sign_negative() {
+            "0xff800000u"
+        } else {
+            "0x7f800000u"
+        };
+        return format!("bitcast<f32>({bits})");
+    }
+
     let formatted = format!("{:.34}", num);
     let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
     trimmed.to_string() + "f"
@@ -282,19 +335,34 @@ impl Display for Variable {
             Variable::GlobalOutputArray(number, _) => {
                 write!(f, "output_{number}_global")
             }
-            Variable::GlobalScalar(number, _, elem) => {
-                write!(f, "scalars_{elem}[{number}]")
-            }
+            Variable::GlobalScalar(number, elem, cube_elem) => match elem.packed_bits() {
+                // Packed like the arrays, see `Variable::is_packed`.
+                Some(bits) => {
+                    let per_word = 32 / bits;
+                    let word = format!("atomicLoad(&scalars_{cube_elem}[{}])", number / per_word);
+                    let word = match elem.is_signed() {
+                        true => format!("bitcast<i32>({word})"),
+                        false => word,
+                    };
+                    let offset = number % per_word * bits;
+                    write!(f, "extractBits({word}, {offset}u, {bits}u)")
+                }
+                None => write!(f, "scalars_{cube_elem}[{number}]"),
+            },
             // We do the conversion in Rust and then render the number to avoid overflow or other
             // precision related problems.
             Variable::ConstantScalar(number, _elem) => match number {
                 ConstantScalarValue::Int(val, kind) => match kind {
-                    IntKind::I32 => write!(f, "{}", *val as i32),
+                    IntKind::I8 | IntKind::I16 | IntKind::I32 => write!(f, "{}", *val as i32),
                     _ => unimplemented!("{:?} not supported in WGSL", kind),
                 },
                 ConstantScalarValue::Float(val, kind) => match kind {
-                    FloatKind::F16 | FloatKind::BF16 | FloatKind::TF32 => {
-                        todo!("Unsupported")
+                    FloatKind::E4M3
+                    | FloatKind::E5M2
+                    | FloatKind::F16
+                    | FloatKind::BF16
+                    | FloatKind::TF32 => {
+                        unimplemented!("{:?} not supported in WGSL", kind)
                     }
                     FloatKind::F32 | FloatKind::Flex32 | FloatKind::F64 => {
                         f.write_str(&format_number(*val))
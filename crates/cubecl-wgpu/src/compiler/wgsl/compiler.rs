@@ -7,7 +7,7 @@ use cubecl_common::ExecutionMode;
 use cubecl_core::{
     Metadata, WgpuCompilationOptions, compute,
     ir::{self as cube, Scope},
-    prelude::{expand_checked_index_assign, expand_erf},
+    prelude::{CubeDim, expand_checked_index_assign, expand_erf, expand_plane_emulated},
 };
 
 /// Wgsl Compiler.
@@ -31,8 +31,8 @@ pub struct WgslCompiler {
     shared_memories: Vec<SharedMemory>,
     const_arrays: Vec<ConstantArray>,
     local_arrays: Vec<LocalArray>,
-    #[allow(dead_code)]
     compilation_options: WgpuCompilationOptions,
+    cube_dim: CubeDim,
     strategy: ExecutionMode,
     subgroup_instructions_used: bool,
 }
@@ -73,6 +73,7 @@ impl WgslCompiler {
         mode: ExecutionMode,
     ) -> wgsl::ComputeShader {
         self.strategy = mode;
+        self.cube_dim = value.cube_dim;
 
         self.num_inputs = value.inputs.len();
         self.num_outputs = value.outputs.len();
@@ -151,6 +152,8 @@ impl WgslCompiler {
     fn compile_elem(value: cube::Elem) -> wgsl::Elem {
         match value {
             cube::Elem::Float(f) => match f {
+                cube::FloatKind::E4M3 => panic!("e4m3 is not a valid WgpuElement"),
+                cube::FloatKind::E5M2 => panic!("e5m2 is not a valid WgpuElement"),
                 cube::FloatKind::F16 => panic!("f16 is not yet supported"),
                 cube::FloatKind::BF16 => panic!("bf16 is not a valid WgpuElement"),
                 cube::FloatKind::TF32 => panic!("tf32 is not a valid WgpuElement"),
@@ -159,10 +162,14 @@ impl WgslCompiler {
                 cube::FloatKind::F64 => panic!("f64 is not a valid WgpuElement"),
             },
             cube::Elem::Int(i) => match i {
+                cube::IntKind::I8 => wgsl::Elem::I8,
+                cube::IntKind::I16 => wgsl::Elem::I16,
                 cube::IntKind::I32 => wgsl::Elem::I32,
                 kind => panic!("{kind:?} is not a valid WgpuElement"),
             },
             cube::Elem::UInt(kind) => match kind {
+                cube::UIntKind::U8 => wgsl::Elem::U8,
+                cube::UIntKind::U16 => wgsl::Elem::U16,
                 cube::UIntKind::U32 => wgsl::Elem::U32,
                 kind => panic!("{kind:?} is not a valid WgpuElement"),
             },
@@ -311,6 +318,18 @@ impl WgslCompiler {
                     self.num_workgroup_no_axis = true;
                     wgsl::Variable::NumWorkgroups
                 }
+                // Emulated planes are the rows of the cube along x.
+                cube::Builtin::PlaneDim if self.compilation_options.emulate_planes => {
+                    let value = cube::ConstantScalarValue::UInt(
+                        self.cube_dim.x as u64,
+                        cube::UIntKind::U32,
+                    );
+                    wgsl::Variable::ConstantScalar(value, Self::compile_elem(value.elem()))
+                }
+                cube::Builtin::UnitPosPlane if self.compilation_options.emulate_planes => {
+                    self.local_invocation_id = true;
+                    wgsl::Variable::LocalInvocationIdX
+                }
                 cube::Builtin::PlaneDim => {
                     self.subgroup_size = true;
                     wgsl::Variable::SubgroupSize
@@ -378,6 +397,10 @@ impl WgslCompiler {
         out: Option<cube::Variable>,
         scope: &mut cube::Scope,
     ) {
+        if let Some(out) = out.filter(|out| needs_narrowing(&operation, out)) {
+            return self.compile_narrowed(instructions, operation, out, scope);
+        }
+
         match operation {
             cube::Operation::Copy(variable) => instructions.push(wgsl::Instruction::Assign {
                 input: self.compile_variable(variable),
@@ -395,6 +418,11 @@ impl WgslCompiler {
             cube::Operation::Synchronization(val) => {
                 self.compile_synchronization(instructions, val)
             }
+            cube::Operation::Plane(op) if self.compilation_options.emulate_planes => {
+                let mut scope = scope.child();
+                expand_plane_emulated(&mut scope, op, out.unwrap(), self.cube_dim);
+                instructions.extend(self.compile_scope(&mut scope));
+            }
             cube::Operation::Plane(op) => self.compile_subgroup(instructions, op, out),
             cube::Operation::CoopMma(_) => {
                 panic!("Cooperative matrix-multiply and accumulate isn't supported on wgpu.")
@@ -413,6 +441,50 @@ impl WgslCompiler {
         }
     }
 
+    /// Narrow integers are computed in a 32-bit register, which is then wrapped to their width.
+    fn compile_narrowed(
+        &mut self,
+        instructions: &mut Vec<wgsl::Instruction>,
+        operation: cube::Operation,
+        out: cube::Variable,
+        scope: &mut cube::Scope,
+    ) {
+        let elem = match out.item.elem {
+            cube::Elem::Int(_) => cube::Elem::Int(cube::IntKind::I32),
+            _ => cube::Elem::UInt(cube::UIntKind::U32),
+        };
+        let wide = *scope.create_local_restricted(cube::Item { elem, ..out.item });
+
+        instructions.push(wgsl::Instruction::DeclareVariable {
+            var: self.compile_variable(wide),
+        });
+        self.compile_operation(instructions, operation, Some(wide), scope);
+        instructions.push(wgsl::Instruction::Narrow {
+            input: self.compile_variable(wide),
+            out: self.compile_variable(out),
+        });
+    }
+
+    /// Wraps a value assigned to a narrow integer to its width, unless it's of the same type.
+    fn narrow_value(
+        &mut self,
+        value: cube::Variable,
+        elem: cube::Elem,
+        instructions: &mut Vec<wgsl::Instruction>,
+        scope: &mut cube::Scope,
+    ) -> cube::Variable {
+        if !is_narrow(elem) || value.item.elem == elem {
+            return value;
+        }
+
+        let narrow = *scope.create_local(cube::Item { elem, ..value.item });
+        instructions.push(wgsl::Instruction::Narrow {
+            input: self.compile_variable(value),
+            out: self.compile_variable(narrow),
+        });
+        narrow
+    }
+
     fn compile_subgroup(
         &mut self,
         instructions: &mut Vec<wgsl::Instruction>,
@@ -752,6 +824,31 @@ impl WgslCompiler {
                 rhs: self.compile_variable(op.rhs),
                 out: self.compile_variable(out),
             }),
+            cube::Arithmetic::SaturatingAdd(op) => {
+                instructions.push(wgsl::Instruction::SaturatingAdd {
+                    lhs: self.compile_variable(op.lhs),
+                    rhs: self.compile_variable(op.rhs),
+                    out: self.compile_variable(out),
+                })
+            }
+            cube::Arithmetic::SaturatingSub(op) => {
+                instructions.push(wgsl::Instruction::SaturatingSub {
+                    lhs: self.compile_variable(op.lhs),
+                    rhs: self.compile_variable(op.rhs),
+                    out: self.compile_variable(out),
+                })
+            }
+            // Integer arithmetic always wraps in WGSL.
+            cube::Arithmetic::WrappingAdd(op) => instructions.push(wgsl::Instruction::Add {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::WrappingMul(op) => instructions.push(wgsl::Instruction::Mul {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(out),
+            }),
         }
     }
 
@@ -908,16 +1005,18 @@ impl WgslCompiler {
                         return;
                     }
                 };
+                let rhs = self.narrow_value(op.rhs, out.item.elem, instructions, scope);
                 instructions.push(wgsl::Instruction::IndexAssign {
                     lhs: self.compile_variable(op.lhs),
-                    rhs: self.compile_variable(op.rhs),
+                    rhs: self.compile_variable(rhs),
                     out: self.compile_variable(out),
                 })
             }
             cube::Operator::UncheckedIndexAssign(op) => {
+                let rhs = self.narrow_value(op.rhs, out.item.elem, instructions, scope);
                 instructions.push(wgsl::Instruction::IndexAssign {
                     lhs: self.compile_variable(op.lhs),
-                    rhs: self.compile_variable(op.rhs),
+                    rhs: self.compile_variable(rhs),
                     out: self.compile_variable(out),
                 })
             }
@@ -936,6 +1035,15 @@ impl WgslCompiler {
                 out: self.compile_variable(out),
             }),
             cube::Operator::Slice(op) => {
+                // Only narrow integers in memory are packed, see `Variable::is_packed`.
+                if is_narrow(op.input.item.elem)
+                    && matches!(op.input.kind, cube::VariableKind::LocalArray { .. })
+                {
+                    panic!(
+                        "Slices of local arrays of {} aren't supported in WGSL",
+                        op.input.item.elem
+                    );
+                }
                 if matches!(self.strategy, ExecutionMode::Checked) && op.input.has_length() {
                     let input = op.input;
                     let input_len = *scope
@@ -1088,6 +1196,44 @@ impl WgslCompiler {
     }
 }
 
+fn is_narrow(elem: cube::Elem) -> bool {
+    matches!(
+        elem,
+        cube::Elem::Int(cube::IntKind::I8 | cube::IntKind::I16)
+            | cube::Elem::UInt(cube::UIntKind::U8 | cube::UIntKind::U16)
+    )
+}
+
+/// Whether the operation can leave a narrow integer output out of its range, which copies and
+/// indexing of the same type can't.
+fn needs_narrowing(operation: &cube::Operation, out: &cube::Variable) -> bool {
+    let is_register = matches!(
+        out.kind,
+        cube::VariableKind::LocalMut { .. }
+            | cube::VariableKind::LocalConst { .. }
+            | cube::VariableKind::Versioned { .. }
+    );
+    if !is_register || !is_narrow(out.item.elem) {
+        return false;
+    }
+
+    match operation {
+        cube::Operation::Copy(input) => input.item.elem != out.item.elem,
+        cube::Operation::Operator(
+            cube::Operator::Index(op) | cube::Operator::UncheckedIndex(op),
+        ) => op.lhs.item.elem != out.item.elem,
+        // Assigning an index of a register keeps its other components.
+        cube::Operation::Operator(
+            cube::Operator::IndexAssign(_) | cube::Operator::UncheckedIndexAssign(_),
+        ) => false,
+        cube::Operation::Arithmetic(_)
+        | cube::Operation::Bitwise(_)
+        | cube::Operation::Operator(_)
+        | cube::Operation::Plane(_) => true,
+        _ => false,
+    }
+}
+
 fn register_extensions(instructions: &[wgsl::Instruction]) -> Vec<wgsl::Extension> {
     let mut extensions = Vec::new();
 
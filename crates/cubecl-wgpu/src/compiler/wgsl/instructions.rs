@@ -87,6 +87,11 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    // Wraps a narrow integer computed in a 32-bit register to its width.
+    Narrow {
+        input: Variable,
+        out: Variable,
+    },
     Modulo {
         lhs: Variable,
         rhs: Variable,
@@ -284,6 +289,16 @@ pub enum Instruction {
         rhs: Variable,
         out: Variable,
     },
+    SaturatingAdd {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    SaturatingSub {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
     Slice {
         input: Variable,
         start: Variable,
@@ -526,6 +541,14 @@ impl Display for Instruction {
                 }
                 _ => index(f, lhs, rhs, out, None, Some(len)),
             },
+            Instruction::Copy {
+                input,
+                in_index,
+                out,
+                out_index,
+            } if input.is_packed() || out.is_packed() => {
+                copy_packed(f, input, &in_index.to_string(), out, &out_index.to_string())
+            }
             Instruction::Copy {
                 input,
                 in_index,
@@ -546,6 +569,20 @@ impl Display for Instruction {
                 };
                 writeln!(f, "{lhs} = {rhs};")
             }
+            Instruction::CopyBulk {
+                input,
+                in_index,
+                out,
+                out_index,
+                len,
+            } if input.is_packed() || out.is_packed() => {
+                for i in 0..*len {
+                    let in_index = format!("{in_index} + {i}");
+                    let out_index = format!("{out_index} + {i}");
+                    copy_packed(f, input, &in_index, out, &out_index)?;
+                }
+                Ok(())
+            }
             Instruction::CopyBulk {
                 input,
                 in_index,
@@ -588,6 +625,59 @@ impl Display for Instruction {
                 let floor = f_type.fmt_cast_to(ty, format!("floor({lhs} / {rhs})"));
                 writeln!(f, "{out} = {lhs} - {rhs} * {floor};")
             }
+            // WGSL integers wrap on overflow, so the saturated bound is selected when the wrapped
+            // result shows an overflow.
+            Instruction::SaturatingAdd { lhs, rhs, out } => {
+                let item = out.item();
+                let lhs = lhs.fmt_cast_to(item);
+                let rhs = rhs.fmt_cast_to(item);
+                let sum = format!("({lhs} + {rhs})");
+                let out = out.fmt_left();
+
+                match item.elem() {
+                    Elem::I32 => writeln!(
+                        f,
+                        "{out} = select({sum}, select({item}(2147483647), {item}(-2147483648), {lhs} < {item}(0)), (({lhs} ^ {sum}) & ({rhs} ^ {sum})) < {item}(0));"
+                    ),
+                    Elem::U32 => {
+                        writeln!(
+                            f,
+                            "{out} = select({sum}, {item}(4294967295u), {sum} < {lhs});"
+                        )
+                    }
+                    // Narrow integers don't overflow their 32-bit registers.
+                    elem if elem.packed_bits().is_some() => {
+                        let (min, max) = narrow_bounds(elem, item);
+                        writeln!(f, "{out} = clamp({sum}, {min}, {max});")
+                    }
+                    elem => panic!("Saturating add is only supported on integers, got {elem}"),
+                }
+            }
+            Instruction::SaturatingSub { lhs, rhs, out } => {
+                let item = out.item();
+                let lhs = lhs.fmt_cast_to(item);
+                let rhs = rhs.fmt_cast_to(item);
+                let diff = format!("({lhs} - {rhs})");
+                let out = out.fmt_left();
+
+                match item.elem() {
+                    Elem::I32 => writeln!(
+                        f,
+                        "{out} = select({diff}, select({item}(2147483647), {item}(-2147483648), {lhs} < {item}(0)), (({lhs} ^ {rhs}) & ({lhs} ^ {diff})) < {item}(0));"
+                    ),
+                    Elem::U32 => writeln!(f, "{out} = select({diff}, {item}(0u), {lhs} < {rhs});"),
+                    // Only unsigned narrow integers wrap in their 32-bit registers.
+                    elem if elem.packed_bits().is_some() => {
+                        let (min, max) = narrow_bounds(elem, item);
+                        let diff = match elem.is_signed() {
+                            true => diff,
+                            false => format!("select({diff}, {item}(0u), {lhs} < {rhs})"),
+                        };
+                        writeln!(f, "{out} = clamp({diff}, {min}, {max});")
+                    }
+                    elem => panic!("Saturating sub is only supported on integers, got {elem}"),
+                }
+            }
             Instruction::Sub { lhs, rhs, out } => {
                 if out.is_atomic() {
                     assert_eq!(lhs, out, "Can't use regular sub on atomic");
@@ -696,6 +786,15 @@ impl Display for Instruction {
                     writeln!(f, "{out} = {input};")
                 }
             }
+            Instruction::Narrow { input, out } => {
+                let bits = out
+                    .elem()
+                    .packed_bits()
+                    .expect("Only narrow integers are wrapped");
+                let input = input.fmt_cast_to(out.item());
+                let out = out.fmt_left();
+                writeln!(f, "{out} = extractBits({input}, 0u, {bits}u);")
+            }
             Instruction::Metadata { info_offset, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = info[{info_offset}];")
@@ -1025,6 +1124,13 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
                     }
 
                     writeln!(f, ");")
+                } else if slice.is_packed() {
+                    let value = packed_read(
+                        &format!("(*{slice}_ptr)"),
+                        slice.item(),
+                        &format!("{index} + {slice}_offset"),
+                    );
+                    writeln!(f, "{out} = select({fallback}, {value}, {cond});")
                 } else {
                     writeln!(
                         f,
@@ -1168,6 +1274,16 @@ fn index(
 
     let (mut value, index) = if is_scalar {
         (format!("{lhs}"), None)
+    } else if lhs.is_packed() {
+        let index = match offset {
+            Some(offset) => format!("{rhs}+{offset}"),
+            None => format!("{rhs}"),
+        };
+
+        (
+            packed_read(&lhs.to_string(), lhs.item(), &index),
+            Some(format!("{rhs}")),
+        )
     } else {
         let value = if let Some(offset) = offset {
             format!("{lhs}[{rhs}+{offset}]")
@@ -1212,6 +1328,17 @@ fn index_assign(
     out: &Variable,
     offset: Option<Variable>,
 ) -> core::fmt::Result {
+    if out.is_packed() {
+        let item = out.item();
+        let elem = Item::Scalar(*item.elem());
+        let components = (0..item.vectorization_factor())
+            .map(|i| rhs.index(i).fmt_cast(elem))
+            .collect::<Vec<_>>();
+        let index = IndexOffset::new(lhs, &offset, 0).to_string();
+
+        return packed_write(f, &out.to_string(), item, &index, &components);
+    }
+
     match lhs.item() {
         Item::Vec4(elem) => {
             let item = Item::Scalar(elem);
@@ -1293,3 +1420,228 @@ fn index_assign(
         }
     }
 }
+
+/// The bounds of a narrow integer, as values of its register `item`.
+fn narrow_bounds(elem: Elem, item: Item) -> (String, String) {
+    let bits = elem.packed_bits().unwrap();
+
+    match elem.is_signed() {
+        true => {
+            let max = (1i32 << (bits - 1)) - 1;
+            (format!("{item}({})", -max - 1), format!("{item}({max})"))
+        }
+        false => (
+            format!("{item}(0u)"),
+            format!("{item}({}u)", (1u32 << bits) - 1),
+        ),
+    }
+}
+
+/// Reads the item at `index` of an array of narrow integers packed in words, widened to its 32-bit
+/// register item.
+fn packed_read(array: &str, item: Item, index: &str) -> String {
+    let elem = *item.elem();
+    let bits = elem.packed_bits().unwrap();
+    let per_word = 32 / bits;
+    let vectorization = item.vectorization_factor() as u32;
+    let index = format!("u32({index})");
+
+    // Lines filling whole words are loaded a word at a time, with the components split by a vector
+    // shift.
+    if vectorization * bits % 32 == 0 {
+        let num_words = vectorization * bits / 32;
+        let word_item = match per_word {
+            4 => Item::Vec4(Elem::U32),
+            _ => Item::Vec2(Elem::U32),
+        };
+        let shifts = |shift: &dyn Fn(u32) -> u32| {
+            let shifts = (0..per_word)
+                .map(|i| format!("{}u", shift(i)))
+                .collect::<Vec<_>>();
+            format!("{word_item}({})", shifts.join(", "))
+        };
+
+        let words = (0..num_words)
+            .map(|k| {
+                let word_index = match num_words {
+                    1 => index.clone(),
+                    _ => format!("{index} * {num_words}u + {k}u"),
+                };
+                let word = format!("{word_item}(atomicLoad(&{array}[{word_index}]))");
+
+                // The sign is extended by shifting the component to the top of an `i32`.
+                match elem.is_signed() {
+                    true => {
+                        let left = shifts(&|i| 32 - bits - i * bits);
+                        let right = shifts(&|_| 32 - bits);
+                        let signed_item = match per_word {
+                            4 => Item::Vec4(Elem::I32),
+                            _ => Item::Vec2(Elem::I32),
+                        };
+                        format!("(bitcast<{signed_item}>({word} << {left}) >> {right})")
+                    }
+                    false => {
+                        let right = shifts(&|i| i * bits);
+                        let mask = shifts(&|_| (1 << bits) - 1);
+                        format!("(({word} >> {right}) & {mask})")
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        return match words.len() {
+            1 => words[0].clone(),
+            _ => format!("{item}({})", words.join(", ")),
+        };
+    }
+
+    let components = (0..vectorization)
+        .map(|i| {
+            let index = match vectorization {
+                1 => index.clone(),
+                _ => format!("({index} * {vectorization}u + {i}u)"),
+            };
+            let word = format!("atomicLoad(&{array}[{index} / {per_word}u])");
+            let word = match elem.is_signed() {
+                true => format!("bitcast<i32>({word})"),
+                false => word,
+            };
+            format!("extractBits({word}, {index} % {per_word}u * {bits}u, {bits}u)")
+        })
+        .collect::<Vec<_>>();
+
+    match components.len() {
+        1 => components[0].clone(),
+        _ => format!("{item}({})", components.join(", ")),
+    }
+}
+
+/// Writes the `components` of the item at `index` of an array of narrow integers packed in words.
+///
+/// Lines filling whole words are stored a word at a time. Otherwise the bits of each component are
+/// cleared and set atomically, so concurrent writes to the other items of the word aren't lost.
+fn packed_write(
+    f: &mut std::fmt::Formatter<'_>,
+    array: &str,
+    item: Item,
+    index: &str,
+    components: &[String],
+) -> core::fmt::Result {
+    let bits = item.elem().packed_bits().unwrap();
+    let mask = (1u32 << bits) - 1;
+    let per_word = 32 / bits;
+    let vectorization = item.vectorization_factor() as u32;
+    let index = format!("u32({index})");
+
+    if vectorization * bits % 32 == 0 {
+        let num_words = vectorization * bits / 32;
+
+        for (k, components) in components.chunks(per_word as usize).enumerate() {
+            let word_index = match num_words {
+                1 => index.clone(),
+                _ => format!("{index} * {num_words}u + {k}u"),
+            };
+            let word = components
+                .iter()
+                .enumerate()
+                .map(|(i, value)| format!("((u32({value}) & {mask}u) << {}u)", i as u32 * bits))
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "atomicStore(&{array}[{word_index}], {});",
+                word.join(" | ")
+            )?;
+        }
+
+        return Ok(());
+    }
+
+    for (i, value) in components.iter().enumerate() {
+        let index = match vectorization {
+            1 => index.clone(),
+            _ => format!("({index} * {vectorization}u + {i}u)"),
+        };
+        let word = format!("&{array}[{index} / {per_word}u]");
+        let shift = format!("({index} % {per_word}u * {bits}u)");
+
+        writeln!(f, "atomicAnd({word}, ~({mask}u << {shift}));")?;
+        writeln!(f, "atomicOr({word}, (u32({value}) & {mask}u) << {shift});")?;
+    }
+
+    Ok(())
+}
+
+/// Copies an item between arrays when either of them is packed.
+fn copy_packed(
+    f: &mut std::fmt::Formatter<'_>,
+    input: &Variable,
+    in_index: &str,
+    out: &Variable,
+    out_index: &str,
+) -> core::fmt::Result {
+    let (input_array, in_index) = match input {
+        Variable::Slice { .. } => (
+            format!("(*{input}_ptr)"),
+            format!("{in_index} + {input}_offset"),
+        ),
+        _ => (input.to_string(), in_index.to_string()),
+    };
+    let (out_array, out_index) = match out {
+        Variable::Slice { .. } => (
+            format!("(*{out}_ptr)"),
+            format!("{out_index} + {out}_offset"),
+        ),
+        _ => (out.to_string(), out_index.to_string()),
+    };
+    let value = match input.is_packed() {
+        true => packed_read(&input_array, input.item(), &in_index),
+        false => format!("{input_array}[{in_index}]"),
+    };
+
+    if !out.is_packed() {
+        return writeln!(f, "{out_array}[{out_index}] = {value};");
+    }
+
+    let item = out.item();
+    let components = match item {
+        Item::Scalar(_) => vec![value],
+        _ => (0..item.vectorization_factor())
+            .map(|i| format!("({value})[{i}]"))
+            .collect(),
+    };
+    packed_write(f, &out_array, item, &out_index, &components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_read_u16_line_2_loads_one_word() {
+        let value = packed_read("input_0_global", Item::Vec2(Elem::U16), "id");
+
+        assert_eq!(
+            value,
+            "((vec2<u32>(atomicLoad(&input_0_global[u32(id)])) >> vec2<u32>(0u, 16u)) & vec2<u32>(65535u, 65535u))"
+        );
+    }
+
+    #[test]
+    fn packed_read_i16_line_4_loads_two_words() {
+        let value = packed_read("input_0_global", Item::Vec4(Elem::I16), "id");
+
+        assert_eq!(value.matches("atomicLoad").count(), 2);
+        assert!(value.starts_with("vec4<i32>("));
+        assert!(value.contains("&input_0_global[u32(id) * 2u + 1u]"));
+    }
+
+    #[test]
+    fn packed_read_u16_scalar_extracts_half_word() {
+        let value = packed_read("input_0_global", Item::Scalar(Elem::U16), "id");
+
+        assert_eq!(
+            value,
+            "extractBits(atomicLoad(&input_0_global[u32(id) / 2u]), u32(id) % 2u * 16u, 16u)"
+        );
+    }
+}
@@ -103,10 +103,11 @@ impl Display for ComputeShader {
         }
 
         for array in self.shared_memories.iter() {
+            let (item, size) = memory_array(array.item, array.size as usize);
             write!(
                 f,
                 "var<{}> shared_memory_{}: array<{}, {}>;\n\n",
-                array.location, array.index, array.item, array.size
+                array.location, array.index, item, size
             )?;
         }
 
@@ -231,13 +232,17 @@ impl ComputeShader {
         num_entry: usize,
     ) -> core::fmt::Result {
         let ty = match binding.size {
-            Some(size) => format!("array<{}, {}>", binding.item, size),
-            None => format!("array<{}>", binding.item),
+            Some(size) => {
+                let (item, size) = memory_array(binding.item, size);
+                format!("array<{item}, {size}>")
+            }
+            None => format!("array<{}>", memory_array(binding.item, 0).0),
         };
 
+        // Packed words are atomics, which need write access.
         let visibility = match binding.visibility {
             #[cfg(exclusive_memory_only)]
-            Visibility::Read => "read",
+            Visibility::Read if binding.item.elem().packed_bits().is_none() => "read",
             _ => "read_write",
         };
 
@@ -254,6 +259,18 @@ var<{}, {}> {}: {};
     }
 }
 
+/// The element type and length of an array of `size` items in memory, with narrow integers packed
+/// in words.
+fn memory_array(item: Item, size: usize) -> (String, usize) {
+    match item.elem().packed_bits() {
+        Some(bits) => {
+            let bits = size * item.vectorization_factor() * bits as usize;
+            ("atomic<u32>".to_string(), bits.div_ceil(32))
+        }
+        None => (item.to_string(), size),
+    }
+}
+
 impl Display for Location {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -28,12 +28,13 @@ pub struct WgpuResource {
 impl WgpuResource {
     /// Return the binding view of the buffer.
     pub fn as_wgpu_bind_resource(&self) -> wgpu::BindingResource {
+        // Storage bindings are sized in words, which narrow elements may not fill. We can safely
+        // round up, as memory is 32 bytes aligned.
+        let size = self.size.next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
         let binding = wgpu::BufferBinding {
             buffer: &self.buffer,
             offset: self.offset,
-            size: Some(
-                NonZeroU64::new(self.size).expect("0 size resources are not yet supported."),
-            ),
+            size: Some(NonZeroU64::new(size).expect("0 size resources are not yet supported.")),
         };
         wgpu::BindingResource::Buffer(binding)
     }
@@ -351,8 +351,16 @@ impl WgpuStream {
         // write_buffer is the recommended way to write this data, as:
         // - On WebGPU, from WASM, this can save a copy to the JS memory.
         // - On devices with unified memory, this could skip the staging buffer entirely.
-        self.queue
-            .write_buffer(resource.buffer(), resource.offset(), data);
+        // Narrow elements may not fill the last word, which is padded instead.
+        if data.len() as u64 == aligned_len {
+            self.queue
+                .write_buffer(resource.buffer(), resource.offset(), data);
+        } else {
+            let mut padded = data.to_vec();
+            padded.resize(aligned_len as usize, 0);
+            self.queue
+                .write_buffer(resource.buffer(), resource.offset(), &padded);
+        }
         self.flush_if_needed();
 
         alloc
@@ -28,12 +28,147 @@ mod tests {
     pub type TestRuntime = crate::WgpuRuntime;
 
     cubecl_core::testgen_all!();
+    cubecl_linalg::testgen_conv1d!([f32]);
+    cubecl_linalg::testgen_conv2d!([f32]);
+    cubecl_linalg::testgen_conv3d!([f32]);
+    cubecl_linalg::testgen_conv_pool!([f32]);
+    cubecl_linalg::testgen_conv_unfold!([f32, u32]);
     cubecl_linalg::testgen_matmul_plane!([f32]);
     cubecl_linalg::testgen_matmul_tiling2d!([flex32, f32]);
+    cubecl_linalg::testgen_matmul_requantize!();
     cubecl_linalg::testgen_matmul_simple!([flex32, f32]);
+    cubecl_linalg::testgen_matmul_spmm!([f32]);
     cubecl_linalg::testgen_tensor_identity!([flex32, f32, u32]);
+    cubecl_linalg::testgen_tensor_data!([f32, u32]);
+    cubecl_linalg::testgen_tensor_layernorm!([f32]);
+    cubecl_linalg::testgen_tensor_concat!([f32, u32]);
     cubecl_reduce::testgen_reduce!();
     cubecl_reduce::testgen_shared_sum!([f32]);
+
+    /// The integer arithmetic of the 8-bit and 16-bit integers, which WGSL emulates in 32-bit
+    /// registers.
+    mod i8_ty {
+        use super::*;
+
+        type IntType = i8;
+
+        cubecl_core::testgen_binary_int!();
+    }
+
+    mod u8_ty {
+        use super::*;
+
+        type UintType = u8;
+
+        cubecl_core::testgen_binary_uint!();
+    }
+
+    mod i16_ty {
+        use super::*;
+
+        type IntType = i16;
+
+        cubecl_core::testgen_binary_int!();
+    }
+
+    mod u16_ty {
+        use super::*;
+
+        type UintType = u16;
+
+        cubecl_core::testgen_binary_uint!();
+    }
+
+    /// The plane tests on a client emulating plane operations with shared memory, whether or not
+    /// the device supports subgroups.
+    mod plane_emulated {
+        use std::sync::OnceLock;
+
+        use cubecl_common::future;
+        use cubecl_core::{Feature, Runtime, prelude::ComputeClient, runtime_tests::plane};
+
+        use crate::{
+            AutoGraphicsApi, GraphicsApi, RuntimeOptions, WgpuDevice, WgpuRuntime, WgpuServer,
+            init_device, runtime::create_setup_for_device,
+        };
+
+        type Client = ComputeClient<WgpuServer, <WgpuRuntime as Runtime>::Channel>;
+
+        fn client() -> Client {
+            static DEVICE: OnceLock<WgpuDevice> = OnceLock::new();
+
+            let device = DEVICE.get_or_init(|| {
+                let setup = future::block_on(create_setup_for_device(
+                    &Default::default(),
+                    AutoGraphicsApi::backend(),
+                ));
+                let options = RuntimeOptions {
+                    emulate_planes: true,
+                    ..Default::default()
+                };
+                init_device(setup, options)
+            });
+            let client = WgpuRuntime::client(device);
+            assert!(client.properties().feature_enabled(Feature::PlaneEmulated));
+
+            client
+        }
+
+        #[test]
+        fn test_plane_sum() {
+            plane::test_plane_sum::<WgpuRuntime, f32>(client(), 4);
+        }
+
+        #[test]
+        fn test_plane_inclusive_sum() {
+            plane::test_plane_inclusive_sum::<WgpuRuntime, f32>(client(), 2);
+        }
+
+        #[test]
+        fn test_plane_exclusive_sum() {
+            plane::test_plane_exclusive_sum::<WgpuRuntime, f32>(client(), 1);
+        }
+
+        #[test]
+        fn test_plane_prod() {
+            plane::test_plane_prod::<WgpuRuntime, f32>(client(), 1);
+        }
+
+        #[test]
+        fn test_plane_exclusive_prod() {
+            plane::test_plane_exclusive_prod::<WgpuRuntime, f32>(client(), 2);
+        }
+
+        #[test]
+        fn test_plane_min() {
+            plane::test_plane_min::<WgpuRuntime, f32>(client(), 1);
+        }
+
+        #[test]
+        fn test_plane_max() {
+            plane::test_plane_max::<WgpuRuntime, f32>(client(), 4);
+        }
+
+        #[test]
+        fn test_plane_all() {
+            plane::test_plane_all::<WgpuRuntime, f32>(client(), 2);
+        }
+
+        #[test]
+        fn test_plane_any() {
+            plane::test_plane_any::<WgpuRuntime, f32>(client(), 1);
+        }
+
+        #[test]
+        fn test_plane_broadcast() {
+            plane::test_plane_broadcast::<WgpuRuntime, f32>(client(), 4);
+        }
+
+        #[test]
+        fn test_plane_ballot() {
+            plane::test_plane_ballot::<WgpuRuntime>(client());
+        }
+    }
 }
 
 #[cfg(all(test, feature = "spirv"))]
@@ -4,7 +4,7 @@ use crate::{
 };
 use cubecl_common::future;
 use cubecl_core::{
-    AtomicFeature, CubeDim, DeviceId, Feature, Runtime,
+    AtomicFeature, CubeDim, DeviceId, Feature, Runtime, WgpuCompilationOptions,
     ir::{Elem, FloatKind},
 };
 pub use cubecl_runtime::memory_management::MemoryConfiguration;
@@ -86,6 +86,12 @@ pub struct RuntimeOptions {
     pub tasks_max: usize,
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// Emulate plane operations with shared memory even when the device supports subgroups,
+    /// registering [Feature::PlaneEmulated] instead of [Feature::Plane].
+    ///
+    /// Devices without subgroups always use the emulation, except with the SPIR-V compiler, which
+    /// doesn't support it and always uses the native operations.
+    pub emulate_planes: bool,
 }
 
 impl Default for RuntimeOptions {
@@ -105,6 +111,7 @@ impl Default for RuntimeOptions {
         Self {
             tasks_max,
             memory_config: MemoryConfiguration::default(),
+            emulate_planes: false,
         }
     }
 }
@@ -188,6 +195,8 @@ pub(crate) fn create_client_on_setup(
     let mem_props = MemoryDeviceProperties {
         max_page_size: limits.max_storage_buffer_binding_size as u64,
         alignment: WgpuStorage::ALIGNMENT.max(limits.min_storage_buffer_offset_alignment as u64),
+        // wgpu doesn't expose the memory of the device.
+        total_memory: None,
     };
     let max_count = adapter_limits.max_compute_workgroups_per_dimension;
     let hardware_props = HardwareProperties {
@@ -204,10 +213,16 @@ pub(crate) fn create_client_on_setup(
         ),
     };
 
-    let mut compilation_options = Default::default();
+    let mut compilation_options = WgpuCompilationOptions::default();
 
     let features = setup.adapter.features();
-    let mut device_props = DeviceProperties::new(&[], mem_props.clone(), hardware_props);
+    let adapter_info = setup.adapter.get_info();
+    let mut device_props =
+        DeviceProperties::new(&[], mem_props.clone(), hardware_props, adapter_info.name);
+    device_props.register_driver_version(format!(
+        "{} {}",
+        adapter_info.driver, adapter_info.driver_info
+    ));
 
     // Workaround: WebGPU does support subgroups and correctly reports this, but wgpu
     // doesn't plumb through this info. Instead min/max are just reported as 0, which can cause issues.
@@ -215,11 +230,17 @@ pub(crate) fn create_client_on_setup(
     let fake_plane_info =
         adapter_limits.min_subgroup_size == 0 && adapter_limits.max_subgroup_size == 0;
 
+    let emulation = backend::supports_plane_emulation(&setup.adapter);
+
     if features.contains(wgpu::Features::SUBGROUP)
         && setup.adapter.get_info().device_type != wgpu::DeviceType::Cpu
         && !fake_plane_info
+        && !(options.emulate_planes && emulation)
     {
         device_props.register_feature(Feature::Plane);
+    } else if emulation {
+        compilation_options.emulate_planes = true;
+        device_props.register_feature(Feature::PlaneEmulated);
     }
     backend::register_features(&setup.adapter, &mut device_props, &mut compilation_options);
 
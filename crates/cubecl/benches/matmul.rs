@@ -69,6 +69,7 @@ fn run<R: Runtime, E: Float>(device: R::Device, strategy: matmul::Strategy) {
         (2, 4096, 4096, 4096),
         (16, 6144, 2048, 513),
         (32, 256, 256, 256),
+        (4096, 16, 16, 16),
     ] {
         let bench = MatmulBench::<R, E> {
             b,
@@ -14,3 +14,8 @@ pub use cubecl_linalg as linalg;
 
 #[cfg(feature = "reduce")]
 pub use cubecl_reduce as reduce;
+
+#[cfg(any(feature = "wgpu", feature = "cuda", feature = "hip"))]
+mod source;
+#[cfg(any(feature = "wgpu", feature = "cuda", feature = "hip"))]
+pub use source::{CompilationTarget, compile_to_source};
@@ -0,0 +1,116 @@
+use cubecl_core::{Compiler, ExecutionMode, prelude::KernelDefinition};
+
+/// The code generators a [kernel definition](KernelDefinition) can be compiled with.
+///
+/// Only the targets of the enabled runtimes are available, and the whole module requires at least
+/// one of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompilationTarget {
+    /// WGSL shader, as used by the wgpu runtime.
+    #[cfg(feature = "wgpu")]
+    Wgsl,
+    /// Disassembled SPIR-V module, as used by the wgpu runtime on Vulkan.
+    #[cfg(feature = "wgpu-spirv")]
+    SpirV,
+    /// CUDA C++ source, as used by the cuda runtime.
+    #[cfg(feature = "cuda")]
+    Cuda,
+    /// HIP C++ source, as used by the hip runtime.
+    #[cfg(feature = "hip")]
+    Hip,
+}
+
+/// Compiles the [kernel definition](KernelDefinition) for the given target and returns the
+/// generated source, without creating a client or launching anything.
+///
+/// The default compilation options of each compiler are used, so the output may differ slightly
+/// from what a runtime compiles for a specific device (e.g. the warp size or fast math flags).
+pub fn compile_to_source(kernel: &KernelDefinition, target: CompilationTarget) -> String {
+    let mode = ExecutionMode::Checked;
+
+    match target {
+        #[cfg(feature = "wgpu")]
+        CompilationTarget::Wgsl => {
+            let mut compiler = cubecl_wgpu::AutoCompiler::Wgsl(Default::default());
+            compile(&mut compiler, kernel, mode)
+        }
+        #[cfg(feature = "wgpu-spirv")]
+        CompilationTarget::SpirV => {
+            let mut compiler = cubecl_wgpu::AutoCompiler::SpirV(Default::default());
+            compile(&mut compiler, kernel, mode)
+        }
+        #[cfg(feature = "cuda")]
+        CompilationTarget::Cuda => {
+            let mut compiler =
+                <cubecl_cuda::CudaRuntime as cubecl_core::Runtime>::Compiler::default();
+            compile(&mut compiler, kernel, mode)
+        }
+        #[cfg(feature = "hip")]
+        CompilationTarget::Hip => {
+            let mut compiler = cubecl_hip::runtime::HipCompiler::default();
+            compile(&mut compiler, kernel, mode)
+        }
+    }
+}
+
+fn compile<C: Compiler>(
+    compiler: &mut C,
+    kernel: &KernelDefinition,
+    mode: ExecutionMode,
+) -> String {
+    compiler
+        .compile(kernel.clone(), &C::CompilationOptions::default(), mode)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        ir::{Elem, FloatKind, Item},
+        prelude::{KernelBuilder, KernelSettings},
+    };
+
+    const KERNEL_NAME: &str = "source_test_kernel";
+
+    fn kernel() -> KernelDefinition {
+        let mut builder = KernelBuilder::default();
+        builder.input_array(Item::new(Elem::Float(FloatKind::F32)));
+        builder.output_array(Item::new(Elem::Float(FloatKind::F32)));
+        builder.build(KernelSettings::default().kernel_name(KERNEL_NAME))
+    }
+
+    fn assert_source(target: CompilationTarget) {
+        let source = compile_to_source(&kernel(), target);
+
+        assert!(!source.is_empty(), "{target:?} source is empty");
+        assert!(
+            source.contains(KERNEL_NAME),
+            "{target:?} source doesn't contain the kernel name:\n{source}"
+        );
+    }
+
+    #[cfg(feature = "wgpu")]
+    #[test]
+    fn compile_to_wgsl() {
+        assert_source(CompilationTarget::Wgsl);
+    }
+
+    #[cfg(feature = "wgpu-spirv")]
+    #[test]
+    fn compile_to_spirv() {
+        assert_source(CompilationTarget::SpirV);
+    }
+
+    #[cfg(feature = "cuda")]
+    #[test]
+    fn compile_to_cuda() {
+        assert_source(CompilationTarget::Cuda);
+    }
+
+    #[cfg(feature = "hip")]
+    #[test]
+    fn compile_to_hip() {
+        assert_source(CompilationTarget::Hip);
+    }
+}